@@ -2,12 +2,57 @@ use proc_macro2::Span;
 use quote::{ToTokens, TokenStreamExt};
 use serde::{Deserialize, Serialize};
 use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// An identifier, analogous to `syn::Ident` and `proc_macro2::Ident`.
-#[derive(Hash, Eq, PartialEq, Serialize, Clone, Debug, Ord, PartialOrd)]
+///
+/// Identifiers parsed from source remember where they came from, see [`Ident::span()`].
+/// The span does not take part in comparisons, hashing, or serialization.
+#[derive(Serialize, Clone)]
 #[serde(transparent)]
-pub struct Ident(Cow<'static, str>);
+pub struct Ident(Cow<'static, str>, #[serde(skip)] Option<SourceSpan>);
+
+/// The location of an [`Ident`] in the file it was parsed from.
+///
+/// Lines are 1-indexed and columns are 0-indexed and counted in UTF-8 characters,
+/// like in `proc_macro2::LineColumn`. The end is exclusive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct SourceSpan {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl SourceSpan {
+    /// Get the location of a `proc_macro2::Span`, if it is known.
+    ///
+    /// Spans are only known with the `span-locations` feature, and for tokens parsed from
+    /// source text outside of a proc macro; other spans (e.g. `Span::call_site()`) are empty.
+    #[cfg(feature = "span-locations")]
+    pub fn from_span(span: Span) -> Option<Self> {
+        let (start, end) = (span.start(), span.end());
+        if start.line == 0 || start == end {
+            return None;
+        }
+        Some(Self {
+            line: start.line,
+            column: start.column,
+            end_line: end.line,
+            end_column: end.column,
+        })
+    }
+
+    /// Get the location of a `proc_macro2::Span`, which is never known without the
+    /// `span-locations` feature.
+    #[cfg(not(feature = "span-locations"))]
+    pub fn from_span(_span: Span) -> Option<Self> {
+        None
+    }
+}
 
 impl Ident {
     /// Validate a string
@@ -20,7 +65,7 @@ impl Ident {
     /// This function fails if the input isn't valid according to
     /// `proc_macro2::Ident`'s invariants.
     pub fn try_new(string: String) -> syn::Result<Self> {
-        Self::validate(&string).map(|_| Self(Cow::from(string)))
+        Self::validate(&string).map(|_| Self(Cow::from(string), None))
     }
 
     pub fn to_syn(&self) -> syn::Ident {
@@ -32,21 +77,58 @@ impl Ident {
         &self.0
     }
 
+    /// Where this identifier was found in the source, if it was parsed from a file.
+    pub fn span(&self) -> Option<SourceSpan> {
+        self.1
+    }
+
     /// An [`Ident`] containing "this".
-    pub const THIS: Self = Ident(Cow::Borrowed("this"));
+    pub const THIS: Self = Ident(Cow::Borrowed("this"), None);
+}
+
+impl PartialEq for Ident {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Ident {}
+
+impl fmt::Debug for Ident {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Ident").field(&self.0).finish()
+    }
+}
+
+impl Hash for Ident {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl PartialOrd for Ident {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ident {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
 }
 
 impl From<&'static str> for Ident {
     fn from(string: &'static str) -> Self {
         Self::validate(string).unwrap();
-        Self(Cow::from(string))
+        Self(Cow::from(string), None)
     }
 }
 
 impl From<String> for Ident {
     fn from(string: String) -> Self {
         Self::validate(&string).unwrap();
-        Self(Cow::from(string))
+        Self(Cow::from(string), None)
     }
 }
 
@@ -75,7 +157,10 @@ impl fmt::Display for Ident {
 
 impl From<&syn::Ident> for Ident {
     fn from(ident: &syn::Ident) -> Self {
-        Self(Cow::from(ident.to_string()))
+        Self(
+            Cow::from(ident.to_string()),
+            SourceSpan::from_span(ident.span()),
+        )
     }
 }
 
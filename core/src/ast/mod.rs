@@ -34,7 +34,7 @@ mod paths;
 pub use paths::Path;
 
 mod idents;
-pub use idents::Ident;
+pub use idents::{Ident, SourceSpan};
 
 mod docs;
 pub use docs::{DocType, Docs, DocsUrlGenerator, RustLink, RustLinkDisplay};
@@ -271,6 +271,15 @@ pub struct File {
 impl File {
     /// Fuses all declared types into a single environment `HashMap`.
     pub fn all_types(&self) -> Env {
+        self.all_types_with_externs(&[])
+    }
+
+    /// Like [`Self::all_types()`], but also makes the types of other bridge crates available,
+    /// so that they can be referenced as `other_crate::module::Type` (or imported with `use`).
+    ///
+    /// The modules of each external crate are placed under a top-level module named after the crate,
+    /// so crate names must not clash with each other or with the modules of this bridge.
+    pub fn all_types_with_externs(&self, externs: &[(&str, &File)]) -> Env {
         let mut out = Env::default();
         let mut top_symbols = ModuleEnv::new(Default::default());
 
@@ -279,6 +288,19 @@ impl File {
             top_symbols.insert(m.name.clone(), ModSymbol::SubModule(m.name.clone()));
         });
 
+        for (name, file) in externs {
+            let name = Ident::from(name.to_string());
+            let crate_path = Path::empty().sub_path(name.clone());
+            let mut crate_symbols = ModuleEnv::new(Default::default());
+            file.modules.values().for_each(|m| {
+                m.insert_all_types(crate_path.clone(), &mut out);
+                crate_symbols.insert(m.name.clone(), ModSymbol::SubModule(m.name.clone()));
+            });
+            out.insert(crate_path, crate_symbols);
+            top_symbols.insert(name.clone(), ModSymbol::SubModule(name.clone()));
+            out.external_crates.insert(name);
+        }
+
         out.insert(Path::empty(), top_symbols);
 
         out
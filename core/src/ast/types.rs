@@ -170,12 +170,19 @@ impl PathType {
         for (i, elem) in local_path.elements.iter().enumerate() {
             match elem.as_str() {
                 "crate" => {
-                    // TODO(#34): get the name of enclosing crate from env when we support multiple crates
-                    cur_path = Path::empty()
+                    cur_path = match env.external_crate(in_path) {
+                        Some(krate) => Path::empty().sub_path(krate.clone()),
+                        None => Path::empty(),
+                    }
                 }
 
                 "super" => cur_path = cur_path.get_super(),
 
+                // Paths starting with the name of an external crate, e.g. from `use other_crate::ffi::Foo`
+                o if i == 0 && env.get(&cur_path, o).is_none() && env.is_external_crate(o) => {
+                    cur_path = Path::empty().sub_path(Ident::from(o.to_string()));
+                }
+
                 o => match env.get(&cur_path, o) {
                     Some(ModSymbol::Alias(p)) => {
                         let mut remaining_elements: Vec<Ident> =
@@ -435,6 +442,10 @@ pub enum TypeName {
     /// A `Result<T, E>` or `diplomat_runtime::DiplomatResult` type.
     Result(Box<TypeName>, Box<TypeName>, StdlibOrDiplomat),
     Write,
+    /// A `diplomat_runtime::DiplomatWrite16`, which is written to as UTF-16.
+    Write16,
+    /// A `diplomat_runtime::DiplomatRead`, a data source provided by the caller.
+    Read,
     /// A `&DiplomatStr` or `Box<DiplomatStr>` type.
     /// Owned strings don't have a lifetime.
     ///
@@ -578,7 +589,7 @@ impl TypeName {
             // These are specified using FFI-safe diplomat_runtime types
             TypeName::StrReference(.., StdlibOrDiplomat::Diplomat) | TypeName::StrSlice(.., StdlibOrDiplomat::Diplomat) |TypeName::PrimitiveSlice(.., StdlibOrDiplomat::Diplomat) => true,
             // These are special anyway and shouldn't show up in structs
-            TypeName::Unit | TypeName::Write | TypeName::Result(..) |
+            TypeName::Unit | TypeName::Write | TypeName::Write16 | TypeName::Read | TypeName::Result(..) |
             // This is basically only useful in return types
             TypeName::Ordering |
             // These are specified using Rust stdlib types and not safe across FFI
@@ -665,6 +676,12 @@ impl TypeName {
             TypeName::Write => {
                 syn::parse_quote_spanned!(Span::call_site() => diplomat_runtime::DiplomatWrite)
             }
+            TypeName::Write16 => {
+                syn::parse_quote_spanned!(Span::call_site() => diplomat_runtime::DiplomatWrite16)
+            }
+            TypeName::Read => {
+                syn::parse_quote_spanned!(Span::call_site() => diplomat_runtime::DiplomatRead)
+            }
             TypeName::StrReference(lt, encoding, is_stdlib_type) => {
                 if *is_stdlib_type == StdlibOrDiplomat::Stdlib {
                     encoding.get_stdlib_slice_type(lt)
@@ -713,6 +730,8 @@ impl TypeName {
     /// - If the type is a path with a single element [`Result`], returns a [`TypeName::Result`] with the type parameters recursively converted
     /// - If the type is a path equal to [`diplomat_runtime::DiplomatResult`], returns a [`TypeName::DiplomatResult`] with the type parameters recursively converted
     /// - If the type is a path equal to [`diplomat_runtime::DiplomatWrite`], returns a [`TypeName::Write`]
+    /// - If the type is a path equal to [`diplomat_runtime::DiplomatWrite16`], returns a [`TypeName::Write16`]
+    /// - If the type is a path equal to [`diplomat_runtime::DiplomatRead`], returns a [`TypeName::Read`]
     /// - If the type is a owned or borrowed string type, returns a [`TypeName::StrReference`]
     /// - If the type is a owned or borrowed slice of a Rust primitive, returns a [`TypeName::PrimitiveSlice`]
     /// - If the type is a reference (`&` or `&mut`), returns a [`TypeName::Reference`] with the referenced type recursively converted
@@ -973,6 +992,10 @@ impl TypeName {
                     }
                 } else if is_runtime_type(p, "DiplomatWrite") {
                     TypeName::Write
+                } else if is_runtime_type(p, "DiplomatWrite16") {
+                    TypeName::Write16
+                } else if is_runtime_type(p, "DiplomatRead") {
+                    TypeName::Read
                 } else {
                     TypeName::Named(PathType::from(p))
                 }
@@ -1182,6 +1205,8 @@ impl fmt::Display for TypeName {
                 write!(f, "Result<{ok}, {err}>")
             }
             TypeName::Write => "DiplomatWrite".fmt(f),
+            TypeName::Write16 => "DiplomatWrite16".fmt(f),
+            TypeName::Read => "DiplomatRead".fmt(f),
             TypeName::StrReference(lt, encoding, is_stdlib_type) => {
                 if let Some(lt) = lt {
                     if *is_stdlib_type == StdlibOrDiplomat::Stdlib {
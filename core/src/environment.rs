@@ -1,5 +1,5 @@
 use crate::ast::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ops::Index;
 
 /// The type resolution environment
@@ -8,6 +8,9 @@ use std::ops::Index;
 #[derive(Default, Clone)]
 pub struct Env {
     pub(crate) env: BTreeMap<Path, ModuleEnv>,
+    /// Other bridge crates whose types may be referenced. Their modules live under
+    /// a top-level module named after the crate.
+    pub(crate) external_crates: BTreeSet<Ident>,
 }
 
 /// The type resolution environment within a specific module
@@ -28,6 +31,18 @@ impl Env {
         self.env.get(path).and_then(|m| m.module.get(name))
     }
 
+    /// If `path` is within an external crate, get the name of that crate
+    pub fn external_crate(&self, path: &Path) -> Option<&Ident> {
+        path.elements
+            .first()
+            .and_then(|first| self.external_crates.get(first))
+    }
+
+    /// Whether `name` is the name of an external crate
+    pub fn is_external_crate(&self, name: &str) -> bool {
+        self.external_crates.contains(name)
+    }
+
     /// Iterate over all items in the environment
     ///
     /// This will occur in a stable lexically sorted order by path and then name
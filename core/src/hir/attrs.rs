@@ -5,7 +5,7 @@ use crate::ast::attrs::{AttrInheritContext, DiplomatBackendAttrCfg, StandardAttr
 use crate::hir::lowering::ErrorStore;
 use crate::hir::{
     EnumVariant, LoweringError, Method, Mutability, OpaqueId, ReturnType, SelfType, SuccessType,
-    TraitDef, Type, TypeDef, TypeId,
+    TraitDef, Type, TypeDef, TypeId, WriteEncoding,
 };
 use syn::Meta;
 
@@ -41,6 +41,16 @@ pub struct Attrs {
     /// This attribute does not participate in inheritance and must always
     /// be specified on individual methods
     pub special_method: Option<SpecialMethod>,
+    /// Whether the Rust type is `Send` and/or `Sync`, from the `send`, `sync` and `thread_safe` (both) markers.
+    /// `#[diplomat::bridge]` statically asserts these against the Rust type.
+    ///
+    /// This attribute is only allowed on opaques, and does not participate in inheritance
+    pub thread_safety: ThreadSafety,
+    /// Also generate a variant of this method that writes its string output to an output stream
+    /// of the host language as it is written, instead of returning it, see [`BackendAttrSupport::streaming_writes`].
+    ///
+    /// This attribute is only allowed on methods returning a string, and does not participate in inheritance
+    pub stream: bool,
 
     /// From #[diplomat::demo()]. Created from [`crate::ast::attrs::Attrs::demo_attrs`].
     /// List of attributes specific to automatic demo generation.
@@ -65,6 +75,30 @@ pub struct DemoInputCFG {
     ///
     /// Should ALWAYS be a string. The HTML renderer is expected to do validation for us.
     pub default_value: String,
+
+    /// `#[diplomat::demo(input(min = ...))]`
+    /// Smallest value accepted by a numeric input (or by each item of a numeric slice).
+    pub min: Option<f64>,
+
+    /// `#[diplomat::demo(input(max = ...))]`
+    /// Largest value accepted by a numeric input (or by each item of a numeric slice).
+    pub max: Option<f64>,
+
+    /// `#[diplomat::demo(input(step = ...))]`
+    /// Granularity of a numeric input, i.e. `0.5` only accepts multiples of `0.5` (offset by [`DemoInputCFG::min`], if set).
+    pub step: Option<f64>,
+
+    /// `#[diplomat::demo(input(pattern = "..."))]`
+    /// Regular expression (in JS syntax) that a string input has to match.
+    pub pattern: Option<String>,
+
+    /// `#[diplomat::demo(input(choices = ["...", "..."]))]`
+    /// Restricts a string input to a fixed list of values, or an enum input to a subset of its variants.
+    pub choices: Vec<String>,
+
+    /// `#[diplomat::demo(input(multiline))]`
+    /// Use a multi-line text area for a string input.
+    pub multiline: bool,
 }
 
 #[non_exhaustive]
@@ -133,6 +167,51 @@ pub enum SpecialMethod {
     Indexer,
 }
 
+/// The thread-safety markers of an opaque, see [`Attrs::thread_safety`].
+///
+/// Backends can use these to document whether instances may be shared across threads, and to guard
+/// against it when they may not.
+#[non_exhaustive]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct ThreadSafety {
+    /// `#[diplomat::attr(..., send)]`: instances may be moved to (and dropped on) another thread.
+    pub send: bool,
+    /// `#[diplomat::attr(..., sync)]`: instances may be used through `&self` from several threads at once.
+    pub sync: bool,
+}
+
+impl ThreadSafety {
+    /// Whether the type is both `Send` and `Sync`, i.e. instances can be freely shared across threads.
+    pub fn is_thread_safe(self) -> bool {
+        self.send && self.sync
+    }
+
+    /// Whether a method taking `&self` (or `&mut self` if `mutable`) may only be called on the
+    /// thread that created the instance, for backends that check it.
+    ///
+    /// Instances that aren't `send` are bound to their thread, except for `&self` methods of types
+    /// that are `sync`. Instances that are `send` are never bound: they may legitimately move to
+    /// another thread, and a thread check can't tell that apart from the concurrent use that
+    /// `sync` is about.
+    pub fn is_thread_bound(self, mutable: bool) -> bool {
+        !self.send && (mutable || !self.sync)
+    }
+
+    /// A sentence for backends to add to the documentation of the type, if it has any markers.
+    pub fn docs(self) -> Option<&'static str> {
+        match (self.send, self.sync) {
+            (true, true) => Some("Instances may be moved to and shared between threads."),
+            (true, false) => Some(
+                "Instances may be moved to another thread, but must not be used from several threads at once.",
+            ),
+            (false, true) => Some(
+                "Instances may be used from several threads at once, but must be destroyed on the thread that created them.",
+            ),
+            (false, false) => None,
+        }
+    }
+}
+
 /// For special methods that affect type semantics, whether this type has this method.
 ///
 /// This will likely only contain a subset of special methods, but feel free to add more as needed.
@@ -162,6 +241,20 @@ pub enum AttributeContext<'a, 'b> {
     Field,
 }
 
+/// Parses the (possibly negative) number literal in `#[diplomat::demo(input(min = -1))]` and friends.
+fn parse_demo_number(value: syn::parse::ParseStream) -> syn::Result<f64> {
+    let negative = value.parse::<Option<syn::Token![-]>>()?.is_some();
+    let ahead = value.lookahead1();
+    let number = if ahead.peek(syn::LitFloat) {
+        value.parse::<syn::LitFloat>()?.base10_parse::<f64>()?
+    } else if ahead.peek(syn::LitInt) {
+        value.parse::<syn::LitInt>()?.base10_parse::<f64>()?
+    } else {
+        return Err(ahead.error());
+    };
+    Ok(if negative { -number } else { number })
+}
+
 fn maybe_error_unsupported(
     auto_found: bool,
     attribute: &str,
@@ -169,9 +262,10 @@ fn maybe_error_unsupported(
     errors: &mut ErrorStore,
 ) {
     if !auto_found {
-        errors.push(LoweringError::Other(format!(
-            "`{attribute}` not supported in backend {backend}"
-        )));
+        errors.push(LoweringError::UnsupportedAttribute {
+            attribute: attribute.into(),
+            backend: backend.into(),
+        });
     }
 }
 impl Attrs {
@@ -215,6 +309,32 @@ impl Attrs {
                                 "`disable` must be a simple path".into(),
                             ))
                         }
+                    } else if path == "send" || path == "sync" || path == "thread_safe" {
+                        if let Meta::Path(_) = attr.meta {
+                            if path != "sync" {
+                                this.thread_safety.send = true;
+                            }
+                            if path != "send" {
+                                this.thread_safety.sync = true;
+                            }
+                        } else {
+                            errors.push(LoweringError::Other(format!(
+                                "`{path}` must be a simple path"
+                            )))
+                        }
+                    } else if path == "stream" {
+                        if !support.streaming_writes {
+                            maybe_error_unsupported(auto_found, "stream", backend, errors);
+                            continue;
+                        }
+                        auto_used = true;
+                        if let Meta::Path(_) = attr.meta {
+                            this.stream = true;
+                        } else {
+                            errors.push(LoweringError::Other(
+                                "`stream` must be a simple path".into(),
+                            ))
+                        }
                     } else if path == "rename" {
                         match RenameAttr::from_meta(&attr.meta) {
                             Ok(rename) => {
@@ -351,7 +471,7 @@ impl Attrs {
                         }
                     } else {
                         errors.push(LoweringError::Other(format!(
-                            "Unknown diplomat attribute {path}: expected one of: `disable, rename, namespace, constructor, stringifier, comparison, named_constructor, getter, setter, indexer`"
+                            "Unknown diplomat attribute {path}: expected one of: `disable, rename, namespace, constructor, stringifier, comparison, named_constructor, getter, setter, indexer, send, sync, thread_safe, stream`"
                         )));
                     }
                     if auto_found && !auto_used {
@@ -361,7 +481,7 @@ impl Attrs {
                     }
                 } else {
                     errors.push(LoweringError::Other(format!(
-                        "Unknown diplomat attribute {path:?}: expected one of: `disable, rename, namespace, constructor, stringifier, comparison, named_constructor, getter, setter, indexer`"
+                        "Unknown diplomat attribute {path:?}: expected one of: `disable, rename, namespace, constructor, stringifier, comparison, named_constructor, getter, setter, indexer, send, sync, thread_safe, stream`"
                     )));
                 }
             }
@@ -407,6 +527,38 @@ impl Attrs {
                                 }
                                 this.demo_attrs.input_cfg.default_value = str_val;
                                 Ok(())
+                            } else if meta.path.is_ident("min") {
+                                this.demo_attrs.input_cfg.min =
+                                    Some(parse_demo_number(meta.value()?)?);
+                                Ok(())
+                            } else if meta.path.is_ident("max") {
+                                this.demo_attrs.input_cfg.max =
+                                    Some(parse_demo_number(meta.value()?)?);
+                                Ok(())
+                            } else if meta.path.is_ident("step") {
+                                this.demo_attrs.input_cfg.step =
+                                    Some(parse_demo_number(meta.value()?)?);
+                                Ok(())
+                            } else if meta.path.is_ident("pattern") {
+                                let s: syn::LitStr = meta.value()?.parse()?;
+                                this.demo_attrs.input_cfg.pattern = Some(s.value());
+                                Ok(())
+                            } else if meta.path.is_ident("choices") {
+                                let value = meta.value()?;
+                                let content;
+                                syn::bracketed!(content in value);
+                                let choices = syn::punctuated::Punctuated::<
+                                    syn::LitStr,
+                                    syn::Token![,],
+                                >::parse_terminated(
+                                    &content
+                                )?;
+                                this.demo_attrs.input_cfg.choices =
+                                    choices.iter().map(syn::LitStr::value).collect();
+                                Ok(())
+                            } else if meta.path.is_ident("multiline") {
+                                this.demo_attrs.input_cfg.multiline = true;
+                                Ok(())
                             } else {
                                 Err(meta.error(format!(
                                     "Unsupported ident {:?}",
@@ -458,9 +610,37 @@ impl Attrs {
             rename,
             abi_rename,
             special_method,
+            thread_safety,
+            stream,
             demo_attrs: _,
         } = &self;
 
+        if *thread_safety != ThreadSafety::default()
+            && !matches!(context, AttributeContext::Type(TypeDef::Opaque(..)))
+        {
+            errors.push(LoweringError::Other(
+                "`send`, `sync` and `thread_safe` can only be used on opaque types".into(),
+            ));
+        }
+
+        if *stream {
+            if let AttributeContext::Method(method, ..) = context {
+                if !matches!(method.output, ReturnType::Infallible(SuccessType::Write)) {
+                    errors.push(LoweringError::Other(
+                        "`stream` can only be used on methods infallibly returning a string".into(),
+                    ));
+                } else if method.write_encoding != WriteEncoding::Utf8 {
+                    errors.push(LoweringError::Other(
+                        "`stream` cannot be used on methods writing to a `DiplomatWrite16`".into(),
+                    ));
+                }
+            } else {
+                errors.push(LoweringError::Other(
+                    "`stream` can only be used on methods".into(),
+                ));
+            }
+        }
+
         if *disable && matches!(context, AttributeContext::EnumVariant(..)) {
             errors.push(LoweringError::Other(
                 "`disable` cannot be used on enum variants".into(),
@@ -773,6 +953,8 @@ impl Attrs {
             abi_rename: Default::default(),
             // Never inherited
             special_method: None,
+            thread_safety: Default::default(),
+            stream: false,
             demo_attrs: Default::default(),
         }
     }
@@ -820,6 +1002,16 @@ pub struct BackendAttrSupport {
     pub utf16_strings: bool,
     /// Whether the language supports using slices with 'static lifetimes.
     pub static_slices: bool,
+    /// Whether the language supports borrowing opaques, or structs with lifetimes, for 'static.
+    pub static_borrows: bool,
+    /// Whether the language supports `i128` and `u128`.
+    pub int128: bool,
+    /// Whether the language supports slices of `i128` and `u128`.
+    pub int128_slices: bool,
+    /// Whether the language supports methods writing UTF-16 to a `DiplomatWrite16`.
+    pub write16: bool,
+    /// Whether the language supports `&mut DiplomatRead` parameters, reading from a host data source.
+    pub reads: bool,
 
     // Special methods
     /// Marking a method as a constructor to generate special constructor methods.
@@ -842,6 +1034,8 @@ pub struct BackendAttrSupport {
     pub iterables: bool,
     /// Marking a method as the `[]` operator, which is special in this language.
     pub indexing: bool,
+    /// Generating variants of methods marked `stream` that write their string output to an output stream.
+    pub streaming_writes: bool,
 
     /// Support for Option<Struct> and Option<Primitive>
     pub option: bool,
@@ -862,6 +1056,11 @@ impl BackendAttrSupport {
             utf8_strings: true,
             utf16_strings: true,
             static_slices: true,
+            static_borrows: true,
+            int128: true,
+            int128_slices: true,
+            write16: true,
+            reads: true,
 
             constructors: true,
             named_constructors: true,
@@ -872,6 +1071,7 @@ impl BackendAttrSupport {
             iterators: true,
             iterables: true,
             indexing: true,
+            streaming_writes: true,
             option: true,
             callbacks: true,
             traits: true,
@@ -994,6 +1194,11 @@ impl AttributeValidator for BasicAttributeValidator {
                 utf8_strings,
                 utf16_strings,
                 static_slices,
+                static_borrows,
+                int128,
+                int128_slices,
+                write16,
+                reads,
 
                 constructors,
                 named_constructors,
@@ -1004,6 +1209,7 @@ impl AttributeValidator for BasicAttributeValidator {
                 iterators,
                 iterables,
                 indexing,
+                streaming_writes,
                 option,
                 callbacks,
                 traits,
@@ -1016,6 +1222,11 @@ impl AttributeValidator for BasicAttributeValidator {
                 "utf8_strings" => utf8_strings,
                 "utf16_strings" => utf16_strings,
                 "static_slices" => static_slices,
+                "static_borrows" => static_borrows,
+                "int128" => int128,
+                "int128_slices" => int128_slices,
+                "write16" => write16,
+                "reads" => reads,
 
                 "constructors" => constructors,
                 "named_constructors" => named_constructors,
@@ -1026,6 +1237,7 @@ impl AttributeValidator for BasicAttributeValidator {
                 "iterators" => iterators,
                 "iterables" => iterables,
                 "indexing" => indexing,
+                "streaming_writes" => streaming_writes,
                 "option" => option,
                 "callbacks" => callbacks,
                 "traits" => traits,
@@ -1073,6 +1285,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_demo_input() {
+        let parsed: syn::File = syn::parse_quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                impl Opaque {
+                    pub fn new(
+                        #[diplomat::demo(input(label = "Value", default_value = 10, min = -1.5, max = 100, step = 0.5))]
+                        v: f64,
+                        #[diplomat::demo(input(pattern = "[a-z]+", choices = ["a", "b"], multiline))]
+                        s: &str,
+                    ) -> Box<Opaque> {
+                        unimplemented!()
+                    }
+                }
+            }
+        };
+
+        let mut attr_validator = hir::BasicAttributeValidator::new("tests");
+        attr_validator.support = hir::BackendAttrSupport::all_true();
+        let tcx = hir::TypeContext::from_syn(&parsed, attr_validator).unwrap();
+        let params = &tcx.opaques()[0].methods[0].params;
+
+        let v = &params[0].attrs.demo_attrs.input_cfg;
+        assert_eq!(v.label, "Value");
+        assert_eq!(v.default_value, "10");
+        assert_eq!(v.min, Some(-1.5));
+        assert_eq!(v.max, Some(100.0));
+        assert_eq!(v.step, Some(0.5));
+        assert_eq!(v.pattern, None);
+        assert!(v.choices.is_empty());
+        assert!(!v.multiline);
+
+        let s = &params[1].attrs.demo_attrs.input_cfg;
+        assert_eq!(s.min, None);
+        assert_eq!(s.pattern.as_deref(), Some("[a-z]+"));
+        assert_eq!(s.choices, ["a", "b"]);
+        assert!(s.multiline);
+    }
+
     #[test]
     fn test_auto() {
         uitest_lowering_attr! { hir::BackendAttrSupport { comparators: true, ..Default::default()},
@@ -1227,6 +1482,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_thread_safety() {
+        uitest_lowering_attr! { hir::BackendAttrSupport::default(),
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                #[diplomat::attr(*, thread_safe)]
+                struct Opaque;
+
+                #[diplomat::opaque]
+                #[diplomat::attr(*, sync = "yes")]
+                struct BadMarker;
+
+                #[diplomat::attr(*, send)]
+                struct Struct {
+                    a: u8,
+                }
+
+                impl Opaque {
+                    #[diplomat::attr(*, send)]
+                    pub fn method(&self) {}
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream() {
+        uitest_lowering_attr! { hir::BackendAttrSupport { streaming_writes: true, write16: true, ..Default::default()},
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                #[diplomat::attr(auto, stream)]
+                struct Opaque;
+
+                impl Opaque {
+                    #[diplomat::attr(auto, stream)]
+                    pub fn serialize(&self, write: &mut DiplomatWrite) {}
+                    #[diplomat::attr(auto, stream)]
+                    pub fn try_serialize(&self, write: &mut DiplomatWrite) -> Result<(), ()> {}
+                    #[diplomat::attr(auto, stream)]
+                    pub fn len(&self) -> usize {}
+                    #[diplomat::attr(auto, stream)]
+                    pub fn serialize16(&self, write: &mut DiplomatWrite16) {}
+                    #[diplomat::attr(auto, stream = "yes")]
+                    pub fn bad_marker(&self, write: &mut DiplomatWrite) {}
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_unsupported_features() {
         uitest_lowering_attr! { hir::BackendAttrSupport::default(),
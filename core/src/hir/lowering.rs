@@ -6,31 +6,156 @@ use super::{
     ParamSelf, PrimitiveType, ReturnLifetimeLowerer, ReturnType, ReturnableStructPath,
     SelfParamLifetimeLowerer, SelfType, Slice, SpecialMethod, SpecialMethodPresence, StructDef,
     StructField, StructPath, SuccessType, SymbolId, TraitDef, TraitParamSelf, TraitPath,
-    TyPosition, Type, TypeDef, TypeId,
+    TyPosition, Type, TypeDef, TypeId, WriteEncoding,
 };
 use crate::ast::attrs::AttrInheritContext;
 use crate::{ast, Env};
 use core::fmt;
+use std::borrow::Cow;
 use strck::IntoCk;
 
 /// An error from lowering the AST to the HIR.
+///
+/// The [`Display`](fmt::Display) impl gives a one-line description of the problem,
+/// and [`LoweringError::help()`] a suggestion on how to fix it, if there is one.
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum LoweringError {
+    /// An identifier could not be converted to a Rust identifier.
+    InvalidIdent {
+        ident: String,
+        /// What the identifier is used for, e.g. "method name"
+        context: &'static str,
+        reason: String,
+    },
+    /// A struct field has a type that isn't FFI-safe.
+    FfiUnsafeField {
+        ty: String,
+        /// The field, as `Struct.field`
+        field: String,
+        /// The FFI-safe type to use instead
+        suggestion: String,
+    },
+    /// An opaque type was passed or returned without being behind a reference or `Box`.
+    OpaqueByValue { ty: String, in_input: bool },
+    /// A type or attribute is used that the current backend does not support,
+    /// and which can be checked for with `#[diplomat::attr(supports = ..., ...)]`.
+    Unsupported {
+        message: Cow<'static, str>,
+        /// The `supports = ...` key for the feature
+        supports: &'static str,
+    },
+    /// An attribute that the backend does not support.
+    UnsupportedAttribute { attribute: String, backend: String },
+    /// `Option<T>` for a non-opaque `T` in a struct field.
+    OptionInStructField {
+        /// "struct/enum" or "primitive"
+        kind: &'static str,
+    },
+    /// A lifetime in the return type of a method was elided.
+    ElidedReturnLifetime,
+    /// A method does not declare a lifetime bound implied by one of its types.
+    MissingLifetimeBound {
+        /// Where the bound comes from, e.g. "param foo" or "return type"
+        param: String,
+        longer: String,
+        shorter: String,
+        cause: String,
+    },
     /// The purpose of having this is that translating to the HIR has enormous
     /// potential for really detailed error handling and giving suggestions.
     ///
-    /// Unfortunately, working out what the error enum should look like to enable
-    /// this is really hard. The plan is that once the lowering code is completely
-    /// written, we ctrl+F for `"LoweringError::Other"` in the lowering code, and turn every
-    /// instance into an specialized enum variant, generalizing where possible
-    /// without losing any information.
+    /// Errors that need to be handled or explained specially get their own variant
+    /// above; the rest are described by a message.
     Other(String),
 }
 
+impl LoweringError {
+    /// A suggestion on how to fix the error, if there is one.
+    pub fn help(&self) -> Option<String> {
+        Some(match *self {
+            Self::FfiUnsafeField { ref suggestion, .. } => {
+                format!("replace the field type with `{suggestion}`")
+            }
+            Self::OpaqueByValue { ref ty, in_input } => {
+                if in_input {
+                    format!("take `&{ty}` or `&mut {ty}` instead")
+                } else {
+                    format!("return `Box<{ty}>` or a reference instead")
+                }
+            }
+            Self::Unsupported { supports, .. } => format!(
+                "disable this item for backends without support using `#[diplomat::attr(not(supports = {supports}), disable)]`"
+            ),
+            Self::UnsupportedAttribute {
+                ref attribute,
+                ref backend,
+            } => format!(
+                "only apply `{attribute}` to other backends, e.g. `#[diplomat::attr(not({backend}), {attribute})]`"
+            ),
+            Self::OptionInStructField { .. } => {
+                "`DiplomatOption<T>` has a stable layout and can be used in structs".into()
+            }
+            Self::ElidedReturnLifetime => {
+                "name the lifetime, e.g. `pub fn foo<'a>(&'a self) -> &'a Foo`".into()
+            }
+            Self::MissingLifetimeBound {
+                ref longer,
+                ref shorter,
+                ..
+            } => format!("add `'{longer}: '{shorter}` to the method's lifetime parameters"),
+            _ => return None,
+        })
+    }
+}
+
 impl fmt::Display for LoweringError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            Self::InvalidIdent {
+                ref ident,
+                context,
+                ref reason,
+            } => write!(
+                f,
+                "Ident `{ident}` from {context} could not be turned into a Rust ident: {reason}"
+            ),
+            Self::FfiUnsafeField {
+                ref ty,
+                ref field,
+                ref suggestion,
+            } => write!(
+                f,
+                "Found FFI-unsafe type {ty} in struct field {field}, consider using {suggestion}"
+            ),
+            Self::OpaqueByValue { ref ty, in_input } => {
+                if in_input {
+                    write!(f, "Opaque passed by value in input: {ty}")
+                } else {
+                    write!(f, "Opaque returned by value: {ty}")
+                }
+            }
+            Self::Unsupported { ref message, .. } => message.fmt(f),
+            Self::UnsupportedAttribute {
+                ref attribute,
+                ref backend,
+            } => write!(f, "`{attribute}` not supported in backend {backend}"),
+            Self::OptionInStructField { kind } => write!(
+                f,
+                "Found Option<T> for {kind} T in a struct field, please use DiplomatOption<T>"
+            ),
+            Self::ElidedReturnLifetime => {
+                "Found elided lifetime in return type, please explicitly specify".fmt(f)
+            }
+            Self::MissingLifetimeBound {
+                ref param,
+                ref longer,
+                ref shorter,
+                ref cause,
+            } => write!(
+                f,
+                "Method should explicitly include this lifetime bound from {param}: '{longer}: '{shorter} ({cause})"
+            ),
             Self::Other(ref s) => s.fmt(f),
         }
     }
@@ -40,6 +165,16 @@ impl fmt::Display for LoweringError {
 pub struct ErrorContext {
     item: String,
     subitem: Option<String>,
+    location: Option<ast::Ident>,
+}
+
+impl ErrorContext {
+    /// The identifier closest to the error, e.g. the parameter, field, method, or type name.
+    ///
+    /// Its [`span()`](ast::Ident::span) is the location of the error in the source, if known.
+    pub fn location(&self) -> Option<&ast::Ident> {
+        self.location.as_ref()
+    }
 }
 
 impl fmt::Display for ErrorContext {
@@ -69,6 +204,8 @@ pub struct ErrorStore<'tree> {
     item: &'tree str,
     /// The current sub-item context (methods, etc)
     subitem: Option<&'tree str>,
+    /// The identifier closest to whatever is being lowered
+    location: Option<ast::Ident>,
 }
 
 pub type ErrorAndContext = (ErrorContext, LoweringError);
@@ -79,6 +216,7 @@ impl<'tree> ErrorStore<'tree> {
         let context = ErrorContext {
             item: self.item.into(),
             subitem: self.subitem.map(|s| s.into()),
+            location: self.location.clone(),
         };
         self.errors.push((context, error));
     }
@@ -94,9 +232,15 @@ impl<'tree> ErrorStore<'tree> {
     pub(super) fn set_item(&mut self, item: &'tree str) {
         self.item = item;
         self.subitem = None;
+        self.location = None;
     }
     pub(super) fn set_subitem(&mut self, subitem: &'tree str) {
         self.subitem = Some(subitem);
+        self.location = None;
+    }
+    /// Point subsequent errors at `ident`, until the next call to this, `set_item()`, or `set_subitem()`.
+    pub(super) fn set_location(&mut self, ident: &ast::Ident) {
+        self.location = Some(ident.clone());
     }
 }
 
@@ -131,9 +275,11 @@ impl<'ast> LoweringContext<'ast> {
         match ident.as_str().ck() {
             Ok(name) => Ok(name.to_owned()),
             Err(e) => {
-                self.errors.push(LoweringError::Other(format!(
-                    "Ident `{ident}` from {context} could not be turned into a Rust ident: {e}"
-                )));
+                self.errors.push(LoweringError::InvalidIdent {
+                    ident: ident.to_string(),
+                    context,
+                    reason: e.to_string(),
+                });
                 Err(())
             }
         }
@@ -193,6 +339,7 @@ impl<'ast> LoweringContext<'ast> {
     fn lower_enum(&mut self, item: ItemAndInfo<'ast, ast::Enum>) -> Result<EnumDef, ()> {
         let ast_enum = item.item;
         self.errors.set_item(ast_enum.name.as_str());
+        self.errors.set_location(&ast_enum.name);
         let name = self.lower_ident(&ast_enum.name, "enum name");
         let attrs = self.attr_validator.attr_from_ast(
             &ast_enum.attrs,
@@ -203,6 +350,7 @@ impl<'ast> LoweringContext<'ast> {
         let mut variants = Ok(Vec::with_capacity(ast_enum.variants.len()));
         let variant_parent_attrs = attrs.for_inheritance(AttrInheritContext::Variant);
         for (ident, discriminant, docs, attrs) in ast_enum.variants.iter() {
+            self.errors.set_location(ident);
             let name = self.lower_ident(ident, "enum variant");
             let attrs =
                 self.attr_validator
@@ -226,6 +374,8 @@ impl<'ast> LoweringContext<'ast> {
             }
         }
 
+        self.errors.set_location(&ast_enum.name);
+
         let mut special_method_presence = SpecialMethodPresence::default();
         let methods = if attrs.disable {
             Vec::new()
@@ -248,6 +398,9 @@ impl<'ast> LoweringContext<'ast> {
             special_method_presence,
         );
 
+        // Lowering the methods and fields pointed errors at them
+        self.errors.set_item(ast_enum.name.as_str());
+        self.errors.set_location(&ast_enum.name);
         self.attr_validator.validate(
             &def.attrs,
             AttributeContext::Type(TypeDef::from(&def)),
@@ -263,6 +416,7 @@ impl<'ast> LoweringContext<'ast> {
     ) -> Result<OpaqueDef, ()> {
         let ast_opaque = item.item;
         self.errors.set_item(ast_opaque.name.as_str());
+        self.errors.set_location(&ast_opaque.name);
         let name = self.lower_ident(&ast_opaque.name, "opaque name");
         let dtor_abi_name = self.lower_ident(&ast_opaque.dtor_abi_name, "opaque dtor abi name");
 
@@ -294,6 +448,9 @@ impl<'ast> LoweringContext<'ast> {
             special_method_presence,
             dtor_abi_name?,
         );
+        // Lowering the methods and fields pointed errors at them
+        self.errors.set_item(ast_opaque.name.as_str());
+        self.errors.set_location(&ast_opaque.name);
         self.attr_validator.validate(
             &def.attrs,
             AttributeContext::Type(TypeDef::from(&def)),
@@ -305,6 +462,7 @@ impl<'ast> LoweringContext<'ast> {
     fn lower_struct(&mut self, item: ItemAndInfo<'ast, ast::Struct>) -> Result<StructDef, ()> {
         let ast_struct = item.item;
         self.errors.set_item(ast_struct.name.as_str());
+        self.errors.set_location(&ast_struct.name);
         let struct_name = self.lower_ident(&ast_struct.name, "struct name")?;
 
         let mut fields = Ok(Vec::with_capacity(ast_struct.fields.len()));
@@ -317,19 +475,37 @@ impl<'ast> LoweringContext<'ast> {
         // Only compute fields if the type isn't disabled, otherwise we may encounter forbidden types
         if !attrs.disable {
             for (name, ty, docs, attrs) in ast_struct.fields.iter() {
+                self.errors.set_location(name);
                 let name = self.lower_ident(name, "struct field name")?;
                 if !ty.is_ffi_safe() {
-                    let ffisafe = ty.ffi_safe_version();
-                    self.errors.push(LoweringError::Other(format!(
-                        "Found FFI-unsafe type {ty} in struct field {struct_name}.{name}, consider using {ffisafe}",
-                    )));
+                    self.errors.push(LoweringError::FfiUnsafeField {
+                        ty: ty.to_string(),
+                        field: format!("{struct_name}.{name}"),
+                        suggestion: ty.ffi_safe_version().to_string(),
+                    });
+                }
+                // Struct fields are lowered as inputs, so DiplomatRead has to be caught here
+                let is_read = match ty {
+                    ast::TypeName::Read => true,
+                    ast::TypeName::Reference(_, _, ref_ty) => **ref_ty == ast::TypeName::Read,
+                    _ => false,
+                };
+                let ty = if is_read {
+                    self.errors.push(LoweringError::Other(
+                        "DiplomatRead can only be used as a method parameter".into(),
+                    ));
+                    Err(())
+                } else {
+                    self.lower_type::<Everywhere>(
+                        ty,
+                        &mut &ast_struct.lifetimes,
+                        false,
+                        item.in_path,
+                    )
+                };
+                if let Ok(ref ty) = ty {
+                    self.check_static_lifetimes(ty, false);
                 }
-                let ty = self.lower_type::<Everywhere>(
-                    ty,
-                    &mut &ast_struct.lifetimes,
-                    false,
-                    item.in_path,
-                );
 
                 let field_attrs =
                     self.attr_validator
@@ -352,6 +528,7 @@ impl<'ast> LoweringContext<'ast> {
                 }
             }
         }
+        self.errors.set_location(&ast_struct.name);
         let lifetimes = self.lower_type_lifetime_env(&ast_struct.lifetimes);
 
         let mut special_method_presence = SpecialMethodPresence::default();
@@ -386,6 +563,9 @@ impl<'ast> LoweringContext<'ast> {
             special_method_presence,
         );
 
+        // Lowering the methods and fields pointed errors at them
+        self.errors.set_item(ast_struct.name.as_str());
+        self.errors.set_location(&ast_struct.name);
         self.attr_validator.validate(
             &def.attrs,
             AttributeContext::Type(TypeDef::from(&def)),
@@ -397,6 +577,7 @@ impl<'ast> LoweringContext<'ast> {
     fn lower_trait(&mut self, item: ItemAndInfo<'ast, ast::Trait>) -> Result<TraitDef, ()> {
         let ast_trait = item.item;
         self.errors.set_item(ast_trait.name.as_str());
+        self.errors.set_location(&ast_trait.name);
         let trait_name = self.lower_ident(&ast_trait.name, "trait name")?;
 
         let attrs = self.attr_validator.attr_from_ast(
@@ -429,6 +610,7 @@ impl<'ast> LoweringContext<'ast> {
         parent_trait_attrs: &Attrs,
     ) -> Result<Callback, ()> {
         self.errors.set_subitem(ast_trait_method.name.as_str());
+        self.errors.set_location(&ast_trait_method.name);
         let name = ast_trait_method.name.clone();
         let self_param_ltl = SelfParamLifetimeLowerer::new(&ast_trait_method.lifetimes, self)?;
         let (param_self, mut param_ltl) =
@@ -471,6 +653,7 @@ impl<'ast> LoweringContext<'ast> {
     ) -> Result<OutStructDef, ()> {
         let ast_out_struct = item.item;
         self.errors.set_item(ast_out_struct.name.as_str());
+        self.errors.set_location(&ast_out_struct.name);
         let name = self.lower_ident(&ast_out_struct.name, "out-struct name");
 
         let attrs = self.attr_validator.attr_from_ast(
@@ -489,6 +672,7 @@ impl<'ast> LoweringContext<'ast> {
             // Only compute fields if the type isn't disabled, otherwise we may encounter forbidden types
             if !attrs.disable {
                 for (name, ty, docs, attrs) in ast_out_struct.fields.iter() {
+                    self.errors.set_location(name);
                     let name = self.lower_ident(name, "out-struct field name");
                     let ty = self.lower_out_type(
                         ty,
@@ -498,6 +682,10 @@ impl<'ast> LoweringContext<'ast> {
                         false,
                     );
 
+                    if let Ok(ref ty) = ty {
+                        self.check_static_lifetimes(ty, true);
+                    }
+
                     match (name, ty, &mut fields) {
                         (Ok(name), Ok(ty), Ok(fields)) => fields.push(OutStructField {
                             docs: docs.clone(),
@@ -516,6 +704,8 @@ impl<'ast> LoweringContext<'ast> {
 
             fields
         };
+        self.errors.set_location(&ast_out_struct.name);
+
         let mut special_method_presence = SpecialMethodPresence::default();
         let methods = if attrs.disable {
             Vec::new()
@@ -540,6 +730,9 @@ impl<'ast> LoweringContext<'ast> {
             special_method_presence,
         );
 
+        // Lowering the methods and fields pointed errors at them
+        self.errors.set_item(ast_out_struct.name.as_str());
+        self.errors.set_location(&ast_out_struct.name);
         self.attr_validator.validate(
             &def.attrs,
             AttributeContext::Type(TypeDef::from(&def)),
@@ -566,6 +759,19 @@ impl<'ast> LoweringContext<'ast> {
             _ => (&method.params[..], false),
         };
 
+        let write_encoding = match method.params.last() {
+            Some(last) if last.is_write16() => {
+                if !self.attr_validator.attrs_supported().write16 {
+                    self.errors.push(LoweringError::Unsupported {
+                        message: "DiplomatWrite16 is not supported by this backend".into(),
+                        supports: "write16",
+                    });
+                }
+                WriteEncoding::Utf16
+            }
+            _ => WriteEncoding::Utf8,
+        };
+
         let self_param_ltl = SelfParamLifetimeLowerer::new(&method.lifetime_env, self)?;
 
         let (param_self, param_ltl) = if let Some(self_param) = method.self_param.as_ref() {
@@ -577,6 +783,7 @@ impl<'ast> LoweringContext<'ast> {
         };
 
         let (params, return_ltl) = self.lower_many_params(ast_params, param_ltl, in_path)?;
+        self.errors.set_location(&method.name);
 
         let (output, lifetime_env) = self.lower_return_type(
             method.return_type.as_ref(),
@@ -585,6 +792,11 @@ impl<'ast> LoweringContext<'ast> {
             in_path,
         )?;
 
+        for param in &params {
+            self.check_static_lifetimes(&param.ty, false);
+        }
+        output.with_contained_types(|ty| self.check_static_lifetimes(ty, true));
+
         let abi_name = self.lower_ident(&method.abi_name, "method abi name")?;
         let hir_method = Method {
             docs: method.docs.clone(),
@@ -594,6 +806,7 @@ impl<'ast> LoweringContext<'ast> {
             param_self,
             params,
             output,
+            write_encoding,
             attrs,
         };
 
@@ -632,6 +845,7 @@ impl<'ast> LoweringContext<'ast> {
 
         for method in ast_methods {
             self.errors.set_subitem(method.name.as_str());
+            self.errors.set_location(&method.name);
             let attrs = self.attr_validator.attr_from_ast(
                 &method.attrs,
                 method_parent_attrs,
@@ -653,6 +867,61 @@ impl<'ast> LoweringContext<'ast> {
         methods
     }
 
+    /// Lowers an [`ast::PrimitiveType`], checking that the backend supports it.
+    fn lower_primitive(&mut self, prim: ast::PrimitiveType) -> PrimitiveType {
+        let prim = PrimitiveType::from_ast(prim);
+        if matches!(prim, PrimitiveType::Int128(_)) && !self.attr_validator.attrs_supported().int128
+        {
+            self.errors.push(LoweringError::Unsupported {
+                message: format!("{} is not supported by this backend", prim.as_str()).into(),
+                supports: "int128",
+            });
+        }
+        prim
+    }
+
+    /// Lowers the element type of a primitive slice, checking that the backend supports slices of it.
+    fn lower_slice_primitive(&mut self, prim: ast::PrimitiveType) -> PrimitiveType {
+        let prim = self.lower_primitive(prim);
+        let support = self.attr_validator.attrs_supported();
+        if matches!(prim, PrimitiveType::Int128(_)) && support.int128 && !support.int128_slices {
+            self.errors.push(LoweringError::Unsupported {
+                message: format!("{} slices are not supported by this backend", prim.as_str())
+                    .into(),
+                supports: "int128_slices",
+            });
+        }
+        prim
+    }
+
+    /// Checks that the backend supports any `'static` lifetimes in a parameter, return or field type `ty`.
+    ///
+    /// `'static` input slices are already checked when lowering them.
+    fn check_static_lifetimes<P: TyPosition>(&mut self, ty: &Type<P>, is_output: bool) {
+        if !ty
+            .lifetimes()
+            .any(|lt| matches!(lt, super::MaybeStatic::Static))
+        {
+            return;
+        }
+        let support = self.attr_validator.attrs_supported();
+        match ty {
+            Type::Slice(_) if is_output && !support.static_slices => {
+                self.errors.push(LoweringError::Unsupported {
+                    message: "'static slice types are not supported".into(),
+                    supports: "static_slices",
+                });
+            }
+            Type::Opaque(_) | Type::Struct(_) if !support.static_borrows => {
+                self.errors.push(LoweringError::Unsupported {
+                    message: "'static borrows of opaques and structs are not supported".into(),
+                    supports: "static_borrows",
+                });
+            }
+            _ => {}
+        }
+    }
+
     /// Lowers an [`ast::TypeName`]s into a [`hir::Type`] (for non-output types)
     ///
     /// If there are any errors, they're pushed to `errors` and `None` is returned.
@@ -664,7 +933,7 @@ impl<'ast> LoweringContext<'ast> {
         in_path: &ast::Path,
     ) -> Result<Type<P>, ()> {
         match ty {
-            ast::TypeName::Primitive(prim) => Ok(Type::Primitive(PrimitiveType::from_ast(*prim))),
+            ast::TypeName::Primitive(prim) => Ok(Type::Primitive(self.lower_primitive(*prim))),
             ast::TypeName::Ordering => {
                 self.errors.push(LoweringError::Other("Found cmp::Ordering in parameter or struct field, it is only allowed in return types".to_string()));
                 Err(())
@@ -692,9 +961,10 @@ impl<'ast> LoweringContext<'ast> {
                     }
                 }
                 ast::CustomType::Opaque(_) => {
-                    self.errors.push(LoweringError::Other(format!(
-                        "Opaque passed by value: {path}"
-                    )));
+                    self.errors.push(LoweringError::OpaqueByValue {
+                        ty: path.to_string(),
+                        in_input: true,
+                    });
                     Err(())
                 }
                 ast::CustomType::Enum(enm) => {
@@ -708,9 +978,10 @@ impl<'ast> LoweringContext<'ast> {
             },
             ast::TypeName::ImplTrait(path) => {
                 if !self.attr_validator.attrs_supported().traits {
-                    self.errors.push(LoweringError::Other(
-                        "Traits are not supported by this backend".into(),
-                    ));
+                    self.errors.push(LoweringError::Unsupported {
+                        message: "Traits are not supported by this backend".into(),
+                        supports: "traits",
+                    });
                 }
                 let trt = path.resolve_trait(in_path, self.env);
                 let tcx_id = self
@@ -751,6 +1022,28 @@ impl<'ast> LoweringContext<'ast> {
                         }
                     }
                 }
+                ast::TypeName::Read => {
+                    if !self.attr_validator.attrs_supported().reads {
+                        self.errors.push(LoweringError::Unsupported {
+                            message: "DiplomatRead is not supported by this backend".into(),
+                            supports: "reads",
+                        });
+                    }
+                    if *mutability != ast::Mutability::Mutable {
+                        self.errors.push(LoweringError::Other(
+                            "DiplomatRead must be passed as &mut DiplomatRead".into(),
+                        ));
+                        return Err(());
+                    }
+                    // The reader is only valid for the duration of the call, so nothing may borrow from it
+                    if *lifetime != ast::Lifetime::Anonymous {
+                        self.errors.push(LoweringError::Other(
+                            "DiplomatRead can only be borrowed for the duration of the call, and can't have a named or 'static lifetime".into(),
+                        ));
+                        return Err(());
+                    }
+                    Ok(Type::Read)
+                }
                 _ => {
                     self.errors.push(LoweringError::Other(format!("found &T in input where T isn't a custom type and therefore not opaque. T = {ref_ty}")));
                     Err(())
@@ -815,11 +1108,16 @@ impl<'ast> LoweringContext<'ast> {
                             }
                             _ => {
                                 if in_struct && *stdlib == ast::StdlibOrDiplomat::Stdlib {
-                                    self.errors.push(LoweringError::Other("Found Option<T> for struct/enum T in a struct field, please use DiplomatOption<T>".into()));
+                                    self.errors.push(LoweringError::OptionInStructField {
+                                        kind: "struct/enum",
+                                    });
                                     return Err(());
                                 }
                                 if !self.attr_validator.attrs_supported().option {
-                                    self.errors.push(LoweringError::Other("Options of structs/enums/primitives not supported by this backend".into()));
+                                    self.errors.push(LoweringError::Unsupported {
+                                        message: "Options of structs/enums/primitives not supported by this backend".into(),
+                                        supports: "option",
+                                    });
                                 }
                                 let inner = self.lower_type(opt_ty, ltl, in_struct, in_path)?;
                                 Ok(Type::DiplomatOption(Box::new(inner)))
@@ -828,17 +1126,18 @@ impl<'ast> LoweringContext<'ast> {
                     }
                     ast::TypeName::Primitive(prim) => {
                         if in_struct && *stdlib == ast::StdlibOrDiplomat::Stdlib {
-                            self.errors.push(LoweringError::Other("Found Option<T> for primitive T in a struct field, please use DiplomatOption<T>".into()));
+                            self.errors
+                                .push(LoweringError::OptionInStructField { kind: "primitive" });
                             return Err(());
                         }
                         if !self.attr_validator.attrs_supported().option {
-                            self.errors.push(LoweringError::Other(
-                                "Options of structs/enums/primitives not supported by this backend"
-                                    .into(),
-                            ));
+                            self.errors.push(LoweringError::Unsupported {
+                                message: "Options of structs/enums/primitives not supported by this backend".into(),
+                                supports: "option",
+                            });
                         }
                         Ok(Type::DiplomatOption(Box::new(Type::Primitive(
-                            PrimitiveType::from_ast(*prim),
+                            self.lower_primitive(*prim),
                         ))))
                     }
                     ast::TypeName::Box(box_ty) => {
@@ -858,9 +1157,15 @@ impl<'ast> LoweringContext<'ast> {
                 ));
                 Err(())
             }
-            ast::TypeName::Write => {
+            ast::TypeName::Write | ast::TypeName::Write16 => {
+                self.errors.push(LoweringError::Other(format!(
+                    "{ty} can only appear as the last parameter of a method"
+                )));
+                Err(())
+            }
+            ast::TypeName::Read => {
                 self.errors.push(LoweringError::Other(
-                    "DiplomatWrite can only appear as the last parameter of a method".into(),
+                    "DiplomatRead must be passed as &mut DiplomatRead".into(),
                 ));
                 Err(())
             }
@@ -868,9 +1173,10 @@ impl<'ast> LoweringContext<'ast> {
                 let new_lifetime = lifetime.as_ref().map(|lt| ltl.lower_lifetime(lt));
                 if let Some(super::MaybeStatic::Static) = new_lifetime {
                     if !self.attr_validator.attrs_supported().static_slices {
-                        self.errors.push(LoweringError::Other(
-                            "'static string slice types are not supported. Try #[diplomat::attr(not(supports = static_slices), disable)]".into()
-                        ));
+                        self.errors.push(LoweringError::Unsupported {
+                            message: "'static string slice types are not supported".into(),
+                            supports: "static_slices",
+                        });
                     }
                 }
                 Ok(Type::Slice(Slice::Str(new_lifetime, *encoding)))
@@ -884,23 +1190,26 @@ impl<'ast> LoweringContext<'ast> {
                 if let Some(b) = new_lifetime {
                     if let super::MaybeStatic::Static = b.lifetime {
                         if !self.attr_validator.attrs_supported().static_slices {
-                            self.errors.push(LoweringError::Other(
-                                format!("'static {prim:?} slice types not supported. Try #[diplomat::attr(not(supports = static_slices), disable)]")
-                            ));
+                            self.errors.push(LoweringError::Unsupported {
+                                message: format!("'static {prim:?} slice types not supported")
+                                    .into(),
+                                supports: "static_slices",
+                            });
                         }
                     }
                 }
 
                 Ok(Type::Slice(Slice::Primitive(
                     new_lifetime,
-                    PrimitiveType::from_ast(*prim),
+                    self.lower_slice_primitive(*prim),
                 )))
             }
             ast::TypeName::Function(input_types, out_type) => {
                 if !self.attr_validator.attrs_supported().callbacks {
-                    self.errors.push(LoweringError::Other(
-                        "Callback arguments are not supported by this backend".into(),
-                    ));
+                    self.errors.push(LoweringError::Unsupported {
+                        message: "Callback arguments are not supported by this backend".into(),
+                        supports: "callbacks",
+                    });
                 }
                 if in_struct {
                     self.errors.push(LoweringError::Other(
@@ -953,9 +1262,7 @@ impl<'ast> LoweringContext<'ast> {
         in_result_option: bool,
     ) -> Result<OutType, ()> {
         match ty {
-            ast::TypeName::Primitive(prim) => {
-                Ok(OutType::Primitive(PrimitiveType::from_ast(*prim)))
-            }
+            ast::TypeName::Primitive(prim) => Ok(OutType::Primitive(self.lower_primitive(*prim))),
             ast::TypeName::Ordering => {
                 if in_struct {
                     self.errors.push(LoweringError::Other(
@@ -990,9 +1297,10 @@ impl<'ast> LoweringContext<'ast> {
                         }
                     }
                     ast::CustomType::Opaque(_) => {
-                        self.errors.push(LoweringError::Other(format!(
-                            "Opaque passed by value in input: {path}"
-                        )));
+                        self.errors.push(LoweringError::OpaqueByValue {
+                            ty: path.to_string(),
+                            in_input: false,
+                        });
                         Err(())
                     }
                     ast::CustomType::Enum(enm) => {
@@ -1031,6 +1339,12 @@ impl<'ast> LoweringContext<'ast> {
                         }
                     }
                 }
+                ast::TypeName::Read => {
+                    self.errors.push(LoweringError::Other(
+                        "DiplomatRead can only be used as a method parameter".into(),
+                    ));
+                    Err(())
+                }
                 _ => {
                     self.errors.push(LoweringError::Other(format!("found &T in output where T isn't a custom type and therefore not opaque. T = {ref_ty}")));
                     Err(())
@@ -1149,11 +1463,16 @@ impl<'ast> LoweringContext<'ast> {
                         }
                         _ => {
                             if in_struct && *stdlib == ast::StdlibOrDiplomat::Stdlib {
-                                self.errors.push(LoweringError::Other("Found Option<T> for struct/enum T in a struct field, please use DiplomatOption<T>".into()));
+                                self.errors.push(LoweringError::OptionInStructField {
+                                    kind: "struct/enum",
+                                });
                                 return Err(());
                             }
                             if !self.attr_validator.attrs_supported().option {
-                                self.errors.push(LoweringError::Other("Options of structs/enums/primitives not supported by this backend".into()));
+                                self.errors.push(LoweringError::Unsupported {
+                                    message: "Options of structs/enums/primitives not supported by this backend".into(),
+                                    supports: "option",
+                                });
                             }
                             let inner =
                                 self.lower_out_type(opt_ty, ltl, in_path, in_struct, true)?;
@@ -1163,17 +1482,20 @@ impl<'ast> LoweringContext<'ast> {
                 }
                 ast::TypeName::Primitive(prim) => {
                     if in_struct && *stdlib == ast::StdlibOrDiplomat::Stdlib {
-                        self.errors.push(LoweringError::Other("Found Option<T> for primitive T in a struct field, please use DiplomatOption<T>".into()));
+                        self.errors
+                            .push(LoweringError::OptionInStructField { kind: "primitive" });
                         return Err(());
                     }
                     if !self.attr_validator.attrs_supported().option {
-                        self.errors.push(LoweringError::Other(
-                            "Options of structs/enums/primitives not supported by this backend"
-                                .into(),
-                        ));
+                        self.errors.push(LoweringError::Unsupported {
+                            message:
+                                "Options of structs/enums/primitives not supported by this backend"
+                                    .into(),
+                            supports: "option",
+                        });
                     }
                     Ok(Type::DiplomatOption(Box::new(Type::Primitive(
-                        PrimitiveType::from_ast(*prim),
+                        self.lower_primitive(*prim),
                     ))))
                 }
                 _ => {
@@ -1187,9 +1509,15 @@ impl<'ast> LoweringContext<'ast> {
                 ));
                 Err(())
             }
-            ast::TypeName::Write => {
+            ast::TypeName::Write | ast::TypeName::Write16 => {
+                self.errors.push(LoweringError::Other(format!(
+                    "{ty} can only appear as the last parameter of a method"
+                )));
+                Err(())
+            }
+            ast::TypeName::Read => {
                 self.errors.push(LoweringError::Other(
-                    "DiplomatWrite can only appear as the last parameter of a method".into(),
+                    "DiplomatRead can only be used as a method parameter".into(),
                 ));
                 Err(())
             }
@@ -1212,7 +1540,7 @@ impl<'ast> LoweringContext<'ast> {
             ast::TypeName::PrimitiveSlice(Some((lt, m)), prim, _stdlib) => {
                 Ok(OutType::Slice(Slice::Primitive(
                     Some(Borrow::new(ltl.lower_lifetime(lt), *m)),
-                    PrimitiveType::from_ast(*prim),
+                    self.lower_slice_primitive(*prim),
                 )))
             }
             ast::TypeName::Unit => {
@@ -1416,6 +1744,7 @@ impl<'ast> LoweringContext<'ast> {
         ltl: &mut impl LifetimeLowerer,
         in_path: &ast::Path,
     ) -> Result<Param, ()> {
+        self.errors.set_location(&param.name);
         let name = self.lower_ident(&param.name, "param name");
         let ty = self.lower_type::<InputOnly>(&param.ty, ltl, false, in_path);
 
@@ -1465,6 +1794,7 @@ impl<'ast> LoweringContext<'ast> {
         ltl: &mut impl LifetimeLowerer,
         in_path: &ast::Path,
     ) -> Result<CallbackParam, ()> {
+        self.errors.set_location(&param.name);
         let name = self.lower_ident(&param.name, "param name")?;
         let ty = self.lower_out_type(
             &param.ty, ltl, in_path, false, /* in_struct */
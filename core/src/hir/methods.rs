@@ -35,6 +35,9 @@ pub struct Method {
     pub params: Vec<Param>,
     /// The output type, including whether it returns a Result/Option/Writeable/etc
     pub output: ReturnType,
+    /// The encoding of the written string, if `output` contains a [`SuccessType::Write`].
+    /// Always [`WriteEncoding::Utf8`] for methods that don't write.
+    pub write_encoding: WriteEncoding,
     /// Resolved (and inherited) diplomat::attr attributes on this method
     pub attrs: Attrs,
 }
@@ -86,6 +89,7 @@ impl CallbackInstantiationFunctionality for NoCallback {
 #[non_exhaustive]
 pub enum SuccessType {
     /// Conceptually returns a string, which gets written to the `write: DiplomatWrite` argument
+    /// (or `DiplomatWrite16`, see [`Method::write_encoding`])
     Write,
     /// A Diplomat type. Some types can be outputs, but not inputs, which is expressed by the `OutType` parameter.
     OutType(OutType),
@@ -93,6 +97,16 @@ pub enum SuccessType {
     Unit,
 }
 
+/// The encoding of the string written by a method returning [`SuccessType::Write`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WriteEncoding {
+    /// UTF-8, written to a `DiplomatWrite`
+    Utf8,
+    /// UTF-16, written to a `DiplomatWrite16`
+    Utf16,
+}
+
 /// Whether or not the method returns a value or a result.
 #[derive(Debug)]
 #[allow(clippy::exhaustive_enums)] // this only exists for fallible/infallible, breaking changes for more complex returns are ok
@@ -24,6 +24,8 @@ pub struct TypeContext {
     opaques: Vec<OpaqueDef>,
     enums: Vec<EnumDef>,
     traits: Vec<TraitDef>,
+    /// The crate each symbol from an external bridge crate was defined in
+    external_crates: HashMap<SymbolId, String>,
 }
 
 /// Key used to index into a [`TypeContext`] representing a struct.
@@ -171,6 +173,15 @@ impl TypeContext {
         self.traits.index(id.0)
     }
 
+    /// If this type or trait is defined in another bridge crate (see [`Self::from_syn_with_externs()`]),
+    /// get the name of that crate.
+    ///
+    /// Backends should not generate code for such symbols, and should instead import the
+    /// bindings generated for the other crate.
+    pub fn external_crate(&self, id: impl Into<SymbolId>) -> Option<&str> {
+        self.external_crates.get(&id.into()).map(|s| s.as_str())
+    }
+
     /// Resolve and format a named type for use in diagnostics
     /// (don't apply rename rules and such)
     pub fn fmt_type_name_diagnostics(&self, id: TypeId) -> Cow<str> {
@@ -189,7 +200,46 @@ impl TypeContext {
         s: &'ast syn::File,
         attr_validator: impl AttributeValidator + 'static,
     ) -> Result<Self, Vec<ErrorAndContext>> {
-        let types = ast::File::from(s).all_types();
+        Self::from_syn_with_externs(s, &[], attr_validator)
+    }
+
+    /// Like [`Self::from_syn()`], but the bridge may reference types from the given
+    /// other bridge crates, as `(crate name, crate root)` pairs.
+    ///
+    /// Types from external crates are lowered and validated as well, so that they can be
+    /// resolved like any other type, but backends should not generate code for them: see
+    /// [`Self::external_crate()`].
+    pub fn from_syn_with_externs<'ast>(
+        s: &'ast syn::File,
+        externs: &[(&str, &'ast syn::File)],
+        attr_validator: impl AttributeValidator + 'static,
+    ) -> Result<Self, Vec<ErrorAndContext>> {
+        let file = ast::File::from(s);
+        let mut errors = ErrorStore::default();
+        for (i, (name, _)) in externs.iter().enumerate() {
+            errors.set_item(name);
+            if file.modules.contains_key(*name) {
+                errors.push(LoweringError::Other(format!(
+                    "External crate `{name}` has the same name as a module of the bridge"
+                )));
+            } else if externs[..i].iter().any(|(other, _)| other == name) {
+                errors.push(LoweringError::Other(format!(
+                    "External crate `{name}` is given more than once"
+                )));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors.take_errors());
+        }
+        let externs = externs
+            .iter()
+            .map(|(name, file)| (*name, ast::File::from(*file)))
+            .collect::<Vec<_>>();
+        let externs = externs
+            .iter()
+            .map(|(name, file)| (*name, file))
+            .collect::<Vec<_>>();
+        let types = file.all_types_with_externs(&externs);
         let (mut ctx, hir) = Self::from_ast_without_validation(&types, attr_validator)?;
         ctx.errors.set_item("(validation)");
         hir.validate(&mut ctx.errors);
@@ -211,6 +261,7 @@ impl TypeContext {
         let mut ast_traits = SmallVec::<[_; 16]>::new();
 
         let mut errors = ErrorStore::default();
+        let mut external_crates = HashMap::new();
 
         for (path, mod_env) in env.iter_modules() {
             errors.set_item(
@@ -229,6 +280,8 @@ impl TypeContext {
             let method_attrs =
                 mod_attrs.for_inheritance(AttrInheritContext::MethodOrImplFromModule);
 
+            let krate = env.external_crate(path).map(|c| c.to_string());
+
             for sym in mod_env.items() {
                 match sym {
                     ast::ModSymbol::CustomType(custom_type) => match custom_type {
@@ -245,6 +298,9 @@ impl TypeContext {
                                 method_parent_attrs: method_attrs.clone(),
                                 id: id.into(),
                             };
+                            if let Some(ref krate) = krate {
+                                external_crates.insert(item.id, krate.clone());
+                            }
                             if strct.output_only {
                                 ast_out_structs.push(item);
                             } else {
@@ -259,6 +315,9 @@ impl TypeContext {
                                 method_parent_attrs: method_attrs.clone(),
                                 id: TypeId::Opaque(OpaqueId(ast_opaques.len())).into(),
                             };
+                            if let Some(ref krate) = krate {
+                                external_crates.insert(item.id, krate.clone());
+                            }
                             ast_opaques.push(item)
                         }
                         ast::CustomType::Enum(enm) => {
@@ -269,6 +328,9 @@ impl TypeContext {
                                 method_parent_attrs: method_attrs.clone(),
                                 id: TypeId::Enum(EnumId(ast_enums.len())).into(),
                             };
+                            if let Some(ref krate) = krate {
+                                external_crates.insert(item.id, krate.clone());
+                            }
                             ast_enums.push(item)
                         }
                     },
@@ -280,6 +342,9 @@ impl TypeContext {
                             method_parent_attrs: method_attrs.clone(),
                             id: TraitId(ast_traits.len()).into(),
                         };
+                        if let Some(ref krate) = krate {
+                            external_crates.insert(item.id, krate.clone());
+                        }
                         ast_traits.push(item)
                     }
                     _ => {}
@@ -317,6 +382,7 @@ impl TypeContext {
                     opaques,
                     enums,
                     traits,
+                    external_crates,
                 };
 
                 if !ctx.errors.is_empty() {
@@ -353,9 +419,7 @@ impl TypeContext {
                     for lt in out_ty.lifetimes() {
                         if let MaybeStatic::NonStatic(lt) = lt {
                             if method.lifetime_env.get_bounds(lt).is_none() {
-                                errors.push(LoweringError::Other(
-                                    "Found elided lifetime in return type, please explicitly specify".into(),
-                                ));
+                                errors.push(LoweringError::ElidedReturnLifetime);
 
                                 failed = true;
                                 break;
@@ -442,8 +506,12 @@ impl TypeContext {
                         // This case is technically already handled in the lifetime lowerer, we're being careful
                         "comes from &-ref's lifetime in parameter".into()
                     };
-                    errors.push(LoweringError::Other(format!("Method should explicitly include this \
-                                        lifetime bound from {param}: '{use_longer_name}: '{use_name} ({def_cause})")))
+                    errors.push(LoweringError::MissingLifetimeBound {
+                        param: param.to_string(),
+                        longer: use_longer_name.into(),
+                        shorter: use_name.into(),
+                        cause: def_cause,
+                    })
                 }
             }
         }
@@ -585,13 +653,13 @@ mod tests {
     use std::fmt::Write;
 
     macro_rules! uitest_lowering {
-        ($($file:tt)*) => {
+        (support = $support:expr; $($file:tt)*) => {
             let parsed: syn::File = syn::parse_quote! { $($file)* };
 
             let mut output = String::new();
 
             let mut attr_validator = hir::BasicAttributeValidator::new("tests");
-            attr_validator.support.option = true;
+            attr_validator.support = $support;
             match hir::TypeContext::from_syn(&parsed, attr_validator) {
                 Ok(_context) => (),
                 Err(e) => {
@@ -603,7 +671,13 @@ mod tests {
             insta::with_settings!({}, {
                 insta::assert_snapshot!(output)
             });
-        }
+        };
+        ($($file:tt)*) => {
+            uitest_lowering! {
+                support = hir::BackendAttrSupport { option: true, ..Default::default() };
+                $($file)*
+            }
+        };
     }
 
     #[test]
@@ -928,4 +1002,189 @@ mod tests {
             }
         };
     }
+
+    #[test]
+    fn test_unsupported_types() {
+        uitest_lowering! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Foo {}
+
+                impl Foo {
+                    pub fn int128(x: i128) -> u128 {
+                        x as u128
+                    }
+                    pub fn write16(&self, w: &mut DiplomatWrite16) {}
+                    pub fn read(&self, r: &mut DiplomatRead) {}
+                    pub fn static_param(x: &'static Foo) {}
+                    pub fn static_return() -> &'static Foo {
+                        todo!()
+                    }
+                }
+            }
+        };
+    }
+
+    #[test]
+    fn test_int128_slices() {
+        let parsed: syn::File = syn::parse_quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Foo {}
+
+                impl Foo {
+                    pub fn int128(x: i128) -> u128 {
+                        x as u128
+                    }
+                    pub fn slice_param(x: &[i128]) {}
+                    pub fn slice_return<'a>(&'a self) -> &'a [u128] {
+                        todo!()
+                    }
+                }
+            }
+        };
+
+        let mut output = String::new();
+
+        let mut attr_validator = hir::BasicAttributeValidator::new("tests");
+        attr_validator.support.int128 = true;
+        match hir::TypeContext::from_syn(&parsed, attr_validator) {
+            Ok(_context) => (),
+            Err(e) => {
+                for (ctx, err) in e {
+                    writeln!(&mut output, "Lowering error in {ctx}: {err}").unwrap();
+                }
+            }
+        };
+        insta::with_settings!({}, { insta::assert_snapshot!(output) });
+    }
+
+    #[test]
+    fn test_read_params() {
+        uitest_lowering! {
+            support = hir::BackendAttrSupport { reads: true, ..Default::default() };
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Foo {}
+
+                struct Bar<'a> {
+                    r: &'a mut DiplomatRead,
+                }
+
+                impl Foo {
+                    pub fn read(&self, r: &mut DiplomatRead) {}
+                    pub fn by_value(r: DiplomatRead) {}
+                    pub fn immutable(r: &DiplomatRead) {}
+                    pub fn named<'a>(r: &'a mut DiplomatRead) -> Box<Foo> {
+                        todo!()
+                    }
+                    pub fn returned(&self) -> &DiplomatRead {
+                        todo!()
+                    }
+                }
+            }
+        };
+    }
+
+    #[test]
+    fn test_external_crate() {
+        let other: syn::File = syn::parse_quote! {
+            #[diplomat::bridge]
+            pub mod ffi {
+                #[diplomat::opaque]
+                pub struct Locale;
+
+                pub struct Options {
+                    pub locale_len: usize,
+                    pub inner: crate::ffi::Inner,
+                }
+
+                pub struct Inner {
+                    pub x: u8,
+                }
+            }
+        };
+        let parsed: syn::File = syn::parse_quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                use other_ffi::ffi::Locale;
+
+                #[diplomat::opaque]
+                struct Formatter;
+
+                impl Formatter {
+                    pub fn new(locale: &Locale, options: other_ffi::ffi::Options) -> Box<Formatter> {
+                        unimplemented!()
+                    }
+                }
+            }
+        };
+
+        let tcx = hir::TypeContext::from_syn_with_externs(
+            &parsed,
+            &[("other_ffi", &other)],
+            hir::BasicAttributeValidator::new("tests"),
+        )
+        .unwrap_or_else(|e| {
+            let errors = e.iter().map(|(ctx, err)| format!("{ctx}: {err}"));
+            panic!("Lowering failed: {}", errors.collect::<Vec<_>>().join("; "))
+        });
+
+        let externals = tcx
+            .all_types()
+            .map(|(id, ty)| (ty.name().as_str().to_owned(), tcx.external_crate(id)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            externals,
+            [
+                ("Inner".to_owned(), Some("other_ffi")),
+                ("Options".to_owned(), Some("other_ffi")),
+                ("Formatter".to_owned(), None),
+                ("Locale".to_owned(), Some("other_ffi")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_external_crate_name_clash() {
+        let other: syn::File = syn::parse_quote! {
+            #[diplomat::bridge]
+            pub mod ffi {
+                #[diplomat::opaque]
+                pub struct Locale;
+            }
+        };
+        let parsed: syn::File = syn::parse_quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Formatter;
+            }
+        };
+
+        let errors = hir::TypeContext::from_syn_with_externs(
+            &parsed,
+            &[
+                ("ffi", &other),
+                ("other_ffi", &other),
+                ("other_ffi", &other),
+            ],
+            hir::BasicAttributeValidator::new("tests"),
+        )
+        .expect_err("crate names clash");
+        let errors = errors
+            .iter()
+            .map(|(ctx, err)| format!("{ctx}: {err}"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                "ffi: External crate `ffi` has the same name as a module of the bridge",
+                "other_ffi: External crate `other_ffi` is given more than once",
+            ]
+        );
+    }
 }
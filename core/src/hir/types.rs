@@ -24,6 +24,10 @@ pub enum Type<P: TyPosition = Everywhere> {
     Enum(EnumPath),
     Slice(Slice),
     Callback(P::CallbackInstantiation), // only a Callback if P == InputOnly
+    /// `&mut DiplomatRead`, a data source provided by the caller.
+    ///
+    /// Only valid as a method parameter, and can't be borrowed by the output.
+    Read,
     /// `DiplomatOption<T>`, for  a primitive, struct, or enum `T`.
     ///
     /// In some cases this can be specified as `Option<T>`, but under the hood it gets translated to
@@ -103,7 +107,7 @@ impl<P: TyPosition<StructPath = StructPath>> Type<P> {
                 (acc.0 + inner.0, acc.1 + inner.1)
             }),
             Type::Opaque(_) | Type::Slice(_) | Type::Callback(_) | Type::ImplTrait(_) => (1, 1),
-            Type::Primitive(_) | Type::Enum(_) => (0, 0),
+            Type::Primitive(_) | Type::Enum(_) | Type::Read => (0, 0),
             Type::DiplomatOption(ty) => ty.field_leaf_lifetime_counts(tcx),
         }
     }
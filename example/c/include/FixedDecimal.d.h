@@ -1,19 +0,0 @@
-#ifndef FixedDecimal_D_H
-#define FixedDecimal_D_H
-
-#include <stdio.h>
-#include <stdint.h>
-#include <stddef.h>
-#include <stdbool.h>
-#include "diplomat_runtime.h"
-
-
-
-
-
-typedef struct FixedDecimal FixedDecimal;
-
-
-
-
-#endif // FixedDecimal_D_H
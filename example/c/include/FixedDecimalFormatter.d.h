@@ -1,19 +0,0 @@
-#ifndef FixedDecimalFormatter_D_H
-#define FixedDecimalFormatter_D_H
-
-#include <stdio.h>
-#include <stdint.h>
-#include <stddef.h>
-#include <stdbool.h>
-#include "diplomat_runtime.h"
-
-
-
-
-
-typedef struct FixedDecimalFormatter FixedDecimalFormatter;
-
-
-
-
-#endif // FixedDecimalFormatter_D_H
@@ -1,25 +0,0 @@
-#ifndef FixedDecimalFormatterOptions_H
-#define FixedDecimalFormatterOptions_H
-
-#include <stdio.h>
-#include <stdint.h>
-#include <stddef.h>
-#include <stdbool.h>
-#include "diplomat_runtime.h"
-
-
-#include "FixedDecimalFormatterOptions.d.h"
-
-
-
-
-
-
-FixedDecimalFormatterOptions icu4x_FixedDecimalFormatterOptions_default_mv1(void);
-
-
-
-
-
-
-#endif // FixedDecimalFormatterOptions_H
@@ -1,23 +0,0 @@
-#ifndef FixedDecimalGroupingStrategy_H
-#define FixedDecimalGroupingStrategy_H
-
-#include <stdio.h>
-#include <stdint.h>
-#include <stddef.h>
-#include <stdbool.h>
-#include "diplomat_runtime.h"
-
-
-#include "FixedDecimalGroupingStrategy.d.h"
-
-
-
-
-
-
-
-
-
-
-
-#endif // FixedDecimalGroupingStrategy_H
@@ -0,0 +1,19 @@
+#ifndef icu4x_DataProvider_D_H
+#define icu4x_DataProvider_D_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+
+
+
+typedef struct icu4x_DataProvider icu4x_DataProvider;
+
+
+
+
+#endif // icu4x_DataProvider_D_H
@@ -1,5 +1,5 @@
-#ifndef DataProvider_H
-#define DataProvider_H
+#ifndef icu4x_DataProvider_H
+#define icu4x_DataProvider_H
 
 #include <stdio.h>
 #include <stdint.h>
@@ -8,23 +8,23 @@
 #include "diplomat_runtime.h"
 
 
-#include "DataProvider.d.h"
+#include "icu4x_DataProvider.d.h"
 
 
 
 
 
 
-DataProvider* icu4x_DataProvider_new_static_mv1(void);
+icu4x_DataProvider* icu4x_DataProvider_new_static_mv1(void);
 
 typedef struct icu4x_DataProvider_returns_result_mv1_result { bool is_ok;} icu4x_DataProvider_returns_result_mv1_result;
 icu4x_DataProvider_returns_result_mv1_result icu4x_DataProvider_returns_result_mv1(void);
 
 
-void icu4x_DataProvider_destroy_mv1(DataProvider* self);
+void icu4x_DataProvider_destroy_mv1(icu4x_DataProvider* self);
 
 
 
 
 
-#endif // DataProvider_H
+#endif // icu4x_DataProvider_H
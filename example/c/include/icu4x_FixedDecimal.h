@@ -0,0 +1,32 @@
+#ifndef icu4x_FixedDecimal_H
+#define icu4x_FixedDecimal_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+#include "icu4x_FixedDecimal.d.h"
+
+
+
+
+
+
+icu4x_FixedDecimal* icu4x_FixedDecimal_new_mv1(int32_t v);
+
+void icu4x_FixedDecimal_multiply_pow10_mv1(icu4x_FixedDecimal* self, int16_t power);
+
+typedef struct icu4x_FixedDecimal_to_string_mv1_result { bool is_ok;} icu4x_FixedDecimal_to_string_mv1_result;
+icu4x_FixedDecimal_to_string_mv1_result icu4x_FixedDecimal_to_string_mv1(const icu4x_FixedDecimal* self, DiplomatWrite* write);
+
+
+void icu4x_FixedDecimal_destroy_mv1(icu4x_FixedDecimal* self);
+
+
+
+
+
+#endif // icu4x_FixedDecimal_H
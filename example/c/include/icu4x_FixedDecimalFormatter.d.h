@@ -0,0 +1,19 @@
+#ifndef icu4x_FixedDecimalFormatter_D_H
+#define icu4x_FixedDecimalFormatter_D_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+
+
+
+typedef struct icu4x_FixedDecimalFormatter icu4x_FixedDecimalFormatter;
+
+
+
+
+#endif // icu4x_FixedDecimalFormatter_D_H
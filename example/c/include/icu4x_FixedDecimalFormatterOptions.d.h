@@ -0,0 +1,27 @@
+#ifndef icu4x_FixedDecimalFormatterOptions_D_H
+#define icu4x_FixedDecimalFormatterOptions_D_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+#include "icu4x_FixedDecimalGroupingStrategy.d.h"
+
+
+
+
+typedef struct icu4x_FixedDecimalFormatterOptions {
+  icu4x_FixedDecimalGroupingStrategy grouping_strategy;
+  bool some_other_config;
+} icu4x_FixedDecimalFormatterOptions;
+DIPLOMAT_ASSERT_LAYOUT(icu4x_FixedDecimalFormatterOptions, 8, 4, 8, 4);
+DIPLOMAT_ASSERT_OFFSET(icu4x_FixedDecimalFormatterOptions, grouping_strategy, 0, 0);
+DIPLOMAT_ASSERT_OFFSET(icu4x_FixedDecimalFormatterOptions, some_other_config, 4, 4);
+
+typedef struct icu4x_FixedDecimalFormatterOptions_option {union { icu4x_FixedDecimalFormatterOptions ok; }; bool is_ok; } icu4x_FixedDecimalFormatterOptions_option;
+
+
+
+#endif // icu4x_FixedDecimalFormatterOptions_D_H
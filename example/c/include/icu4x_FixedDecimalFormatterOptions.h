@@ -0,0 +1,25 @@
+#ifndef icu4x_FixedDecimalFormatterOptions_H
+#define icu4x_FixedDecimalFormatterOptions_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+#include "icu4x_FixedDecimalFormatterOptions.d.h"
+
+
+
+
+
+
+icu4x_FixedDecimalFormatterOptions icu4x_FixedDecimalFormatterOptions_default_mv1(void);
+
+
+
+
+
+
+#endif // icu4x_FixedDecimalFormatterOptions_H
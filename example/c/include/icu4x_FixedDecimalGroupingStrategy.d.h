@@ -0,0 +1,25 @@
+#ifndef icu4x_FixedDecimalGroupingStrategy_D_H
+#define icu4x_FixedDecimalGroupingStrategy_D_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+
+
+
+typedef enum icu4x_FixedDecimalGroupingStrategy {
+  icu4x_FixedDecimalGroupingStrategy_Auto = 0,
+  icu4x_FixedDecimalGroupingStrategy_Never = 1,
+  icu4x_FixedDecimalGroupingStrategy_Always = 2,
+  icu4x_FixedDecimalGroupingStrategy_Min2 = 3,
+} icu4x_FixedDecimalGroupingStrategy;
+
+typedef struct icu4x_FixedDecimalGroupingStrategy_option {union { icu4x_FixedDecimalGroupingStrategy ok; }; bool is_ok; } icu4x_FixedDecimalGroupingStrategy_option;
+
+
+
+#endif // icu4x_FixedDecimalGroupingStrategy_D_H
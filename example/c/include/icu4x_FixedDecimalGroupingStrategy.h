@@ -0,0 +1,23 @@
+#ifndef icu4x_FixedDecimalGroupingStrategy_H
+#define icu4x_FixedDecimalGroupingStrategy_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+#include "icu4x_FixedDecimalGroupingStrategy.d.h"
+
+
+
+
+
+
+
+
+
+
+
+#endif // icu4x_FixedDecimalGroupingStrategy_H
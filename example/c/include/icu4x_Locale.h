@@ -0,0 +1,27 @@
+#ifndef icu4x_Locale_H
+#define icu4x_Locale_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+#include "icu4x_Locale.d.h"
+
+
+
+
+
+
+icu4x_Locale* icu4x_Locale_new_mv1(DiplomatStringView name);
+
+
+void icu4x_Locale_destroy_mv1(icu4x_Locale* self);
+
+
+
+
+
+#endif // icu4x_Locale_H
@@ -1,13 +1,13 @@
 #include <stdio.h>
 #include <assert.h>
 
-#include "include/Locale.h"
-#include "include/DataProvider.h"
-#include "include/FixedDecimal.h"
-#include "include/FixedDecimalFormatter.h"
-#include "include/FixedDecimalFormatterOptions.h"
+#include "include/icu4x_Locale.h"
+#include "include/icu4x_DataProvider.h"
+#include "include/icu4x_FixedDecimal.h"
+#include "include/icu4x_FixedDecimalFormatter.h"
+#include "include/icu4x_FixedDecimalFormatterOptions.h"
 
-void print_decimal(FixedDecimal* fd) {
+void print_decimal(icu4x_FixedDecimal* fd) {
     char output[40];
     DiplomatWrite out = diplomat_simple_write(output, 40);
     assert(icu4x_FixedDecimal_to_string_mv1(fd, &out).is_ok == true);
@@ -15,7 +15,7 @@ void print_decimal(FixedDecimal* fd) {
     printf("%s\n", output);
 }
 
-void format_decimal(FixedDecimalFormatter* fdf, FixedDecimal* fd) {
+void format_decimal(icu4x_FixedDecimalFormatter* fdf, icu4x_FixedDecimal* fd) {
     char output[40];
     DiplomatWrite out = diplomat_simple_write(output, 40);
     icu4x_FixedDecimalFormatter_format_write_mv1(fdf, fd, &out);
@@ -24,7 +24,7 @@ void format_decimal(FixedDecimalFormatter* fdf, FixedDecimal* fd) {
 }
 
 int main(int argc, char *argv[]) {
-    FixedDecimal* fd = icu4x_FixedDecimal_new_mv1(123);
+    icu4x_FixedDecimal* fd = icu4x_FixedDecimal_new_mv1(123);
 
     print_decimal(fd);
 
@@ -33,9 +33,9 @@ int main(int argc, char *argv[]) {
 
     print_decimal(fd);
 
-    Locale* locale = icu4x_Locale_new_mv1((DiplomatStringView){.data = "bn", .len = 2});
+    icu4x_Locale* locale = icu4x_Locale_new_mv1((DiplomatStringView){.data = "bn", .len = 2});
 
-    DataProvider* data_provider = icu4x_DataProvider_new_static_mv1();
+    icu4x_DataProvider* data_provider = icu4x_DataProvider_new_static_mv1();
 
     struct icu4x_FixedDecimalFormatter_try_new_mv1_result fdf = icu4x_FixedDecimalFormatter_try_new_mv1(locale, data_provider, icu4x_FixedDecimalFormatterOptions_default_mv1());
     printf("%d\n", fdf.is_ok);
@@ -22,6 +22,9 @@ namespace capi {
       icu4x::capi::FixedDecimalGroupingStrategy grouping_strategy;
       bool some_other_config;
     };
+    DIPLOMAT_ASSERT_LAYOUT(FixedDecimalFormatterOptions, 8, 4, 8, 4);
+    DIPLOMAT_ASSERT_OFFSET(FixedDecimalFormatterOptions, grouping_strategy, 0, 0);
+    DIPLOMAT_ASSERT_OFFSET(FixedDecimalFormatterOptions, some_other_config, 4, 4);
     
     typedef struct FixedDecimalFormatterOptions_option {union { FixedDecimalFormatterOptions ok; }; bool is_ok; } FixedDecimalFormatterOptions_option;
 } // namespace capi
@@ -35,6 +38,9 @@ struct FixedDecimalFormatterOptions {
 
   inline static icu4x::FixedDecimalFormatterOptions default_();
 
+  inline FixedDecimalFormatterOptions(icu4x::FixedDecimalGroupingStrategy grouping_strategy, bool some_other_config)
+    : grouping_strategy(std::move(grouping_strategy)), some_other_config(std::move(some_other_config)) {}
+  inline FixedDecimalFormatterOptions();
   inline icu4x::capi::FixedDecimalFormatterOptions AsFFI() const;
   inline static icu4x::FixedDecimalFormatterOptions FromFFI(icu4x::capi::FixedDecimalFormatterOptions c_struct);
 };
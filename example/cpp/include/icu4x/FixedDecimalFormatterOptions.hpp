@@ -30,6 +30,10 @@ inline icu4x::FixedDecimalFormatterOptions icu4x::FixedDecimalFormatterOptions::
 }
 
 
+inline icu4x::FixedDecimalFormatterOptions::FixedDecimalFormatterOptions()
+  : icu4x::FixedDecimalFormatterOptions(icu4x::FixedDecimalFormatterOptions::default_()) {}
+
+
 inline icu4x::capi::FixedDecimalFormatterOptions icu4x::FixedDecimalFormatterOptions::AsFFI() const {
   return icu4x::capi::FixedDecimalFormatterOptions {
     /* .grouping_strategy = */ grouping_strategy.AsFFI(),
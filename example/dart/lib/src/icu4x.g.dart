@@ -0,0 +1,5 @@
+// generated by diplomat-tool
+
+export 'lib.g.dart' show FixedDecimalFormatterOptions, DataProvider, FixedDecimalFormatter, FixedDecimal, Locale, FixedDecimalGroupingStrategy;
+
+
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
 
-part of 'lib.g.dart';
+part of '../lib.g.dart';
 
 /// An  data provider, capable of loading  data keys from some source.
 ///
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
 
-part of 'lib.g.dart';
+part of '../lib.g.dart';
 
 /// See the [Rust documentation for `FixedDecimal`](https://docs.rs/fixed_decimal/latest/fixed_decimal/struct.FixedDecimal.html) for more information.
 final class FixedDecimal implements ffi.Finalizable {
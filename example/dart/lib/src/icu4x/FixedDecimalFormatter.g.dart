@@ -1,6 +1,6 @@
 // generated by diplomat-tool
 
-part of 'lib.g.dart';
+part of '../lib.g.dart';
 
 /// An  Fixed Decimal Format object, capable of formatting a [`FixedDecimal`] as a string.
 ///
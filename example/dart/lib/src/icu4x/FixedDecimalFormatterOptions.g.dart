@@ -1,6 +1,6 @@
 // generated by diplomat-tool
 
-part of 'lib.g.dart';
+part of '../lib.g.dart';
 
 final class _FixedDecimalFormatterOptionsFfi extends ffi.Struct {
   @ffi.Int32()
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
 
-part of 'lib.g.dart';
+part of '../lib.g.dart';
 
 /// An  Locale, capable of representing strings like `"en-US"`.
 ///
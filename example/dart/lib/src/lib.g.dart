@@ -7,15 +7,16 @@ import 'dart:typed_data';
 // ignore: unused_shown_name
 import 'dart:core' show int, double, bool, String, Object, override;
 import 'dart:convert';
+import 'dart:developer' as developer;
 import 'dart:ffi' as ffi;
 import 'package:ffi/ffi.dart' as ffi2 show Arena, calloc;
 import 'package:meta/meta.dart' as meta;
-part 'DataProvider.g.dart';
-part 'FixedDecimal.g.dart';
-part 'FixedDecimalFormatter.g.dart';
-part 'FixedDecimalFormatterOptions.g.dart';
-part 'FixedDecimalGroupingStrategy.g.dart';
-part 'Locale.g.dart';
+part 'icu4x/DataProvider.g.dart';
+part 'icu4x/FixedDecimal.g.dart';
+part 'icu4x/FixedDecimalFormatter.g.dart';
+part 'icu4x/FixedDecimalFormatterOptions.g.dart';
+part 'icu4x/FixedDecimalGroupingStrategy.g.dart';
+part 'icu4x/Locale.g.dart';
 
 /// A [Rune] is a Unicode code point, such as `a`, or `💡`.
 /// 
@@ -40,6 +41,11 @@ final _nopFree = core.Finalizer((nothing) => {});
 // ignore: unused_element
 final _rustFree = core.Finalizer((({ffi.Pointer<ffi.Void> pointer, int bytes, int align}) record) => _diplomat_free(record.pointer, record.bytes, record.align));
 
+// Owns the memory of `'static` slices passed to Rust. Rust may hold on to these for
+// the rest of the program, so this arena is never released.
+// ignore: unused_element
+final _staticArena = ffi2.Arena();
+
 // ignore: unused_element
 final class _RustAlloc implements ffi.Allocator {
   @override
@@ -82,6 +88,95 @@ class _FinalizedArena {
   }
 }
 
+/// The level of a log record emitted by the native library, from the least to the most verbose.
+enum DiplomatLogLevel { off, error, warn, info, debug, trace }
+
+typedef _LogSinkNative = ffi.Void Function(ffi.Uint8, ffi.Pointer<ffi.Uint8>, ffi.Size, ffi.Pointer<ffi.Uint8>, ffi.Size);
+
+ffi.NativeCallable<_LogSinkNative>? _logSink;
+
+/// Routes the `log` records of the native library up to [maxLevel] to [sink], replacing the previous
+/// sink. Passing `null` stops forwarding records.
+///
+/// This requires the library to be built with the `log` feature of `diplomat-runtime`.
+///
+/// The sink is called synchronously, so the library must only log on the thread of the isolate that
+/// registered it, which is the case for records logged while calling into the library.
+void setLogSink(void Function(DiplomatLogLevel level, String target, String message)? sink, {DiplomatLogLevel maxLevel = DiplomatLogLevel.info}) {
+  final old = _logSink;
+  _logSink = sink == null ? null : ffi.NativeCallable<_LogSinkNative>.isolateLocal(
+    (int level, ffi.Pointer<ffi.Uint8> target, int targetLen, ffi.Pointer<ffi.Uint8> message, int messageLen) {
+      sink(
+        DiplomatLogLevel.values[level],
+        Utf8Decoder(allowMalformed: true).convert(target.asTypedList(targetLen)),
+        Utf8Decoder(allowMalformed: true).convert(message.asTypedList(messageLen)),
+      );
+    },
+  );
+  _diplomat_log_set_sink(_logSink?.nativeFunction ?? ffi.nullptr, maxLevel.index);
+  old?.close();
+}
+
+/// Routes the `log` records of the native library up to [maxLevel] to `dart:developer`'s [developer.log],
+/// named after their target. See [setLogSink].
+void useDeveloperLog({DiplomatLogLevel maxLevel = DiplomatLogLevel.info}) {
+  setLogSink((level, target, message) {
+    // The values of `package:logging`'s levels.
+    final value = switch (level) {
+      DiplomatLogLevel.off => 2000,
+      DiplomatLogLevel.error => 1000,
+      DiplomatLogLevel.warn => 900,
+      DiplomatLogLevel.info => 800,
+      DiplomatLogLevel.debug => 500,
+      DiplomatLogLevel.trace => 300,
+    };
+    developer.log(message, name: target, level: value);
+  }, maxLevel: maxLevel);
+}
+
+/// Sets the most verbose level of the native library's log records that are forwarded. See [setLogSink].
+void setLogLevel(DiplomatLogLevel level) {
+  _diplomat_log_set_max_level(level.index);
+}
+
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.NativeFunction<_LogSinkNative>>, ffi.Uint8)>(symbol: 'diplomat_log_set_sink')
+// ignore: non_constant_identifier_names
+external void _diplomat_log_set_sink(ffi.Pointer<ffi.NativeFunction<_LogSinkNative>> sink, int maxLevel);
+
+@ffi.Native<ffi.Void Function(ffi.Uint8)>(symbol: 'diplomat_log_set_max_level', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_log_set_max_level(int level);
+
+/// A report of the objects of the native library that haven't been freed, with one `<name>: <count>`
+/// line per kind of object. Empty if everything was freed.
+///
+/// Requires the library to be built with the `leak-check` feature of `diplomat-runtime`. Objects are
+/// freed by finalizers once they are garbage collected, so this is most useful after disposing arenas.
+String leakReport() {
+  if (_diplomat_leak_count() == 0) {
+    return '';
+  }
+  final write = _Write();
+  _diplomat_leak_report(write._ffi);
+  return write.finalize();
+}
+
+/// Throws a [StateError] listing the objects of the native library that haven't been freed. See [leakReport].
+void assertNoLeaks() {
+  final report = leakReport();
+  if (report.isNotEmpty) {
+    throw StateError('Leaked objects:\n$report');
+  }
+}
+
+@ffi.Native<ffi.Size Function()>(symbol: 'diplomat_leak_count', isLeaf: true)
+// ignore: non_constant_identifier_names
+external int _diplomat_leak_count();
+
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_leak_report', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_leak_report(ffi.Pointer<ffi.Opaque> write);
+
 final class _ResultOpaqueVoidUnion extends ffi.Union {
   external ffi.Pointer<ffi.Opaque> ok;
 
@@ -152,9 +247,11 @@ final class _SliceUtf8 extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  String _toDart(core.List<Object> lifetimeEdges) {
+  String _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
     final r = Utf8Decoder().convert(_data.asTypedList(_length));
-    if (lifetimeEdges.isEmpty) {
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       _diplomat_free(_data.cast(), _length, 1);
     } else {
       // Lifetime edges will be cleaned up
@@ -174,6 +271,45 @@ extension on String {
   }
 }
 
+/// Checks that the structs and their fields are laid out like the ones the Rust library was built with,
+/// throwing a [core.StateError] listing the ones that aren't.
+@meta.visibleForTesting
+void checkStructLayouts() {
+  final is64Bit = ffi.sizeOf<ffi.Pointer>() == 8;
+  final mismatches = <String>[];
+  void check(String name, int size, int size32, int size64) {
+    final expected = is64Bit ? size64 : size32;
+    if (size != expected) {
+      mismatches.add('$name is $size bytes instead of $expected');
+    }
+  }
+  void checkOffset(String name, int offset, int offset32, int offset64) {
+    final expected = is64Bit ? offset64 : offset32;
+    if (offset != expected) {
+      mismatches.add('$name is at offset $offset instead of $expected');
+    }
+  }
+  // Finds a field by copying it from a struct of ones into a zeroed one
+  int offsetOf(int size, void Function(ffi.Pointer<ffi.Uint8> dst, ffi.Pointer<ffi.Uint8> src) copyField) {
+    final src = ffi2.calloc<ffi.Uint8>(size);
+    final dst = ffi2.calloc<ffi.Uint8>(size);
+    try {
+      src.asTypedList(size).fillRange(0, size, 1);
+      copyField(dst, src);
+      return dst.asTypedList(size).indexWhere((byte) => byte != 0);
+    } finally {
+      ffi2.calloc.free(src);
+      ffi2.calloc.free(dst);
+    }
+  }
+  check('FixedDecimalFormatterOptions', ffi.sizeOf<_FixedDecimalFormatterOptionsFfi>(), 8, 8);
+  checkOffset('FixedDecimalFormatterOptions.groupingStrategy', offsetOf(ffi.sizeOf<_FixedDecimalFormatterOptionsFfi>(), (dst, src) => dst.cast<_FixedDecimalFormatterOptionsFfi>().ref.groupingStrategy = src.cast<_FixedDecimalFormatterOptionsFfi>().ref.groupingStrategy), 0, 0);
+  checkOffset('FixedDecimalFormatterOptions.someOtherConfig', offsetOf(ffi.sizeOf<_FixedDecimalFormatterOptionsFfi>(), (dst, src) => dst.cast<_FixedDecimalFormatterOptionsFfi>().ref.someOtherConfig = src.cast<_FixedDecimalFormatterOptionsFfi>().ref.someOtherConfig), 4, 4);
+  if (mismatches.isNotEmpty) {
+    throw core.StateError(mismatches.join('\n'));
+  }
+}
+
 final class _Write {
   final ffi.Pointer<ffi.Opaque> _ffi;
 
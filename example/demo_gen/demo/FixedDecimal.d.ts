@@ -1,2 +1,2 @@
-import { FixedDecimal } from "../../js/lib/api/index.mjs"
+import { icu4x } from "../../js/lib/api/index.mjs"
 export function toString(v: number);
@@ -1,9 +1,9 @@
-import { FixedDecimal } from "../../js/lib/api/index.mjs"
+import { icu4x } from "../../js/lib/api/index.mjs"
 export function toString(v) {
     return (function (...args) { return args[0].toString(...args.slice(1)) }).apply(
         null,
         [
-            FixedDecimal.new_.apply(
+            ((...args) => new icu4x.FixedDecimal(...args)).apply(
                 null,
                 [
                     v
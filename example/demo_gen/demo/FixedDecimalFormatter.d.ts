@@ -1,6 +1,2 @@
-import { DataProvider } from "../../js/lib/api/index.mjs"
-import { FixedDecimal } from "../../js/lib/api/index.mjs"
-import { FixedDecimalFormatter } from "../../js/lib/api/index.mjs"
-import { FixedDecimalFormatterOptions } from "../../js/lib/api/index.mjs"
-import { Locale } from "../../js/lib/api/index.mjs"
-export function formatWrite(name: string, grouping_strategy: FixedDecimalGroupingStrategy, some_other_config: boolean, v: number);
+import { icu4x } from "../../js/lib/api/index.mjs"
+export function formatWrite(name: string, grouping_strategy: icu4x.FixedDecimalGroupingStrategy, some_other_config: boolean, v: number);
@@ -1,28 +1,24 @@
-import { DataProvider } from "../../js/lib/api/index.mjs"
-import { FixedDecimal } from "../../js/lib/api/index.mjs"
-import { FixedDecimalFormatter } from "../../js/lib/api/index.mjs"
-import { FixedDecimalFormatterOptions } from "../../js/lib/api/index.mjs"
-import { Locale } from "../../js/lib/api/index.mjs"
+import { icu4x } from "../../js/lib/api/index.mjs"
 export function formatWrite(name, grouping_strategy, some_other_config, v) {
     return (function (...args) { return args[0].formatWrite(...args.slice(1)) }).apply(
         null,
         [
-            FixedDecimalFormatter.tryNew.apply(
+            icu4x.FixedDecimalFormatter.tryNew.apply(
                 null,
                 [
-                    Locale.new_.apply(
+                    ((...args) => new icu4x.Locale(...args)).apply(
                         null,
                         [
                             name
                         ]
                     ),
-                    DataProvider.newStatic.apply(
+                    icu4x.DataProvider.static.apply(
                         null,
                         [
                         ]
                     ),
                     (function (...args) {
-                        return new FixedDecimalFormatterOptions({
+                        return new icu4x.FixedDecimalFormatterOptions({
                             groupingStrategy: args[0],
                             someOtherConfig: args[1]});
                     }).apply(
@@ -34,7 +30,7 @@ export function formatWrite(name, grouping_strategy, some_other_config, v) {
                     )
                 ]
             ),
-            FixedDecimal.new_.apply(
+            ((...args) => new icu4x.FixedDecimal(...args)).apply(
                 null,
                 [
                     v
@@ -22,8 +22,9 @@ let termini = Object.assign({
             
             {
                 name: "ICU4X Fixed Decimal Grouping Strategy",
-                type: "FixedDecimalGroupingStrategy",
-                typeUse: "enumerator"
+                type: "icu4x.FixedDecimalGroupingStrategy",
+                typeUse: "enumerator",
+                choices: ["Auto","Never","Always","Min2"]
             },
             
             {
@@ -37,7 +38,10 @@ let termini = Object.assign({
                 name: "ICU4XFixedDecimal Value",
                 type: "number",
                 typeUse: "number",
-                defaultValue: "1000"
+                defaultValue: "1000",
+                min: -1000000,
+                max: 1000000,
+                step: 1
             }
             
         ]
@@ -53,7 +57,10 @@ let termini = Object.assign({
                 name: "ICU4XFixedDecimal Value",
                 type: "number",
                 typeUse: "number",
-                defaultValue: "1000"
+                defaultValue: "1000",
+                min: -1000000,
+                max: 1000000,
+                step: 1
             }
             
         ]
@@ -4,38 +4,81 @@ function generateTemplate(className, variable, selector) {
     }
 }
 
+/// Sets the `min`, `max` and `step` constraints from RenderInfo on an `<input>`, so the browser validates them for us.
+function applyConstraints(element, options) {
+    for (let constraint of ["min", "max", "step"]) {
+        if (constraint in options) {
+            element.setAttribute(constraint, options[constraint]);
+        }
+    }
+}
+
+function matchesPattern(options, value) {
+    return !("pattern" in options) || new RegExp(`^(?:${options.pattern})$`, "u").test(value);
+}
+
+function parseNumber(value) {
+    let number = Number(value);
+    if (value === "" || Number.isNaN(number)) {
+        throw new Error(`"${value}" is not a number`);
+    }
+    return number;
+}
+
+function parseCodepoint(value) {
+    let characters = [...value];
+    if (characters.length !== 1) {
+        throw new Error(`"${value}" is not a single character`);
+    }
+    return characters[0].codePointAt(0);
+}
+
+function splitArray(value) {
+    if (Array.isArray(value)) {
+        return value;
+    }
+    return value === "" ? [] : String(value).split(",").map((item) => item.trim());
+}
+
 class ParameterTemplate extends HTMLElement {
     default = null;
 
     inputElement = null;
 
+    #error = null;
+
     static baseTemplate;
     constructor(options = {}, className, selector, defaultValue=null, ...args) {
         super();
+        this.options = options;
         generateTemplate(ParameterTemplate, "baseTemplate", "#parameter");
         generateTemplate(className, "template", selector);
         let baseClone = ParameterTemplate.baseTemplate.cloneNode(true);
 
         let clone = className["template"].cloneNode(true);
 
-        this.initialize(clone, ...args);
+        this.initialize(clone, options, ...args);
 
         this.inputElement = clone.querySelector("*[data-oninput]");
         if (this.inputElement !== null) {
             this.inputElement.addEventListener("input", this.input.bind(this));
+            applyConstraints(this.inputElement, options);
         }
-        
+
         clone.slot = "parameter";
         baseClone.appendChild(clone);
 
         const shadowRoot = this.attachShadow({ mode: "open" });
         shadowRoot.appendChild(baseClone);
 
-        if ("defaultValue" in options) {
-            this.default = options.defaultValue;
-            this.setValue(options.defaultValue);
-        } else if (this.default === null) {
-            this.default = defaultValue;
+        let rawDefault = "defaultValue" in options ? options.defaultValue : defaultValue;
+        if (rawDefault !== null) {
+            this.setValue(rawDefault);
+            try {
+                this.default = this.parse(rawDefault);
+            } catch (e) {
+                this.#error = e.message;
+            }
         }
     }
 
@@ -45,17 +88,42 @@ class ParameterTemplate extends HTMLElement {
         }
     }
 
+    /// Turns the raw value of the input into the value passed to the render terminus. Throws on invalid input.
+    parse(v) {
+        return v;
+    }
+
     getEventValue(event) {
-        return event.target.value;
+        return this.parse(event.target.value);
     }
 
     input(event) {
+        let value;
+        try {
+            value = this.getEventValue(event);
+        } catch (e) {
+            this.#error = e.message;
+            return;
+        }
+        this.#error = null;
+
         this.dispatchEvent(new CustomEvent("parameter-input", {
-            detail: this.getEventValue(event)
+            detail: value
         }));
     }
 
-    initialize(clone) {
+    /// Returns why the current input is invalid, or null if it can be passed to the render terminus.
+    validate() {
+        if (this.#error !== null) {
+            return this.#error;
+        }
+        if (this.inputElement !== null && !this.inputElement.checkValidity()) {
+            return this.inputElement.validationMessage;
+        }
+        return null;
+    }
+
+    initialize(clone, options) {
 
     }
 }
@@ -68,12 +136,16 @@ class BooleanTemplate extends ParameterTemplate {
         super(options, BooleanTemplate, "template#boolean", false);
     }
 
+    parse(v) {
+        return v === true || v === "true";
+    }
+
     getEventValue(event) {
         return event.target.checked;
     }
 
     setValue(v) {
-        this.inputElement.checked = v;
+        this.inputElement.checked = this.parse(v);
     }
 }
 
@@ -81,39 +153,177 @@ customElements.define("terminus-param-boolean", BooleanTemplate);
 
 class NumberTemplate extends ParameterTemplate {
     static template;
-    constructor(options) {
-        super(options, NumberTemplate, "template#number", 0);
+    constructor(options, className = NumberTemplate, defaultValue = 0) {
+        super(options, className, "template#number", defaultValue);
     }
-    
-    getEventValue(event) {
-        return parseFloat(event.target.value);
+
+    parse(v) {
+        return parseNumber(v);
     }
 }
 
 customElements.define("terminus-param-number", NumberTemplate);
 
-class StringTemplate extends ParameterTemplate {
+class BigIntTemplate extends NumberTemplate {
     static template;
     constructor(options) {
-        super(options, StringTemplate, "template#string", "");
+        super(options, BigIntTemplate, 0n);
+    }
+
+    parse(v) {
+        return BigInt(v);
+    }
+}
+
+customElements.define("terminus-param-bigint", BigIntTemplate);
+
+class CodepointTemplate extends ParameterTemplate {
+    static template;
+    constructor(options) {
+        super(options, CodepointTemplate, "template#string", null);
+    }
+
+    parse(v) {
+        return parseCodepoint(v);
+    }
+
+    validate() {
+        return this.default === null && this.inputElement.value === "" ? "A character is required" : super.validate();
+    }
+}
+
+customElements.define("terminus-param-codepoint", CodepointTemplate);
+
+class StringTemplate extends ParameterTemplate {
+    static template;
+    constructor(options, className = StringTemplate, selector = "template#string") {
+        super(options, className, selector, "");
+    }
+
+    validate() {
+        let value = this.inputElement.value;
+        if (!matchesPattern(this.options, value)) {
+            return `"${value}" does not match ${this.options.pattern}`;
+        }
+        return super.validate();
     }
 }
 
 customElements.define("terminus-param-string", StringTemplate);
 
+class MultilineStringTemplate extends StringTemplate {
+    static template;
+    constructor(options) {
+        super(options, MultilineStringTemplate, "template#string-multiline");
+    }
+}
+
+customElements.define("terminus-param-string-multiline", MultilineStringTemplate);
+
 class StringArrayTemplate extends ParameterTemplate {
     static template;
     constructor(options) {
         super(options, StringArrayTemplate, "template#string-array", []);
     }
 
-    getEventValue(event) {
-        return event.target.value.split(",");
+    parse(v) {
+        if (Array.isArray(v)) {
+            return v;
+        }
+        return v === "" ? [] : v.split(",");
+    }
+
+    validate() {
+        for (let item of this.parse(this.inputElement.value)) {
+            if (!matchesPattern(this.options, item)) {
+                return `"${item}" does not match ${this.options.pattern}`;
+            }
+        }
+        return super.validate();
     }
 }
 
 customElements.define("terminus-param-string-array", StringArrayTemplate);
 
+/// Editor for slices of primitives, with one input per item.
+class ArrayTemplate extends ParameterTemplate {
+    static template;
+    static itemTemplate;
+    constructor(options) {
+        super(options, ArrayTemplate, "template#array", []);
+    }
+
+    // Called from the ParameterTemplate constructor, so we cannot use class fields here.
+    initialize(clone, options) {
+        generateTemplate(ArrayTemplate, "itemTemplate", "template#array-item");
+        this.itemsElement = clone.querySelector("*[data-items]");
+        clone.querySelector("*[data-add]").addEventListener("click", () => {
+            this.addItem(this.options.typeUse === "Array<boolean>" ? false : "");
+            this.input();
+        });
+    }
+
+    addItem(value) {
+        let item = ArrayTemplate.itemTemplate.cloneNode(true).firstElementChild;
+        let input = item.querySelector("input[data-item]");
+        if (this.options.typeUse === "Array<boolean>") {
+            input.type = "checkbox";
+            input.checked = value === true || value === "true";
+        } else {
+            input.type = this.options.typeUse === "Array<codepoint>" ? "text" : "number";
+            applyConstraints(input, this.options);
+            input.value = value;
+        }
+        input.addEventListener("input", () => this.input());
+        item.querySelector("*[data-remove]").addEventListener("click", () => {
+            item.remove();
+            this.input();
+        });
+        this.itemsElement.appendChild(item);
+    }
+
+    setValue(v) {
+        this.itemsElement.replaceChildren();
+        for (let item of splitArray(v)) {
+            this.addItem(item);
+        }
+    }
+
+    parse(v) {
+        let values = splitArray(v).map((item) => {
+            switch (this.options.typeUse) {
+                case "Array<boolean>":
+                    return item === true || item === "true";
+                case "Array<bigint>":
+                    return BigInt(item);
+                case "Array<codepoint>":
+                    return parseCodepoint(item);
+                default:
+                    return parseNumber(item);
+            }
+        });
+        return this.options.typeUse === "Uint8Array" ? Uint8Array.from(values) : values;
+    }
+
+    getEventValue() {
+        return this.parse([...this.itemsElement.querySelectorAll("input[data-item]")].map(
+            (input) => input.type === "checkbox" ? input.checked : input.value
+        ));
+    }
+
+    validate() {
+        for (let input of this.itemsElement.querySelectorAll("input[data-item]")) {
+            if (!input.checkValidity()) {
+                return input.validationMessage;
+            }
+        }
+        return super.validate();
+    }
+
+}
+
+customElements.define("terminus-param-array", ArrayTemplate);
+
 class EnumOption extends HTMLElement {
     static template;
     constructor(optionText) {
@@ -122,36 +332,54 @@ class EnumOption extends HTMLElement {
         let clone = EnumOption.template.cloneNode(true);
 
         clone.querySelector("slot[name='option-text']").parentElement.innerText = optionText;
-        
+
         this.append(...clone.children);
     }
 }
 
 customElements.define("terminus-enum-option", EnumOption);
 
+/// Dropdown for string parameters with `#[diplomat::demo(input(choices = [...]))]`.
+class ChoiceTemplate extends ParameterTemplate {
+    static template;
+    constructor(options) {
+        super(options, ChoiceTemplate, "template#enum", options.choices[0]);
+    }
+
+    initialize(clone, options) {
+        let select = clone.querySelector("*[data-options]");
+        for (let choice of options.choices) {
+            select.append(...(new EnumOption(choice)).children);
+        }
+    }
+}
+
+customElements.define("terminus-param-choice", ChoiceTemplate);
+
 class EnumTemplate extends ParameterTemplate {
     static template;
 
-    #enumType;
     constructor(options, enumType) {
         super(options, EnumTemplate, "template#enum", null, enumType);
-        this.#enumType = enumType;
     }
 
-    initialize(clone, enumType) {
-        let options = clone.querySelector("*[data-options]");
-        
-        for (let entry of enumType.getAllEntries()) {
-            
+    // Called from the ParameterTemplate constructor, so we cannot use class fields here.
+    initialize(clone, options, enumType) {
+        this.enumType = enumType;
+
+        let select = clone.querySelector("*[data-options]");
+        let choices = options.choices ?? [...enumType.getAllEntries()].map((entry) => entry[0]);
+
+        for (let choice of choices) {
             if (this.default === null) {
-                this.default = entry[0];
+                this.default = enumType[choice];
             }
-            options.append(...(new EnumOption(entry[0])).children);
+            select.append(...(new EnumOption(choice)).children);
         }
     }
 
-    getEventValue(event) {
-        return this.#enumType[event.target.value];
+    parse(v) {
+        return this.enumType[v];
     }
 }
 
@@ -160,10 +388,12 @@ customElements.define("terminus-param-enum", EnumTemplate);
 class TerminusParams extends HTMLElement {
     #params = [];
 
+    #children = [];
+
     constructor(library, evaluateExternal, params){
         super();
 
-        for (var i = 0; i < params.length; i++) {
+        for (let i = 0; i < params.length; i++) {
             let param = params[i];
             let paramName = document.createElement("span");
             paramName.slot = "param-name";
@@ -173,38 +403,54 @@ class TerminusParams extends HTMLElement {
 
             switch (param.typeUse) {
                 case "string":
-                    newChild = new StringTemplate(param);
-                    this.#params[i] = "";
+                    if ("choices" in param) {
+                        newChild = new ChoiceTemplate(param);
+                    } else if (param.multiline) {
+                        newChild = new MultilineStringTemplate(param);
+                    } else {
+                        newChild = new StringTemplate(param);
+                    }
                     break;
                 case "boolean":
                     newChild = new BooleanTemplate(param);
-                    this.#params[i] = false;
                     break;
                 case "number":
                     newChild = new NumberTemplate(param);
-                    this.#params[i] = 0;
+                    break;
+                case "bigint":
+                    newChild = new BigIntTemplate(param);
+                    break;
+                case "codepoint":
+                    newChild = new CodepointTemplate(param);
                     break;
                 case "Array<string>":
                     newChild = new StringArrayTemplate(param);
-                    this.#params[i] = [];
+                    break;
+                case "Array<number>":
+                case "Array<bigint>":
+                case "Array<boolean>":
+                case "Array<codepoint>":
+                case "Uint8Array":
+                    newChild = new ArrayTemplate(param);
                     break;
                 case "enumerator":
-                    newChild = new EnumTemplate(param, library[param.type]);
-                    this.#params[i] = newChild.default
+                    // Namespaced types are nested, e.g. `ns.MyEnum`.
+                    newChild = new EnumTemplate(param, param.type.split(".").reduce((module, name) => module[name], library));
                     break;
                 case "external":
                     let updateParamEvent = (value) => {
                         this.#params[i] = value;
                     };
                     evaluateExternal(param, updateParamEvent);
-                    break;
+                    continue;
                 default:
                     console.error("Unrecognized parameter: ", param);
-                    break;
+                    continue;
             }
 
             newChild.addEventListener("parameter-input", this.input.bind(this, i));
             this.#params[i] = newChild.default;
+            this.#children.push([param, newChild]);
 
             newChild.appendChild(paramName);
             this.appendChild(newChild);
@@ -218,6 +464,18 @@ class TerminusParams extends HTMLElement {
     get paramArray() {
         return this.#params;
     }
+
+    /// Messages for every parameter that can't be passed to the render terminus as-is.
+    validate() {
+        let errors = [];
+        for (let [param, child] of this.#children) {
+            let error = child.validate();
+            if (error !== null) {
+                errors.push(`${param.name}: ${error}`);
+            }
+        }
+        return errors;
+    }
 }
 
 customElements.define("terminus-params", TerminusParams);
@@ -259,6 +517,13 @@ export class TerminusRender extends HTMLElement {
     }
 
     submit() {
+        // Invalid input would otherwise only fail once it reaches Rust, usually with a panic.
+        let errors = this.#parameters.validate();
+        if (errors.length > 0) {
+            this.#output.innerText = errors.join("\n");
+            return;
+        }
+
         try {
             this.#output.innerText = this.#func(...this.#parameters.paramArray);
         } catch(e) {
@@ -268,4 +533,4 @@ export class TerminusRender extends HTMLElement {
     }
 }
 
-customElements.define("terminus-render", TerminusRender);
\ No newline at end of file
+customElements.define("terminus-render", TerminusRender);
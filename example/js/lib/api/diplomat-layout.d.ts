@@ -0,0 +1,7 @@
+/**
+ * Checks that the structs are laid out like the ones the WebAssembly module was built with,
+ * which `#[diplomat::bridge]` records in its `diplomat_layout` custom section.
+ *
+ * Throws an `Error` listing the structs that aren't.
+ */
+export function checkStructLayouts(): void;
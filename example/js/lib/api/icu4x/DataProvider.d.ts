@@ -1,5 +1,5 @@
 // generated by diplomat-tool
-import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
 
 
 /** An  data provider, capable of loading  data keys from some source.
@@ -11,7 +11,7 @@ export class DataProvider {
 
     get ffiValue(): pointer;
 
-    static newStatic(): DataProvider;
+    static static(): DataProvider;
 
     static returnsResult(): boolean;
 }
\ No newline at end of file
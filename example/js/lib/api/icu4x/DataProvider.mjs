@@ -1,6 +1,6 @@
 // generated by diplomat-tool
-import wasm from "./diplomat-wasm.mjs";
-import * as diplomatRuntime from "./diplomat-runtime.mjs";
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
 
 
 /** An  data provider, capable of loading  data keys from some source.
@@ -38,7 +38,7 @@ export class DataProvider {
         return this.#ptr;
     }
 
-    static newStatic() {
+    static static() {
         const result = wasm.icu4x_DataProvider_new_static_mv1();
     
         try {
@@ -1,5 +1,5 @@
 // generated by diplomat-tool
-import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
 
 
 /** See the [Rust documentation for `FixedDecimal`](https://docs.rs/fixed_decimal/latest/fixed_decimal/struct.FixedDecimal.html) for more information.
@@ -9,7 +9,7 @@ export class FixedDecimal {
 
     get ffiValue(): pointer;
 
-    static new_(v: number): FixedDecimal;
+    constructor(v: number);
 
     multiplyPow10(power: number): void;
 
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
-import wasm from "./diplomat-wasm.mjs";
-import * as diplomatRuntime from "./diplomat-runtime.mjs";
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
 
 
 /** See the [Rust documentation for `FixedDecimal`](https://docs.rs/fixed_decimal/latest/fixed_decimal/struct.FixedDecimal.html) for more information.
@@ -19,8 +19,7 @@ export class FixedDecimal {
     
     constructor(symbol, ptr, selfEdge) {
         if (symbol !== diplomatRuntime.internalConstructor) {
-            console.error("FixedDecimal is an Opaque type. You cannot call its constructor.");
-            return;
+            return this.#defaultConstructor(...arguments);
         }
         
         this.#ptr = ptr;
@@ -36,7 +35,7 @@ export class FixedDecimal {
         return this.#ptr;
     }
 
-    static new_(v) {
+    #defaultConstructor(v) {
         const result = wasm.icu4x_FixedDecimal_new_mv1(v);
     
         try {
@@ -1,9 +1,9 @@
 // generated by diplomat-tool
-import type { DataProvider } from "./DataProvider"
-import type { FixedDecimal } from "./FixedDecimal"
-import type { FixedDecimalFormatterOptions } from "./FixedDecimalFormatterOptions"
-import type { Locale } from "./Locale"
-import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+import type { DataProvider } from "../icu4x/DataProvider"
+import type { FixedDecimal } from "../icu4x/FixedDecimal"
+import type { FixedDecimalFormatterOptions } from "../icu4x/FixedDecimalFormatterOptions"
+import type { Locale } from "../icu4x/Locale"
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
 
 
 /** An  Fixed Decimal Format object, capable of formatting a [`FixedDecimal`] as a string.
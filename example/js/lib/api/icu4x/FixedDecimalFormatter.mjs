@@ -1,10 +1,10 @@
 // generated by diplomat-tool
-import { DataProvider } from "./DataProvider.mjs"
-import { FixedDecimal } from "./FixedDecimal.mjs"
-import { FixedDecimalFormatterOptions } from "./FixedDecimalFormatterOptions.mjs"
-import { Locale } from "./Locale.mjs"
-import wasm from "./diplomat-wasm.mjs";
-import * as diplomatRuntime from "./diplomat-runtime.mjs";
+import { DataProvider } from "../icu4x/DataProvider.mjs"
+import { FixedDecimal } from "../icu4x/FixedDecimal.mjs"
+import { FixedDecimalFormatterOptions } from "../icu4x/FixedDecimalFormatterOptions.mjs"
+import { Locale } from "../icu4x/Locale.mjs"
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
 
 
 /** An  Fixed Decimal Format object, capable of formatting a [`FixedDecimal`] as a string.
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
-import type { FixedDecimalGroupingStrategy } from "./FixedDecimalGroupingStrategy"
-import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+import type { FixedDecimalGroupingStrategy } from "../icu4x/FixedDecimalGroupingStrategy"
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
 
 type FixedDecimalFormatterOptions_Obj = {
     groupingStrategy: FixedDecimalGroupingStrategy;
@@ -14,7 +14,7 @@ export class FixedDecimalFormatterOptions {
 
     get someOtherConfig() : boolean;
     set someOtherConfig(value: boolean); 
-    constructor(structObj : FixedDecimalFormatterOptions_Obj);
+    static fromFields(structObj : FixedDecimalFormatterOptions_Obj) : FixedDecimalFormatterOptions;
 
-    static default_(): FixedDecimalFormatterOptions;
+    constructor();
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 // generated by diplomat-tool
-import { FixedDecimalGroupingStrategy } from "./FixedDecimalGroupingStrategy.mjs"
-import wasm from "./diplomat-wasm.mjs";
-import * as diplomatRuntime from "./diplomat-runtime.mjs";
+import { FixedDecimalGroupingStrategy } from "../icu4x/FixedDecimalGroupingStrategy.mjs"
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
 
 export class FixedDecimalFormatterOptions {
 
@@ -20,7 +20,15 @@ export class FixedDecimalFormatterOptions {
     set someOtherConfig(value) {
         this.#someOtherConfig = value;
     }
-    constructor(structObj) {
+    // `new FixedDecimalFormatterOptions(...)` calls the Rust constructor, so construction from fields goes through here.
+    static fromFields(structObj) {
+        return new FixedDecimalFormatterOptions(structObj, diplomatRuntime.exposeConstructor);
+    }
+    constructor(structObj, internalConstructor) {
+        if (internalConstructor !== diplomatRuntime.internalConstructor && internalConstructor !== diplomatRuntime.exposeConstructor) {
+            return this.#defaultConstructor(...arguments);
+        }
+
         if (typeof structObj !== "object") {
             throw new Error("FixedDecimalFormatterOptions's constructor takes an object of FixedDecimalFormatterOptions's fields.");
         }
@@ -82,7 +90,7 @@ export class FixedDecimalFormatterOptions {
         return new FixedDecimalFormatterOptions(structObj, internalConstructor);
     }
 
-    static default_() {
+    #defaultConstructor() {
         const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 8, 4, false);
         
         const result = wasm.icu4x_FixedDecimalFormatterOptions_default_mv1(diplomatReceive.buffer);
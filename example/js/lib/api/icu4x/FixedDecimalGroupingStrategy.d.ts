@@ -1,5 +1,5 @@
 // generated by diplomat-tool
-import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
 
 // Base enumerator definition
 export class FixedDecimalGroupingStrategy {
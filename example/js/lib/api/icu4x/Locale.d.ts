@@ -1,5 +1,5 @@
 // generated by diplomat-tool
-import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
 
 
 /** An  Locale, capable of representing strings like `"en-US"`.
@@ -11,5 +11,5 @@ export class Locale {
 
     get ffiValue(): pointer;
 
-    static new_(name: string): Locale;
+    constructor(name: string);
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
-import wasm from "./diplomat-wasm.mjs";
-import * as diplomatRuntime from "./diplomat-runtime.mjs";
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
 
 
 /** An  Locale, capable of representing strings like `"en-US"`.
@@ -21,8 +21,7 @@ export class Locale {
     
     constructor(symbol, ptr, selfEdge) {
         if (symbol !== diplomatRuntime.internalConstructor) {
-            console.error("Locale is an Opaque type. You cannot call its constructor.");
-            return;
+            return this.#defaultConstructor(...arguments);
         }
         
         this.#ptr = ptr;
@@ -38,7 +37,7 @@ export class Locale {
         return this.#ptr;
     }
 
-    static new_(name) {
+    #defaultConstructor(name) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const nameSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.str8(wasm, name));
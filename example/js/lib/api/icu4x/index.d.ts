@@ -0,0 +1,13 @@
+
+
+export { FixedDecimalFormatterOptions } from "./FixedDecimalFormatterOptions"
+
+export { DataProvider } from "./DataProvider"
+
+export { FixedDecimalFormatter } from "./FixedDecimalFormatter"
+
+export { FixedDecimal } from "./FixedDecimal"
+
+export { Locale } from "./Locale"
+
+export { FixedDecimalGroupingStrategy } from "./FixedDecimalGroupingStrategy"
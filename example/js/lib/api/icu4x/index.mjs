@@ -0,0 +1,13 @@
+
+
+export { FixedDecimalFormatterOptions } from "./FixedDecimalFormatterOptions.mjs"
+
+export { DataProvider } from "./DataProvider.mjs"
+
+export { FixedDecimalFormatter } from "./FixedDecimalFormatter.mjs"
+
+export { FixedDecimal } from "./FixedDecimal.mjs"
+
+export { Locale } from "./Locale.mjs"
+
+export { FixedDecimalGroupingStrategy } from "./FixedDecimalGroupingStrategy.mjs"
@@ -1,15 +1,6 @@
 
-export { codepoint } from './diplomat-runtime';
+export { codepoint, RustPanicError, assertNoLeaks } from './diplomat-runtime';
+export { checkStructLayouts } from './diplomat-layout';
 
 
-export { FixedDecimalFormatterOptions } from "./FixedDecimalFormatterOptions"
-
-export { DataProvider } from "./DataProvider"
-
-export { FixedDecimalFormatter } from "./FixedDecimalFormatter"
-
-export { FixedDecimal } from "./FixedDecimal"
-
-export { Locale } from "./Locale"
-
-export { FixedDecimalGroupingStrategy } from "./FixedDecimalGroupingStrategy"
+export * as icu4x from "./icu4x/index"
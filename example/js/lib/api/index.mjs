@@ -1,13 +1,6 @@
 
+export { RustPanicError, assertNoLeaks } from './diplomat-runtime.mjs';
+export { checkStructLayouts } from './diplomat-layout.mjs';
 
-export { FixedDecimalFormatterOptions } from "./FixedDecimalFormatterOptions.mjs"
 
-export { DataProvider } from "./DataProvider.mjs"
-
-export { FixedDecimalFormatter } from "./FixedDecimalFormatter.mjs"
-
-export { FixedDecimal } from "./FixedDecimal.mjs"
-
-export { Locale } from "./Locale.mjs"
-
-export { FixedDecimalGroupingStrategy } from "./FixedDecimalGroupingStrategy.mjs"
+export * as icu4x from "./icu4x/index.mjs"
@@ -1,10 +1,10 @@
 import test from "ava";
 
 import { FixedDecimalDemo, FixedDecimalFormatterDemo } from "demo/demo"; 
-import { FixedDecimalGroupingStrategy } from "demo";
+import { icu4x } from "demo";
 
 test("Format write returns a formatted decimal.", t => {
-	t.is("1,000", FixedDecimalFormatterDemo.formatWrite("en", FixedDecimalGroupingStrategy.Auto, false, 1000));
+	t.is("1,000", FixedDecimalFormatterDemo.formatWrite("en", icu4x.FixedDecimalGroupingStrategy.Auto, false, 1000));
 });
 
 test("toString returns the proper string.", t => {
@@ -1,5 +1,6 @@
 package dev.diplomattest.somelib;
 
+import com.sun.jna.Callback
 import com.sun.jna.JNIEnv
 import com.sun.jna.Library
 import com.sun.jna.Memory
@@ -8,6 +9,7 @@ import com.sun.jna.Pointer
 import com.sun.jna.Structure
 import com.sun.jna.Union
 import java.util.Collections
+import dev.diplomattest.somelib.icu4x.*
 
 
 // We spawn a cleaner for the library which is responsible for cleaning opaque types.
@@ -19,6 +21,10 @@ interface DiplomatWriteLib: Library {
     fun diplomat_buffer_write_get_bytes(diplomatWrite: Pointer): Pointer
     fun diplomat_buffer_write_len(diplomatWrite: Pointer): Long
     fun diplomat_buffer_write_destroy(diplomatWrite: Pointer)
+    fun diplomat_buffer_write16_create(size: Long): Pointer
+    fun diplomat_buffer_write16_get_units(diplomatWrite: Pointer): Pointer
+    fun diplomat_buffer_write16_len(diplomatWrite: Pointer): Long
+    fun diplomat_buffer_write16_destroy(diplomatWrite: Pointer)
 }
 
 object DW {
@@ -39,6 +45,20 @@ object DW {
             lib.diplomat_buffer_write_destroy(write);
         }
     }
+
+    fun writeToString16 (write: Pointer): String {
+        try {
+            val pointer = lib.diplomat_buffer_write16_get_units(write)
+            if (pointer == null) {
+                throw OutOfMemoryError();
+            }
+            val len = lib.diplomat_buffer_write16_len(write)
+            val chars = pointer.getCharArray(0, len.toInt())
+            return String(chars);
+        } finally {
+            lib.diplomat_buffer_write16_destroy(write);
+        }
+    }
 }
 
 internal interface DiplomatJVMRuntimeLib: Library {
@@ -61,6 +81,200 @@ internal class DiplomatJVMRuntime {
     }
 }
 
+internal interface DiplomatLogSink: Callback {
+    fun invoke(level: Byte, target: Pointer, targetLen: size_t, message: Pointer, messageLen: size_t)
+}
+
+internal interface DiplomatLogLib: Library {
+    fun diplomat_log_set_sink(sink: DiplomatLogSink?, maxLevel: Byte)
+    fun diplomat_log_set_max_level(level: Byte)
+}
+
+/** The level of a log record, from the least to the most verbose. */
+enum class DiplomatLogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace;
+
+    /** The closest [java.util.logging.Level]. */
+    fun toJavaLevel(): java.util.logging.Level = when (this) {
+        Off -> java.util.logging.Level.OFF
+        Error -> java.util.logging.Level.SEVERE
+        Warn -> java.util.logging.Level.WARNING
+        Info -> java.util.logging.Level.INFO
+        Debug -> java.util.logging.Level.FINE
+        Trace -> java.util.logging.Level.FINEST
+    }
+}
+
+/**
+ * Forwards the `log` records of the native library, if it is built with the `log` feature of `diplomat-runtime`.
+ *
+ * Use [useJavaUtilLogging] to log to [java.util.logging] (which SLF4J can pick up with `jul-to-slf4j`),
+ * or [setSink] to handle records directly.
+ */
+object DiplomatLog {
+    private val lib: DiplomatLogLib = Native.load("somelib", DiplomatLogLib::class.java)
+
+    // JNA only holds a weak reference to callbacks, so the registered sink needs to be kept alive here.
+    @Volatile
+    private var sink: DiplomatLogSink? = null
+
+    /** Route records up to [maxLevel] to [sink], which receives the level, target and message of each record. */
+    fun setSink(maxLevel: DiplomatLogLevel = DiplomatLogLevel.Info, sink: ((DiplomatLogLevel, String, String) -> Unit)?) {
+        val nativeSink = sink?.let {
+            object : DiplomatLogSink {
+                override fun invoke(level: Byte, target: Pointer, targetLen: size_t, message: Pointer, messageLen: size_t) {
+                    it(
+                        DiplomatLogLevel.values()[level.toInt()],
+                        target.getByteArray(0, targetLen.toInt()).decodeToString(),
+                        message.getByteArray(0, messageLen.toInt()).decodeToString(),
+                    )
+                }
+            }
+        }
+        this.sink = nativeSink
+        lib.diplomat_log_set_sink(nativeSink, maxLevel.ordinal.toByte())
+    }
+
+    /** Route records up to [maxLevel] to the [java.util.logging.Logger] named after their target. */
+    fun useJavaUtilLogging(maxLevel: DiplomatLogLevel = DiplomatLogLevel.Info) {
+        setSink(maxLevel) { level, target, message ->
+            java.util.logging.Logger.getLogger(target).log(level.toJavaLevel(), message)
+        }
+    }
+
+    /** Set the most verbose level of records that are forwarded. */
+    fun setMaxLevel(level: DiplomatLogLevel) {
+        lib.diplomat_log_set_max_level(level.ordinal.toByte())
+    }
+}
+
+internal interface DiplomatLeakCheckLib: Library {
+    fun diplomat_leak_count(): size_t
+    fun diplomat_leak_report(write: Pointer)
+}
+
+/**
+ * Checks for objects of the native library that haven't been freed, if it is built with the `leak-check`
+ * feature of `diplomat-runtime`.
+ *
+ * Objects are freed by cleaners once they are garbage collected, so tests should only check for leaks
+ * after the objects they created are unreachable and collected.
+ */
+object DiplomatLeakCheck {
+    private val lib: DiplomatLeakCheckLib = Native.load("somelib", DiplomatLeakCheckLib::class.java)
+
+    /** The number of opaques and runtime allocations that haven't been freed. */
+    fun count(): Long = lib.diplomat_leak_count().toLong()
+
+    /** A report of the objects that haven't been freed, with one `<name>: <count>` line per kind of object. Empty if everything was freed. */
+    fun report(): String {
+        if (count() == 0L) {
+            return ""
+        }
+        val write = DW.lib.diplomat_buffer_write_create(0)
+        lib.diplomat_leak_report(write)
+        return DW.writeToString(write)
+    }
+
+    /** Throws an [IllegalStateException] listing the objects that haven't been freed. */
+    fun assertNoLeaks() {
+        val report = report()
+        check(report.isEmpty()) { "Leaked objects:\n$report" }
+    }
+}
+
+internal interface DiplomatWriteSink: Callback {
+    // Returns a `bool`, which JNA would otherwise pass as an `int`
+    fun invoke(context: Pointer?, chunk: Pointer, len: size_t): Byte
+}
+
+internal interface DiplomatStreamWriteLib: Library {
+    fun diplomat_stream_write_create(context: Pointer?, sink: DiplomatWriteSink, cap: size_t): Pointer
+    fun diplomat_stream_write_destroy(write: Pointer)
+}
+
+/** A `DiplomatWrite` that writes its output to [out] as it is written, used by methods marked `stream`. */
+internal class DiplomatStreamWrite(out: java.io.OutputStream) {
+    private var exception: java.io.IOException? = null
+
+    // JNA only holds a weak reference to callbacks, so the sink needs to be kept alive here.
+    private val sink = object : DiplomatWriteSink {
+        override fun invoke(context: Pointer?, chunk: Pointer, len: size_t): Byte {
+            try {
+                out.write(chunk.getByteArray(0, len.toInt()))
+                return 1
+            } catch (e: java.io.IOException) {
+                exception = e
+                return 0
+            }
+        }
+    }
+
+    val write: Pointer = lib.diplomat_stream_write_create(null, sink, size_t(1024))
+
+    /** Frees the write, and rethrows the exception thrown by the output stream, if any. */
+    fun finish() {
+        lib.diplomat_stream_write_destroy(write)
+        exception?.let { throw it }
+    }
+
+    companion object {
+        private val lib: DiplomatStreamWriteLib = Native.load("somelib", DiplomatStreamWriteLib::class.java)
+    }
+}
+
+internal interface DiplomatReadFn: Callback {
+    fun invoke(context: Pointer?, buf: Pointer, len: size_t): ssize_t
+}
+
+internal interface DiplomatReadLib: Library {
+    fun diplomat_read_create(context: Pointer?, read: DiplomatReadFn): Pointer
+    fun diplomat_read_destroy(read: Pointer)
+}
+
+/** A `DiplomatRead` that reads from [input], used by methods taking a `DiplomatRead`. */
+internal class DiplomatReader(input: java.io.InputStream) {
+    private var exception: java.io.IOException? = null
+
+    // JNA only holds a weak reference to callbacks, so the callback needs to be kept alive here.
+    private val readFn = object : DiplomatReadFn {
+        override fun invoke(context: Pointer?, buf: Pointer, len: size_t): ssize_t {
+            if (exception != null) {
+                return ssize_t(-1)
+            }
+            try {
+                val bytes = ByteArray(len.toInt())
+                val read = input.read(bytes)
+                if (read == -1) {
+                    return ssize_t(0)
+                }
+                buf.write(0, bytes, 0, read)
+                return ssize_t(read.toLong())
+            } catch (e: java.io.IOException) {
+                exception = e
+                return ssize_t(-1)
+            }
+        }
+    }
+
+    val read: Pointer = lib.diplomat_read_create(null, readFn)
+
+    /** Frees the reader, and rethrows the exception thrown by the input stream, if any. */
+    fun finish() {
+        lib.diplomat_read_destroy(read)
+        exception?.let { throw it }
+    }
+
+    companion object {
+        private val lib: DiplomatReadLib = Native.load("somelib", DiplomatReadLib::class.java)
+    }
+}
+
 
 internal object PrimitiveArrayTools {
 
@@ -311,6 +525,13 @@ class size_t(val value: Long = 0): com.sun.jna.IntegerType(Native.SIZE_T_SIZE, v
     override fun toShort(): Short = value.toShort()
 }
 
+/** The `isize` returned by the callback of a `DiplomatRead`. */
+internal class ssize_t(val value: Long = 0): com.sun.jna.IntegerType(Native.SIZE_T_SIZE, value, false)  {
+    override fun toByte(): Byte = value.toByte()
+    override fun toChar(): Char = value.toInt().toChar()
+    override fun toShort(): Short = value.toShort()
+}
+
 class Slice: Structure(), Structure.ByValue {
 
     @JvmField var data: Pointer = Pointer(0)// Pointer to const char
@@ -1,9 +1,10 @@
-package dev.diplomattest.somelib;
+package dev.diplomattest.somelib.icu4x;
 import com.sun.jna.Callback
 import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.*
 
 
 internal interface DataProviderLib: Library {
@@ -55,6 +56,12 @@ class DataProvider internal constructor (
                 return Unit.err()
             }
         }
+        
+        /** See the [Rust documentation for `get_static_provider`](https://docs.rs/icu_testdata/latest/icu_testdata/fn.get_static_provider.html) for more information.
+        */
+        fun static_(): DataProvider {
+            return newStatic()
+        }
     }
 
 }
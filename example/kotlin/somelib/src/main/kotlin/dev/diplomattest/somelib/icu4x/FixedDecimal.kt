@@ -1,9 +1,10 @@
-package dev.diplomattest.somelib;
+package dev.diplomattest.somelib.icu4x;
 import com.sun.jna.Callback
 import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.*
 
 
 internal interface FixedDecimalLib: Library {
@@ -42,6 +43,12 @@ class FixedDecimal internal constructor (
             CLEANER.register(returnOpaque, FixedDecimal.FixedDecimalCleaner(handle, FixedDecimal.lib));
             return returnOpaque
         }
+        
+        /** Construct an [`FixedDecimal`] from an integer.
+        */
+        operator fun invoke(v: Int): FixedDecimal {
+            return new_(v)
+        }
     }
     
     /** Multiply the [`FixedDecimal`] by a given power of ten.
@@ -1,9 +1,10 @@
-package dev.diplomattest.somelib;
+package dev.diplomattest.somelib.icu4x;
 import com.sun.jna.Callback
 import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.*
 
 
 internal interface FixedDecimalFormatterLib: Library {
@@ -1,10 +1,11 @@
-package dev.diplomattest.somelib
+package dev.diplomattest.somelib.icu4x
 
 import com.sun.jna.Callback
 import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.*
 
 internal interface FixedDecimalFormatterOptionsLib: Library {
     fun icu4x_FixedDecimalFormatterOptions_default_mv1(): FixedDecimalFormatterOptionsNative
@@ -39,6 +40,10 @@ class FixedDecimalFormatterOptions internal constructor (
             val returnStruct = FixedDecimalFormatterOptions(returnVal)
             return returnStruct
         }
+        
+        operator fun invoke(): FixedDecimalFormatterOptions {
+            return default_()
+        }
     }
 
 }
@@ -1,9 +1,10 @@
-package dev.diplomattest.somelib;
+package dev.diplomattest.somelib.icu4x;
 import com.sun.jna.Callback
 import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.*
 
 
 internal interface LocaleLib: Library {
@@ -44,6 +45,12 @@ class Locale internal constructor (
             nameMem.close()
             return returnOpaque
         }
+        
+        /** Construct an [`Locale`] from a locale identifier represented as a string.
+        */
+        operator fun invoke(name: String): Locale {
+            return new_(name)
+        }
     }
 
 }
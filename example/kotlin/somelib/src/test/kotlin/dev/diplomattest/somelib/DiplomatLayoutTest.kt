@@ -0,0 +1,26 @@
+package dev.diplomattest.somelib
+
+import com.sun.jna.Native
+import com.sun.jna.Structure
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import dev.diplomattest.somelib.icu4x.*
+
+/** Checks that the structs and their fields are laid out like the ones the Rust library was built with. */
+class DiplomatLayoutTest {
+    private fun expected(size32: Int, size64: Int): Int = if (Native.POINTER_SIZE == 8) size64 else size32
+
+    // JNA computes the offsets of the fields but only exposes them to subclasses
+    private val fieldOffset = Structure::class.java.getDeclaredMethod("fieldOffset", String::class.java).apply { isAccessible = true }
+
+    private fun offsetOf(native: Structure, field: String): Int = fieldOffset.invoke(native, field) as Int
+
+    @Test
+    fun testStructLayouts() {
+        FixedDecimalFormatterOptionsNative().let { native ->
+            assertEquals(expected(8, 8), native.size(), "FixedDecimalFormatterOptions")
+            assertEquals(expected(0, 0), offsetOf(native, "groupingStrategy"), "FixedDecimalFormatterOptions.groupingStrategy")
+            assertEquals(expected(4, 4), offsetOf(native, "someOtherConfig"), "FixedDecimalFormatterOptions.someOtherConfig")
+        }
+    }
+}
\ No newline at end of file
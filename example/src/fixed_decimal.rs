@@ -16,7 +16,7 @@ pub mod ffi {
         /// Construct an [`FixedDecimal`] from an integer.
         #[diplomat::attr(auto, constructor)]
         pub fn new(
-            #[diplomat::demo(input(label = "ICU4XFixedDecimal Value", default_value = 1000))]
+            #[diplomat::demo(input(label = "ICU4XFixedDecimal Value", default_value = 1000, min = -1000000, max = 1000000))]
             v: i32,
         ) -> Box<FixedDecimal> {
             Box::new(FixedDecimal(fixed_decimal::FixedDecimal::from(v)))
@@ -1,19 +0,0 @@
-#ifndef AttrOpaque1_D_H
-#define AttrOpaque1_D_H
-
-#include <stdio.h>
-#include <stdint.h>
-#include <stddef.h>
-#include <stdbool.h>
-#include "diplomat_runtime.h"
-
-
-
-
-
-typedef struct AttrOpaque1 AttrOpaque1;
-
-
-
-
-#endif // AttrOpaque1_D_H
@@ -1,25 +0,0 @@
-#ifndef AttrOpaque2_H
-#define AttrOpaque2_H
-
-#include <stdio.h>
-#include <stdint.h>
-#include <stddef.h>
-#include <stdbool.h>
-#include "diplomat_runtime.h"
-
-
-#include "AttrOpaque2.d.h"
-
-
-
-
-
-
-
-void namespace_AttrOpaque2_destroy(AttrOpaque2* self);
-
-
-
-
-
-#endif // AttrOpaque2_H
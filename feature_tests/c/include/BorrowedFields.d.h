@@ -16,6 +16,10 @@ typedef struct BorrowedFields {
   DiplomatStringView b;
   DiplomatStringView c;
 } BorrowedFields;
+DIPLOMAT_ASSERT_LAYOUT(BorrowedFields, 24, 4, 48, 8);
+DIPLOMAT_ASSERT_OFFSET(BorrowedFields, a, 0, 0);
+DIPLOMAT_ASSERT_OFFSET(BorrowedFields, b, 8, 16);
+DIPLOMAT_ASSERT_OFFSET(BorrowedFields, c, 16, 32);
 
 typedef struct BorrowedFields_option {union { BorrowedFields ok; }; bool is_ok; } BorrowedFields_option;
 
@@ -14,6 +14,8 @@
 typedef struct BorrowedFieldsReturning {
   DiplomatStringView bytes;
 } BorrowedFieldsReturning;
+DIPLOMAT_ASSERT_LAYOUT(BorrowedFieldsReturning, 8, 4, 16, 8);
+DIPLOMAT_ASSERT_OFFSET(BorrowedFieldsReturning, bytes, 0, 0);
 
 typedef struct BorrowedFieldsReturning_option {union { BorrowedFieldsReturning ok; }; bool is_ok; } BorrowedFieldsReturning_option;
 
@@ -16,6 +16,10 @@ typedef struct BorrowedFieldsWithBounds {
   DiplomatStringView field_b;
   DiplomatStringView field_c;
 } BorrowedFieldsWithBounds;
+DIPLOMAT_ASSERT_LAYOUT(BorrowedFieldsWithBounds, 24, 4, 48, 8);
+DIPLOMAT_ASSERT_OFFSET(BorrowedFieldsWithBounds, field_a, 0, 0);
+DIPLOMAT_ASSERT_OFFSET(BorrowedFieldsWithBounds, field_b, 8, 16);
+DIPLOMAT_ASSERT_OFFSET(BorrowedFieldsWithBounds, field_c, 16, 32);
 
 typedef struct BorrowedFieldsWithBounds_option {union { BorrowedFieldsWithBounds ok; }; bool is_ok; } BorrowedFieldsWithBounds_option;
 
@@ -0,0 +1,28 @@
+#ifndef BorrowingOptionStruct_D_H
+#define BorrowingOptionStruct_D_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+#include "OptionInputStruct.d.h"
+#include "OptionOpaque.d.h"
+
+
+
+
+typedef struct BorrowingOptionStruct {
+  OptionInputStruct_option input;
+  const OptionOpaque* opaque;
+} BorrowingOptionStruct;
+DIPLOMAT_ASSERT_LAYOUT(BorrowingOptionStruct, 28, 4, 32, 8);
+DIPLOMAT_ASSERT_OFFSET(BorrowingOptionStruct, input, 0, 0);
+DIPLOMAT_ASSERT_OFFSET(BorrowingOptionStruct, opaque, 24, 24);
+
+typedef struct BorrowingOptionStruct_option {union { BorrowingOptionStruct ok; }; bool is_ok; } BorrowingOptionStruct_option;
+
+
+
+#endif // BorrowingOptionStruct_D_H
@@ -0,0 +1,23 @@
+#ifndef BorrowingOptionStruct_H
+#define BorrowingOptionStruct_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+#include "BorrowingOptionStruct.d.h"
+
+
+
+
+
+
+
+
+
+
+
+#endif // BorrowingOptionStruct_H
@@ -15,6 +15,9 @@ typedef struct CallbackTestingStruct {
   int32_t x;
   int32_t y;
 } CallbackTestingStruct;
+DIPLOMAT_ASSERT_LAYOUT(CallbackTestingStruct, 8, 4, 8, 4);
+DIPLOMAT_ASSERT_OFFSET(CallbackTestingStruct, x, 0, 0);
+DIPLOMAT_ASSERT_OFFSET(CallbackTestingStruct, y, 4, 4);
 
 typedef struct CallbackTestingStruct_option {union { CallbackTestingStruct ok; }; bool is_ok; } CallbackTestingStruct_option;
 
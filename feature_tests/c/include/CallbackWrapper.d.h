@@ -14,6 +14,8 @@
 typedef struct CallbackWrapper {
   bool cant_be_empty;
 } CallbackWrapper;
+DIPLOMAT_ASSERT_LAYOUT(CallbackWrapper, 1, 1, 1, 1);
+DIPLOMAT_ASSERT_OFFSET(CallbackWrapper, cant_be_empty, 0, 0);
 
 typedef struct CallbackWrapper_option {union { CallbackWrapper ok; }; bool is_ok; } CallbackWrapper_option;
 
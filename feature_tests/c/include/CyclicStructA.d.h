@@ -15,6 +15,8 @@
 typedef struct CyclicStructA {
   CyclicStructB a;
 } CyclicStructA;
+DIPLOMAT_ASSERT_LAYOUT(CyclicStructA, 1, 1, 1, 1);
+DIPLOMAT_ASSERT_OFFSET(CyclicStructA, a, 0, 0);
 
 typedef struct CyclicStructA_option {union { CyclicStructA ok; }; bool is_ok; } CyclicStructA_option;
 
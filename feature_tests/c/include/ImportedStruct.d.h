@@ -16,6 +16,9 @@ typedef struct ImportedStruct {
   UnimportedEnum foo;
   uint8_t count;
 } ImportedStruct;
+DIPLOMAT_ASSERT_LAYOUT(ImportedStruct, 8, 4, 8, 4);
+DIPLOMAT_ASSERT_OFFSET(ImportedStruct, foo, 0, 0);
+DIPLOMAT_ASSERT_OFFSET(ImportedStruct, count, 4, 4);
 
 typedef struct ImportedStruct_option {union { ImportedStruct ok; }; bool is_ok; } ImportedStruct_option;
 
@@ -31,6 +31,8 @@ void MyString_string_transform(DiplomatStringView foo, DiplomatWrite* write);
 
 DiplomatStringView MyString_borrow(const MyString* self);
 
+DiplomatU16View MyString_static_lookup_table(void);
+
 
 void MyString_destroy(MyString* self);
 
@@ -21,6 +21,14 @@ typedef struct MyStruct {
   char32_t f;
   MyEnum g;
 } MyStruct;
+DIPLOMAT_ASSERT_LAYOUT(MyStruct, 32, 8, 32, 8);
+DIPLOMAT_ASSERT_OFFSET(MyStruct, a, 0, 0);
+DIPLOMAT_ASSERT_OFFSET(MyStruct, b, 1, 1);
+DIPLOMAT_ASSERT_OFFSET(MyStruct, c, 2, 2);
+DIPLOMAT_ASSERT_OFFSET(MyStruct, d, 8, 8);
+DIPLOMAT_ASSERT_OFFSET(MyStruct, e, 16, 16);
+DIPLOMAT_ASSERT_OFFSET(MyStruct, f, 20, 20);
+DIPLOMAT_ASSERT_OFFSET(MyStruct, g, 24, 24);
 
 typedef struct MyStruct_option {union { MyStruct ok; }; bool is_ok; } MyStruct_option;
 
@@ -18,6 +18,10 @@ typedef struct NestedBorrowedFields {
   BorrowedFieldsWithBounds bounds;
   BorrowedFieldsWithBounds bounds2;
 } NestedBorrowedFields;
+DIPLOMAT_ASSERT_LAYOUT(NestedBorrowedFields, 72, 4, 144, 8);
+DIPLOMAT_ASSERT_OFFSET(NestedBorrowedFields, fields, 0, 0);
+DIPLOMAT_ASSERT_OFFSET(NestedBorrowedFields, bounds, 24, 48);
+DIPLOMAT_ASSERT_OFFSET(NestedBorrowedFields, bounds2, 48, 96);
 
 typedef struct NestedBorrowedFields_option {union { NestedBorrowedFields ok; }; bool is_ok; } NestedBorrowedFields_option;
 
@@ -23,8 +23,12 @@ Opaque* Opaque_try_from_utf8(DiplomatStringView input);
 
 Opaque* Opaque_from_str(DiplomatStringView input);
 
+Opaque* Opaque_from_read(DiplomatRead* read);
+
 void Opaque_get_debug_str(const Opaque* self, DiplomatWrite* write);
 
+void Opaque_repeat(const Opaque* self, size_t count, DiplomatWrite* write);
+
 void Opaque_assert_struct(const Opaque* self, MyStruct s);
 
 size_t Opaque_returns_usize(void);
@@ -7,6 +7,7 @@
 #include <stdbool.h>
 #include "diplomat_runtime.h"
 
+#include "BorrowingOptionStruct.d.h"
 #include "OptionEnum.d.h"
 #include "OptionInputStruct.d.h"
 #include "OptionStruct.d.h"
@@ -56,6 +57,10 @@ OptionOpaque_accepts_option_input_struct_result OptionOpaque_accepts_option_inpu
 
 OptionInputStruct OptionOpaque_returns_option_input_struct(void);
 
+BorrowingOptionStruct OptionOpaque_borrowing_option_struct(const OptionOpaque* self);
+
+BorrowingOptionStruct OptionOpaque_borrowing_option_struct_nones(const OptionOpaque* self);
+
 
 void OptionOpaque_destroy(OptionOpaque* self);
 
@@ -15,6 +15,9 @@ typedef struct TraitTestingStruct {
   int32_t x;
   int32_t y;
 } TraitTestingStruct;
+DIPLOMAT_ASSERT_LAYOUT(TraitTestingStruct, 8, 4, 8, 4);
+DIPLOMAT_ASSERT_OFFSET(TraitTestingStruct, x, 0, 0);
+DIPLOMAT_ASSERT_OFFSET(TraitTestingStruct, y, 4, 4);
 
 typedef struct TraitTestingStruct_option {union { TraitTestingStruct ok; }; bool is_ok; } TraitTestingStruct_option;
 
@@ -14,6 +14,8 @@
 typedef struct TraitWrapper {
   bool cant_be_empty;
 } TraitWrapper;
+DIPLOMAT_ASSERT_LAYOUT(TraitWrapper, 1, 1, 1, 1);
+DIPLOMAT_ASSERT_OFFSET(TraitWrapper, cant_be_empty, 0, 0);
 
 typedef struct TraitWrapper_option {union { TraitWrapper ok; }; bool is_ok; } TraitWrapper_option;
 
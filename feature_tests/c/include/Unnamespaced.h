@@ -7,8 +7,8 @@
 #include <stdbool.h>
 #include "diplomat_runtime.h"
 
-#include "AttrEnum.d.h"
-#include "AttrOpaque1.d.h"
+#include "ns_AttrEnum.d.h"
+#include "ns_AttrOpaque1.d.h"
 
 #include "Unnamespaced.d.h"
 
@@ -17,9 +17,9 @@
 
 
 
-Unnamespaced* namespace_Unnamespaced_make(AttrEnum _e);
+Unnamespaced* namespace_Unnamespaced_make(ns_AttrEnum _e);
 
-void namespace_Unnamespaced_use_namespaced(const Unnamespaced* self, const AttrOpaque1* _n);
+void namespace_Unnamespaced_use_namespaced(const Unnamespaced* self, const ns_AttrOpaque1* _n);
 
 
 void namespace_Unnamespaced_destroy(Unnamespaced* self);
@@ -30,8 +30,44 @@ typedef struct DiplomatWrite {
     bool (*grow)(struct DiplomatWrite*, size_t);
 } DiplomatWrite;
 
+// Like `DiplomatWrite`, but `buf` holds UTF-16 code units, and `len` and `cap` count code units.
+typedef struct DiplomatWrite16 {
+    void* context;
+    char16_t* buf;
+    size_t len;
+    size_t cap;
+    bool grow_failed;
+    void (*flush)(struct DiplomatWrite16*);
+    bool (*grow)(struct DiplomatWrite16*, size_t);
+} DiplomatWrite16;
+
+// Reads at most `len` bytes into `buf`, returning the number of bytes read, 0 at the end of the input,
+// or a negative number if reading failed.
+typedef intptr_t (*DiplomatReadFn)(void* context, uint8_t* buf, size_t len);
+
+// A source of bytes that Rust reads from, passed as `DiplomatRead*` to methods.
+typedef struct DiplomatRead {
+    void* context;
+    DiplomatReadFn read;
+} DiplomatRead;
+
 bool diplomat_is_str(const char* buf, size_t len);
 
+// Receives the library's log records, see `diplomat_log_set_sink`.
+// `level` is 1 (error) to 5 (trace). `target` and `message` are UTF-8, and only valid during the call.
+typedef void (*DiplomatLogSink)(uint8_t level, const char* target, size_t target_len, const char* message, size_t message_len);
+
+// Only available if the library is built with the `log` feature of `diplomat-runtime`.
+// `max_level` is 0 (off) to 5 (trace). A null `sink` stops forwarding records.
+void diplomat_log_set_sink(DiplomatLogSink sink, uint8_t max_level);
+void diplomat_log_set_max_level(uint8_t level);
+
+// Only available if the library is built with the `leak-check` feature of `diplomat-runtime`.
+// The number of opaques and runtime allocations that haven't been freed, and a report of them
+// with one `<name>: <count>` line per kind of object.
+size_t diplomat_leak_count(void);
+void diplomat_leak_report(DiplomatWrite* write);
+
 #define MAKE_SLICES(name, c_ty) \
     typedef struct Diplomat##name##View { \
         const c_ty* data; \
@@ -69,6 +105,20 @@ MAKE_SLICES(String16, char16_t)
 MAKE_SLICES(Strings, DiplomatStringView)
 MAKE_SLICES(Strings16, DiplomatString16View)
 
+// The layouts of structs are checked against the ones the Rust library was built with, on targets
+// with the natural alignment for 64-bit types and 32- or 64-bit pointers.
+#ifdef __cplusplus
+#define DIPLOMAT_ALIGNOF(ty) alignof(ty)
+#else
+#define DIPLOMAT_ALIGNOF(ty) _Alignof(ty)
+#endif
+#define DIPLOMAT_LAYOUT_CHECKED (DIPLOMAT_ALIGNOF(uint64_t) == 8 && DIPLOMAT_ALIGNOF(double) == 8 && (sizeof(void*) == 4 || sizeof(void*) == 8))
+#define DIPLOMAT_LAYOUT_VALUE(v32, v64) (sizeof(void*) == 8 ? (v64) : (v32))
+#define DIPLOMAT_ASSERT_LAYOUT(ty, size32, align32, size64, align64) \
+    static_assert(!DIPLOMAT_LAYOUT_CHECKED || (sizeof(ty) == DIPLOMAT_LAYOUT_VALUE(size32, size64) && DIPLOMAT_ALIGNOF(ty) == DIPLOMAT_LAYOUT_VALUE(align32, align64)), "`" #ty "` doesn't have the layout of the Rust struct")
+#define DIPLOMAT_ASSERT_OFFSET(ty, field, offset32, offset64) \
+    static_assert(!DIPLOMAT_LAYOUT_CHECKED || offsetof(ty, field) == DIPLOMAT_LAYOUT_VALUE(offset32, offset64), "`" #ty "." #field "` isn't where the Rust struct has it")
+
 DiplomatWrite diplomat_simple_write(char* buf, size_t buf_size);
 
 DiplomatWrite* diplomat_buffer_write_create(size_t cap);
@@ -76,4 +126,20 @@ char* diplomat_buffer_write_get_bytes(DiplomatWrite* t);
 size_t diplomat_buffer_write_len(DiplomatWrite* t);
 void diplomat_buffer_write_destroy(DiplomatWrite* t);
 
+DiplomatWrite16* diplomat_buffer_write16_create(size_t cap);
+char16_t* diplomat_buffer_write16_get_units(DiplomatWrite16* t);
+size_t diplomat_buffer_write16_len(DiplomatWrite16* t);
+void diplomat_buffer_write16_destroy(DiplomatWrite16* t);
+
+// Receives the output of a `DiplomatWrite` created by `diplomat_stream_write_create`, in chunks of
+// UTF-8 that are only valid during the call and may end in the middle of a character.
+// Returning false stops the write.
+typedef bool (*DiplomatWriteSink)(void* context, const char* chunk, size_t len);
+
+DiplomatWrite* diplomat_stream_write_create(void* context, DiplomatWriteSink sink, size_t cap);
+void diplomat_stream_write_destroy(DiplomatWrite* t);
+
+DiplomatRead* diplomat_read_create(void* context, DiplomatReadFn read);
+void diplomat_read_destroy(DiplomatRead* t);
+
 #endif
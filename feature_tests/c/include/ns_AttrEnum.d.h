@@ -0,0 +1,24 @@
+#ifndef ns_AttrEnum_D_H
+#define ns_AttrEnum_D_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+
+
+
+typedef enum ns_AttrEnum {
+  ns_AttrEnum_A = 0,
+  ns_AttrEnum_B = 1,
+  ns_AttrEnum_C = 2,
+} ns_AttrEnum;
+
+typedef struct ns_AttrEnum_option {union { ns_AttrEnum ok; }; bool is_ok; } ns_AttrEnum_option;
+
+
+
+#endif // ns_AttrEnum_D_H
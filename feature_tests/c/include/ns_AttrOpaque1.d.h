@@ -0,0 +1,19 @@
+#ifndef ns_AttrOpaque1_D_H
+#define ns_AttrOpaque1_D_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+
+
+
+typedef struct ns_AttrOpaque1 ns_AttrOpaque1;
+
+
+
+
+#endif // ns_AttrOpaque1_D_H
@@ -0,0 +1,37 @@
+#ifndef ns_AttrOpaque1_H
+#define ns_AttrOpaque1_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+#include "Unnamespaced.d.h"
+#include "ns_AttrEnum.d.h"
+
+#include "ns_AttrOpaque1.d.h"
+
+
+
+
+
+
+ns_AttrOpaque1* namespace_AttrOpaque1_new(void);
+
+uint8_t namespace_AttrOpaque1_method(const ns_AttrOpaque1* self);
+
+uint8_t renamed_on_abi_only(const ns_AttrOpaque1* self);
+
+void namespace_AttrOpaque1_use_unnamespaced(const ns_AttrOpaque1* self, const Unnamespaced* _un);
+
+void namespace_AttrOpaque1_use_namespaced(const ns_AttrOpaque1* self, ns_AttrEnum _n);
+
+
+void namespace_AttrOpaque1_destroy(ns_AttrOpaque1* self);
+
+
+
+
+
+#endif // ns_AttrOpaque1_H
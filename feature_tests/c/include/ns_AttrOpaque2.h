@@ -0,0 +1,25 @@
+#ifndef ns_AttrOpaque2_H
+#define ns_AttrOpaque2_H
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include "diplomat_runtime.h"
+
+
+#include "ns_AttrOpaque2.d.h"
+
+
+
+
+
+
+
+void namespace_AttrOpaque2_destroy(ns_AttrOpaque2* self);
+
+
+
+
+
+#endif // ns_AttrOpaque2_H
@@ -20,6 +20,10 @@ namespace capi {
       diplomat::capi::DiplomatStringView b;
       diplomat::capi::DiplomatStringView c;
     };
+    DIPLOMAT_ASSERT_LAYOUT(BorrowedFields, 24, 4, 48, 8);
+    DIPLOMAT_ASSERT_OFFSET(BorrowedFields, a, 0, 0);
+    DIPLOMAT_ASSERT_OFFSET(BorrowedFields, b, 8, 16);
+    DIPLOMAT_ASSERT_OFFSET(BorrowedFields, c, 16, 32);
     
     typedef struct BorrowedFields_option {union { BorrowedFields ok; }; bool is_ok; } BorrowedFields_option;
 } // namespace capi
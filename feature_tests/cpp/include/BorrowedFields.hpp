@@ -35,6 +35,7 @@ inline diplomat::result<BorrowedFields, diplomat::Utf8Error> BorrowedFields::fro
 }
 
 
+
 inline diplomat::capi::BorrowedFields BorrowedFields::AsFFI() const {
   return diplomat::capi::BorrowedFields {
     /* .a = */ {a.data(), a.size()},
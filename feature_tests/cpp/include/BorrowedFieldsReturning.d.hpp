@@ -15,6 +15,8 @@ namespace capi {
     struct BorrowedFieldsReturning {
       diplomat::capi::DiplomatStringView bytes;
     };
+    DIPLOMAT_ASSERT_LAYOUT(BorrowedFieldsReturning, 8, 4, 16, 8);
+    DIPLOMAT_ASSERT_OFFSET(BorrowedFieldsReturning, bytes, 0, 0);
     
     typedef struct BorrowedFieldsReturning_option {union { BorrowedFieldsReturning ok; }; bool is_ok; } BorrowedFieldsReturning_option;
 } // namespace capi
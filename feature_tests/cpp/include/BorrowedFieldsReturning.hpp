@@ -22,6 +22,7 @@ namespace capi {
 } // namespace
 
 
+
 inline diplomat::capi::BorrowedFieldsReturning BorrowedFieldsReturning::AsFFI() const {
   return diplomat::capi::BorrowedFieldsReturning {
     /* .bytes = */ {bytes.data(), bytes.size()},
@@ -35,6 +35,7 @@ inline diplomat::result<BorrowedFieldsWithBounds, diplomat::Utf8Error> BorrowedF
 }
 
 
+
 inline diplomat::capi::BorrowedFieldsWithBounds BorrowedFieldsWithBounds::AsFFI() const {
   return diplomat::capi::BorrowedFieldsWithBounds {
     /* .field_a = */ {field_a.data(), field_a.size()},
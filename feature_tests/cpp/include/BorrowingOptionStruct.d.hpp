@@ -0,0 +1,42 @@
+#ifndef BorrowingOptionStruct_D_HPP
+#define BorrowingOptionStruct_D_HPP
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include <memory>
+#include <optional>
+#include "OptionInputStruct.d.hpp"
+#include "diplomat_runtime.hpp"
+
+namespace diplomat::capi { struct OptionOpaque; }
+class OptionOpaque;
+struct OptionInputStruct;
+
+
+namespace diplomat {
+namespace capi {
+    struct BorrowingOptionStruct {
+      diplomat::capi::OptionInputStruct_option input;
+      const diplomat::capi::OptionOpaque* opaque;
+    };
+    DIPLOMAT_ASSERT_LAYOUT(BorrowingOptionStruct, 28, 4, 32, 8);
+    DIPLOMAT_ASSERT_OFFSET(BorrowingOptionStruct, input, 0, 0);
+    DIPLOMAT_ASSERT_OFFSET(BorrowingOptionStruct, opaque, 24, 24);
+    
+    typedef struct BorrowingOptionStruct_option {union { BorrowingOptionStruct ok; }; bool is_ok; } BorrowingOptionStruct_option;
+} // namespace capi
+} // namespace
+
+
+struct BorrowingOptionStruct {
+  std::optional<OptionInputStruct> input;
+  const OptionOpaque* opaque;
+
+  inline diplomat::capi::BorrowingOptionStruct AsFFI() const;
+  inline static BorrowingOptionStruct FromFFI(diplomat::capi::BorrowingOptionStruct c_struct);
+};
+
+
+#endif // BorrowingOptionStruct_D_HPP
@@ -0,0 +1,43 @@
+#ifndef BorrowingOptionStruct_HPP
+#define BorrowingOptionStruct_HPP
+
+#include "BorrowingOptionStruct.d.hpp"
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include <memory>
+#include <optional>
+#include "OptionInputStruct.hpp"
+#include "OptionOpaque.hpp"
+#include "diplomat_runtime.hpp"
+
+
+namespace diplomat {
+namespace capi {
+    extern "C" {
+    
+    
+    } // extern "C"
+} // namespace capi
+} // namespace
+
+
+
+inline diplomat::capi::BorrowingOptionStruct BorrowingOptionStruct::AsFFI() const {
+  return diplomat::capi::BorrowingOptionStruct {
+    /* .input = */ input.has_value() ? (diplomat::capi::OptionInputStruct_option{ { input.value().AsFFI() }, true }) : (diplomat::capi::OptionInputStruct_option{ {}, false }),
+    /* .opaque = */ opaque ? opaque->AsFFI() : nullptr,
+  };
+}
+
+inline BorrowingOptionStruct BorrowingOptionStruct::FromFFI(diplomat::capi::BorrowingOptionStruct c_struct) {
+  return BorrowingOptionStruct {
+    /* .input = */ c_struct.input.is_ok ? std::optional(OptionInputStruct::FromFFI(c_struct.input.ok)) : std::nullopt,
+    /* .opaque = */ OptionOpaque::FromFFI(c_struct.opaque),
+  };
+}
+
+
+#endif // BorrowingOptionStruct_HPP
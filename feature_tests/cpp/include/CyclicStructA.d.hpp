@@ -18,6 +18,8 @@ namespace capi {
     struct CyclicStructA {
       diplomat::capi::CyclicStructB a;
     };
+    DIPLOMAT_ASSERT_LAYOUT(CyclicStructA, 1, 1, 1, 1);
+    DIPLOMAT_ASSERT_OFFSET(CyclicStructA, a, 0, 0);
     
     typedef struct CyclicStructA_option {union { CyclicStructA ok; }; bool is_ok; } CyclicStructA_option;
 } // namespace capi
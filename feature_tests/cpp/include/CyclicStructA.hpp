@@ -30,6 +30,7 @@ inline CyclicStructB CyclicStructA::get_b() {
 }
 
 
+
 inline diplomat::capi::CyclicStructA CyclicStructA::AsFFI() const {
   return diplomat::capi::CyclicStructA {
     /* .a = */ a.AsFFI(),
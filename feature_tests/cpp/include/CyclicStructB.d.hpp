@@ -17,6 +17,8 @@ namespace capi {
     struct CyclicStructB {
       uint8_t field;
     };
+    DIPLOMAT_ASSERT_LAYOUT(CyclicStructB, 1, 1, 1, 1);
+    DIPLOMAT_ASSERT_OFFSET(CyclicStructB, field, 0, 0);
     
     typedef struct CyclicStructB_option {union { CyclicStructB ok; }; bool is_ok; } CyclicStructB_option;
 } // namespace capi
@@ -30,6 +30,7 @@ inline CyclicStructA CyclicStructB::get_a() {
 }
 
 
+
 inline diplomat::capi::CyclicStructB CyclicStructB::AsFFI() const {
   return diplomat::capi::CyclicStructB {
     /* .field = */ field,
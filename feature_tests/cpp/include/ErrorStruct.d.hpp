@@ -16,6 +16,9 @@ namespace capi {
       int32_t i;
       int32_t j;
     };
+    DIPLOMAT_ASSERT_LAYOUT(ErrorStruct, 8, 4, 8, 4);
+    DIPLOMAT_ASSERT_OFFSET(ErrorStruct, i, 0, 0);
+    DIPLOMAT_ASSERT_OFFSET(ErrorStruct, j, 4, 4);
     
     typedef struct ErrorStruct_option {union { ErrorStruct ok; }; bool is_ok; } ErrorStruct_option;
 } // namespace capi
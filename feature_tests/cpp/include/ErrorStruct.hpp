@@ -22,6 +22,7 @@ namespace capi {
 } // namespace
 
 
+
 inline diplomat::capi::ErrorStruct ErrorStruct::AsFFI() const {
   return diplomat::capi::ErrorStruct {
     /* .i = */ i,
@@ -0,0 +1,39 @@
+#ifndef FallibleUnitConstructor_D_HPP
+#define FallibleUnitConstructor_D_HPP
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include <memory>
+#include <optional>
+#include "diplomat_runtime.hpp"
+
+
+namespace diplomat {
+namespace capi {
+    struct FallibleUnitConstructor;
+} // namespace capi
+} // namespace
+
+class FallibleUnitConstructor {
+public:
+
+  inline static diplomat::result<std::unique_ptr<FallibleUnitConstructor>, std::monostate> new_(uint8_t i);
+
+  inline const diplomat::capi::FallibleUnitConstructor* AsFFI() const;
+  inline diplomat::capi::FallibleUnitConstructor* AsFFI();
+  inline static const FallibleUnitConstructor* FromFFI(const diplomat::capi::FallibleUnitConstructor* ptr);
+  inline static FallibleUnitConstructor* FromFFI(diplomat::capi::FallibleUnitConstructor* ptr);
+  inline static void operator delete(void* ptr);
+private:
+  FallibleUnitConstructor() = delete;
+  FallibleUnitConstructor(const FallibleUnitConstructor&) = delete;
+  FallibleUnitConstructor(FallibleUnitConstructor&&) noexcept = delete;
+  FallibleUnitConstructor operator=(const FallibleUnitConstructor&) = delete;
+  FallibleUnitConstructor operator=(FallibleUnitConstructor&&) noexcept = delete;
+  static void operator delete[](void*, size_t) = delete;
+};
+
+
+#endif // FallibleUnitConstructor_D_HPP
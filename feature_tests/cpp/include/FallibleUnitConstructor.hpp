@@ -0,0 +1,55 @@
+#ifndef FallibleUnitConstructor_HPP
+#define FallibleUnitConstructor_HPP
+
+#include "FallibleUnitConstructor.d.hpp"
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include <memory>
+#include <optional>
+#include "diplomat_runtime.hpp"
+
+
+namespace diplomat {
+namespace capi {
+    extern "C" {
+    
+    typedef struct FallibleUnitConstructor_new_result {union {diplomat::capi::FallibleUnitConstructor* ok; }; bool is_ok;} FallibleUnitConstructor_new_result;
+    FallibleUnitConstructor_new_result FallibleUnitConstructor_new(uint8_t i);
+    
+    
+    void FallibleUnitConstructor_destroy(FallibleUnitConstructor* self);
+    
+    } // extern "C"
+} // namespace capi
+} // namespace
+
+inline diplomat::result<std::unique_ptr<FallibleUnitConstructor>, std::monostate> FallibleUnitConstructor::new_(uint8_t i) {
+  auto result = diplomat::capi::FallibleUnitConstructor_new(i);
+  return result.is_ok ? diplomat::result<std::unique_ptr<FallibleUnitConstructor>, std::monostate>(diplomat::Ok<std::unique_ptr<FallibleUnitConstructor>>(std::unique_ptr<FallibleUnitConstructor>(FallibleUnitConstructor::FromFFI(result.ok)))) : diplomat::result<std::unique_ptr<FallibleUnitConstructor>, std::monostate>(diplomat::Err<std::monostate>());
+}
+
+inline const diplomat::capi::FallibleUnitConstructor* FallibleUnitConstructor::AsFFI() const {
+  return reinterpret_cast<const diplomat::capi::FallibleUnitConstructor*>(this);
+}
+
+inline diplomat::capi::FallibleUnitConstructor* FallibleUnitConstructor::AsFFI() {
+  return reinterpret_cast<diplomat::capi::FallibleUnitConstructor*>(this);
+}
+
+inline const FallibleUnitConstructor* FallibleUnitConstructor::FromFFI(const diplomat::capi::FallibleUnitConstructor* ptr) {
+  return reinterpret_cast<const FallibleUnitConstructor*>(ptr);
+}
+
+inline FallibleUnitConstructor* FallibleUnitConstructor::FromFFI(diplomat::capi::FallibleUnitConstructor* ptr) {
+  return reinterpret_cast<FallibleUnitConstructor*>(ptr);
+}
+
+inline void FallibleUnitConstructor::operator delete(void* ptr) {
+  diplomat::capi::FallibleUnitConstructor_destroy(reinterpret_cast<diplomat::capi::FallibleUnitConstructor*>(ptr));
+}
+
+
+#endif // FallibleUnitConstructor_HPP
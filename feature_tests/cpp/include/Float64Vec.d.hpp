@@ -45,6 +45,15 @@ public:
 
   inline std::optional<double> get(size_t i) const;
 
+
+  inline friend std::ostream& operator<<(std::ostream& os, const Float64Vec& value) {
+    return os << value.to_string();
+  }
+  inline friend std::string to_string(const Float64Vec& value) {
+    return value.to_string();
+  }
+
+  inline std::optional<double> operator[](size_t i) const;
   inline const diplomat::capi::Float64Vec* AsFFI() const;
   inline diplomat::capi::Float64Vec* AsFFI();
   inline static const Float64Vec* FromFFI(const diplomat::capi::Float64Vec* ptr);
@@ -119,6 +119,10 @@ inline std::optional<double> Float64Vec::get(size_t i) const {
   return result.is_ok ? std::optional<double>(result.ok) : std::nullopt;
 }
 
+inline std::optional<double> Float64Vec::operator[](size_t i) const {
+  return this->get(i);
+}
+
 inline const diplomat::capi::Float64Vec* Float64Vec::AsFFI() const {
   return reinterpret_cast<const diplomat::capi::Float64Vec*>(this);
 }
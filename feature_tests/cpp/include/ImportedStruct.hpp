@@ -23,6 +23,7 @@ namespace capi {
 } // namespace
 
 
+
 inline diplomat::capi::ImportedStruct ImportedStruct::AsFFI() const {
   return diplomat::capi::ImportedStruct {
     /* .foo = */ foo.AsFFI(),
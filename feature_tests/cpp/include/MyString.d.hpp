@@ -33,6 +33,8 @@ public:
 
   inline std::string_view borrow() const;
 
+  inline static diplomat::span<const uint16_t> static_lookup_table();
+
   inline const diplomat::capi::MyString* AsFFI() const;
   inline diplomat::capi::MyString* AsFFI();
   inline static const MyString* FromFFI(const diplomat::capi::MyString* ptr);
@@ -30,6 +30,8 @@ namespace capi {
     
     diplomat::capi::DiplomatStringView MyString_borrow(const diplomat::capi::MyString* self);
     
+    diplomat::capi::DiplomatU16View MyString_static_lookup_table(void);
+    
     
     void MyString_destroy(MyString* self);
     
@@ -84,6 +86,11 @@ inline std::string_view MyString::borrow() const {
   return std::string_view(result.data, result.len);
 }
 
+inline diplomat::span<const uint16_t> MyString::static_lookup_table() {
+  auto result = diplomat::capi::MyString_static_lookup_table();
+  return diplomat::span<const uint16_t>(result.data, result.len);
+}
+
 inline const diplomat::capi::MyString* MyString::AsFFI() const {
   return reinterpret_cast<const diplomat::capi::MyString*>(this);
 }
@@ -25,6 +25,14 @@ namespace capi {
       char32_t f;
       diplomat::capi::MyEnum g;
     };
+    DIPLOMAT_ASSERT_LAYOUT(MyStruct, 32, 8, 32, 8);
+    DIPLOMAT_ASSERT_OFFSET(MyStruct, a, 0, 0);
+    DIPLOMAT_ASSERT_OFFSET(MyStruct, b, 1, 1);
+    DIPLOMAT_ASSERT_OFFSET(MyStruct, c, 2, 2);
+    DIPLOMAT_ASSERT_OFFSET(MyStruct, d, 8, 8);
+    DIPLOMAT_ASSERT_OFFSET(MyStruct, e, 16, 16);
+    DIPLOMAT_ASSERT_OFFSET(MyStruct, f, 20, 20);
+    DIPLOMAT_ASSERT_OFFSET(MyStruct, g, 24, 24);
     
     typedef struct MyStruct_option {union { MyStruct ok; }; bool is_ok; } MyStruct_option;
 } // namespace capi
@@ -48,6 +56,9 @@ struct MyStruct {
 
   inline static diplomat::result<std::monostate, MyZst> fails_zst_result();
 
+  inline MyStruct(uint8_t a, bool b, uint8_t c, uint64_t d, int32_t e, char32_t f, MyEnum g)
+    : a(std::move(a)), b(std::move(b)), c(std::move(c)), d(std::move(d)), e(std::move(e)), f(std::move(f)), g(std::move(g)) {}
+  inline MyStruct();
   inline diplomat::capi::MyStruct AsFFI() const;
   inline static MyStruct FromFFI(diplomat::capi::MyStruct c_struct);
 };
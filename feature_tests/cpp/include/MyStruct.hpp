@@ -54,6 +54,10 @@ inline diplomat::result<std::monostate, MyZst> MyStruct::fails_zst_result() {
 }
 
 
+inline MyStruct::MyStruct()
+  : MyStruct(MyStruct::new_()) {}
+
+
 inline diplomat::capi::MyStruct MyStruct::AsFFI() const {
   return diplomat::capi::MyStruct {
     /* .a = */ a,
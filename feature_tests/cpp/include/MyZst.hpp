@@ -24,4 +24,5 @@ namespace capi {
 
 
 
+
 #endif // MyZst_HPP
@@ -44,6 +44,7 @@ inline diplomat::result<NestedBorrowedFields, diplomat::Utf8Error> NestedBorrowe
 }
 
 
+
 inline diplomat::capi::NestedBorrowedFields NestedBorrowedFields::AsFFI() const {
   return diplomat::capi::NestedBorrowedFields {
     /* .fields = */ fields.AsFFI(),
@@ -0,0 +1,39 @@
+#ifndef NullableConstructor_D_HPP
+#define NullableConstructor_D_HPP
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include <memory>
+#include <optional>
+#include "diplomat_runtime.hpp"
+
+
+namespace diplomat {
+namespace capi {
+    struct NullableConstructor;
+} // namespace capi
+} // namespace
+
+class NullableConstructor {
+public:
+
+  inline static std::unique_ptr<NullableConstructor> new_(uint8_t i);
+
+  inline const diplomat::capi::NullableConstructor* AsFFI() const;
+  inline diplomat::capi::NullableConstructor* AsFFI();
+  inline static const NullableConstructor* FromFFI(const diplomat::capi::NullableConstructor* ptr);
+  inline static NullableConstructor* FromFFI(diplomat::capi::NullableConstructor* ptr);
+  inline static void operator delete(void* ptr);
+private:
+  NullableConstructor() = delete;
+  NullableConstructor(const NullableConstructor&) = delete;
+  NullableConstructor(NullableConstructor&&) noexcept = delete;
+  NullableConstructor operator=(const NullableConstructor&) = delete;
+  NullableConstructor operator=(NullableConstructor&&) noexcept = delete;
+  static void operator delete[](void*, size_t) = delete;
+};
+
+
+#endif // NullableConstructor_D_HPP
@@ -0,0 +1,54 @@
+#ifndef NullableConstructor_HPP
+#define NullableConstructor_HPP
+
+#include "NullableConstructor.d.hpp"
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include <memory>
+#include <optional>
+#include "diplomat_runtime.hpp"
+
+
+namespace diplomat {
+namespace capi {
+    extern "C" {
+    
+    diplomat::capi::NullableConstructor* NullableConstructor_new(uint8_t i);
+    
+    
+    void NullableConstructor_destroy(NullableConstructor* self);
+    
+    } // extern "C"
+} // namespace capi
+} // namespace
+
+inline std::unique_ptr<NullableConstructor> NullableConstructor::new_(uint8_t i) {
+  auto result = diplomat::capi::NullableConstructor_new(i);
+  return std::unique_ptr<NullableConstructor>(NullableConstructor::FromFFI(result));
+}
+
+inline const diplomat::capi::NullableConstructor* NullableConstructor::AsFFI() const {
+  return reinterpret_cast<const diplomat::capi::NullableConstructor*>(this);
+}
+
+inline diplomat::capi::NullableConstructor* NullableConstructor::AsFFI() {
+  return reinterpret_cast<diplomat::capi::NullableConstructor*>(this);
+}
+
+inline const NullableConstructor* NullableConstructor::FromFFI(const diplomat::capi::NullableConstructor* ptr) {
+  return reinterpret_cast<const NullableConstructor*>(ptr);
+}
+
+inline NullableConstructor* NullableConstructor::FromFFI(diplomat::capi::NullableConstructor* ptr) {
+  return reinterpret_cast<NullableConstructor*>(ptr);
+}
+
+inline void NullableConstructor::operator delete(void* ptr) {
+  diplomat::capi::NullableConstructor_destroy(reinterpret_cast<diplomat::capi::NullableConstructor*>(ptr));
+}
+
+
+#endif // NullableConstructor_HPP
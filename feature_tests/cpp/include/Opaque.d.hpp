@@ -28,8 +28,14 @@ public:
 
   inline static diplomat::result<std::unique_ptr<Opaque>, diplomat::Utf8Error> from_str(std::string_view input);
 
+  inline static std::unique_ptr<Opaque> from_read(std::istream& read);
+
   inline std::string get_debug_str() const;
 
+  inline std::string repeat(size_t count) const;
+
+  inline void repeat(size_t count, std::ostream& out) const;
+
   inline void assert_struct(MyStruct s) const;
 
   inline static size_t returns_usize();
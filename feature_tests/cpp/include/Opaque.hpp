@@ -24,8 +24,12 @@ namespace capi {
     
     diplomat::capi::Opaque* Opaque_from_str(diplomat::capi::DiplomatStringView input);
     
+    diplomat::capi::Opaque* Opaque_from_read(diplomat::capi::DiplomatRead* read);
+    
     void Opaque_get_debug_str(const diplomat::capi::Opaque* self, diplomat::capi::DiplomatWrite* write);
     
+    void Opaque_repeat(const diplomat::capi::Opaque* self, size_t count, diplomat::capi::DiplomatWrite* write);
+    
     void Opaque_assert_struct(const diplomat::capi::Opaque* self, diplomat::capi::MyStruct s);
     
     size_t Opaque_returns_usize(void);
@@ -59,6 +63,11 @@ inline diplomat::result<std::unique_ptr<Opaque>, diplomat::Utf8Error> Opaque::fr
   return diplomat::Ok<std::unique_ptr<Opaque>>(std::unique_ptr<Opaque>(Opaque::FromFFI(result)));
 }
 
+inline std::unique_ptr<Opaque> Opaque::from_read(std::istream& read) {
+  auto result = diplomat::capi::Opaque_from_read(diplomat::IstreamRead(read).AsFFI());
+  return std::unique_ptr<Opaque>(Opaque::FromFFI(result));
+}
+
 inline std::string Opaque::get_debug_str() const {
   std::string output;
   diplomat::capi::DiplomatWrite write = diplomat::WriteFromString(output);
@@ -67,6 +76,22 @@ inline std::string Opaque::get_debug_str() const {
   return output;
 }
 
+inline std::string Opaque::repeat(size_t count) const {
+  std::string output;
+  diplomat::capi::DiplomatWrite write = diplomat::WriteFromString(output);
+  diplomat::capi::Opaque_repeat(this->AsFFI(),
+    count,
+    &write);
+  return output;
+}
+
+inline void Opaque::repeat(size_t count, std::ostream& out) const {
+  diplomat::StreamWrite write(out);
+  diplomat::capi::Opaque_repeat(this->AsFFI(),
+    count,
+    write.AsFFI());
+}
+
 inline void Opaque::assert_struct(MyStruct s) const {
   diplomat::capi::Opaque_assert_struct(this->AsFFI(),
     s.AsFFI());
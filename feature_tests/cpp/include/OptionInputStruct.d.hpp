@@ -20,6 +20,10 @@ namespace capi {
       diplomat::capi::OptionChar b;
       diplomat::capi::OptionEnum_option c;
     };
+    DIPLOMAT_ASSERT_LAYOUT(OptionInputStruct, 20, 4, 20, 4);
+    DIPLOMAT_ASSERT_OFFSET(OptionInputStruct, a, 0, 0);
+    DIPLOMAT_ASSERT_OFFSET(OptionInputStruct, b, 4, 4);
+    DIPLOMAT_ASSERT_OFFSET(OptionInputStruct, c, 12, 12);
     
     typedef struct OptionInputStruct_option {union { OptionInputStruct ok; }; bool is_ok; } OptionInputStruct_option;
 } // namespace capi
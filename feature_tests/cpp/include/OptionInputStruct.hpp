@@ -23,6 +23,7 @@ namespace capi {
 } // namespace
 
 
+
 inline diplomat::capi::OptionInputStruct OptionInputStruct::AsFFI() const {
   return diplomat::capi::OptionInputStruct {
     /* .a = */ a.has_value() ? (diplomat::capi::OptionU8{ { a.value() }, true }) : (diplomat::capi::OptionU8{ {}, false }),
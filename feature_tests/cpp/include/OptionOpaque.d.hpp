@@ -9,6 +9,7 @@
 #include <optional>
 #include "diplomat_runtime.hpp"
 
+struct BorrowingOptionStruct;
 struct OptionInputStruct;
 struct OptionStruct;
 class OptionEnum;
@@ -53,6 +54,10 @@ public:
 
   inline static OptionInputStruct returns_option_input_struct();
 
+  inline BorrowingOptionStruct borrowing_option_struct() const;
+
+  inline BorrowingOptionStruct borrowing_option_struct_nones() const;
+
   inline const diplomat::capi::OptionOpaque* AsFFI() const;
   inline diplomat::capi::OptionOpaque* AsFFI();
   inline static const OptionOpaque* FromFFI(const diplomat::capi::OptionOpaque* ptr);
@@ -9,6 +9,7 @@
 #include <stdbool.h>
 #include <memory>
 #include <optional>
+#include "BorrowingOptionStruct.hpp"
 #include "OptionEnum.hpp"
 #include "OptionInputStruct.hpp"
 #include "OptionStruct.hpp"
@@ -57,6 +58,10 @@ namespace capi {
     
     diplomat::capi::OptionInputStruct OptionOpaque_returns_option_input_struct(void);
     
+    diplomat::capi::BorrowingOptionStruct OptionOpaque_borrowing_option_struct(const diplomat::capi::OptionOpaque* self);
+    
+    diplomat::capi::BorrowingOptionStruct OptionOpaque_borrowing_option_struct_nones(const diplomat::capi::OptionOpaque* self);
+    
     
     void OptionOpaque_destroy(OptionOpaque* self);
     
@@ -139,6 +144,16 @@ inline OptionInputStruct OptionOpaque::returns_option_input_struct() {
   return OptionInputStruct::FromFFI(result);
 }
 
+inline BorrowingOptionStruct OptionOpaque::borrowing_option_struct() const {
+  auto result = diplomat::capi::OptionOpaque_borrowing_option_struct(this->AsFFI());
+  return BorrowingOptionStruct::FromFFI(result);
+}
+
+inline BorrowingOptionStruct OptionOpaque::borrowing_option_struct_nones() const {
+  auto result = diplomat::capi::OptionOpaque_borrowing_option_struct_nones(this->AsFFI());
+  return BorrowingOptionStruct::FromFFI(result);
+}
+
 inline const diplomat::capi::OptionOpaque* OptionOpaque::AsFFI() const {
   return reinterpret_cast<const diplomat::capi::OptionOpaque*>(this);
 }
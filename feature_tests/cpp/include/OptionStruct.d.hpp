@@ -23,6 +23,11 @@ namespace capi {
       uint32_t c;
       diplomat::capi::OptionOpaque* d;
     };
+    DIPLOMAT_ASSERT_LAYOUT(OptionStruct, 16, 4, 32, 8);
+    DIPLOMAT_ASSERT_OFFSET(OptionStruct, a, 0, 0);
+    DIPLOMAT_ASSERT_OFFSET(OptionStruct, b, 4, 8);
+    DIPLOMAT_ASSERT_OFFSET(OptionStruct, c, 8, 16);
+    DIPLOMAT_ASSERT_OFFSET(OptionStruct, d, 12, 24);
     
     typedef struct OptionStruct_option {union { OptionStruct ok; }; bool is_ok; } OptionStruct_option;
 } // namespace capi
@@ -24,6 +24,7 @@ namespace capi {
 } // namespace
 
 
+
 inline diplomat::capi::OptionStruct OptionStruct::AsFFI() const {
   return diplomat::capi::OptionStruct {
     /* .a = */ a ? a->AsFFI() : nullptr,
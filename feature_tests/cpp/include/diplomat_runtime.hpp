@@ -1,12 +1,19 @@
 #ifndef DIPLOMAT_RUNTIME_CPP_H
 #define DIPLOMAT_RUNTIME_CPP_H
 
+#include <cstddef>
+#include <functional>
+#include <istream>
+#include <memory>
 #include <optional>
+#include <ostream>
 #include <string>
+#include <string_view>
 #include <type_traits>
 #include <variant>
 
 #if __cplusplus >= 202002L
+#include <compare>
 #include <span>
 #else
 #include <array>
@@ -31,8 +38,44 @@ typedef struct DiplomatWrite {
     bool (*grow)(struct DiplomatWrite*, size_t);
 } DiplomatWrite;
 
+// Like `DiplomatWrite`, but `buf` holds UTF-16 code units, and `len` and `cap` count code units.
+typedef struct DiplomatWrite16 {
+    void* context;
+    char16_t* buf;
+    size_t len;
+    size_t cap;
+    bool grow_failed;
+    void (*flush)(struct DiplomatWrite16*);
+    bool (*grow)(struct DiplomatWrite16*, size_t);
+} DiplomatWrite16;
+
+// Reads at most `len` bytes into `buf`, returning the number of bytes read, 0 at the end of the input,
+// or a negative number if reading failed.
+typedef intptr_t (*DiplomatReadFn)(void* context, uint8_t* buf, size_t len);
+
+// A source of bytes that Rust reads from, passed as `DiplomatRead*` to methods.
+typedef struct DiplomatRead {
+    void* context;
+    DiplomatReadFn read;
+} DiplomatRead;
+
 bool diplomat_is_str(const char* buf, size_t len);
 
+// Receives the library's log records, see `diplomat_log_set_sink`.
+// `level` is 1 (error) to 5 (trace). `target` and `message` are UTF-8, and only valid during the call.
+typedef void (*DiplomatLogSink)(uint8_t level, const char* target, size_t target_len, const char* message, size_t message_len);
+
+// Only available if the library is built with the `log` feature of `diplomat-runtime`.
+// `max_level` is 0 (off) to 5 (trace). A null `sink` stops forwarding records.
+void diplomat_log_set_sink(DiplomatLogSink sink, uint8_t max_level);
+void diplomat_log_set_max_level(uint8_t level);
+
+// Only available if the library is built with the `leak-check` feature of `diplomat-runtime`.
+// The number of opaques and runtime allocations that haven't been freed, and a report of them
+// with one `<name>: <count>` line per kind of object.
+size_t diplomat_leak_count(void);
+void diplomat_leak_report(DiplomatWrite* write);
+
 #define MAKE_SLICES(name, c_ty) \
     typedef struct Diplomat##name##View { \
         const c_ty* data; \
@@ -70,6 +113,20 @@ MAKE_SLICES(String16, char16_t)
 MAKE_SLICES(Strings, DiplomatStringView)
 MAKE_SLICES(Strings16, DiplomatString16View)
 
+// The layouts of structs are checked against the ones the Rust library was built with, on targets
+// with the natural alignment for 64-bit types and 32- or 64-bit pointers.
+#ifdef __cplusplus
+#define DIPLOMAT_ALIGNOF(ty) alignof(ty)
+#else
+#define DIPLOMAT_ALIGNOF(ty) _Alignof(ty)
+#endif
+#define DIPLOMAT_LAYOUT_CHECKED (DIPLOMAT_ALIGNOF(uint64_t) == 8 && DIPLOMAT_ALIGNOF(double) == 8 && (sizeof(void*) == 4 || sizeof(void*) == 8))
+#define DIPLOMAT_LAYOUT_VALUE(v32, v64) (sizeof(void*) == 8 ? (v64) : (v32))
+#define DIPLOMAT_ASSERT_LAYOUT(ty, size32, align32, size64, align64) \
+    static_assert(!DIPLOMAT_LAYOUT_CHECKED || (sizeof(ty) == DIPLOMAT_LAYOUT_VALUE(size32, size64) && DIPLOMAT_ALIGNOF(ty) == DIPLOMAT_LAYOUT_VALUE(align32, align64)), "`" #ty "` doesn't have the layout of the Rust struct")
+#define DIPLOMAT_ASSERT_OFFSET(ty, field, offset32, offset64) \
+    static_assert(!DIPLOMAT_LAYOUT_CHECKED || offsetof(ty, field) == DIPLOMAT_LAYOUT_VALUE(offset32, offset64), "`" #ty "." #field "` isn't where the Rust struct has it")
+
 } // extern "C"
 } // namespace capi
 
@@ -99,6 +156,72 @@ inline capi::DiplomatWrite WriteFromString(std::string& string) {
   return w;
 };
 
+extern "C" inline bool _ostream_grow(capi::DiplomatWrite* w, uintptr_t) {
+  std::ostream* out = reinterpret_cast<std::ostream*>(w->context);
+  out->write(w->buf, w->len);
+  w->len = 0;
+  return out->good();
+};
+
+extern "C" inline void _ostream_flush(capi::DiplomatWrite* w) {
+  if (!w->grow_failed && !_ostream_grow(w, 0)) {
+    w->grow_failed = true;
+  }
+};
+
+/// A `DiplomatWrite` that writes its output to an `std::ostream` in chunks as it is written,
+/// used by the `std::ostream&` overloads of methods marked `stream`.
+///
+/// Nothing is written after the stream fails.
+class StreamWrite {
+public:
+  explicit StreamWrite(std::ostream& out) {
+    write.context = &out;
+    write.buf = buf;
+    write.len = 0;
+    write.cap = sizeof(buf);
+    write.grow_failed = false;
+    write.flush = _ostream_flush;
+    write.grow = _ostream_grow;
+  }
+  // `write` points into `buf`
+  StreamWrite(const StreamWrite&) = delete;
+  StreamWrite& operator=(const StreamWrite&) = delete;
+
+  capi::DiplomatWrite* AsFFI() {
+    return &write;
+  }
+
+private:
+  char buf[1024];
+  capi::DiplomatWrite write;
+};
+
+extern "C" inline intptr_t _istream_read(void* context, uint8_t* buf, size_t len) {
+  std::istream* in = reinterpret_cast<std::istream*>(context);
+  in->read(reinterpret_cast<char*>(buf), static_cast<std::streamsize>(len));
+  return in->bad() ? -1 : static_cast<intptr_t>(in->gcount());
+};
+
+/// A `DiplomatRead` that reads from an `std::istream`, used for `std::istream&` parameters.
+///
+/// Reading fails if the stream goes bad, and the input ends when the stream reaches its end or
+/// otherwise fails.
+class IstreamRead {
+public:
+  explicit IstreamRead(std::istream& in) {
+    read.context = &in;
+    read.read = _istream_read;
+  }
+
+  capi::DiplomatRead* AsFFI() {
+    return &read;
+  }
+
+private:
+  capi::DiplomatRead read;
+};
+
 template<class T> struct Ok {
   T inner;
   Ok(T&& i): inner(std::move(i)) {}
@@ -180,6 +303,12 @@ public:
 
 class Utf8Error {};
 
+// Whether instances of the opaque type `T` may be moved to and destroyed on another thread (`Send` in
+// Rust), and whether they may be used through `const` references from several threads at once (`Sync`).
+// These are specialized for the types marked `send`, `sync` or `thread_safe`.
+template<class T> struct is_send : std::false_type {};
+template<class T> struct is_sync : std::false_type {};
+
 // Use custom std::span on C++17, otherwise use std::span
 #if __cplusplus >= 202002L
 
@@ -210,6 +339,104 @@ private:
 
 #endif // __cplusplus >= 202002L
 
+/// The end of a `next_to_iter_helper`.
+struct next_to_iter_end {};
+
+/// Adapts a Diplomat iterator (a type with a `next()` method returning a `std::optional`
+/// or a `std::unique_ptr`) to a C++ iterator, so that it can be used in range-based for loops.
+template<class Iter>
+class next_to_iter_helper {
+public:
+  using next_type = decltype(std::declval<Iter&>().next());
+
+  explicit next_to_iter_helper(std::unique_ptr<Iter> iter)
+    : iter_(std::move(iter)), current_(iter_->next()) {}
+
+  auto& operator*() {
+    return unwrap(current_);
+  }
+  next_to_iter_helper& operator++() {
+    current_ = iter_->next();
+    return *this;
+  }
+  bool operator==(next_to_iter_end) const {
+    return !static_cast<bool>(current_);
+  }
+  bool operator!=(next_to_iter_end) const {
+    return static_cast<bool>(current_);
+  }
+
+private:
+  template<class T> static T& unwrap(std::optional<T>& next) {
+    return *next;
+  }
+  template<class T> static std::unique_ptr<T>& unwrap(std::unique_ptr<T>& next) {
+    return next;
+  }
+
+  std::unique_ptr<Iter> iter_;
+  next_type current_;
+};
+
+/// Forwarding of the library's `log` records, if it is built with the `log` feature of `diplomat-runtime`.
+namespace log {
+
+enum class Level : uint8_t {
+  Off = 0,
+  Error = 1,
+  Warn = 2,
+  Info = 3,
+  Debug = 4,
+  Trace = 5,
+};
+
+/// Receives a log record. May be called from any thread that logs.
+using Sink = std::function<void(Level level, std::string_view target, std::string_view message)>;
+
+inline Sink& _sink() {
+  static Sink sink;
+  return sink;
+}
+
+extern "C" inline void _log(uint8_t level, const char* target, size_t target_len, const char* message, size_t message_len) {
+  _sink()(static_cast<Level>(level), std::string_view(target, target_len), std::string_view(message, message_len));
+}
+
+/// Route the library's log records up to `max_level` to `sink`, replacing the previous sink.
+/// Passing an empty `sink` stops forwarding records.
+///
+/// This should be called before the library logs anything, the sink is not synchronized.
+inline void set_sink(Sink sink, Level max_level = Level::Info) {
+  _sink() = std::move(sink);
+  capi::diplomat_log_set_sink(_sink() ? _log : nullptr, static_cast<uint8_t>(max_level));
+}
+
+/// Set the most verbose level of log records that are forwarded.
+inline void set_max_level(Level level) {
+  capi::diplomat_log_set_max_level(static_cast<uint8_t>(level));
+}
+
+} // namespace log
+
+/// Only available if the library is built with the `leak-check` feature of `diplomat-runtime`.
+namespace leak_check {
+
+/// The number of opaques and runtime allocations that haven't been freed.
+inline size_t count() {
+  return capi::diplomat_leak_count();
+}
+
+/// A report of the opaques and runtime allocations that haven't been freed, with one
+/// `<name>: <count>` line per kind of object. Empty if everything was freed.
+inline std::string report() {
+  std::string out;
+  capi::DiplomatWrite write = WriteFromString(out);
+  capi::diplomat_leak_report(&write);
+  return out;
+}
+
+} // namespace leak_check
+
 } // namespace diplomat
 
 #endif
@@ -0,0 +1,48 @@
+#ifndef ns_RenamedOpaqueIterable_D_HPP
+#define ns_RenamedOpaqueIterable_D_HPP
+
+#include <stdio.h>
+#include <stdint.h>
+#include <stddef.h>
+#include <stdbool.h>
+#include <memory>
+#include <optional>
+#include "../diplomat_runtime.hpp"
+
+namespace ns {
+namespace capi { struct RenamedOpaqueIterator; }
+class RenamedOpaqueIterator;
+}
+
+
+namespace ns {
+namespace capi {
+    struct RenamedOpaqueIterable;
+} // namespace capi
+} // namespace
+
+namespace ns {
+class RenamedOpaqueIterable {
+public:
+
+  inline std::unique_ptr<ns::RenamedOpaqueIterator> iter() const;
+
+
+  inline diplomat::next_to_iter_helper<ns::RenamedOpaqueIterator> begin() const;
+  inline diplomat::next_to_iter_end end() const { return {}; }
+  inline const ns::capi::RenamedOpaqueIterable* AsFFI() const;
+  inline ns::capi::RenamedOpaqueIterable* AsFFI();
+  inline static const ns::RenamedOpaqueIterable* FromFFI(const ns::capi::RenamedOpaqueIterable* ptr);
+  inline static ns::RenamedOpaqueIterable* FromFFI(ns::capi::RenamedOpaqueIterable* ptr);
+  inline static void operator delete(void* ptr);
+private:
+  RenamedOpaqueIterable() = delete;
+  RenamedOpaqueIterable(const ns::RenamedOpaqueIterable&) = delete;
+  RenamedOpaqueIterable(ns::RenamedOpaqueIterable&&) noexcept = delete;
+  RenamedOpaqueIterable operator=(const ns::RenamedOpaqueIterable&) = delete;
+  RenamedOpaqueIterable operator=(ns::RenamedOpaqueIterable&&) noexcept = delete;
+  static void operator delete[](void*, size_t) = delete;
+};
+
+} // namespace
+#endif // ns_RenamedOpaqueIterable_D_HPP
@@ -2,6 +2,7 @@
 #include "../include/OptionOpaqueChar.hpp"
 #include "../include/OptionOpaque.hpp"
 #include "../include/OptionStruct.hpp"
+#include "../include/BorrowingOptionStruct.hpp"
 #include "assert.hpp"
 
 int main(int argc, char *argv[]) {
@@ -44,4 +45,15 @@ int main(int argc, char *argv[]) {
     simple_assert("returns_option_input_struct returns the right values", opt_struct.value().a == 6);
     simple_assert("returns_option_input_struct returns the right values", !opt_struct.value().b.has_value());
     simple_assert("returns_option_input_struct returns the right values", opt_struct.value().c == OptionEnum::Bar);
+
+    std::unique_ptr<OptionOpaque> owner = OptionOpaque::new_(5);
+    BorrowingOptionStruct borrowing = owner->borrowing_option_struct();
+    simple_assert("borrowing_option_struct returns Some", borrowing.input.has_value());
+    simple_assert("borrowing_option_struct returns the right values", borrowing.input.value().a == 6);
+    simple_assert("borrowing_option_struct borrows the owner", borrowing.opaque == owner.get());
+    borrowing.opaque->assert_integer(5);
+
+    borrowing = owner->borrowing_option_struct_nones();
+    simple_assert("borrowing_option_struct_nones returns None", !borrowing.input.has_value());
+    simple_assert("borrowing_option_struct_nones returns None", !borrowing.opaque);
 }
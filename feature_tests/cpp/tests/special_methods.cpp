@@ -0,0 +1,62 @@
+#include <iostream>
+#include <sstream>
+#include <vector>
+#include "../include/MyStruct.hpp"
+#include "../include/Float64Vec.hpp"
+#include "../include/ns/RenamedComparable.hpp"
+#include "../include/ns/RenamedMyIndexer.hpp"
+#include "../include/ns/RenamedMyIterable.hpp"
+#include "assert.hpp"
+
+int main(int argc, char *argv[]) {
+    // Constructors
+    MyStruct s;
+    simple_assert_eq("constructor", s.a, 17);
+    simple_assert_eq("constructor", s.d, 1234);
+    MyStruct fields(1, false, 2, 3, 4, U'a', MyEnum::A);
+    simple_assert_eq("field-wise constructor", fields.a, 1);
+    simple_assert_eq("field-wise constructor", fields.e, 4);
+    MyStruct braced {1, false, 2, 3, 4, U'a', MyEnum::A};
+    simple_assert_eq("brace initialization", braced.c, 2);
+
+    // Stringifiers
+    double values[] = {1.0, 2.0};
+    std::unique_ptr<Float64Vec> v = Float64Vec::new_(values);
+    std::ostringstream os;
+    os << *v;
+    simple_assert_eq("operator<<", os.str(), "[1.0, 2.0]");
+    simple_assert_eq("to_string", to_string(*v), "[1.0, 2.0]");
+
+    // Comparators
+    std::unique_ptr<ns::RenamedComparable> one = ns::RenamedComparable::new_(1);
+    std::unique_ptr<ns::RenamedComparable> two = ns::RenamedComparable::new_(2);
+    simple_assert("operator==", *one == *one);
+    simple_assert("operator!=", *one != *two);
+    simple_assert("operator<", *one < *two);
+    simple_assert("operator<=", *one <= *one);
+    simple_assert("operator>", *two > *one);
+    simple_assert("operator>=", *two >= *two);
+    simple_assert("operator<=>", (*one <=> *two) == std::strong_ordering::less);
+    simple_assert("operator<=>", (*two <=> *one) == std::strong_ordering::greater);
+    simple_assert("operator<=>", (*one <=> *one) == std::strong_ordering::equal);
+
+    // Indexers
+    std::unique_ptr<ns::RenamedMyIndexer> indexer = ns::RenamedMyIndexer::new_("a bc def");
+    simple_assert("operator[]", (*indexer)[1] == "bc");
+    simple_assert("operator[]", (*indexer)[2] == "def");
+    simple_assert("operator[] out of bounds", !(*indexer)[3]);
+
+    // Iterables
+    uint8_t bytes[] = {1, 2, 3};
+    std::unique_ptr<ns::RenamedMyIterable> iterable = ns::RenamedMyIterable::new_(bytes);
+    std::vector<uint8_t> iterated;
+    for (uint8_t byte : *iterable) {
+        iterated.push_back(byte);
+    }
+    simple_assert("range-for", iterated == std::vector<uint8_t>({1, 2, 3}));
+    iterated.clear();
+    for (uint8_t byte : *ns::RenamedMyIterable::new_({})) {
+        iterated.push_back(byte);
+    }
+    simple_assert("range-for over empty iterable", iterated.empty());
+}
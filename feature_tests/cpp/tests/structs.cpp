@@ -1,4 +1,5 @@
 #include <iostream>
+#include <sstream>
 #include "../include/MyStruct.hpp"
 #include "../include/MyEnum.hpp"
 #include "../include/Opaque.hpp"
@@ -20,4 +21,17 @@ int main(int argc, char *argv[]) {
 
     simple_assert_eq("enum fn", s.g.into_value(), -1);
     simple_assert_eq("struct fn", s.into_a(), 17);
+
+    // Longer than the 1024 byte chunks `StreamWrite` passes to the stream
+    std::unique_ptr<Opaque> abc = Opaque::from_str("abc").ok().value();
+    std::ostringstream streamed;
+    abc->repeat(500, streamed);
+    simple_assert_eq("streamed write", streamed.str().size(), 1500);
+    simple_assert("streamed write", streamed.str() == abc->repeat(500));
+
+    // Longer than the 16 byte chunks `Opaque::from_read` reads
+    std::istringstream input(abc->repeat(10));
+    std::unique_ptr<Opaque> read = Opaque::from_read(input);
+    simple_assert("read from istream", read);
+    simple_assert("read from istream", read->repeat(1) == abc->repeat(10));
 }
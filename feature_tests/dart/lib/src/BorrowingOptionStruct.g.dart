@@ -0,0 +1,51 @@
+// generated by diplomat-tool
+
+part of 'lib.g.dart';
+
+final class _BorrowingOptionStructFfi extends ffi.Struct {
+  external _ResultOptionInputStructFfiVoid input;
+  external ffi.Pointer<ffi.Opaque> opaque;
+}
+
+final class BorrowingOptionStruct {
+  final OptionInputStruct? input;
+  final OptionOpaque? opaque;
+
+  // ignore: unused_element
+  BorrowingOptionStruct._fromFfi(_BorrowingOptionStructFfi ffi, core.List<Object> aEdges) :
+    input = ffi.input.isOk ? OptionInputStruct._fromFfi(ffi.input.union.ok) : null,
+    opaque = ffi.opaque.address == 0 ? null : OptionOpaque._fromFfi(ffi.opaque, aEdges);
+
+  // If this struct contains any slices, their lifetime-edge-relevant objects (typically _FinalizedArenas) will only
+  // be constructed here, and can be appended to any relevant lifetime arrays here. <lifetime>AppendArray accepts a list
+  // of arrays for each lifetime to do so. It accepts multiple lists per lifetime in case the caller needs to tie a lifetime to multiple
+  // output arrays. Null is equivalent to an empty list: this lifetime is not being borrowed from.
+  // ignore: unused_element
+  _BorrowingOptionStructFfi _toFfi(ffi.Allocator temp, {core.List<core.List<Object>> aAppendArray = const []}) {
+    final struct = ffi.Struct.create<_BorrowingOptionStructFfi>();
+    OptionInputStruct? input = this.input;
+    struct.input = input != null ? _ResultOptionInputStructFfiVoid.ok(input._toFfi(temp)) : _ResultOptionInputStructFfiVoid.err();
+    struct.opaque = opaque?._ffi ?? ffi.Pointer.fromAddress(0);
+    return struct;
+  }
+
+  @override
+  bool operator ==(Object other) =>
+      other is BorrowingOptionStruct &&
+      other.input == input &&
+      other.opaque == opaque;
+
+  @override
+  int get hashCode => Object.hashAll([
+        input,
+        opaque,
+      ]);
+
+  // Return all fields corresponding to lifetime `'a` 
+  // without handling lifetime dependencies (this is the job of the caller)
+  // This is all fields that may be borrowed from if borrowing `'a`,
+  // assuming that there are no `'other: a`. bounds. In case of such bounds,
+  // the caller should take care to also call _fieldsForLifetimeOther
+  // ignore: unused_element
+  core.List<Object> get _fieldsForLifetimeA => [opaque];
+}
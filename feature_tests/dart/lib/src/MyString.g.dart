@@ -68,6 +68,11 @@ final class MyString implements ffi.Finalizable {
     final result = _MyString_borrow(_ffi);
     return result._toDart(aEdges);
   }
+
+  static core.List<int> staticLookupTable() {
+    final result = _MyString_static_lookup_table();
+    return result._toDart([], isStatic: true);
+  }
 }
 
 @meta.RecordUse()
@@ -114,3 +119,8 @@ external void _MyString_string_transform(_SliceUtf8 foo, ffi.Pointer<ffi.Opaque>
 @ffi.Native<_SliceUtf8 Function(ffi.Pointer<ffi.Opaque>)>(isLeaf: true, symbol: 'MyString_borrow')
 // ignore: non_constant_identifier_names
 external _SliceUtf8 _MyString_borrow(ffi.Pointer<ffi.Opaque> self);
+
+@meta.RecordUse()
+@ffi.Native<_SliceUint16 Function()>(isLeaf: true, symbol: 'MyString_static_lookup_table')
+// ignore: non_constant_identifier_names
+external _SliceUint16 _MyString_static_lookup_table();
@@ -38,12 +38,36 @@ final class Opaque implements ffi.Finalizable {
     return Opaque._fromFfi(result, []);
   }
 
+  /// Reads a UTF-8 string from `read` in chunks of 16 bytes, so longer inputs take several reads.
+  static Opaque? fromRead(core.Iterable<core.List<int>> read) {
+    final readReader = _Reader(read);
+    final result = _Opaque_from_read(readReader._ffi);
+    readReader.finalize();
+    return result.address == 0 ? null : Opaque._fromFfi(result, []);
+  }
+
   String getDebugStr() {
     final write = _Write();
     _Opaque_get_debug_str(_ffi, write._ffi);
     return write.finalize();
   }
 
+  /// Writes the string `count` times, which can be more than fits in one chunk of a streamed write.
+  String repeat(int count) {
+    final write = _Write();
+    _Opaque_repeat(_ffi, count, write._ffi);
+    return write.finalize();
+  }
+
+  /// Writes the string `count` times, which can be more than fits in one chunk of a streamed write.
+  ///
+  /// Adds the output to [sink] as it is written, instead of returning it.
+  void repeatTo(int count, core.Sink<core.List<int>> sink) {
+    final write = _StreamWrite(sink);
+    _Opaque_repeat(_ffi, count, write._ffi);
+    write.finalize();
+  }
+
   /// See the [Rust documentation for `something`](https://docs.rs/Something/latest/struct.Something.html#method.something) for more information.
   ///
   /// See the [Rust documentation for `something_else`](https://docs.rs/Something/latest/struct.Something.html#method.something_else) for more information.
@@ -90,11 +114,21 @@ external ffi.Pointer<ffi.Opaque> _Opaque_try_from_utf8(_SliceUtf8 input);
 // ignore: non_constant_identifier_names
 external ffi.Pointer<ffi.Opaque> _Opaque_from_str(_SliceUtf8 input);
 
+@meta.RecordUse()
+@ffi.Native<ffi.Pointer<ffi.Opaque> Function(ffi.Pointer<ffi.Opaque>)>(isLeaf: false, symbol: 'Opaque_from_read')
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Opaque> _Opaque_from_read(ffi.Pointer<ffi.Opaque> read);
+
 @meta.RecordUse()
 @ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>, ffi.Pointer<ffi.Opaque>)>(isLeaf: true, symbol: 'Opaque_get_debug_str')
 // ignore: non_constant_identifier_names
 external void _Opaque_get_debug_str(ffi.Pointer<ffi.Opaque> self, ffi.Pointer<ffi.Opaque> write);
 
+@meta.RecordUse()
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>, ffi.Size, ffi.Pointer<ffi.Opaque>)>(isLeaf: false, symbol: 'Opaque_repeat')
+// ignore: non_constant_identifier_names
+external void _Opaque_repeat(ffi.Pointer<ffi.Opaque> self, int count, ffi.Pointer<ffi.Opaque> write);
+
 @meta.RecordUse()
 @ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>, _MyStructFfi)>(isLeaf: true, symbol: 'Opaque_assert_struct')
 // ignore: non_constant_identifier_names
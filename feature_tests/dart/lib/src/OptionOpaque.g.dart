@@ -119,6 +119,20 @@ final class OptionOpaque implements ffi.Finalizable {
     final result = _OptionOpaque_returns_option_input_struct();
     return OptionInputStruct._fromFfi(result);
   }
+
+  BorrowingOptionStruct borrowingOptionStruct() {
+    // This lifetime edge depends on lifetimes: 'a
+    core.List<Object> aEdges = [this];
+    final result = _OptionOpaque_borrowing_option_struct(_ffi);
+    return BorrowingOptionStruct._fromFfi(result, aEdges);
+  }
+
+  BorrowingOptionStruct borrowingOptionStructNones() {
+    // This lifetime edge depends on lifetimes: 'a
+    core.List<Object> aEdges = [this];
+    final result = _OptionOpaque_borrowing_option_struct_nones(_ffi);
+    return BorrowingOptionStruct._fromFfi(result, aEdges);
+  }
 }
 
 @meta.RecordUse()
@@ -200,3 +214,13 @@ external _ResultOptionInputStructFfiVoid _OptionOpaque_accepts_option_input_stru
 @ffi.Native<_OptionInputStructFfi Function()>(isLeaf: true, symbol: 'OptionOpaque_returns_option_input_struct')
 // ignore: non_constant_identifier_names
 external _OptionInputStructFfi _OptionOpaque_returns_option_input_struct();
+
+@meta.RecordUse()
+@ffi.Native<_BorrowingOptionStructFfi Function(ffi.Pointer<ffi.Opaque>)>(isLeaf: true, symbol: 'OptionOpaque_borrowing_option_struct')
+// ignore: non_constant_identifier_names
+external _BorrowingOptionStructFfi _OptionOpaque_borrowing_option_struct(ffi.Pointer<ffi.Opaque> self);
+
+@meta.RecordUse()
+@ffi.Native<_BorrowingOptionStructFfi Function(ffi.Pointer<ffi.Opaque>)>(isLeaf: true, symbol: 'OptionOpaque_borrowing_option_struct_nones')
+// ignore: non_constant_identifier_names
+external _BorrowingOptionStructFfi _OptionOpaque_borrowing_option_struct_nones(ffi.Pointer<ffi.Opaque> self);
@@ -33,6 +33,12 @@ final class Utf16Wrap implements ffi.Finalizable {
     return write.finalize();
   }
 
+  String toLossyString() {
+    final write = _Write16();
+    _Utf16Wrap_to_lossy_string(_ffi, write._ffi);
+    return write.finalize();
+  }
+
   String borrowCont() {
     // This lifetime edge depends on lifetimes: 'a
     core.List<Object> aEdges = [this];
@@ -56,6 +62,11 @@ external ffi.Pointer<ffi.Opaque> _Utf16Wrap_from_utf16(_SliceUtf16 input);
 // ignore: non_constant_identifier_names
 external void _Utf16Wrap_get_debug_str(ffi.Pointer<ffi.Opaque> self, ffi.Pointer<ffi.Opaque> write);
 
+@meta.RecordUse()
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>, ffi.Pointer<ffi.Opaque>)>(isLeaf: true, symbol: 'Utf16Wrap_to_lossy_string')
+// ignore: non_constant_identifier_names
+external void _Utf16Wrap_to_lossy_string(ffi.Pointer<ffi.Opaque> self, ffi.Pointer<ffi.Opaque> write);
+
 @meta.RecordUse()
 @ffi.Native<_SliceUtf16 Function(ffi.Pointer<ffi.Opaque>)>(isLeaf: true, symbol: 'Utf16Wrap_borrow_cont')
 // ignore: non_constant_identifier_names
@@ -7,14 +7,15 @@ import 'dart:typed_data';
 // ignore: unused_shown_name
 import 'dart:core' show int, double, bool, String, Object, override;
 import 'dart:convert';
+import 'dart:developer' as developer;
 import 'dart:ffi' as ffi;
 import 'package:ffi/ffi.dart' as ffi2 show Arena, calloc;
 import 'package:meta/meta.dart' as meta;
-part 'AttrOpaque1Renamed.g.dart';
 part 'Bar.g.dart';
 part 'BorrowedFields.g.dart';
 part 'BorrowedFieldsReturning.g.dart';
 part 'BorrowedFieldsWithBounds.g.dart';
+part 'BorrowingOptionStruct.g.dart';
 part 'ContiguousEnum.g.dart';
 part 'CyclicStructA.g.dart';
 part 'CyclicStructB.g.dart';
@@ -38,19 +39,20 @@ part 'OptionOpaqueChar.g.dart';
 part 'OptionStruct.g.dart';
 part 'RefList.g.dart';
 part 'RefListParameter.g.dart';
-part 'RenamedAttrEnum.g.dart';
-part 'RenamedAttrOpaque2.g.dart';
-part 'RenamedComparable.g.dart';
-part 'RenamedMyIndexer.g.dart';
-part 'RenamedMyIterable.g.dart';
-part 'RenamedMyIterator.g.dart';
-part 'RenamedOpaqueIterable.g.dart';
-part 'RenamedOpaqueIterator.g.dart';
 part 'ResultOpaque.g.dart';
 part 'Two.g.dart';
 part 'UnimportedEnum.g.dart';
 part 'Unnamespaced.g.dart';
 part 'Utf16Wrap.g.dart';
+part 'ns/AttrOpaque1Renamed.g.dart';
+part 'ns/RenamedAttrEnum.g.dart';
+part 'ns/RenamedAttrOpaque2.g.dart';
+part 'ns/RenamedComparable.g.dart';
+part 'ns/RenamedMyIndexer.g.dart';
+part 'ns/RenamedMyIterable.g.dart';
+part 'ns/RenamedMyIterator.g.dart';
+part 'ns/RenamedOpaqueIterable.g.dart';
+part 'ns/RenamedOpaqueIterator.g.dart';
 
 /// A [Rune] is a Unicode code point, such as `a`, or `💡`.
 /// 
@@ -75,6 +77,11 @@ final _nopFree = core.Finalizer((nothing) => {});
 // ignore: unused_element
 final _rustFree = core.Finalizer((({ffi.Pointer<ffi.Void> pointer, int bytes, int align}) record) => _diplomat_free(record.pointer, record.bytes, record.align));
 
+// Owns the memory of `'static` slices passed to Rust. Rust may hold on to these for
+// the rest of the program, so this arena is never released.
+// ignore: unused_element
+final _staticArena = ffi2.Arena();
+
 // ignore: unused_element
 final class _RustAlloc implements ffi.Allocator {
   @override
@@ -117,6 +124,95 @@ class _FinalizedArena {
   }
 }
 
+/// The level of a log record emitted by the native library, from the least to the most verbose.
+enum DiplomatLogLevel { off, error, warn, info, debug, trace }
+
+typedef _LogSinkNative = ffi.Void Function(ffi.Uint8, ffi.Pointer<ffi.Uint8>, ffi.Size, ffi.Pointer<ffi.Uint8>, ffi.Size);
+
+ffi.NativeCallable<_LogSinkNative>? _logSink;
+
+/// Routes the `log` records of the native library up to [maxLevel] to [sink], replacing the previous
+/// sink. Passing `null` stops forwarding records.
+///
+/// This requires the library to be built with the `log` feature of `diplomat-runtime`.
+///
+/// The sink is called synchronously, so the library must only log on the thread of the isolate that
+/// registered it, which is the case for records logged while calling into the library.
+void setLogSink(void Function(DiplomatLogLevel level, String target, String message)? sink, {DiplomatLogLevel maxLevel = DiplomatLogLevel.info}) {
+  final old = _logSink;
+  _logSink = sink == null ? null : ffi.NativeCallable<_LogSinkNative>.isolateLocal(
+    (int level, ffi.Pointer<ffi.Uint8> target, int targetLen, ffi.Pointer<ffi.Uint8> message, int messageLen) {
+      sink(
+        DiplomatLogLevel.values[level],
+        Utf8Decoder(allowMalformed: true).convert(target.asTypedList(targetLen)),
+        Utf8Decoder(allowMalformed: true).convert(message.asTypedList(messageLen)),
+      );
+    },
+  );
+  _diplomat_log_set_sink(_logSink?.nativeFunction ?? ffi.nullptr, maxLevel.index);
+  old?.close();
+}
+
+/// Routes the `log` records of the native library up to [maxLevel] to `dart:developer`'s [developer.log],
+/// named after their target. See [setLogSink].
+void useDeveloperLog({DiplomatLogLevel maxLevel = DiplomatLogLevel.info}) {
+  setLogSink((level, target, message) {
+    // The values of `package:logging`'s levels.
+    final value = switch (level) {
+      DiplomatLogLevel.off => 2000,
+      DiplomatLogLevel.error => 1000,
+      DiplomatLogLevel.warn => 900,
+      DiplomatLogLevel.info => 800,
+      DiplomatLogLevel.debug => 500,
+      DiplomatLogLevel.trace => 300,
+    };
+    developer.log(message, name: target, level: value);
+  }, maxLevel: maxLevel);
+}
+
+/// Sets the most verbose level of the native library's log records that are forwarded. See [setLogSink].
+void setLogLevel(DiplomatLogLevel level) {
+  _diplomat_log_set_max_level(level.index);
+}
+
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.NativeFunction<_LogSinkNative>>, ffi.Uint8)>(symbol: 'diplomat_log_set_sink')
+// ignore: non_constant_identifier_names
+external void _diplomat_log_set_sink(ffi.Pointer<ffi.NativeFunction<_LogSinkNative>> sink, int maxLevel);
+
+@ffi.Native<ffi.Void Function(ffi.Uint8)>(symbol: 'diplomat_log_set_max_level', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_log_set_max_level(int level);
+
+/// A report of the objects of the native library that haven't been freed, with one `<name>: <count>`
+/// line per kind of object. Empty if everything was freed.
+///
+/// Requires the library to be built with the `leak-check` feature of `diplomat-runtime`. Objects are
+/// freed by finalizers once they are garbage collected, so this is most useful after disposing arenas.
+String leakReport() {
+  if (_diplomat_leak_count() == 0) {
+    return '';
+  }
+  final write = _Write();
+  _diplomat_leak_report(write._ffi);
+  return write.finalize();
+}
+
+/// Throws a [StateError] listing the objects of the native library that haven't been freed. See [leakReport].
+void assertNoLeaks() {
+  final report = leakReport();
+  if (report.isNotEmpty) {
+    throw StateError('Leaked objects:\n$report');
+  }
+}
+
+@ffi.Native<ffi.Size Function()>(symbol: 'diplomat_leak_count', isLeaf: true)
+// ignore: non_constant_identifier_names
+external int _diplomat_leak_count();
+
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_leak_report', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_leak_report(ffi.Pointer<ffi.Opaque> write);
+
 final class _ResultDoubleVoidUnion extends ffi.Union {
   @ffi.Double()
   external double ok;
@@ -526,9 +622,11 @@ final class _SliceBool extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  core.List<bool> _toDart(core.List<Object> lifetimeEdges) {
+  core.List<bool> _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
     final r = core.Iterable.generate(_length).map((i) => _data[i]).toList(growable: false);
-    if (lifetimeEdges.isEmpty) {
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       _rustFree.attach(r, (pointer: _data.cast(), bytes: _length, align: 1));
     } else {
       _nopFree.attach(r, lifetimeEdges); // Keep lifetime edges alive
@@ -576,9 +674,11 @@ final class _SliceDouble extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  core.List<double> _toDart(core.List<Object> lifetimeEdges) {
+  core.List<double> _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
     final r = _data.asTypedList(_length);
-    if (lifetimeEdges.isEmpty) {
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       _rustFree.attach(r, (pointer: _data.cast(), bytes: _length * 8, align: 8));
     } else {
       _nopFree.attach(r, lifetimeEdges); // Keep lifetime edges alive
@@ -623,9 +723,11 @@ final class _SliceInt16 extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  core.List<int> _toDart(core.List<Object> lifetimeEdges) {
+  core.List<int> _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
     final r = _data.asTypedList(_length);
-    if (lifetimeEdges.isEmpty) {
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       _rustFree.attach(r, (pointer: _data.cast(), bytes: _length * 2, align: 2));
     } else {
       _nopFree.attach(r, lifetimeEdges); // Keep lifetime edges alive
@@ -670,9 +772,11 @@ final class _SliceIsize extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  core.List<int> _toDart(core.List<Object> lifetimeEdges) {
+  core.List<int> _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
     final r = core.Iterable.generate(_length).map((i) => _data[i]).toList(growable: false);
-    if (lifetimeEdges.isEmpty) {
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       _diplomat_free(_data.cast(), _length * ffi.sizeOf<ffi.Size>(), ffi.sizeOf<ffi.Size>());
     } else {
       // Lifetime edges will be cleaned up
@@ -720,9 +824,11 @@ final class _SliceSliceUtf8 extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  core.List<core.String> _toDart(core.List<Object> lifetimeEdges) {
-    final r = core.Iterable.generate(_length).map((i) => _data[i]._toDart(lifetimeEdges)).toList(growable: false);
-    if (lifetimeEdges.isEmpty) {
+  core.List<core.String> _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
+    final r = core.Iterable.generate(_length).map((i) => _data[i]._toDart(lifetimeEdges, isStatic: isStatic)).toList(growable: false);
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       // unsupported
     } else {
       // Lifetime edges will be cleaned up
@@ -770,9 +876,11 @@ final class _SliceUint16 extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  core.List<int> _toDart(core.List<Object> lifetimeEdges) {
+  core.List<int> _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
     final r = _data.asTypedList(_length);
-    if (lifetimeEdges.isEmpty) {
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       _rustFree.attach(r, (pointer: _data.cast(), bytes: _length * 2, align: 2));
     } else {
       _nopFree.attach(r, lifetimeEdges); // Keep lifetime edges alive
@@ -820,9 +928,11 @@ final class _SliceUint8 extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  core.List<int> _toDart(core.List<Object> lifetimeEdges) {
+  core.List<int> _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
     final r = _data.asTypedList(_length);
-    if (lifetimeEdges.isEmpty) {
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       _rustFree.attach(r, (pointer: _data.cast(), bytes: _length, align: 1));
     } else {
       _nopFree.attach(r, lifetimeEdges); // Keep lifetime edges alive
@@ -870,9 +980,11 @@ final class _SliceUsize extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  core.List<int> _toDart(core.List<Object> lifetimeEdges) {
+  core.List<int> _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
     final r = core.Iterable.generate(_length).map((i) => _data[i]).toList(growable: false);
-    if (lifetimeEdges.isEmpty) {
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       _diplomat_free(_data.cast(), _length * ffi.sizeOf<ffi.Size>(), ffi.sizeOf<ffi.Size>());
     } else {
       // Lifetime edges will be cleaned up
@@ -920,9 +1032,11 @@ final class _SliceUtf16 extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  String _toDart(core.List<Object> lifetimeEdges) {
+  String _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
     final r = core.String.fromCharCodes(_data.asTypedList(_length));
-    if (lifetimeEdges.isEmpty) {
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       _diplomat_free(_data.cast(), _length * 2, 2);
     } else {
       // Lifetime edges will be cleaned up
@@ -967,9 +1081,11 @@ final class _SliceUtf8 extends ffi.Struct {
   int get hashCode => _length.hashCode;
 
   // ignore: unused_element
-  String _toDart(core.List<Object> lifetimeEdges) {
+  String _toDart(core.List<Object> lifetimeEdges, {bool isStatic = false}) {
     final r = Utf8Decoder().convert(_data.asTypedList(_length));
-    if (lifetimeEdges.isEmpty) {
+    if (isStatic) {
+      // `'static` slices are never freed
+    } else if (lifetimeEdges.isEmpty) {
       _diplomat_free(_data.cast(), _length, 1);
     } else {
       // Lifetime edges will be cleaned up
@@ -989,6 +1105,196 @@ extension on String {
   }
 }
 
+/// Checks that the structs and their fields are laid out like the ones the Rust library was built with,
+/// throwing a [core.StateError] listing the ones that aren't.
+@meta.visibleForTesting
+void checkStructLayouts() {
+  final is64Bit = ffi.sizeOf<ffi.Pointer>() == 8;
+  final mismatches = <String>[];
+  void check(String name, int size, int size32, int size64) {
+    final expected = is64Bit ? size64 : size32;
+    if (size != expected) {
+      mismatches.add('$name is $size bytes instead of $expected');
+    }
+  }
+  void checkOffset(String name, int offset, int offset32, int offset64) {
+    final expected = is64Bit ? offset64 : offset32;
+    if (offset != expected) {
+      mismatches.add('$name is at offset $offset instead of $expected');
+    }
+  }
+  // Finds a field by copying it from a struct of ones into a zeroed one
+  int offsetOf(int size, void Function(ffi.Pointer<ffi.Uint8> dst, ffi.Pointer<ffi.Uint8> src) copyField) {
+    final src = ffi2.calloc<ffi.Uint8>(size);
+    final dst = ffi2.calloc<ffi.Uint8>(size);
+    try {
+      src.asTypedList(size).fillRange(0, size, 1);
+      copyField(dst, src);
+      return dst.asTypedList(size).indexWhere((byte) => byte != 0);
+    } finally {
+      ffi2.calloc.free(src);
+      ffi2.calloc.free(dst);
+    }
+  }
+  check('BorrowedFields', ffi.sizeOf<_BorrowedFieldsFfi>(), 24, 48);
+  checkOffset('BorrowedFields.a', offsetOf(ffi.sizeOf<_BorrowedFieldsFfi>(), (dst, src) => dst.cast<_BorrowedFieldsFfi>().ref.a = src.cast<_BorrowedFieldsFfi>().ref.a), 0, 0);
+  checkOffset('BorrowedFields.b', offsetOf(ffi.sizeOf<_BorrowedFieldsFfi>(), (dst, src) => dst.cast<_BorrowedFieldsFfi>().ref.b = src.cast<_BorrowedFieldsFfi>().ref.b), 8, 16);
+  checkOffset('BorrowedFields.c', offsetOf(ffi.sizeOf<_BorrowedFieldsFfi>(), (dst, src) => dst.cast<_BorrowedFieldsFfi>().ref.c = src.cast<_BorrowedFieldsFfi>().ref.c), 16, 32);
+  check('BorrowedFieldsReturning', ffi.sizeOf<_BorrowedFieldsReturningFfi>(), 8, 16);
+  checkOffset('BorrowedFieldsReturning.bytes', offsetOf(ffi.sizeOf<_BorrowedFieldsReturningFfi>(), (dst, src) => dst.cast<_BorrowedFieldsReturningFfi>().ref.bytes = src.cast<_BorrowedFieldsReturningFfi>().ref.bytes), 0, 0);
+  check('BorrowedFieldsWithBounds', ffi.sizeOf<_BorrowedFieldsWithBoundsFfi>(), 24, 48);
+  checkOffset('BorrowedFieldsWithBounds.fieldA', offsetOf(ffi.sizeOf<_BorrowedFieldsWithBoundsFfi>(), (dst, src) => dst.cast<_BorrowedFieldsWithBoundsFfi>().ref.fieldA = src.cast<_BorrowedFieldsWithBoundsFfi>().ref.fieldA), 0, 0);
+  checkOffset('BorrowedFieldsWithBounds.fieldB', offsetOf(ffi.sizeOf<_BorrowedFieldsWithBoundsFfi>(), (dst, src) => dst.cast<_BorrowedFieldsWithBoundsFfi>().ref.fieldB = src.cast<_BorrowedFieldsWithBoundsFfi>().ref.fieldB), 8, 16);
+  checkOffset('BorrowedFieldsWithBounds.fieldC', offsetOf(ffi.sizeOf<_BorrowedFieldsWithBoundsFfi>(), (dst, src) => dst.cast<_BorrowedFieldsWithBoundsFfi>().ref.fieldC = src.cast<_BorrowedFieldsWithBoundsFfi>().ref.fieldC), 16, 32);
+  check('BorrowingOptionStruct', ffi.sizeOf<_BorrowingOptionStructFfi>(), 28, 32);
+  checkOffset('BorrowingOptionStruct.input', offsetOf(ffi.sizeOf<_BorrowingOptionStructFfi>(), (dst, src) => dst.cast<_BorrowingOptionStructFfi>().ref.input = src.cast<_BorrowingOptionStructFfi>().ref.input), 0, 0);
+  checkOffset('BorrowingOptionStruct.opaque', offsetOf(ffi.sizeOf<_BorrowingOptionStructFfi>(), (dst, src) => dst.cast<_BorrowingOptionStructFfi>().ref.opaque = src.cast<_BorrowingOptionStructFfi>().ref.opaque), 24, 24);
+  check('CyclicStructA', ffi.sizeOf<_CyclicStructAFfi>(), 1, 1);
+  checkOffset('CyclicStructA.a', offsetOf(ffi.sizeOf<_CyclicStructAFfi>(), (dst, src) => dst.cast<_CyclicStructAFfi>().ref.a = src.cast<_CyclicStructAFfi>().ref.a), 0, 0);
+  check('CyclicStructB', ffi.sizeOf<_CyclicStructBFfi>(), 1, 1);
+  checkOffset('CyclicStructB.field', offsetOf(ffi.sizeOf<_CyclicStructBFfi>(), (dst, src) => dst.cast<_CyclicStructBFfi>().ref.field = src.cast<_CyclicStructBFfi>().ref.field), 0, 0);
+  check('ErrorStruct', ffi.sizeOf<_ErrorStructFfi>(), 8, 8);
+  checkOffset('ErrorStruct.i', offsetOf(ffi.sizeOf<_ErrorStructFfi>(), (dst, src) => dst.cast<_ErrorStructFfi>().ref.i = src.cast<_ErrorStructFfi>().ref.i), 0, 0);
+  checkOffset('ErrorStruct.j', offsetOf(ffi.sizeOf<_ErrorStructFfi>(), (dst, src) => dst.cast<_ErrorStructFfi>().ref.j = src.cast<_ErrorStructFfi>().ref.j), 4, 4);
+  check('ImportedStruct', ffi.sizeOf<_ImportedStructFfi>(), 8, 8);
+  checkOffset('ImportedStruct.foo', offsetOf(ffi.sizeOf<_ImportedStructFfi>(), (dst, src) => dst.cast<_ImportedStructFfi>().ref.foo = src.cast<_ImportedStructFfi>().ref.foo), 0, 0);
+  checkOffset('ImportedStruct.count', offsetOf(ffi.sizeOf<_ImportedStructFfi>(), (dst, src) => dst.cast<_ImportedStructFfi>().ref.count = src.cast<_ImportedStructFfi>().ref.count), 4, 4);
+  check('MyStruct', ffi.sizeOf<_MyStructFfi>(), 32, 32);
+  checkOffset('MyStruct.a', offsetOf(ffi.sizeOf<_MyStructFfi>(), (dst, src) => dst.cast<_MyStructFfi>().ref.a = src.cast<_MyStructFfi>().ref.a), 0, 0);
+  checkOffset('MyStruct.b', offsetOf(ffi.sizeOf<_MyStructFfi>(), (dst, src) => dst.cast<_MyStructFfi>().ref.b = src.cast<_MyStructFfi>().ref.b), 1, 1);
+  checkOffset('MyStruct.c', offsetOf(ffi.sizeOf<_MyStructFfi>(), (dst, src) => dst.cast<_MyStructFfi>().ref.c = src.cast<_MyStructFfi>().ref.c), 2, 2);
+  checkOffset('MyStruct.d', offsetOf(ffi.sizeOf<_MyStructFfi>(), (dst, src) => dst.cast<_MyStructFfi>().ref.d = src.cast<_MyStructFfi>().ref.d), 8, 8);
+  checkOffset('MyStruct.e', offsetOf(ffi.sizeOf<_MyStructFfi>(), (dst, src) => dst.cast<_MyStructFfi>().ref.e = src.cast<_MyStructFfi>().ref.e), 16, 16);
+  checkOffset('MyStruct.f', offsetOf(ffi.sizeOf<_MyStructFfi>(), (dst, src) => dst.cast<_MyStructFfi>().ref.f = src.cast<_MyStructFfi>().ref.f), 20, 20);
+  checkOffset('MyStruct.g', offsetOf(ffi.sizeOf<_MyStructFfi>(), (dst, src) => dst.cast<_MyStructFfi>().ref.g = src.cast<_MyStructFfi>().ref.g), 24, 24);
+  check('NestedBorrowedFields', ffi.sizeOf<_NestedBorrowedFieldsFfi>(), 72, 144);
+  checkOffset('NestedBorrowedFields.fields', offsetOf(ffi.sizeOf<_NestedBorrowedFieldsFfi>(), (dst, src) => dst.cast<_NestedBorrowedFieldsFfi>().ref.fields = src.cast<_NestedBorrowedFieldsFfi>().ref.fields), 0, 0);
+  checkOffset('NestedBorrowedFields.bounds', offsetOf(ffi.sizeOf<_NestedBorrowedFieldsFfi>(), (dst, src) => dst.cast<_NestedBorrowedFieldsFfi>().ref.bounds = src.cast<_NestedBorrowedFieldsFfi>().ref.bounds), 24, 48);
+  checkOffset('NestedBorrowedFields.bounds2', offsetOf(ffi.sizeOf<_NestedBorrowedFieldsFfi>(), (dst, src) => dst.cast<_NestedBorrowedFieldsFfi>().ref.bounds2 = src.cast<_NestedBorrowedFieldsFfi>().ref.bounds2), 48, 96);
+  check('OptionInputStruct', ffi.sizeOf<_OptionInputStructFfi>(), 20, 20);
+  checkOffset('OptionInputStruct.a', offsetOf(ffi.sizeOf<_OptionInputStructFfi>(), (dst, src) => dst.cast<_OptionInputStructFfi>().ref.a = src.cast<_OptionInputStructFfi>().ref.a), 0, 0);
+  checkOffset('OptionInputStruct.b', offsetOf(ffi.sizeOf<_OptionInputStructFfi>(), (dst, src) => dst.cast<_OptionInputStructFfi>().ref.b = src.cast<_OptionInputStructFfi>().ref.b), 4, 4);
+  checkOffset('OptionInputStruct.c', offsetOf(ffi.sizeOf<_OptionInputStructFfi>(), (dst, src) => dst.cast<_OptionInputStructFfi>().ref.c = src.cast<_OptionInputStructFfi>().ref.c), 12, 12);
+  check('OptionStruct', ffi.sizeOf<_OptionStructFfi>(), 16, 32);
+  checkOffset('OptionStruct.a', offsetOf(ffi.sizeOf<_OptionStructFfi>(), (dst, src) => dst.cast<_OptionStructFfi>().ref.a = src.cast<_OptionStructFfi>().ref.a), 0, 0);
+  checkOffset('OptionStruct.b', offsetOf(ffi.sizeOf<_OptionStructFfi>(), (dst, src) => dst.cast<_OptionStructFfi>().ref.b = src.cast<_OptionStructFfi>().ref.b), 4, 8);
+  checkOffset('OptionStruct.c', offsetOf(ffi.sizeOf<_OptionStructFfi>(), (dst, src) => dst.cast<_OptionStructFfi>().ref.c = src.cast<_OptionStructFfi>().ref.c), 8, 16);
+  checkOffset('OptionStruct.d', offsetOf(ffi.sizeOf<_OptionStructFfi>(), (dst, src) => dst.cast<_OptionStructFfi>().ref.d = src.cast<_OptionStructFfi>().ref.d), 12, 24);
+  if (mismatches.isNotEmpty) {
+    throw core.StateError(mismatches.join('\n'));
+  }
+}
+
+typedef _ReadSourceNative = ffi.IntPtr Function(ffi.Pointer<ffi.Void>, ffi.Pointer<ffi.Uint8>, ffi.Size);
+
+/// A `DiplomatRead` that reads from the chunks of a [core.Iterable], for `DiplomatRead` parameters.
+final class _Reader {
+  late final ffi.NativeCallable<_ReadSourceNative> _callable;
+  late final ffi.Pointer<ffi.Opaque> _ffi;
+  core.Object? _error;
+  core.StackTrace? _stackTrace;
+
+  _Reader(core.Iterable<core.List<int>> source) {
+    final chunks = source.iterator;
+    core.List<int> chunk = const [];
+    var offset = 0;
+    _callable = ffi.NativeCallable<_ReadSourceNative>.isolateLocal(
+      (ffi.Pointer<ffi.Void> _, ffi.Pointer<ffi.Uint8> buf, int len) {
+        if (_error != null) {
+          return -1;
+        }
+        try {
+          while (offset == chunk.length) {
+            if (!chunks.moveNext()) {
+              return 0;
+            }
+            chunk = chunks.current;
+            offset = 0;
+          }
+        } catch (e, s) {
+          _error = e;
+          _stackTrace = s;
+          return -1;
+        }
+        final read = chunk.length - offset < len ? chunk.length - offset : len;
+        buf.asTypedList(read).setRange(0, read, chunk, offset);
+        offset += read;
+        return read;
+      },
+      exceptionalReturn: -1,
+    );
+    _ffi = _diplomat_read_create(ffi.nullptr, _callable.nativeFunction);
+  }
+
+  /// Frees the reader, and rethrows the error thrown by the source, if any.
+  void finalize() {
+    _diplomat_read_destroy(_ffi);
+    _callable.close();
+    final error = _error;
+    if (error != null) {
+      core.Error.throwWithStackTrace(error, _stackTrace!);
+    }
+  }
+}
+
+@meta.RecordUse()
+@ffi.Native<ffi.Pointer<ffi.Opaque> Function(ffi.Pointer<ffi.Void>, ffi.Pointer<ffi.NativeFunction<_ReadSourceNative>>)>(symbol: 'diplomat_read_create', isLeaf: true)
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Opaque> _diplomat_read_create(ffi.Pointer<ffi.Void> context, ffi.Pointer<ffi.NativeFunction<_ReadSourceNative>> read);
+
+@meta.RecordUse()
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_read_destroy', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_read_destroy(ffi.Pointer<ffi.Opaque> ptr);
+
+typedef _WriteSinkNative = ffi.Bool Function(ffi.Pointer<ffi.Void>, ffi.Pointer<ffi.Uint8>, ffi.Size);
+
+/// A `DiplomatWrite` that adds its output to a [core.Sink] as it is written, for methods marked `stream`.
+final class _StreamWrite {
+  late final ffi.NativeCallable<_WriteSinkNative> _callable;
+  late final ffi.Pointer<ffi.Opaque> _ffi;
+  core.Object? _error;
+  core.StackTrace? _stackTrace;
+
+  _StreamWrite(core.Sink<core.List<int>> sink) {
+    _callable = ffi.NativeCallable<_WriteSinkNative>.isolateLocal(
+      (ffi.Pointer<ffi.Void> _, ffi.Pointer<ffi.Uint8> chunk, int len) {
+        try {
+          // The chunk is only valid during the call
+          sink.add(Uint8List.fromList(chunk.asTypedList(len)));
+          return true;
+        } catch (e, s) {
+          _error = e;
+          _stackTrace = s;
+          return false;
+        }
+      },
+      exceptionalReturn: false,
+    );
+    _ffi = _diplomat_stream_write_create(ffi.nullptr, _callable.nativeFunction, 1024);
+  }
+
+  /// Frees the write, and rethrows the error thrown by the sink, if any.
+  void finalize() {
+    _diplomat_stream_write_destroy(_ffi);
+    _callable.close();
+    final error = _error;
+    if (error != null) {
+      core.Error.throwWithStackTrace(error, _stackTrace!);
+    }
+  }
+}
+
+@meta.RecordUse()
+@ffi.Native<ffi.Pointer<ffi.Opaque> Function(ffi.Pointer<ffi.Void>, ffi.Pointer<ffi.NativeFunction<_WriteSinkNative>>, ffi.Size)>(symbol: 'diplomat_stream_write_create', isLeaf: true)
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Opaque> _diplomat_stream_write_create(ffi.Pointer<ffi.Void> context, ffi.Pointer<ffi.NativeFunction<_WriteSinkNative>> sink, int cap);
+
+@meta.RecordUse()
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_stream_write_destroy', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_stream_write_destroy(ffi.Pointer<ffi.Opaque> ptr);
+
 final class _Write {
   final ffi.Pointer<ffi.Opaque> _ffi;
 
@@ -1026,3 +1332,41 @@ external ffi.Pointer<ffi.Uint8> _diplomat_buffer_write_get_bytes(ffi.Pointer<ffi
 @ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_buffer_write_destroy', isLeaf: true)
 // ignore: non_constant_identifier_names
 external void _diplomat_buffer_write_destroy(ffi.Pointer<ffi.Opaque> ptr);
+
+final class _Write16 {
+  final ffi.Pointer<ffi.Opaque> _ffi;
+
+  _Write16() : _ffi = _diplomat_buffer_write16_create(0);
+  
+  String finalize() {
+    try {
+      final buf = _diplomat_buffer_write16_get_units(_ffi);
+      if (buf == ffi.Pointer.fromAddress(0)) {
+        throw core.OutOfMemoryError();
+      }
+      return core.String.fromCharCodes(buf.asTypedList(_diplomat_buffer_write16_len(_ffi)));
+    } finally {
+      _diplomat_buffer_write16_destroy(_ffi);
+    }
+  }
+}
+
+@meta.RecordUse()
+@ffi.Native<ffi.Pointer<ffi.Opaque> Function(ffi.Size)>(symbol: 'diplomat_buffer_write16_create', isLeaf: true)
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Opaque> _diplomat_buffer_write16_create(int len);
+
+@meta.RecordUse()
+@ffi.Native<ffi.Size Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_buffer_write16_len', isLeaf: true)
+// ignore: non_constant_identifier_names
+external int _diplomat_buffer_write16_len(ffi.Pointer<ffi.Opaque> ptr);
+
+@meta.RecordUse()
+@ffi.Native<ffi.Pointer<ffi.Uint16> Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_buffer_write16_get_units', isLeaf: true)
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Uint16> _diplomat_buffer_write16_get_units(ffi.Pointer<ffi.Opaque> ptr);
+
+@meta.RecordUse()
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_buffer_write16_destroy', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_buffer_write16_destroy(ffi.Pointer<ffi.Opaque> ptr);
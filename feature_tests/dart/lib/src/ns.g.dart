@@ -0,0 +1,5 @@
+// generated by diplomat-tool
+
+export 'lib.g.dart' show AttrOpaque1Renamed, RenamedAttrOpaque2, RenamedComparable, RenamedMyIndexer, RenamedMyIterable, RenamedMyIterator, RenamedOpaqueIterable, RenamedOpaqueIterator, RenamedAttrEnum;
+
+
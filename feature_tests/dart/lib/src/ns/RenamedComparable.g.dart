@@ -1,6 +1,6 @@
 // generated by diplomat-tool
 
-part of 'lib.g.dart';
+part of '../lib.g.dart';
 
 final class RenamedComparable implements ffi.Finalizable, core.Comparable<RenamedComparable> {
   final ffi.Pointer<ffi.Opaque> _ffi;
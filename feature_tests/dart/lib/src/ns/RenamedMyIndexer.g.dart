@@ -1,6 +1,6 @@
 // generated by diplomat-tool
 
-part of 'lib.g.dart';
+part of '../lib.g.dart';
 
 final class RenamedMyIndexer implements ffi.Finalizable {
   final ffi.Pointer<ffi.Opaque> _ffi;
@@ -21,6 +21,12 @@ final class RenamedMyIndexer implements ffi.Finalizable {
 
   static final _finalizer = ffi.NativeFinalizer(ffi.Native.addressOf(_namespace_MyIndexer_destroy));
 
+  static RenamedMyIndexer new_(String words) {
+    final temp = _FinalizedArena();
+    final result = _namespace_MyIndexer_new(words._utf8AllocIn(temp.arena));
+    return RenamedMyIndexer._fromFfi(result, []);
+  }
+
   String? operator [](int i) {
     // This lifetime edge depends on lifetimes: 'a
     core.List<Object> aEdges = [this];
@@ -37,6 +43,11 @@ final class RenamedMyIndexer implements ffi.Finalizable {
 // ignore: non_constant_identifier_names
 external void _namespace_MyIndexer_destroy(ffi.Pointer<ffi.Void> self);
 
+@meta.RecordUse()
+@ffi.Native<ffi.Pointer<ffi.Opaque> Function(_SliceUtf8)>(isLeaf: true, symbol: 'namespace_MyIndexer_new')
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Opaque> _namespace_MyIndexer_new(_SliceUtf8 words);
+
 @meta.RecordUse()
 @ffi.Native<_ResultSliceUtf8Void Function(ffi.Pointer<ffi.Opaque>, ffi.Size)>(isLeaf: true, symbol: 'namespace_MyIndexer_get')
 // ignore: non_constant_identifier_names
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
 
-part of 'lib.g.dart';
+part of '../lib.g.dart';
 
 final class RenamedMyIterator implements ffi.Finalizable, core.Iterator<int> {
   final ffi.Pointer<ffi.Opaque> _ffi;
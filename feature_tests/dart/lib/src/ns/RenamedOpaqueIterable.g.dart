@@ -1,6 +1,6 @@
 // generated by diplomat-tool
 
-part of 'lib.g.dart';
+part of '../lib.g.dart';
 
 final class RenamedOpaqueIterable with core.Iterable<AttrOpaque1Renamed> implements ffi.Finalizable {
   final ffi.Pointer<ffi.Opaque> _ffi;
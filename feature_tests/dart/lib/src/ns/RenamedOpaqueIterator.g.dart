@@ -1,6 +1,6 @@
 // generated by diplomat-tool
 
-part of 'lib.g.dart';
+part of '../lib.g.dart';
 
 final class RenamedOpaqueIterator implements ffi.Finalizable, core.Iterator<AttrOpaque1Renamed> {
   final ffi.Pointer<ffi.Opaque> _ffi;
@@ -0,0 +1,8 @@
+import 'package:feature_tests/lib.dart';
+import 'package:test/test.dart';
+
+void main() {
+    test("Struct layouts match the Rust library", () {
+        checkStructLayouts();
+    });
+}
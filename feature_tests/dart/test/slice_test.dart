@@ -1,5 +1,6 @@
 import 'package:feature_tests/lib.dart';
 import 'package:test/test.dart';
+import 'dart:convert';
 import 'dart:typed_data';
 
 void main() {
@@ -36,4 +37,57 @@ void main() {
     final s = MyString.newFromFirst(["foo", "bar"]);
     expect(s.str, "foo");
   });
+
+  test("static slice", () {
+    expect(MyString.staticLookupTable(), [1, 2, 3, 5, 8, 13]);
+  });
+
+  test("UTF-16 write", () {
+    expect(Utf16Wrap("Non-BMP: 𐐷").toLossyString(), "Non-BMP: 𐐷");
+  });
+
+  test("Streamed write", () {
+    final o = Opaque.fromStr("abc");
+    final sink = _ChunkSink();
+    o.repeatTo(500, sink);
+    expect(sink.chunks.length, greaterThan(1));
+    expect(utf8.decode(sink.chunks.expand((c) => c).toList()), o.repeat(500));
+  });
+
+  test("Streamed write rethrows sink errors", () {
+    final o = Opaque.fromStr("abc");
+    expect(() => o.repeatTo(500, _ChunkSink(fail: true)), throwsStateError);
+  });
+
+  test("Read from chunks", () {
+    final input = ["a string longer ", "", "than one read ", "餐"].map(utf8.encode);
+    expect(Opaque.fromRead(input)!.getDebugStr(), '"a string longer than one read 餐"');
+  });
+
+  test("Read rethrows source errors", () {
+    Iterable<List<int>> source() sync* {
+      yield utf8.encode("abc");
+      throw StateError("source closed");
+    }
+
+    expect(() => Opaque.fromRead(source()), throwsStateError);
+  });
+}
+
+class _ChunkSink implements Sink<List<int>> {
+  final bool fail;
+  final chunks = <List<int>>[];
+
+  _ChunkSink({this.fail = false});
+
+  @override
+  void add(List<int> data) {
+    if (fail) {
+      throw StateError("sink closed");
+    }
+    chunks.add(data);
+  }
+
+  @override
+  void close() {}
 }
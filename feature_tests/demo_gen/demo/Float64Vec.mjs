@@ -3,7 +3,7 @@ export function toString(v) {
     return (function (...args) { return args[0].toString(...args.slice(1)) }).apply(
         null,
         [
-            Float64Vec.newFromOwned.apply(
+            ((...args) => new Float64Vec(...args)).apply(
                 null,
                 [
                     v
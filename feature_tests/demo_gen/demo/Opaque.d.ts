@@ -1,2 +1,3 @@
 import { Opaque } from "../../js/api/index.mjs"
 export function getDebugStr();
+export function repeat(count: number);
@@ -3,7 +3,7 @@ export function getDebugStr() {
     return (function (...args) { return args[0].getDebugStr(...args.slice(1)) }).apply(
         null,
         [
-            Opaque.new_.apply(
+            ((...args) => new Opaque(...args)).apply(
                 null,
                 [
                 ]
@@ -11,3 +11,16 @@ export function getDebugStr() {
         ]
     );
 }
+export function repeat(count) {
+    return (function (...args) { return args[0].repeat(...args.slice(1)) }).apply(
+        null,
+        [
+            ((...args) => new Opaque(...args)).apply(
+                null,
+                [
+                ]
+            ),
+            count
+        ]
+    );
+}
@@ -1,2 +1,3 @@
 import { Utf16Wrap } from "../../js/api/index.mjs"
 export function getDebugStr(input: string);
+export function toLossyString(input: string);
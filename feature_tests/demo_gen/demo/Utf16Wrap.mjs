@@ -3,7 +3,20 @@ export function getDebugStr(input) {
     return (function (...args) { return args[0].getDebugStr(...args.slice(1)) }).apply(
         null,
         [
-            Utf16Wrap.fromUtf16.apply(
+            ((...args) => new Utf16Wrap(...args)).apply(
+                null,
+                [
+                    input
+                ]
+            )
+        ]
+    );
+}
+export function toLossyString(input) {
+    return (function (...args) { return args[0].toLossyString(...args.slice(1)) }).apply(
+        null,
+        [
+            ((...args) => new Utf16Wrap(...args)).apply(
                 null,
                 [
                     input
@@ -37,7 +37,8 @@ let termini = Object.assign({
             {
                 name: "Self:V",
                 type: "Array<number>",
-                typeUse: "Array<number>"
+                typeUse: "Array<number>",
+                step: "any"
             }
             
         ]
@@ -82,6 +83,24 @@ let termini = Object.assign({
         ]
     },
 
+    "Opaque.repeat": {
+        func: OpaqueDemo.repeat,
+        // For avoiding webpacking minifying issues:
+        funcName: "Opaque.repeat",
+        parameters: [
+            
+            {
+                name: "Count",
+                type: "number",
+                typeUse: "number",
+                min: 0,
+                max: 4294967295,
+                step: 1
+            }
+            
+        ]
+    },
+
     "Utf16Wrap.getDebugStr": {
         func: Utf16WrapDemo.getDebugStr,
         // For avoiding webpacking minifying issues:
@@ -94,6 +113,21 @@ let termini = Object.assign({
                 typeUse: "string"
             }
             
+        ]
+    },
+
+    "Utf16Wrap.toLossyString": {
+        func: Utf16WrapDemo.toLossyString,
+        // For avoiding webpacking minifying issues:
+        funcName: "Utf16Wrap.toLossyString",
+        parameters: [
+            
+            {
+                name: "Self:Input",
+                type: "string",
+                typeUse: "string"
+            }
+            
         ]
     }
 });
@@ -0,0 +1,13 @@
+// generated by diplomat-tool
+import type { OptionInputStruct } from "./OptionInputStruct"
+import type { OptionOpaque } from "./OptionOpaque"
+import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+
+export class BorrowingOptionStruct {
+
+    get input() : OptionInputStruct | null;
+    
+
+    get opaque() : OptionOpaque | null;
+    
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+// generated by diplomat-tool
+import { OptionInputStruct } from "./OptionInputStruct.mjs"
+import { OptionOpaque } from "./OptionOpaque.mjs"
+import wasm from "./diplomat-wasm.mjs";
+import * as diplomatRuntime from "./diplomat-runtime.mjs";
+
+export class BorrowingOptionStruct {
+
+    #input;
+    get input()  {
+        return this.#input;
+    }
+    
+
+    #opaque;
+    get opaque()  {
+        return this.#opaque;
+    }
+    
+    constructor(structObj, internalConstructor) {
+        if (typeof structObj !== "object") {
+            throw new Error("BorrowingOptionStruct's constructor takes an object of BorrowingOptionStruct's fields.");
+        }
+
+        if (internalConstructor !== diplomatRuntime.internalConstructor) {
+            throw new Error("BorrowingOptionStruct is an out struct and can only be created internally.");
+        }
+        if ("input" in structObj) {
+            this.#input = structObj.input;
+        } else {
+            this.#input = null;
+        }
+
+        if ("opaque" in structObj) {
+            this.#opaque = structObj.opaque;
+        } else {
+            throw new Error("Missing required field opaque.");
+        }
+
+    }
+
+    // Return this struct in FFI function friendly format.
+    // Returns an array that can be expanded with spread syntax (...)
+    // If this struct contains any slices, their lifetime-edge-relevant information will be
+    // set up here, and can be appended to any relevant lifetime arrays here. <lifetime>AppendArray accepts a list
+    // of arrays for each lifetime to do so. It accepts multiple lists per lifetime in case the caller needs to tie a lifetime to multiple
+    // output arrays. Null is equivalent to an empty list: this lifetime is not being borrowed from.
+    _intoFFI(
+        functionCleanupArena,
+        appendArrayMap
+    ) {
+        return [...diplomatRuntime.optionToArgsForCalling(this.#input, 20, 4, false, (arrayBuffer, offset, jsValue) => [jsValue._writeToArrayBuffer(arrayBuffer, offset + 0, functionCleanupArena, {})]), this.#opaque.ffiValue ?? 0]
+    }
+
+    _writeToArrayBuffer(
+        arrayBuffer,
+        offset,
+        functionCleanupArena,
+        appendArrayMap
+    ) {
+        diplomatRuntime.writeOptionToArrayBuffer(arrayBuffer, offset + 0, this.#input, 20, 4, (arrayBuffer, offset, jsValue) => jsValue._writeToArrayBuffer(arrayBuffer, offset + 0, functionCleanupArena, {}));
+        diplomatRuntime.writeToArrayBuffer(arrayBuffer, offset + 24, this.#opaque.ffiValue ?? 0, Uint32Array);
+    }
+
+    static _fromFFI(internalConstructor, ptr, aEdges) {
+        if (internalConstructor !== diplomatRuntime.internalConstructor) {
+            throw new Error("BorrowingOptionStruct._fromFFI is not meant to be called externally. Please use the default constructor.");
+        }
+        var structObj = {};
+        const inputDeref = ptr;
+        structObj.input = diplomatRuntime.readOption(wasm, inputDeref, 20, (wasm, offset) => { const deref = offset; return OptionInputStruct._fromFFI(diplomatRuntime.internalConstructor, deref) });
+        const opaqueDeref = diplomatRuntime.ptrRead(wasm, ptr + 24);
+        structObj.opaque = opaqueDeref === 0 ? null : new OptionOpaque(diplomatRuntime.internalConstructor, opaqueDeref, aEdges);
+
+        return new BorrowingOptionStruct(structObj, internalConstructor);
+    }
+
+    // Return all fields corresponding to lifetime `'a` 
+    // without handling lifetime dependencies (this is the job of the caller)
+    // This is all fields that may be borrowed from if borrowing `'a`,
+    // assuming that there are no `'other: a`. bounds. In case of such bounds,
+    // the caller should take care to also call _fieldsForLifetimeOther
+    get _fieldsForLifetimeA() { 
+        return [opaque];
+    };
+}
\ No newline at end of file
@@ -0,0 +1,10 @@
+// generated by diplomat-tool
+import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+
+export class FallibleUnitConstructor {
+    
+
+    get ffiValue(): pointer;
+
+    constructor(i: number);
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+// generated by diplomat-tool
+import wasm from "./diplomat-wasm.mjs";
+import * as diplomatRuntime from "./diplomat-runtime.mjs";
+
+const FallibleUnitConstructor_box_destroy_registry = new FinalizationRegistry((ptr) => {
+    wasm.FallibleUnitConstructor_destroy(ptr);
+});
+
+export class FallibleUnitConstructor {
+    // Internal ptr reference:
+    #ptr = null;
+
+    // Lifetimes are only to keep dependencies alive.
+    // Since JS won't garbage collect until there are no incoming edges.
+    #selfEdge = [];
+    
+    constructor(symbol, ptr, selfEdge) {
+        if (symbol !== diplomatRuntime.internalConstructor) {
+            return this.#defaultConstructor(...arguments);
+        }
+        
+        this.#ptr = ptr;
+        this.#selfEdge = selfEdge;
+        
+        // Are we being borrowed? If not, we can register.
+        if (this.#selfEdge.length === 0) {
+            FallibleUnitConstructor_box_destroy_registry.register(this, this.#ptr);
+        }
+    }
+
+    get ffiValue() {
+        return this.#ptr;
+    }
+
+    #defaultConstructor(i) {
+        const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 5, 4, true);
+        
+        const result = wasm.FallibleUnitConstructor_new(diplomatReceive.buffer, i);
+    
+        try {
+            if (!diplomatReceive.resultFlag) {
+                throw new globalThis.Error('Failed to construct FallibleUnitConstructor');
+            }
+            return new FallibleUnitConstructor(diplomatRuntime.internalConstructor, diplomatRuntime.ptrRead(wasm, diplomatReceive.buffer), []);
+        }
+        
+        finally {
+            diplomatReceive.free();
+        }
+    }
+}
\ No newline at end of file
@@ -6,21 +6,21 @@ export class Float64Vec {
 
     get ffiValue(): pointer;
 
-    static newBool(v: Array<boolean>): Float64Vec;
+    static bool(v: Array<boolean>): Float64Vec;
 
-    static newI16(v: Array<number>): Float64Vec;
+    static i16(v: Array<number>): Float64Vec;
 
-    static newU16(v: Array<number>): Float64Vec;
+    static u16(v: Array<number>): Float64Vec;
 
-    static newIsize(v: Array<number>): Float64Vec;
+    static isize(v: Array<number>): Float64Vec;
 
-    static newUsize(v: Array<number>): Float64Vec;
+    static usize(v: Array<number>): Float64Vec;
 
-    static newF64BeBytes(v: Uint8Array): Float64Vec;
+    static f64BeBytes(v: Uint8Array): Float64Vec;
 
-    static newFromOwned(v: Array<number>): Float64Vec;
+    constructor(v: Array<number>);
 
-    get asSlice(): Array<number>;
+    get asSlice(): Float64Array;
 
     fillSlice(v: Array<number>): void;
 
@@ -28,7 +28,10 @@ export class Float64Vec {
 
     toString(): string;
 
-    borrow(): Array<number>;
+    borrow(): Float64Array;
 
-    get(i: number): number | null;
+    at(i: number): number | null;
+
+    
+    [Symbol.iterator]() : Iterator<number>;
 }
\ No newline at end of file
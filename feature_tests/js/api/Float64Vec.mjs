@@ -16,8 +16,7 @@ export class Float64Vec {
     
     constructor(symbol, ptr, selfEdge) {
         if (symbol !== diplomatRuntime.internalConstructor) {
-            console.error("Float64Vec is an Opaque type. You cannot call its constructor.");
-            return;
+            return this.#defaultConstructor(...arguments);
         }
         
         this.#ptr = ptr;
@@ -33,7 +32,7 @@ export class Float64Vec {
         return this.#ptr;
     }
 
-    static newBool(v) {
+    static bool(v) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const vSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.slice(wasm, v, "boolean"));
@@ -49,7 +48,7 @@ export class Float64Vec {
         }
     }
 
-    static newI16(v) {
+    static i16(v) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const vSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.slice(wasm, v, "i16"));
@@ -65,7 +64,7 @@ export class Float64Vec {
         }
     }
 
-    static newU16(v) {
+    static u16(v) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const vSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.slice(wasm, v, "u16"));
@@ -81,7 +80,7 @@ export class Float64Vec {
         }
     }
 
-    static newIsize(v) {
+    static isize(v) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const vSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.slice(wasm, v, "i32"));
@@ -97,7 +96,7 @@ export class Float64Vec {
         }
     }
 
-    static newUsize(v) {
+    static usize(v) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const vSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.slice(wasm, v, "u32"));
@@ -113,7 +112,7 @@ export class Float64Vec {
         }
     }
 
-    static newF64BeBytes(v) {
+    static f64BeBytes(v) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const vSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.slice(wasm, v, "u8"));
@@ -129,7 +128,7 @@ export class Float64Vec {
         }
     }
 
-    static newFromOwned(v) {
+    #defaultConstructor(v) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const vSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.slice(wasm, v, "f64"));
@@ -154,7 +153,7 @@ export class Float64Vec {
         const result = wasm.Float64Vec_as_slice(diplomatReceive.buffer, this.ffiValue);
     
         try {
-            return Array.from(new diplomatRuntime.DiplomatSlicePrimitive(wasm, diplomatReceive.buffer, "f64", aEdges).getValue());
+            return new diplomatRuntime.DiplomatSlicePrimitive(wasm, diplomatReceive.buffer, "f64", aEdges).getView();
         }
         
         finally {
@@ -210,7 +209,7 @@ export class Float64Vec {
         const result = wasm.Float64Vec_borrow(diplomatReceive.buffer, this.ffiValue);
     
         try {
-            return Array.from(new diplomatRuntime.DiplomatSlicePrimitive(wasm, diplomatReceive.buffer, "f64", aEdges).getValue());
+            return new diplomatRuntime.DiplomatSlicePrimitive(wasm, diplomatReceive.buffer, "f64", aEdges).getView();
         }
         
         finally {
@@ -218,7 +217,7 @@ export class Float64Vec {
         }
     }
 
-    get(i) {
+    at(i) {
         const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 9, 8, true);
         
         const result = wasm.Float64Vec_get(diplomatReceive.buffer, this.ffiValue, i);
@@ -234,4 +233,15 @@ export class Float64Vec {
             diplomatReceive.free();
         }
     }
+
+    
+    *[Symbol.iterator]() {
+        for (let i = 0; ; i++) {
+            const item = this.at(i);
+            if (item === null) {
+                return;
+            }
+            yield item;
+        }
+    }
 }
\ No newline at end of file
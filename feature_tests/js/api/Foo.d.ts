@@ -10,10 +10,12 @@ export class Foo {
 
     get ffiValue(): pointer;
 
-    static new_(x: string): Foo;
+    constructor(x: string);
 
     get bar(): Bar;
 
+    static static(x: string): Foo;
+
     asReturning(): BorrowedFieldsReturning;
 
     static extractFromFields(fields: BorrowedFields): Foo;
@@ -21,8 +21,7 @@ export class Foo {
     
     constructor(symbol, ptr, selfEdge, aEdge) {
         if (symbol !== diplomatRuntime.internalConstructor) {
-            console.error("Foo is an Opaque type. You cannot call its constructor.");
-            return;
+            return this.#defaultConstructor(...arguments);
         }
         
         
@@ -41,7 +40,7 @@ export class Foo {
         return this.#ptr;
     }
 
-    static new_(x) {
+    #defaultConstructor(x) {
         let functionGarbageCollectorGrip = new diplomatRuntime.GarbageCollectorGrip();
         const xSlice = functionGarbageCollectorGrip.alloc(diplomatRuntime.DiplomatBuf.str8(wasm, x));
         
@@ -75,6 +74,25 @@ export class Foo {
         finally {}
     }
 
+    static static(x) {
+        let functionCleanupArena = new diplomatRuntime.CleanupArena();
+        
+        const xSlice = diplomatRuntime.leakingArena.alloc(diplomatRuntime.DiplomatBuf.str8(wasm, x));
+        
+        // This lifetime edge depends on lifetimes 'a
+        let aEdges = [];
+        
+        const result = wasm.Foo_new_static(...xSlice.splat());
+    
+        try {
+            return new Foo(diplomatRuntime.internalConstructor, result, [], aEdges);
+        }
+        
+        finally {
+            functionCleanupArena.free();
+        }
+    }
+
     asReturning() {
         const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 8, 4, false);
         
@@ -6,9 +6,9 @@ export class MyString {
 
     get ffiValue(): pointer;
 
-    static new_(v: string): MyString;
+    constructor(v: string);
 
-    static newUnsafe(v: string): MyString;
+    static unsafe(v: string): MyString;
 
     static newOwned(v: string): MyString;
 
@@ -21,4 +21,6 @@ export class MyString {
     static stringTransform(foo: string): string;
 
     borrow(): string;
+
+    static staticLookupTable(): Uint16Array;
 }
\ No newline at end of file
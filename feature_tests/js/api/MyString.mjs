@@ -16,8 +16,7 @@ export class MyString {
     
     constructor(symbol, ptr, selfEdge) {
         if (symbol !== diplomatRuntime.internalConstructor) {
-            console.error("MyString is an Opaque type. You cannot call its constructor.");
-            return;
+            return this.#defaultConstructor(...arguments);
         }
         
         this.#ptr = ptr;
@@ -33,7 +32,7 @@ export class MyString {
         return this.#ptr;
     }
 
-    static new_(v) {
+    #defaultConstructor(v) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const vSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.str8(wasm, v));
@@ -49,7 +48,7 @@ export class MyString {
         }
     }
 
-    static newUnsafe(v) {
+    static unsafe(v) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const vSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.str8(wasm, v));
@@ -158,4 +157,18 @@ export class MyString {
             diplomatReceive.free();
         }
     }
+
+    static staticLookupTable() {
+        const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 8, 4, false);
+        
+        const result = wasm.MyString_static_lookup_table(diplomatReceive.buffer);
+    
+        try {
+            return new diplomatRuntime.DiplomatSlicePrimitive(wasm, diplomatReceive.buffer, "u16", []).getView();
+        }
+        
+        finally {
+            diplomatReceive.free();
+        }
+    }
 }
\ No newline at end of file
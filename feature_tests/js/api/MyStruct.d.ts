@@ -35,9 +35,9 @@ export class MyStruct {
 
     get g() : MyEnum;
     set g(value: MyEnum); 
-    constructor(structObj : MyStruct_Obj);
+    static fromFields(structObj : MyStruct_Obj) : MyStruct;
 
-    static new_(): MyStruct;
+    constructor();
 
     intoA(): number;
 
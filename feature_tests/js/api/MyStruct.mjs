@@ -61,7 +61,15 @@ export class MyStruct {
     set g(value) {
         this.#g = value;
     }
-    constructor(structObj) {
+    // `new MyStruct(...)` calls the Rust constructor, so construction from fields goes through here.
+    static fromFields(structObj) {
+        return new MyStruct(structObj, diplomatRuntime.exposeConstructor);
+    }
+    constructor(structObj, internalConstructor) {
+        if (internalConstructor !== diplomatRuntime.internalConstructor && internalConstructor !== diplomatRuntime.exposeConstructor) {
+            return this.#defaultConstructor(...arguments);
+        }
+
         if (typeof structObj !== "object") {
             throw new Error("MyStruct's constructor takes an object of MyStruct's fields.");
         }
@@ -163,7 +171,7 @@ export class MyStruct {
         return new MyStruct(structObj, internalConstructor);
     }
 
-    static new_() {
+    #defaultConstructor() {
         const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 32, 8, false);
         
         const result = wasm.MyStruct_new(diplomatReceive.buffer);
@@ -1,8 +1,10 @@
 // generated by diplomat-tool
 import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
 
-export class RenamedAttrOpaque2 {
+export class NullableConstructor {
     
 
     get ffiValue(): pointer;
+
+    constructor(i: number);
 }
\ No newline at end of file
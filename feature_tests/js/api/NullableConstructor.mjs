@@ -0,0 +1,47 @@
+// generated by diplomat-tool
+import wasm from "./diplomat-wasm.mjs";
+import * as diplomatRuntime from "./diplomat-runtime.mjs";
+
+const NullableConstructor_box_destroy_registry = new FinalizationRegistry((ptr) => {
+    wasm.NullableConstructor_destroy(ptr);
+});
+
+export class NullableConstructor {
+    // Internal ptr reference:
+    #ptr = null;
+
+    // Lifetimes are only to keep dependencies alive.
+    // Since JS won't garbage collect until there are no incoming edges.
+    #selfEdge = [];
+    
+    constructor(symbol, ptr, selfEdge) {
+        if (symbol !== diplomatRuntime.internalConstructor) {
+            return this.#defaultConstructor(...arguments);
+        }
+        
+        this.#ptr = ptr;
+        this.#selfEdge = selfEdge;
+        
+        // Are we being borrowed? If not, we can register.
+        if (this.#selfEdge.length === 0) {
+            NullableConstructor_box_destroy_registry.register(this, this.#ptr);
+        }
+    }
+
+    get ffiValue() {
+        return this.#ptr;
+    }
+
+    #defaultConstructor(i) {
+        const result = wasm.NullableConstructor_new(i);
+    
+        try {
+            if (result === 0) {
+                throw new globalThis.Error('Failed to construct NullableConstructor');
+            }
+            return result === 0 ? null : new NullableConstructor(diplomatRuntime.internalConstructor, result, []);
+        }
+        
+        finally {}
+    }
+}
\ No newline at end of file
@@ -8,14 +8,20 @@ export class Opaque {
 
     get ffiValue(): pointer;
 
-    static new_(): Opaque;
+    constructor();
 
     static tryFromUtf8(input: string): Opaque | null;
 
     static fromStr(input: string): Opaque;
 
+    static fromRead(read: Uint8Array | Iterable<Uint8Array>): Opaque | null;
+
     getDebugStr(): string;
 
+    repeat(count: number): string;
+
+    repeatTo(count: number, sink: (chunk: Uint8Array) => boolean | void): void;
+
     assertStruct(s: MyStruct): void;
 
     static returnsUsize(): number;
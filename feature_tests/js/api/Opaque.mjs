@@ -18,8 +18,7 @@ export class Opaque {
     
     constructor(symbol, ptr, selfEdge) {
         if (symbol !== diplomatRuntime.internalConstructor) {
-            console.error("Opaque is an Opaque type. You cannot call its constructor.");
-            return;
+            return this.#defaultConstructor(...arguments);
         }
         
         this.#ptr = ptr;
@@ -35,7 +34,7 @@ export class Opaque {
         return this.#ptr;
     }
 
-    static new_() {
+    #defaultConstructor() {
         const result = wasm.Opaque_new();
     
         try {
@@ -77,6 +76,21 @@ export class Opaque {
         }
     }
 
+    static fromRead(read) {
+        const readReader = new diplomatRuntime.DiplomatReader(wasm, read);
+        
+        const result = wasm.Opaque_from_read(readReader.buffer);
+    
+        try {
+            readReader.finish();
+            return result === 0 ? null : new Opaque(diplomatRuntime.internalConstructor, result, []);
+        }
+        
+        finally {
+            readReader.free();
+        }
+    }
+
     getDebugStr() {
         const write = new diplomatRuntime.DiplomatWriteBuf(wasm);
         wasm.Opaque_get_debug_str(this.ffiValue, write.buffer);
@@ -90,6 +104,32 @@ export class Opaque {
         }
     }
 
+    repeat(count) {
+        const write = new diplomatRuntime.DiplomatWriteBuf(wasm);
+        wasm.Opaque_repeat(this.ffiValue, count, write.buffer);
+    
+        try {
+            return write.readString8();
+        }
+        
+        finally {
+            write.free();
+        }
+    }
+
+    repeatTo(count, sink) {
+        const write = new diplomatRuntime.DiplomatStreamWrite(wasm, sink);
+        wasm.Opaque_repeat(this.ffiValue, count, write.buffer);
+    
+        try {
+            write.finish();
+        }
+        
+        finally {
+            write.free();
+        }
+    }
+
     assertStruct(s) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         wasm.Opaque_assert_struct(this.ffiValue, ...s._intoFFI(functionCleanupArena, {}));
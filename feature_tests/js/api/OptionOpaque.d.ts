@@ -1,4 +1,5 @@
 // generated by diplomat-tool
+import type { BorrowingOptionStruct } from "./BorrowingOptionStruct"
 import type { OptionEnum } from "./OptionEnum"
 import type { OptionInputStruct } from "./OptionInputStruct"
 import type { OptionStruct } from "./OptionStruct"
@@ -38,4 +39,8 @@ export class OptionOpaque {
     static acceptsOptionInputStruct(arg: OptionInputStruct | null): OptionInputStruct | null;
 
     static returnsOptionInputStruct(): OptionInputStruct;
+
+    borrowingOptionStruct(): BorrowingOptionStruct;
+
+    borrowingOptionStructNones(): BorrowingOptionStruct;
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 // generated by diplomat-tool
+import { BorrowingOptionStruct } from "./BorrowingOptionStruct.mjs"
 import { OptionEnum } from "./OptionEnum.mjs"
 import { OptionInputStruct } from "./OptionInputStruct.mjs"
 import { OptionStruct } from "./OptionStruct.mjs"
@@ -254,4 +255,38 @@ export class OptionOpaque {
             diplomatReceive.free();
         }
     }
+
+    borrowingOptionStruct() {
+        const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 28, 4, false);
+        
+        // This lifetime edge depends on lifetimes 'a
+        let aEdges = [this];
+        
+        const result = wasm.OptionOpaque_borrowing_option_struct(diplomatReceive.buffer, this.ffiValue);
+    
+        try {
+            return BorrowingOptionStruct._fromFFI(diplomatRuntime.internalConstructor, diplomatReceive.buffer, aEdges);
+        }
+        
+        finally {
+            diplomatReceive.free();
+        }
+    }
+
+    borrowingOptionStructNones() {
+        const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 28, 4, false);
+        
+        // This lifetime edge depends on lifetimes 'a
+        let aEdges = [this];
+        
+        const result = wasm.OptionOpaque_borrowing_option_struct_nones(diplomatReceive.buffer, this.ffiValue);
+    
+        try {
+            return BorrowingOptionStruct._fromFFI(diplomatRuntime.internalConstructor, diplomatReceive.buffer, aEdges);
+        }
+        
+        finally {
+            diplomatReceive.free();
+        }
+    }
 }
\ No newline at end of file
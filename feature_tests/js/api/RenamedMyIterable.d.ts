@@ -1,13 +0,0 @@
-// generated by diplomat-tool
-import type { RenamedMyIterator } from "./RenamedMyIterator"
-import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
-
-export class RenamedMyIterable {
-    
-
-    get ffiValue(): pointer;
-
-    static new_(x: Array<number>): RenamedMyIterable;
-
-    [Symbol.iterator](): RenamedMyIterator;
-}
\ No newline at end of file
@@ -8,15 +8,15 @@ export class ResultOpaque {
 
     get ffiValue(): pointer;
 
-    static new_(i: number): ResultOpaque;
+    constructor(i: number);
 
-    static newFailingFoo(): ResultOpaque;
+    static failingFoo(): ResultOpaque;
 
-    static newFailingBar(): ResultOpaque;
+    static failingBar(): ResultOpaque;
 
     static newFailingUnit(): ResultOpaque | null;
 
-    static newFailingStruct(i: number): ResultOpaque;
+    static failingStruct(i: number): ResultOpaque;
 
     static newInErr(i: number): void;
 
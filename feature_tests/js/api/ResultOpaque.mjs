@@ -18,8 +18,7 @@ export class ResultOpaque {
     
     constructor(symbol, ptr, selfEdge) {
         if (symbol !== diplomatRuntime.internalConstructor) {
-            console.error("ResultOpaque is an Opaque type. You cannot call its constructor.");
-            return;
+            return this.#defaultConstructor(...arguments);
         }
         
         this.#ptr = ptr;
@@ -35,7 +34,7 @@ export class ResultOpaque {
         return this.#ptr;
     }
 
-    static new_(i) {
+    #defaultConstructor(i) {
         const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 5, 4, true);
         
         const result = wasm.ResultOpaque_new(diplomatReceive.buffer, i);
@@ -53,7 +52,7 @@ export class ResultOpaque {
         }
     }
 
-    static newFailingFoo() {
+    static failingFoo() {
         const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 5, 4, true);
         
         const result = wasm.ResultOpaque_new_failing_foo(diplomatReceive.buffer);
@@ -71,7 +70,7 @@ export class ResultOpaque {
         }
     }
 
-    static newFailingBar() {
+    static failingBar() {
         const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 5, 4, true);
         
         const result = wasm.ResultOpaque_new_failing_bar(diplomatReceive.buffer);
@@ -106,7 +105,7 @@ export class ResultOpaque {
         }
     }
 
-    static newFailingStruct(i) {
+    static failingStruct(i) {
         const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 9, 4, true);
         
         const result = wasm.ResultOpaque_new_failing_struct(diplomatReceive.buffer, i);
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
-import type { AttrOpaque1Renamed } from "./AttrOpaque1Renamed"
-import type { RenamedAttrEnum } from "./RenamedAttrEnum"
+import type { AttrOpaque1Renamed } from "./ns/AttrOpaque1Renamed"
+import type { RenamedAttrEnum } from "./ns/RenamedAttrEnum"
 import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
 
 export class Unnamespaced {
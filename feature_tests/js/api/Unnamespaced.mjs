@@ -1,6 +1,6 @@
 // generated by diplomat-tool
-import { AttrOpaque1Renamed } from "./AttrOpaque1Renamed.mjs"
-import { RenamedAttrEnum } from "./RenamedAttrEnum.mjs"
+import { AttrOpaque1Renamed } from "./ns/AttrOpaque1Renamed.mjs"
+import { RenamedAttrEnum } from "./ns/RenamedAttrEnum.mjs"
 import wasm from "./diplomat-wasm.mjs";
 import * as diplomatRuntime from "./diplomat-runtime.mjs";
 
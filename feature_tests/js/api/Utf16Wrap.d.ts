@@ -6,9 +6,11 @@ export class Utf16Wrap {
 
     get ffiValue(): pointer;
 
-    static fromUtf16(input: string): Utf16Wrap;
+    constructor(input: string);
 
     getDebugStr(): string;
 
+    toLossyString(): string;
+
     borrowCont(): string;
 }
\ No newline at end of file
@@ -16,8 +16,7 @@ export class Utf16Wrap {
     
     constructor(symbol, ptr, selfEdge) {
         if (symbol !== diplomatRuntime.internalConstructor) {
-            console.error("Utf16Wrap is an Opaque type. You cannot call its constructor.");
-            return;
+            return this.#defaultConstructor(...arguments);
         }
         
         this.#ptr = ptr;
@@ -33,7 +32,7 @@ export class Utf16Wrap {
         return this.#ptr;
     }
 
-    static fromUtf16(input) {
+    #defaultConstructor(input) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const inputSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.str16(wasm, input));
@@ -62,6 +61,19 @@ export class Utf16Wrap {
         }
     }
 
+    toLossyString() {
+        const write = new diplomatRuntime.DiplomatWrite16Buf(wasm);
+        wasm.Utf16Wrap_to_lossy_string(this.ffiValue, write.buffer);
+    
+        try {
+            return write.readString16();
+        }
+        
+        finally {
+            write.free();
+        }
+    }
+
     borrowCont() {
         const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 8, 4, false);
         
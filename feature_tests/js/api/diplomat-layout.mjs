@@ -0,0 +1,63 @@
+import { wasmModule } from "./diplomat-wasm.mjs";
+
+// The size, alignment and field offsets the bindings use for each struct
+const expectedLayouts = {
+    "BigStructWithStuff": [20, 4, 0, 2, 4, 8, 16],
+    "BorrowedFields": [24, 4, 0, 8, 16],
+    "BorrowedFieldsReturning": [8, 4, 0],
+    "BorrowedFieldsWithBounds": [24, 4, 0, 8, 16],
+    "BorrowingOptionStruct": [28, 4, 0, 24],
+    "CyclicStructA": [1, 1, 0],
+    "CyclicStructB": [1, 1, 0],
+    "ErrorStruct": [8, 4, 0, 4],
+    "ImportedStruct": [8, 4, 0, 4],
+    "MyStruct": [32, 8, 0, 1, 2, 8, 16, 20, 24],
+    "NestedBorrowedFields": [72, 4, 0, 24, 48],
+    "OptionInputStruct": [20, 4, 0, 4, 12],
+    "OptionStruct": [16, 4, 0, 4, 8, 12],
+    "ScalarPairWithPadding": [8, 4, 0, 4],
+};
+
+/**
+ * Checks that the structs are laid out like the ones the WebAssembly module was built with,
+ * which `#[diplomat::bridge]` records in its `diplomat_layout` custom section.
+ *
+ * Throws an `Error` listing the structs that aren't.
+ */
+export function checkStructLayouts() {
+    const actualLayouts = new Map();
+    for (const section of WebAssembly.Module.customSections(wasmModule, "diplomat_layout")) {
+        // Each struct is recorded as little-endian `u32`s: the length of its name followed by
+        // the name, then the number of values that follow, its size, alignment and field offsets.
+        const view = new DataView(section);
+        let pos = 0;
+        const nextU32 = () => {
+            const value = view.getUint32(pos, true);
+            pos += 4;
+            return value;
+        };
+        while (pos < view.byteLength) {
+            const nameLength = nextU32();
+            const name = new TextDecoder().decode(new Uint8Array(section, pos, nameLength));
+            pos += nameLength;
+            const layout = [];
+            for (let count = nextU32(); count > 0; count--) {
+                layout.push(nextU32());
+            }
+            actualLayouts.set(name, layout);
+        }
+    }
+
+    const mismatches = [];
+    for (const [name, expected] of Object.entries(expectedLayouts)) {
+        const actual = actualLayouts.get(name);
+        if (actual === undefined) {
+            mismatches.push(`${name} is missing from the diplomat_layout section`);
+        } else if (actual.join() !== expected.join()) {
+            mismatches.push(`${name} has the size, alignment and field offsets [${actual}] instead of [${expected}]`);
+        }
+    }
+    if (mismatches.length > 0) {
+        throw new Error(mismatches.join("\n"));
+    }
+}
\ No newline at end of file
@@ -1,18 +1,44 @@
 /** For internal Diplomat use when constructing opaques or structs. */
 export const internalConstructor = Symbol("constructor");
+/** For internal Diplomat use when constructing structs from their fields, when `new` calls a Rust constructor. */
+export const exposeConstructor = Symbol("exposeConstructor");
+
+/**
+ * The size of pointers and `usize` in bytes: 4 on wasm32, 8 on wasm64 (memory64).
+ *
+ * Set by diplomat-tool from the `pointer-width` library config option.
+ * On wasm64, WebAssembly takes and returns pointers and `usize`s as `bigint`s.
+ */
+export const POINTER_WIDTH = 4;
+
+/**
+ * Convert a JS `number` to a `usize` (or pointer) that can be passed to WebAssembly.
+ * @param {number} value
+ * @returns {number | bigint}
+ */
+export function usize(value) {
+    return POINTER_WIDTH === 8 ? BigInt(value) : value;
+}
+
+/**
+ * The typed array type that pointers and `usize`s are read from and written to memory with.
+ */
+function usizeArray() {
+    return POINTER_WIDTH === 8 ? BigUint64Array : Uint32Array;
+}
 
 export function readString8(wasm, ptr, len) {
-    const buf = new Uint8Array(wasm.memory.buffer, ptr, len);
+    const buf = new Uint8Array(wasm.memory.buffer, Number(ptr), Number(len));
     return (new TextDecoder("utf-8")).decode(buf)
 }
 
 export function readString16(wasm, ptr, len) {
-    const buf = new Uint16Array(wasm.memory.buffer, ptr, len);
+    const buf = new Uint16Array(wasm.memory.buffer, Number(ptr), Number(len));
     return String.fromCharCode.apply(null, buf)
 }
 
 export function withDiplomatWrite(wasm, callback) {
-    const write = wasm.diplomat_buffer_write_create(0);
+    const write = wasm.diplomat_buffer_write_create(usize(0));
     try {
     callback(write);
     const outStringPtr = wasm.diplomat_buffer_write_get_bytes(write);
@@ -38,25 +64,25 @@ export function withDiplomatWrite(wasm, callback) {
  * one of those types is another ptr. Call this method to get access to the returned
  * ptr, so the return buffer can be freed.
  * @param {WebAssembly.Exports} wasm Provided by diplomat generated files. 
- * @param {number} ptr Pointer of a pointer, to be read.
- * @returns {number} The underlying pointer.
+ * @param {number | bigint} ptr Pointer of a pointer, to be read.
+ * @returns {number | bigint} The underlying pointer, a `bigint` on wasm64.
  */
 export function ptrRead(wasm, ptr) {
-    return (new Uint32Array(wasm.memory.buffer, ptr, 1))[0];
+    return (new (usizeArray())(wasm.memory.buffer, Number(ptr), 1))[0];
 }
 
 /** 
  * Get the flag of a result type.
  */
 export function resultFlag(wasm, ptr, offset) {
-    return (new Uint8Array(wasm.memory.buffer, ptr + offset, 1))[0];
+    return (new Uint8Array(wasm.memory.buffer, Number(ptr) + offset, 1))[0];
 }
 
 /** 
  * Get the discriminant of a Rust enum.
 */
 export function enumDiscriminant(wasm, ptr) {
-    return (new Int32Array(wasm.memory.buffer, ptr, 1))[0]
+    return (new Int32Array(wasm.memory.buffer, Number(ptr), 1))[0]
 }
 
 /**
@@ -130,7 +156,8 @@ export function optionToArgsForCalling(jsValue, size, align, needsPaddingFields,
         args = Array.from(buffer);
         args.push(1);
     } else {
-        args = Array(size / align).fill(0);
+        // Like the values read from `buffer` above, 8-byte fields (like pointers on wasm64) are `bigint`s.
+        args = Array(size / align).fill(align == 8 ? 0n : 0);
         args.push(0);
     }
 
@@ -179,24 +206,24 @@ export class DiplomatBuf {
         }
     }
 
-    const ptr = wasm.diplomat_alloc(utf8Length, 1);
+    const ptr = wasm.diplomat_alloc(usize(utf8Length), usize(1));
 
-    const result = (new TextEncoder()).encodeInto(string, new Uint8Array(wasm.memory.buffer, ptr, utf8Length));
+    const result = (new TextEncoder()).encodeInto(string, new Uint8Array(wasm.memory.buffer, Number(ptr), utf8Length));
     console.assert(string.length === result.read && utf8Length === result.written, "UTF-8 write error");
 
-    return new DiplomatBuf(ptr, utf8Length, () => wasm.diplomat_free(ptr, utf8Length, 1));
+    return new DiplomatBuf(ptr, utf8Length, () => wasm.diplomat_free(ptr, usize(utf8Length), usize(1)));
     }
 
     static str16 = (wasm, string) => {
     const byteLength = string.length * 2;
-    const ptr = wasm.diplomat_alloc(byteLength, 2);
+    const ptr = wasm.diplomat_alloc(usize(byteLength), usize(2));
 
-    const destination = new Uint16Array(wasm.memory.buffer, ptr, string.length);
+    const destination = new Uint16Array(wasm.memory.buffer, Number(ptr), string.length);
     for (let i = 0; i < string.length; i++) {
         destination[i] = string.charCodeAt(i);
     }
 
-    return new DiplomatBuf(ptr, string.length, () => wasm.diplomat_free(ptr, byteLength, 2));
+    return new DiplomatBuf(ptr, string.length, () => wasm.diplomat_free(ptr, usize(byteLength), usize(2)));
     }
 
     static slice = (wasm, list, rustType) => {
@@ -206,36 +233,37 @@ export class DiplomatBuf {
             4;
 
     const byteLength = list.length * elementSize;
-    const ptr = wasm.diplomat_alloc(byteLength, elementSize);
+    const ptr = wasm.diplomat_alloc(usize(byteLength), usize(elementSize));
+    const offset = Number(ptr);
 
     /** 
      * Create an array view of the buffer. This gives us the `set` method which correctly handles untyped values
      */
     const destination =
-        rustType === "u8" || rustType === "boolean" ? new Uint8Array(wasm.memory.buffer, ptr, byteLength) :
-        rustType === "i8" ? new Int8Array(wasm.memory.buffer, ptr, byteLength) :
-            rustType === "u16" ? new Uint16Array(wasm.memory.buffer, ptr, byteLength) :
-            rustType === "i16" ? new Int16Array(wasm.memory.buffer, ptr, byteLength) :
-                rustType === "i32" ? new Int32Array(wasm.memory.buffer, ptr, byteLength) :
-                rustType === "u64" ? new BigUint64Array(wasm.memory.buffer, ptr, byteLength) :
-                    rustType === "i64" ? new BigInt64Array(wasm.memory.buffer, ptr, byteLength) :
-                    rustType === "f32" ? new Float32Array(wasm.memory.buffer, ptr, byteLength) :
-                        rustType === "f64" ? new Float64Array(wasm.memory.buffer, ptr, byteLength) :
-                        new Uint32Array(wasm.memory.buffer, ptr, byteLength);
+        rustType === "u8" || rustType === "boolean" ? new Uint8Array(wasm.memory.buffer, offset, list.length) :
+        rustType === "i8" ? new Int8Array(wasm.memory.buffer, offset, list.length) :
+            rustType === "u16" ? new Uint16Array(wasm.memory.buffer, offset, list.length) :
+            rustType === "i16" ? new Int16Array(wasm.memory.buffer, offset, list.length) :
+                rustType === "i32" ? new Int32Array(wasm.memory.buffer, offset, list.length) :
+                rustType === "u64" ? new BigUint64Array(wasm.memory.buffer, offset, list.length) :
+                    rustType === "i64" ? new BigInt64Array(wasm.memory.buffer, offset, list.length) :
+                    rustType === "f32" ? new Float32Array(wasm.memory.buffer, offset, list.length) :
+                        rustType === "f64" ? new Float64Array(wasm.memory.buffer, offset, list.length) :
+                        new Uint32Array(wasm.memory.buffer, offset, list.length);
     destination.set(list);
 
-    return new DiplomatBuf(ptr, list.length, () => wasm.diplomat_free(ptr, byteLength, elementSize));
+    return new DiplomatBuf(ptr, list.length, () => wasm.diplomat_free(ptr, usize(byteLength), usize(elementSize)));
     }
 
     
     static strs = (wasm, strings, encoding) => {
         let encodeStr = (encoding === "string16") ? DiplomatBuf.str16 : DiplomatBuf.str8;
 
-        const byteLength = strings.length * 4 * 2;
+        const byteLength = strings.length * POINTER_WIDTH * 2;
 
-        const ptr = wasm.diplomat_alloc(byteLength, 4);
+        const ptr = wasm.diplomat_alloc(usize(byteLength), usize(POINTER_WIDTH));
 
-        const destination = new Uint32Array(wasm.memory.buffer, ptr, byteLength);
+        const destination = new (usizeArray())(wasm.memory.buffer, Number(ptr), strings.length * 2);
 
         const stringsAlloc = [];
 
@@ -243,11 +271,11 @@ export class DiplomatBuf {
             stringsAlloc.push(encodeStr(wasm, strings[i]));
 
             destination[2 * i] = stringsAlloc[i].ptr;
-            destination[(2 * i) + 1] = stringsAlloc[i].size;
+            destination[(2 * i) + 1] = usize(stringsAlloc[i].size);
         }
 
         return new DiplomatBuf(ptr, strings.length, () => {
-            wasm.diplomat_free(ptr, byteLength, 4);
+            wasm.diplomat_free(ptr, usize(byteLength), usize(POINTER_WIDTH));
             for (let i = 0; i < stringsAlloc.length; i++) {
                 stringsAlloc[i].free();
             }
@@ -270,15 +298,15 @@ export class DiplomatBuf {
     }
 
     splat() {
-        return [this.ptr, this.size];
+        return [this.ptr, usize(this.size)];
     }
 
     /**
      * Write the (ptr, len) pair to an array buffer at byte offset `offset`
      */
     writePtrLenToArrayBuffer(arrayBuffer, offset) {
-        writeToArrayBuffer(arrayBuffer, offset, this.ptr, Uint32Array);
-        writeToArrayBuffer(arrayBuffer, offset + 4, this.size, Uint32Array);
+        writeToArrayBuffer(arrayBuffer, offset, this.ptr, usizeArray());
+        writeToArrayBuffer(arrayBuffer, offset + POINTER_WIDTH, usize(this.size), usizeArray());
     }
 }
 
@@ -294,7 +322,7 @@ export class DiplomatWriteBuf {
 
     constructor(wasm) {
         this.#wasm = wasm;
-        this.#buffer = this.#wasm.diplomat_buffer_write_create(0);
+        this.#buffer = this.#wasm.diplomat_buffer_write_create(usize(0));
 
         this.leak = () => { };
     }
@@ -324,6 +352,173 @@ export class DiplomatWriteBuf {
     }
 }
 
+/**
+ * Like {@link DiplomatWriteBuf}, for methods that write UTF-16 to a `DiplomatWrite16`.
+ * The units can be turned into a string without decoding UTF-8.
+ */
+export class DiplomatWrite16Buf {
+    leak;
+
+    #wasm;
+    #buffer;
+
+    constructor(wasm) {
+        this.#wasm = wasm;
+        this.#buffer = this.#wasm.diplomat_buffer_write16_create(usize(0));
+
+        this.leak = () => { };
+    }
+    
+    free() {
+        this.#wasm.diplomat_buffer_write16_destroy(this.#buffer);
+    }
+
+    releaseToGarbageCollector() {
+        DiplomatBufferFinalizer.register(this, this.free);
+    }
+
+    readString16() {
+        return readString16(this.#wasm, this.ptr, this.size);
+    }
+
+    get buffer() {
+        return this.#buffer;
+    }
+
+    get ptr() {
+        return this.#wasm.diplomat_buffer_write16_get_units(this.#buffer);
+    }
+
+    get size() {
+        return this.#wasm.diplomat_buffer_write16_len(this.#buffer);
+    }
+}
+
+/**
+ * Helper class for the `sink` variants of methods marked `stream`, which pass their output to
+ * `sink` as it is written instead of returning a string.
+ *
+ * `sink` receives chunks of UTF-8, which may end in the middle of a character, and can return
+ * `false` to stop the write. An error thrown by `sink` stops the write and is rethrown by `finish()`.
+ */
+export class DiplomatStreamWrite {
+    static #sinks = new Map();
+    static #nextId = 1;
+
+    #wasm;
+    #buffer;
+    #id;
+    #error;
+
+    constructor(wasm, sink, chunkSize = 1024) {
+        this.#wasm = wasm;
+        this.#id = DiplomatStreamWrite.#nextId++;
+        DiplomatStreamWrite.#sinks.set(this.#id, (chunk) => {
+            try {
+                return sink(chunk) !== false;
+            } catch (e) {
+                this.#error = e;
+                return false;
+            }
+        });
+        this.#buffer = this.#wasm.diplomat_stream_write_create_js(usize(this.#id), usize(chunkSize));
+    }
+
+    /**
+     * Passes a chunk to the sink registered under `id`, called by the `diplomat_stream_write_js` import.
+     */
+    static write(wasm, id, ptr, len) {
+        const sink = DiplomatStreamWrite.#sinks.get(Number(id));
+        // The chunk is copied out of WebAssembly memory, so sinks can hold on to it.
+        return sink(new Uint8Array(wasm.memory.buffer, Number(ptr), Number(len)).slice());
+    }
+
+    get buffer() {
+        return this.#buffer;
+    }
+
+    finish() {
+        if (this.#error !== undefined) {
+            throw this.#error;
+        }
+    }
+
+    free() {
+        this.#wasm.diplomat_stream_write_destroy(this.#buffer);
+        DiplomatStreamWrite.#sinks.delete(this.#id);
+    }
+}
+
+/**
+ * Helper class for `DiplomatRead` parameters, which Rust reads from as it needs more input.
+ *
+ * The source is a `Uint8Array`, or an iterable of `Uint8Array` chunks that is only consumed as far
+ * as Rust reads. WebAssembly calls can't wait for more input, so a `ReadableStream` has to be read
+ * into chunks first, e.g. with `await Array.fromAsync(stream)`. An error thrown by the iterable fails
+ * the read, and is rethrown by `finish()`.
+ */
+export class DiplomatReader {
+    static #sources = new Map();
+    static #nextId = 1;
+
+    #wasm;
+    #buffer;
+    #id;
+    #error;
+
+    constructor(wasm, source) {
+        this.#wasm = wasm;
+        this.#id = DiplomatReader.#nextId++;
+        const chunks = (source instanceof Uint8Array ? [source] : source)[Symbol.iterator]();
+        let chunk = new Uint8Array(0);
+        DiplomatReader.#sources.set(this.#id, (buf) => {
+            if (this.#error !== undefined) {
+                return -1;
+            }
+            try {
+                while (chunk.length === 0) {
+                    const next = chunks.next();
+                    if (next.done) {
+                        return 0;
+                    }
+                    chunk = next.value;
+                }
+            } catch (e) {
+                this.#error = e;
+                return -1;
+            }
+            const len = Math.min(chunk.length, buf.length);
+            buf.set(chunk.subarray(0, len));
+            chunk = chunk.subarray(len);
+            return len;
+        });
+        this.#buffer = this.#wasm.diplomat_read_create_js(usize(this.#id));
+    }
+
+    /**
+     * Reads into a buffer from the source registered under `id`, called by the `diplomat_read_js` import.
+     */
+    static read(wasm, id, ptr, len) {
+        const read = DiplomatReader.#sources.get(Number(id));
+        return usize(read(new Uint8Array(wasm.memory.buffer, Number(ptr), Number(len))));
+    }
+
+    get buffer() {
+        return this.#buffer;
+    }
+
+    finish() {
+        if (this.#error !== undefined) {
+            throw this.#error;
+        }
+    }
+
+    free() {
+        this.#wasm.diplomat_read_destroy(this.#buffer);
+        DiplomatReader.#sources.delete(this.#id);
+    }
+}
+
 /**
  * Represents an underlying slice that we've grabbed from WebAssembly.
  * You can treat this in JS as a regular slice of primitives, but it handles additional data for you behind the scenes.
@@ -336,8 +531,16 @@ export class DiplomatSlice {
         return this.#bufferType;
     }
 
+    #ptr;
+    #size;
+
     #buffer;
     get buffer() {
+        // Growing WebAssembly memory detaches all views of it, so recreate ours if that happened.
+        // The slice itself stays where it is.
+        if (this.#buffer.buffer !== this.#wasm.memory.buffer) {
+            this.#buffer = new this.#bufferType(this.#wasm.memory.buffer, this.#ptr, this.#size);
+        }
         return this.#buffer;
     }
 
@@ -346,16 +549,54 @@ export class DiplomatSlice {
     constructor(wasm, buffer, bufferType, lifetimeEdges) {
         this.#wasm = wasm;
         
-        const [ptr, size] = new Uint32Array(this.#wasm.memory.buffer, buffer, 2);
+        const [ptr, size] = new (usizeArray())(this.#wasm.memory.buffer, Number(buffer), 2);
 
-        this.#buffer = new bufferType(this.#wasm.memory.buffer, ptr, size);
+        this.#ptr = Number(ptr);
+        this.#size = Number(size);
+        this.#buffer = new bufferType(this.#wasm.memory.buffer, this.#ptr, this.#size);
         this.#bufferType = bufferType;
 
         this.#lifetimeEdges = lifetimeEdges;
     }
 
     getValue() {
-        return this.#buffer;
+        return this.buffer;
+    }
+
+    /**
+     * Get a typed array viewing the slice in WebAssembly memory, without copying it.
+     *
+     * The view keeps alive whatever the slice borrows from. Growing WebAssembly memory detaches
+     * plain views of it, so every access goes through {@link DiplomatSlice#buffer}, which
+     * re-derives the view from the current `memory.buffer` when that happens.
+     */
+    getView() {
+        const slice = this;
+        const lifetimeEdges = this.#lifetimeEdges;
+        return new Proxy(this.buffer, {
+            get(_target, prop) {
+                if (prop === "diplomatLifetimeEdges") {
+                    return lifetimeEdges;
+                }
+                const view = slice.buffer;
+                const value = Reflect.get(view, prop, view);
+                // Typed array methods need the real view as `this`, not the proxy.
+                return typeof value === "function" ? value.bind(view) : value;
+            },
+            set(_target, prop, value) {
+                const view = slice.buffer;
+                return Reflect.set(view, prop, value, view);
+            },
+            has(_target, prop) {
+                return Reflect.has(slice.buffer, prop);
+            },
+            ownKeys(_target) {
+                return Reflect.ownKeys(slice.buffer);
+            },
+            getOwnPropertyDescriptor(_target, prop) {
+                return Reflect.getOwnPropertyDescriptor(slice.buffer, prop);
+            }
+        });
     }
 
     [Symbol.toPrimitive]() {
@@ -369,8 +610,6 @@ export class DiplomatSlice {
 
 export class DiplomatSlicePrimitive extends DiplomatSlice {
     constructor(wasm, buffer, sliceType, lifetimeEdges) {
-        const [ptr, size] = new Uint32Array(wasm.memory.buffer, buffer, 2);
-
         let arrayType;
         switch (sliceType) {
             case "u8":
@@ -454,7 +693,7 @@ export class DiplomatSliceStr extends DiplomatSlice {
 export class DiplomatSliceStrings extends DiplomatSlice {
     #strings = [];
     constructor(wasm, buffer, stringEncoding, lifetimeEdges) {
-        super(wasm, buffer, Uint32Array, lifetimeEdges);
+        super(wasm, buffer, usizeArray(), lifetimeEdges);
 
         for (let i = this.buffer.byteOffset; i < this.buffer.byteLength; i += this.buffer.BYTES_PER_ELEMENT * 2) {
             this.#strings.push(new DiplomatSliceStr(wasm, i, stringEncoding, lifetimeEdges));
@@ -489,13 +728,13 @@ export class DiplomatReceiveBuf {
 
         this.#hasResult = hasResult;
 
-        this.#buffer = this.#wasm.diplomat_alloc(this.#size, this.#align);
+        this.#buffer = this.#wasm.diplomat_alloc(usize(this.#size), usize(this.#align));
 
         this.leak = () => { };
     }
     
     free() {
-        this.#wasm.diplomat_free(this.#buffer, this.#size, this.#align);
+        this.#wasm.diplomat_free(this.#buffer, usize(this.#size), usize(this.#align));
     }
     
     get buffer() {
@@ -606,4 +845,77 @@ export class GarbageCollectorGrip {
     }
 }
 
+/**
+ * An arena for slices passed to Rust as `&'static`, which must never be freed.
+ *
+ * Slices allocated here are leaked for the rest of the program.
+ */
+export const leakingArena = {
+    /**
+     * @param {DiplomatBuf} item
+     * @returns {DiplomatBuf}
+     */
+    alloc(item) {
+        return item;
+    }
+};
+
+/**
+ * Throws an `Error` listing the opaques and runtime allocations of the library that haven't been freed.
+ *
+ * Requires the library to be built with the `leak-check` feature of `diplomat-runtime`. Opaques are freed
+ * when they are garbage collected, so tests should run the garbage collector (e.g. `node --expose-gc`)
+ * and let finalizers run before calling this.
+ * @param {WebAssembly.Exports} wasm Provided by diplomat generated files.
+ */
+export function assertNoLeaks(wasm) {
+    if (wasm.diplomat_leak_count === undefined) {
+        throw new Error("The library isn't built with the `leak-check` feature of `diplomat-runtime`");
+    }
+    if (Number(wasm.diplomat_leak_count()) !== 0) {
+        const report = withDiplomatWrite(wasm, (write) => wasm.diplomat_leak_report(write));
+        throw new Error(`Leaked objects:\n${report}`);
+    }
+}
+
+/**
+ * A panic of the Rust library, thrown when the panic hook of `diplomat-runtime` is installed.
+ *
+ * The hook is installed by debug builds, and by release builds when `panic_hook` is set in `diplomat.config.mjs`.
+ */
+export class RustPanicError extends Error {
+    /**
+     * @param {string} message The panic message.
+     * @param {string | null} file The source file that panicked, if known.
+     * @param {number | null} line The line of the panic in `file`, if known.
+     * @param {number | null} column The column of the panic in `file`, if known.
+     */
+    constructor(message, file, line, column) {
+        super(message);
+        this.name = "RustPanicError";
+        this.file = file;
+        this.line = line;
+        this.column = column;
+    }
+
+    /**
+     * The last panic of the library, or `null` if it hasn't panicked since the hook was installed.
+     * @param {WebAssembly.Exports} wasm Provided by diplomat generated files.
+     * @returns {RustPanicError | null}
+     */
+    static last(wasm) {
+        const ptr = wasm.diplomat_last_panic();
+        if (Number(ptr) === 0) {
+            return null;
+        }
+        const [messagePtr, messageLen, filePtr, fileLen] = new (usizeArray())(wasm.memory.buffer, Number(ptr), 4);
+        const [line, column] = new Uint32Array(wasm.memory.buffer, Number(ptr) + 4 * POINTER_WIDTH, 2);
+        const message = readString8(wasm, messagePtr, messageLen);
+        if (Number(fileLen) === 0) {
+            return new RustPanicError(message, null, null, null);
+        }
+        return new RustPanicError(message, readString8(wasm, filePtr, fileLen), line, column);
+    }
+}
+
 const DiplomatBufferFinalizer = new FinalizationRegistry(free => free());
@@ -1,5 +1,6 @@
 
-export { codepoint } from './diplomat-runtime';
+export { codepoint, RustPanicError, assertNoLeaks } from './diplomat-runtime';
+export { checkStructLayouts } from './diplomat-layout';
 
 
 export { ImportedStruct } from "./ImportedStruct"
@@ -28,19 +29,9 @@ export { MyZst } from "./MyZst"
 
 export { ScalarPairWithPadding } from "./ScalarPairWithPadding"
 
-export { OptionStruct } from "./OptionStruct"
-
-export { AttrOpaque1Renamed } from "./AttrOpaque1Renamed"
-
-export { RenamedAttrOpaque2 } from "./RenamedAttrOpaque2"
-
-export { RenamedMyIterable } from "./RenamedMyIterable"
-
-export { RenamedMyIterator } from "./RenamedMyIterator"
-
-export { RenamedOpaqueIterable } from "./RenamedOpaqueIterable"
+export { BorrowingOptionStruct } from "./BorrowingOptionStruct"
 
-export { RenamedOpaqueIterator } from "./RenamedOpaqueIterator"
+export { OptionStruct } from "./OptionStruct"
 
 export { Unnamespaced } from "./Unnamespaced"
 
@@ -52,12 +43,16 @@ export { One } from "./One"
 
 export { Two } from "./Two"
 
+export { NullableConstructor } from "./NullableConstructor"
+
 export { OptionOpaque } from "./OptionOpaque"
 
 export { OptionOpaqueChar } from "./OptionOpaqueChar"
 
 export { OptionString } from "./OptionString"
 
+export { FallibleUnitConstructor } from "./FallibleUnitConstructor"
+
 export { ResultOpaque } from "./ResultOpaque"
 
 export { RefList } from "./RefList"
@@ -74,8 +69,6 @@ export { OpaqueMutexedString } from "./OpaqueMutexedString"
 
 export { Utf16Wrap } from "./Utf16Wrap"
 
-export { RenamedAttrEnum } from "./RenamedAttrEnum"
-
 export { UnimportedEnum } from "./UnimportedEnum"
 
 export { OptionEnum } from "./OptionEnum"
@@ -85,3 +78,5 @@ export { ErrorEnum } from "./ErrorEnum"
 export { ContiguousEnum } from "./ContiguousEnum"
 
 export { MyEnum } from "./MyEnum"
+
+export * as ns from "./ns/index"
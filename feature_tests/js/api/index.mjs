@@ -1,4 +1,7 @@
 
+export { RustPanicError, assertNoLeaks } from './diplomat-runtime.mjs';
+export { checkStructLayouts } from './diplomat-layout.mjs';
+
 
 export { ImportedStruct } from "./ImportedStruct.mjs"
 
@@ -26,19 +29,9 @@ export { MyZst } from "./MyZst.mjs"
 
 export { ScalarPairWithPadding } from "./ScalarPairWithPadding.mjs"
 
-export { OptionStruct } from "./OptionStruct.mjs"
-
-export { AttrOpaque1Renamed } from "./AttrOpaque1Renamed.mjs"
-
-export { RenamedAttrOpaque2 } from "./RenamedAttrOpaque2.mjs"
-
-export { RenamedMyIterable } from "./RenamedMyIterable.mjs"
-
-export { RenamedMyIterator } from "./RenamedMyIterator.mjs"
+export { BorrowingOptionStruct } from "./BorrowingOptionStruct.mjs"
 
-export { RenamedOpaqueIterable } from "./RenamedOpaqueIterable.mjs"
-
-export { RenamedOpaqueIterator } from "./RenamedOpaqueIterator.mjs"
+export { OptionStruct } from "./OptionStruct.mjs"
 
 export { Unnamespaced } from "./Unnamespaced.mjs"
 
@@ -50,12 +43,16 @@ export { One } from "./One.mjs"
 
 export { Two } from "./Two.mjs"
 
+export { NullableConstructor } from "./NullableConstructor.mjs"
+
 export { OptionOpaque } from "./OptionOpaque.mjs"
 
 export { OptionOpaqueChar } from "./OptionOpaqueChar.mjs"
 
 export { OptionString } from "./OptionString.mjs"
 
+export { FallibleUnitConstructor } from "./FallibleUnitConstructor.mjs"
+
 export { ResultOpaque } from "./ResultOpaque.mjs"
 
 export { RefList } from "./RefList.mjs"
@@ -72,8 +69,6 @@ export { OpaqueMutexedString } from "./OpaqueMutexedString.mjs"
 
 export { Utf16Wrap } from "./Utf16Wrap.mjs"
 
-export { RenamedAttrEnum } from "./RenamedAttrEnum.mjs"
-
 export { UnimportedEnum } from "./UnimportedEnum.mjs"
 
 export { OptionEnum } from "./OptionEnum.mjs"
@@ -83,3 +78,5 @@ export { ErrorEnum } from "./ErrorEnum.mjs"
 export { ContiguousEnum } from "./ContiguousEnum.mjs"
 
 export { MyEnum } from "./MyEnum.mjs"
+
+export * as ns from "./ns/index.mjs"
@@ -1,14 +1,14 @@
 // generated by diplomat-tool
-import type { RenamedAttrEnum } from "./RenamedAttrEnum"
-import type { Unnamespaced } from "./Unnamespaced"
-import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+import type { RenamedAttrEnum } from "../ns/RenamedAttrEnum"
+import type { Unnamespaced } from "../Unnamespaced"
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
 
 export class AttrOpaque1Renamed {
     
 
     get ffiValue(): pointer;
 
-    static totallyNotNew(): AttrOpaque1Renamed;
+    constructor();
 
     get methodRenamed(): number;
 
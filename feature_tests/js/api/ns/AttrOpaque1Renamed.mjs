@@ -1,8 +1,8 @@
 // generated by diplomat-tool
-import { RenamedAttrEnum } from "./RenamedAttrEnum.mjs"
-import { Unnamespaced } from "./Unnamespaced.mjs"
-import wasm from "./diplomat-wasm.mjs";
-import * as diplomatRuntime from "./diplomat-runtime.mjs";
+import { RenamedAttrEnum } from "../ns/RenamedAttrEnum.mjs"
+import { Unnamespaced } from "../Unnamespaced.mjs"
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
 
 const AttrOpaque1Renamed_box_destroy_registry = new FinalizationRegistry((ptr) => {
     wasm.namespace_AttrOpaque1_destroy(ptr);
@@ -18,8 +18,7 @@ export class AttrOpaque1Renamed {
     
     constructor(symbol, ptr, selfEdge) {
         if (symbol !== diplomatRuntime.internalConstructor) {
-            console.error("AttrOpaque1Renamed is an Opaque type. You cannot call its constructor.");
-            return;
+            return this.#defaultConstructor(...arguments);
         }
         
         this.#ptr = ptr;
@@ -35,7 +34,7 @@ export class AttrOpaque1Renamed {
         return this.#ptr;
     }
 
-    static totallyNotNew() {
+    #defaultConstructor() {
         const result = wasm.namespace_AttrOpaque1_new();
     
         try {
@@ -0,0 +1,8 @@
+// generated by diplomat-tool
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
+
+export class RenamedAttrOpaque2 {
+    
+
+    get ffiValue(): pointer;
+}
\ No newline at end of file
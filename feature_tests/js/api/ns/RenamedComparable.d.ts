@@ -0,0 +1,15 @@
+// generated by diplomat-tool
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
+
+export class RenamedComparable {
+    
+
+    get ffiValue(): pointer;
+
+    static new_(int: number): RenamedComparable;
+
+    cmp(other: RenamedComparable): number;
+
+    
+    static compare(a: RenamedComparable, b: RenamedComparable) : number;
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+// generated by diplomat-tool
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
+
+const RenamedComparable_box_destroy_registry = new FinalizationRegistry((ptr) => {
+    wasm.namespace_Comparable_destroy(ptr);
+});
+
+export class RenamedComparable {
+    // Internal ptr reference:
+    #ptr = null;
+
+    // Lifetimes are only to keep dependencies alive.
+    // Since JS won't garbage collect until there are no incoming edges.
+    #selfEdge = [];
+    
+    constructor(symbol, ptr, selfEdge) {
+        if (symbol !== diplomatRuntime.internalConstructor) {
+            console.error("RenamedComparable is an Opaque type. You cannot call its constructor.");
+            return;
+        }
+        
+        this.#ptr = ptr;
+        this.#selfEdge = selfEdge;
+        
+        // Are we being borrowed? If not, we can register.
+        if (this.#selfEdge.length === 0) {
+            RenamedComparable_box_destroy_registry.register(this, this.#ptr);
+        }
+    }
+
+    get ffiValue() {
+        return this.#ptr;
+    }
+
+    static new_(int) {
+        const result = wasm.namespace_Comparable_new(int);
+    
+        try {
+            return new RenamedComparable(diplomatRuntime.internalConstructor, result, []);
+        }
+        
+        finally {}
+    }
+
+    cmp(other) {
+        const result = wasm.namespace_Comparable_cmp(this.ffiValue, other.ffiValue);
+    
+        try {
+            return result;
+        }
+        
+        finally {}
+    }
+
+    
+    static compare(a, b) {
+        return a.cmp(b);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+// generated by diplomat-tool
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
+
+export class RenamedMyIndexer {
+    
+
+    get ffiValue(): pointer;
+
+    static new_(words: string): RenamedMyIndexer;
+
+    at(i: number): string | null;
+
+    
+    [Symbol.iterator]() : Iterator<string>;
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+// generated by diplomat-tool
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
+
+const RenamedMyIndexer_box_destroy_registry = new FinalizationRegistry((ptr) => {
+    wasm.namespace_MyIndexer_destroy(ptr);
+});
+
+export class RenamedMyIndexer {
+    // Internal ptr reference:
+    #ptr = null;
+
+    // Lifetimes are only to keep dependencies alive.
+    // Since JS won't garbage collect until there are no incoming edges.
+    #selfEdge = [];
+    
+    constructor(symbol, ptr, selfEdge) {
+        if (symbol !== diplomatRuntime.internalConstructor) {
+            console.error("RenamedMyIndexer is an Opaque type. You cannot call its constructor.");
+            return;
+        }
+        
+        this.#ptr = ptr;
+        this.#selfEdge = selfEdge;
+        
+        // Are we being borrowed? If not, we can register.
+        if (this.#selfEdge.length === 0) {
+            RenamedMyIndexer_box_destroy_registry.register(this, this.#ptr);
+        }
+    }
+
+    get ffiValue() {
+        return this.#ptr;
+    }
+
+    static new_(words) {
+        let functionCleanupArena = new diplomatRuntime.CleanupArena();
+        
+        const wordsSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.str8(wasm, words));
+        
+        const result = wasm.namespace_MyIndexer_new(...wordsSlice.splat());
+    
+        try {
+            return new RenamedMyIndexer(diplomatRuntime.internalConstructor, result, []);
+        }
+        
+        finally {
+            functionCleanupArena.free();
+        }
+    }
+
+    at(i) {
+        const diplomatReceive = new diplomatRuntime.DiplomatReceiveBuf(wasm, 9, 4, true);
+        
+        // This lifetime edge depends on lifetimes 'a
+        let aEdges = [this];
+        
+        const result = wasm.namespace_MyIndexer_get(diplomatReceive.buffer, this.ffiValue, i);
+    
+        try {
+            if (!diplomatReceive.resultFlag) {
+                return null;
+            }
+            return new diplomatRuntime.DiplomatSliceStr(wasm, diplomatReceive.buffer,  "string8", aEdges).getValue();
+        }
+        
+        finally {
+            diplomatReceive.free();
+        }
+    }
+
+    
+    *[Symbol.iterator]() {
+        for (let i = 0; ; i++) {
+            const item = this.at(i);
+            if (item === null) {
+                return;
+            }
+            yield item;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+// generated by diplomat-tool
+import type { RenamedMyIterator } from "../ns/RenamedMyIterator"
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
+
+export class RenamedMyIterable {
+    
+
+    get ffiValue(): pointer;
+
+    constructor(x: Array<number>);
+
+    [Symbol.iterator](): RenamedMyIterator;
+}
\ No newline at end of file
@@ -1,7 +1,7 @@
 // generated by diplomat-tool
-import { RenamedMyIterator } from "./RenamedMyIterator.mjs"
-import wasm from "./diplomat-wasm.mjs";
-import * as diplomatRuntime from "./diplomat-runtime.mjs";
+import { RenamedMyIterator } from "../ns/RenamedMyIterator.mjs"
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
 
 const RenamedMyIterable_box_destroy_registry = new FinalizationRegistry((ptr) => {
     wasm.namespace_MyIterable_destroy(ptr);
@@ -17,8 +17,7 @@ export class RenamedMyIterable {
     
     constructor(symbol, ptr, selfEdge) {
         if (symbol !== diplomatRuntime.internalConstructor) {
-            console.error("RenamedMyIterable is an Opaque type. You cannot call its constructor.");
-            return;
+            return this.#defaultConstructor(...arguments);
         }
         
         this.#ptr = ptr;
@@ -34,7 +33,7 @@ export class RenamedMyIterable {
         return this.#ptr;
     }
 
-    static new_(x) {
+    #defaultConstructor(x) {
         let functionCleanupArena = new diplomatRuntime.CleanupArena();
         
         const xSlice = functionCleanupArena.alloc(diplomatRuntime.DiplomatBuf.slice(wasm, x, "u8"));
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
-import wasm from "./diplomat-wasm.mjs";
-import * as diplomatRuntime from "./diplomat-runtime.mjs";
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
 
 const RenamedMyIterator_box_destroy_registry = new FinalizationRegistry((ptr) => {
     wasm.namespace_MyIterator_destroy(ptr);
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
-import type { RenamedOpaqueIterator } from "./RenamedOpaqueIterator"
-import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+import type { RenamedOpaqueIterator } from "../ns/RenamedOpaqueIterator"
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
 
 export class RenamedOpaqueIterable {
     
@@ -1,6 +1,6 @@
 // generated by diplomat-tool
-import type { AttrOpaque1Renamed } from "./AttrOpaque1Renamed"
-import type { pointer, codepoint } from "./diplomat-runtime.d.ts";
+import type { AttrOpaque1Renamed } from "../ns/AttrOpaque1Renamed"
+import type { pointer, codepoint } from "../diplomat-runtime.d.ts";
 
 export class RenamedOpaqueIterator {
     
@@ -1,7 +1,7 @@
 // generated by diplomat-tool
-import { AttrOpaque1Renamed } from "./AttrOpaque1Renamed.mjs"
-import wasm from "./diplomat-wasm.mjs";
-import * as diplomatRuntime from "./diplomat-runtime.mjs";
+import { AttrOpaque1Renamed } from "../ns/AttrOpaque1Renamed.mjs"
+import wasm from "../diplomat-wasm.mjs";
+import * as diplomatRuntime from "../diplomat-runtime.mjs";
 
 const RenamedOpaqueIterator_box_destroy_registry = new FinalizationRegistry((ptr) => {
     wasm.namespace_OpaqueIterator_destroy(ptr);
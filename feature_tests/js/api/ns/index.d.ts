@@ -0,0 +1,19 @@
+
+
+export { AttrOpaque1Renamed } from "./AttrOpaque1Renamed"
+
+export { RenamedAttrOpaque2 } from "./RenamedAttrOpaque2"
+
+export { RenamedComparable } from "./RenamedComparable"
+
+export { RenamedMyIndexer } from "./RenamedMyIndexer"
+
+export { RenamedMyIterable } from "./RenamedMyIterable"
+
+export { RenamedMyIterator } from "./RenamedMyIterator"
+
+export { RenamedOpaqueIterable } from "./RenamedOpaqueIterable"
+
+export { RenamedOpaqueIterator } from "./RenamedOpaqueIterator"
+
+export { RenamedAttrEnum } from "./RenamedAttrEnum"
@@ -0,0 +1,19 @@
+
+
+export { AttrOpaque1Renamed } from "./AttrOpaque1Renamed.mjs"
+
+export { RenamedAttrOpaque2 } from "./RenamedAttrOpaque2.mjs"
+
+export { RenamedComparable } from "./RenamedComparable.mjs"
+
+export { RenamedMyIndexer } from "./RenamedMyIndexer.mjs"
+
+export { RenamedMyIterable } from "./RenamedMyIterable.mjs"
+
+export { RenamedMyIterator } from "./RenamedMyIterator.mjs"
+
+export { RenamedOpaqueIterable } from "./RenamedOpaqueIterable.mjs"
+
+export { RenamedOpaqueIterator } from "./RenamedOpaqueIterator.mjs"
+
+export { RenamedAttrEnum } from "./RenamedAttrEnum.mjs"
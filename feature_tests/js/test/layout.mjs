@@ -0,0 +1,6 @@
+import test from 'ava';
+import { checkStructLayouts } from "diplomat-wasm-js-feature-tests";
+
+test("Structs are laid out like in Rust", t => {
+    t.notThrows(() => checkStructLayouts());
+});
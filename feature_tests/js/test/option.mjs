@@ -1,6 +1,6 @@
 import test from 'ava';
 
-import { OptionOpaque, OptionInputStruct, OptionEnum } from "diplomat-wasm-js-feature-tests";
+import { NullableConstructor, OptionOpaque, OptionInputStruct, OptionEnum } from "diplomat-wasm-js-feature-tests";
 
 test("Verify option methods", t => {
     const o = OptionOpaque.new_(5);
@@ -9,6 +9,10 @@ test("Verify option methods", t => {
     const on = OptionOpaque.newNone();
     t.assert(!on);
 
+    t.assert(new NullableConstructor(1));
+    const error = t.throws(() => new NullableConstructor(0));
+    t.is(error.message, "Failed to construct NullableConstructor");
+
     const s = OptionOpaque.newStruct();
 
     s.a.assertInteger(101);
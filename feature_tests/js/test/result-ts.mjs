@@ -1,16 +1,16 @@
 import test from 'ava';
-import { ErrorEnum, MyStruct, ResultOpaque } from 'diplomat-wasm-js-feature-tests';
+import { ErrorEnum, FallibleUnitConstructor, MyStruct, ResultOpaque } from 'diplomat-wasm-js-feature-tests';
 test('Verify result methods', t => {
-    const s = ResultOpaque.new_(5);
+    const s = new ResultOpaque(5);
     s.assertInteger(5);
-    const error1 = t.throws(() => ResultOpaque.newFailingFoo());
+    const error1 = t.throws(() => ResultOpaque.failingFoo());
     t.is(error1.message, 'ErrorEnum: Foo');
     t.is(error1.cause, ErrorEnum.Foo);
-    const error2 = t.throws(() => ResultOpaque.newFailingBar());
+    const error2 = t.throws(() => ResultOpaque.failingBar());
     t.is(error2.message, 'ErrorEnum: Bar');
     t.is(error2.cause, ErrorEnum.Bar);
     t.is(ResultOpaque.newFailingUnit(), null);
-    const error3 = t.throws(() => ResultOpaque.newFailingStruct(109));
+    const error3 = t.throws(() => ResultOpaque.failingStruct(109));
     t.is(error3.message, 'ErrorStruct: [object Object]');
     t.is(error3.cause.i, 109);
     const error4 = t.throws(() => ResultOpaque.newInErr(559));
@@ -21,4 +21,7 @@ test('Verify result methods', t => {
     error5.cause.assertInteger(881);
     const error6 = t.throws(() => MyStruct.failsZstResult());
     t.is(error6.message, "MyZst");
+    t.assert(new FallibleUnitConstructor(1));
+    const error7 = t.throws(() => new FallibleUnitConstructor(0));
+    t.is(error7.message, 'Failed to construct FallibleUnitConstructor');
 });
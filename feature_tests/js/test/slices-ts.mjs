@@ -1,7 +1,7 @@
 import test from "ava";
 import { MyString, Float64Vec } from "diplomat-wasm-js-feature-tests";
 test("MyString functionality", (t) => {
-    let str = MyString.new_("This is a test value.");
+    let str = new MyString("This is a test value.");
     t.is(str.str, "This is a test value.");
 });
 test("String List", (t) => {
@@ -9,11 +9,17 @@ test("String List", (t) => {
     t.is(str.str, "This");
 });
 test("MyString borrow", (t) => {
-    let str = MyString.new_("This is a test.");
+    let str = new MyString("This is a test.");
     t.is(str.borrow(), "This is a test.");
 });
 test("Float64Vec", (t) => {
     let input = [1, 2, 3, 4, 5];
-    let data = Float64Vec.newIsize(input);
-    t.deepEqual(data.borrow(), input);
+    let data = Float64Vec.isize(input);
+    t.deepEqual(Array.from(data.borrow()), input);
+});
+
+test("Static slice", (t) => {
+    let table = MyString.staticLookupTable();
+    t.true(table instanceof Uint16Array);
+    t.deepEqual(Array.from(table), [1, 2, 3, 5, 8, 13]);
 });
@@ -3,7 +3,7 @@ import { MyString, Float64Vec } from "diplomat-wasm-js-feature-tests";
 import wasm from "../api/diplomat-wasm.mjs";
 
 test("MyString functionality", (t) => {
-  let str = MyString.new_("This is a test value.");
+  let str = new MyString("This is a test value.");
   t.is(str.str, "This is a test value.");
 });
 
@@ -13,12 +13,18 @@ test("String List", (t) => {
 });
 
 test("MyString borrow", (t) => {
-  let str = MyString.new_("This is a test.");
+  let str = new MyString("This is a test.");
   t.is(str.borrow(), "This is a test.");
 });
 
 test("Float64Vec", (t) => {
   let input = [1, 2, 3, 4, 5];
-  let data = Float64Vec.newIsize(input);
-  t.deepEqual(data.borrow(), input);
+  let data = Float64Vec.isize(input);
+  t.deepEqual(Array.from(data.borrow()), input);
+});
+
+test("Static slice", (t) => {
+  let table = MyString.staticLookupTable();
+  t.true(table instanceof Uint16Array);
+  t.deepEqual(Array.from(table), [1, 2, 3, 5, 8, 13]);
 });
@@ -1,8 +1,8 @@
 import test from "ava";
-import { MyString, Float64Vec } from "diplomat-wasm-js-feature-tests";
+import { MyString, Float64Vec, Opaque, Utf16Wrap } from "diplomat-wasm-js-feature-tests";
 
 test("MyString functionality", (t) => {
-  let str = MyString.new_("This is a test value.");
+  let str = new MyString("This is a test value.");
   t.is(str.str, "This is a test value.");
 });
 
@@ -12,12 +12,62 @@ test("String List", (t) => {
 });
 
 test("MyString borrow", (t) => {
-  let str = MyString.new_("This is a test.");
+  let str = new MyString("This is a test.");
   t.is(str.borrow(), "This is a test.");
 });
 
 test("Float64Vec", (t) => {
   let input = [1, 2, 3, 4, 5];
-  let data = Float64Vec.newIsize(input);
-  t.deepEqual(data.borrow(), input);
+  let data = Float64Vec.isize(input);
+  t.deepEqual(Array.from(data.borrow()), input);
+});
+
+test("Static slice", (t) => {
+  let table = MyString.staticLookupTable();
+  t.true(table instanceof Uint16Array);
+  t.deepEqual(Array.from(table), [1, 2, 3, 5, 8, 13]);
+});
+
+test("UTF-16 write", (t) => {
+  let str = Utf16Wrap.fromUtf16("Non-BMP: 𐐷");
+  t.is(str.toLossyString(), "Non-BMP: 𐐷");
+});
+
+test("Streamed write", (t) => {
+  let o = Opaque.fromStr("abc");
+  let chunks = [];
+  o.repeatTo(500, (chunk) => {
+    chunks.push(chunk);
+  });
+  t.true(chunks.length > 1);
+  t.is(new TextDecoder().decode(Buffer.concat(chunks)), o.repeat(500));
+});
+
+test("Streamed write stops when the sink throws", (t) => {
+  let o = Opaque.fromStr("abc");
+  let calls = 0;
+  t.throws(() => o.repeatTo(500, () => {
+    calls++;
+    throw new Error("sink closed");
+  }), { message: "sink closed" });
+  t.is(calls, 1);
+});
+
+test("Read from bytes", (t) => {
+  let input = new TextEncoder().encode("a string longer than one read");
+  t.is(Opaque.fromRead(input).getDebugStr(), '"a string longer than one read"');
+});
+
+test("Read from chunks", (t) => {
+  let encoder = new TextEncoder();
+  let chunks = ["abc", "", "餐", "def"].map((s) => encoder.encode(s));
+  t.is(Opaque.fromRead(chunks).getDebugStr(), '"abc餐def"');
+});
+
+test("Read fails when the source throws", (t) => {
+  function* source() {
+    yield new TextEncoder().encode("abc");
+    throw new Error("source closed");
+  }
+  t.throws(() => Opaque.fromRead(source()), { message: "source closed" });
 });
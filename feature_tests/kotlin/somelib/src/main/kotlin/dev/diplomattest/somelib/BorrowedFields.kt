@@ -5,6 +5,7 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 internal interface BorrowedFieldsLib: Library {
     fun BorrowedFields_from_bar_and_strings(bar: Pointer, dstr16: Slice, utf8Str: Slice): BorrowedFieldsNative
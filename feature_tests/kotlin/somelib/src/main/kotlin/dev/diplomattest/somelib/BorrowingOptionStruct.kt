@@ -0,0 +1,38 @@
+package dev.diplomattest.somelib
+
+import com.sun.jna.Callback
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
+
+internal interface BorrowingOptionStructLib: Library {
+}
+
+internal class BorrowingOptionStructNative: Structure(), Structure.ByValue {
+    @JvmField
+    internal var input: OptionOptionInputStructNative = OptionOptionInputStructNative();
+    @JvmField
+    internal var opaque: Pointer? = null;
+  
+    // Define the fields of the struct
+    override fun getFieldOrder(): List<String> {
+        return listOf("input", "opaque")
+    }
+}
+
+class BorrowingOptionStruct internal constructor (
+    internal val nativeStruct: BorrowingOptionStructNative,
+    internal val aEdges: List<Any>
+    ) {
+    val input: OptionInputStruct? = nativeStruct.input.option()?.let { OptionInputStruct(it) }
+    val opaque: OptionOpaque? = nativeStruct.opaque?.let { OptionOpaque(it, aEdges) }
+
+    companion object {
+        internal val libClass: Class<BorrowingOptionStructLib> = BorrowingOptionStructLib::class.java
+        internal val lib: BorrowingOptionStructLib = Native.load("somelib", libClass)
+        val NATIVESIZE: Long = Native.getNativeSize(BorrowingOptionStructNative::class.java).toLong()
+    }
+
+}
@@ -0,0 +1,51 @@
+package dev.diplomattest.somelib;
+import com.sun.jna.Callback
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
+
+
+internal interface FallibleUnitConstructorLib: Library {
+    fun FallibleUnitConstructor_destroy(handle: Pointer)
+    fun FallibleUnitConstructor_new(i: UByte): ResultPointerUnit
+}
+
+class FallibleUnitConstructor internal constructor (
+    internal val handle: Pointer,
+    // These ensure that anything that is borrowed is kept alive and not cleaned
+    // up by the garbage collector.
+    internal val selfEdges: List<Any>,
+)  {
+
+    internal class FallibleUnitConstructorCleaner(val handle: Pointer, val lib: FallibleUnitConstructorLib) : Runnable {
+        override fun run() {
+            lib.FallibleUnitConstructor_destroy(handle)
+        }
+    }
+
+    companion object {
+        internal val libClass: Class<FallibleUnitConstructorLib> = FallibleUnitConstructorLib::class.java
+        internal val lib: FallibleUnitConstructorLib = Native.load("somelib", libClass)
+        
+        fun new_(i: UByte): Result<FallibleUnitConstructor> {
+            
+            val returnVal = lib.FallibleUnitConstructor_new(i.toByte());
+            if (returnVal.isOk == 1.toByte()) {
+                val selfEdges: List<Any> = listOf()
+                val handle = returnVal.union.ok 
+                val returnOpaque = FallibleUnitConstructor(handle, selfEdges)
+                CLEANER.register(returnOpaque, FallibleUnitConstructor.FallibleUnitConstructorCleaner(handle, FallibleUnitConstructor.lib));
+                return returnOpaque.ok()
+            } else {
+                return Unit.err()
+            }
+        }
+        
+        operator fun invoke(i: UByte): FallibleUnitConstructor {
+            return new_(i).getOrThrow()
+        }
+    }
+
+}
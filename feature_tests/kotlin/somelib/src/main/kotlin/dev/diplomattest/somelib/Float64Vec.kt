@@ -4,6 +4,7 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 
 internal interface Float64VecLib: Library {
@@ -122,6 +123,34 @@ class Float64Vec internal constructor (
             CLEANER.register(returnOpaque, Float64Vec.Float64VecCleaner(handle, Float64Vec.lib));
             return returnOpaque
         }
+        
+        fun bool(v: BooleanArray): Float64Vec {
+            return newBool(v)
+        }
+        
+        fun i16(v: ShortArray): Float64Vec {
+            return newI16(v)
+        }
+        
+        fun u16(v: UShortArray): Float64Vec {
+            return newU16(v)
+        }
+        
+        fun isize(v: LongArray): Float64Vec {
+            return newIsize(v)
+        }
+        
+        fun usize(v: ULongArray): Float64Vec {
+            return newUsize(v)
+        }
+        
+        fun f64BeBytes(v: ByteArray): Float64Vec {
+            return newF64BeBytes(v)
+        }
+        
+        operator fun invoke(v: DoubleArray): Float64Vec {
+            return newFromOwned(v)
+        }
     }
     
     fun asSlice(): DoubleArray {
@@ -173,4 +202,8 @@ class Float64Vec internal constructor (
         }
     }
 
+    override fun toString(): String {
+        return toString_()
+    }
+
 }
@@ -4,6 +4,7 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 
 internal interface FooLib: Library {
@@ -83,6 +84,14 @@ class Foo internal constructor (
             CLEANER.register(returnOpaque, Foo.FooCleaner(handle, Foo.lib));
             return returnOpaque
         }
+        
+        operator fun invoke(x: String): Foo {
+            return new_(x)
+        }
+        
+        fun static_(x: String): Foo {
+            return newStatic(x)
+        }
     }
     
     fun getBar(): Bar {
@@ -4,6 +4,7 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 
 internal interface MyStringLib: Library {
@@ -16,6 +17,7 @@ internal interface MyStringLib: Library {
     fun MyString_get_str(handle: Pointer, write: Pointer): Unit
     fun MyString_string_transform(foo: Slice, write: Pointer): Unit
     fun MyString_borrow(handle: Pointer): Slice
+    fun MyString_static_lookup_table(): Slice
 }
 
 class MyString internal constructor (
@@ -90,6 +92,20 @@ class MyString internal constructor (
             val returnString = DW.writeToString(write)
             return returnString
         }
+        
+        fun staticLookupTable(): UShortArray {
+            
+            val returnVal = lib.MyString_static_lookup_table();
+                return PrimitiveArrayTools.getUShortArray(returnVal)
+        }
+        
+        operator fun invoke(v: String): MyString {
+            return new_(v)
+        }
+        
+        fun unsafe(v: String): MyString {
+            return newUnsafe(v)
+        }
     }
     
     fun setStr(newStr: String): Unit {
@@ -5,6 +5,7 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 internal interface MyStructLib: Library {
     fun MyStruct_new(): MyStructNative
@@ -77,6 +78,10 @@ class MyStruct internal constructor (
                 return MyZst().err()
             }
         }
+        
+        operator fun invoke(): MyStruct {
+            return new_()
+        }
     }
     
     fun intoA(): UByte {
@@ -5,6 +5,7 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 internal interface NestedBorrowedFieldsLib: Library {
     fun NestedBorrowedFields_from_bar_and_foo_and_strings(bar: Pointer, foo: Pointer, dstr16X: Slice, dstr16Z: Slice, utf8StrY: Slice, utf8StrZ: Slice): NestedBorrowedFieldsNative
@@ -30,8 +31,8 @@ class NestedBorrowedFields internal constructor (
     internal val yEdges: List<Any>,
     internal val zEdges: List<Any>
     ) {
-    val fields: BorrowedFields = BorrowedFields(nativeStruct.fields, xEdges)
-    val bounds: BorrowedFieldsWithBounds = BorrowedFieldsWithBounds(nativeStruct.bounds, xEdges, yEdges, yEdges)
+    val fields: BorrowedFields = BorrowedFields(nativeStruct.fields, xEdges+yEdges)
+    val bounds: BorrowedFieldsWithBounds = BorrowedFieldsWithBounds(nativeStruct.bounds, xEdges+yEdges, yEdges, yEdges)
     val bounds2: BorrowedFieldsWithBounds = BorrowedFieldsWithBounds(nativeStruct.bounds2, zEdges, zEdges, zEdges)
 
     companion object {
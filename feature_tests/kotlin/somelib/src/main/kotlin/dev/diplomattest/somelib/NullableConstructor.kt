@@ -0,0 +1,47 @@
+package dev.diplomattest.somelib;
+import com.sun.jna.Callback
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
+
+
+internal interface NullableConstructorLib: Library {
+    fun NullableConstructor_destroy(handle: Pointer)
+    fun NullableConstructor_new(i: UByte): Pointer?
+}
+
+class NullableConstructor internal constructor (
+    internal val handle: Pointer,
+    // These ensure that anything that is borrowed is kept alive and not cleaned
+    // up by the garbage collector.
+    internal val selfEdges: List<Any>,
+)  {
+
+    internal class NullableConstructorCleaner(val handle: Pointer, val lib: NullableConstructorLib) : Runnable {
+        override fun run() {
+            lib.NullableConstructor_destroy(handle)
+        }
+    }
+
+    companion object {
+        internal val libClass: Class<NullableConstructorLib> = NullableConstructorLib::class.java
+        internal val lib: NullableConstructorLib = Native.load("somelib", libClass)
+        
+        fun new_(i: UByte): NullableConstructor? {
+            
+            val returnVal = lib.NullableConstructor_new(i.toByte());
+            val selfEdges: List<Any> = listOf()
+            val handle = returnVal ?: return null
+            val returnOpaque = NullableConstructor(handle, selfEdges)
+            CLEANER.register(returnOpaque, NullableConstructor.NullableConstructorCleaner(handle, NullableConstructor.lib));
+            return returnOpaque
+        }
+        
+        operator fun invoke(i: UByte): NullableConstructor? {
+            return new_(i)
+        }
+    }
+
+}
@@ -4,6 +4,7 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 
 internal interface OpaqueLib: Library {
@@ -11,7 +12,9 @@ internal interface OpaqueLib: Library {
     fun Opaque_new(): Pointer
     fun Opaque_try_from_utf8(input: Slice): Pointer?
     fun Opaque_from_str(input: Slice): Pointer
+    fun Opaque_from_read(read: Pointer): Pointer?
     fun Opaque_get_debug_str(handle: Pointer, write: Pointer): Unit
+    fun Opaque_repeat(handle: Pointer, count: Long, write: Pointer): Unit
     fun Opaque_assert_struct(handle: Pointer, s: MyStructNative): Unit
     fun Opaque_returns_usize(): Long
     fun Opaque_returns_imported(): ImportedStructNative
@@ -69,6 +72,20 @@ class Opaque internal constructor (
             return returnOpaque
         }
         
+        /** Reads a UTF-8 string from `read` in chunks of 16 bytes, so longer inputs take several reads.
+        */
+        fun fromRead(read: java.io.InputStream): Opaque? {
+            val readReader = DiplomatReader(read)
+            
+            val returnVal = lib.Opaque_from_read(readReader.read);
+            readReader.finish()
+            val selfEdges: List<Any> = listOf()
+            val handle = returnVal ?: return null
+            val returnOpaque = Opaque(handle, selfEdges)
+            CLEANER.register(returnOpaque, Opaque.OpaqueCleaner(handle, Opaque.lib));
+            return returnOpaque
+        }
+        
         fun returnsUsize(): ULong {
             
             val returnVal = lib.Opaque_returns_usize();
@@ -88,6 +105,10 @@ class Opaque internal constructor (
             val returnVal = lib.Opaque_cmp();
             return (returnVal)
         }
+        
+        operator fun invoke(): Opaque {
+            return new_()
+        }
     }
     
     fun getDebugStr(): String {
@@ -98,6 +119,26 @@ class Opaque internal constructor (
         return returnString
     }
     
+    /** Writes the string `count` times, which can be more than fits in one chunk of a streamed write.
+    */
+    fun repeat(count: ULong): String {
+        val write = DW.lib.diplomat_buffer_write_create(0)
+        val returnVal = lib.Opaque_repeat(handle, count.toLong(), write);
+        
+        val returnString = DW.writeToString(write)
+        return returnString
+    }
+    
+    /** Writes the string `count` times, which can be more than fits in one chunk of a streamed write.
+    *
+    *Writes the output to [out] as it is written, instead of returning it.
+    */
+    fun repeatTo(count: ULong, out: java.io.OutputStream): Unit {
+        val write = DiplomatStreamWrite(out)
+        val returnVal = lib.Opaque_repeat(handle, count.toLong(), write.write);
+        write.finish()
+    }
+    
     /** See the [Rust documentation for `something`](https://docs.rs/Something/latest/struct.Something.html#method.something) for more information.
     *
     *See the [Rust documentation for `something_else`](https://docs.rs/Something/latest/struct.Something.html#method.something_else) for more information.
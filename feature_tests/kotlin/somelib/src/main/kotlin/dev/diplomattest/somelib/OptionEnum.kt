@@ -0,0 +1,32 @@
+package dev.diplomattest.somelib
+
+import com.sun.jna.Callback
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
+
+internal interface OptionEnumLib: Library {
+}
+enum class OptionEnum {
+    Foo,
+    Bar;
+
+    fun toNative(): Int {
+        return this.ordinal
+    }
+
+
+    companion object {
+        internal val libClass: Class<OptionEnumLib> = OptionEnumLib::class.java
+        internal val lib: OptionEnumLib = Native.load("somelib", libClass) 
+        fun fromNative(native: Int): OptionEnum {
+            return OptionEnum.entries[native]
+        }
+
+        fun default(): OptionEnum {
+            return Foo
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,39 @@
+package dev.diplomattest.somelib
+
+import com.sun.jna.Callback
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
+
+internal interface OptionInputStructLib: Library {
+}
+
+internal class OptionInputStructNative: Structure(), Structure.ByValue {
+    @JvmField
+    internal var a: OptionByte = OptionByte();
+    @JvmField
+    internal var b: OptionInt = OptionInt();
+    @JvmField
+    internal var c: OptionInt = OptionInt();
+  
+    // Define the fields of the struct
+    override fun getFieldOrder(): List<String> {
+        return listOf("a", "b", "c")
+    }
+}
+
+class OptionInputStruct internal constructor (
+    internal val nativeStruct: OptionInputStructNative) {
+    val a: UByte? = nativeStruct.a.option()?.let { it.toUByte() }
+    val b: Int? = nativeStruct.b.option()?.let { it }
+    val c: OptionEnum? = nativeStruct.c.option()?.let { OptionEnum.fromNative(it) }
+
+    companion object {
+        internal val libClass: Class<OptionInputStructLib> = OptionInputStructLib::class.java
+        internal val lib: OptionInputStructLib = Native.load("somelib", libClass)
+        val NATIVESIZE: Long = Native.getNativeSize(OptionInputStructNative::class.java).toLong()
+    }
+
+}
@@ -4,6 +4,7 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 
 internal interface OptionOpaqueLib: Library {
@@ -19,6 +20,12 @@ internal interface OptionOpaqueLib: Library {
     fun OptionOpaque_new_struct_nones(): OptionStructNative
     fun OptionOpaque_assert_integer(handle: Pointer, i: Int): Unit
     fun OptionOpaque_option_opaque_argument(arg: Pointer?): Byte
+    fun OptionOpaque_accepts_option_u8(arg: OptionByte): OptionByte
+    fun OptionOpaque_accepts_option_enum(arg: OptionInt): OptionInt
+    fun OptionOpaque_accepts_option_input_struct(arg: OptionOptionInputStructNative): OptionOptionInputStructNative
+    fun OptionOpaque_returns_option_input_struct(): OptionInputStructNative
+    fun OptionOpaque_borrowing_option_struct(handle: Pointer): BorrowingOptionStructNative
+    fun OptionOpaque_borrowing_option_struct_nones(handle: Pointer): BorrowingOptionStructNative
 }
 
 class OptionOpaque internal constructor (
@@ -90,6 +97,39 @@ class OptionOpaque internal constructor (
             val returnVal = lib.OptionOpaque_option_opaque_argument(arg?.handle);
             return (returnVal > 0)
         }
+        
+        fun acceptsOptionU8(arg: UByte?): UByte? {
+            
+            val returnVal = lib.OptionOpaque_accepts_option_u8(arg?.let { OptionByte.some(it.toByte()) } ?: OptionByte.none());
+            return returnVal.option()?.toUByte()
+        }
+        
+        fun acceptsOptionEnum(arg: OptionEnum?): OptionEnum? {
+            
+            val returnVal = lib.OptionOpaque_accepts_option_enum(arg?.let { OptionInt.some(it.toNative()) } ?: OptionInt.none());
+            
+            val intermediateOption = returnVal.option() ?: return null
+            return OptionEnum.fromNative(intermediateOption)
+        }
+        
+        fun acceptsOptionInputStruct(arg: OptionInputStruct?): OptionInputStruct? {
+            
+            val returnVal = lib.OptionOpaque_accepts_option_input_struct(arg?.let { OptionOptionInputStructNative.some(it.nativeStruct) } ?: OptionOptionInputStructNative.none());
+            
+            val intermediateOption = returnVal.option() ?: return null
+            
+            val returnStruct = OptionInputStruct(intermediateOption)
+            return returnStruct
+                                    
+        }
+        
+        fun returnsOptionInputStruct(): OptionInputStruct {
+            
+            val returnVal = lib.OptionOpaque_returns_option_input_struct();
+            
+            val returnStruct = OptionInputStruct(returnVal)
+            return returnStruct
+        }
     }
     
     fun optionIsize(): Long? {
@@ -121,5 +161,23 @@ class OptionOpaque internal constructor (
         val returnVal = lib.OptionOpaque_assert_integer(handle, i);
         
     }
+    
+    fun borrowingOptionStruct(): BorrowingOptionStruct {
+        
+        val returnVal = lib.OptionOpaque_borrowing_option_struct(handle);
+        
+        val aEdges: List<Any> = listOf(this)
+        val returnStruct = BorrowingOptionStruct(returnVal, aEdges)
+        return returnStruct
+    }
+    
+    fun borrowingOptionStructNones(): BorrowingOptionStruct {
+        
+        val returnVal = lib.OptionOpaque_borrowing_option_struct_nones(handle);
+        
+        val aEdges: List<Any> = listOf(this)
+        val returnStruct = BorrowingOptionStruct(returnVal, aEdges)
+        return returnStruct
+    }
 
 }
@@ -5,6 +5,7 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 internal interface OptionStructLib: Library {
 }
@@ -27,22 +28,10 @@ internal class OptionStructNative: Structure(), Structure.ByValue {
 
 class OptionStruct internal constructor (
     internal val nativeStruct: OptionStructNative) {
-    val a: OptionOpaque? = if (nativeStruct.a == null) {
-        null
-    } else {
-        OptionOpaque(nativeStruct.a!!, listOf())
-    }
-    val b: OptionOpaqueChar? = if (nativeStruct.b == null) {
-        null
-    } else {
-        OptionOpaqueChar(nativeStruct.b!!, listOf())
-    }
+    val a: OptionOpaque? = nativeStruct.a?.let { OptionOpaque(it, listOf()) }
+    val b: OptionOpaqueChar? = nativeStruct.b?.let { OptionOpaqueChar(it, listOf()) }
     val c: UInt = nativeStruct.c.toUInt()
-    val d: OptionOpaque? = if (nativeStruct.d == null) {
-        null
-    } else {
-        OptionOpaque(nativeStruct.d!!, listOf())
-    }
+    val d: OptionOpaque? = nativeStruct.d?.let { OptionOpaque(it, listOf()) }
 
     companion object {
         internal val libClass: Class<OptionStructLib> = OptionStructLib::class.java
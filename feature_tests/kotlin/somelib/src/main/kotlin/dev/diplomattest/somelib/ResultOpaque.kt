@@ -4,6 +4,7 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 
 internal interface ResultOpaqueLib: Library {
@@ -145,6 +146,22 @@ class ResultOpaque internal constructor (
                 return returnOpaque.err()
             }
         }
+        
+        operator fun invoke(i: Int): ResultOpaque {
+            return new_(i).getOrThrow()
+        }
+        
+        fun failingFoo(): ResultOpaque {
+            return newFailingFoo().getOrThrow()
+        }
+        
+        fun failingBar(): ResultOpaque {
+            return newFailingBar().getOrThrow()
+        }
+        
+        fun failingStruct(i: Int): ResultOpaque {
+            return newFailingStruct(i).getOrThrow()
+        }
     }
     
     fun assertInteger(i: Int): Unit {
@@ -4,12 +4,14 @@ import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.ns.*
 
 
 internal interface Utf16WrapLib: Library {
     fun Utf16Wrap_destroy(handle: Pointer)
     fun Utf16Wrap_from_utf16(input: Slice): Pointer
     fun Utf16Wrap_get_debug_str(handle: Pointer, write: Pointer): Unit
+    fun Utf16Wrap_to_lossy_string(handle: Pointer, write: Pointer): Unit
     fun Utf16Wrap_borrow_cont(handle: Pointer): Slice
 }
 
@@ -41,6 +43,10 @@ class Utf16Wrap internal constructor (
             inputMem.close()
             return returnOpaque
         }
+        
+        operator fun invoke(input: String): Utf16Wrap {
+            return fromUtf16(input)
+        }
     }
     
     fun getDebugStr(): String {
@@ -51,6 +57,14 @@ class Utf16Wrap internal constructor (
         return returnString
     }
     
+    fun toLossyString(): String {
+        val write = DW.lib.diplomat_buffer_write16_create(0)
+        val returnVal = lib.Utf16Wrap_to_lossy_string(handle, write);
+        
+        val returnString = DW.writeToString16(write)
+        return returnString
+    }
+    
     fun borrowCont(): String {
         
         val returnVal = lib.Utf16Wrap_borrow_cont(handle);
@@ -1,9 +1,10 @@
-package dev.diplomattest.somelib;
+package dev.diplomattest.somelib.ns;
 import com.sun.jna.Callback
 import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.*
 
 
 internal interface AttrOpaque1Lib: Library {
@@ -41,6 +42,10 @@ class AttrOpaque1 internal constructor (
             CLEANER.register(returnOpaque, AttrOpaque1.AttrOpaque1Cleaner(handle, AttrOpaque1.lib));
             return returnOpaque
         }
+        
+        operator fun invoke(): AttrOpaque1 {
+            return new_()
+        }
     }
     
     fun method(): UByte {
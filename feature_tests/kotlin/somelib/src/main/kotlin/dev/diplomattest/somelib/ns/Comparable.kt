@@ -0,0 +1,54 @@
+package dev.diplomattest.somelib.ns;
+import com.sun.jna.Callback
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import dev.diplomattest.somelib.*
+
+
+internal interface ComparableLib: Library {
+    fun namespace_Comparable_destroy(handle: Pointer)
+    fun namespace_Comparable_new(int: UByte): Pointer
+    fun namespace_Comparable_cmp(handle: Pointer, other: Pointer): Byte
+}
+
+class Comparable internal constructor (
+    internal val handle: Pointer,
+    // These ensure that anything that is borrowed is kept alive and not cleaned
+    // up by the garbage collector.
+    internal val selfEdges: List<Any>,
+): Comparable<Comparable> {
+
+    internal class ComparableCleaner(val handle: Pointer, val lib: ComparableLib) : Runnable {
+        override fun run() {
+            lib.namespace_Comparable_destroy(handle)
+        }
+    }
+
+    companion object {
+        internal val libClass: Class<ComparableLib> = ComparableLib::class.java
+        internal val lib: ComparableLib = Native.load("somelib", libClass)
+        
+        fun new_(int: UByte): Comparable {
+            
+            val returnVal = lib.namespace_Comparable_new(int.toByte());
+            val selfEdges: List<Any> = listOf()
+            val handle = returnVal 
+            val returnOpaque = Comparable(handle, selfEdges)
+            CLEANER.register(returnOpaque, Comparable.ComparableCleaner(handle, Comparable.lib));
+            return returnOpaque
+        }
+    }
+    
+    fun cmp(other: Comparable): Byte {
+        
+        val returnVal = lib.namespace_Comparable_cmp(handle, other.handle);
+        return (returnVal)
+    }
+
+    override fun compareTo(other: Comparable): Int {
+        return cmp(other).toInt()
+    }
+
+}
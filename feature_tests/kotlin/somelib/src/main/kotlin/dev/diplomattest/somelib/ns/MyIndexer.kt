@@ -1,13 +1,15 @@
-package dev.diplomattest.somelib;
+package dev.diplomattest.somelib.ns;
 import com.sun.jna.Callback
 import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.*
 
 
 internal interface MyIndexerLib: Library {
     fun namespace_MyIndexer_destroy(handle: Pointer)
+    fun namespace_MyIndexer_new(words: Slice): Pointer
     fun namespace_MyIndexer_get(handle: Pointer, i: Long): OptionSlice
 }
 
@@ -27,6 +29,18 @@ class MyIndexer internal constructor (
     companion object {
         internal val libClass: Class<MyIndexerLib> = MyIndexerLib::class.java
         internal val lib: MyIndexerLib = Native.load("somelib", libClass)
+        
+        fun new_(words: String): MyIndexer {
+            val (wordsMem, wordsSlice) = PrimitiveArrayTools.readUtf8(words)
+            
+            val returnVal = lib.namespace_MyIndexer_new(wordsSlice);
+            val selfEdges: List<Any> = listOf()
+            val handle = returnVal 
+            val returnOpaque = MyIndexer(handle, selfEdges)
+            CLEANER.register(returnOpaque, MyIndexer.MyIndexerCleaner(handle, MyIndexer.lib));
+            wordsMem.close()
+            return returnOpaque
+        }
     }
     
     internal fun getInternal(i: ULong): String? {
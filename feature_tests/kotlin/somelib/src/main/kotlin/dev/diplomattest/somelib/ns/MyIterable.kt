@@ -1,9 +1,10 @@
-package dev.diplomattest.somelib;
+package dev.diplomattest.somelib.ns;
 import com.sun.jna.Callback
 import com.sun.jna.Library
 import com.sun.jna.Native
 import com.sun.jna.Pointer
 import com.sun.jna.Structure
+import dev.diplomattest.somelib.*
 
 
 internal interface MyIterableLib: Library {
@@ -40,6 +41,10 @@ class MyIterable internal constructor (
             xMem.close()
             return returnOpaque
         }
+        
+        operator fun invoke(x: UByteArray): MyIterable {
+            return new_(x)
+        }
     }
     
     override fun iterator(): MyIterator {
@@ -0,0 +1,107 @@
+package dev.diplomattest.somelib
+
+import com.sun.jna.Native
+import com.sun.jna.Structure
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+import dev.diplomattest.somelib.ns.*
+
+/** Checks that the structs and their fields are laid out like the ones the Rust library was built with. */
+class DiplomatLayoutTest {
+    private fun expected(size32: Int, size64: Int): Int = if (Native.POINTER_SIZE == 8) size64 else size32
+
+    // JNA computes the offsets of the fields but only exposes them to subclasses
+    private val fieldOffset = Structure::class.java.getDeclaredMethod("fieldOffset", String::class.java).apply { isAccessible = true }
+
+    private fun offsetOf(native: Structure, field: String): Int = fieldOffset.invoke(native, field) as Int
+
+    @Test
+    fun testStructLayouts() {
+        BorrowedFieldsNative().let { native ->
+            assertEquals(expected(24, 48), native.size(), "BorrowedFields")
+            assertEquals(expected(0, 0), offsetOf(native, "a"), "BorrowedFields.a")
+            assertEquals(expected(8, 16), offsetOf(native, "b"), "BorrowedFields.b")
+            assertEquals(expected(16, 32), offsetOf(native, "c"), "BorrowedFields.c")
+        }
+        BorrowedFieldsReturningNative().let { native ->
+            assertEquals(expected(8, 16), native.size(), "BorrowedFieldsReturning")
+            assertEquals(expected(0, 0), offsetOf(native, "bytes"), "BorrowedFieldsReturning.bytes")
+        }
+        BorrowedFieldsWithBoundsNative().let { native ->
+            assertEquals(expected(24, 48), native.size(), "BorrowedFieldsWithBounds")
+            assertEquals(expected(0, 0), offsetOf(native, "fieldA"), "BorrowedFieldsWithBounds.fieldA")
+            assertEquals(expected(8, 16), offsetOf(native, "fieldB"), "BorrowedFieldsWithBounds.fieldB")
+            assertEquals(expected(16, 32), offsetOf(native, "fieldC"), "BorrowedFieldsWithBounds.fieldC")
+        }
+        BorrowingOptionStructNative().let { native ->
+            assertEquals(expected(28, 32), native.size(), "BorrowingOptionStruct")
+            assertEquals(expected(0, 0), offsetOf(native, "input"), "BorrowingOptionStruct.input")
+            assertEquals(expected(24, 24), offsetOf(native, "opaque"), "BorrowingOptionStruct.opaque")
+        }
+        CallbackTestingStructNative().let { native ->
+            assertEquals(expected(8, 8), native.size(), "CallbackTestingStruct")
+            assertEquals(expected(0, 0), offsetOf(native, "x"), "CallbackTestingStruct.x")
+            assertEquals(expected(4, 4), offsetOf(native, "y"), "CallbackTestingStruct.y")
+        }
+        CallbackWrapperNative().let { native ->
+            assertEquals(expected(1, 1), native.size(), "CallbackWrapper")
+            assertEquals(expected(0, 0), offsetOf(native, "cantBeEmpty"), "CallbackWrapper.cantBeEmpty")
+        }
+        CyclicStructANative().let { native ->
+            assertEquals(expected(1, 1), native.size(), "CyclicStructA")
+            assertEquals(expected(0, 0), offsetOf(native, "a"), "CyclicStructA.a")
+        }
+        CyclicStructBNative().let { native ->
+            assertEquals(expected(1, 1), native.size(), "CyclicStructB")
+            assertEquals(expected(0, 0), offsetOf(native, "field"), "CyclicStructB.field")
+        }
+        ErrorStructNative().let { native ->
+            assertEquals(expected(8, 8), native.size(), "ErrorStruct")
+            assertEquals(expected(0, 0), offsetOf(native, "i"), "ErrorStruct.i")
+            assertEquals(expected(4, 4), offsetOf(native, "j"), "ErrorStruct.j")
+        }
+        ImportedStructNative().let { native ->
+            assertEquals(expected(8, 8), native.size(), "ImportedStruct")
+            assertEquals(expected(0, 0), offsetOf(native, "foo"), "ImportedStruct.foo")
+            assertEquals(expected(4, 4), offsetOf(native, "count"), "ImportedStruct.count")
+        }
+        MyStructNative().let { native ->
+            assertEquals(expected(32, 32), native.size(), "MyStruct")
+            assertEquals(expected(0, 0), offsetOf(native, "a"), "MyStruct.a")
+            assertEquals(expected(1, 1), offsetOf(native, "b"), "MyStruct.b")
+            assertEquals(expected(2, 2), offsetOf(native, "c"), "MyStruct.c")
+            assertEquals(expected(8, 8), offsetOf(native, "d"), "MyStruct.d")
+            assertEquals(expected(16, 16), offsetOf(native, "e"), "MyStruct.e")
+            assertEquals(expected(20, 20), offsetOf(native, "f"), "MyStruct.f")
+            assertEquals(expected(24, 24), offsetOf(native, "g"), "MyStruct.g")
+        }
+        NestedBorrowedFieldsNative().let { native ->
+            assertEquals(expected(72, 144), native.size(), "NestedBorrowedFields")
+            assertEquals(expected(0, 0), offsetOf(native, "fields"), "NestedBorrowedFields.fields")
+            assertEquals(expected(24, 48), offsetOf(native, "bounds"), "NestedBorrowedFields.bounds")
+            assertEquals(expected(48, 96), offsetOf(native, "bounds2"), "NestedBorrowedFields.bounds2")
+        }
+        OptionInputStructNative().let { native ->
+            assertEquals(expected(20, 20), native.size(), "OptionInputStruct")
+            assertEquals(expected(0, 0), offsetOf(native, "a"), "OptionInputStruct.a")
+            assertEquals(expected(4, 4), offsetOf(native, "b"), "OptionInputStruct.b")
+            assertEquals(expected(12, 12), offsetOf(native, "c"), "OptionInputStruct.c")
+        }
+        OptionStructNative().let { native ->
+            assertEquals(expected(16, 32), native.size(), "OptionStruct")
+            assertEquals(expected(0, 0), offsetOf(native, "a"), "OptionStruct.a")
+            assertEquals(expected(4, 8), offsetOf(native, "b"), "OptionStruct.b")
+            assertEquals(expected(8, 16), offsetOf(native, "c"), "OptionStruct.c")
+            assertEquals(expected(12, 24), offsetOf(native, "d"), "OptionStruct.d")
+        }
+        TraitTestingStructNative().let { native ->
+            assertEquals(expected(8, 8), native.size(), "TraitTestingStruct")
+            assertEquals(expected(0, 0), offsetOf(native, "x"), "TraitTestingStruct.x")
+            assertEquals(expected(4, 4), offsetOf(native, "y"), "TraitTestingStruct.y")
+        }
+        TraitWrapperNative().let { native ->
+            assertEquals(expected(1, 1), native.size(), "TraitWrapper")
+            assertEquals(expected(0, 0), offsetOf(native, "cantBeEmpty"), "TraitWrapper.cantBeEmpty")
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+package dev.diplomattest.somelib
+
+import org.junit.jupiter.api.Test
+import java.io.ByteArrayInputStream
+import java.io.IOException
+import java.io.InputStream
+import kotlin.test.assertEquals
+import kotlin.test.assertFailsWith
+
+class OpaqueReadTest {
+    @Test
+    fun testFromRead() {
+        val input = "a string longer than one read 餐".toByteArray(Charsets.UTF_8)
+        val opaque = Opaque.fromRead(ByteArrayInputStream(input))
+        assertEquals("\"a string longer than one read 餐\"", opaque?.getDebugStr())
+    }
+
+    @Test
+    fun testFromReadRethrows() {
+        val failing = object : InputStream() {
+            override fun read(): Int = throw IOException("input closed")
+        }
+        val e = assertFailsWith<IOException> { Opaque.fromRead(failing) }
+        assertEquals("input closed", e.message)
+    }
+}
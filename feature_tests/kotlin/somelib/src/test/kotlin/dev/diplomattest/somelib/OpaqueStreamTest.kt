@@ -0,0 +1,16 @@
+package dev.diplomattest.somelib
+
+import org.junit.jupiter.api.Test
+import java.io.ByteArrayOutputStream
+import kotlin.test.assertEquals
+
+class OpaqueStreamTest {
+    @Test
+    fun testRepeatTo() {
+        val opaque = Opaque.fromStr("abc")
+        val out = ByteArrayOutputStream()
+        opaque.repeatTo(500u, out)
+        assertEquals(1500, out.size())
+        assertEquals(opaque.repeat(500u), out.toString(Charsets.UTF_8.name()))
+    }
+}
@@ -41,4 +41,36 @@ class OptionOpaqueTest {
         assertEquals(someOption.optionU32(), 10.toUInt())
 
     }
+
+    @Test
+    fun testOptionInputStruct() {
+        val struct = OptionOpaque.returnsOptionInputStruct()
+        assertEquals(struct.a, 6.toUByte())
+        assertNull(struct.b)
+        assertEquals(struct.c, OptionEnum.Bar)
+
+        assertEquals(OptionOpaque.acceptsOptionU8(5.toUByte()), 5.toUByte())
+        assertNull(OptionOpaque.acceptsOptionU8(null))
+        assertEquals(OptionOpaque.acceptsOptionEnum(OptionEnum.Foo), OptionEnum.Foo)
+        assertNull(OptionOpaque.acceptsOptionEnum(null))
+
+        val roundTrip = OptionOpaque.acceptsOptionInputStruct(struct)
+        assertEquals(roundTrip?.a, 6.toUByte())
+        assertEquals(roundTrip?.c, OptionEnum.Bar)
+        assertNull(OptionOpaque.acceptsOptionInputStruct(null))
+    }
+
+    @Test
+    fun testBorrowingOptionStruct() {
+        val opaque = OptionOpaque.new_(5) ?: throw RuntimeException("Failed to get option")
+        val struct = opaque.borrowingOptionStruct()
+        assertEquals(struct.input?.a, 6.toUByte())
+        assertEquals(struct.input?.c, OptionEnum.Bar)
+        assertNotNull(struct.opaque)
+        struct.opaque?.assertInteger(5)
+
+        val nones = opaque.borrowingOptionStructNones()
+        assertNull(nones.input)
+        assertNull(nones.opaque)
+    }
 }
\ No newline at end of file
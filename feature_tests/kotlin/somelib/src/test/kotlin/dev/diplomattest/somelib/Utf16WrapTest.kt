@@ -0,0 +1,12 @@
+package dev.diplomattest.somelib
+
+import org.junit.jupiter.api.Test
+import kotlin.test.assertEquals
+
+class Utf16WrapTest {
+    @Test
+    fun testToLossyString() {
+        val str = "Non-BMP: 𐐷"
+        assertEquals(str, Utf16Wrap.fromUtf16(str).toLossyString())
+    }
+}
@@ -105,6 +105,15 @@ pub mod ffi {
     }
 
     impl MyIndexer {
+        pub fn new(words: &DiplomatStr) -> Box<Self> {
+            Box::new(Self(
+                String::from_utf8_lossy(words)
+                    .split_whitespace()
+                    .map(Into::into)
+                    .collect(),
+            ))
+        }
+
         #[diplomat::attr(auto, indexer)]
         pub fn get<'a>(&'a self, i: usize) -> Option<&'a DiplomatStr> {
             self.0.get(i).as_ref().map(|string| string.as_bytes())
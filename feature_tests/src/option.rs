@@ -60,6 +60,13 @@ pub mod ffi {
         }
     }
 
+    #[diplomat::out]
+    #[diplomat::attr(not(supports = option), disable)]
+    pub struct BorrowingOptionStruct<'a> {
+        input: DiplomatOption<OptionInputStruct>,
+        opaque: Option<&'a OptionOpaque>,
+    }
+
     #[diplomat::attr(not(supports = option), disable)]
     #[derive(Debug)]
     pub enum OptionEnum {
@@ -145,6 +152,38 @@ pub mod ffi {
                 c: Some(OptionEnum::Bar).into(),
             }
         }
+
+        #[diplomat::attr(not(supports = option), disable)]
+        pub fn borrowing_option_struct<'a>(&'a self) -> BorrowingOptionStruct<'a> {
+            BorrowingOptionStruct {
+                input: Some(Self::returns_option_input_struct()).into(),
+                opaque: Some(self),
+            }
+        }
+
+        #[diplomat::attr(not(supports = option), disable)]
+        pub fn borrowing_option_struct_nones<'a>(&'a self) -> BorrowingOptionStruct<'a> {
+            BorrowingOptionStruct {
+                input: None.into(),
+                opaque: None,
+            }
+        }
+    }
+
+    // Dart factory constructors cannot return null yet
+    #[diplomat::opaque]
+    #[diplomat::attr(any(dart, not(supports = fallible_constructors)), disable)]
+    pub struct NullableConstructor(u8);
+
+    impl NullableConstructor {
+        #[diplomat::attr(auto, constructor)]
+        pub fn new(i: u8) -> Option<Box<NullableConstructor>> {
+            if i == 0 {
+                None
+            } else {
+                Some(Box::new(NullableConstructor(i)))
+            }
+        }
     }
 
     impl OptionOpaqueChar {
@@ -58,4 +58,20 @@ pub mod ffi {
             assert_eq!(i, self.0);
         }
     }
+
+    // Dart factory constructors cannot return null yet
+    #[diplomat::opaque]
+    #[diplomat::attr(any(dart, not(supports = fallible_constructors)), disable)]
+    pub struct FallibleUnitConstructor(u8);
+
+    impl FallibleUnitConstructor {
+        #[diplomat::attr(auto, constructor)]
+        pub fn new(i: u8) -> Result<Box<FallibleUnitConstructor>, ()> {
+            if i == 0 {
+                Err(())
+            } else {
+                Ok(Box::new(FallibleUnitConstructor(i)))
+            }
+        }
+    }
 }
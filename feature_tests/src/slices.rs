@@ -44,6 +44,11 @@ mod ffi {
         pub fn borrow<'a>(&'a self) -> DiplomatStrSlice<'a> {
             AsRef::<[u8]>::as_ref(&self.0).into()
         }
+
+        #[diplomat::attr(not(supports = static_slices), disable)]
+        pub fn static_lookup_table() -> &'static [u16] {
+            &[1, 2, 3, 5, 8, 13]
+        }
     }
 
     #[diplomat::opaque]
@@ -62,10 +62,32 @@ pub mod ffi {
             Box::new(Self(input.into()))
         }
 
+        /// Reads a UTF-8 string from `read` in chunks of 16 bytes, so longer inputs take several reads.
+        #[diplomat::attr(not(supports = reads), disable)]
+        pub fn from_read(read: &mut DiplomatRead) -> Option<Box<Self>> {
+            let mut input = Vec::new();
+            let mut buf = [0; 16];
+            loop {
+                match read.read_bytes(&mut buf)? {
+                    0 => break,
+                    len => input.extend_from_slice(&buf[..len]),
+                }
+            }
+            Some(Box::new(Self(String::from_utf8(input).ok()?)))
+        }
+
         pub fn get_debug_str(&self, write: &mut DiplomatWrite) {
             let _infallible = write!(write, "{:?}", &self.0);
         }
 
+        /// Writes the string `count` times, which can be more than fits in one chunk of a streamed write.
+        #[diplomat::attr(auto, stream)]
+        pub fn repeat(&self, count: usize, write: &mut DiplomatWrite) {
+            for _ in 0..count {
+                let _infallible = write.write_str(&self.0);
+            }
+        }
+
         #[diplomat::rust_link(Something::something, FnInStruct)]
         #[diplomat::rust_link(Something::something_else, FnInStruct)]
         #[diplomat::rust_link(Something::something_small, FnInStruct, compact)]
@@ -145,6 +167,11 @@ pub mod ffi {
             let _infallible = write!(write, "{:?}", &self.0);
         }
 
+        #[diplomat::attr(not(supports = write16), disable)]
+        pub fn to_lossy_string(&self, write: &mut DiplomatWrite16) {
+            let _infallible = write.write_str(&String::from_utf16_lossy(&self.0));
+        }
+
         pub fn borrow_cont<'a>(&'a self) -> &'a DiplomatStr16 {
             &self.0
         }
@@ -326,8 +326,13 @@ fn gen_custom_type_method(strct: &ast::CustomType, m: &ast::Method) -> Item {
         })
         .collect::<Vec<_>>();
 
+    let track_created = m
+        .return_type
+        .as_ref()
+        .and_then(|ty| track_created_opaques(ty, quote! { &ret }));
+
     let cfg = cfgs_to_stream(&m.attrs.cfg);
-    if write_flushes.is_empty() {
+    if write_flushes.is_empty() && track_created.is_none() {
         Item::Fn(syn::parse_quote! {
             #[no_mangle]
             #cfg
@@ -344,12 +349,61 @@ fn gen_custom_type_method(strct: &ast::CustomType, m: &ast::Method) -> Item {
                 #(#all_params_conversion)*
                 let ret = #method_invocation(#(#all_params_names),*);
                 #(#write_flushes)*
+                #track_created
                 ret #maybe_into
             }
         })
     }
 }
 
+/// Generates code counting the opaques owned by `value`, a reference to a returned `ty`, as created
+/// for `diplomat_runtime::leak_check`. Returns `None` if `ty` can't own an opaque.
+fn track_created_opaques(
+    ty: &ast::TypeName,
+    value: proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    match ty {
+        ast::TypeName::Box(inner)
+            if matches!(
+                **inner,
+                ast::TypeName::Named(_) | ast::TypeName::SelfType(_)
+            ) =>
+        {
+            Some(quote! { diplomat_runtime::leak_check::created(&**#value); })
+        }
+        ast::TypeName::Option(inner, is_std_option) => {
+            let track_inner = track_created_opaques(inner, quote! { value })?;
+            Some(if *is_std_option == StdlibOrDiplomat::Stdlib {
+                quote! { if let Some(value) = #value { #track_inner } }
+            } else {
+                quote! { if let Ok(value) = (#value).as_ref() { #track_inner } }
+            })
+        }
+        ast::TypeName::Result(ok, err, is_std_result) => {
+            let track_ok = track_created_opaques(ok, quote! { value });
+            let track_err = track_created_opaques(err, quote! { value });
+            if track_ok.is_none() && track_err.is_none() {
+                return None;
+            }
+            let value = if *is_std_result == StdlibOrDiplomat::Stdlib {
+                value
+            } else {
+                quote! { (#value).as_ref() }
+            };
+            let ok_arm = match track_ok {
+                Some(track_ok) => quote! { Ok(value) => { #track_ok } },
+                None => quote! { Ok(_) => {} },
+            };
+            let err_arm = match track_err {
+                Some(track_err) => quote! { Err(value) => { #track_err } },
+                None => quote! { Err(_) => {} },
+            };
+            Some(quote! { match #value { #ok_arm #err_arm } })
+        }
+        _ => None,
+    }
+}
+
 struct AttributeInfo {
     repr: bool,
     opaque: bool,
@@ -408,6 +462,107 @@ impl AttributeInfo {
     }
 }
 
+/// Asserts that rustc lays out a struct the way backends assume, see `diplomat_runtime::layout`.
+fn gen_struct_layout_check(strct: &ast::Struct, module: &ast::Module) -> Item {
+    let type_ident = strct.name.to_syn();
+    let lifetimes = if strct.lifetimes.is_empty() {
+        quote! {}
+    } else {
+        let statics = (0..strct.lifetimes.len()).map(|_| quote! { 'static });
+        quote! { <#(#statics),*> }
+    };
+    let cfg = cfgs_to_stream(&strct.attrs.cfg);
+
+    let fields = strct.fields.iter().map(|(name, ty, _, _)| {
+        let name = name.to_syn();
+        let expected = match expected_field_layout(ty, module) {
+            Some(layout) => quote! { Some(#layout) },
+            None => quote! { None },
+        };
+        quote! {
+            .field(base, ::core::ptr::addr_of!((*base).#name), #expected)
+        }
+    });
+
+    if strct.fields.is_empty() {
+        return syn::parse_quote! {
+            #cfg
+            const _: () = diplomat_runtime::layout::StructLayoutCheck::new().finish::<#type_ident #lifetimes>();
+        };
+    }
+
+    // The JS bindings check their layout against this at runtime
+    let name = strct.name.to_string();
+    let record_len = name.len() + 4 * (4 + strct.fields.len());
+    let offsets = strct.fields.iter().map(|(name, ..)| {
+        let name = name.to_syn();
+        quote! {
+            diplomat_runtime::layout::offset_of(base, ::core::ptr::addr_of!((*base).#name))
+        }
+    });
+
+    syn::parse_quote! {
+        #cfg
+        const _: () = {
+            let uninit = ::core::mem::MaybeUninit::<#type_ident #lifetimes>::uninit();
+            let base = uninit.as_ptr();
+            unsafe { diplomat_runtime::layout::StructLayoutCheck::new() #(#fields)* }.finish::<#type_ident #lifetimes>();
+
+            #[cfg(target_family = "wasm")]
+            #[link_section = "diplomat_layout"]
+            #[used]
+            static LAYOUT: [u8; #record_len] = {
+                let uninit = ::core::mem::MaybeUninit::<#type_ident #lifetimes>::uninit();
+                let base = uninit.as_ptr();
+                diplomat_runtime::layout::wasm_layout_record::<#type_ident #lifetimes, #record_len>(#name, unsafe { &[#(#offsets),*] })
+            };
+        };
+    }
+}
+
+/// The layout backends assume for a struct field of type `ty`, if it isn't simply the layout of
+/// the Rust type (like for primitives and structs).
+fn expected_field_layout(
+    ty: &ast::TypeName,
+    module: &ast::Module,
+) -> Option<proc_macro2::TokenStream> {
+    match ty {
+        ast::TypeName::Reference(..)
+        | ast::TypeName::Box(..)
+        | ast::TypeName::Option(_, ast::StdlibOrDiplomat::Stdlib) => {
+            Some(quote! { diplomat_runtime::layout::TypeLayout::POINTER })
+        }
+        ast::TypeName::StrReference(..)
+        | ast::TypeName::StrSlice(..)
+        | ast::TypeName::PrimitiveSlice(..) => {
+            Some(quote! { diplomat_runtime::layout::TypeLayout::SLICE })
+        }
+        // Enums from other modules can't be told apart from structs
+        ast::TypeName::Named(path) | ast::TypeName::SelfType(path) => {
+            match path.path.elements.as_slice() {
+                [name] => match module.declared_types.get(name) {
+                    Some(ast::CustomType::Enum(_)) => {
+                        Some(quote! { diplomat_runtime::layout::TypeLayout::ENUM })
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        ast::TypeName::Option(inner, ast::StdlibOrDiplomat::Diplomat) => {
+            let inner = match &**inner {
+                ast::TypeName::Primitive(_) => {
+                    let inner = inner.to_syn();
+                    quote! { diplomat_runtime::layout::TypeLayout::of::<#inner>() }
+                }
+                inner => expected_field_layout(inner, module)?,
+            };
+            Some(quote! { diplomat_runtime::layout::TypeLayout::option(#inner) })
+        }
+        _ => None,
+    }
+}
+
 fn gen_bridge(mut input: ItemMod) -> ItemMod {
     let module = ast::Module::from_syn(&input, true);
     // Clean out any diplomat attributes so Rust doesn't get mad
@@ -497,6 +652,10 @@ fn gen_bridge(mut input: ItemMod) -> ItemMod {
             new_contents.push(gen_m);
         });
 
+        if let ast::CustomType::Struct(strct) = custom_type {
+            new_contents.push(gen_struct_layout_check(strct, &module));
+        }
+
         if let ast::CustomType::Opaque(opaque) = custom_type {
             let destroy_ident = Ident::new(opaque.dtor_abi_name.as_str(), Span::call_site());
 
@@ -513,13 +672,50 @@ fn gen_bridge(mut input: ItemMod) -> ItemMod {
 
             let cfg = cfgs_to_stream(&custom_type.attrs().cfg);
 
-            // for now, body is empty since all we need to do is drop the box
+            // for now, body only counts the destruction, since all we need to do is drop the box
             // TODO(#13): change to take a `*mut` and handle DST boxes appropriately
             new_contents.push(Item::Fn(syn::parse_quote! {
                 #[no_mangle]
                 #cfg
-                extern "C" fn #destroy_ident#lifetime_defs(this: Box<#type_ident#lifetimes>) {}
+                extern "C" fn #destroy_ident#lifetime_defs(this: Box<#type_ident#lifetimes>) {
+                    diplomat_runtime::leak_check::destroyed(&*this);
+                }
+            }));
+
+            let type_name = custom_type.name().as_str();
+            new_contents.push(Item::Impl(syn::parse_quote! {
+                #cfg
+                impl #lifetime_defs diplomat_runtime::leak_check::Tracked for #type_ident#lifetimes {
+                    fn live_count() -> &'static diplomat_runtime::leak_check::LiveCount {
+                        static LIVE_COUNT: diplomat_runtime::leak_check::LiveCount =
+                            diplomat_runtime::leak_check::LiveCount::new(#type_name);
+                        &LIVE_COUNT
+                    }
+                }
             }));
+
+            // Backends rely on the `send`/`sync`/`thread_safe` markers whichever backends they
+            // are enabled for, so they're checked against the Rust type unconditionally
+            let (mut send, mut sync) = (false, false);
+            for attr in &custom_type.attrs().attrs {
+                let path = attr.meta.path();
+                send |= path.is_ident("send") || path.is_ident("thread_safe");
+                sync |= path.is_ident("sync") || path.is_ident("thread_safe");
+            }
+            if send || sync {
+                let send = send.then(|| quote! { + ::core::marker::Send });
+                let sync = sync.then(|| quote! { + ::core::marker::Sync });
+                new_contents.push(Item::Const(syn::parse_quote! {
+                    #cfg
+                    #[allow(dead_code)]
+                    const _: () = {
+                        fn assert_thread_safety<T: ?Sized #send #sync>() {}
+                        fn check #lifetime_defs() {
+                            assert_thread_safety::<#type_ident #lifetimes>();
+                        }
+                    };
+                }));
+            }
         }
     }
 
@@ -1006,6 +1202,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn thread_safety_markers() {
+        insta::assert_snapshot!(rustfmt_code(
+            &gen_bridge(parse_quote! {
+                mod ffi {
+                    #[diplomat::opaque]
+                    #[diplomat::attr(*, thread_safe)]
+                    struct Foo(u8);
+
+                    #[diplomat::opaque]
+                    #[diplomat::attr(kotlin, send)]
+                    struct Bar<'a>(&'a Foo);
+                }
+            })
+            .to_token_stream()
+            .to_string()
+        ));
+    }
+
     #[test]
     fn callback_arguments() {
         insta::assert_snapshot!(rustfmt_code(
@@ -1139,4 +1354,33 @@ mod tests {
             .to_string()
         ));
     }
+
+    #[test]
+    fn struct_layout_check() {
+        insta::assert_snapshot!(rustfmt_code(
+            &gen_bridge(parse_quote! {
+                mod ffi {
+                    use diplomat_runtime::{DiplomatOption, DiplomatStrSlice};
+                    #[diplomat::opaque]
+                    struct Foo {}
+                    enum Kind {
+                        A,
+                        B,
+                    }
+                    struct Empty {}
+                    #[cfg(feature = "foo")]
+                    struct Options<'a> {
+                        kind: Kind,
+                        maybe_kind: DiplomatOption<Kind>,
+                        count: DiplomatOption<u32>,
+                        foo: Option<&'a Foo>,
+                        name: DiplomatStrSlice<'a>,
+                        flag: bool,
+                    }
+                }
+            })
+            .to_token_stream()
+            .to_string()
+        ));
+    }
 }
@@ -0,0 +1,168 @@
+//! Compile-time checks of the layout of bridged structs.
+//!
+//! Backends don't ask rustc how the `#[repr(C)]` structs of a bridge are laid out: diplomat-tool
+//! computes it (for JS), or the host language does from the fields the bindings declare (for C,
+//! C++, Dart and Kotlin). Both assume a layout for each field type, e.g. two pointer-sized words
+//! for slices and an `int32_t` for enums, and C's layout rules. `#[diplomat::bridge]` checks the
+//! structs against those assumptions with [`StructLayoutCheck`], so that a mismatch fails the build
+//! instead of corrupting memory.
+//!
+//! The JS bindings compute field offsets themselves rather than declaring fields, so on
+//! WebAssembly the bridge also records the actual layout of each struct with
+//! [`wasm_layout_record`], for the bindings to check at runtime.
+
+use core::mem;
+
+/// The size and alignment of a type.
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct TypeLayout {
+    size: usize,
+    align: usize,
+}
+
+impl TypeLayout {
+    /// Pointers to opaques, and nullable pointers to opaques.
+    pub const POINTER: Self = Self::of::<usize>();
+    /// `(pointer, length)` pairs, which all slice and string types are.
+    pub const SLICE: Self = Self {
+        size: 2 * mem::size_of::<usize>(),
+        align: mem::align_of::<usize>(),
+    };
+    /// Fieldless enums, which are passed as `int32_t`.
+    pub const ENUM: Self = Self::of::<i32>();
+
+    pub const fn of<T>() -> Self {
+        Self {
+            size: mem::size_of::<T>(),
+            align: mem::align_of::<T>(),
+        }
+    }
+
+    /// A `DiplomatOption` of a type with the `inner` layout: the value followed by a `bool`.
+    pub const fn option(inner: Self) -> Self {
+        Self {
+            size: round_up(inner.size + 1, inner.align),
+            align: inner.align,
+        }
+    }
+}
+
+/// Checks the fields of a struct one by one, then the struct itself.
+#[doc(hidden)]
+pub struct StructLayoutCheck {
+    /// The end of the previous field
+    end: usize,
+    /// The largest alignment of the fields so far
+    align: usize,
+}
+
+impl StructLayoutCheck {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self { end: 0, align: 1 }
+    }
+
+    /// Checks the next field of the struct at `base`, at `field`, which backends expect to have
+    /// the `expected` layout (or its Rust type's layout, if `None`).
+    ///
+    /// # Safety
+    /// `field` must point to a field of `*base`.
+    pub const unsafe fn field<S, F>(
+        self,
+        base: *const S,
+        field: *const F,
+        expected: Option<TypeLayout>,
+    ) -> Self {
+        let actual = TypeLayout::of::<F>();
+        if let Some(expected) = expected {
+            assert!(
+                actual.size == expected.size && actual.align == expected.align,
+                "a field of a bridged struct doesn't have the layout backends assume for its type"
+            );
+        }
+        let offset = offset_of(base, field);
+        assert!(
+            offset == round_up(self.end, actual.align),
+            "a field of a bridged struct isn't where backends assume it is, the struct must be `#[repr(C)]`"
+        );
+        Self {
+            end: offset + actual.size,
+            align: if actual.align > self.align {
+                actual.align
+            } else {
+                self.align
+            },
+        }
+    }
+
+    /// Checks the size and alignment of the struct once all fields have been checked.
+    pub const fn finish<S>(self) {
+        let actual = TypeLayout::of::<S>();
+        assert!(
+            actual.size == round_up(self.end, self.align) && actual.align == self.align,
+            "a bridged struct doesn't have the size or alignment backends assume, the struct must be `#[repr(C)]`"
+        );
+    }
+}
+
+/// The offset of `field` in the struct at `base`.
+///
+/// # Safety
+/// `field` must point to a field of `*base`.
+#[doc(hidden)]
+pub const unsafe fn offset_of<S, F>(base: *const S, field: *const F) -> usize {
+    // `offset_from` is const since 1.65, within the `rust-version` of the workspace
+    #[allow(clippy::incompatible_msrv)]
+    let offset = (field as *const u8).offset_from(base as *const u8);
+    offset as usize
+}
+
+/// The record of the struct `S` named `name` in the `diplomat_layout` custom section of
+/// WebAssembly modules, which the JS bindings check their layout against.
+///
+/// It is made of little-endian `u32`s: the length of the name followed by its bytes, then the
+/// number of values that follow, the size and alignment of the struct and the offsets of its
+/// fields. `N` must be the length of the record, `name.len() + 4 * (4 + offsets.len())`.
+#[doc(hidden)]
+pub const fn wasm_layout_record<S, const N: usize>(name: &str, offsets: &[usize]) -> [u8; N] {
+    const fn write_u32<const N: usize>(mut record: [u8; N], pos: usize, value: usize) -> [u8; N] {
+        let bytes = (value as u32).to_le_bytes();
+        let mut i = 0;
+        while i < 4 {
+            record[pos + i] = bytes[i];
+            i += 1;
+        }
+        record
+    }
+
+    let name = name.as_bytes();
+    assert!(
+        N == name.len() + 4 * (4 + offsets.len()),
+        "the layout record has the wrong length"
+    );
+    let mut record = write_u32([0; N], 0, name.len());
+    let mut pos = 4;
+    let mut i = 0;
+    while i < name.len() {
+        record[pos] = name[i];
+        pos += 1;
+        i += 1;
+    }
+    let layout = TypeLayout::of::<S>();
+    record = write_u32(record, pos, 2 + offsets.len());
+    record = write_u32(record, pos + 4, layout.size);
+    record = write_u32(record, pos + 8, layout.align);
+    pos += 12;
+    let mut i = 0;
+    while i < offsets.len() {
+        record = write_u32(record, pos, offsets[i]);
+        pos += 4;
+        i += 1;
+    }
+    record
+}
+
+const fn round_up(n: usize, align: usize) -> usize {
+    (n + align - 1) / align * align
+}
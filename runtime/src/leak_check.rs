@@ -0,0 +1,163 @@
+//! Counting of live opaques and runtime allocations, to find leaks in bindings.
+//!
+//! `#[diplomat::bridge]` counts every opaque returned to the host and destroyed by it, and the runtime
+//! counts the buffers from [`diplomat_alloc()`](crate::diplomat_alloc), owned slices and
+//! [`DiplomatWrite`] buffers. Without the `leak-check` feature, nothing is counted and
+//! the report functions aren't exported.
+
+#[cfg(feature = "leak-check")]
+use crate::DiplomatWrite;
+#[cfg(feature = "leak-check")]
+use core::sync::atomic::{AtomicBool, AtomicIsize, AtomicPtr, Ordering};
+
+/// Implemented by `#[diplomat::bridge]` for every opaque type.
+#[doc(hidden)]
+pub trait Tracked {
+    fn live_count() -> &'static LiveCount;
+}
+
+/// The number of live instances of an opaque type.
+#[doc(hidden)]
+pub struct LiveCount {
+    #[cfg(feature = "leak-check")]
+    name: &'static str,
+    #[cfg(feature = "leak-check")]
+    live: AtomicIsize,
+    #[cfg(feature = "leak-check")]
+    registered: AtomicBool,
+    /// The next count in [`OPAQUES`], once registered.
+    #[cfg(feature = "leak-check")]
+    next: AtomicPtr<LiveCount>,
+}
+
+impl LiveCount {
+    #[allow(unused_variables)]
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            #[cfg(feature = "leak-check")]
+            name,
+            #[cfg(feature = "leak-check")]
+            live: AtomicIsize::new(0),
+            #[cfg(feature = "leak-check")]
+            registered: AtomicBool::new(false),
+            #[cfg(feature = "leak-check")]
+            next: AtomicPtr::new(core::ptr::null_mut()),
+        }
+    }
+}
+
+/// All opaque types that have been created, as a linked list of their `LiveCount`s.
+#[cfg(feature = "leak-check")]
+static OPAQUES: AtomicPtr<LiveCount> = AtomicPtr::new(core::ptr::null_mut());
+/// Live buffers from `diplomat_alloc` and owned slices.
+#[cfg(feature = "leak-check")]
+static ALLOCATIONS: AtomicIsize = AtomicIsize::new(0);
+/// Live `DiplomatWrite`s and `DiplomatWrite16`s from `diplomat_buffer_write_create` and `diplomat_buffer_write16_create`.
+#[cfg(feature = "leak-check")]
+static WRITES: AtomicIsize = AtomicIsize::new(0);
+
+/// Counts an opaque returned to the host.
+#[doc(hidden)]
+#[inline]
+#[allow(unused_variables)]
+pub fn created<T: Tracked + ?Sized>(opaque: &T) {
+    #[cfg(feature = "leak-check")]
+    {
+        let count = T::live_count();
+        count.live.fetch_add(1, Ordering::Relaxed);
+        if !count.registered.swap(true, Ordering::AcqRel) {
+            let ptr = count as *const LiveCount as *mut LiveCount;
+            let mut head = OPAQUES.load(Ordering::Acquire);
+            loop {
+                count.next.store(head, Ordering::Relaxed);
+                match OPAQUES.compare_exchange_weak(head, ptr, Ordering::AcqRel, Ordering::Acquire)
+                {
+                    Ok(_) => break,
+                    Err(new_head) => head = new_head,
+                }
+            }
+        }
+    }
+}
+
+/// Counts an opaque destroyed by the host.
+#[doc(hidden)]
+#[inline]
+#[allow(unused_variables)]
+pub fn destroyed<T: Tracked + ?Sized>(opaque: &T) {
+    #[cfg(feature = "leak-check")]
+    T::live_count().live.fetch_sub(1, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn allocated() {
+    #[cfg(feature = "leak-check")]
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn freed() {
+    #[cfg(feature = "leak-check")]
+    ALLOCATIONS.fetch_sub(1, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn write_created() {
+    #[cfg(feature = "leak-check")]
+    WRITES.fetch_add(1, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn write_destroyed() {
+    #[cfg(feature = "leak-check")]
+    WRITES.fetch_sub(1, Ordering::Relaxed);
+}
+
+/// Calls `f` with the name and live count of every leaked kind of object.
+///
+/// `exclude_write` excludes a `DiplomatWrite` buffer from the count, for the one the report is written to.
+#[cfg(feature = "leak-check")]
+fn for_each_leak(exclude_write: bool, mut f: impl FnMut(&str, isize)) {
+    let mut count = OPAQUES.load(Ordering::Acquire);
+    while let Some(c) = unsafe { count.as_ref() } {
+        let live = c.live.load(Ordering::Relaxed);
+        if live != 0 {
+            f(c.name, live);
+        }
+        count = c.next.load(Ordering::Relaxed);
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    if allocations != 0 {
+        f("runtime allocations", allocations);
+    }
+    let writes = WRITES.load(Ordering::Relaxed) - exclude_write as isize;
+    if writes != 0 {
+        f("DiplomatWrite buffers", writes);
+    }
+}
+
+/// Returns the number of live opaques and runtime allocations.
+///
+/// This is 0 if the host freed everything the library returned to it.
+#[cfg(feature = "leak-check")]
+#[no_mangle]
+pub extern "C" fn diplomat_leak_count() -> usize {
+    let mut total = 0;
+    for_each_leak(false, |_, live| total += live.unsigned_abs());
+    total
+}
+
+/// Writes a report of the live opaques and runtime allocations, one `<name>: <count>` line per
+/// kind of object, or nothing if there are none.
+///
+/// A negative count means more objects were freed than created, e.g. because of a double free.
+#[cfg(feature = "leak-check")]
+#[no_mangle]
+pub extern "C" fn diplomat_leak_report(write: &mut DiplomatWrite) {
+    use core::fmt::Write;
+    let exclude_write = crate::write::is_buffer_write(write);
+    for_each_leak(exclude_write, |name, live| {
+        let _infallible = writeln!(write, "{name}: {live}");
+    });
+    write.flush();
+}
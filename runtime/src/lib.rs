@@ -1,16 +1,47 @@
-#![cfg_attr(not(any(target_arch = "wasm32")), no_std)]
+#![cfg_attr(
+    not(any(target_arch = "wasm32", target_arch = "wasm64", feature = "std")),
+    no_std
+)]
 
 extern crate alloc;
 
 use alloc::alloc::Layout;
 
-#[cfg(target_arch = "wasm32")]
-// defines `extern "C" diplomat_init()`
+#[cfg(all(
+    any(target_arch = "wasm32", target_arch = "wasm64"),
+    not(target_os = "wasi")
+))]
+// defines `extern "C" diplomat_init()`, and imports from the JS runtime (which WASI hosts don't provide)
 mod wasm_glue;
 
+#[cfg(feature = "log")]
+mod logging;
+#[cfg(all(
+    feature = "log",
+    not(all(
+        any(target_arch = "wasm32", target_arch = "wasm64"),
+        not(target_os = "wasi")
+    ))
+))]
+pub use logging::DiplomatLogSink;
+
+pub mod layout;
+pub mod leak_check;
+
 mod write;
-pub use write::DiplomatWrite;
-pub use write::{diplomat_buffer_write_create, diplomat_buffer_write_destroy};
+pub use write::{
+    diplomat_buffer_write_create, diplomat_buffer_write_destroy, diplomat_buffer_write_get_bytes,
+    diplomat_buffer_write_len, diplomat_stream_write_create, diplomat_stream_write_destroy,
+};
+pub use write::{DiplomatWrite, DiplomatWriteSink};
+mod read;
+pub use read::{diplomat_read_create, diplomat_read_destroy, DiplomatRead, DiplomatReadFn};
+mod write16;
+pub use write16::DiplomatWrite16;
+pub use write16::{
+    diplomat_buffer_write16_create, diplomat_buffer_write16_destroy,
+    diplomat_buffer_write16_get_units, diplomat_buffer_write16_len,
+};
 mod slices;
 pub use slices::{
     DiplomatOwnedSlice, DiplomatOwnedStr16Slice, DiplomatOwnedStrSlice, DiplomatOwnedUTF8StrSlice,
@@ -43,6 +74,9 @@ pub type DiplomatByte = u8;
 /// - The allocated buffer must be freed with [`diplomat_free()`].
 #[no_mangle]
 pub unsafe extern "C" fn diplomat_alloc(size: usize, align: usize) -> *mut u8 {
+    if size != 0 {
+        leak_check::allocated();
+    }
     alloc::alloc::alloc(Layout::from_size_align(size, align).unwrap())
 }
 
@@ -51,6 +85,9 @@ pub unsafe extern "C" fn diplomat_alloc(size: usize, align: usize) -> *mut u8 {
 /// - `ptr` must be a pointer to a valid buffer allocated by [`diplomat_alloc()`].
 #[no_mangle]
 pub unsafe extern "C" fn diplomat_free(ptr: *mut u8, size: usize, align: usize) {
+    if size != 0 {
+        leak_check::freed();
+    }
     alloc::alloc::dealloc(ptr, Layout::from_size_align(size, align).unwrap())
 }
 
@@ -0,0 +1,99 @@
+//! Forwarding of [`log`] records to a sink registered by the host.
+//!
+//! On wasm (outside of WASI) records are instead written to the JS console by `wasm_glue`, and only
+//! [`diplomat_log_set_max_level`] is available.
+
+/// A host function receiving the library's log records.
+///
+/// `level` is 1 (error) to 5 (trace), as in [`log::Level`]. `target` and `message` are UTF-8, and only
+/// valid for the duration of the call. The sink may be called from any thread that logs.
+#[cfg(not(all(
+    any(target_arch = "wasm32", target_arch = "wasm64"),
+    not(target_os = "wasi")
+)))]
+pub type DiplomatLogSink = extern "C" fn(
+    level: u8,
+    target: *const u8,
+    target_len: usize,
+    message: *const u8,
+    message_len: usize,
+);
+
+#[cfg(not(all(
+    any(target_arch = "wasm32", target_arch = "wasm64"),
+    not(target_os = "wasi")
+)))]
+mod sink {
+    use super::DiplomatLogSink;
+    use alloc::borrow::Cow;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    /// The registered [`DiplomatLogSink`], or 0.
+    static SINK: AtomicUsize = AtomicUsize::new(0);
+
+    struct SinkLogger;
+
+    impl log::Log for SinkLogger {
+        #[inline]
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::max_level() && SINK.load(Ordering::Relaxed) != 0
+        }
+
+        fn log(&self, record: &log::Record) {
+            if record.level() > log::max_level() {
+                return;
+            }
+            let sink = SINK.load(Ordering::Acquire);
+            if sink == 0 {
+                return;
+            }
+            // Safety: only `DiplomatLogSink`s are stored in `SINK`.
+            let sink: DiplomatLogSink = unsafe { core::mem::transmute(sink) };
+
+            let message = match record.args().as_str() {
+                Some(message) => Cow::Borrowed(message),
+                None => Cow::Owned(alloc::format!("{}", record.args())),
+            };
+            let target = record.target();
+            sink(
+                record.level() as u8,
+                target.as_ptr(),
+                target.len(),
+                message.as_ptr(),
+                message.len(),
+            );
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Register `sink` to receive the library's log records up to `max_level` (see
+    /// [`diplomat_log_set_max_level`]), replacing the previously registered sink. A null `sink` stops
+    /// forwarding records.
+    ///
+    /// The first call installs the global [`log`] logger; if the library already installed its own
+    /// logger, records keep going to that logger instead.
+    #[no_mangle]
+    pub extern "C" fn diplomat_log_set_sink(sink: Option<DiplomatLogSink>, max_level: u8) {
+        SINK.store(sink.map_or(0, |sink| sink as usize), Ordering::Release);
+        // Fails if a logger is already installed, which is either ours or the library's.
+        let _ = log::set_logger(&SinkLogger);
+        super::diplomat_log_set_max_level(max_level);
+    }
+}
+
+/// Set the most verbose level of log records that are emitted: 0 (off), 1 (error), 2 (warn), 3 (info),
+/// 4 (debug) or 5 (trace). Larger values are treated as trace.
+///
+/// Records above this level are filtered out before they are formatted.
+#[no_mangle]
+pub extern "C" fn diplomat_log_set_max_level(level: u8) {
+    log::set_max_level(match level {
+        0 => log::LevelFilter::Off,
+        1 => log::LevelFilter::Error,
+        2 => log::LevelFilter::Warn,
+        3 => log::LevelFilter::Info,
+        4 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    });
+}
@@ -0,0 +1,163 @@
+use alloc::boxed::Box;
+use core::convert::TryFrom;
+use core::ffi::c_void;
+
+/// A source of bytes provided by the foreign side, which Rust reads from
+///
+/// This allows methods to take input incrementally from a host data source, for example a
+/// C++ `std::istream`, instead of requiring the whole input to be collected into a slice first.
+/// Methods take it as a `&mut DiplomatRead` parameter.
+///
+/// The foreign side fills out `read` with a callback (using `context` to reference any state it
+/// needs), and Rust calls it until it has read as much as it needs. With `std` it implements
+/// [`std::io::Read`].
+///
+/// [`diplomat_read_create()`] can be used by languages that can't lay out this struct themselves.
+///
+/// DiplomatRead will not perform any cleanup on `context`, it is logically a "borrow" from the
+/// FFI side.
+///
+/// # Safety invariants:
+///  - `read()` will be passed `context` and it should always be safe to do so.
+///    `context` may be null, however `read()` must then be ready to receive it as such.
+///  - `read()` must not write more than `len` bytes to `buf`, and must return at most `len`.
+#[repr(C)]
+pub struct DiplomatRead {
+    /// Context pointer for additional data needed by `read()`. May be `null`.
+    context: *mut c_void,
+    /// Called by Rust to read more input.
+    ///
+    /// Arguments:
+    /// - `context` (`*mut c_void`): The context pointer
+    /// - `buf` (`*mut u8`): The buffer to read into
+    /// - `len` (`usize`): The length of `buf`
+    ///
+    /// Returns: The number of bytes read into `buf`, `0` at the end of the input (or if `len` is
+    /// `0`), or a negative number if reading failed.
+    read: DiplomatReadFn,
+}
+
+/// The callback of a [`DiplomatRead`], reading at most `len` bytes into `buf`.
+pub type DiplomatReadFn = extern "C" fn(context: *mut c_void, buf: *mut u8, len: usize) -> isize;
+
+impl DiplomatRead {
+    /// Reads some bytes into `buf`, returning how many were read.
+    ///
+    /// Returns `Some(0)` at the end of the input, and `None` if the foreign side failed to read.
+    pub fn read_bytes(&mut self, buf: &mut [u8]) -> Option<usize> {
+        let read = (self.read)(self.context, buf.as_mut_ptr(), buf.len());
+        usize::try_from(read).ok().map(|read| read.min(buf.len()))
+    }
+}
+
+#[cfg(any(feature = "std", target_arch = "wasm32", target_arch = "wasm64"))]
+impl std::io::Read for DiplomatRead {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.read_bytes(buf)
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "DiplomatRead failed"))
+    }
+}
+
+/// Create a [`DiplomatRead`] that calls `read` with `context`.
+///
+/// Use [`diplomat_read_destroy()`] to free it.
+#[no_mangle]
+pub extern "C" fn diplomat_read_create(
+    context: *mut c_void,
+    read: DiplomatReadFn,
+) -> *mut DiplomatRead {
+    crate::leak_check::allocated();
+    Box::into_raw(Box::new(DiplomatRead { context, read }))
+}
+
+/// Destructor for readers created by [`diplomat_read_create()`].
+///
+/// # Safety
+/// - `this` must be a pointer to a valid [`DiplomatRead`] constructed by
+///   [`diplomat_read_create()`].
+#[no_mangle]
+pub unsafe extern "C" fn diplomat_read_destroy(this: *mut DiplomatRead) {
+    drop(Box::from_raw(this));
+    crate::leak_check::freed();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Reads at most `max` bytes of `data` at a time.
+    struct Source<'a> {
+        data: &'a [u8],
+        max: usize,
+        failed: bool,
+    }
+
+    extern "C" fn read_source(context: *mut c_void, buf: *mut u8, len: usize) -> isize {
+        let source = unsafe { &mut *(context as *mut Source) };
+        if source.failed {
+            return -1;
+        }
+        let n = len.min(source.max).min(source.data.len());
+        unsafe { core::ptr::copy_nonoverlapping(source.data.as_ptr(), buf, n) };
+        source.data = &source.data[n..];
+        n as isize
+    }
+
+    fn reader(source: &mut Source) -> DiplomatRead {
+        DiplomatRead {
+            context: source as *mut Source as *mut c_void,
+            read: read_source,
+        }
+    }
+
+    #[test]
+    fn test_short_reads() {
+        let mut source = Source {
+            data: b"abcdefg",
+            max: 3,
+            failed: false,
+        };
+        let mut read = reader(&mut source);
+        let mut buf = [0; 8];
+        assert_eq!(read.read_bytes(&mut buf), Some(3));
+        assert_eq!(&buf[..3], b"abc");
+        assert_eq!(read.read_bytes(&mut buf[..2]), Some(2));
+        assert_eq!(&buf[..2], b"de");
+        assert_eq!(read.read_bytes(&mut buf), Some(2));
+        assert_eq!(&buf[..2], b"fg");
+        // EOF, for as long as it is read from
+        assert_eq!(read.read_bytes(&mut buf), Some(0));
+        assert_eq!(read.read_bytes(&mut buf), Some(0));
+    }
+
+    #[test]
+    fn test_eof_and_failure() {
+        let mut source = Source {
+            data: b"",
+            max: 3,
+            failed: false,
+        };
+        assert_eq!(reader(&mut source).read_bytes(&mut [0; 4]), Some(0));
+        source.failed = true;
+        assert_eq!(reader(&mut source).read_bytes(&mut [0; 4]), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io_read() {
+        use std::io::Read;
+
+        let mut source = Source {
+            data: b"hello world",
+            max: 4,
+            failed: false,
+        };
+        let mut out = Vec::new();
+        reader(&mut source).read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"hello world");
+
+        source.failed = true;
+        let err = reader(&mut source).read_to_end(&mut out).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Other);
+    }
+}
@@ -128,6 +128,9 @@ pub struct DiplomatOwnedSlice<T> {
 impl<T> Drop for DiplomatOwnedSlice<T> {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
+            if self.is_allocated() {
+                crate::leak_check::freed();
+            }
             unsafe {
                 // Safety: This is equivalent to a valid Box
                 drop(Box::from_raw(core::ptr::slice_from_raw_parts_mut(
@@ -142,11 +145,22 @@ impl<T> From<Box<[T]>> for DiplomatOwnedSlice<T> {
     fn from(x: Box<[T]>) -> Self {
         // Safe to construct since we're constructing it from a valid Box<[T]>
         let len = x.len();
-        DiplomatOwnedSlice {
+        let slice = DiplomatOwnedSlice {
             ptr: Box::into_raw(x) as *mut T,
             len,
             phantom: PhantomData,
+        };
+        if slice.is_allocated() {
+            crate::leak_check::allocated();
         }
+        slice
+    }
+}
+
+impl<T> DiplomatOwnedSlice<T> {
+    /// Whether the slice owns an allocation, as opposed to being empty or zero-sized.
+    fn is_allocated(&self) -> bool {
+        !self.ptr.is_null() && self.len * core::mem::size_of::<T>() != 0
     }
 }
 
@@ -159,6 +173,9 @@ impl<T> From<DiplomatOwnedSlice<T>> for Box<[T]> {
                 let dangling = core::ptr::NonNull::dangling().as_ptr();
                 return Box::from_raw(core::ptr::slice_from_raw_parts_mut(dangling, x.len));
             }
+            if x.is_allocated() {
+                crate::leak_check::freed();
+            }
             // Safety: carrying over safety variants from DiplomatOwnedSlice
             Box::from_raw(core::ptr::slice_from_raw_parts_mut(x.ptr, x.len))
         }
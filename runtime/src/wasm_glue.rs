@@ -1,15 +1,91 @@
-use alloc::format;
+use std::sync::Mutex;
 
 #[no_mangle]
 unsafe extern "C" fn diplomat_init() {
     #[cfg(debug_assertions)]
-    std::panic::set_hook(Box::new(panic_handler));
+    diplomat_install_panic_hook();
     #[cfg(feature = "log")]
     log::set_logger(&ConsoleLogger)
         .map(|()| log::set_max_level(log::LevelFilter::Debug))
         .unwrap();
 }
 
+/// Installs a panic hook that records the panic for [`diplomat_last_panic()`], and throws it
+/// as a `RustPanicError` in JS.
+///
+/// `diplomat_init()` installs it in debug builds; release builds install it when `panic_hook`
+/// is set in `diplomat.config.mjs`.
+#[no_mangle]
+extern "C" fn diplomat_install_panic_hook() {
+    std::panic::set_hook(Box::new(panic_handler));
+}
+
+/// The message and location of a panic, as read by `RustPanicError.last()` in JS.
+///
+/// `file` is empty and `line` and `column` are 0 if the location is unknown.
+#[repr(C)]
+struct DiplomatPanicInfo {
+    message: *const u8,
+    message_len: usize,
+    file: *const u8,
+    file_len: usize,
+    line: u32,
+    column: u32,
+}
+
+struct LastPanic {
+    // Owns the buffers `info` points to
+    _message: String,
+    _file: String,
+    info: DiplomatPanicInfo,
+}
+
+// SAFETY: the pointers in `info` point into the strings owned by the same `LastPanic`
+unsafe impl Send for LastPanic {}
+
+static LAST_PANIC: Mutex<Option<LastPanic>> = Mutex::new(None);
+
+/// Returns the last panic recorded by the panic hook, or null if there was none.
+///
+/// The returned pointer is valid until the next panic.
+#[no_mangle]
+extern "C" fn diplomat_last_panic() -> *const DiplomatPanicInfo {
+    match &*LAST_PANIC.lock().unwrap_or_else(|e| e.into_inner()) {
+        Some(last) => &last.info,
+        None => core::ptr::null(),
+    }
+}
+
+/// Create a [`DiplomatWrite`](crate::DiplomatWrite) that passes its output to the sink registered
+/// in JS under `id` (see `DiplomatStreamWrite` in the JS runtime), in chunks of at most `cap` bytes.
+///
+/// Free it with [`diplomat_stream_write_destroy()`](crate::diplomat_stream_write_destroy).
+#[no_mangle]
+extern "C" fn diplomat_stream_write_create_js(id: usize, cap: usize) -> *mut crate::DiplomatWrite {
+    extern "C" {
+        fn diplomat_stream_write_js(id: usize, chunk: *const u8, len: usize) -> bool;
+    }
+    extern "C" fn sink(context: *mut core::ffi::c_void, chunk: *const u8, len: usize) -> bool {
+        unsafe { diplomat_stream_write_js(context as usize, chunk, len) }
+    }
+    crate::diplomat_stream_write_create(id as *mut core::ffi::c_void, sink, cap)
+}
+
+/// Create a [`DiplomatRead`](crate::DiplomatRead) that reads from the source registered in JS
+/// under `id` (see `DiplomatReader` in the JS runtime).
+///
+/// Free it with [`diplomat_read_destroy()`](crate::diplomat_read_destroy).
+#[no_mangle]
+extern "C" fn diplomat_read_create_js(id: usize) -> *mut crate::DiplomatRead {
+    extern "C" {
+        fn diplomat_read_js(id: usize, buf: *mut u8, len: usize) -> isize;
+    }
+    extern "C" fn read(context: *mut core::ffi::c_void, buf: *mut u8, len: usize) -> isize {
+        unsafe { diplomat_read_js(context as usize, buf, len) }
+    }
+    crate::diplomat_read_create(id as *mut core::ffi::c_void, read)
+}
+
 fn panic_handler(info: &std::panic::PanicInfo) {
     let msg = match info.payload().downcast_ref::<&'static str>() {
         Some(&s) => s,
@@ -19,7 +95,28 @@ fn panic_handler(info: &std::panic::PanicInfo) {
         },
     };
 
-    let msg = match info.location() {
+    let message = msg.to_string();
+    let (file, line, column) = match info.location() {
+        Some(l) => (l.file().to_string(), l.line(), l.column()),
+        None => (String::new(), 0, 0),
+    };
+
+    *LAST_PANIC.lock().unwrap_or_else(|e| e.into_inner()) = Some(LastPanic {
+        info: DiplomatPanicInfo {
+            message: message.as_ptr(),
+            message_len: message.len(),
+            file: file.as_ptr(),
+            file_len: file.len(),
+            line,
+            column,
+        },
+        _message: message,
+        _file: file,
+    });
+
+    // Hosts written for earlier versions throw an `Error` with the formatted message, the
+    // generated bindings ignore it and throw `RustPanicError.last()` instead
+    let formatted = match info.location() {
         Some(l) => format!(
             "wasm panicked at {}:{}:{}:\n{msg}",
             l.file(),
@@ -33,7 +130,7 @@ fn panic_handler(info: &std::panic::PanicInfo) {
         fn diplomat_throw_error_js(ptr: *const u8, len: usize);
     }
 
-    unsafe { diplomat_throw_error_js(msg.as_ptr(), msg.len()) }
+    unsafe { diplomat_throw_error_js(formatted.as_ptr(), formatted.len()) }
 }
 
 #[cfg(feature = "log")]
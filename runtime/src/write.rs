@@ -18,7 +18,8 @@ use core::{fmt, ptr};
 /// need not perform additional state updates after passing an [`DiplomatWrite`] to
 /// a function.
 ///
-/// [`diplomat_simple_write()`] can be used to write to a fixed-size char buffer.
+/// [`diplomat_simple_write()`] can be used to write to a fixed-size char buffer, and
+/// [`diplomat_stream_write_create()`] to pass the output to a callback as it is written.
 ///
 /// May be extended in the future to support further invariants
 ///
@@ -29,8 +30,8 @@ use core::{fmt, ptr};
 ///  - `flush()` and `grow()` will be passed `self` including `context` and it should always be safe to do so.
 ///     `context` may be  null, however `flush()` and `grow()` must then be ready to receive it as such.
 ///  - `buf` must be `cap` bytes long
-///  - `grow()` must either return false or update `buf` and `cap` for a valid buffer
-///    of at least the requested buffer size
+///  - `grow()` must either return false, update `buf` and `cap` for a valid buffer
+///    of at least the requested buffer size, or consume the contents of the buffer and reduce `len`
 ///  - `DiplomatWrite::flush()` will be automatically called by Diplomat. `flush()` might also be called
 ///    (erroneously) on the Rust side (it's a public method), so it must be idempotent.
 #[repr(C)]
@@ -58,6 +59,10 @@ pub struct DiplomatWrite {
     /// Called by Rust to request more capacity in the buffer. The implementation should allocate a new
     /// buffer and copy the contents of the old buffer into the new buffer, updating `self.buf` and `self.cap`
     ///
+    /// To stream the output instead, the implementation can consume the contents of the buffer and reset
+    /// `self.len`. If the buffer is still too small, Rust fills it and calls `grow()` again, so
+    /// the consumed chunks may end in the middle of a UTF-8 character.
+    ///
     /// Arguments:
     /// - `self` (`*mut DiplomatWrite`): This `DiplomatWrite`
     /// - `capacity` (`usize`): The requested capacity.
@@ -77,19 +82,32 @@ impl fmt::Write for DiplomatWrite {
         if self.grow_failed {
             return Ok(());
         }
-        let needed_len = self.len + s.len();
-        if needed_len > self.cap {
-            let success = (self.grow)(self, needed_len);
+        let mut rest = s.as_bytes();
+        while self.len + rest.len() > self.cap {
+            let success = (self.grow)(self, self.len + rest.len());
             if !success {
                 self.grow_failed = true;
                 return Ok(());
             }
+            if self.len + rest.len() <= self.cap {
+                break;
+            }
+            // `grow` consumed the buffer, but it's too small for the rest of the string
+            let chunk_len = self.cap - self.len;
+            if chunk_len == 0 {
+                self.grow_failed = true;
+                return Ok(());
+            }
+            unsafe {
+                ptr::copy_nonoverlapping(rest.as_ptr(), self.buf.add(self.len), chunk_len);
+            }
+            self.len += chunk_len;
+            rest = &rest[chunk_len..];
         }
-        debug_assert!(needed_len <= self.cap);
         unsafe {
-            ptr::copy_nonoverlapping(s.as_bytes().as_ptr(), self.buf.add(self.len), s.len());
+            ptr::copy_nonoverlapping(rest.as_ptr(), self.buf.add(self.len), rest.len());
         }
-        self.len = needed_len;
+        self.len += rest.len();
         Ok(())
     }
 }
@@ -125,40 +143,52 @@ pub unsafe extern "C" fn diplomat_simple_write(buf: *mut u8, buf_size: usize) ->
     }
 }
 
+/// The `context` of writables constructed by [`diplomat_buffer_write_create()`], which don't need one.
+///
+/// Marks them so that they can be told apart from foreign writables.
+static BUFFER_WRITE_CONTEXT: u8 = 0;
+
 /// Create an [`DiplomatWrite`] that can write to a dynamically allocated buffer managed by Rust.
 ///
 /// Use [`diplomat_buffer_write_destroy()`] to free the writable and its underlying buffer.
 #[no_mangle]
 pub extern "C" fn diplomat_buffer_write_create(cap: usize) -> *mut DiplomatWrite {
-    extern "C" fn grow(this: *mut DiplomatWrite, new_cap: usize) -> bool {
-        unsafe {
-            let this = this.as_mut().unwrap();
-            let mut vec = Vec::from_raw_parts(this.buf, 0, this.cap);
-            vec.reserve(new_cap);
-            this.cap = vec.capacity();
-            this.buf = vec.as_mut_ptr();
-            core::mem::forget(vec);
-        }
-        true
-    }
-
     extern "C" fn flush(_: *mut DiplomatWrite) {}
 
     let mut vec = Vec::<u8>::with_capacity(cap);
     let ret = DiplomatWrite {
-        context: ptr::null_mut(),
+        context: &BUFFER_WRITE_CONTEXT as *const u8 as *mut c_void,
         buf: vec.as_mut_ptr(),
         len: 0,
         grow_failed: false,
         cap,
         flush,
-        grow,
+        grow: buffer_write_grow,
     };
 
     core::mem::forget(vec);
+    crate::leak_check::write_created();
     Box::into_raw(Box::new(ret))
 }
 
+extern "C" fn buffer_write_grow(this: *mut DiplomatWrite, new_cap: usize) -> bool {
+    unsafe {
+        let this = this.as_mut().unwrap();
+        let mut vec = Vec::from_raw_parts(this.buf, 0, this.cap);
+        vec.reserve(new_cap);
+        this.cap = vec.capacity();
+        this.buf = vec.as_mut_ptr();
+        core::mem::forget(vec);
+    }
+    true
+}
+
+/// Whether `this` was constructed by [`diplomat_buffer_write_create()`].
+#[cfg(feature = "leak-check")]
+pub(crate) fn is_buffer_write(this: &DiplomatWrite) -> bool {
+    ptr::eq(this.context as *const u8, &BUFFER_WRITE_CONTEXT)
+}
+
 /// Grabs a pointer to the underlying buffer of a writable.
 ///
 /// Returns null if there was an allocation error during the write construction.
@@ -203,4 +233,171 @@ pub unsafe extern "C" fn diplomat_buffer_write_destroy(this: *mut DiplomatWrite)
     let vec = Vec::from_raw_parts(this.buf, 0, this.cap);
     drop(vec);
     drop(this);
+    crate::leak_check::write_destroyed();
+}
+
+/// Receives the output of a [`DiplomatWrite`] created by [`diplomat_stream_write_create()`].
+///
+/// Called with the `context` the write was created with and a chunk of UTF-8, which is only valid
+/// during the call and may end in the middle of a character. Returns `false` to stop the write,
+/// in which case the rest of the output is discarded.
+pub type DiplomatWriteSink =
+    extern "C" fn(context: *mut c_void, chunk: *const u8, len: usize) -> bool;
+
+struct StreamContext {
+    context: *mut c_void,
+    sink: DiplomatWriteSink,
+}
+
+/// Create a [`DiplomatWrite`] that passes its output to `sink` as it is written, in chunks of
+/// at most `cap` bytes, instead of collecting it.
+///
+/// `sink` is called whenever the buffer is full, and with the rest of the output when the write
+/// is flushed. Nothing is written after it returns `false`.
+///
+/// Use [`diplomat_stream_write_destroy()`] to free the writable and its buffer.
+#[no_mangle]
+pub extern "C" fn diplomat_stream_write_create(
+    context: *mut c_void,
+    sink: DiplomatWriteSink,
+    cap: usize,
+) -> *mut DiplomatWrite {
+    fn drain(this: &mut DiplomatWrite) -> bool {
+        let stream = unsafe { &*(this.context as *const StreamContext) };
+        if this.len > 0 && !(stream.sink)(stream.context, this.buf, this.len) {
+            return false;
+        }
+        this.len = 0;
+        true
+    }
+    extern "C" fn grow(this: *mut DiplomatWrite, _cap: usize) -> bool {
+        drain(unsafe { this.as_mut().unwrap() })
+    }
+    extern "C" fn flush(this: *mut DiplomatWrite) {
+        let this = unsafe { this.as_mut().unwrap() };
+        if !this.grow_failed && !drain(this) {
+            this.grow_failed = true;
+        }
+    }
+
+    // A buffer that can't hold anything could never be written to
+    let cap = cap.max(1);
+    let mut vec = Vec::<u8>::with_capacity(cap);
+    let stream = Box::new(StreamContext { context, sink });
+    let ret = DiplomatWrite {
+        context: Box::into_raw(stream).cast(),
+        buf: vec.as_mut_ptr(),
+        len: 0,
+        grow_failed: false,
+        cap,
+        flush,
+        grow,
+    };
+
+    core::mem::forget(vec);
+    crate::leak_check::write_created();
+    Box::into_raw(Box::new(ret))
+}
+
+/// Destructor for writables created by [`diplomat_stream_write_create()`].
+///
+/// # Safety
+/// - `this` must be a pointer to a valid [`DiplomatWrite`] constructed by
+///   [`diplomat_stream_write_create()`].
+#[no_mangle]
+pub unsafe extern "C" fn diplomat_stream_write_destroy(this: *mut DiplomatWrite) {
+    let this = Box::from_raw(this);
+    drop(Box::from_raw(this.context as *mut StreamContext));
+    let vec = Vec::from_raw_parts(this.buf, 0, this.cap);
+    drop(vec);
+    drop(this);
+    crate::leak_check::write_destroyed();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::fmt::Write;
+
+    /// Collects the chunks passed to the sink into the `Vec<Vec<u8>>` at `context`, accepting
+    /// at most `LIMIT` of them.
+    extern "C" fn collect<const LIMIT: usize>(
+        context: *mut c_void,
+        chunk: *const u8,
+        len: usize,
+    ) -> bool {
+        let chunks = unsafe { &mut *(context as *mut Vec<Vec<u8>>) };
+        if chunks.len() == LIMIT {
+            return false;
+        }
+        chunks.push(unsafe { core::slice::from_raw_parts(chunk, len) }.to_vec());
+        true
+    }
+
+    #[test]
+    fn test_buffer_write() {
+        let write = diplomat_buffer_write_create(2);
+        let this = unsafe { &mut *write };
+        let name = "wörld";
+        write!(this, "hello {name}").unwrap();
+        this.flush();
+
+        let len = diplomat_buffer_write_len(this);
+        let bytes =
+            unsafe { core::slice::from_raw_parts(diplomat_buffer_write_get_bytes(this), len) };
+        assert_eq!(bytes, "hello wörld".as_bytes());
+        unsafe { diplomat_buffer_write_destroy(write) };
+    }
+
+    #[test]
+    fn test_simple_write() {
+        let mut buf = [0xff; 4];
+        let mut write = unsafe { diplomat_simple_write(buf.as_mut_ptr(), buf.len()) };
+        write.write_str("ab").unwrap();
+        // Doesn't fit next to the null terminator, and nothing is written after that
+        write.write_str("cd").unwrap();
+        write.write_str("e").unwrap();
+        write.flush();
+        assert!(write.grow_failed);
+        assert_eq!(buf, [b'a', b'b', 0, 0xff]);
+    }
+
+    #[test]
+    fn test_stream_write() {
+        let mut chunks = Vec::<Vec<u8>>::new();
+        let write = diplomat_stream_write_create(
+            &mut chunks as *mut Vec<Vec<u8>> as *mut c_void,
+            collect::<{ usize::MAX }>,
+            4,
+        );
+        let this = unsafe { &mut *write };
+        this.write_str("hello").unwrap();
+        // Written in full chunks, splitting the `ö`
+        this.write_str(" wörld").unwrap();
+        assert_eq!(chunks, [&b"hell"[..], b"o", " wö".as_bytes()]);
+        // The rest is only passed on when flushing
+        this.flush();
+        this.flush();
+        assert_eq!(chunks.concat(), "hello wörld".as_bytes());
+        assert_eq!(chunks.last().unwrap(), b"rld");
+        assert!(!this.grow_failed);
+        unsafe { diplomat_stream_write_destroy(write) };
+    }
+
+    #[test]
+    fn test_stream_write_stopped() {
+        let mut chunks = Vec::<Vec<u8>>::new();
+        let write = diplomat_stream_write_create(
+            &mut chunks as *mut Vec<Vec<u8>> as *mut c_void,
+            collect::<1>,
+            2,
+        );
+        let this = unsafe { &mut *write };
+        this.write_str("abcdef").unwrap();
+        this.write_str("gh").unwrap();
+        this.flush();
+        assert!(this.grow_failed);
+        assert_eq!(chunks, [b"ab"]);
+        unsafe { diplomat_stream_write_destroy(write) };
+    }
 }
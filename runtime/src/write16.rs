@@ -0,0 +1,207 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ffi::c_void;
+use core::{fmt, ptr};
+
+/// An object that one can write strings to as UTF-16
+///
+/// This works like [`DiplomatWrite`](crate::DiplomatWrite), except that `buf` holds UTF-16 code
+/// units and `len` and `cap` count code units. Rust transcodes what it writes, so that languages
+/// with UTF-16 strings (JS, Dart, Kotlin) can use the output without decoding UTF-8.
+///
+/// # Safety invariants:
+///  - `flush()` and `grow()` will be passed `self` including `context` and it should always be safe to do so.
+///    `context` may be  null, however `flush()` and `grow()` must then be ready to receive it as such.
+///  - `buf` must be `cap` code units long
+///  - `grow()` must either return false or update `buf` and `cap` for a valid buffer
+///    of at least the requested buffer size
+///  - `DiplomatWrite16::flush()` will be automatically called by Diplomat. `flush()` might also be called
+///    (erroneously) on the Rust side (it's a public method), so it must be idempotent.
+#[repr(C)]
+pub struct DiplomatWrite16 {
+    /// Context pointer for additional data needed by `grow()` and `flush()`. May be `null`.
+    context: *mut c_void,
+    /// The raw UTF-16 buffer, which will be mutated on the Rust side.
+    buf: *mut u16,
+    /// The current filled size of the buffer, in code units
+    len: usize,
+    /// The current capacity of the buffer, in code units
+    cap: usize,
+    /// Set to true if `grow` ever fails.
+    grow_failed: bool,
+    /// Called by Rust to indicate that there is no more data to write.
+    ///
+    /// May be called multiple times.
+    flush: extern "C" fn(*mut DiplomatWrite16),
+    /// Called by Rust to request more capacity in the buffer, in code units. The implementation
+    /// should allocate a new buffer and copy the contents of the old buffer into the new buffer,
+    /// updating `self.buf` and `self.cap`
+    ///
+    /// Returns: `true` if the allocation succeeded. Should not update any state if it failed.
+    grow: extern "C" fn(*mut DiplomatWrite16, usize) -> bool,
+}
+
+impl DiplomatWrite16 {
+    /// Call this function before releasing the buffer to C
+    pub fn flush(&mut self) {
+        (self.flush)(self);
+    }
+}
+
+impl fmt::Write for DiplomatWrite16 {
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        if self.grow_failed {
+            return Ok(());
+        }
+        let needed_len = self.len + s.encode_utf16().count();
+        if needed_len > self.cap {
+            let success = (self.grow)(self, needed_len);
+            if !success {
+                self.grow_failed = true;
+                return Ok(());
+            }
+        }
+        debug_assert!(needed_len <= self.cap);
+        for (i, unit) in s.encode_utf16().enumerate() {
+            unsafe {
+                ptr::write(self.buf.add(self.len + i), unit);
+            }
+        }
+        self.len = needed_len;
+        Ok(())
+    }
+}
+
+/// Create a [`DiplomatWrite16`] that can write to a dynamically allocated buffer managed by Rust.
+///
+/// Use [`diplomat_buffer_write16_destroy()`] to free the writable and its underlying buffer.
+#[no_mangle]
+pub extern "C" fn diplomat_buffer_write16_create(cap: usize) -> *mut DiplomatWrite16 {
+    extern "C" fn flush(_: *mut DiplomatWrite16) {}
+
+    let mut vec = Vec::<u16>::with_capacity(cap);
+    let ret = DiplomatWrite16 {
+        context: ptr::null_mut(),
+        buf: vec.as_mut_ptr(),
+        len: 0,
+        grow_failed: false,
+        cap,
+        flush,
+        grow: buffer_write16_grow,
+    };
+
+    core::mem::forget(vec);
+    crate::leak_check::write_created();
+    Box::into_raw(Box::new(ret))
+}
+
+extern "C" fn buffer_write16_grow(this: *mut DiplomatWrite16, new_cap: usize) -> bool {
+    unsafe {
+        let this = this.as_mut().unwrap();
+        let mut vec = Vec::from_raw_parts(this.buf, 0, this.cap);
+        vec.reserve(new_cap);
+        this.cap = vec.capacity();
+        this.buf = vec.as_mut_ptr();
+        core::mem::forget(vec);
+    }
+    true
+}
+
+/// Grabs a pointer to the underlying buffer of a writable.
+///
+/// Returns null if there was an allocation error during the write construction.
+///
+/// # Safety
+/// - The returned pointer is valid until the passed writable is destroyed.
+/// - `this` must be a pointer to a valid [`DiplomatWrite16`] constructed by
+///   [`diplomat_buffer_write16_create()`].
+#[no_mangle]
+pub extern "C" fn diplomat_buffer_write16_get_units(this: &DiplomatWrite16) -> *mut u16 {
+    if this.grow_failed {
+        core::ptr::null_mut()
+    } else {
+        this.buf
+    }
+}
+
+/// Gets the length in code units of the content written to the writable.
+///
+/// Returns 0 if there was an allocation error during the write construction.
+///
+/// # Safety
+/// - `this` must be a pointer to a valid [`DiplomatWrite16`] constructed by
+///   [`diplomat_buffer_write16_create()`].
+#[no_mangle]
+pub extern "C" fn diplomat_buffer_write16_len(this: &DiplomatWrite16) -> usize {
+    if this.grow_failed {
+        0
+    } else {
+        this.len
+    }
+}
+
+/// Destructor for Rust-memory backed writables.
+///
+/// # Safety
+/// - `this` must be a pointer to a valid [`DiplomatWrite16`] constructed by
+///   [`diplomat_buffer_write16_create()`].
+#[no_mangle]
+pub unsafe extern "C" fn diplomat_buffer_write16_destroy(this: *mut DiplomatWrite16) {
+    let this = Box::from_raw(this);
+    let vec = Vec::from_raw_parts(this.buf, 0, this.cap);
+    drop(vec);
+    drop(this);
+    crate::leak_check::write_destroyed();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::fmt::Write;
+
+    #[test]
+    fn test_buffer_write16() {
+        let write = diplomat_buffer_write16_create(1);
+        let this = unsafe { &mut *write };
+        // Astral characters take two code units, which have to fit after growing
+        let emoji = '😀';
+        write!(this, "a{emoji}é").unwrap();
+        this.write_str("z").unwrap();
+        this.flush();
+
+        let len = diplomat_buffer_write16_len(this);
+        let units =
+            unsafe { core::slice::from_raw_parts(diplomat_buffer_write16_get_units(this), len) };
+        assert_eq!(units, "a😀éz".encode_utf16().collect::<Vec<_>>());
+        assert_eq!(len, 5);
+        unsafe { diplomat_buffer_write16_destroy(write) };
+    }
+
+    #[test]
+    fn test_grow_failure() {
+        extern "C" fn flush(_: *mut DiplomatWrite16) {}
+        extern "C" fn grow(_: *mut DiplomatWrite16, _: usize) -> bool {
+            false
+        }
+
+        let mut buf = [0u16; 2];
+        let mut write = DiplomatWrite16 {
+            context: ptr::null_mut(),
+            buf: buf.as_mut_ptr(),
+            len: 0,
+            cap: buf.len(),
+            grow_failed: false,
+            flush,
+            grow,
+        };
+        write.write_str("ab").unwrap();
+        // Doesn't fit, and nothing is written after that
+        write.write_str("😀").unwrap();
+        write.write_str("c").unwrap();
+        assert!(write.grow_failed);
+        assert_eq!(write.len, 2);
+        assert_eq!(buf, [u16::from(b'a'), u16::from(b'b')]);
+        assert!(diplomat_buffer_write16_get_units(&write).is_null());
+        assert_eq!(diplomat_buffer_write16_len(&write), 0);
+    }
+}
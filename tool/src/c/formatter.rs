@@ -1,7 +1,7 @@
 //! This module contains functions for formatting types
 
 use diplomat_core::hir::{
-    self, StringEncoding, SymbolId, TraitId, TyPosition, TypeContext, TypeId,
+    self, StringEncoding, SymbolId, TraitId, TyPosition, TypeContext, TypeId, WriteEncoding,
 };
 use std::borrow::Cow;
 
@@ -41,7 +41,7 @@ impl<'tcx> CFormatter<'tcx> {
         if self.is_for_cpp {
             attrs.rename.apply(name)
         } else {
-            name
+            self.c_namespace_prefix(attrs, name)
         }
     }
 
@@ -56,7 +56,7 @@ impl<'tcx> CFormatter<'tcx> {
         if self.is_for_cpp {
             attrs.rename.apply(name)
         } else {
-            name
+            self.c_namespace_prefix(attrs, name)
         }
     }
 
@@ -99,7 +99,7 @@ impl<'tcx> CFormatter<'tcx> {
         let name = if self.is_for_cpp {
             attrs.rename.apply(name)
         } else {
-            name
+            self.c_namespace_prefix(attrs, name)
         };
         if self.is_for_cpp {
             if let Some(ref ns) = attrs.namespace {
@@ -144,9 +144,15 @@ impl<'tcx> CFormatter<'tcx> {
         format!("{type_name}_{variant_name}").into()
     }
     /// Format a field name or parameter name
+    ///
+    /// When generating headers for C++, names that are C++ keywords get a trailing underscore.
     // might need splitting in the future if we decide to support renames here
     pub fn fmt_param_name<'a>(&self, ident: &'a str) -> Cow<'a, str> {
-        ident.into()
+        if self.is_for_cpp && CPP_RESERVED.contains(&ident) {
+            format!("{ident}_").into()
+        } else {
+            ident.into()
+        }
     }
 
     pub fn fmt_ptr<'a>(&self, ident: &'a str, mutability: hir::Mutability) -> Cow<'a, str> {
@@ -173,7 +179,7 @@ impl<'tcx> CFormatter<'tcx> {
             PrimitiveType::Int(IntType::U32) => "uint32_t",
             PrimitiveType::Int(IntType::I64) => "int64_t",
             PrimitiveType::Int(IntType::U64) => "uint64_t",
-            PrimitiveType::Int128(_) => panic!("i128 not supported in C"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
             PrimitiveType::IntSize(IntSizeType::Isize) => "intptr_t",
             PrimitiveType::IntSize(IntSizeType::Usize) => "size_t",
             PrimitiveType::Float(FloatType::F32) => "float",
@@ -196,7 +202,7 @@ impl<'tcx> CFormatter<'tcx> {
             PrimitiveType::Int(IntType::U32) => "U32",
             PrimitiveType::Int(IntType::I64) => "I64",
             PrimitiveType::Int(IntType::U64) => "U64",
-            PrimitiveType::Int128(_) => panic!("i128 not supported in C"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
             PrimitiveType::IntSize(IntSizeType::Isize) => "Isize",
             PrimitiveType::IntSize(IntSizeType::Usize) => "Usize",
             PrimitiveType::Float(FloatType::F32) => "F32",
@@ -217,8 +223,15 @@ impl<'tcx> CFormatter<'tcx> {
         self.diplomat_namespace(format!("Diplomat{prim}View{mtb}").into())
     }
 
-    pub(crate) fn fmt_write_name(&self) -> Cow<'tcx, str> {
-        self.diplomat_namespace("DiplomatWrite".into())
+    pub(crate) fn fmt_write_name(&self, encoding: WriteEncoding) -> Cow<'tcx, str> {
+        match encoding {
+            WriteEncoding::Utf16 => self.diplomat_namespace("DiplomatWrite16".into()),
+            _ => self.diplomat_namespace("DiplomatWrite".into()),
+        }
+    }
+
+    pub(crate) fn fmt_read_name(&self) -> Cow<'tcx, str> {
+        self.diplomat_namespace("DiplomatRead".into())
     }
 
     pub(crate) fn fmt_str_view_name(&self, encoding: StringEncoding) -> Cow<'tcx, str> {
@@ -241,6 +254,15 @@ impl<'tcx> CFormatter<'tcx> {
         )
     }
 
+    /// C has no namespaces, so in pure C mode the names of types in a namespace `a::b`
+    /// are prefixed with `a_b_` instead.
+    fn c_namespace_prefix(&self, attrs: &hir::Attrs, name: Cow<'tcx, str>) -> Cow<'tcx, str> {
+        match attrs.namespace {
+            Some(ref ns) => format!("{}_{name}", ns.replace("::", "_")).into(),
+            None => name,
+        }
+    }
+
     fn diplomat_namespace(&self, ty: Cow<'tcx, str>) -> Cow<'tcx, str> {
         if self.is_for_cpp {
             format!("diplomat::{CAPI_NAMESPACE}::{ty}").into()
@@ -249,3 +271,59 @@ impl<'tcx> CFormatter<'tcx> {
         }
     }
 }
+
+/// Keywords of C++ that cannot be used as field or parameter names.
+const CPP_RESERVED: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "asm",
+    "auto",
+    "bool",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "constexpr",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "explicit",
+    "export",
+    "extern",
+    "float",
+    "friend",
+    "goto",
+    "inline",
+    "int",
+    "long",
+    "namespace",
+    "new",
+    "not",
+    "operator",
+    "or",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "switch",
+    "template",
+    "this",
+    "throw",
+    "try",
+    "typedef",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "xor",
+];
@@ -14,13 +14,18 @@ use diplomat_core::hir::BackendAttrSupport;
 pub(crate) fn attr_support() -> BackendAttrSupport {
     let mut a = BackendAttrSupport::default();
 
-    a.namespacing = false;
+    a.namespacing = true;
     a.memory_sharing = true;
     a.non_exhaustive_structs = false;
     a.method_overloading = false;
     a.utf8_strings = true;
     a.utf16_strings = true;
     a.static_slices = true;
+    a.static_borrows = true;
+    a.int128 = false;
+    a.int128_slices = false;
+    a.write16 = true;
+    a.reads = true;
 
     a.constructors = false;
     a.named_constructors = false;
@@ -31,6 +36,7 @@ pub(crate) fn attr_support() -> BackendAttrSupport {
     a.iterators = false;
     a.iterables = false;
     a.indexing = false;
+    a.streaming_writes = false;
     a.option = true;
     a.callbacks = true;
     a.traits = true;
@@ -55,6 +61,11 @@ pub(crate) fn run(tcx: &hir::TypeContext) -> (FileMap, ErrorStore<String>) {
             continue;
         }
 
+        if tcx.external_crate(id).is_some() {
+            // Included from the other crate's bindings
+            continue;
+        }
+
         let decl_header_path = formatter.fmt_decl_header_path(id.into());
         let impl_header_path = formatter.fmt_impl_header_path(id.into());
 
@@ -84,8 +95,8 @@ pub(crate) fn run(tcx: &hir::TypeContext) -> (FileMap, ErrorStore<String>) {
     }
 
     for (id, trt) in tcx.all_traits() {
-        if trt.attrs.disable {
-            // Skip type if disabled
+        if trt.attrs.disable || tcx.external_crate(id).is_some() {
+            // Skip type if disabled or from another crate
             continue;
         }
 
@@ -24,6 +24,7 @@ struct EnumTemplate<'a> {
 struct StructTemplate<'a> {
     ty_name: Cow<'a, str>,
     fields: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    layout_asserts: Vec<String>,
     is_for_cpp: bool,
 }
 
@@ -122,9 +123,16 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
             ));
         }
 
+        let layout_asserts = if fields.is_empty() {
+            vec![]
+        } else {
+            self.gen_layout_asserts(&ty_name, def, &fields)
+        };
+
         StructTemplate {
             ty_name,
             fields,
+            layout_asserts,
             is_for_cpp: self.is_for_cpp,
         }
         .render_into(&mut decl_header)
@@ -133,6 +141,34 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
         decl_header
     }
 
+    /// Generates assertions that the C compiler lays the struct out like the Rust struct, which
+    /// `#[diplomat::bridge]` checks against the same rules, for both 32- and 64-bit pointers.
+    fn gen_layout_asserts<P: TyPosition>(
+        &self,
+        ty_name: &str,
+        def: &'tcx hir::StructDef<P>,
+        fields: &[(Cow<'_, str>, Cow<'_, str>)],
+    ) -> Vec<String> {
+        let [info32, info64] = crate::js::layout::struct_field_info_by_width(def, self.tcx);
+
+        let mut asserts = vec![format!(
+            "DIPLOMAT_ASSERT_LAYOUT({ty_name}, {}, {}, {}, {});",
+            info32.struct_layout.size(),
+            info32.struct_layout.align(),
+            info64.struct_layout.size(),
+            info64.struct_layout.align(),
+        )];
+        for (((_, field_name), field32), field64) in
+            fields.iter().zip(&info32.fields).zip(&info64.fields)
+        {
+            asserts.push(format!(
+                "DIPLOMAT_ASSERT_OFFSET({ty_name}, {field_name}, {}, {});",
+                field32.offset, field64.offset
+            ));
+        }
+        asserts
+    }
+
     pub fn gen_trait_def(&self, def: &'tcx hir::TraitDef) -> Header {
         let mut decl_header = Header::new(self.decl_header_path.clone(), self.is_for_cpp);
         let trt_name = self.formatter.fmt_trait_name(self.id.try_into().unwrap());
@@ -252,7 +288,7 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
             ReturnType::Infallible(SuccessType::Unit) => "void".into(),
             ReturnType::Infallible(SuccessType::Write) => {
                 param_decls.push((
-                    format!("{}*", self.formatter.fmt_write_name()).into(),
+                    format!("{}*", self.formatter.fmt_write_name(method.write_encoding)).into(),
                     "write".into(),
                 ));
                 "void".into()
@@ -268,7 +304,8 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
                 let ok_ty = match ok {
                     SuccessType::Write => {
                         param_decls.push((
-                            format!("{}*", self.formatter.fmt_write_name()).into(),
+                            format!("{}*", self.formatter.fmt_write_name(method.write_encoding))
+                                .into(),
                             "write".into(),
                         ));
                         None
@@ -491,6 +528,7 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
                 let inner = self.gen_ty_name(s, header);
                 self.formatter.fmt_optional_type_name(s, &inner).into()
             }
+            Type::Read => format!("{}*", self.formatter.fmt_read_name()).into(),
             Type::ImplTrait(ref t) => {
                 let t_id = t.id();
                 let trt_name = self.formatter.fmt_type_name_maybe_namespaced(t_id.into());
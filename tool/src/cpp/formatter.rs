@@ -93,7 +93,7 @@ impl<'tcx> Cpp2Formatter<'tcx> {
     /// Format a field name or parameter name
     // might need splitting in the future if we decide to support renames here
     pub fn fmt_param_name<'a>(&self, ident: &'a str) -> Cow<'a, str> {
-        ident.into()
+        self.c.fmt_param_name(ident)
     }
 
     pub fn fmt_c_type_name(&self, id: TypeId) -> Cow<'tcx, str> {
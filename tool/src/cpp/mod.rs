@@ -2,6 +2,7 @@ mod formatter;
 mod header;
 mod ty;
 
+use crate::templates::TemplateOverrides;
 use crate::{ErrorStore, FileMap};
 use diplomat_core::hir::{self, BackendAttrSupport};
 use formatter::Cpp2Formatter;
@@ -17,16 +18,22 @@ pub(crate) fn attr_support() -> BackendAttrSupport {
     a.utf8_strings = true;
     a.utf16_strings = true;
     a.static_slices = true;
+    a.static_borrows = true;
+    a.int128 = false;
+    a.int128_slices = false;
+    a.write16 = false;
+    a.reads = true;
 
-    a.constructors = false; // TODO
-    a.named_constructors = false;
-    a.fallible_constructors = false;
+    a.constructors = true;
+    a.named_constructors = true;
+    a.fallible_constructors = true;
     a.accessors = false;
-    a.comparators = false; // TODO
-    a.stringifiers = false; // TODO
-    a.iterators = false; // TODO
-    a.iterables = false; // TODO
-    a.indexing = false; // TODO
+    a.comparators = true;
+    a.stringifiers = true;
+    a.iterators = true;
+    a.iterables = true;
+    a.indexing = true;
+    a.streaming_writes = true;
     a.option = true;
     a.callbacks = false;
     a.traits = false;
@@ -34,7 +41,10 @@ pub(crate) fn attr_support() -> BackendAttrSupport {
     a
 }
 
-pub(crate) fn run(tcx: &hir::TypeContext) -> (FileMap, ErrorStore<String>) {
+pub(crate) fn run<'tcx>(
+    tcx: &'tcx hir::TypeContext,
+    templates: &TemplateOverrides,
+) -> (FileMap, ErrorStore<'tcx, String>) {
     let files = FileMap::default();
     let formatter = Cpp2Formatter::new(tcx);
     let errors = ErrorStore::default();
@@ -50,6 +60,10 @@ pub(crate) fn run(tcx: &hir::TypeContext) -> (FileMap, ErrorStore<String>) {
             // Skip type if disabled
             continue;
         }
+        if tcx.external_crate(id).is_some() {
+            // Included from the other crate's bindings
+            continue;
+        }
         let type_name_unnamespaced = formatter.fmt_type_name(id);
         let decl_header_path = formatter.fmt_decl_header_path(id);
         let mut decl_header = header::Header::new(decl_header_path.clone());
@@ -59,6 +73,7 @@ pub(crate) fn run(tcx: &hir::TypeContext) -> (FileMap, ErrorStore<String>) {
         let mut context = TyGenContext {
             formatter: &formatter,
             errors: &errors,
+            templates,
             c: crate::c::TyGenContext {
                 tcx,
                 formatter: &formatter.c,
@@ -11,6 +11,7 @@ use diplomat_core::hir::{
 use std::borrow::Cow;
 
 use crate::c::CAPI_NAMESPACE;
+use crate::templates::{ClassHookVars, MethodHookVars, MethodHooks, TemplateOverrides};
 
 /// An expression with a corresponding variable name, such as a struct field or a function parameter.
 struct NamedExpression<'a> {
@@ -48,12 +49,69 @@ struct MethodInfo<'a> {
     /// the C function return value is saved to a variable named `result` or that the
     /// DiplomatWrite, if present, is saved to a variable named `output`.
     c_to_cpp_return_expression: Option<Cow<'a, str>>,
+    /// The user's method hooks, rendered for this method
+    hooks: MethodHooks,
+    /// Whether this is the `std::ostream&` overload of a method marked `stream`
+    stream: bool,
+}
+
+/// C++ operators and other special members generated from methods marked as special methods.
+///
+/// Each of these forwards to the regular method it was generated from.
+#[derive(Default)]
+struct SpecialMethods<'a> {
+    /// Constructors, only generated for structs and enums. Constructors of opaques are static
+    /// methods returning a `std::unique_ptr`, as are fallible constructors, which return a `diplomat::result`.
+    constructors: Vec<ConstructorInfo<'a>>,
+    /// The method that `operator<<` and `to_string` forward to
+    stringifier: Option<SpecialMethodInfo<'a>>,
+    /// The method that comparison operators forward to
+    comparator: Option<SpecialMethodInfo<'a>>,
+    /// The method that `operator[]` forwards to
+    indexer: Option<SpecialMethodInfo<'a>>,
+    /// The method that `begin()` gets the iterator from
+    iterable: Option<SpecialMethodInfo<'a>>,
+}
+
+impl SpecialMethods<'_> {
+    /// Whether one of the constructors takes no parameters, replacing the implicit default constructor.
+    fn has_default_constructor(&self) -> bool {
+        self.constructors.iter().any(|c| c.param_decls.is_empty())
+    }
+}
+
+/// A C++ constructor delegating to a static method returning `Self`.
+struct ConstructorInfo<'a> {
+    /// The static method this constructor delegates to
+    method_name: Cow<'a, str>,
+    param_decls: Vec<NamedType<'a>>,
+}
+
+/// Everything needed to render an operator or other special member forwarding to a method.
+struct SpecialMethodInfo<'a> {
+    method_name: Cow<'a, str>,
+    return_ty: Cow<'a, str>,
+    param_decls: Vec<NamedType<'a>>,
+    post_qualifiers: Vec<Cow<'a, str>>,
+    /// For iterables, the type of the iterator
+    iterator_ty: Option<Cow<'a, str>>,
+}
+
+fn clone_named_types<'a>(types: &[NamedType<'a>]) -> Vec<NamedType<'a>> {
+    types
+        .iter()
+        .map(|t| NamedType {
+            var_name: t.var_name.clone(),
+            type_name: t.type_name.clone(),
+        })
+        .collect()
 }
 
 /// Context for generating a particular type's header
 pub(super) struct TyGenContext<'ccx, 'tcx, 'header> {
     pub formatter: &'ccx Cpp2Formatter<'tcx>,
     pub errors: &'ccx ErrorStore<'tcx, String>,
+    pub templates: &'ccx TemplateOverrides,
     pub c: C2TyGenContext<'ccx, 'tcx>,
     pub impl_header: &'header mut Header,
     pub decl_header: &'header mut Header,
@@ -78,9 +136,23 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
         let methods = ty
             .methods
             .iter()
-            .flat_map(|method| self.gen_method_info(id, method))
+            .flat_map(|method| self.gen_method_infos(id, method))
             .collect::<Vec<_>>();
 
+        let mut special = self.gen_special_methods(&methods, &ty.special_method_presence, true);
+        // Enums implicitly convert to and from `Value`, so comparison operators taking the
+        // enum would make comparisons like `e == Enum::A` ambiguous. They compare by value already.
+        special.comparator = None;
+
+        let class_prologue = self.templates.class_prologue(
+            &ClassHookVars {
+                type_name: &type_name,
+                namespace: ty.attrs.namespace.as_deref(),
+                kind: "enum",
+            },
+            self.errors,
+        );
+
         #[derive(Template)]
         #[template(path = "cpp/enum_decl.h.jinja", escape = "none")]
         struct DeclTemplate<'a> {
@@ -89,9 +161,11 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             type_name: &'a str,
             ctype: &'a str,
             methods: &'a [MethodInfo<'a>],
+            special: &'a SpecialMethods<'a>,
             namespace: Option<&'a str>,
             type_name_unnamespaced: &'a str,
             c_header: C2Header,
+            class_prologue: Option<String>,
         }
 
         DeclTemplate {
@@ -100,9 +174,11 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             type_name: &type_name,
             ctype: &ctype,
             methods: methods.as_slice(),
+            special: &special,
             namespace: ty.attrs.namespace.as_deref(),
             type_name_unnamespaced: &type_name_unnamespaced,
             c_header,
+            class_prologue,
         }
         .render_into(self.decl_header)
         .unwrap();
@@ -113,8 +189,10 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             ty: &'a hir::EnumDef,
             fmt: &'a Cpp2Formatter<'a>,
             type_name: &'a str,
+            type_name_unnamespaced: &'a str,
             ctype: &'a str,
             methods: &'a [MethodInfo<'a>],
+            special: &'a SpecialMethods<'a>,
             namespace: Option<&'a str>,
             c_header: C2Header,
         }
@@ -123,8 +201,10 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             ty,
             fmt: self.formatter,
             type_name: &type_name,
+            type_name_unnamespaced: &type_name_unnamespaced,
             ctype: &ctype,
             methods: methods.as_slice(),
+            special: &special,
             namespace: ty.attrs.namespace.as_deref(),
             c_header: c_impl_header,
         }
@@ -146,9 +226,20 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
         let methods = ty
             .methods
             .iter()
-            .flat_map(|method| self.gen_method_info(id, method))
+            .flat_map(|method| self.gen_method_infos(id, method))
             .collect::<Vec<_>>();
 
+        let special = self.gen_special_methods(&methods, &ty.special_method_presence, false);
+
+        let class_prologue = self.templates.class_prologue(
+            &ClassHookVars {
+                type_name: &type_name,
+                namespace: ty.attrs.namespace.as_deref(),
+                kind: "opaque",
+            },
+            self.errors,
+        );
+
         #[derive(Template)]
         #[template(path = "cpp/opaque_decl.h.jinja", escape = "none")]
         struct DeclTemplate<'a> {
@@ -157,9 +248,12 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             type_name: &'a str,
             ctype: &'a str,
             methods: &'a [MethodInfo<'a>],
+            special: &'a SpecialMethods<'a>,
             namespace: Option<&'a str>,
             type_name_unnamespaced: &'a str,
             c_header: C2Header,
+            class_prologue: Option<String>,
+            thread_safety: hir::ThreadSafety,
         }
 
         DeclTemplate {
@@ -168,9 +262,12 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             type_name: &type_name,
             ctype: &ctype,
             methods: methods.as_slice(),
+            special: &special,
             namespace: ty.attrs.namespace.as_deref(),
             type_name_unnamespaced: &type_name_unnamespaced,
             c_header,
+            class_prologue,
+            thread_safety: ty.attrs.thread_safety,
         }
         .render_into(self.decl_header)
         .unwrap();
@@ -181,9 +278,11 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             // ty: &'a hir::OpaqueDef,
             fmt: &'a Cpp2Formatter<'a>,
             type_name: &'a str,
+            type_name_unnamespaced: &'a str,
             ctype: &'a str,
             dtor_name: String,
             methods: &'a [MethodInfo<'a>],
+            special: &'a SpecialMethods<'a>,
             namespace: Option<&'a str>,
             c_header: C2Header,
         }
@@ -192,9 +291,11 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             // ty,
             fmt: self.formatter,
             type_name: &type_name,
+            type_name_unnamespaced: &type_name_unnamespaced,
             ctype: &ctype,
             dtor_name,
             methods: methods.as_slice(),
+            special: &special,
             namespace: ty.attrs.namespace.as_deref(),
             c_header: c_impl_header,
         }
@@ -233,9 +334,31 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
         let methods = def
             .methods
             .iter()
-            .flat_map(|method| self.gen_method_info(id, method))
+            .flat_map(|method| self.gen_method_infos(id, method))
             .collect::<Vec<_>>();
 
+        let special = self.gen_special_methods(&methods, &def.special_method_presence, true);
+        // Constructors make the struct a non-aggregate, so we generate a field-wise constructor
+        // to keep brace initialization working, unless a constructor already has that signature.
+        let field_constructor = !field_decls.is_empty()
+            && !special.constructors.iter().any(|ctor| {
+                ctor.param_decls.len() == field_decls.len()
+                    && ctor
+                        .param_decls
+                        .iter()
+                        .zip(&field_decls)
+                        .all(|(param, field)| param.type_name == field.type_name)
+            });
+
+        let class_prologue = self.templates.class_prologue(
+            &ClassHookVars {
+                type_name: &type_name,
+                namespace: def.attrs.namespace.as_deref(),
+                kind: "struct",
+            },
+            self.errors,
+        );
+
         #[derive(Template)]
         #[template(path = "cpp/struct_decl.h.jinja", escape = "none")]
         struct DeclTemplate<'a> {
@@ -245,9 +368,12 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             ctype: &'a str,
             fields: &'a [NamedType<'a>],
             methods: &'a [MethodInfo<'a>],
+            special: &'a SpecialMethods<'a>,
+            field_constructor: bool,
             namespace: Option<&'a str>,
             type_name_unnamespaced: &'a str,
             c_header: C2Header,
+            class_prologue: Option<String>,
         }
 
         DeclTemplate {
@@ -257,9 +383,12 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             ctype: &ctype,
             fields: field_decls.as_slice(),
             methods: methods.as_slice(),
+            special: &special,
+            field_constructor,
             namespace: def.attrs.namespace.as_deref(),
             type_name_unnamespaced: &type_name_unnamespaced,
             c_header,
+            class_prologue,
         }
         .render_into(self.decl_header)
         .unwrap();
@@ -270,10 +399,12 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             // ty: &'a hir::OpaqueDef,
             // fmt: &'a Cpp2Formatter<'a>,
             type_name: &'a str,
+            type_name_unnamespaced: &'a str,
             ctype: &'a str,
             cpp_to_c_fields: &'a [NamedExpression<'a>],
             c_to_cpp_fields: &'a [NamedExpression<'a>],
             methods: &'a [MethodInfo<'a>],
+            special: &'a SpecialMethods<'a>,
             namespace: Option<&'a str>,
             c_header: C2Header,
         }
@@ -282,10 +413,12 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             // ty,
             // fmt: &self.formatter,
             type_name: &type_name,
+            type_name_unnamespaced: &type_name_unnamespaced,
             ctype: &ctype,
             cpp_to_c_fields: cpp_to_c_fields.as_slice(),
             c_to_cpp_fields: c_to_cpp_fields.as_slice(),
             methods: methods.as_slice(),
+            special: &special,
             namespace: def.attrs.namespace.as_deref(),
             c_header: c_impl_header,
         }
@@ -321,6 +454,8 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
 
         for param in method.params.iter() {
             let decls = self.gen_ty_decl(&param.ty, param.name.as_str());
+            // The C++ name may differ from the Rust one, if it had to be escaped
+            let var_name = decls.var_name.clone();
             param_decls.push(decls);
             if matches!(
                 param.ty,
@@ -328,11 +463,11 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             ) {
                 param_validations.push(format!(
                     "if (!diplomat::capi::diplomat_is_str({param}.data(), {param}.size())) {{\n  return diplomat::Err<diplomat::Utf8Error>(diplomat::Utf8Error());\n}}",
-                    param = param.name.as_str(),
+                    param = var_name,
                 ));
                 returns_utf8_err = true;
             }
-            let conversion = self.gen_cpp_to_c_for_type(&param.ty, param.name.as_str().into());
+            let conversion = self.gen_cpp_to_c_for_type(&param.ty, var_name);
             cpp_to_c_params.push(conversion);
         }
 
@@ -366,6 +501,15 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             }
         });
 
+        let hooks = self.templates.method_hooks(
+            &MethodHookVars {
+                type_name: &self.formatter.fmt_type_name(id),
+                method_name: &method_name,
+                abi_name: &abi_name,
+            },
+            self.errors,
+        );
+
         let pre_qualifiers = if method.param_self.is_none() {
             vec!["static".into()]
         } else {
@@ -389,9 +533,126 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             param_validations,
             cpp_to_c_params,
             c_to_cpp_return_expression,
+            hooks,
+            stream: false,
         })
     }
 
+    /// Generates the [`MethodInfo`] of a method, followed by that of its `std::ostream&` overload
+    /// if it is marked `stream`.
+    fn gen_method_infos(&mut self, id: TypeId, method: &'tcx hir::Method) -> Vec<MethodInfo<'ccx>> {
+        let Some(info) = self.gen_method_info(id, method) else {
+            return Vec::new();
+        };
+        if !method.attrs.stream {
+            return vec![info];
+        }
+
+        // Takes the stream as an additional parameter, and writes to it instead of a `std::string`
+        let mut param_decls = clone_named_types(&info.param_decls);
+        param_decls.push(NamedType {
+            var_name: "out".into(),
+            type_name: "std::ostream&".into(),
+        });
+        let mut cpp_to_c_params = info.cpp_to_c_params.clone();
+        *cpp_to_c_params.last_mut().unwrap() = "write.AsFFI()".into();
+        let (return_ty, c_to_cpp_return_expression) = if info.param_validations.is_empty() {
+            ("void".into(), None)
+        } else {
+            (
+                "diplomat::result<std::monostate, diplomat::Utf8Error>".into(),
+                Some("diplomat::Ok<std::monostate>(std::monostate)".into()),
+            )
+        };
+        let stream = MethodInfo {
+            method,
+            return_ty,
+            method_name: info.method_name.clone(),
+            abi_name: info.abi_name.clone(),
+            pre_qualifiers: info.pre_qualifiers.clone(),
+            post_qualifiers: info.post_qualifiers.clone(),
+            param_decls,
+            param_validations: info.param_validations.clone(),
+            cpp_to_c_params,
+            c_to_cpp_return_expression,
+            hooks: info.hooks.clone(),
+            stream: true,
+        };
+        vec![info, stream]
+    }
+
+    /// Generates the operators and other special members for the methods of a type marked as special methods.
+    ///
+    /// `allow_constructors` should be false for opaques, which can only be constructed behind a `std::unique_ptr`.
+    fn gen_special_methods(
+        &self,
+        methods: &[MethodInfo<'ccx>],
+        presence: &hir::SpecialMethodPresence,
+        allow_constructors: bool,
+    ) -> SpecialMethods<'ccx> {
+        let mut special = SpecialMethods::default();
+        for m in methods.iter().filter(|m| !m.stream) {
+            let Some(ref special_method) = m.method.attrs.special_method else {
+                continue;
+            };
+            let _guard = self.errors.set_context_method(
+                self.c.tcx.fmt_symbol_name_diagnostics(self.c.id),
+                m.method.name.to_string().into(),
+            );
+            let info = || SpecialMethodInfo {
+                method_name: m.method_name.clone(),
+                return_ty: m.return_ty.clone(),
+                param_decls: clone_named_types(&m.param_decls),
+                post_qualifiers: m.post_qualifiers.clone(),
+                iterator_ty: None,
+            };
+            match special_method {
+                hir::SpecialMethod::Constructor
+                    if allow_constructors
+                        && matches!(m.method.output, ReturnType::Infallible(_))
+                        && m.param_validations.is_empty() =>
+                {
+                    special.constructors.push(ConstructorInfo {
+                        method_name: m.method_name.clone(),
+                        param_decls: clone_named_types(&m.param_decls),
+                    });
+                }
+                hir::SpecialMethod::Stringifier => {
+                    // `operator<<` and `to_string` have nowhere to put an error or a missing value
+                    if matches!(m.method.output, ReturnType::Infallible(_)) {
+                        special.stringifier = Some(info());
+                    } else {
+                        self.errors.push_error(
+                            "Stringifiers must be infallible in C++, use a regular method instead"
+                                .into(),
+                        );
+                    }
+                }
+                hir::SpecialMethod::Comparison => special.comparator = Some(info()),
+                hir::SpecialMethod::Indexer => {
+                    if m.method.param_self.is_none() {
+                        self.errors
+                            .push_error("Indexers must take `self` in C++".into());
+                    } else {
+                        special.indexer = Some(info());
+                    }
+                }
+                hir::SpecialMethod::Iterable => {
+                    if let Some(iterator) = presence.iterable {
+                        special.iterable = Some(SpecialMethodInfo {
+                            iterator_ty: Some(self.formatter.fmt_type_name(iterator.into())),
+                            ..info()
+                        });
+                    }
+                }
+                // Other constructors are regular static methods, and
+                // iterators only need their `next()` method.
+                _ => {}
+            }
+        }
+        special
+    }
+
     /// Generates C++ code for referencing a particular type with a given name.
     fn gen_ty_decl<'a, P: TyPosition>(&mut self, ty: &Type<P>, var_name: &'a str) -> NamedType<'a>
     where
@@ -501,6 +762,7 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
             Type::DiplomatOption(ref inner) => {
                 format!("std::optional<{}>", self.gen_type_name(inner)).into()
             }
+            Type::Read => "std::istream&".into(),
             _ => unreachable!("unknown AST/HIR variant"),
         }
     }
@@ -559,6 +821,7 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
                 let copt = self.c.gen_ty_name(ty, &mut Default::default());
                 format!("{cpp_name}.has_value() ? ({copt}{{ {{ {conversion} }}, true }}) : ({copt}{{ {{}}, false }})").into()
             }
+            Type::Read => format!("diplomat::IstreamRead({cpp_name}).AsFFI()").into(),
             _ => unreachable!("unknown AST/HIR variant"),
         }
     }
@@ -633,8 +896,7 @@ impl<'ccx, 'tcx: 'ccx, 'header> TyGenContext<'ccx, 'tcx, 'header> {
                 let id = op.tcx_id.into();
                 let type_name = self.formatter.fmt_type_name(id);
                 // Note: The impl file is imported in gen_type_name().
-                format!("{var_name} ? {{ *{type_name}::FromFFI({var_name}) }} : std::nullopt")
-                    .into()
+                format!("{type_name}::FromFFI({var_name})").into()
             }
             Type::Opaque(ref op) => {
                 let id = op.tcx_id.into();
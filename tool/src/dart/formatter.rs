@@ -1,8 +1,9 @@
 //! This module contains functions for formatting types
 
 use diplomat_core::hir::{self, DocsUrlGenerator, TypeContext, TypeId};
-use heck::ToLowerCamelCase;
+use heck::{ToLowerCamelCase, ToUpperCamelCase};
 use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// This type mediates all formatting
 ///
@@ -16,6 +17,8 @@ use std::borrow::Cow;
 pub(super) struct DartFormatter<'tcx> {
     tcx: &'tcx TypeContext,
     docs_url_gen: &'tcx DocsUrlGenerator,
+    /// Names of types that are declared in more than one namespace
+    clashing_names: BTreeSet<Cow<'tcx, str>>,
 }
 
 const INVALID_METHOD_NAMES: &[&str] = &["new", "static", "default"];
@@ -24,7 +27,25 @@ const DISALLOWED_CORE_TYPES: &[&str] = &["Object", "String"];
 
 impl<'tcx> DartFormatter<'tcx> {
     pub fn new(tcx: &'tcx TypeContext, docs_url_gen: &'tcx DocsUrlGenerator) -> Self {
-        Self { tcx, docs_url_gen }
+        let mut namespaces = BTreeMap::<_, BTreeSet<_>>::new();
+        for (id, ty) in tcx.all_types() {
+            if !ty.attrs().disable {
+                namespaces
+                    .entry(Self::fmt_unqualified_type_name(tcx, id))
+                    .or_default()
+                    .insert(ty.attrs().namespace.as_deref());
+            }
+        }
+        let clashing_names = namespaces
+            .into_iter()
+            .filter(|(_, namespaces)| namespaces.len() > 1)
+            .map(|(name, _)| name)
+            .collect();
+        Self {
+            tcx,
+            docs_url_gen,
+            clashing_names,
+        }
     }
 
     pub fn fmt_lifetime_edge_array(
@@ -59,8 +80,45 @@ impl<'tcx> DartFormatter<'tcx> {
         .into()
     }
 
-    pub fn fmt_part_of_lib(&self) -> Cow<'static, str> {
-        format!("part of '{}';", self.fmt_file_name("lib")).into()
+    /// Format a namespace (`a::b`) as a directory relative to the library root (`a/b`)
+    pub fn fmt_namespace_dir(&self, namespace: &str) -> String {
+        namespace.replace("::", "/")
+    }
+
+    /// The path to the library root from the directory containing `file_name`
+    fn fmt_path_to_root(&self, file_name: &str) -> String {
+        "../".repeat(file_name.matches('/').count())
+    }
+
+    pub fn fmt_part_of_lib(&self, part: &str) -> Cow<'static, str> {
+        format!(
+            "part of '{}{}';",
+            self.fmt_path_to_root(part),
+            self.fmt_file_name("lib")
+        )
+        .into()
+    }
+
+    /// Re-export the given types of the library from the barrel file `barrel`
+    pub fn fmt_export_from_lib(&self, barrel: &str, types: &[Cow<str>]) -> Cow<'static, str> {
+        format!(
+            "export '{}{}' show {};",
+            self.fmt_path_to_root(barrel),
+            self.fmt_file_name("lib"),
+            types.join(", ")
+        )
+        .into()
+    }
+
+    /// Import the given types of the library into the barrel file `barrel`
+    pub fn fmt_import_from_lib(&self, barrel: &str, types: &[Cow<str>]) -> Cow<'static, str> {
+        format!(
+            "import '{}{}' show {};",
+            self.fmt_path_to_root(barrel),
+            self.fmt_file_name("lib"),
+            types.join(", ")
+        )
+        .into()
     }
 
     pub fn fmt_part(&self, part: &str) -> Cow<'static, str> {
@@ -75,8 +133,29 @@ impl<'tcx> DartFormatter<'tcx> {
     }
 
     /// Resolve and format a named type for use in code
+    ///
+    /// All types are part of the same library, so a type whose name is also used in another
+    /// namespace is prefixed with its namespace (`a::b::Foo` becomes `ABFoo`). The barrel file
+    /// of the namespace declares the unprefixed name as an alias, see [`Self::fmt_type_alias`].
     pub fn fmt_type_name(&self, id: TypeId) -> Cow<'tcx, str> {
-        let resolved = self.tcx.resolve_type(id);
+        let name = Self::fmt_unqualified_type_name(self.tcx, id);
+        match self.tcx.resolve_type(id).attrs().namespace {
+            Some(ref ns) if self.clashing_names.contains(&name) => {
+                format!("{}{name}", ns.to_upper_camel_case()).into()
+            }
+            _ => name,
+        }
+    }
+
+    /// The alias for a type with a prefixed name, to be declared in the barrel file of its namespace
+    pub fn fmt_type_alias(&self, id: TypeId) -> Option<String> {
+        let name = Self::fmt_unqualified_type_name(self.tcx, id);
+        let qualified = self.fmt_type_name(id);
+        (name != qualified).then(|| format!("typedef {name} = {qualified};"))
+    }
+
+    fn fmt_unqualified_type_name(tcx: &'tcx TypeContext, id: TypeId) -> Cow<'tcx, str> {
+        let resolved = tcx.resolve_type(id);
 
         let candidate = resolved.name().as_str();
 
@@ -107,6 +186,16 @@ impl<'tcx> DartFormatter<'tcx> {
         }
     }
 
+    /// The name of the variant of a method marked `stream` that writes to a `Sink`, such as `toStringTo`.
+    pub fn fmt_stream_method_name(&self, method: &hir::Method) -> String {
+        let name = method
+            .attrs
+            .rename
+            .apply(method.name.as_str().into())
+            .to_lower_camel_case();
+        format!("{name}To")
+    }
+
     /// Format a method
     pub fn fmt_method_name(&self, method: &hir::Method) -> String {
         // TODO(#60): handle other keywords
@@ -185,7 +274,7 @@ impl<'tcx> DartFormatter<'tcx> {
                 PrimitiveType::Char => "Rune",
                 PrimitiveType::Int(_) | PrimitiveType::IntSize(_) | PrimitiveType::Byte => "int",
                 PrimitiveType::Float(_) => "double",
-                PrimitiveType::Int128(_) => panic!("i128 not supported in Dart"),
+                PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
             }
         } else {
             match prim {
@@ -203,7 +292,7 @@ impl<'tcx> DartFormatter<'tcx> {
                 PrimitiveType::IntSize(IntSizeType::Usize) => "ffi.Size",
                 PrimitiveType::Float(FloatType::F32) => "ffi.Float",
                 PrimitiveType::Float(FloatType::F64) => "ffi.Double",
-                PrimitiveType::Int128(_) => panic!("i128 not supported in Dart"),
+                PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
             }
         }
     }
@@ -224,7 +313,7 @@ impl<'tcx> DartFormatter<'tcx> {
             PrimitiveType::Byte => "ByteBuffer",
             PrimitiveType::Int(_) | PrimitiveType::IntSize(_) => "core.List<int>",
             PrimitiveType::Float(_) => "core.List<double>",
-            PrimitiveType::Int128(_) => panic!("i128 not supported in Dart"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
         }
     }
 
@@ -253,7 +342,7 @@ impl<'tcx> DartFormatter<'tcx> {
             PrimitiveType::IntSize(IntSizeType::Isize) => "_isizeAllocIn",
             PrimitiveType::Float(FloatType::F32) => "_float32AllocIn",
             PrimitiveType::Float(FloatType::F64) => "_float64AllocIn",
-            PrimitiveType::Int128(_) => panic!("i128 not supported in Dart"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
         }
     }
 
@@ -302,7 +391,7 @@ impl<'tcx> DartFormatter<'tcx> {
             PrimitiveType::IntSize(IntSizeType::Isize) => "_SliceIsize",
             PrimitiveType::Float(FloatType::F32) => "_SliceFloat",
             PrimitiveType::Float(FloatType::F64) => "_SliceDouble",
-            PrimitiveType::Int128(_) => panic!("i128 not supported in Dart"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
         }
     }
 
@@ -1,7 +1,9 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Write;
+use std::path::Path;
 
+use crate::templates::{ClassHookVars, MethodHookVars, MethodHooks, TemplateOverrides};
 use crate::{ErrorStore, FileMap};
 use diplomat_core::hir::OutputOnly;
 use diplomat_core::hir::{
@@ -15,6 +17,7 @@ use diplomat_core::hir::{
 };
 
 use askama::Template;
+use serde::Deserialize;
 
 mod formatter;
 use formatter::DartFormatter;
@@ -22,13 +25,18 @@ use formatter::DartFormatter;
 pub(crate) fn attr_support() -> BackendAttrSupport {
     let mut a = BackendAttrSupport::default();
 
-    a.namespacing = false;
+    a.namespacing = true;
     a.memory_sharing = false;
     a.non_exhaustive_structs = true;
     a.method_overloading = false;
     a.utf8_strings = false;
     a.utf16_strings = true;
-    a.static_slices = false;
+    a.static_slices = true;
+    a.static_borrows = false;
+    a.int128 = false;
+    a.int128_slices = false;
+    a.write16 = true;
+    a.reads = true;
 
     a.constructors = true;
     a.named_constructors = true;
@@ -39,6 +47,7 @@ pub(crate) fn attr_support() -> BackendAttrSupport {
     a.iterators = true;
     a.iterables = true;
     a.indexing = true;
+    a.streaming_writes = true;
     a.option = true;
     a.callbacks = false;
     a.traits = false;
@@ -46,10 +55,30 @@ pub(crate) fn attr_support() -> BackendAttrSupport {
     a
 }
 
+/// Configuration for the Dart backend, read from the `--library-config` file.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct DartConfig {
+    /// Assert that opaques which aren't `send` are only used on the isolate that created them.
+    thread_checks: Option<bool>,
+}
+
 pub(crate) fn run<'cx>(
     tcx: &'cx TypeContext,
+    conf_path: Option<&Path>,
     docs_url_gen: &'cx DocsUrlGenerator,
+    templates: &TemplateOverrides,
 ) -> (FileMap, ErrorStore<'cx, String>) {
+    let conf = conf_path
+        .map(|conf_path| {
+            let conf_str = std::fs::read_to_string(conf_path).unwrap_or_else(|err| {
+                panic!("Could not open config toml file: {conf_path:?} : {err}")
+            });
+            toml::from_str::<DartConfig>(&conf_str)
+                .unwrap_or_else(|err| panic!("Parsing error in {conf_path:?}: {err}"))
+        })
+        .unwrap_or_default();
+    let thread_checks = conf.thread_checks.unwrap_or(false);
     let formatter = DartFormatter::new(tcx, docs_url_gen);
 
     let files = FileMap::default();
@@ -61,10 +90,17 @@ pub(crate) fn run<'cx>(
     let mut context = TyGenContext {
         tcx,
         errors: &errors,
+        templates,
         helper_classes: &mut helper_classes,
         formatter: &formatter,
+        thread_checks,
     };
 
+    // Namespaced types are parts of the same library, in subdirectories, and are
+    // additionally exported from a barrel file per namespace, which also declares
+    // the unprefixed names of types whose names clash with another namespace.
+    let mut namespaces = BTreeMap::<&str, (Vec<_>, Vec<_>)>::new();
+
     for (id, ty) in tcx.all_types() {
         if ty.attrs().disable {
             continue;
@@ -72,18 +108,46 @@ pub(crate) fn run<'cx>(
 
         let (file_name, body) = context.gen(id);
 
+        let file_name = if let Some(ref ns) = ty.attrs().namespace {
+            let (types, aliases) = namespaces.entry(ns.as_str()).or_default();
+            if let Some(alias) = formatter.fmt_type_alias(id) {
+                aliases.push((formatter.fmt_type_name(id), alias));
+            } else {
+                types.push(formatter.fmt_type_name(id));
+            }
+            format!("{}/{file_name}", formatter.fmt_namespace_dir(ns))
+        } else {
+            file_name
+        };
+
         directives.insert(formatter.fmt_part(&file_name));
 
         files.add_file(
-            file_name,
+            file_name.clone(),
             render_class(
                 body,
-                BTreeSet::from_iter([formatter.fmt_part_of_lib()]),
+                BTreeSet::from_iter([formatter.fmt_part_of_lib(&file_name)]),
                 Default::default(),
             ),
         );
     }
 
+    for (ns, (types, aliases)) in namespaces {
+        let barrel = formatter.fmt_file_name(&formatter.fmt_namespace_dir(ns));
+        let mut directives = BTreeSet::new();
+        if !types.is_empty() {
+            directives.insert(formatter.fmt_export_from_lib(&barrel, &types));
+        }
+        let (aliased, aliases): (Vec<_>, Vec<_>) = aliases.into_iter().unzip();
+        if !aliased.is_empty() {
+            directives.insert(formatter.fmt_import_from_lib(&barrel, &aliased));
+        }
+        files.add_file(
+            barrel.clone(),
+            render_class(aliases.join("\n"), directives, Default::default()),
+        );
+    }
+
     directives.insert(formatter.fmt_import(
         "dart:core",
         Some("show int, double, bool, String, Object, override"),
@@ -102,9 +166,25 @@ pub(crate) fn run<'cx>(
     // For DiplomatWrite and slices
     directives.insert(formatter.fmt_import("dart:convert", None, None));
 
+    // For `useDeveloperLog`
+    directives.insert(formatter.fmt_import("dart:developer", Some("as developer"), None));
+
     // For slices
     directives.insert(formatter.fmt_import("dart:typed_data", None, Some("unused_import")));
 
+    // For the owner isolate checks
+    if thread_checks {
+        directives.insert(formatter.fmt_import("dart:isolate", Some("as isolate"), None));
+    }
+
+    // For `leakReport`
+    helper_classes.insert(
+        "write".into(),
+        include_str!("../../templates/dart/write.dart").into(),
+    );
+
+    helper_classes.insert("layout".into(), gen_layout_check(tcx, &formatter));
+
     files.add_file(
         formatter.fmt_file_name("lib"),
         render_class(
@@ -117,6 +197,57 @@ pub(crate) fn run<'cx>(
     (files, errors)
 }
 
+/// Generates `checkStructLayouts`, which checks the sizes of the FFI structs against the layouts
+/// `#[diplomat::bridge]` asserts for the Rust structs.
+fn gen_layout_check(tcx: &TypeContext, formatter: &DartFormatter) -> String {
+    #[derive(askama::Template)]
+    #[template(path = "dart/layout.dart.jinja", escape = "none")]
+    struct LayoutTemplate<'a> {
+        structs: Vec<LayoutStruct<'a>>,
+    }
+
+    struct LayoutStruct<'a> {
+        name: Cow<'a, str>,
+        size32: usize,
+        size64: usize,
+        /// The names and 32- and 64-bit offsets of the fields
+        fields: Vec<(Cow<'a, str>, usize, usize)>,
+    }
+
+    let mut structs = tcx
+        .all_types()
+        .filter(|(_, ty)| !ty.attrs().disable)
+        .filter_map(|(id, ty)| {
+            let ([info32, info64], field_names) = match ty {
+                TypeDef::Struct(s) if !s.fields.is_empty() => (
+                    crate::js::layout::struct_field_info_by_width(s, tcx),
+                    s.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(),
+                ),
+                TypeDef::OutStruct(s) if !s.fields.is_empty() => (
+                    crate::js::layout::struct_field_info_by_width(s, tcx),
+                    s.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(),
+                ),
+                _ => return None,
+            };
+            Some(LayoutStruct {
+                name: formatter.fmt_type_name(id),
+                size32: info32.struct_layout.size(),
+                size64: info64.struct_layout.size(),
+                fields: field_names
+                    .into_iter()
+                    .zip(info32.fields.iter().zip(&info64.fields))
+                    .map(|(name, (f32, f64))| {
+                        (formatter.fmt_param_name(name), f32.offset, f64.offset)
+                    })
+                    .collect(),
+            })
+        })
+        .collect::<Vec<_>>();
+    structs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    LayoutTemplate { structs }.render().unwrap()
+}
+
 fn render_class(
     body: String,
     directives: BTreeSet<Cow<'static, str>>,
@@ -143,7 +274,10 @@ struct TyGenContext<'a, 'cx> {
     tcx: &'cx TypeContext,
     formatter: &'a DartFormatter<'cx>,
     errors: &'a ErrorStore<'cx, String>,
+    templates: &'a TemplateOverrides,
     helper_classes: &'a mut BTreeMap<String, String>,
+    /// Whether to check that opaques which aren't `send` stay on their isolate
+    thread_checks: bool,
 }
 
 impl<'a, 'cx> TyGenContext<'a, 'cx> {
@@ -165,12 +299,24 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
         )
     }
 
+    /// Render the user's `class_prologue` hook for a type. Dart has no namespaces.
+    fn class_prologue(&self, type_name: &str, kind: &'static str) -> Option<String> {
+        self.templates.class_prologue(
+            &ClassHookVars {
+                type_name,
+                namespace: None,
+                kind,
+            },
+            self.errors,
+        )
+    }
+
     fn gen_enum(&mut self, ty: &'cx hir::EnumDef, id: TypeId, type_name: &str) -> String {
         let methods = ty
             .methods
             .iter()
             .filter(|m| !m.attrs.disable)
-            .flat_map(|method| self.gen_method_info(id, method, type_name))
+            .flat_map(|method| self.gen_method_infos(id, method, type_name))
             .collect::<Vec<_>>();
 
         let special = self.gen_special_method_info(&ty.special_method_presence);
@@ -185,6 +331,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             docs: String,
             is_contiguous: bool,
             special: SpecialMethodGenInfo<'a>,
+            class_prologue: Option<String>,
         }
 
         ImplTemplate {
@@ -195,6 +342,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             docs: self.formatter.fmt_docs(&ty.docs),
             is_contiguous: is_contiguous_enum(ty),
             special,
+            class_prologue: self.class_prologue(type_name, "enum"),
         }
         .render()
         .unwrap()
@@ -205,7 +353,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             .methods
             .iter()
             .filter(|m| !m.attrs.disable)
-            .flat_map(|method| self.gen_method_info(id, method, type_name))
+            .flat_map(|method| self.gen_method_infos(id, method, type_name))
             .collect::<Vec<_>>();
 
         let destructor = &ty.dtor_abi_name;
@@ -220,15 +368,27 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             destructor: &'a str,
             lifetimes: &'a LifetimeEnv,
             special: SpecialMethodGenInfo<'a>,
+            class_prologue: Option<String>,
+            thread_check: bool,
+        }
+
+        let mut docs = self.formatter.fmt_docs(&ty.docs);
+        if let Some(thread_safety_docs) = ty.attrs.thread_safety.docs() {
+            if !docs.is_empty() {
+                docs.push_str("\n///\n/// ");
+            }
+            docs.push_str(thread_safety_docs);
         }
 
         ImplTemplate {
             type_name,
             methods: methods.as_slice(),
             destructor: destructor.as_str(),
-            docs: self.formatter.fmt_docs(&ty.docs),
+            docs,
+            thread_check: self.thread_checks && !ty.attrs.thread_safety.send,
             lifetimes: &ty.lifetimes,
             special,
+            class_prologue: self.class_prologue(type_name, "opaque"),
         }
         .render()
         .unwrap()
@@ -272,10 +432,8 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                 /// Get the name/initializer of the allocator needed for a particular type
                 fn alloc_name<P: TyPosition>(ty: &hir::StructDef<P>, field_ty: &Type<P>) -> Option<String> {
                     if let &hir::Type::Slice(slice) = field_ty {
-                        if let Some(lt) = slice.lifetime() {
-                            let MaybeStatic::NonStatic(lt) = lt else {
-                                panic!("'static not supported in Dart");
-                            };
+                        // `'static` slices live in `_staticArena`, so they don't need a finalized arena
+                        if let Some(MaybeStatic::NonStatic(lt)) = slice.lifetime() {
                             Some(format!(
                                 "{lt_name}AppendArray.isNotEmpty ? _FinalizedArena.withLifetime({lt_name}AppendArray).arena : temp",
                                 lt_name = ty.lifetimes.fmt_lifetime(lt),
@@ -326,7 +484,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             .methods
             .iter()
             .filter(|m| !m.attrs.disable)
-            .flat_map(|method| self.gen_method_info(id, method, type_name))
+            .flat_map(|method| self.gen_method_infos(id, method, type_name))
             .collect::<Vec<_>>();
         let special = self.gen_special_method_info(&ty.special_method_presence);
 
@@ -389,6 +547,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             docs: String,
             lifetimes: &'a LifetimeEnv,
             special: SpecialMethodGenInfo<'a>,
+            class_prologue: Option<String>,
         }
 
         ImplTemplate {
@@ -400,16 +559,33 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             docs: self.formatter.fmt_docs(&ty.docs),
             lifetimes: &ty.lifetimes,
             special,
+            class_prologue: self.class_prologue(type_name, "struct"),
         }
         .render()
         .unwrap()
     }
 
+    /// Generates the [`MethodInfo`] of a method, followed by that of its variant writing to a
+    /// `Sink` if it is marked `stream`.
+    fn gen_method_infos(
+        &mut self,
+        id: TypeId,
+        method: &'cx hir::Method,
+        type_name: &str,
+    ) -> Vec<MethodInfo<'cx>> {
+        let mut infos = Vec::from_iter(self.gen_method_info(id, method, type_name, false));
+        if method.attrs.stream {
+            infos.extend(self.gen_method_info(id, method, type_name, true));
+        }
+        infos
+    }
+
     fn gen_method_info(
         &mut self,
         id: TypeId,
         method: &'cx hir::Method,
         type_name: &str,
+        stream: bool,
     ) -> Option<MethodInfo<'cx>> {
         if method.attrs.disable {
             return None;
@@ -445,9 +621,18 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
         }
 
         let mut arenas = Vec::new();
+        let mut readers = Vec::new();
 
         for param in method.params.iter() {
             let param_name = self.formatter.fmt_param_name(param.name.as_str());
+            if let hir::Type::Read = param.ty {
+                arenas.push(format!("final {param_name}Reader = _Reader({param_name});").into());
+                readers.push(param_name.clone());
+                self.helper_classes.insert(
+                    "read".into(),
+                    include_str!("../../templates/dart/read.dart").into(),
+                );
+            }
             param_decls_dart.push(format!("{} {param_name}", self.gen_type_name(&param.ty),));
             param_names_ffi.push(param_name.clone());
             param_types_ffi.push(self.gen_type_name_ffi(&param.ty, false));
@@ -477,6 +662,12 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                         }
                         // Owned slices use the Rust allocator
                         ParamBorrowInfo::TemporarySlice if s.lifetime().is_none() => None,
+                        // `'static` slices live in `_staticArena`
+                        ParamBorrowInfo::TemporarySlice
+                            if matches!(s.lifetime(), Some(MaybeStatic::Static)) =>
+                        {
+                            None
+                        }
                         ParamBorrowInfo::TemporarySlice => {
                             // Everyone else uses the temporary arena that keeps stuff alive until the method is called
                             *needs_temp_arena = true;
@@ -534,53 +725,126 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             param_types_ffi.push(self.formatter.fmt_opaque_as_ffi().into());
             param_types_ffi_cast.push(self.formatter.fmt_opaque_as_ffi().into());
             param_names_ffi.push("write".into());
-            self.helper_classes.insert(
-                "write".into(),
-                include_str!("../../templates/dart/write.dart").into(),
-            );
+            if stream {
+                param_decls_dart.push("core.Sink<core.List<int>> sink".into());
+                self.helper_classes.insert(
+                    "stream_write".into(),
+                    include_str!("../../templates/dart/stream_write.dart").into(),
+                );
+            } else if method.write_encoding == hir::WriteEncoding::Utf16 {
+                self.helper_classes.insert(
+                    "write16".into(),
+                    include_str!("../../templates/dart/write16.dart").into(),
+                );
+            } else {
+                self.helper_classes.insert(
+                    "write".into(),
+                    include_str!("../../templates/dart/write.dart").into(),
+                );
+            }
         }
 
-        let return_ty = self.gen_return_type_name(&method.output);
+        let return_ty = if stream {
+            "void".into()
+        } else {
+            self.gen_return_type_name(&method.output)
+        };
         let return_type_ffi = self.gen_return_type_name_ffi(&method.output, false);
         let return_type_ffi_cast = self.gen_return_type_name_ffi(&method.output, true);
 
-        let return_expression =
-            self.gen_c_to_dart_for_return_type(&method.output, &method.lifetime_env);
+        let mut return_expression = if stream {
+            Some("write.finalize();".into())
+        } else {
+            self.gen_c_to_dart_for_return_type(&method.output, &method.lifetime_env)
+        };
+
+        // Errors thrown by the sources of readers are rethrown once Rust is done reading
+        if !readers.is_empty() {
+            let finalize = readers
+                .iter()
+                .map(|name| format!("{name}Reader.finalize();"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            return_expression = Some(match return_expression {
+                Some(ret) => format!("{finalize}\n{ret}").into(),
+                None => finalize.into(),
+            });
+        }
 
         let params = param_decls_dart.join(", ");
 
-        let declaration = match &method.attrs.special_method {
-            Some(SpecialMethod::Constructor) => format!("factory {type_name}({params})"),
-            Some(SpecialMethod::NamedConstructor(name)) => format!(
-                "factory {type_name}.{}({params})",
-                self.formatter.fmt_constructor_name(name, method)
-            ),
-            Some(SpecialMethod::Getter(name)) => format!(
-                "{return_ty} get {}",
-                self.formatter.fmt_accessor_name(name, method)
-            ),
-            Some(SpecialMethod::Setter(name)) => format!(
-                "set {}({params})",
-                self.formatter.fmt_accessor_name(name, method)
+        let (declaration, method_name) = match &method.attrs.special_method {
+            _ if stream => {
+                let name = self.formatter.fmt_stream_method_name(method);
+                let modifier = if method.param_self.is_none() {
+                    "static "
+                } else {
+                    ""
+                };
+                (format!("{modifier}{return_ty} {name}({params})"), name)
+            }
+            Some(SpecialMethod::Constructor) => {
+                (format!("factory {type_name}({params})"), type_name.into())
+            }
+            Some(SpecialMethod::NamedConstructor(name)) => {
+                let name = self.formatter.fmt_constructor_name(name, method);
+                (format!("factory {type_name}.{name}({params})"), name)
+            }
+            Some(SpecialMethod::Getter(name)) => {
+                let name = self.formatter.fmt_accessor_name(name, method);
+                (format!("{return_ty} get {name}"), name)
+            }
+            Some(SpecialMethod::Setter(name)) => {
+                let name = self.formatter.fmt_accessor_name(name, method);
+                (format!("set {name}({params})"), name)
+            }
+            Some(SpecialMethod::Stringifier) => (
+                "@core.override\n  String toString()".into(),
+                "toString".into(),
             ),
-            Some(SpecialMethod::Stringifier) => "@core.override\n  String toString()".into(),
-            Some(SpecialMethod::Comparison) => format!("int compareTo({type_name} other)"),
-            Some(SpecialMethod::Iterator) => format!("{return_ty} _iteratorNext({params})"),
-            Some(SpecialMethod::Iterable) => format!("{return_ty} get iterator"),
-            Some(SpecialMethod::Indexer) => format!("{return_ty} operator []({params})"),
-            None if method.param_self.is_none() => format!(
-                "static {return_ty} {}({params})",
-                self.formatter.fmt_method_name(method)
+            Some(SpecialMethod::Comparison) => (
+                format!("int compareTo({type_name} other)"),
+                "compareTo".into(),
             ),
-            None => format!(
-                "{return_ty} {}({params})",
-                self.formatter.fmt_method_name(method)
+            Some(SpecialMethod::Iterator) => (
+                format!("{return_ty} _iteratorNext({params})"),
+                "_iteratorNext".into(),
             ),
+            Some(SpecialMethod::Iterable) => {
+                (format!("{return_ty} get iterator"), "iterator".into())
+            }
+            Some(SpecialMethod::Indexer) => {
+                (format!("{return_ty} operator []({params})"), "[]".into())
+            }
+            None if method.param_self.is_none() => {
+                let name = self.formatter.fmt_method_name(method);
+                (format!("static {return_ty} {name}({params})"), name)
+            }
+            None => {
+                let name = self.formatter.fmt_method_name(method);
+                (format!("{return_ty} {name}({params})"), name)
+            }
             Some(special) => unimplemented!("Found unknown special method type {special:?}"),
         };
 
+        let hooks = self.templates.method_hooks(
+            &MethodHookVars {
+                type_name,
+                method_name: &method_name,
+                abi_name,
+            },
+            self.errors,
+        );
+
         let mut docs = self.formatter.fmt_docs(&method.docs);
 
+        if stream {
+            if !docs.is_empty() {
+                docs.push_str("\n///\n/// ");
+            }
+            docs.push_str("Adds the output to [sink] as it is written, instead of returning it.");
+        }
+
         if let hir::ReturnType::Fallible(_, Some(e)) = &method.output {
             write!(
                 &mut docs,
@@ -590,9 +854,15 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             .unwrap();
         }
 
+        let thread_check = self.thread_checks
+            && matches!(&method.param_self, Some(hir::ParamSelf { ty: SelfType::Opaque(o), .. })
+                if self.tcx.resolve_opaque(o.tcx_id).attrs.thread_safety
+                    .is_thread_bound(o.owner.mutability.is_mutable()));
+
         Some(MethodInfo {
             method,
             docs,
+            thread_check,
             declaration,
             abi_name,
             param_types_ffi,
@@ -605,6 +875,8 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             return_expression,
             lifetimes: &method.lifetime_env,
             method_lifetimes_map: visitor.borrow_map(),
+            hooks,
+            stream,
         })
     }
 
@@ -690,6 +962,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                 self.formatter.fmt_primitive_list_type(p).into()
             }
             Type::Slice(hir::Slice::Strs(..)) => "core.List<core.String>".into(),
+            Type::Read => "core.Iterable<core.List<int>>".into(),
             Type::DiplomatOption(ref inner) => {
                 let inner = self.gen_type_name(inner);
                 self.formatter.fmt_nullable(&inner).into()
@@ -760,6 +1033,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                 self.formatter.fmt_enum_as_ffi(cast).into()
             }
             Type::Slice(s) => self.gen_slice(&s).into(),
+            Type::Read => self.formatter.fmt_opaque_as_ffi().into(),
             Type::DiplomatOption(ref inner) => self.gen_result(Some(inner), None).into(),
             _ => unreachable!("unknown AST/HIR variant"),
         }
@@ -793,7 +1067,9 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
     fn gen_self_type_name_ffi(&self, ty: &SelfType, cast: bool) -> Cow<'cx, str> {
         match ty {
             SelfType::Opaque(_) => self.formatter.fmt_opaque_as_ffi().into(),
-            SelfType::Struct(s) => format!("_{}Ffi", s.resolve(self.tcx).name.as_str()).into(),
+            SelfType::Struct(s) => {
+                format!("_{}Ffi", self.formatter.fmt_type_name(s.tcx_id.into())).into()
+            }
             SelfType::Enum(_) => self.formatter.fmt_enum_as_ffi(cast).into(),
             _ => unreachable!("unknown AST/HIR variant"),
         }
@@ -844,13 +1120,15 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                     hir::Slice::Strs(encoding) => self.formatter.fmt_str_slice_alloc_in(encoding),
                     _ => unreachable!("unknown AST/HIR variant"),
                 };
-                let alloc = if s.lifetime().is_none() {
-                    "_RustAlloc()"
-                } else {
-                    alloc.expect("need allocator for slice")
+                let alloc = match s.lifetime() {
+                    None => "_RustAlloc()",
+                    // `'static` slices must never be freed
+                    Some(MaybeStatic::Static) => "_staticArena",
+                    Some(MaybeStatic::NonStatic(_)) => alloc.expect("need allocator for slice"),
                 };
                 format!("{dart_name}.{alloc_in}({alloc})",).into()
             }
+            Type::Read => format!("{dart_name}Reader._ffi").into(),
             Type::DiplomatOption(ref inner) => {
                 let conversion = self.gen_dart_to_c_for_type(
                     inner,
@@ -913,7 +1191,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
 
                 let mut edges = if let Some(lt) = op.owner.lifetime() {
                     let MaybeStatic::NonStatic(lt) = lt else {
-                        panic!("'static not supported in Dart")
+                        unreachable!("'static lifetimes are rejected during lowering")
                     };
                     self.formatter
                         .fmt_lifetime_edge_array(lt, lifetime_env)
@@ -924,7 +1202,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
 
                 for lt in op.lifetimes.lifetimes() {
                     let MaybeStatic::NonStatic(lt) = lt else {
-                        panic!("'static not supported in Dart");
+                        unreachable!("'static lifetimes are rejected during lowering");
                     };
                     // We only generate a single edge in the list per lifetime, despite transitivity
                     //
@@ -960,7 +1238,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                     let mut edges = String::new();
                     for lt in st.lifetimes().lifetimes() {
                         let MaybeStatic::NonStatic(lt) = lt else {
-                            panic!("'static not supported in Dart")
+                            unreachable!("'static lifetimes are rejected during lowering")
                         };
                         write!(&mut edges, ", {}Edges", lifetime_env.fmt_lifetime(lt)).unwrap();
                     }
@@ -978,20 +1256,18 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                 let type_name = self.formatter.fmt_type_name(id);
                 format!("{type_name}.values.firstWhere((v) => v._ffi == {var_name})").into()
             }
-            Type::Slice(slice) => {
-                if let Some(lt) = slice.lifetime() {
-                    let MaybeStatic::NonStatic(lifetime) = lt else {
-                        panic!("'static not supported in Dart");
-                    };
-                    format!(
-                        "{var_name}._toDart({}Edges)",
-                        lifetime_env.fmt_lifetime(lifetime)
-                    )
-                    .into()
-                } else {
-                    format!("{var_name}._toDart([])").into()
+            Type::Slice(slice) => match slice.lifetime() {
+                Some(MaybeStatic::NonStatic(lifetime)) => format!(
+                    "{var_name}._toDart({}Edges)",
+                    lifetime_env.fmt_lifetime(lifetime)
+                )
+                .into(),
+                // `'static` slices can be viewed forever, and must never be freed
+                Some(MaybeStatic::Static) => {
+                    format!("{var_name}._toDart([], isStatic: true)").into()
                 }
-            }
+                None => format!("{var_name}._toDart([])").into(),
+            },
             Type::DiplomatOption(ref inner) => {
                 let conversion = self.gen_c_to_dart_for_type(
                     inner,
@@ -1119,7 +1395,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             // special case: not typed lists for platform-specific integers, so cannot borrow
             hir::Slice::Primitive(_, hir::PrimitiveType::IntSize(_) | hir::PrimitiveType::Bool) => "core.Iterable.generate(_length).map((i) => _data[i]).toList(growable: false)",
             hir::Slice::Primitive(..) => "_data.asTypedList(_length)",
-            hir::Slice::Strs(..) => "core.Iterable.generate(_length).map((i) => _data[i]._toDart(lifetimeEdges)).toList(growable: false)",
+            hir::Slice::Strs(..) => "core.Iterable.generate(_length).map((i) => _data[i]._toDart(lifetimeEdges, isStatic: isStatic)).toList(growable: false)",
             _ => unreachable!("unknown AST/HIR variant"),
         };
 
@@ -1149,7 +1425,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                 "slice._data = alloc(length)..asTypedList(length).setRange(0, length, this);".into(),
                 "slice._length = length;".into(),
             ],
-            hir::Slice::Primitive(_, hir::PrimitiveType::Int128(_)) => panic!("i128 not supported in Dart"),
+            hir::Slice::Primitive(_, hir::PrimitiveType::Int128(_)) => unreachable!("i128 is rejected during lowering"),
             // Manual construction
             _ => vec![
                 "slice._data = alloc(length);".into(),
@@ -1189,7 +1465,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                 hir::PrimitiveType::Int(hir::IntType::U32 | hir::IntType::I32) | hir::PrimitiveType::Float(hir::FloatType::F32) => (" * 4", "4"),
                 hir::PrimitiveType::Int(hir::IntType::U64 | hir::IntType::I64) | hir::PrimitiveType::Float(hir::FloatType::F64) => (" * 8", "8"),
                 hir::PrimitiveType::IntSize(..) => ("* ffi.sizeOf<ffi.Size>()", "ffi.sizeOf<ffi.Size>()"),
-                hir::PrimitiveType::Int128(_) => panic!("i128 not supported in Dart"),
+                hir::PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
             };
             format!("_rustFree.attach(r, (pointer: _data.cast(), bytes: _length{size}, align: {align}));").into()
         }
@@ -1320,6 +1596,8 @@ struct MethodInfo<'a> {
     method: &'a hir::Method,
     /// Docs
     docs: String,
+    /// Whether to check that `this` is used on the isolate that created it
+    thread_check: bool,
     /// The declaration (everything before the parameter list)
     declaration: String,
     /// The ABI name of the method
@@ -1349,6 +1627,11 @@ struct MethodInfo<'a> {
     /// it borrows from. The parameter list may contain the parameter name, or
     /// a spread of a struct's `_fiellsForLifetimeFoo` getter.
     method_lifetimes_map: BTreeMap<Lifetime, BorrowedLifetimeInfo<'a>>,
+    /// The user's method hooks, rendered for this method
+    hooks: MethodHooks,
+    /// Whether this is the variant of a method marked `stream` that writes to a `Sink`. Its
+    /// native function is declared by the regular variant.
+    stream: bool,
 }
 
 struct FieldInfo<'a, P: TyPosition> {
@@ -1365,6 +1648,25 @@ struct FieldInfo<'a, P: TyPosition> {
 
 // Helpers used in templates (Askama has restrictions on Rust syntax)
 
+fn write_constructor(m: &MethodInfo) -> &'static str {
+    match m.method.write_encoding {
+        _ if m.stream => "_StreamWrite(sink)",
+        hir::WriteEncoding::Utf16 => "_Write16()",
+        _ => "_Write()",
+    }
+}
+
+/// Methods that call back into Dart, writing to a `Sink` or reading from a `DiplomatRead`, can't
+/// be leaf calls.
+fn is_leaf(m: &MethodInfo) -> bool {
+    !m.method.attrs.stream
+        && !m
+            .method
+            .params
+            .iter()
+            .any(|param| matches!(param.ty, hir::Type::Read))
+}
+
 fn display_lifetime_edge<'a>(edge: &'a LifetimeEdge) -> Cow<'a, str> {
     let param_name = &edge.param_name;
     match edge.kind {
@@ -1394,7 +1696,7 @@ fn iter_fields_with_lifetimes_from_set<'a, P: TyPosition>(
     fn does_type_use_lifetime_from_set<P: TyPosition>(ty: &Type<P>, lifetime: &Lifetime) -> bool {
         ty.lifetimes().any(|lt| {
             let MaybeStatic::NonStatic(lt) = lt else {
-                panic!("'static not supported in Dart");
+                unreachable!("'static lifetimes are rejected during lowering");
             };
             lt == *lifetime
         })
@@ -0,0 +1,142 @@
+//! The command-line renderer: a Rust binary that prompts for the parameters of each render terminus
+//! on stdin, and prints its output.
+//!
+//! The Rust expressions used here are built by [`super::terminus::RenderTerminusContext`]
+//! alongside the JS ones, see [`super::terminus::ParamInfo::rust`].
+use std::{collections::BTreeMap, path::Path};
+
+use askama::Template;
+
+use super::terminus::TerminusInfo;
+use crate::{rust_paths, ErrorStore};
+
+/// Find the full Rust path (i.e. `my_crate::decimal::ffi::FixedDecimal`) of every type in the bridge at `entry`,
+/// keyed by type name.
+pub(super) fn rust_type_paths(
+    entry: &Path,
+    errors: &ErrorStore<'_, String>,
+) -> BTreeMap<String, String> {
+    let Some(crate_name) = rust_paths::crate_name(entry) else {
+        errors.push_error(format!(
+            "Could not find the crate name of {entry:?} for the CLI demo, \
+            the entry file needs to be part of a crate with a Cargo.toml"
+        ));
+        return BTreeMap::new();
+    };
+
+    rust_paths::type_paths(entry, &crate_name)
+}
+
+struct CliTerminus<'a> {
+    /// Name of the terminus, as shown to the user. Matches the keys of `RenderInfo.termini`.
+    name: String,
+    /// Name of the Rust function that runs the terminus.
+    fn_name: String,
+    info: &'a TerminusInfo,
+}
+
+#[derive(Template)]
+#[template(path = "demo_gen/cli/main.rs.jinja", escape = "none")]
+struct CliInfo<'a> {
+    termini: Vec<CliTerminus<'a>>,
+    /// Termini that need `#[diplomat::demo(external)]` parameters, which we cannot prompt for.
+    skipped: Vec<String>,
+}
+
+/// Generate `cli/main.rs`.
+pub(super) fn generate(termini: &[TerminusInfo]) -> String {
+    let mut info = CliInfo {
+        termini: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for terminus in termini {
+        let name = format!("{}.{}", terminus.type_name, terminus.function_name);
+        if terminus
+            .out_params
+            .iter()
+            .any(|param| param.type_use == "external")
+        {
+            info.skipped.push(name);
+            continue;
+        }
+
+        info.termini.push(CliTerminus {
+            fn_name: heck::AsSnakeCase(&name.replace('.', "_")).to_string(),
+            name,
+            info: terminus,
+        });
+    }
+
+    info.render().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature_tests_entry() -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../feature_tests/src/lib.rs")
+    }
+
+    #[test]
+    fn test_crate_name() {
+        assert_eq!(
+            rust_paths::crate_name(&feature_tests_entry()).as_deref(),
+            Some("diplomat_feature_tests")
+        );
+        assert_eq!(
+            rust_paths::crate_name(Path::new("/no_such_dir/lib.rs")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rust_type_paths() {
+        let errors = ErrorStore::default();
+        let paths = rust_type_paths(&feature_tests_entry(), &errors);
+        assert!(errors.take_all().is_empty());
+        assert_eq!(
+            paths.get("Opaque").map(String::as_str),
+            Some("diplomat_feature_tests::structs::ffi::Opaque")
+        );
+    }
+
+    fn terminus(type_name: &str, function_name: &str, type_use: &str) -> TerminusInfo {
+        TerminusInfo {
+            function_name: function_name.into(),
+            out_params: vec![super::super::terminus::OutParam {
+                param_name: "value".into(),
+                label: "Value".into(),
+                default_value: "".into(),
+                type_name: "number".into(),
+                type_use: type_use.into(),
+                rust_name: "value".into(),
+                rust_input: "prompt::<i32>(\"Value\", None)".into(),
+                rust_mut: false,
+                validation: Vec::new(),
+            }],
+            type_name: type_name.into(),
+            js_file_name: format!("{type_name}.mjs"),
+            node_call_stack: "".into(),
+            rust_call_stack: format!("{type_name}::{function_name}(value, write)"),
+            typescript: false,
+            imports: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let main = generate(&[
+            terminus("FixedDecimal", "toString", "number"),
+            terminus("DataProvider", "format", "external"),
+        ]);
+        assert!(main.contains("\"FixedDecimal.toString\" => fixed_decimal_to_string(),"));
+        assert!(main.contains("fn fixed_decimal_to_string() -> String {"));
+        assert!(main.contains("    let value = prompt::<i32>(\"Value\", None);"));
+        assert!(main.contains("with_write(|write| FixedDecimal::toString(value, write))"));
+        // Termini with external parameters are only listed in the docs
+        assert!(main.contains("//! - `DataProvider.format`"));
+        assert!(!main.contains("fn data_provider_format"));
+    }
+}
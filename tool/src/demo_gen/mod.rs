@@ -1,10 +1,11 @@
 //! Backend for creating automatic demonstrations of using FFI functions.
 //!
-//! Designed to work in conjunction with the JS backend.
+//! Designed to work in conjunction with the JS backend. Besides the JS used by web renderers,
+//! this can also produce a self-contained static HTML page and a command-line demo in Rust.
 //!
 //! See docs/demo_gen.md for more.
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::Write,
 };
 
@@ -18,17 +19,12 @@ use crate::{
     ErrorStore, FileMap,
 };
 
+mod cli;
+mod static_html;
 mod terminus;
 
 pub(crate) fn attr_support() -> BackendAttrSupport {
-    let mut a = js::attr_support();
-
-    // For automagical construction detection:
-    a.constructors = true;
-    a.fallible_constructors = true;
-    a.named_constructors = true;
-
-    a
+    js::attr_support()
 }
 
 /// Configuration for demo_gen generation. Set from a `.toml` file, you can specify the path of the file with `--library-config` option flag.
@@ -50,6 +46,24 @@ pub(crate) struct DemoConfig {
     /// The relative path to Javascript to use in `import` statements for demo files.
     /// If this is set, we do not generate the js/ folder.
     pub relative_js_path: Option<String>,
+
+    /// Generates `static/index.html`, a single page with all JS and the `.wasm` library inlined,
+    /// which can be opened without a web server or a bundler. Requires [DemoConfig::wasm_path].
+    ///
+    /// Not compatible with [DemoConfig::module_name], as the JS bindings have to be available locally.
+    pub static_html: Option<bool>,
+
+    /// Path of the compiled `.wasm` library to inline into the static HTML page (see [DemoConfig::static_html]).
+    /// Relative to the current working directory.
+    pub wasm_path: Option<String>,
+
+    /// Generates `cli/main.rs`, a Rust binary that prompts on stdin for the parameters of each render terminus.
+    /// It calls the FFI functions directly, so it has to be built as an example or binary of the library crate.
+    pub cli: Option<bool>,
+
+    /// `32` (the default) for `wasm32` libraries, `64` for `wasm64` (memory64) libraries.
+    /// Shared with the JS backend, see [`js::pointer_width_from_config`].
+    pub pointer_width: Option<u8>,
 }
 
 /// Per docs/demo_gen.md
@@ -60,11 +74,11 @@ pub(crate) struct DemoConfig {
 /// Render Termini that can be called, and internal functions to construct dependencies that the Render Terminus function needs.
 pub(crate) fn run<'tcx>(
     entry: &std::path::Path,
+    out_folder: &std::path::Path,
     tcx: &'tcx TypeContext,
     docs: &'tcx diplomat_core::ast::DocsUrlGenerator,
     conf: Option<DemoConfig>,
 ) -> (FileMap, ErrorStore<'tcx, String>) {
-    let formatter = JSFormatter::new(tcx, docs);
     let errors = ErrorStore::default();
     let files = FileMap::default();
 
@@ -72,6 +86,9 @@ pub(crate) fn run<'tcx>(
 
     let unwrapped_conf = conf.unwrap_or_default();
 
+    let pointer_width = js::pointer_width_from_config(unwrapped_conf.pointer_width, &errors);
+    let formatter = JSFormatter::new(tcx, docs, pointer_width);
+
     let import_path_exists =
         unwrapped_conf.relative_js_path.is_some() || unwrapped_conf.module_name.is_some();
 
@@ -82,7 +99,10 @@ pub(crate) fn run<'tcx>(
             None => "./js/".into(),
         });
 
-    let module_name = unwrapped_conf.module_name.unwrap_or("index.mjs".into());
+    let module_name = unwrapped_conf
+        .module_name
+        .clone()
+        .unwrap_or("index.mjs".into());
 
     struct TerminusExport {
         type_name: String,
@@ -111,6 +131,13 @@ pub(crate) fn run<'tcx>(
 
     let is_explicit = unwrapped_conf.explicit_generation.unwrap_or(false);
 
+    let generate_cli = unwrapped_conf.cli.unwrap_or(false);
+    let rust_paths = if generate_cli {
+        cli::rust_type_paths(entry, &errors)
+    } else {
+        BTreeMap::new()
+    };
+
     for (id, ty) in tcx.all_types() {
         let _guard = errors.set_context_ty(ty.name().as_str().into());
 
@@ -197,6 +224,7 @@ pub(crate) fn run<'tcx>(
                         js_file_name: js_file_name.clone(),
 
                         node_call_stack: String::default(),
+                        rust_call_stack: String::default(),
 
                         // We set this in the init function of WebDemoGenerationContext.
                         typescript: false,
@@ -208,9 +236,11 @@ pub(crate) fn run<'tcx>(
 
                     relative_import_path: import_path.clone(),
                     module_name: module_name.clone(),
+
+                    rust_paths: &rust_paths,
                 };
 
-                ctx.evaluate(type_name.clone(), method);
+                ctx.evaluate(id, method);
 
                 termini.push(ctx.terminus_info);
             }
@@ -276,5 +306,31 @@ pub(crate) fn run<'tcx>(
         );
     }
 
+    if unwrapped_conf.static_html.unwrap_or(false) {
+        if unwrapped_conf.module_name.is_some() {
+            errors.push_error(
+                "static-html needs the JS bindings to be generated locally, \
+                so it cannot be combined with module-name"
+                    .into(),
+            );
+        } else if let Some(wasm_path) = &unwrapped_conf.wasm_path {
+            if let Some(page) = static_html::generate(
+                &files,
+                &out_folder.join(&import_path),
+                &import_path,
+                wasm_path,
+                &errors,
+            ) {
+                files.add_file("static/index.html".into(), page);
+            }
+        } else {
+            errors.push_error("static-html requires wasm-path to be set".into());
+        }
+    }
+
+    if generate_cli {
+        files.add_file("cli/main.rs".into(), cli::generate(&out_info.termini));
+    }
+
     (files, errors)
 }
@@ -0,0 +1,252 @@
+//! The static HTML renderer: a single page that inlines the demo JS, the JS bindings and the `.wasm` library.
+//!
+//! Every JS module is turned into a `data:` URL and registered in an [import map] under `diplomat-demo/<path>`,
+//! where `<path>` is the module's path relative to the demo_gen output folder. Relative imports are rewritten to
+//! these names, which lets modules import each other (cyclically, even) without a web server or a bundler.
+//!
+//! [import map]: https://developer.mozilla.org/en-US/docs/Web/HTML/Element/script/type/importmap
+use std::{collections::BTreeMap, path::Path};
+
+use askama::Template;
+
+use crate::{ErrorStore, FileMap};
+
+/// Prefix of the import map entries for all inlined modules.
+const MODULE_PREFIX: &str = "diplomat-demo/";
+
+/// The module that `diplomat-wasm.mjs` reads `wasm_path` from. We replace it with a module pointing to the inlined `.wasm` library.
+const CONFIG_MODULE: &str = "diplomat.config.mjs";
+
+#[derive(Template)]
+#[template(path = "demo_gen/static/index.html.jinja", escape = "none")]
+struct StaticHtml {
+    import_map: String,
+}
+
+/// Generate `static/index.html`.
+///
+/// `files` are the files generated by demo_gen so far, `js_dir` is where the JS bindings can be found on disk,
+/// and `import_path` is how the demo files import them (see [`super::DemoConfig::relative_js_path`]).
+pub(super) fn generate(
+    files: &FileMap,
+    js_dir: &Path,
+    import_path: &str,
+    wasm_path: &str,
+    errors: &ErrorStore<'_, String>,
+) -> Option<String> {
+    let wasm = match std::fs::read(wasm_path) {
+        Ok(wasm) => wasm,
+        Err(e) => {
+            errors.push_error(format!("Could not read {wasm_path} to inline: {e}"));
+            return None;
+        }
+    };
+
+    let mut modules = BTreeMap::new();
+
+    for (name, contents) in files.files().iter() {
+        if name.ends_with(".mjs") && name != CONFIG_MODULE && !name.starts_with("rendering/") {
+            modules.insert(name.clone(), contents.clone());
+        }
+    }
+
+    modules.insert(
+        "rendering/rendering.mjs".into(),
+        include_str!("../../templates/demo_gen/default_renderer/rendering.mjs").into(),
+    );
+
+    if let Err(e) = read_js_dir(js_dir, &resolve("", import_path), &mut modules) {
+        errors.push_error(format!(
+            "Could not read the JS bindings in {js_dir:?} to inline: {e}"
+        ));
+        return None;
+    }
+
+    modules.insert(
+        CONFIG_MODULE.into(),
+        format!(
+            "export default {{ wasm_path: \"data:application/wasm;base64,{}\" }};",
+            base64(&wasm)
+        ),
+    );
+
+    let mut imports = BTreeMap::new();
+    for (name, contents) in &modules {
+        let contents = rewrite_imports(contents, |specifier| {
+            let resolved = resolve(name, specifier);
+            if resolved == CONFIG_MODULE || resolved.ends_with(&format!("/{CONFIG_MODULE}")) {
+                format!("{MODULE_PREFIX}{CONFIG_MODULE}")
+            } else {
+                if !modules.contains_key(&resolved) {
+                    errors.push_error(format!("Could not inline {specifier}, imported by {name}"));
+                }
+                format!("{MODULE_PREFIX}{resolved}")
+            }
+        });
+        imports.insert(
+            format!("{MODULE_PREFIX}{name}"),
+            format!(
+                "data:text/javascript;base64,{}",
+                base64(contents.as_bytes())
+            ),
+        );
+    }
+
+    let import_map = serde_json::json!({ "imports": imports });
+
+    Some(
+        StaticHtml {
+            import_map: serde_json::to_string_pretty(&import_map).unwrap(),
+        }
+        .render()
+        .unwrap(),
+    )
+}
+
+/// Recursively add all `.mjs` files in `dir` to `modules`, as `{prefix}/{file name}`.
+fn read_js_dir(
+    dir: &Path,
+    prefix: &str,
+    modules: &mut BTreeMap<String, String>,
+) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = format!(
+            "{prefix}/{}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+        let name = name.trim_start_matches('/');
+        if path.is_dir() {
+            read_js_dir(&path, name, modules)?;
+        } else if name.ends_with(".mjs") {
+            modules.insert(name.into(), std::fs::read_to_string(&path)?);
+        }
+    }
+    Ok(())
+}
+
+/// Resolve the relative import `specifier` from the module at `from`.
+///
+/// Both `from` and the result are relative to the output folder. Leading `..` segments are kept.
+fn resolve(from: &str, specifier: &str) -> String {
+    let mut segments = from.split('/').collect::<Vec<_>>();
+    // The directory of `from`:
+    segments.pop();
+
+    for segment in specifier.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if matches!(segments.last(), Some(s) if *s != "..") => {
+                segments.pop();
+            }
+            s => segments.push(s),
+        }
+    }
+
+    segments.join("/")
+}
+
+/// Replace every relative specifier in the `import` and `export ... from` statements of `source`.
+fn rewrite_imports(source: &str, mut rewrite: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut last = 0;
+
+    for (i, quote) in source.char_indices() {
+        if (quote != '"' && quote != '\'') || i < last {
+            continue;
+        }
+        let statement = source[..i].trim_end();
+        if !statement.ends_with("from") && !statement.ends_with("import") {
+            continue;
+        }
+        let Some(len) = source[i + 1..].find(quote) else {
+            continue;
+        };
+        let specifier = &source[i + 1..i + 1 + len];
+        if !specifier.starts_with("./") && !specifier.starts_with("../") {
+            continue;
+        }
+
+        out.push_str(&source[last..=i]);
+        out.push_str(&rewrite(specifier));
+        last = i + 1 + len;
+    }
+
+    out.push_str(&source[last..]);
+    out
+}
+
+/// Standard base64 encoding with padding, for `data:` URLs.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (u32::from(*b) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(resolve("index.mjs", "./lib.mjs"), "lib.mjs");
+        assert_eq!(resolve("a/b.mjs", "./c.mjs"), "a/c.mjs");
+        assert_eq!(resolve("a/b.mjs", "../c.mjs"), "c.mjs");
+        assert_eq!(resolve("a/b/c.mjs", ".././d/e.mjs"), "a/d/e.mjs");
+        // Leading `..` segments are kept
+        assert_eq!(
+            resolve("index.mjs", "../js/api/index.mjs"),
+            "../js/api/index.mjs"
+        );
+        assert_eq!(resolve("a.mjs", "../../b.mjs"), "../../b.mjs");
+        assert_eq!(resolve("", "../js/api"), "../js/api");
+    }
+
+    #[test]
+    fn test_rewrite_imports() {
+        let source = r#"import { a } from "./a.mjs";
+import * as b from '../b.mjs';
+export { c } from "./c.mjs";
+import "./side_effect.mjs";
+import { d } from "d";
+const s = "./not_an_import.mjs";
+"#;
+        let rewritten = rewrite_imports(source, |specifier| format!("x:{specifier}"));
+        assert_eq!(
+            rewritten,
+            r#"import { a } from "x:./a.mjs";
+import * as b from 'x:../b.mjs';
+export { c } from "x:./c.mjs";
+import "x:./side_effect.mjs";
+import { d } from "d";
+const s = "./not_an_import.mjs";
+"#
+        );
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe, 0x00]), "//4A");
+    }
+}
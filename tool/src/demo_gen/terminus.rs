@@ -1,7 +1,9 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
 
 use diplomat_core::hir::{
-    self, DemoInfo, Method, OpaqueDef, StructDef, StructPath, TyPosition, Type, TypeContext,
+    self, DemoInfo, DemoInputCFG, Method, OpaqueDef, OpaqueOwner, ReturnType, StringEncoding,
+    StructDef, StructPath, TyPosition, Type, TypeContext, TypeId,
 };
 
 use crate::{js::formatter::JSFormatter, ErrorStore};
@@ -12,6 +14,8 @@ use askama::{self, Template};
 pub struct ParamInfo {
     /// The javascript that represents this parameter.
     pub js: String,
+    /// The Rust expression that represents this parameter, for the CLI renderer.
+    pub rust: String,
 }
 
 pub struct OutParam {
@@ -24,6 +28,14 @@ pub struct OutParam {
     pub type_name: String,
     /// Also for typescript and RenderInfo output. This is used for types where we might want to know more information, like if it's an enumerator, or a custom type to be set by the default renderer.
     pub type_use: String,
+    /// Name of the local variable holding this parameter in the CLI renderer.
+    pub rust_name: String,
+    /// Rust expression that prompts for this parameter in the CLI renderer.
+    pub rust_input: String,
+    /// Whether [`OutParam::rust_name`] has to be declared as `mut` (for `&mut [T]` parameters).
+    pub rust_mut: bool,
+    /// Extra RenderInfo fields that configure the input widget and its validation (i.e. `min`), as JS values.
+    pub validation: Vec<(&'static str, String)>,
 }
 
 /// Represents a function that we'll be using when constructing the ultimate output of a RenderTerminus function. See [`TerminusInfo`] for full output.
@@ -41,6 +53,9 @@ struct MethodDependency {
     /// Javascript to invoke for this method.
     method_js: String,
 
+    /// Path of the Rust function to invoke for this method.
+    method_rust: String,
+
     /// Parameters to pass into the method.
     params: Vec<ParamInfo>,
 
@@ -59,12 +74,16 @@ pub(super) struct RenderTerminusContext<'ctx, 'tcx> {
 
     pub relative_import_path: String,
     pub module_name: String,
+
+    /// Rust paths of each type, keyed by type name. Only filled in when generating the CLI renderer.
+    pub rust_paths: &'ctx BTreeMap<String, String>,
 }
 
 impl MethodDependency {
-    pub fn new(method_js: String, owning_param: Option<String>) -> Self {
+    pub fn new(method_js: String, method_rust: String, owning_param: Option<String>) -> Self {
         MethodDependency {
             method_js,
+            method_rust,
             params: Vec::new(),
             owning_param,
         }
@@ -137,6 +156,10 @@ pub(super) struct TerminusInfo {
     /// Final result of recursively calling [`RenderTerminusContext::evaluate_constructor`] on [`MethodDependency`]
     pub node_call_stack: String,
 
+    /// Same as [`TerminusInfo::node_call_stack`], but as a Rust expression for the CLI renderer.
+    /// Expects a `write: &mut DiplomatWrite` to be in scope.
+    pub rust_call_stack: String,
+
     /// Are we a typescript file? Set by [`super::WebDemoGenerationContext::init`]
     pub typescript: bool,
 
@@ -155,22 +178,31 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
 
     /// Create a Render Terminus .js file from a method.
     /// We define this (for now) as any function that outputs [`hir::SuccessType::Write`]
-    pub fn evaluate(&mut self, type_name: String, method: &Method) {
+    pub fn evaluate(&mut self, id: TypeId, method: &Method) {
         // TODO: I think it would be nice to have a stack of the current namespace a given parameter.
         // For instance, ICU4XFixedDecimalFormatter.formatWrite() needs a constructed ICU4XFixedDecimal, which takes an i32 called v as input.
         // Someone just trying to read the .d.ts file will only see function formatWrite(v: number); which doesn't really help them figure out where that's from or why it's there.
 
         // Not making this as part of the RenderTerminusContext because we want each evaluation to have a specific node,
         // which I find easier easier to represent as a parameter to each function than something like an updating the current node in the struct.
-        let mut root =
-            MethodDependency::new(self.get_constructor_js(type_name.clone(), method), None);
+        let mut root = MethodDependency::new(
+            self.get_constructor_js(self.formatter.fmt_type_path(id), method),
+            self.get_constructor_rust(id, method),
+            None,
+        );
 
         // And then we just treat the terminus as a regular constructor method:
-        self.terminus_info.node_call_stack = self.evaluate_constructor(method, &mut root);
+        let call = self.evaluate_constructor(method, &mut root);
+        self.terminus_info.node_call_stack = call.js;
+        self.terminus_info.rust_call_stack = call.rust;
 
-        let type_n = type_name.clone();
+        self.add_import(id);
+    }
+
+    /// Import the type `id` from the library, or its outermost namespace if it is namespaced.
+    fn add_import(&mut self, id: TypeId) {
         let format = self.formatter.fmt_import_module(
-            &type_n,
+            &self.formatter.fmt_type_import_name(id),
             self.module_name.clone(),
             self.relative_import_path.clone(),
         );
@@ -208,14 +240,15 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
             )
             .to_string()
         } else {
-            attrs_default.input_cfg.label
+            attrs_default.input_cfg.label.clone()
         };
 
+        let validation = self.get_input_validation(type_info, &attrs_default.input_cfg, &label);
         let default_value = attrs_default.input_cfg.default_value;
 
         let type_name = match type_info {
             Type::Primitive(p) => self.formatter.fmt_primitive_as_ffi(*p).to_string(),
-            Type::Enum(e) => self.formatter.fmt_type_name(e.tcx_id.into()).to_string(),
+            Type::Enum(e) => self.formatter.fmt_type_path(e.tcx_id.into()),
             Type::Slice(hir::Slice::Str(..)) => self.formatter.fmt_string().to_string(),
             Type::Slice(hir::Slice::Primitive(.., p)) => {
                 self.formatter.fmt_primitive_list_type(*p).to_string()
@@ -223,7 +256,7 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
             Type::Slice(hir::Slice::Strs(..)) => "Array<string>".to_string(),
             _ => {
                 if let Some(i) = type_info.id() {
-                    self.formatter.fmt_type_name(i).to_string()
+                    self.formatter.fmt_type_path(i)
                 } else {
                     panic!("Type {type_info:?} not recognized.");
                 }
@@ -249,21 +282,163 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
 
         self.out_param_collision.insert(param_name, n);
 
+        let rust_name = match heck::AsSnakeCase(&p).to_string() {
+            s if s == "self" => "self_".into(),
+            s => s,
+        };
+        let (rust_input, rust, rust_mut) =
+            self.get_out_param_rust(type_info, &rust_name, &label, &default_value);
+
         let out_param = OutParam {
             param_name: p.clone(),
             label,
             type_name: type_name.clone(),
             type_use,
             default_value,
+            rust_name,
+            rust_input,
+            rust_mut,
+            validation,
         };
 
         self.terminus_info.out_params.push(out_param);
 
-        let param_info = ParamInfo { js: p };
+        let param_info = ParamInfo { js: p, rust };
 
         node.params.push(param_info);
     }
 
+    /// Get the extra RenderInfo fields for an out parameter, from `#[diplomat::demo(input(...))]` and the parameter's type.
+    ///
+    /// Numeric inputs get `min`, `max` and `step` (defaulting to the range of integer types), string inputs `pattern`,
+    /// `choices` and `multiline`, and enums the `choices` of variants to pick from.
+    fn get_input_validation<P: TyPosition<StructPath = StructPath>>(
+        &self,
+        type_info: &Type<P>,
+        cfg: &DemoInputCFG,
+        label: &str,
+    ) -> Vec<(&'static str, String)> {
+        use hir::{IntSizeType, IntType, PrimitiveType};
+
+        let mut fields = Vec::new();
+
+        let numeric = match type_info {
+            Type::Primitive(p) | Type::Slice(hir::Slice::Primitive(_, p)) => {
+                Some(*p).filter(|p| !matches!(p, PrimitiveType::Bool | PrimitiveType::Char))
+            }
+            _ => None,
+        };
+
+        if let Some(p) = numeric {
+            // `isize` and `usize` are 32 bits on wasm32.
+            let (type_min, type_max): (Option<i64>, Option<i64>) = match p {
+                PrimitiveType::Byte | PrimitiveType::Int(IntType::U8) => {
+                    (Some(0), Some(u8::MAX.into()))
+                }
+                PrimitiveType::Int(IntType::I8) => (Some(i8::MIN.into()), Some(i8::MAX.into())),
+                PrimitiveType::Int(IntType::U16) => (Some(0), Some(u16::MAX.into())),
+                PrimitiveType::Int(IntType::I16) => (Some(i16::MIN.into()), Some(i16::MAX.into())),
+                PrimitiveType::Int(IntType::U32) | PrimitiveType::IntSize(IntSizeType::Usize) => {
+                    (Some(0), Some(u32::MAX.into()))
+                }
+                PrimitiveType::Int(IntType::I32) | PrimitiveType::IntSize(IntSizeType::Isize) => {
+                    (Some(i32::MIN.into()), Some(i32::MAX.into()))
+                }
+                PrimitiveType::Int(IntType::U64) => (Some(0), None),
+                _ => (None, None),
+            };
+            let is_integer = !matches!(p, PrimitiveType::Float(_));
+
+            if let Some(min) = cfg
+                .min
+                .map(|m| m.to_string())
+                .or(type_min.map(|m| m.to_string()))
+            {
+                fields.push(("min", min));
+            }
+            if let Some(max) = cfg
+                .max
+                .map(|m| m.to_string())
+                .or(type_max.map(|m| m.to_string()))
+            {
+                fields.push(("max", max));
+            }
+            // Browsers only accept integers in `<input type="number">` without a `step`.
+            let step = cfg.step.map(|s| s.to_string()).unwrap_or_else(|| {
+                if is_integer {
+                    "1".into()
+                } else {
+                    "\"any\"".into()
+                }
+            });
+            fields.push(("step", step));
+        } else if cfg.min.is_some() || cfg.max.is_some() || cfg.step.is_some() {
+            self.errors.push_error(format!(
+                "#[diplomat::demo(input(min, max, step))] are only supported on numeric inputs, found on {label}"
+            ));
+        }
+
+        let is_string = matches!(
+            type_info,
+            Type::Slice(hir::Slice::Str(..) | hir::Slice::Strs(..))
+        );
+
+        if let Some(pattern) = &cfg.pattern {
+            if is_string {
+                fields.push(("pattern", serde_json::to_string(pattern).unwrap()));
+            } else {
+                self.errors.push_error(format!(
+                    "#[diplomat::demo(input(pattern))] is only supported on string inputs, found on {label}"
+                ));
+            }
+        }
+
+        if cfg.multiline {
+            if matches!(type_info, Type::Slice(hir::Slice::Str(..))) {
+                fields.push(("multiline", "true".into()));
+            } else {
+                self.errors.push_error(format!(
+                    "#[diplomat::demo(input(multiline))] is only supported on string inputs, found on {label}"
+                ));
+            }
+        }
+
+        match type_info {
+            Type::Enum(e) => {
+                let variants = e
+                    .resolve(self.tcx)
+                    .variants
+                    .iter()
+                    .map(|v| self.formatter.fmt_enum_variant(v).into_owned())
+                    .collect::<Vec<_>>();
+                for choice in &cfg.choices {
+                    if !variants.contains(choice) {
+                        self.errors.push_error(format!(
+                            "#[diplomat::demo(input(choices))] on {label} contains {choice:?}, which is not one of {variants:?}"
+                        ));
+                    }
+                }
+                let choices = if cfg.choices.is_empty() {
+                    &variants
+                } else {
+                    &cfg.choices
+                };
+                fields.push(("choices", serde_json::to_string(choices).unwrap()));
+            }
+            Type::Slice(hir::Slice::Str(..)) if !cfg.choices.is_empty() => {
+                fields.push(("choices", serde_json::to_string(&cfg.choices).unwrap()));
+            }
+            _ if !cfg.choices.is_empty() => {
+                self.errors.push_error(format!(
+                    "#[diplomat::demo(input(choices))] is only supported on string and enum inputs, found on {label}"
+                ));
+            }
+            _ => {}
+        }
+
+        fields
+    }
+
     /// Take a parameter passed to a terminus (or a constructor), and either:
     /// 1. Add it to the list of parameters that the terminus function takes for the render engine to call.
     /// 2. Go a step deeper and look at its possible constructors to call evaluate_param on.
@@ -311,7 +486,19 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
                     return;
                 }
 
-                self.evaluate_op_constructors(op, type_name.to_string(), param_name, node);
+                self.evaluate_op_constructors(op, o.tcx_id.into(), param_name, node);
+                // Constructors return a `Box`, which we may need to borrow from:
+                let param = node.params.last_mut().unwrap();
+                let borrow = match o.owner.mutability() {
+                    None => "",
+                    Some(m) if m.is_mutable() => "&mut *",
+                    Some(_) => "&*",
+                };
+                param.rust = if o.is_optional() {
+                    format!("Some({borrow}{})", param.rust)
+                } else {
+                    format!("{borrow}{}", param.rust)
+                };
             }
             Type::Struct(s) => {
                 let st = s.resolve(self.tcx);
@@ -322,10 +509,14 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
                         .push_error(format!("Found usage of disabled type {type_name}"))
                 }
 
-                self.evaluate_struct_fields(st, type_name.to_string(), param_name, node);
+                self.evaluate_struct_fields(st, s.tcx_id.into(), param_name, node);
             }
             Type::DiplomatOption(ref inner) => {
-                self.evaluate_param(inner, param_name, node, param_attrs)
+                self.evaluate_param(inner, param_name, node, param_attrs);
+
+                // Works for both `Option<T>` and `DiplomatOption<T>` parameters.
+                let param = node.params.last_mut().unwrap();
+                param.rust = format!("Some({}).into()", param.rust);
             }
             _ => unreachable!("Unknown HIR type {:?}", param_type),
         }
@@ -342,7 +533,19 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
     ///
     /// `method` - The method we're trying to call.
     fn get_constructor_js(&self, owner_type_name: String, method: &Method) -> String {
-        let method_name = self.formatter.fmt_method_name(method);
+        // Special methods are exposed under different names in the JS backend, see `js::gen::TyGenContext::generate_method`.
+        let method_name = match &method.attrs.special_method {
+            Some(hir::SpecialMethod::Constructor) => {
+                // Only reachable for opaques, whose constructors are called with `new`.
+                return format!("((...args) => new {owner_type_name}(...args))");
+            }
+            Some(hir::SpecialMethod::NamedConstructor(name)) => {
+                self.formatter.fmt_method_field_name(name, method)
+            }
+            Some(hir::SpecialMethod::Stringifier) => "toString".into(),
+            Some(hir::SpecialMethod::Indexer) => "at".into(),
+            _ => self.formatter.fmt_method_name(method),
+        };
         if method.param_self.is_some() {
             // We represent as function () instead of () => since closures ignore the `this` args applied to them for whatever reason.
 
@@ -361,11 +564,148 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
         }
     }
 
+    /// Get the path of the Rust function that a constructor (or terminus) calls, i.e. `my_crate::ffi::FixedDecimal::new`.
+    ///
+    /// Methods taking `self` are called like associated functions, with `self` as the first parameter.
+    fn get_constructor_rust(&self, owner: TypeId, method: &Method) -> String {
+        format!("{}::{}", self.get_rust_path(owner), method.name.as_str())
+    }
+
+    /// Get the full Rust path of a type, falling back to its name if [`RenderTerminusContext::rust_paths`] doesn't have it.
+    fn get_rust_path(&self, id: TypeId) -> &str {
+        let name = self.tcx.resolve_type(id).name().as_str();
+        self.rust_paths
+            .get(name)
+            .map(String::as_str)
+            .unwrap_or(name)
+    }
+
+    /// Get the Rust code that the CLI renderer uses for an out parameter.
+    ///
+    /// Returns the expression that prompts for the parameter (stored in a local named `rust_name`),
+    /// the expression that passes that local to a method, and whether the local needs to be mutable.
+    fn get_out_param_rust<P: TyPosition<StructPath = StructPath>>(
+        &self,
+        type_info: &Type<P>,
+        rust_name: &str,
+        label: &str,
+        default_value: &str,
+    ) -> (String, String, bool) {
+        let default = if default_value.is_empty() {
+            "None".to_string()
+        } else {
+            format!("Some({default_value:?})")
+        };
+
+        let rust_primitive = |p: &hir::PrimitiveType| match p {
+            hir::PrimitiveType::Byte => "u8",
+            p => p.as_str(),
+        };
+
+        let str_slice = |encoding: &StringEncoding| match encoding {
+            StringEncoding::UnvalidatedUtf8 => {
+                "diplomat_runtime::DiplomatStrSlice::from(s.as_bytes())"
+            }
+            StringEncoding::UnvalidatedUtf16 => {
+                "diplomat_runtime::DiplomatStr16Slice::from(&s[..])"
+            }
+            _ => "diplomat_runtime::DiplomatUtf8StrSlice::from(s.as_str())",
+        };
+
+        match type_info {
+            Type::Primitive(p) => (
+                format!("prompt::<{}>({label:?}, {default})", rust_primitive(p)),
+                match p {
+                    // `DiplomatChar` parameters are `u32`s.
+                    hir::PrimitiveType::Char => format!("{rust_name}.into()"),
+                    _ => rust_name.to_string(),
+                },
+                false,
+            ),
+            Type::Enum(e) => {
+                let enum_path = self.get_rust_path(e.tcx_id.into());
+                let variants = e
+                    .resolve(self.tcx)
+                    .variants
+                    .iter()
+                    .map(|v| v.name.as_str())
+                    .collect::<Vec<_>>();
+                let arms = variants
+                    .iter()
+                    .map(|v| format!("{v:?} => {enum_path}::{v}, "))
+                    .collect::<String>();
+                (
+                    format!(
+                        "match prompt_choice({label:?}, &{variants:?}, {default}) {{ {arms}_ => unreachable!() }}"
+                    ),
+                    rust_name.to_string(),
+                    false,
+                )
+            }
+            Type::Slice(hir::Slice::Str(lifetime, encoding)) => {
+                let input = format!("prompt::<String>({label:?}, {default})");
+                match (encoding, lifetime) {
+                    (StringEncoding::UnvalidatedUtf16, lifetime) => (
+                        format!("{input}.encode_utf16().collect::<Vec<u16>>()"),
+                        if lifetime.is_some() {
+                            format!("(&{rust_name}[..]).into()")
+                        } else {
+                            format!("{rust_name}.into_boxed_slice().into()")
+                        },
+                        false,
+                    ),
+                    (StringEncoding::UnvalidatedUtf8, Some(_)) => {
+                        (input, format!("{rust_name}.as_bytes().into()"), false)
+                    }
+                    (StringEncoding::UnvalidatedUtf8, None) => (
+                        input,
+                        format!("{rust_name}.into_bytes().into_boxed_slice().into()"),
+                        false,
+                    ),
+                    (_, Some(_)) => (input, format!("{rust_name}.as_str().into()"), false),
+                    (_, None) => (input, format!("{rust_name}.into_boxed_str().into()"), false),
+                }
+            }
+            Type::Slice(hir::Slice::Primitive(borrow, p)) => {
+                let input = format!("prompt_list::<{}>({label:?}, {default})", rust_primitive(p));
+                match borrow {
+                    Some(b) if b.mutability.is_mutable() => {
+                        (input, format!("(&mut {rust_name}[..]).into()"), true)
+                    }
+                    Some(_) => (input, format!("(&{rust_name}[..]).into()"), false),
+                    None => (
+                        input,
+                        format!("{rust_name}.into_boxed_slice().into()"),
+                        false,
+                    ),
+                }
+            }
+            Type::Slice(hir::Slice::Strs(encoding)) => {
+                let input = format!("prompt_list::<String>({label:?}, {default})");
+                (
+                    match encoding {
+                        StringEncoding::UnvalidatedUtf16 => format!(
+                            "{input}.iter().map(|s| s.encode_utf16().collect::<Vec<u16>>()).collect::<Vec<_>>()"
+                        ),
+                        _ => input,
+                    },
+                    format!(
+                        "(&{rust_name}.iter().map(|s| {}).collect::<Vec<_>>()[..]).into()",
+                        str_slice(encoding)
+                    ),
+                    false,
+                )
+            }
+            // External parameters cannot be prompted for, the CLI renderer skips termini that need them.
+            _ => (String::new(), rust_name.to_string(), false),
+        }
+    }
+
     /// Find an opaque constructor that suits our purposes (see the `usable_constructor` variable), then evaluate it with [`RenderTerminusContext::evaluate_constructor`].
     fn evaluate_op_constructors(
         &mut self,
         op: &OpaqueDef,
-        type_name: String,
+        id: TypeId,
         param_name: String,
         node: &mut MethodDependency,
     ) {
@@ -384,21 +724,16 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
             }
 
             if usable_constructor {
-                self.terminus_info
-                    .imports
-                    .insert(self.formatter.fmt_import_module(
-                        &type_name.clone(),
-                        self.module_name.clone(),
-                        self.relative_import_path.clone(),
-                    ));
+                self.add_import(id);
 
                 let mut child = MethodDependency::new(
-                    self.get_constructor_js(type_name.to_string(), method),
+                    self.get_constructor_js(self.formatter.fmt_type_path(id), method),
+                    self.get_constructor_rust(id, method),
                     Some(param_name),
                 );
 
                 let call = self.evaluate_constructor(method, &mut child);
-                node.params.push(ParamInfo { js: call });
+                node.params.push(call);
                 break;
             }
         }
@@ -420,6 +755,7 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
                     Try adding #[diplomat::demo(default_constructor)]*/",
                     op.name.as_str()
                 ),
+                rust: "unimplemented!()".into(),
             });
         }
     }
@@ -428,19 +764,13 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
     fn evaluate_struct_fields(
         &mut self,
         st: &StructDef,
-        type_name: String,
+        id: TypeId,
         param_name: String,
         node: &mut MethodDependency,
     ) {
-        self.terminus_info
-            .imports
-            .insert(self.formatter.fmt_import_module(
-                &type_name,
-                self.module_name.clone(),
-                self.relative_import_path.clone(),
-            ));
+        self.add_import(id);
 
-        let mut child = MethodDependency::new("".to_string(), Some(param_name));
+        let mut child = MethodDependency::new("".to_string(), "".to_string(), Some(param_name));
 
         #[derive(Template)]
         #[template(path = "demo_gen/struct.js.jinja", escape = "none")]
@@ -462,19 +792,29 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
         }
 
         child.method_js = StructInfo {
-            type_name: type_name.clone(),
+            type_name: self.formatter.fmt_type_path(id),
             fields,
         }
         .render()
         .unwrap();
 
+        let struct_path = self.get_rust_path(id);
+        let rust_fields = st
+            .fields
+            .iter()
+            .zip(child.params.iter())
+            .map(|(field, param)| format!("{}: {}", field.name.as_str(), param.rust))
+            .collect::<Vec<_>>()
+            .join(", ");
+
         node.params.push(ParamInfo {
             js: child.render().unwrap(),
+            rust: format!("{struct_path} {{ {rust_fields} }}"),
         });
     }
 
     /// Read a constructor that will be created by our terminus, and add any parameters we might need.
-    fn evaluate_constructor(&mut self, method: &Method, node: &mut MethodDependency) -> String {
+    fn evaluate_constructor(&mut self, method: &Method, node: &mut MethodDependency) -> ParamInfo {
         let param_self = method.param_self.as_ref();
 
         if param_self.is_some() {
@@ -493,7 +833,216 @@ impl<'ctx, 'tcx> RenderTerminusContext<'ctx, 'tcx> {
             );
         }
 
+        let mut rust_args = node
+            .params
+            .iter()
+            .map(|p| p.rust.clone())
+            .collect::<Vec<_>>();
+        if method.output.success_type().is_write() {
+            rust_args.push("write".into());
+        }
+        let mut rust = format!("{}({})", node.method_rust, rust_args.join(", "));
+        if matches!(
+            method.output,
+            ReturnType::Fallible(..) | ReturnType::Nullable(..)
+        ) {
+            write!(rust, ".demo_unwrap({:?})", node.method_rust).unwrap();
+        }
+
         // The node that is awaiting this node as a child needs the rendered output:
-        node.render().unwrap()
+        ParamInfo {
+            js: node.render().unwrap(),
+            rust,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+    use diplomat_core::hir::{self, TypeContext};
+    use quote::quote;
+
+    use super::{RenderTerminusContext, TerminusInfo};
+    use crate::js::{formatter::JSFormatter, PointerWidth};
+    use crate::ErrorStore;
+
+    type Fields = Vec<(&'static str, String)>;
+
+    /// Runs [`RenderTerminusContext::get_input_validation`] on every parameter of the first method of the first opaque.
+    fn validation(tk_stream: proc_macro2::TokenStream) -> (Vec<Fields>, Vec<String>) {
+        let file = syn::parse2::<syn::File>(tk_stream).expect("failed to parse item");
+        let mut attr_validator = hir::BasicAttributeValidator::new("demo_gen");
+        attr_validator.support = super::super::attr_support();
+        let tcx = TypeContext::from_syn(&file, attr_validator).expect("failed to lower");
+
+        let docs = hir::DocsUrlGenerator::with_base_urls(None, Default::default());
+        let formatter = JSFormatter::new(&tcx, &docs, PointerWidth::default());
+        let errors = ErrorStore::default();
+        let rust_paths = BTreeMap::new();
+        let ctx = RenderTerminusContext {
+            tcx: &tcx,
+            formatter: &formatter,
+            errors: &errors,
+            terminus_info: TerminusInfo {
+                function_name: String::new(),
+                out_params: Vec::new(),
+                type_name: String::new(),
+                js_file_name: String::new(),
+                node_call_stack: String::new(),
+                rust_call_stack: String::new(),
+                typescript: false,
+                imports: BTreeSet::new(),
+            },
+            out_param_collision: HashMap::new(),
+            relative_import_path: String::new(),
+            module_name: String::new(),
+            rust_paths: &rust_paths,
+        };
+
+        let fields = tcx.opaques()[0].methods[0]
+            .params
+            .iter()
+            .map(|param| {
+                ctx.get_input_validation(
+                    &param.ty,
+                    &param.attrs.demo_attrs.input_cfg,
+                    param.name.as_str(),
+                )
+            })
+            .collect();
+        let errors = errors.take_all().into_iter().map(|(_, e)| e).collect();
+        (fields, errors)
+    }
+
+    fn fields(pairs: &[(&'static str, &str)]) -> Fields {
+        pairs.iter().map(|(k, v)| (*k, v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_numeric_validation() {
+        let (validation, errors) = validation(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                impl Opaque {
+                    pub fn new(
+                        a: u8,
+                        b: i16,
+                        #[diplomat::demo(input(min = -5, max = 5))]
+                        c: i32,
+                        d: usize,
+                        e: u64,
+                        f: i64,
+                        #[diplomat::demo(input(step = 0.25))]
+                        g: f64,
+                        h: &[u16],
+                    ) -> Box<Opaque> {
+                        unimplemented!()
+                    }
+                }
+            }
+        });
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(
+            validation,
+            [
+                fields(&[("min", "0"), ("max", "255"), ("step", "1")]),
+                fields(&[("min", "-32768"), ("max", "32767"), ("step", "1")]),
+                fields(&[("min", "-5"), ("max", "5"), ("step", "1")]),
+                fields(&[("min", "0"), ("max", "4294967295"), ("step", "1")]),
+                fields(&[("min", "0"), ("step", "1")]),
+                fields(&[("step", "1")]),
+                fields(&[("step", "0.25")]),
+                fields(&[("min", "0"), ("max", "65535"), ("step", "1")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_and_enum_validation() {
+        let (validation, errors) = validation(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                enum Color {
+                    Red,
+                    Green,
+                    Blue,
+                }
+
+                impl Opaque {
+                    pub fn new(
+                        #[diplomat::demo(input(pattern = "[a-z]+", multiline))]
+                        a: &str,
+                        #[diplomat::demo(input(choices = ["x", "y"]))]
+                        b: &str,
+                        c: Color,
+                        #[diplomat::demo(input(choices = ["Green"]))]
+                        d: Color,
+                    ) -> Box<Opaque> {
+                        unimplemented!()
+                    }
+                }
+            }
+        });
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(
+            validation,
+            [
+                fields(&[("pattern", "\"[a-z]+\""), ("multiline", "true")]),
+                fields(&[("choices", "[\"x\",\"y\"]")]),
+                fields(&[("choices", "[\"Red\",\"Green\",\"Blue\"]")]),
+                fields(&[("choices", "[\"Green\"]")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_validation() {
+        let (validation, errors) = validation(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                enum Color {
+                    Red,
+                }
+
+                impl Opaque {
+                    pub fn new(
+                        #[diplomat::demo(input(min = 1))]
+                        a: &str,
+                        #[diplomat::demo(input(pattern = "[0-9]"))]
+                        b: i32,
+                        #[diplomat::demo(input(multiline))]
+                        c: bool,
+                        #[diplomat::demo(input(choices = ["Blue"]))]
+                        d: Color,
+                    ) -> Box<Opaque> {
+                        unimplemented!()
+                    }
+                }
+            }
+        });
+        assert_eq!(
+            validation[1],
+            fields(&[("min", "-2147483648"), ("max", "2147483647"), ("step", "1")])
+        );
+        assert_eq!(
+            errors,
+            [
+                "#[diplomat::demo(input(min, max, step))] are only supported on numeric inputs, found on a",
+                "#[diplomat::demo(input(pattern))] is only supported on string inputs, found on b",
+                "#[diplomat::demo(input(multiline))] is only supported on string inputs, found on c",
+                "#[diplomat::demo(input(choices))] on d contains \"Blue\", which is not one of [\"Red\"]",
+            ]
+        );
     }
 }
@@ -0,0 +1,263 @@
+//! Reporting of errors found while lowering a bridge, in the style of rustc diagnostics.
+
+use colored::*;
+use diplomat_core::ast::SourceSpan;
+use diplomat_core::hir::{ErrorAndContext, ErrorContext, LoweringError};
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// How diagnostics are printed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Human-readable, with source snippets
+    #[default]
+    Human,
+    /// One JSON object per line, for editors and other tools
+    Json,
+}
+
+impl FromStr for MessageFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("expected `human` or `json`, found `{s}`")),
+        }
+    }
+}
+
+/// A diagnostic in `--message-format=json`.
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    level: &'static str,
+    message: String,
+    /// The item the error was found in, e.g. `Foo::bar`
+    context: String,
+    help: Option<String>,
+    span: Option<JsonSpan<'a>>,
+}
+
+/// Lines and columns are 1-indexed, and the ends are exclusive, like rustc's JSON diagnostics.
+#[derive(Serialize)]
+struct JsonSpan<'a> {
+    file_name: &'a Path,
+    line_start: usize,
+    line_end: usize,
+    column_start: usize,
+    column_end: usize,
+}
+
+/// Print lowering errors to stderr.
+pub(crate) fn report_lowering_errors(
+    sources: &SourceFiles,
+    errors: &[ErrorAndContext],
+    format: MessageFormat,
+) {
+    for (ctx, err) in errors {
+        eprintln!("{}", render_diagnostic(sources, ctx, err, format));
+    }
+
+    if format == MessageFormat::Human {
+        let count = errors.len();
+        let plural = if count == 1 { "" } else { "s" };
+        eprintln!(
+            "{}: could not lower the bridge due to {count} previous error{plural}",
+            "error".red().bold()
+        );
+    }
+}
+
+/// Render a single lowering error in the given format.
+fn render_diagnostic(
+    sources: &SourceFiles,
+    ctx: &ErrorContext,
+    err: &LoweringError,
+    format: MessageFormat,
+) -> String {
+    let location = ctx
+        .location()
+        .and_then(|ident| Some((ident.as_str(), ident.span()?)))
+        .and_then(|(ident, span)| Some((sources.find(ident, span)?, span)));
+    match format {
+        MessageFormat::Human => {
+            let mut out = format!("{}: {}\n", "error".red().bold(), err.to_string().bold());
+            if let Some(((file, line), span)) = location {
+                render_snippet(&mut out, file, line, span);
+            }
+            let _ = writeln!(out, "  {} in {ctx}", "= note:".bold());
+            if let Some(help) = err.help() {
+                let _ = writeln!(out, "  {} {help}", "= help:".bold());
+            }
+            out
+        }
+        MessageFormat::Json => {
+            let diagnostic = JsonDiagnostic {
+                level: "error",
+                message: err.to_string(),
+                context: ctx.to_string(),
+                help: err.help(),
+                span: location.map(|((file, _), span)| JsonSpan {
+                    file_name: file,
+                    line_start: span.line,
+                    line_end: span.end_line,
+                    column_start: span.column + 1,
+                    column_end: span.end_column + 1,
+                }),
+            };
+            serde_json::to_string(&diagnostic).expect("diagnostics are serializable")
+        }
+    }
+}
+
+/// Append a rustc-style snippet pointing at `span` in `line`.
+fn render_snippet(out: &mut String, file: &Path, line: &str, span: SourceSpan) {
+    let line_no = span.line.to_string();
+    let gutter = " ".repeat(line_no.len());
+    let width = if span.end_line == span.line {
+        span.end_column.saturating_sub(span.column).max(1)
+    } else {
+        1
+    };
+    let _ = writeln!(
+        out,
+        "{gutter}{} {}:{}:{}",
+        "-->".blue().bold(),
+        file.display(),
+        span.line,
+        span.column + 1
+    );
+    let _ = writeln!(out, "{gutter} {}", "|".blue().bold());
+    let _ = writeln!(
+        out,
+        "{} {} {line}",
+        line_no.blue().bold(),
+        "|".blue().bold()
+    );
+    let _ = writeln!(
+        out,
+        "{gutter} {} {}{}",
+        "|".blue().bold(),
+        " ".repeat(span.column),
+        "^".repeat(width).red().bold()
+    );
+    let _ = writeln!(out, "{gutter} {}", "|".blue().bold());
+}
+
+/// The source files a bridge was parsed from, as loaded by the module inliner.
+///
+/// Spans only carry lines and columns, and bridges can be spread across modules in
+/// several files, so we find the file by looking for the identifier at the span's position.
+#[derive(Default)]
+pub(crate) struct SourceFiles {
+    files: Vec<(PathBuf, String)>,
+}
+
+impl SourceFiles {
+    /// Record a file loaded while parsing the bridge.
+    pub(crate) fn add(&mut self, path: &Path, text: String) {
+        self.files.push((path.to_owned(), text));
+    }
+
+    /// Find the file that contains `ident` at `span`, returning it and the line the span starts on.
+    fn find(&self, ident: &str, span: SourceSpan) -> Option<(&Path, &str)> {
+        self.files.iter().find_map(|(path, text)| {
+            let line = text.lines().nth(span.line.checked_sub(1)?)?;
+            let (start, _) = line.char_indices().nth(span.column)?;
+            line[start..]
+                .starts_with(ident)
+                .then(|| (path.as_path(), line))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{render_diagnostic, MessageFormat, SourceFiles};
+    use diplomat_core::hir::TypeContext;
+    use std::path::Path;
+
+    const BRIDGE: &str = r#"#[diplomat::bridge]
+mod ffi {
+    #[diplomat::opaque]
+    pub struct Bar;
+    impl Bar {
+        pub fn make(x: Box<Bar>) {}
+    }
+}
+"#;
+
+    /// Lower `BRIDGE` as if it was found in `src/other.rs`, next to a `src/lib.rs` that has
+    /// a different identifier at the same position.
+    fn render(format: MessageFormat) -> Vec<String> {
+        let mut sources = SourceFiles::default();
+        sources.add(Path::new("src/lib.rs"), BRIDGE.replace("(x:", "(y:"));
+        sources.add(Path::new("src/other.rs"), BRIDGE.into());
+
+        let file = syn::parse_file(BRIDGE).expect("failed to parse bridge");
+        let errors = TypeContext::from_syn(&file, crate::attr_validator("c"))
+            .expect_err("bridge should fail to lower");
+        errors
+            .iter()
+            .map(|(ctx, err)| render_diagnostic(&sources, ctx, err, format))
+            .collect()
+    }
+
+    #[test]
+    fn test_human() {
+        colored::control::set_override(false);
+        assert_eq!(
+            render(MessageFormat::Human),
+            [
+                r#"error: found Box<T> in input where T is an opaque, but owned opaques aren't allowed in inputs. try &T instead? T = Bar
+ --> src/other.rs:6:21
+  |
+6 |         pub fn make(x: Box<Bar>) {}
+  |                     ^
+  |
+  = note: in Bar::make
+"#
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let rendered = render(MessageFormat::Json);
+        assert_eq!(rendered.len(), 1);
+        let diagnostic: serde_json::Value = serde_json::from_str(&rendered[0]).unwrap();
+        assert_eq!(diagnostic["level"], "error");
+        assert_eq!(diagnostic["context"], "Bar::make");
+        assert_eq!(
+            diagnostic["span"],
+            serde_json::json!({
+                "file_name": "src/other.rs",
+                "line_start": 6,
+                "line_end": 6,
+                "column_start": 21,
+                "column_end": 22,
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_file() {
+        let mut sources = SourceFiles::default();
+        sources.add(Path::new("src/lib.rs"), "mod other;\n".into());
+        let file = syn::parse_file(BRIDGE).expect("failed to parse bridge");
+        let errors = TypeContext::from_syn(&file, crate::attr_validator("c")).unwrap_err();
+        let (ctx, err) = &errors[0];
+        let rendered = render_diagnostic(&sources, ctx, err, MessageFormat::Json);
+        let diagnostic: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(diagnostic["span"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_message_format() {
+        assert_eq!("human".parse(), Ok(MessageFormat::Human));
+        assert_eq!("json".parse(), Ok(MessageFormat::Json));
+        assert!("short".parse::<MessageFormat>().is_err());
+    }
+}
@@ -0,0 +1,824 @@
+//! API compatibility checking between two versions of a bridge.
+//!
+//! Both versions are lowered to a [`TypeContext`] for each backend (so that `disable`s and
+//! `rename`s are taken into account), and then compared item by item. Each change is classified
+//! as ABI-breaking (existing binaries or previously generated bindings stop working against
+//! the new library) and/or source-breaking (code written against the old bindings no longer
+//! compiles against the new ones).
+
+use diplomat_core::hir::{
+    self, BackendAttrSupport, CallbackInstantiationFunctionality, Method, Mutability, OpaqueOwner,
+    ReturnType, SelfType, StringEncoding, StructPathLike, SuccessType, TraitIdGetter, TyPosition,
+    Type, TypeContext, TypeDef,
+};
+use std::borrow::Cow;
+use std::fmt;
+
+/// A single difference between two versions of a bridge.
+#[derive(Debug)]
+pub struct Change {
+    /// The affected item, e.g. `Foo` or `Foo::bar`
+    pub item: String,
+    pub description: String,
+    /// Binaries (and generated bindings) built against the old version will not work with the new one
+    pub abi_breaking: bool,
+    /// Code written against the old bindings will not compile against the new ones
+    pub source_breaking: bool,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match (self.abi_breaking, self.source_breaking) {
+            (true, true) => "ABI- and source-breaking",
+            (true, false) => "ABI-breaking",
+            (false, true) => "source-breaking",
+            (false, false) => "compatible",
+        };
+        write!(f, "[{kind}] {}: {}", self.item, self.description)
+    }
+}
+
+/// Compares two [`TypeContext`]s lowered for the same backend.
+pub struct DiffContext<'tcx> {
+    pub old: &'tcx TypeContext,
+    pub new: &'tcx TypeContext,
+    /// The attributes supported by the backend both were lowered for
+    pub support: BackendAttrSupport,
+    /// Whether the backend exposes methods under their ABI names and ignores `rename`s (i.e. C)
+    pub abi_names_are_source_names: bool,
+    /// Whether the backend exposes borrowed opaques as references, and nullable ones as
+    /// pointers (i.e. C++), so that changing their nullability changes their type
+    pub borrowed_opaques_are_references: bool,
+    changes: Vec<Change>,
+}
+
+/// The position of a type in a method signature, which determines how nullability changes
+/// affect callers.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Position {
+    Param,
+    Return,
+}
+
+impl<'tcx> DiffContext<'tcx> {
+    pub fn new(
+        old: &'tcx TypeContext,
+        new: &'tcx TypeContext,
+        support: BackendAttrSupport,
+        abi_names_are_source_names: bool,
+        borrowed_opaques_are_references: bool,
+    ) -> Self {
+        Self {
+            old,
+            new,
+            support,
+            abi_names_are_source_names,
+            borrowed_opaques_are_references,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Compare the two versions, returning the list of changes found.
+    pub fn run(mut self) -> Vec<Change> {
+        for (_, old_ty) in self.old.all_types() {
+            if old_ty.attrs().disable {
+                continue;
+            }
+            let name = old_ty.name().as_str();
+            let new_ty = self
+                .new
+                .all_types()
+                .map(|(_, ty)| ty)
+                .find(|ty| ty.name().as_str() == name && !ty.attrs().disable);
+            match new_ty {
+                Some(new_ty) => self.diff_type(old_ty, new_ty),
+                None => self.push(name, "type removed", true, true),
+            }
+        }
+
+        for (_, old_trt) in self.old.all_traits() {
+            if old_trt.attrs.disable {
+                continue;
+            }
+            let name = old_trt.name.as_str();
+            let new_trt = self
+                .new
+                .all_traits()
+                .map(|(_, trt)| trt)
+                .find(|trt| trt.name.as_str() == name && !trt.attrs.disable);
+            match new_trt {
+                Some(new_trt) => self.diff_trait(old_trt, new_trt),
+                None => self.push(name, "trait removed", true, true),
+            }
+        }
+
+        self.changes
+    }
+
+    fn push(
+        &mut self,
+        item: impl Into<String>,
+        description: impl Into<String>,
+        abi: bool,
+        source: bool,
+    ) {
+        self.changes.push(Change {
+            item: item.into(),
+            description: description.into(),
+            abi_breaking: abi,
+            source_breaking: source,
+        })
+    }
+
+    /// The name an item will have in the generated bindings
+    fn source_name<'a>(&self, attrs: &'a hir::Attrs, name: &'a str) -> Cow<'a, str> {
+        if self.abi_names_are_source_names {
+            name.into()
+        } else {
+            attrs.rename.apply(name.into())
+        }
+    }
+
+    fn diff_type(&mut self, old: TypeDef<'tcx>, new: TypeDef<'tcx>) {
+        let name = old.name().as_str();
+        let old_source_name = self.source_name(old.attrs(), name);
+        let new_source_name = self.source_name(new.attrs(), name);
+        if old_source_name != new_source_name {
+            self.push(
+                name,
+                format!("renamed from `{old_source_name}` to `{new_source_name}`"),
+                false,
+                true,
+            );
+        }
+        if old.attrs().namespace != new.attrs().namespace {
+            self.push(name, "namespace changed", false, true);
+        }
+
+        match (old, new) {
+            (TypeDef::Struct(old_s), TypeDef::Struct(new_s)) => {
+                let old_fields = old_s
+                    .fields
+                    .iter()
+                    .map(|f| (f.name.as_str(), fmt_type(self.old, &f.ty)));
+                let new_fields = new_s
+                    .fields
+                    .iter()
+                    .map(|f| (f.name.as_str(), fmt_type(self.new, &f.ty)));
+                self.diff_fields(name, old_fields.collect(), new_fields.collect());
+            }
+            (TypeDef::OutStruct(old_s), TypeDef::OutStruct(new_s)) => {
+                let old_fields = old_s
+                    .fields
+                    .iter()
+                    .map(|f| (f.name.as_str(), fmt_type(self.old, &f.ty)));
+                let new_fields = new_s
+                    .fields
+                    .iter()
+                    .map(|f| (f.name.as_str(), fmt_type(self.new, &f.ty)));
+                self.diff_fields(name, old_fields.collect(), new_fields.collect());
+            }
+            (TypeDef::Opaque(old_o), TypeDef::Opaque(new_o)) => {
+                if old_o.dtor_abi_name != new_o.dtor_abi_name {
+                    self.push(
+                        name,
+                        format!(
+                            "destructor symbol changed from `{}` to `{}`",
+                            old_o.dtor_abi_name, new_o.dtor_abi_name
+                        ),
+                        true,
+                        self.abi_names_are_source_names,
+                    );
+                }
+            }
+            (TypeDef::Enum(old_e), TypeDef::Enum(new_e)) => {
+                for old_v in &old_e.variants {
+                    let item = format!("{name}::{}", old_v.name);
+                    match new_e.variants.iter().find(|v| v.name == old_v.name) {
+                        Some(new_v) => {
+                            if old_v.discriminant != new_v.discriminant {
+                                self.push(
+                                    item,
+                                    format!(
+                                        "discriminant changed from {} to {}",
+                                        old_v.discriminant, new_v.discriminant
+                                    ),
+                                    true,
+                                    false,
+                                );
+                            }
+                        }
+                        None => self.push(item, "variant removed", true, true),
+                    }
+                }
+            }
+            (TypeDef::Struct(_), TypeDef::OutStruct(_)) => {
+                self.push(name, "struct became an output-only struct", false, true);
+            }
+            (TypeDef::OutStruct(_), TypeDef::Struct(_)) => {
+                // Out structs may only be returned, so this does not break anything
+            }
+            _ => {
+                self.push(
+                    name,
+                    format!("changed from {} to {}", fmt_kind(old), fmt_kind(new)),
+                    true,
+                    true,
+                );
+                // Methods on a type of a different kind can't be compared meaningfully
+                return;
+            }
+        }
+
+        self.diff_methods(name, old.methods(), new.methods());
+    }
+
+    fn diff_fields(&mut self, ty: &str, old: Vec<(&str, String)>, new: Vec<(&str, String)>) {
+        let mut reordered = false;
+        for (i, (field, old_ty)) in old.iter().enumerate() {
+            let item = format!("{ty}.{field}");
+            match new.iter().position(|(n, _)| n == field) {
+                Some(j) => {
+                    if i != j {
+                        reordered = true;
+                    }
+                    let new_ty = &new[j].1;
+                    if old_ty != new_ty {
+                        self.push(
+                            item,
+                            format!("field type changed from `{old_ty}` to `{new_ty}`"),
+                            true,
+                            true,
+                        );
+                    }
+                }
+                None => self.push(item, "field removed", true, true),
+            }
+        }
+        if reordered {
+            self.push(ty, "field order changed", true, false);
+        }
+        for (field, _) in new.iter().filter(|(n, _)| !old.iter().any(|(o, _)| o == n)) {
+            self.push(
+                format!("{ty}.{field}"),
+                "field added",
+                true,
+                !self.support.non_exhaustive_structs,
+            );
+        }
+    }
+
+    fn diff_methods(&mut self, ty: &str, old: &'tcx [Method], new: &'tcx [Method]) {
+        let new_methods = new.iter().filter(|m| !m.attrs.disable);
+        for old_m in old.iter().filter(|m| !m.attrs.disable) {
+            let item = format!("{ty}::{}", old_m.name);
+            let by_name = new_methods.clone().find(|m| m.name == old_m.name);
+            let by_abi = new_methods.clone().find(|m| m.abi_name == old_m.abi_name);
+            let new_m = match (by_name, by_abi) {
+                (Some(new_m), _) => {
+                    if new_m.abi_name != old_m.abi_name {
+                        self.push(
+                            item.as_str(),
+                            format!(
+                                "symbol changed from `{}` to `{}`",
+                                old_m.abi_name, new_m.abi_name
+                            ),
+                            true,
+                            self.abi_names_are_source_names,
+                        );
+                    }
+                    new_m
+                }
+                (None, Some(new_m)) => {
+                    // The method was renamed in Rust, but kept its symbol name
+                    new_m
+                }
+                (None, None) => {
+                    self.push(item, "method removed", true, true);
+                    continue;
+                }
+            };
+            self.diff_method(&item, old_m, new_m);
+        }
+    }
+
+    fn diff_method(&mut self, item: &str, old: &'tcx Method, new: &'tcx Method) {
+        let old_name = self.source_name(&old.attrs, old.name.as_str());
+        let new_name = self.source_name(&new.attrs, new.name.as_str());
+        if !self.abi_names_are_source_names && old_name != new_name {
+            self.push(
+                item,
+                format!("renamed from `{old_name}` to `{new_name}`"),
+                false,
+                true,
+            );
+        }
+
+        let old_special = old.attrs.special_method.as_ref().map(|s| format!("{s:?}"));
+        let new_special = new.attrs.special_method.as_ref().map(|s| format!("{s:?}"));
+        if old_special != new_special {
+            let describe = |s: Option<String>| s.unwrap_or_else(|| "a regular method".into());
+            self.push(
+                item,
+                format!(
+                    "changed from {} to {}",
+                    describe(old_special),
+                    describe(new_special)
+                ),
+                false,
+                true,
+            );
+        }
+
+        match (&old.param_self, &new.param_self) {
+            (Some(old_self), Some(new_self)) => {
+                let old_ty = fmt_self_type(self.old, &old_self.ty);
+                let new_ty = fmt_self_type(self.new, &new_self.ty);
+                if old_ty != new_ty {
+                    let abi = !matches!(
+                        (&old_self.ty, &new_self.ty),
+                        (SelfType::Opaque(..), SelfType::Opaque(..))
+                    );
+                    self.push(
+                        item,
+                        format!("self parameter changed from `{old_ty}` to `{new_ty}`"),
+                        abi,
+                        true,
+                    );
+                }
+            }
+            (None, Some(_)) => self.push(item, "static method became non-static", true, true),
+            (Some(_), None) => self.push(item, "method became static", true, true),
+            (None, None) => (),
+        }
+
+        for (i, old_p) in old.params.iter().enumerate() {
+            let Some(new_p) = new.params.get(i) else {
+                self.push(
+                    item,
+                    format!("parameter `{}` removed", old_p.name),
+                    true,
+                    true,
+                );
+                continue;
+            };
+            let param = format!("parameter `{}`", old_p.name);
+            self.diff_ty(item, &param, Position::Param, &old_p.ty, &new_p.ty);
+        }
+        for new_p in new.params.iter().skip(old.params.len()) {
+            self.push(
+                item,
+                format!("new required parameter `{}`", new_p.name),
+                true,
+                true,
+            );
+        }
+
+        self.diff_output(item, &old.output, &new.output);
+
+        let writes = |m: &Method| matches!(m.output.success_type(), SuccessType::Write);
+        if writes(old) && writes(new) && old.write_encoding != new.write_encoding {
+            // Only C exposes the `DiplomatWrite`, everything else returns a string
+            self.push(
+                item,
+                format!(
+                    "written string changed from {:?} to {:?}",
+                    old.write_encoding, new.write_encoding
+                ),
+                true,
+                self.abi_names_are_source_names,
+            );
+        }
+    }
+
+    fn diff_output(&mut self, item: &str, old: &'tcx ReturnType, new: &'tcx ReturnType) {
+        let fallible = |r: &ReturnType| matches!(r, ReturnType::Fallible(..));
+        let nullable = |r: &ReturnType| matches!(r, ReturnType::Nullable(..));
+        if fallible(old) != fallible(new) {
+            let desc = if fallible(new) {
+                "method became fallible"
+            } else {
+                "method is no longer fallible"
+            };
+            self.push(item, desc, true, true);
+        }
+        if nullable(old) != nullable(new) {
+            let desc = if nullable(new) {
+                "return value became nullable"
+            } else {
+                "return value is no longer nullable"
+            };
+            self.push(item, desc, true, true);
+        }
+
+        match (old.success_type(), new.success_type()) {
+            (SuccessType::OutType(old_ty), SuccessType::OutType(new_ty)) => {
+                self.diff_ty(item, "return type", Position::Return, old_ty, new_ty)
+            }
+            (old_s, new_s) => {
+                let old_s = fmt_success(self.old, old_s);
+                let new_s = fmt_success(self.new, new_s);
+                if old_s != new_s {
+                    self.push(
+                        item,
+                        format!("return type changed from `{old_s}` to `{new_s}`"),
+                        true,
+                        true,
+                    );
+                }
+            }
+        }
+
+        if let (ReturnType::Fallible(_, old_e), ReturnType::Fallible(_, new_e)) = (old, new) {
+            let old_e = old_e.as_ref().map(|e| fmt_type(self.old, e));
+            let new_e = new_e.as_ref().map(|e| fmt_type(self.new, e));
+            if old_e != new_e {
+                let describe = |e: Option<String>| e.unwrap_or_else(|| "()".into());
+                self.push(
+                    item,
+                    format!(
+                        "error type changed from `{}` to `{}`",
+                        describe(old_e),
+                        describe(new_e)
+                    ),
+                    true,
+                    true,
+                );
+            }
+        }
+    }
+
+    /// Compare two types in a method signature, where `what` describes where they are found.
+    fn diff_ty<P: TyPosition>(
+        &mut self,
+        item: &str,
+        what: &str,
+        position: Position,
+        old: &Type<P>,
+        new: &Type<P>,
+    ) {
+        let old_fmt = fmt_type(self.old, old);
+        let new_fmt = fmt_type(self.new, new);
+        if old_fmt == new_fmt {
+            return;
+        }
+        let old_inner = fmt_non_nullable(self.old, old);
+        let new_inner = fmt_non_nullable(self.new, new);
+        if old_inner != new_inner {
+            self.push(
+                item,
+                format!("{what} changed from `{old_fmt}` to `{new_fmt}`"),
+                true,
+                true,
+            );
+            return;
+        }
+
+        // Only nullability changed. Nullable opaques are nullable pointers, which have the same
+        // representation as non-nullable ones, whereas `DiplomatOption` is a different type.
+        let abi =
+            matches!(old, Type::DiplomatOption(..)) || matches!(new, Type::DiplomatOption(..));
+        let became_nullable = new.is_option();
+        let borrowed_opaque = matches!(old, Type::Opaque(o) if !o.owner.is_owned());
+        // Accepting more values or returning fewer is fine for callers, unless the
+        // nullable type is spelled differently.
+        let source = match position {
+            _ if borrowed_opaque && self.borrowed_opaques_are_references => true,
+            Position::Param => !became_nullable,
+            Position::Return => became_nullable,
+        };
+        let desc = if became_nullable {
+            format!("{what} became nullable")
+        } else {
+            format!("{what} is no longer nullable")
+        };
+        self.push(item, desc, abi, source);
+    }
+
+    fn diff_trait(&mut self, old: &'tcx hir::TraitDef, new: &'tcx hir::TraitDef) {
+        let name = old.name.as_str();
+        for old_m in &old.methods {
+            let Some(ref method_name) = old_m.name else {
+                continue;
+            };
+            let item = format!("{name}::{method_name}");
+            match new.methods.iter().find(|m| m.name == old_m.name) {
+                Some(new_m) => {
+                    let old_sig = fmt_callback(self.old, old_m);
+                    let new_sig = fmt_callback(self.new, new_m);
+                    if old_sig != new_sig {
+                        self.push(
+                            item,
+                            format!("signature changed from `{old_sig}` to `{new_sig}`"),
+                            true,
+                            true,
+                        );
+                    }
+                }
+                None => self.push(item, "trait method removed", true, true),
+            }
+        }
+        if new.methods.len() > old.methods.len() {
+            // Trait methods are implemented by the foreign side
+            self.push(name, "trait method added", true, true);
+        } else if old
+            .methods
+            .iter()
+            .zip(&new.methods)
+            .any(|(o, n)| o.name != n.name)
+        {
+            // Trait vtables are laid out in declaration order
+            self.push(name, "trait methods reordered", true, false);
+        }
+    }
+}
+
+/// Format a type like [`fmt_type`], but with any outer nullability removed
+fn fmt_non_nullable<P: TyPosition>(tcx: &TypeContext, ty: &Type<P>) -> String {
+    match ty {
+        Type::DiplomatOption(inner) => fmt_type(tcx, inner),
+        Type::Opaque(o) => fmt_opaque_ptr(o.resolve(tcx).name.as_str(), o.owner.mutability()),
+        _ => fmt_type(tcx, ty),
+    }
+}
+
+fn fmt_opaque_ptr(name: &str, mutability: Option<Mutability>) -> String {
+    match mutability {
+        None => format!("Box<{name}>"),
+        Some(Mutability::Immutable) => format!("&{name}"),
+        Some(Mutability::Mutable) => format!("&mut {name}"),
+    }
+}
+
+fn fmt_kind(ty: TypeDef) -> &'static str {
+    match ty {
+        TypeDef::Struct(_) => "a struct",
+        TypeDef::OutStruct(_) => "an output-only struct",
+        TypeDef::Opaque(_) => "an opaque",
+        TypeDef::Enum(_) => "an enum",
+        _ => unreachable!("unknown AST/HIR variant"),
+    }
+}
+
+/// Format a type in a Rust-like syntax, ignoring lifetimes
+fn fmt_type<P: TyPosition>(tcx: &TypeContext, ty: &Type<P>) -> String {
+    match ty {
+        Type::Primitive(p) => p.as_str().into(),
+        Type::Opaque(o) => {
+            let ptr = fmt_opaque_ptr(o.resolve(tcx).name.as_str(), o.owner.mutability());
+            if o.is_optional() {
+                format!("Option<{ptr}>")
+            } else {
+                ptr
+            }
+        }
+        Type::Struct(s) => tcx.resolve_type(s.id()).name().to_string(),
+        Type::Enum(e) => e.resolve(tcx).name.to_string(),
+        Type::Slice(s) => fmt_slice(s),
+        Type::DiplomatOption(inner) => format!("DiplomatOption<{}>", fmt_type(tcx, inner)),
+        Type::ImplTrait(t) => format!("impl {}", tcx.resolve_trait(t.id()).name),
+        Type::Callback(c) => {
+            let params = c
+                .get_inputs()
+                .map(|params| {
+                    params
+                        .iter()
+                        .map(|p| fmt_type(tcx, &p.ty))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or_default();
+            match c.get_output_type() {
+                Ok(Some(out)) => format!("impl Fn({params}) -> {}", fmt_type(tcx, out)),
+                _ => format!("impl Fn({params})"),
+            }
+        }
+        Type::Read => "&mut DiplomatRead".into(),
+        _ => unreachable!("unknown AST/HIR variant"),
+    }
+}
+
+fn fmt_self_type(tcx: &TypeContext, ty: &SelfType) -> String {
+    match ty {
+        SelfType::Opaque(o) => {
+            fmt_opaque_ptr(o.resolve(tcx).name.as_str(), Some(o.owner.mutability))
+        }
+        SelfType::Struct(s) => s.resolve(tcx).name.to_string(),
+        SelfType::Enum(e) => e.resolve(tcx).name.to_string(),
+        _ => unreachable!("unknown AST/HIR variant"),
+    }
+}
+
+fn fmt_slice(slice: &hir::Slice) -> String {
+    let str_name = |encoding: &StringEncoding| match encoding {
+        StringEncoding::Utf8 => "str",
+        StringEncoding::UnvalidatedUtf8 => "DiplomatStr",
+        StringEncoding::UnvalidatedUtf16 => "DiplomatStr16",
+        _ => unreachable!("unknown AST/HIR variant"),
+    };
+    match slice {
+        hir::Slice::Str(Some(_), encoding) => format!("&{}", str_name(encoding)),
+        hir::Slice::Str(None, encoding) => format!("Box<{}>", str_name(encoding)),
+        hir::Slice::Primitive(Some(b), p) if b.mutability == Mutability::Mutable => {
+            format!("&mut [{}]", p.as_str())
+        }
+        hir::Slice::Primitive(Some(_), p) => format!("&[{}]", p.as_str()),
+        hir::Slice::Primitive(None, p) => format!("Box<[{}]>", p.as_str()),
+        hir::Slice::Strs(encoding) => format!("&[&{}]", str_name(encoding)),
+        _ => unreachable!("unknown AST/HIR variant"),
+    }
+}
+
+fn fmt_success(tcx: &TypeContext, success: &SuccessType) -> String {
+    match success {
+        SuccessType::Write => "DiplomatWrite".into(),
+        SuccessType::Unit => "()".into(),
+        SuccessType::OutType(o) => fmt_type(tcx, o),
+        _ => unreachable!("unknown AST/HIR variant"),
+    }
+}
+
+fn fmt_callback(tcx: &TypeContext, cb: &hir::Callback) -> String {
+    let params = cb
+        .params
+        .iter()
+        .map(|p| fmt_type(tcx, &p.ty))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let self_param = if cb.param_self.is_some() {
+        if params.is_empty() {
+            "&self"
+        } else {
+            "&self, "
+        }
+    } else {
+        ""
+    };
+    match *cb.output {
+        Some(ref out) => format!("fn({self_param}{params}) -> {}", fmt_type(tcx, out)),
+        None => format!("fn({self_param}{params})"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DiffContext;
+    use diplomat_core::hir::{self, TypeContext};
+    use proc_macro2::TokenStream;
+    use quote::quote;
+
+    fn new_tcx(tk_stream: TokenStream) -> TypeContext {
+        let file = syn::parse2::<syn::File>(tk_stream).expect("failed to parse item");
+        let mut attr_validator = hir::BasicAttributeValidator::new("cpp");
+        attr_validator.support = crate::cpp::attr_support();
+        TypeContext::from_syn(&file, attr_validator).expect("failed to lower")
+    }
+
+    fn run_diff(old: TokenStream, new: TokenStream, cpp: bool) -> Vec<String> {
+        let (old, new) = (new_tcx(old), new_tcx(new));
+        DiffContext::new(&old, &new, crate::cpp::attr_support(), false, cpp)
+            .run()
+            .iter()
+            .map(|c| c.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                struct Point {
+                    x: i32,
+                    y: i32,
+                }
+
+                enum Mode {
+                    A = 0,
+                    B = 1,
+                }
+
+                impl Opaque {
+                    pub fn removed(&self) {}
+                    pub fn lookup(&self, key: &Opaque) -> Option<Box<Opaque>> {
+                        unimplemented!()
+                    }
+                    pub fn point(&self, mode: Mode) -> Point {
+                        unimplemented!()
+                    }
+                }
+            }
+        };
+        let new = quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                struct Point {
+                    y: i32,
+                    x: i32,
+                }
+
+                enum Mode {
+                    A = 0,
+                    B = 2,
+                }
+
+                impl Opaque {
+                    pub fn lookup(&self, key: Option<&Opaque>) -> Option<Box<Opaque>> {
+                        unimplemented!()
+                    }
+                    pub fn point(&self, mode: Mode, scale: f64) -> Result<Point, ()> {
+                        unimplemented!()
+                    }
+                }
+            }
+        };
+
+        let changes = run_diff(old, new, false);
+        assert_eq!(
+            changes,
+            [
+                "[ABI-breaking] Point: field order changed",
+                "[ABI- and source-breaking] Opaque::removed: method removed",
+                "[compatible] Opaque::lookup: parameter `key` became nullable",
+                "[ABI- and source-breaking] Opaque::point: new required parameter `scale`",
+                "[ABI- and source-breaking] Opaque::point: method became fallible",
+                "[ABI-breaking] Mode::B: discriminant changed from 1 to 2",
+            ]
+        );
+    }
+    #[test]
+    fn test_diff_nullable_references() {
+        let old = quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                impl Opaque {
+                    pub fn lookup(&self, key: &Opaque) {}
+                }
+            }
+        };
+        let new = quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                impl Opaque {
+                    pub fn lookup(&self, key: Option<&Opaque>) {}
+                }
+            }
+        };
+
+        // In C++, `const Opaque&` becomes `const Opaque*`
+        assert_eq!(
+            run_diff(old.clone(), new.clone(), true),
+            ["[source-breaking] Opaque::lookup: parameter `key` became nullable"]
+        );
+        assert_eq!(
+            run_diff(old, new, false),
+            ["[compatible] Opaque::lookup: parameter `key` became nullable"]
+        );
+    }
+
+    #[test]
+    fn test_diff_disabled_methods() {
+        let old = quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                impl Opaque {
+                    #[diplomat::attr(*, disable)]
+                    pub fn hidden(&self) {}
+                    pub fn shown(&self) {}
+                }
+            }
+        };
+        let new = quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                impl Opaque {
+                    #[diplomat::attr(*, disable)]
+                    pub fn shown(&self) {}
+                }
+            }
+        };
+
+        assert_eq!(
+            run_diff(old, new, false),
+            ["[ABI- and source-breaking] Opaque::shown: method removed"]
+        );
+    }
+}
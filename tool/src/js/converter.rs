@@ -4,9 +4,9 @@
 use std::borrow::Cow;
 
 use diplomat_core::hir::{
-    self, borrowing_param::StructBorrowInfo, IntType, LifetimeEnv, Method, OpaqueOwner,
-    PrimitiveType, ReturnType, ReturnableStructDef, SelfType, StructPathLike, SuccessType,
-    TyPosition, Type,
+    self, borrowing_param::StructBorrowInfo, IntSizeType, IntType, LifetimeEnv, Method,
+    OpaqueOwner, OutType, PrimitiveType, ReturnType, ReturnableStructDef, SelfType, StructPathLike,
+    SuccessType, TyPosition, Type, WriteEncoding,
 };
 use std::fmt::Write;
 
@@ -67,10 +67,7 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
             Type::Primitive(primitive) => self.formatter.fmt_primitive_as_ffi(primitive).into(),
             Type::Opaque(ref op) => {
                 let opaque_id = op.tcx_id.into();
-                let type_name = self.formatter.fmt_type_name(opaque_id);
-
-                // Add to the import list:
-                self.add_import(type_name.clone().into());
+                let type_name = self.import_type(opaque_id);
 
                 if self.tcx.resolve_type(opaque_id).attrs().disable {
                     self.errors
@@ -85,10 +82,7 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
             }
             Type::Struct(ref st) => {
                 let id = st.id();
-                let type_name = self.formatter.fmt_type_name(id);
-
-                // Add to the import list:
-                self.add_import(type_name.clone().into());
+                let type_name = self.import_type(id);
 
                 if self.tcx.resolve_type(id).attrs().disable {
                     self.errors
@@ -98,10 +92,7 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
             }
             Type::Enum(ref enumerator) => {
                 let enum_id = enumerator.tcx_id.into();
-                let type_name = self.formatter.fmt_type_name(enum_id);
-
-                // Add to the import list:
-                self.add_import(type_name.clone().into());
+                let type_name = self.import_type(enum_id);
 
                 if self.tcx.resolve_type(enum_id).attrs().disable {
                     self.errors
@@ -114,6 +105,7 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                 self.formatter.fmt_primitive_list_type(p).into()
             }
             Type::Slice(hir::Slice::Strs(..)) => "Array<string>".into(),
+            Type::Read => "Uint8Array | Iterable<Uint8Array>".into(),
             Type::DiplomatOption(ref inner) => {
                 let inner = self.gen_js_type_str(inner);
                 // This is suboptimal for struct fields; we should instead be using optional fields,
@@ -145,7 +137,7 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
             Type::Primitive(..) => variable_name,
             Type::Opaque(ref op) => {
                 let type_id = op.tcx_id.into();
-                let type_name = self.formatter.fmt_type_name(type_id);
+                let type_name = self.import_type(type_id);
 
                 let mut edges = if let Some(lt) = op.owner.lifetime() {
                     match lt {
@@ -153,7 +145,7 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                             .formatter
                             .fmt_lifetime_edge_array(lt, lifetime_environment)
                             .into_owned(),
-                        _ => panic!("'static not supported for JS backend"),
+                        _ => unreachable!("'static lifetimes are rejected during lowering"),
                     }
                 } else {
                     "[]".into()
@@ -168,13 +160,14 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                                 .fmt_lifetime_edge_array(lt, lifetime_environment)
                         )
                         .unwrap(),
-                        _ => panic!("'static not supported for JS backend"),
+                        _ => unreachable!("'static lifetimes are rejected during lowering"),
                     }
                 }
 
                 if op.is_optional() {
                     format!(
-                        "{variable_name} === 0 ? null : new {type_name}(diplomatRuntime.internalConstructor, {variable_name}, {edges})"
+                        "{variable_name} === {null} ? null : new {type_name}(diplomatRuntime.internalConstructor, {variable_name}, {edges})",
+                        null = self.formatter.fmt_ptr_int(0)
                     )
                     .into()
                 } else {
@@ -185,12 +178,17 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                 let inner_deref = self.gen_c_to_js_deref_for_type(inner, "offset".into(), 0);
                 let inner_conversion =
                     self.gen_c_to_js_for_type(inner, "deref".into(), lifetime_environment);
-                let size = crate::js::layout::type_size_alignment(inner, self.tcx).size();
+                let size = crate::js::layout::type_size_alignment(
+                    inner,
+                    self.tcx,
+                    self.formatter.pointer_width(),
+                )
+                .size();
                 format!("diplomatRuntime.readOption(wasm, {variable_name}, {size}, (wasm, offset) => {{ const deref = {inner_deref}; return {inner_conversion} }})").into()
             }
             Type::Struct(ref st) => {
                 let id = st.id();
-                let type_name = self.formatter.fmt_type_name(id);
+                let type_name = self.import_type(id);
                 let mut edges = String::new();
                 for lt in st.lifetimes().lifetimes() {
                     match lt {
@@ -198,7 +196,7 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                             write!(edges, ", {}Edges", lifetime_environment.fmt_lifetime(lt))
                                 .unwrap()
                         }
-                        _ => panic!("'static not supported for JS backend"),
+                        _ => unreachable!("'static lifetimes are rejected during lowering"),
                     }
                 }
 
@@ -221,20 +219,12 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
             }
             Type::Enum(ref enum_path) => {
                 let id = enum_path.tcx_id.into();
-                let type_name = self.formatter.fmt_type_name(id);
+                let type_name = self.import_type(id);
                 format!("new {type_name}(diplomatRuntime.internalConstructor, {variable_name})")
                     .into()
             }
             Type::Slice(slice) => {
-                let edges = match slice.lifetime() {
-                    Some(lt) => {
-                        let hir::MaybeStatic::NonStatic(lifetime) = lt else {
-                            panic!("'static not supported for JS backend");
-                        };
-                        format!("{}Edges", lifetime_environment.fmt_lifetime(lifetime))
-                    }
-                    _ => "[]".into(),
-                };
+                let edges = self.gen_slice_edges(&slice, lifetime_environment);
 
                 // Slices are always returned to us by way of pointers, so we assume that we can just access DiplomatReceiveBuf's helper functions:
                 match slice {
@@ -274,6 +264,61 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
         }
     }
 
+    /// The lifetime edges to keep alive for a slice returned from Rust.
+    ///
+    /// `'static` slices don't borrow anything that could be freed, so they don't need any.
+    fn gen_slice_edges(&self, slice: &hir::Slice, lifetime_environment: &LifetimeEnv) -> String {
+        match slice.lifetime() {
+            Some(hir::MaybeStatic::NonStatic(lifetime)) => {
+                format!("{}Edges", lifetime_environment.fmt_lifetime(lifetime))
+            }
+            _ => "[]".into(),
+        }
+    }
+
+    /// Whether `ty` is a borrowed slice of numbers, which is returned from methods as a typed array viewing WebAssembly memory,
+    /// instead of being copied into an array.
+    fn is_slice_view<P: hir::TyPosition>(ty: &Type<P>) -> bool {
+        matches!(
+            ty,
+            Type::Slice(hir::Slice::Primitive(
+                Some(_),
+                PrimitiveType::Byte
+                    | PrimitiveType::Int(..)
+                    | PrimitiveType::IntSize(..)
+                    | PrimitiveType::Float(..)
+            ))
+        )
+    }
+
+    /// Like [`Self::gen_js_type_str`], but for types returned from methods.
+    fn gen_js_out_type_str(&self, o: &OutType) -> Cow<'tcx, str> {
+        match o {
+            Type::Slice(hir::Slice::Primitive(_, p)) if Self::is_slice_view(o) => {
+                self.formatter.fmt_primitive_slice(*p).into()
+            }
+            _ => self.gen_js_type_str(o),
+        }
+    }
+
+    /// Like [`Self::gen_c_to_js_for_type`], but for types returned from methods.
+    fn gen_c_to_js_for_out_type(
+        &self,
+        o: &OutType,
+        variable_name: Cow<'tcx, str>,
+        lifetime_environment: &LifetimeEnv,
+    ) -> Cow<'tcx, str> {
+        match o {
+            Type::Slice(slice @ hir::Slice::Primitive(_, p)) if Self::is_slice_view(o) => format!(
+                r#"new diplomatRuntime.DiplomatSlicePrimitive(wasm, {variable_name}, "{}", {}).getView()"#,
+                self.formatter.fmt_primitive_list_view(*p),
+                self.gen_slice_edges(slice, lifetime_environment)
+            )
+            .into(),
+            _ => self.gen_c_to_js_for_type(o, variable_name, lifetime_environment),
+        }
+    }
+
     /// If we have a type that's hidden behind a pointer, de-reference that pointer in JS. Meant to be used in conjunction with [`Self::gen_c_to_js_for_type`].
     ///
     /// See [`super::FieldInfo::c_to_js_deref`] for an example of this.
@@ -286,7 +331,7 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
         let pointer = if offset == 0 {
             variable_name
         } else {
-            format!("{variable_name} + {offset}").into()
+            format!("{variable_name} + {}", self.formatter.fmt_ptr_int(offset)).into()
         };
         match *ty {
             Type::Enum(..) => format!("diplomatRuntime.enumDiscriminant(wasm, {pointer})").into(),
@@ -295,8 +340,9 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
             // See NestedBorrowedFields
             Type::Struct(..) | Type::Slice(..) | Type::DiplomatOption(..) => pointer,
             Type::Primitive(p) => format!(
-                "(new {ctor}(wasm.memory.buffer, {pointer}, 1))[0]{cmp}",
+                "(new {ctor}(wasm.memory.buffer, {index}, 1))[0]{cmp}",
                 ctor = self.formatter.fmt_primitive_slice(p),
+                index = self.formatter.fmt_ptr_as_index(&pointer),
                 cmp = match p {
                     PrimitiveType::Bool => " === 1",
                     _ => "",
@@ -324,7 +370,9 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
 
             // Anything we get returned that is not a [`SuccessType::Write`].
             ReturnType::Infallible(SuccessType::OutType(ref o))
-            | ReturnType::Fallible(SuccessType::OutType(ref o), Some(_)) => self.gen_js_type_str(o),
+            | ReturnType::Fallible(SuccessType::OutType(ref o), Some(_)) => {
+                self.gen_js_out_type_str(o)
+            }
 
             // Nullable string (no error on return).
             ReturnType::Fallible(SuccessType::Write, None)
@@ -342,14 +390,61 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
 
             // A nullable out type. Something like `MyStruct?` in Typescript.
             ReturnType::Fallible(SuccessType::OutType(ref o), None)
-            | ReturnType::Nullable(SuccessType::OutType(ref o)) => {
-                self.formatter.fmt_nullable(&self.gen_js_type_str(o)).into()
-            }
+            | ReturnType::Nullable(SuccessType::OutType(ref o)) => self
+                .formatter
+                .fmt_nullable(&self.gen_js_out_type_str(o))
+                .into(),
 
             _ => unreachable!("AST/HIR variant {:?} unknown.", return_type),
         }
     }
 
+    /// Allocates the buffer a method returning [`SuccessType::Write`] writes to, returning the
+    /// expression that reads the string out of it.
+    fn gen_write_buf(
+        method_info: &mut super::gen::MethodInfo,
+        encoding: WriteEncoding,
+    ) -> &'static str {
+        let (buf_class, read) = match encoding {
+            WriteEncoding::Utf16 => ("DiplomatWrite16Buf", "write.readString16()"),
+            _ => ("DiplomatWriteBuf", "write.readString8()"),
+        };
+        method_info
+            .alloc_expressions
+            .push(format!("const write = new diplomatRuntime.{buf_class}(wasm);").into());
+        method_info.param_conversions.push("write.buffer".into());
+        method_info.cleanup_expressions.push("write.free();".into());
+        read
+    }
+
+    /// Allocates the writable the `stream` variant of a method returning [`SuccessType::Write`]
+    /// writes to, which passes the output to the `sink` parameter as it is written. Returns the
+    /// statement rethrowing any error thrown by the sink.
+    pub(super) fn gen_stream_write(method_info: &mut super::gen::MethodInfo) -> Cow<'static, str> {
+        method_info
+            .alloc_expressions
+            .push("const write = new diplomatRuntime.DiplomatStreamWrite(wasm, sink);".into());
+        method_info.param_conversions.push("write.buffer".into());
+        method_info.cleanup_expressions.push("write.free();".into());
+        "write.finish();".into()
+    }
+
+    /// Allocates the `DiplomatRead` a [`Type::Read`] parameter is passed as, which reads from the
+    /// parameter's `Uint8Array` or chunks. Returns the expression passing it to Rust.
+    pub(super) fn gen_reader(
+        method_info: &mut super::gen::MethodInfo,
+        name: &str,
+    ) -> Cow<'static, str> {
+        method_info.alloc_expressions.push(
+            format!("const {name}Reader = new diplomatRuntime.DiplomatReader(wasm, {name});")
+                .into(),
+        );
+        method_info
+            .cleanup_expressions
+            .push(format!("{name}Reader.free();").into());
+        format!("{name}Reader.buffer").into()
+    }
+
     /// Give us pure JS for returning types.
     /// This basically handles the conversions from whatever the WASM gives us to a JS-friendly type.
     /// We access [`super::MethodInfo`] to handle allocation and cleanup.
@@ -369,12 +464,8 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
             ReturnType::Infallible(SuccessType::Unit) => None,
 
             ReturnType::Infallible(SuccessType::Write) => {
-                method_info
-                    .alloc_expressions
-                    .push("const write = new diplomatRuntime.DiplomatWriteBuf(wasm);".into());
-                method_info.param_conversions.push("write.buffer".into());
-                method_info.cleanup_expressions.push("write.free();".into());
-                Some("return write.readString8();".into())
+                let read = Self::gen_write_buf(method_info, method.write_encoding);
+                Some(format!("return {read};").into())
             }
 
             // Any out that is not a [`SuccessType::Write`].
@@ -382,7 +473,11 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                 let mut result = "result";
                 match o {
                     Type::Struct(_) | Type::Slice(_) => {
-                        let layout = crate::js::layout::type_size_alignment(o, self.tcx);
+                        let layout = crate::js::layout::type_size_alignment(
+                            o,
+                            self.tcx,
+                            self.formatter.pointer_width(),
+                        );
                         let size = layout.size();
                         let align = layout.align();
 
@@ -401,10 +496,19 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                     }
                     _ => (),
                 }
+                let null_check = match o {
+                    // `new` cannot return null, so constructors throw instead
+                    Type::Opaque(op) if op.is_optional() && method_info.is_constructor => format!(
+                        "if (result === {}) {{\n    throw new globalThis.Error('Failed to construct {}');\n}}\n",
+                        self.formatter.fmt_ptr_int(0),
+                        self.type_name
+                    ),
+                    _ => String::new(),
+                };
                 Some(
                     format!(
-                        "return {};",
-                        self.gen_c_to_js_for_type(o, result.into(), &method.lifetime_env)
+                        "{null_check}return {};",
+                        self.gen_c_to_js_for_out_type(o, result.into(), &method.lifetime_env)
                     )
                     .into(),
                 )
@@ -417,20 +521,15 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
             // Result<Write, ()> or Option<Write>.
             ReturnType::Fallible(SuccessType::Write, None)
             | ReturnType::Nullable(SuccessType::Write) => {
-                method_info
-                    .alloc_expressions
-                    .push("const write = new diplomatRuntime.DiplomatWriteBuf(wasm);".into());
-                method_info.param_conversions.push("write.buffer".into());
-                method_info.cleanup_expressions.push("write.free();".into());
-                Some("return result === 0 ? null : write.readString8();".into())
+                let read = Self::gen_write_buf(method_info, method.write_encoding);
+                Some(format!("return result === 0 ? null : {read};").into())
             }
 
             // Result<Type, Error> or Option<Type>
             ReturnType::Fallible(ref ok, _) | ReturnType::Nullable(ref ok) => {
                 let (requires_buf, error_ret) = match return_type {
                     ReturnType::Fallible(s, Some(e)) => {
-                        let type_name = self.formatter.fmt_type_name(e.id().unwrap());
-                        self.add_import(type_name.into());
+                        self.import_type(e.id().unwrap());
 
                         let fields_empty = matches!(e, Type::Struct(s) if match s.resolve(self.tcx) {
                                 ReturnableStructDef::Struct(s) => s.fields.is_empty(),
@@ -469,6 +568,18 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                         },
                         ))
                     }
+                    // `new` cannot return null, so constructors throw instead
+                    ReturnType::Nullable(_) | ReturnType::Fallible(_, None)
+                        if method_info.is_constructor =>
+                    {
+                        (
+                            true,
+                            format!(
+                                "throw new globalThis.Error('Failed to construct {}')",
+                                self.type_name
+                            ),
+                        )
+                    }
                     ReturnType::Nullable(_) | ReturnType::Fallible(_, None) => {
                         (true, "return null".into())
                     }
@@ -476,16 +587,24 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                 };
 
                 let layout = match ok {
-                    SuccessType::Unit => crate::js::layout::unit_size_alignment(),
-                    SuccessType::OutType(ref o) => {
-                        crate::js::layout::type_size_alignment(o, self.tcx)
+                    SuccessType::Unit => {
+                        crate::js::layout::unit_size_alignment(self.formatter.pointer_width())
                     }
+                    SuccessType::OutType(ref o) => crate::js::layout::type_size_alignment(
+                        o,
+                        self.tcx,
+                        self.formatter.pointer_width(),
+                    ),
                     SuccessType::Write => match return_type {
                         ReturnType::Fallible(_, ref err) if err.is_some() => {
-                            crate::js::layout::type_size_alignment(&err.clone().unwrap(), self.tcx)
+                            crate::js::layout::type_size_alignment(
+                                &err.clone().unwrap(),
+                                self.tcx,
+                                self.formatter.pointer_width(),
+                            )
                         }
                         ReturnType::Fallible(_, None) | ReturnType::Nullable(_) => {
-                            crate::js::layout::unit_size_alignment()
+                            crate::js::layout::unit_size_alignment(self.formatter.pointer_width())
                         }
                         _ => unreachable!("AST/HIR variant {:?} unknown.", return_type),
                     },
@@ -497,8 +616,12 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                     match return_type {
                         // We already account for an error in the Write match up above:
                         ReturnType::Fallible(_, e) if e.is_some() => {
-                            crate::js::layout::type_size_alignment(&e.clone().unwrap(), self.tcx)
-                                .size()
+                            crate::js::layout::type_size_alignment(
+                                &e.clone().unwrap(),
+                                self.tcx,
+                                self.formatter.pointer_width(),
+                            )
+                            .size()
                         }
                         _ => 0,
                     },
@@ -534,12 +657,8 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                     match ok {
                         SuccessType::Unit => err_check,
                         SuccessType::Write => {
-                            method_info.alloc_expressions.push(
-                                "const write = new diplomatRuntime.DiplomatWriteBuf(wasm);".into(),
-                            );
-                            method_info.param_conversions.push("write.buffer".into());
-                            method_info.cleanup_expressions.push("write.free();".into());
-                            format!("{err_check}return write.readString8();")
+                            let read = Self::gen_write_buf(method_info, method.write_encoding);
+                            format!("{err_check}return {read};")
                         }
                         SuccessType::OutType(ref o) => {
                             let ptr_deref = self.gen_c_to_js_deref_for_type(
@@ -549,7 +668,7 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                             );
                             format!(
                                 "{err_check}return {};",
-                                self.gen_c_to_js_for_type(o, ptr_deref, &method.lifetime_env)
+                                self.gen_c_to_js_for_out_type(o, ptr_deref, &method.lifetime_env)
                             )
                         }
                         _ => unreachable!("AST/HIR variant {:?} unknown.", return_type),
@@ -591,14 +710,14 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
         match *ty {
             Type::Primitive(p) => self.maybe_wrap_in_write(js_name, gen_context, p),
             Type::Opaque(ref op) if op.is_optional() => self.maybe_wrap_in_write(
-                format!("{js_name}.ffiValue ?? 0").into(),
+                format!("{js_name}.ffiValue ?? {}", self.formatter.fmt_ptr_int(0)).into(),
                 gen_context,
-                PrimitiveType::Int(IntType::U32),
+                PrimitiveType::IntSize(IntSizeType::Usize),
             ),
             Type::Opaque(..) => self.maybe_wrap_in_write(
                 format!("{js_name}.ffiValue").into(),
                 gen_context,
-                PrimitiveType::Int(IntType::U32),
+                PrimitiveType::IntSize(IntSizeType::Usize),
             ),
             Type::Enum(..) => self.maybe_wrap_in_write(
                 format!("{js_name}.ffiValue").into(),
@@ -612,7 +731,11 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                 gen_context,
             ),
             Type::DiplomatOption(ref inner) => {
-                let layout = crate::js::layout::type_size_alignment(inner, self.tcx);
+                let layout = crate::js::layout::type_size_alignment(
+                    inner,
+                    self.tcx,
+                    self.formatter.pointer_width(),
+                );
                 let size = layout.size();
                 let align = layout.align();
                 let inner_conversion = self.gen_js_to_c_for_type(
@@ -642,53 +765,53 @@ impl<'jsctx, 'tcx> TyGenContext<'jsctx, 'tcx> {
                 }
             }
             Type::Slice(slice) => {
-                if let Some(hir::MaybeStatic::Static) = slice.lifetime() {
-                    panic!("'static not supported for JS backend.")
+                // `'static` slices are never freed, so they are leaked instead of being allocated
+                // in an arena.
+                let alloc = if let Some(hir::MaybeStatic::Static) = slice.lifetime() {
+                    "diplomatRuntime.leakingArena"
                 } else {
-                    let alloc = alloc.expect(
+                    alloc.expect(
                         "Must provide some allocation anchor for slice conversion generation!",
-                    );
+                    )
+                };
 
-                    let (spread_pre, spread_post) = match gen_context {
-                        // SlicePreAlloc just wants the DiplomatBufe
-                        JsToCConversionContext::SlicePrealloc => ("", Cow::Borrowed("")),
-                        // List mode wants a list of (ptr, len)
-                        JsToCConversionContext::List(_) => ("...", ".splat()".into()),
-                        // WriteToBuffer needs to write to buffer arrayBuffer
-                        JsToCConversionContext::WriteToBuffer(offset_var, offset) => (
-                            "",
-                            format!(
-                                ".writePtrLenToArrayBuffer(arrayBuffer, {offset_var} + {offset})"
-                            )
+                let (spread_pre, spread_post) = match gen_context {
+                    // SlicePreAlloc just wants the DiplomatBufe
+                    JsToCConversionContext::SlicePrealloc => ("", Cow::Borrowed("")),
+                    // List mode wants a list of (ptr, len)
+                    JsToCConversionContext::List(_) => ("...", ".splat()".into()),
+                    // WriteToBuffer needs to write to buffer arrayBuffer
+                    JsToCConversionContext::WriteToBuffer(offset_var, offset) => (
+                        "",
+                        format!(".writePtrLenToArrayBuffer(arrayBuffer, {offset_var} + {offset})")
                             .into(),
-                        ),
-                    };
-
-                    match slice {
-                        hir::Slice::Str(_, encoding) => match encoding {
-                            hir::StringEncoding::UnvalidatedUtf8
-                            | hir::StringEncoding::Utf8 => {
-                                format!("{spread_pre}{alloc}.alloc(diplomatRuntime.DiplomatBuf.str8(wasm, {js_name})){spread_post}")
-                            }
-                            _ => {
-                                format!("{spread_pre}{alloc}.alloc(diplomatRuntime.DiplomatBuf.str16(wasm, {js_name})){spread_post}")
-                            }
-                        },
-                        hir::Slice::Strs(encoding) => format!(
-                            r#"{spread_pre}{alloc}.alloc(diplomatRuntime.DiplomatBuf.strs(wasm, {js_name}, "{}")){spread_post}"#,
-                            match encoding {
-                                hir::StringEncoding::UnvalidatedUtf16 => "string16",
-                                _ => "string8",
-                            }
-                        ),
-                        hir::Slice::Primitive(_, p) => format!(
-                            r#"{spread_pre}{alloc}.alloc(diplomatRuntime.DiplomatBuf.slice(wasm, {js_name}, "{}")){spread_post}"#,
-                            self.formatter.fmt_primitive_list_view(p)
-                        ),
-                        _ => unreachable!("Unknown Slice variant {ty:?}"),
-                    }
-                    .into()
+                    ),
+                };
+
+                match slice {
+                    hir::Slice::Str(_, encoding) => match encoding {
+                        hir::StringEncoding::UnvalidatedUtf8
+                        | hir::StringEncoding::Utf8 => {
+                            format!("{spread_pre}{alloc}.alloc(diplomatRuntime.DiplomatBuf.str8(wasm, {js_name})){spread_post}")
+                        }
+                        _ => {
+                            format!("{spread_pre}{alloc}.alloc(diplomatRuntime.DiplomatBuf.str16(wasm, {js_name})){spread_post}")
+                        }
+                    },
+                    hir::Slice::Strs(encoding) => format!(
+                        r#"{spread_pre}{alloc}.alloc(diplomatRuntime.DiplomatBuf.strs(wasm, {js_name}, "{}")){spread_post}"#,
+                        match encoding {
+                            hir::StringEncoding::UnvalidatedUtf16 => "string16",
+                            _ => "string8",
+                        }
+                    ),
+                    hir::Slice::Primitive(_, p) => format!(
+                        r#"{spread_pre}{alloc}.alloc(diplomatRuntime.DiplomatBuf.slice(wasm, {js_name}, "{}")){spread_post}"#,
+                        self.formatter.fmt_primitive_list_view(p)
+                    ),
+                    _ => unreachable!("Unknown Slice variant {ty:?}"),
                 }
+                .into()
             }
             _ => unreachable!("Unknown AST/HIR variant {ty:?}"),
         }
@@ -2,11 +2,12 @@
 //!
 //! Used in [`super::type_generation`] and [`crate::demo_gen`].
 use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 
 use diplomat_core::hir::{self, Docs, DocsUrlGenerator, EnumVariant, TypeContext, TypeId};
 use heck::{ToLowerCamelCase, ToUpperCamelCase};
 
-use super::FileType;
+use super::{FileType, PointerWidth};
 
 /// Javascript words that a Diplomat user shouldn't create classes or functions from.
 const RESERVED: &[&str] = &[
@@ -60,11 +61,45 @@ pub(crate) struct JSFormatter<'tcx> {
 
     /// For generating doc.rs links
     docs_url_gen: &'tcx DocsUrlGenerator,
+
+    /// Decides how `isize`, `usize` and pointers are represented in JS.
+    pointer_width: PointerWidth,
+
+    /// Names of types that are declared in more than one namespace.
+    clashing_names: BTreeSet<Cow<'tcx, str>>,
 }
 
 impl<'tcx> JSFormatter<'tcx> {
-    pub fn new(tcx: &'tcx TypeContext, docs_url_gen: &'tcx DocsUrlGenerator) -> Self {
-        Self { tcx, docs_url_gen }
+    pub fn new(
+        tcx: &'tcx TypeContext,
+        docs_url_gen: &'tcx DocsUrlGenerator,
+        pointer_width: PointerWidth,
+    ) -> Self {
+        let mut formatter = Self {
+            tcx,
+            docs_url_gen,
+            pointer_width,
+            clashing_names: BTreeSet::new(),
+        };
+        let mut namespaces = BTreeMap::<_, BTreeSet<_>>::new();
+        for (id, ty) in tcx.all_types() {
+            if !ty.attrs().disable {
+                namespaces
+                    .entry(formatter.fmt_type_name(id))
+                    .or_default()
+                    .insert(ty.attrs().namespace.as_deref());
+            }
+        }
+        formatter.clashing_names = namespaces
+            .into_iter()
+            .filter(|(_, namespaces)| namespaces.len() > 1)
+            .map(|(name, _)| name)
+            .collect();
+        formatter
+    }
+
+    pub fn pointer_width(&self) -> PointerWidth {
+        self.pointer_width
     }
 
     /// Given a [`TypeId`] that we're reading, make sure to rename it appropriately, or throw an error if it's reserved.
@@ -96,6 +131,73 @@ impl<'tcx> JSFormatter<'tcx> {
         }
     }
 
+    /// The directory containing the files for `id`, relative to the root of the bindings.
+    ///
+    /// Types in a namespace `a::b` are generated in `a/b/`.
+    pub fn fmt_namespace_dir(&self, id: TypeId) -> String {
+        match self.tcx.resolve_type(id).attrs().namespace {
+            Some(ref ns) => format!("{}/", ns.replace("::", "/")),
+            None => String::new(),
+        }
+    }
+
+    /// The path to the root of the bindings from the files for `id`.
+    pub fn fmt_path_to_root(&self, id: TypeId) -> String {
+        match self.tcx.resolve_type(id).attrs().namespace {
+            Some(ref ns) => "../".repeat(ns.split("::").count()),
+            None => "./".into(),
+        }
+    }
+
+    /// The path to import the file for `id` from, relative to the files for `from`.
+    ///
+    /// Types from other bridge crates are imported from the bindings generated for that crate,
+    /// which are expected to be in a sibling directory named after the crate.
+    pub fn fmt_import_relative_path(&self, id: TypeId, from: TypeId) -> String {
+        let up = match self.fmt_path_to_root(from) {
+            root if root == "./" => String::new(),
+            root => root,
+        };
+        let dir = self.fmt_namespace_dir(id);
+        match self.tcx.external_crate(id) {
+            Some(krate) => format!("{up}../{krate}/{dir}"),
+            None if up.is_empty() => format!("./{dir}"),
+            None => format!("{up}{dir}"),
+        }
+    }
+
+    /// The name a type is imported under if its name is also used in another namespace,
+    /// e.g. `a_b_Foo` for `a::b::Foo` and `root_Foo` for `Foo` in the root namespace.
+    pub fn fmt_type_alias(&self, id: TypeId) -> Option<String> {
+        let name = self.fmt_type_name(id);
+        if !self.clashing_names.contains(&name) {
+            return None;
+        }
+        Some(match self.tcx.resolve_type(id).attrs().namespace {
+            Some(ref ns) => format!("{}_{name}", ns.replace("::", "_")),
+            None => format!("root_{name}"),
+        })
+    }
+
+    /// The path of a type from the root of the bindings, e.g. `a.b.Foo` for `a::b::Foo`,
+    /// as namespaces are exported as nested modules.
+    pub fn fmt_type_path(&self, id: TypeId) -> String {
+        let name = self.fmt_type_name(id);
+        match self.tcx.resolve_type(id).attrs().namespace {
+            Some(ref ns) => format!("{}.{name}", ns.replace("::", ".")),
+            None => name.into(),
+        }
+    }
+
+    /// The name to import from the root of the bindings to use `id`: its outermost namespace,
+    /// or the type itself if it is not namespaced.
+    pub fn fmt_type_import_name(&self, id: TypeId) -> String {
+        match self.tcx.resolve_type(id).attrs().namespace {
+            Some(ref ns) => ns.split("::").next().unwrap_or_default().into(),
+            None => self.fmt_type_name(id).into(),
+        }
+    }
+
     /// Just creates `/** */` doc strings.
     pub fn fmt_docs(&self, docs: &Docs) -> String {
         docs.to_markdown(self.docs_url_gen)
@@ -108,12 +210,17 @@ impl<'tcx> JSFormatter<'tcx> {
     pub fn fmt_module_statement(
         &self,
         type_name: &str,
+        alias: Option<&str>,
         typescript: bool,
         relative_path: String,
     ) -> String {
         let file_name = self.fmt_file_name_extensionless(type_name);
+        let binding = match alias {
+            Some(alias) => format!("{type_name} as {alias}"),
+            None => type_name.into(),
+        };
         format!(
-            r#"{{ {type_name} }} from "{relative_path}{file_name}{}"#,
+            r#"{{ {binding} }} from "{relative_path}{file_name}{}"#,
             match typescript {
                 true => "",
                 false => ".mjs",
@@ -125,6 +232,7 @@ impl<'tcx> JSFormatter<'tcx> {
     pub fn fmt_import_statement(
         &self,
         type_name: &str,
+        alias: Option<&str>,
         typescript: bool,
         relative_path: String,
     ) -> String {
@@ -134,7 +242,7 @@ impl<'tcx> JSFormatter<'tcx> {
                 true => "type ",
                 false => "",
             },
-            self.fmt_module_statement(type_name, typescript, relative_path)
+            self.fmt_module_statement(type_name, alias, typescript, relative_path)
         )
     }
 
@@ -161,16 +269,47 @@ impl<'tcx> JSFormatter<'tcx> {
     ) -> String {
         format!(
             r#"export {}""#,
-            self.fmt_module_statement(type_name, typescript, relative_path)
+            self.fmt_module_statement(type_name, None, typescript, relative_path)
+        )
+    }
+
+    /// Re-export the `index` of the namespace directory `namespace` as the module `namespace`.
+    pub fn fmt_namespace_export_statement(&self, namespace: &str, typescript: bool) -> String {
+        format!(
+            r#"export * as {namespace} from "./{namespace}/index{}""#,
+            match typescript {
+                true => "",
+                false => ".mjs",
+            }
         )
     }
 
     // #region HIR::Type formatting.
     // This is only visible for Typescript definition files, but we use it to check if types are supported.
 
+    /// `isize` and `usize` are represented like the integer type of the same width, which depends on [`PointerWidth`].
+    fn fixed_width(&self, primitive: hir::PrimitiveType) -> hir::PrimitiveType {
+        use hir::{IntSizeType, IntType, PrimitiveType};
+        match (primitive, self.pointer_width) {
+            (PrimitiveType::IntSize(IntSizeType::Isize), PointerWidth::Bits32) => {
+                PrimitiveType::Int(IntType::I32)
+            }
+            (PrimitiveType::IntSize(IntSizeType::Usize), PointerWidth::Bits32) => {
+                PrimitiveType::Int(IntType::U32)
+            }
+            (PrimitiveType::IntSize(IntSizeType::Isize), PointerWidth::Bits64) => {
+                PrimitiveType::Int(IntType::I64)
+            }
+            (PrimitiveType::IntSize(IntSizeType::Usize), PointerWidth::Bits64) => {
+                PrimitiveType::Int(IntType::U64)
+            }
+            (p, _) => p,
+        }
+    }
+
     /// Generate a JS primitive type from a Rust type.
     pub fn fmt_primitive_as_ffi(&self, primitive: hir::PrimitiveType) -> &'static str {
-        match primitive {
+        match self.fixed_width(primitive) {
             hir::PrimitiveType::Bool => "boolean",
             hir::PrimitiveType::Char => "codepoint",
             hir::PrimitiveType::Int(hir::IntType::I64 | hir::IntType::U64)
@@ -184,7 +323,7 @@ impl<'tcx> JSFormatter<'tcx> {
 
     /// Generate a JS primitive slice type from a Rust type.
     pub fn fmt_primitive_slice(&self, primitive_type: hir::PrimitiveType) -> &'static str {
-        match primitive_type {
+        match self.fixed_width(primitive_type) {
             hir::PrimitiveType::Bool
             | hir::PrimitiveType::Byte
             | hir::PrimitiveType::Int(hir::IntType::U8) => "Uint8Array",
@@ -200,15 +339,15 @@ impl<'tcx> JSFormatter<'tcx> {
             hir::PrimitiveType::Int(hir::IntType::U64) => "BigUint64Array",
             hir::PrimitiveType::Float(hir::FloatType::F32) => "Float32Array",
             hir::PrimitiveType::Float(hir::FloatType::F64) => "Float64Array",
-            hir::PrimitiveType::Int128(..) => {
-                panic!("Int128 slices are not a supported type for the JS backend.")
+            hir::PrimitiveType::Int128(_) => {
+                unreachable!("128-bit slices are rejected during lowering")
             }
         }
     }
 
     /// Generates a JS primitive list type from a Rust type.
     pub fn fmt_primitive_list_type(&self, primitive: hir::PrimitiveType) -> &'static str {
-        match primitive {
+        match self.fixed_width(primitive) {
             hir::PrimitiveType::Bool => "Array<boolean>",
             hir::PrimitiveType::Char => "Array<codepoint>",
             hir::PrimitiveType::Byte => "Uint8Array",
@@ -217,14 +356,14 @@ impl<'tcx> JSFormatter<'tcx> {
             | hir::PrimitiveType::IntSize(_)
             | hir::PrimitiveType::Float(_) => "Array<number>",
             hir::PrimitiveType::Int128(_) => {
-                panic!("Int128 slices are not a supported type for the JS backend.")
+                unreachable!("128-bit slices are rejected during lowering")
             }
         }
     }
 
     /// We generate this in JS as a string that we then have to parse in `runtime.mjs`. Used we're trying to determine how to parse a given slice. See `DiplomatBuf` for more.
     pub fn fmt_primitive_list_view(&self, primitive: hir::PrimitiveType) -> &'static str {
-        match primitive {
+        match self.fixed_width(primitive) {
             hir::PrimitiveType::Bool => "boolean",
             hir::PrimitiveType::Char => "u16",
             hir::PrimitiveType::Byte => "u8",
@@ -245,6 +384,22 @@ impl<'tcx> JSFormatter<'tcx> {
         }
     }
 
+    /// Format an integer that is added to or compared with a pointer. Pointers are `bigint`s on wasm64.
+    pub fn fmt_ptr_int(&self, n: usize) -> String {
+        match self.pointer_width {
+            PointerWidth::Bits32 => n.to_string(),
+            PointerWidth::Bits64 => format!("{n}n"),
+        }
+    }
+
+    /// Format a pointer as a byte offset into `wasm.memory.buffer`, which has to be a `number`.
+    pub fn fmt_ptr_as_index<'a>(&self, ptr: &'a str) -> Cow<'a, str> {
+        match self.pointer_width {
+            PointerWidth::Bits32 => ptr.into(),
+            PointerWidth::Bits64 => format!("Number({ptr})").into(),
+        }
+    }
+
     pub fn fmt_void(&self) -> &'static str {
         "void"
     }
@@ -272,6 +427,16 @@ impl<'tcx> JSFormatter<'tcx> {
         }
     }
 
+    /// The name of the variant of a method marked `stream` that passes its output to a sink, such as `toStringTo`.
+    pub fn fmt_stream_method_name(&self, method: &hir::Method) -> String {
+        let name: String = method
+            .attrs
+            .rename
+            .apply(method.name.as_str().into())
+            .to_lower_camel_case();
+        format!("{name}To")
+    }
+
     /// For formatting a JS method that has an associated name with it. Like a named constructor or getter/setter.
     pub fn fmt_method_field_name(&self, name: &Option<String>, method: &hir::Method) -> String {
         let name: String = method
@@ -17,6 +17,7 @@ use diplomat_core::hir::{
 use askama::{self, Template};
 
 use super::formatter::JSFormatter;
+use crate::templates::{ClassHookVars, MethodHookVars, MethodHooks, TemplateOverrides};
 use crate::ErrorStore;
 
 use super::converter::{ForcePaddingStatus, JsToCConversionContext, StructBorrowContext};
@@ -26,11 +27,13 @@ use super::converter::{ForcePaddingStatus, JsToCConversionContext, StructBorrowC
 /// Given an enum, opaque, struct, etc. (anything from [`hir::TypeDef`] that JS supports), this handles creation of the associated `.mjs`` files.
 pub(super) struct TyGenContext<'ctx, 'tcx> {
     pub tcx: &'tcx TypeContext,
+    pub id: TypeId,
     pub type_name: Cow<'tcx, str>,
     pub formatter: &'ctx JSFormatter<'tcx>,
     pub errors: &'ctx ErrorStore<'tcx, String>,
-    /// Imports, stored as a type name. Imports are fully resolved in [`TyGenContext::generate_base`], with a call to [`JSFormatter::fmt_import_statement`].
-    pub imports: RefCell<BTreeSet<String>>,
+    pub templates: &'ctx TemplateOverrides,
+    /// Imports, stored as a type ID. Imports are fully resolved in [`TyGenContext::generate_base`], with a call to [`JSFormatter::fmt_import_statement`].
+    pub imports: RefCell<BTreeSet<TypeId>>,
 }
 
 impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
@@ -44,20 +47,38 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             body: String,
             typescript: bool,
             imports: Vec<String>,
+            root_path: String,
         }
 
+        let mut imports = self
+            .imports
+            .borrow()
+            .iter()
+            .map(|id| {
+                (
+                    self.formatter.fmt_type_name(*id),
+                    self.formatter.fmt_type_alias(*id),
+                    *id,
+                )
+            })
+            .collect::<Vec<_>>();
+        imports.sort();
+
         let mut new_imports = Vec::new();
-        for import in self.imports.borrow().iter() {
-            new_imports.push(
-                self.formatter
-                    .fmt_import_statement(import, typescript, "./".into()),
-            );
+        for (type_name, alias, id) in imports {
+            new_imports.push(self.formatter.fmt_import_statement(
+                &type_name,
+                alias.as_deref(),
+                typescript,
+                self.formatter.fmt_import_relative_path(id, self.id),
+            ));
         }
 
         BaseTemplate {
             body,
             typescript,
             imports: new_imports,
+            root_path: self.formatter.fmt_path_to_root(self.id),
         }
         .render()
         .unwrap()
@@ -66,15 +87,44 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
     /// A wrapper for `borrow_mut`ably inserting new imports.
     ///
     /// I do this to avoid borrow checking madness.
-    pub(super) fn add_import(&self, import_str: String) {
-        self.imports.borrow_mut().insert(import_str);
+    pub(super) fn add_import(&self, id: TypeId) {
+        self.imports.borrow_mut().insert(id);
     }
 
-    /// Exists for the same reason as [`Self::add_import`].
+    /// Import the type `id`, and return the name it is referenced by in this file.
+    /// The type of this file is never imported.
     ///
-    /// Right now, only used for removing any self imports.
-    pub(super) fn remove_import(&self, import_str: String) {
-        self.imports.borrow_mut().remove(&import_str);
+    /// Types whose names are used in several namespaces are imported under an alias,
+    /// see [`JSFormatter::fmt_type_alias`].
+    pub(super) fn import_type(&self, id: TypeId) -> Cow<'tcx, str> {
+        if id == self.id {
+            return self.formatter.fmt_type_name(id);
+        }
+        self.add_import(id);
+        match self.formatter.fmt_type_alias(id) {
+            Some(alias) => alias.into(),
+            None => self.formatter.fmt_type_name(id),
+        }
+    }
+
+    /// Render the user's `class_prologue` hook for this type. Only needed in `.mjs` files.
+    fn class_prologue(
+        &self,
+        typescript: bool,
+        namespace: Option<&str>,
+        kind: &'static str,
+    ) -> Option<String> {
+        if typescript {
+            return None;
+        }
+        self.templates.class_prologue(
+            &ClassHookVars {
+                type_name: &self.type_name,
+                namespace,
+                kind,
+            },
+            self.errors,
+        )
     }
 
     /// Generate an enumerator type's body for a file from the given definition.
@@ -101,6 +151,7 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             is_contiguous: bool,
 
             doc_str: String,
+            class_prologue: Option<String>,
 
             methods: &'a MethodsInfo<'a>,
         }
@@ -112,6 +163,11 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             typescript,
 
             doc_str: self.formatter.fmt_docs(&enum_def.docs),
+            class_prologue: self.class_prologue(
+                typescript,
+                enum_def.attrs.namespace.as_deref(),
+                "enum",
+            ),
             is_contiguous,
 
             methods,
@@ -140,6 +196,7 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             destructor: &'a str,
 
             docs: String,
+            class_prologue: Option<String>,
 
             methods: &'a MethodsInfo<'a>,
         }
@@ -152,6 +209,11 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             destructor,
 
             docs: self.formatter.fmt_docs(&opaque_def.docs),
+            class_prologue: self.class_prologue(
+                typescript,
+                opaque_def.attrs.namespace.as_deref(),
+                "opaque",
+            ),
 
             methods,
         }
@@ -171,8 +233,11 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
         &self,
         struct_def: &'tcx hir::StructDef<P>,
     ) -> (Vec<FieldInfo<P>>, bool) {
-        let struct_field_info =
-            crate::js::layout::struct_field_info(struct_def.fields.iter().map(|f| &f.ty), self.tcx);
+        let struct_field_info = crate::js::layout::struct_field_info(
+            struct_def.fields.iter().map(|f| &f.ty),
+            self.tcx,
+            self.formatter.pointer_width(),
+        );
         let mut needs_force_padding = false;
 
         let fields = struct_def.fields.iter().enumerate()
@@ -192,10 +257,8 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             );
 
             let alloc = if let &hir::Type::Slice(slice) = &field.ty {
-                if let Some(lt) = slice.lifetime() {
-                    let hir::MaybeStatic::NonStatic(lt) = lt else {
-                        panic!("'static not supported in JS backend");
-                    };
+                // `'static` slices are leaked rather than allocated in an arena.
+                if let Some(hir::MaybeStatic::NonStatic(lt)) = slice.lifetime() {
                     let lt_name = struct_def.lifetimes.fmt_lifetime(lt);
                     Some(
                         format!("diplomatRuntime.CleanupArena.maybeCreateWith(functionCleanupArena, ...appendArrayMap['{lt_name}AppendArray'])")
@@ -203,7 +266,7 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
                 } else {
                     None
                 }
-            } else if let &hir::Type::Struct(..) = &field.ty {
+            } else if let hir::Type::Struct(..) = field.ty.unwrap_option() {
                 Some("functionCleanupArena".into())
             } else {
                 // We take ownership
@@ -322,6 +385,7 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             methods: &'a MethodsInfo<'a>,
 
             docs: String,
+            class_prologue: Option<String>,
         }
 
         ImplTemplate {
@@ -337,6 +401,11 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             methods,
 
             docs: self.formatter.fmt_docs(&struct_def.docs),
+            class_prologue: self.class_prologue(
+                typescript,
+                struct_def.attrs.namespace.as_deref(),
+                "struct",
+            ),
         }
         .render()
         .unwrap()
@@ -349,6 +418,7 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
         &self,
         type_id: TypeId,
         method: &'tcx Method,
+        stream: bool,
     ) -> Option<MethodInfo> {
         if method.attrs.disable {
             return None;
@@ -370,6 +440,15 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             ..Default::default()
         };
 
+        // `new Enum(value)` already looks up variants, so enum constructors remain static methods.
+        let is_enum = matches!(self.tcx.resolve_type(type_id), hir::TypeDef::Enum(..));
+        method_info.is_constructor = !stream
+            && !is_enum
+            && matches!(
+                method.attrs.special_method,
+                Some(SpecialMethod::Constructor)
+            );
+
         if let Some(param_self) = method.param_self.as_ref() {
             visitor.visit_param(&param_self.ty.clone().into(), "this");
 
@@ -383,6 +462,8 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             }
         }
 
+        let mut readers = Vec::new();
+
         for param in method.params.iter() {
             let param_info = ParamInfo {
                 name: self.formatter.fmt_param_name(param.name.as_str()),
@@ -425,6 +506,10 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
                     name: param_info.name.clone(),
                     slice_expr: slice_expr.to_string(),
                 });
+            } else if let hir::Type::Read = param.ty {
+                let reader = Self::gen_reader(&mut method_info, &param_info.name);
+                method_info.param_conversions.push(reader);
+                readers.push(param_info.name.clone());
             } else {
                 let alloc = if let hir::Type::Struct(..) = param.ty.unwrap_option() {
                     method_info.needs_slice_cleanup = true;
@@ -458,14 +543,59 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             method_info.parameters.push(param_info);
         }
 
-        method_info.return_type = format!(": {}", self.gen_js_return_type_str(&method.output));
+        if stream {
+            // The output goes to `sink` instead of being returned
+            method_info.parameters.push(ParamInfo {
+                name: "sink".into(),
+                ty: "(chunk: Uint8Array) => boolean | void".into(),
+            });
+            method_info.return_type = ": void".into();
+            method_info.return_expression = Some(Self::gen_stream_write(&mut method_info));
+        } else {
+            method_info.return_type = format!(": {}", self.gen_js_return_type_str(&method.output));
+
+            method_info.return_expression =
+                self.gen_c_to_js_for_return_type(&mut method_info, method);
+        }
 
-        method_info.return_expression = self.gen_c_to_js_for_return_type(&mut method_info, method);
+        // Errors thrown by the sources of readers are rethrown once Rust is done reading
+        if !readers.is_empty() {
+            let finish = readers
+                .iter()
+                .map(|name| format!("{name}Reader.finish();"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            method_info.return_expression = Some(match method_info.return_expression.take() {
+                Some(ret) => format!("{finish}\n{ret}").into(),
+                None => finish.into(),
+            });
+        }
 
         method_info.method_lifetimes_map = visitor.borrow_map();
         method_info.lifetimes = Some(&method.lifetime_env);
 
         method_info.method_decl = match &method.attrs.special_method {
+            _ if stream => {
+                let name = self.formatter.fmt_stream_method_name(method);
+                if method.param_self.is_none() {
+                    format!("static {name}")
+                } else {
+                    name
+                }
+            }
+            _ if method_info.is_constructor => {
+                // Constructors cannot have return type annotations
+                method_info.return_type = Default::default();
+                "#defaultConstructor".to_string()
+            }
+            Some(SpecialMethod::NamedConstructor(name)) => {
+                format!(
+                    "static {}",
+                    self.formatter.fmt_method_field_name(name, method)
+                )
+            }
+            Some(SpecialMethod::Stringifier) => "toString".to_string(),
+            Some(SpecialMethod::Indexer) => "at".to_string(),
             Some(SpecialMethod::Getter(name)) => {
                 format!("get {}", self.formatter.fmt_method_field_name(name, method))
             }
@@ -483,6 +613,19 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             _ => self.formatter.fmt_method_name(method),
         };
 
+        method_info.hooks = self.templates.method_hooks(
+            &MethodHookVars {
+                type_name: &self.type_name,
+                method_name: method_info
+                    .method_decl
+                    .rsplit(' ')
+                    .next()
+                    .unwrap_or_default(),
+                abi_name: &method_info.abi_name,
+            },
+            self.errors,
+        );
+
         Some(method_info)
     }
 
@@ -490,8 +633,13 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
     /// We need to make sure Javascript can access it.
     ///
     /// This is mostly for iterators, using https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Iteration_protocols
+    ///
+    /// Comparators get a static `compare(a, b)` helper, usable with `Array.prototype.sort`, and indexable
+    /// types with a nullable indexer get a `[Symbol.iterator]` calling `at(i)` until it returns `null` (unless they
+    /// are already iterable).
     pub(super) fn generate_special_method(
         &self,
+        methods: &'tcx [Method],
         special_method_presence: &SpecialMethodPresence,
     ) -> SpecialMethodInfo {
         let mut iterator = None;
@@ -500,8 +648,29 @@ impl<'ctx, 'tcx> TyGenContext<'ctx, 'tcx> {
             iterator = Some(self.gen_success_ty(val))
         }
 
+        let enabled = || methods.iter().filter(|m| !m.attrs.disable);
+
+        let comparator = enabled()
+            .find(|m| matches!(m.attrs.special_method, Some(SpecialMethod::Comparison)))
+            .map(|m| self.formatter.fmt_method_name(m));
+
+        let indexer_item = enabled()
+            .find(|m| matches!(m.attrs.special_method, Some(SpecialMethod::Indexer)))
+            .filter(|_| special_method_presence.iterable.is_none())
+            .and_then(|m| match &m.output {
+                hir::ReturnType::Nullable(ok) => Some(self.gen_success_ty(ok)),
+                hir::ReturnType::Infallible(
+                    ok @ hir::SuccessType::OutType(hir::Type::Opaque(o)),
+                ) if o.is_optional() => Some(self.gen_success_ty(ok)),
+                // Iteration needs a null to stop at
+                _ => None,
+            });
+
         SpecialMethodInfo {
+            type_name: self.type_name.clone(),
             iterator,
+            comparator,
+            indexer_item,
             typescript: false,
         }
     }
@@ -569,13 +738,24 @@ pub(super) struct MethodInfo<'info> {
     pub alloc_expressions: Vec<Cow<'info, str>>,
     /// Anything from [`MethodInfo::alloc_expressions`] we need to clean up afterwards.
     pub cleanup_expressions: Vec<Cow<'info, str>>,
+
+    /// The user's method hooks, rendered for this method
+    pub hooks: MethodHooks,
+
+    /// Is this the method called by `new Type(...)`? Declared as `constructor` in `.d.ts` files.
+    pub is_constructor: bool,
 }
 
 /// See [`TyGenContext::generate_special_method`].
 #[derive(Template)]
 #[template(path = "js/iterator.js.jinja", escape = "none")]
 pub(super) struct SpecialMethodInfo<'a> {
+    type_name: Cow<'a, str>,
     iterator: Option<Cow<'a, str>>,
+    /// The name of the comparison method, used by the static `compare` helper.
+    comparator: Option<String>,
+    /// The item type of the indexer, if we generate `[Symbol.iterator]` for it.
+    indexer_item: Option<Cow<'a, str>>,
     pub typescript: bool,
 }
 
@@ -585,6 +765,13 @@ pub(super) struct MethodsInfo<'a> {
     pub special_methods: SpecialMethodInfo<'a>,
 }
 
+impl MethodsInfo<'_> {
+    /// Whether `new Type(...)` calls a Rust constructor.
+    pub fn has_constructor(&self) -> bool {
+        self.methods.iter().any(|m| m.is_constructor)
+    }
+}
+
 /// Represents a re-usable set of information for any [`hir::TypeDef::Struct`]s.
 #[derive(Clone)]
 pub(super) struct FieldInfo<'info, P: hir::TyPosition> {
@@ -655,12 +842,8 @@ fn iter_fields_with_lifetimes_from_set<'a, P: hir::TyPosition>(
         ty: &Type<P>,
         lifetime: &hir::Lifetime,
     ) -> bool {
-        ty.lifetimes().any(|lt| {
-            let hir::MaybeStatic::NonStatic(lt) = lt else {
-                panic!("'static not supported in JS backend");
-            };
-            lt == *lifetime
-        })
+        ty.lifetimes()
+            .any(|lt| matches!(lt, hir::MaybeStatic::NonStatic(lt) if lt == *lifetime))
     }
 
     fields
@@ -1,6 +1,8 @@
 //! The corpse of the old AST backend, wearing a fresh coat of paint. AST used to have this  `layout.rs` file for figuring out how types would look in memory.
 //!
 //! Every backend needed this. But now only Javascript does. And we pretty much only use it for structs; WASM sometimes requires us to create an appropriately sized buffer for a struct. It sometimes also requires us to pad method signatures when inserting a flattened structure (see [`super::type_generation::TyGenContext::gen_c_to_js_for_return_type`] or [`super::type_generation::TyGenContext::generate_fields`] for more).
+//!
+//! The C backend also uses it for the layout assertions in its struct headers, which check the C compiler's layout against the one `#[diplomat::bridge]` asserted for the Rust struct.
 use std::{alloc::Layout, cmp::max};
 
 use diplomat_core::hir::{
@@ -8,8 +10,27 @@ use diplomat_core::hir::{
     TypeContext,
 };
 
-// TODO(#58): support non-32-bit platforms
-use u32 as usize_target;
+/// The width of pointers and `usize` in the WebAssembly module we generate bindings for.
+///
+/// Set with `pointer-width = 64` in the library config, for libraries built for `wasm64` (memory64).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PointerWidth {
+    /// `wasm32`, where pointers are JS `number`s.
+    #[default]
+    Bits32,
+    /// `wasm64`, where pointers are JS `bigint`s.
+    Bits64,
+}
+
+impl PointerWidth {
+    /// The layout of pointers, `usize` and `isize`.
+    pub fn layout(self) -> Layout {
+        match self {
+            PointerWidth::Bits32 => Layout::new::<u32>(),
+            PointerWidth::Bits64 => Layout::new::<u64>(),
+        }
+    }
+}
 
 pub struct StructFieldLayout {
     /// The offset of this field in the struct
@@ -40,6 +61,7 @@ pub struct StructFieldsInfo {
 pub fn struct_field_info<'a, P: hir::TyPosition + 'a>(
     types: impl Iterator<Item = &'a Type<P>>,
     tcx: &'a TypeContext,
+    pointer_width: PointerWidth,
 ) -> StructFieldsInfo {
     let mut max_align = 0;
     let mut next_offset = 0;
@@ -50,14 +72,15 @@ pub fn struct_field_info<'a, P: hir::TyPosition + 'a>(
     if types.is_empty() {
         return StructFieldsInfo {
             fields: vec![],
-            struct_layout: unit_size_alignment(),
+            struct_layout: unit_size_alignment(pointer_width),
             scalar_count: 0,
         };
     }
 
     let mut prev_align = 1;
     for typ in types {
-        let (size_align, field_scalars) = type_size_alignment_and_scalar_count(typ, tcx);
+        let (size_align, field_scalars) =
+            type_size_alignment_and_scalar_count(typ, tcx, pointer_width);
         scalar_count += field_scalars;
         let size = size_align.size();
         let align = size_align.align();
@@ -114,47 +137,70 @@ pub fn struct_field_info<'a, P: hir::TyPosition + 'a>(
     }
 }
 
-pub fn opaque_size_alignment() -> Layout {
-    // TODO: Is this correct?
-    Layout::new::<usize_target>()
+/// The layout of a struct's fields with 32-bit and 64-bit pointers, in that order.
+///
+/// Used by backends that check the layout they declare a struct with against the one `#[diplomat::bridge]` asserts.
+pub fn struct_field_info_by_width<P: hir::TyPosition>(
+    def: &hir::StructDef<P>,
+    tcx: &TypeContext,
+) -> [StructFieldsInfo; 2] {
+    [PointerWidth::Bits32, PointerWidth::Bits64]
+        .map(|width| struct_field_info(def.fields.iter().map(|f| &f.ty), tcx, width))
+}
+
+pub fn opaque_size_alignment(pointer_width: PointerWidth) -> Layout {
+    pointer_width.layout()
 }
 
-pub fn unit_size_alignment() -> Layout {
+pub fn unit_size_alignment(pointer_width: PointerWidth) -> Layout {
     // TODO: Is this correct?
-    Layout::new::<usize_target>()
+    pointer_width.layout()
 }
 
 /// Get the [`Layout`] for a specific type.
-pub fn type_size_alignment<P: hir::TyPosition>(typ: &Type<P>, tcx: &TypeContext) -> Layout {
-    type_size_alignment_and_scalar_count(typ, tcx).0
+pub fn type_size_alignment<P: hir::TyPosition>(
+    typ: &Type<P>,
+    tcx: &TypeContext,
+    pointer_width: PointerWidth,
+) -> Layout {
+    type_size_alignment_and_scalar_count(typ, tcx, pointer_width).0
 }
 
 /// Get the [`Layout`] for a specific type, as well as the number of scalar fields it contains
 pub fn type_size_alignment_and_scalar_count<P: hir::TyPosition>(
     typ: &Type<P>,
     tcx: &TypeContext,
+    pointer_width: PointerWidth,
 ) -> (Layout, usize) {
     match typ {
-        // repr(C) fieldless enums use the default platform representation: isize
-        Type::Enum(..) => (Layout::new::<usize_target>(), 1),
-        Type::Opaque(..) => (opaque_size_alignment(), 1),
-        Type::Slice(..) => (Layout::new::<(usize_target, usize_target)>(), 2),
-        Type::Primitive(p) => (primitive_size_alignment(*p), 1),
+        // repr(C) fieldless enums use the platform's C `int`, which is 32 bits on both wasm32 and wasm64
+        Type::Enum(..) => (Layout::new::<i32>(), 1),
+        Type::Opaque(..) => (opaque_size_alignment(pointer_width), 1),
+        Type::Slice(..) => {
+            // (ptr, len)
+            let ptr = pointer_width.layout();
+            (
+                Layout::from_size_align(2 * ptr.size(), ptr.align()).unwrap(),
+                2,
+            )
+        }
+        Type::Primitive(p) => (primitive_size_alignment(*p, pointer_width), 1),
         Type::Struct(struct_path) => {
             let def = tcx.resolve_type(struct_path.id());
             let info = match def {
                 hir::TypeDef::OutStruct(out_struct) => {
-                    struct_field_info(out_struct.fields.iter().map(|f| &f.ty), tcx)
+                    struct_field_info(out_struct.fields.iter().map(|f| &f.ty), tcx, pointer_width)
                 }
                 hir::TypeDef::Struct(struct_def) => {
-                    struct_field_info(struct_def.fields.iter().map(|f| &f.ty), tcx)
+                    struct_field_info(struct_def.fields.iter().map(|f| &f.ty), tcx, pointer_width)
                 }
                 _ => panic!("Should be a struct TypeDef."),
             };
             (info.struct_layout, info.scalar_count)
         }
         Type::DiplomatOption(inner) => {
-            let (layout, inner_scalar) = type_size_alignment_and_scalar_count(inner, tcx);
+            let (layout, inner_scalar) =
+                type_size_alignment_and_scalar_count(inner, tcx, pointer_width);
             let size = layout.size();
             let align = layout.align();
             debug_assert!(size % align == 0, "Found inner type {typ:?} with size {size} that is not a multiple of its alignment {align}");
@@ -167,7 +213,7 @@ pub fn type_size_alignment_and_scalar_count<P: hir::TyPosition>(
 }
 
 /// Get the [`Layout`] for a specific [`PrimitiveType`].
-pub fn primitive_size_alignment(prim: PrimitiveType) -> Layout {
+pub fn primitive_size_alignment(prim: PrimitiveType, pointer_width: PointerWidth) -> Layout {
     match prim {
         PrimitiveType::Bool => Layout::new::<bool>(),
         PrimitiveType::Char => Layout::new::<char>(),
@@ -181,9 +227,209 @@ pub fn primitive_size_alignment(prim: PrimitiveType) -> Layout {
             Layout::new::<u128>()
         }
         PrimitiveType::IntSize(IntSizeType::Isize) | PrimitiveType::IntSize(IntSizeType::Usize) => {
-            Layout::new::<usize_target>()
+            pointer_width.layout()
         }
         PrimitiveType::Float(FloatType::F32) => Layout::new::<f32>(),
         PrimitiveType::Float(FloatType::F64) => Layout::new::<f64>(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use diplomat_core::hir::{self, TypeContext, TypeDef};
+    use quote::quote;
+
+    use super::{struct_field_info, struct_field_info_by_width, PointerWidth, StructFieldsInfo};
+
+    fn new_tcx(tk_stream: proc_macro2::TokenStream) -> TypeContext {
+        let file = syn::parse2::<syn::File>(tk_stream).expect("failed to parse item ");
+
+        let mut attr_validator = hir::BasicAttributeValidator::new("js_test");
+        attr_validator.support = super::super::attr_support();
+
+        match TypeContext::from_syn(&file, attr_validator) {
+            Ok(context) => context,
+            Err(e) => {
+                for (_cx, err) in e {
+                    eprintln!("Lowering error: {}", err);
+                }
+                panic!("Failed to create context")
+            }
+        }
+    }
+
+    fn field_info(tcx: &TypeContext, name: &str, pointer_width: PointerWidth) -> StructFieldsInfo {
+        let fields = tcx
+            .all_types()
+            .find_map(|(_, ty)| match ty {
+                TypeDef::Struct(s) if s.name.as_str() == name => {
+                    Some(s.fields.iter().map(|f| &f.ty).collect::<Vec<_>>())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("No struct named {name}"));
+        struct_field_info(fields.into_iter(), tcx, pointer_width)
+    }
+
+    fn out_field_info(
+        tcx: &TypeContext,
+        name: &str,
+        pointer_width: PointerWidth,
+    ) -> StructFieldsInfo {
+        let fields = tcx
+            .all_types()
+            .find_map(|(_, ty)| match ty {
+                TypeDef::OutStruct(s) if s.name.as_str() == name => {
+                    Some(s.fields.iter().map(|f| &f.ty).collect::<Vec<_>>())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("No out struct named {name}"));
+        struct_field_info(fields.into_iter(), tcx, pointer_width)
+    }
+
+    /// `(offset, padding_count, padding_field_width)` for every field.
+    fn offsets_and_padding(info: &StructFieldsInfo) -> Vec<(usize, usize, usize)> {
+        info.fields
+            .iter()
+            .map(|f| (f.offset, f.padding_count, f.padding_field_width))
+            .collect()
+    }
+
+    fn tcx() -> TypeContext {
+        new_tcx(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                pub enum Kind {
+                    A,
+                    B,
+                }
+
+                pub struct Mixed<'a> {
+                    a: u8,
+                    b: usize,
+                    c: DiplomatSlice<'a, u8>,
+                    d: u32,
+                    e: bool,
+                }
+
+                pub struct WithEnum {
+                    a: Kind,
+                    b: isize,
+                }
+
+                #[diplomat::out]
+                pub struct WithOpaque {
+                    a: u8,
+                    b: Box<Opaque>,
+                }
+
+                impl Opaque {
+                    pub fn mixed<'a>(m: Mixed<'a>) {}
+                    pub fn with_enum(w: WithEnum) {}
+                    pub fn with_opaque() -> WithOpaque {
+                        todo!()
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_struct_layout_32() {
+        let tcx = tcx();
+
+        let mixed = field_info(&tcx, "Mixed", PointerWidth::Bits32);
+        assert_eq!(
+            offsets_and_padding(&mixed),
+            [(0, 3, 1), (4, 0, 1), (8, 0, 1), (16, 0, 1), (20, 3, 1)]
+        );
+        assert_eq!(
+            (mixed.struct_layout.size(), mixed.struct_layout.align()),
+            (24, 4)
+        );
+        assert_eq!(mixed.scalar_count, 6);
+
+        let with_enum = field_info(&tcx, "WithEnum", PointerWidth::Bits32);
+        assert_eq!(offsets_and_padding(&with_enum), [(0, 0, 1), (4, 0, 1)]);
+        assert_eq!(
+            (
+                with_enum.struct_layout.size(),
+                with_enum.struct_layout.align()
+            ),
+            (8, 4)
+        );
+
+        let with_opaque = out_field_info(&tcx, "WithOpaque", PointerWidth::Bits32);
+        assert_eq!(offsets_and_padding(&with_opaque), [(0, 3, 1), (4, 0, 1)]);
+        assert_eq!(
+            (
+                with_opaque.struct_layout.size(),
+                with_opaque.struct_layout.align()
+            ),
+            (8, 4)
+        );
+    }
+
+    #[test]
+    fn test_struct_layout_64() {
+        let tcx = tcx();
+
+        let mixed = field_info(&tcx, "Mixed", PointerWidth::Bits64);
+        assert_eq!(
+            offsets_and_padding(&mixed),
+            [(0, 7, 1), (8, 0, 1), (16, 0, 1), (32, 0, 1), (36, 3, 1)]
+        );
+        assert_eq!(
+            (mixed.struct_layout.size(), mixed.struct_layout.align()),
+            (40, 8)
+        );
+        assert_eq!(mixed.scalar_count, 6);
+
+        // Enums are C `int`s, so they need 4 bytes of padding (as an i32) before an `isize`.
+        let with_enum = field_info(&tcx, "WithEnum", PointerWidth::Bits64);
+        assert_eq!(offsets_and_padding(&with_enum), [(0, 1, 4), (8, 0, 1)]);
+        assert_eq!(
+            (
+                with_enum.struct_layout.size(),
+                with_enum.struct_layout.align()
+            ),
+            (16, 8)
+        );
+
+        let with_opaque = out_field_info(&tcx, "WithOpaque", PointerWidth::Bits64);
+        assert_eq!(offsets_and_padding(&with_opaque), [(0, 7, 1), (8, 0, 1)]);
+        assert_eq!(
+            (
+                with_opaque.struct_layout.size(),
+                with_opaque.struct_layout.align()
+            ),
+            (16, 8)
+        );
+    }
+
+    #[test]
+    fn test_struct_layout_by_width() {
+        let tcx = tcx();
+        let mixed = tcx
+            .all_types()
+            .find_map(|(_, ty)| match ty {
+                TypeDef::Struct(s) if s.name.as_str() == "Mixed" => Some(s),
+                _ => None,
+            })
+            .unwrap();
+
+        let [info32, info64] = struct_field_info_by_width(mixed, &tcx);
+        for (info, pointer_width) in [
+            (info32, PointerWidth::Bits32),
+            (info64, PointerWidth::Bits64),
+        ] {
+            let expected = field_info(&tcx, "Mixed", pointer_width);
+            assert_eq!(offsets_and_padding(&info), offsets_and_padding(&expected));
+            assert_eq!(info.struct_layout, expected.struct_layout);
+        }
+    }
+}
@@ -1,13 +1,17 @@
 //! JS code generation backend.
 //!
 //! This module specifically handles the overview of generating all the necessary `.mjs` and `.d.ts` files that [`gen`] creates content for.
-use std::collections::BTreeSet;
-use std::{borrow::Cow, cell::RefCell};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
 
+use crate::templates::TemplateOverrides;
 use crate::{ErrorStore, FileMap};
 use diplomat_core::hir::{BackendAttrSupport, DocsUrlGenerator, TypeContext, TypeDef};
 
 use askama::Template;
+use colored::Colorize;
+use serde::Deserialize;
 
 pub(crate) mod formatter;
 use formatter::JSFormatter;
@@ -16,7 +20,61 @@ mod gen;
 use gen::{MethodsInfo, TyGenContext};
 mod converter;
 
-mod layout;
+pub(crate) mod layout;
+pub(crate) use layout::PointerWidth;
+
+/// Configuration for the JS backend, read from the `--library-config` file.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct JsConfig {
+    /// `32` (the default) for `wasm32` libraries, `64` for `wasm64` (memory64) libraries.
+    pointer_width: Option<u8>,
+    /// The keys not read into the fields above.
+    #[serde(flatten)]
+    other: BTreeMap<String, toml::Value>,
+}
+
+/// The keys of the library config that are read by something else than [`JsConfig`] when
+/// generating JS: the templates directory, and the [`DemoConfig`](crate::demo_gen::DemoConfig) that
+/// demo_gen passes on when it generates the JS bindings.
+const OTHER_CONFIG_KEYS: &[&str] = &[
+    "templates-dir",
+    "templates_dir",
+    "explicit-generation",
+    "hide-default-renderer",
+    "module-name",
+    "relative-js-path",
+    "static-html",
+    "wasm-path",
+    "cli",
+];
+
+impl JsConfig {
+    /// The keys of the config that nothing reads, most likely typos.
+    fn unknown_keys(&self) -> impl Iterator<Item = &str> {
+        self.other
+            .keys()
+            .map(String::as_str)
+            .filter(|key| !OTHER_CONFIG_KEYS.contains(key))
+    }
+}
+
+/// Reads the `pointer-width` option of the library config, for the JS backend and demo_gen.
+pub(crate) fn pointer_width_from_config(
+    bits: Option<u8>,
+    errors: &ErrorStore<'_, String>,
+) -> PointerWidth {
+    match bits {
+        None | Some(32) => PointerWidth::Bits32,
+        Some(64) => PointerWidth::Bits64,
+        Some(other) => {
+            errors.push_error(format!(
+                "Unsupported pointer-width {other} in the library config, expected 32 or 64"
+            ));
+            PointerWidth::Bits32
+        }
+    }
+}
 
 /// Since the main difference between .mjs and .d.ts is typing, we just want a differentiator for our various helper functions as to what's being generated: .d.ts, or .mjs?
 pub(crate) enum FileType {
@@ -36,23 +94,29 @@ impl FileType {
 pub(crate) fn attr_support() -> BackendAttrSupport {
     let mut a = BackendAttrSupport::default();
 
-    a.namespacing = false;
+    a.namespacing = true;
     a.memory_sharing = false;
     a.non_exhaustive_structs = true;
     a.method_overloading = false;
     a.utf8_strings = false;
     a.utf16_strings = true;
-    a.static_slices = false;
-
-    a.constructors = false;
-    a.named_constructors = false;
-    a.fallible_constructors = false;
+    a.static_slices = true;
+    a.static_borrows = false;
+    a.int128 = true;
+    a.int128_slices = false;
+    a.write16 = true;
+    a.reads = true;
+
+    a.constructors = true;
+    a.named_constructors = true;
+    a.fallible_constructors = true;
     a.accessors = true;
-    a.comparators = false;
-    a.stringifiers = false; // TODO
+    a.comparators = true;
+    a.stringifiers = true;
     a.iterators = true;
     a.iterables = true;
-    a.indexing = false;
+    a.indexing = true;
+    a.streaming_writes = true;
     a.callbacks = false;
     a.option = true;
     a.traits = false;
@@ -62,26 +126,80 @@ pub(crate) fn attr_support() -> BackendAttrSupport {
 
 pub(crate) fn run<'tcx>(
     tcx: &'tcx TypeContext,
+    conf_path: Option<&Path>,
     docs: &'tcx DocsUrlGenerator,
+    templates: &TemplateOverrides,
+    runtime_crate: Option<&str>,
 ) -> (FileMap, ErrorStore<'tcx, String>) {
-    let formatter = JSFormatter::new(tcx, docs);
     let errors = ErrorStore::default();
     let files = FileMap::default();
-    let mut exports = Vec::new();
-    let mut ts_exports = Vec::new();
+    // The `.mjs` and `.d.ts` exports of the `index` of each namespace directory, by namespace.
+    // Namespaces are exported from the `index` of their parent as nested modules.
+    let mut exports = BTreeMap::<String, (Vec<String>, Vec<String>)>::new();
+
+    let conf = conf_path
+        .map(|conf_path| {
+            let conf_str = std::fs::read_to_string(conf_path).unwrap_or_else(|err| {
+                panic!("Could not open config toml file: {conf_path:?} : {err}")
+            });
+            let conf = toml::from_str::<JsConfig>(&conf_str)
+                .unwrap_or_else(|err| panic!("Parsing error in {conf_path:?}: {err}"));
+            for key in conf.unknown_keys() {
+                eprintln!(
+                    "{}Unknown key `{key}` in {conf_path:?}, ignored by the JS backend",
+                    "Warning: ".yellow().bold()
+                );
+            }
+            conf
+        })
+        .unwrap_or_default();
+    let pointer_width = pointer_width_from_config(conf.pointer_width, &errors);
+    let formatter = JSFormatter::new(tcx, docs, pointer_width);
+
+    if let Some(krate) = runtime_crate {
+        // Bindings of different crates have to share one WebAssembly instance and runtime,
+        // so that pointers and `internalConstructor` are valid across them.
+        files.add_file(
+            "diplomat-runtime.mjs".into(),
+            format!("export * from \"../{krate}/diplomat-runtime.mjs\";\n"),
+        );
+        files.add_file(
+            "diplomat-runtime.d.ts".into(),
+            format!("export * from \"../{krate}/diplomat-runtime\";\n"),
+        );
+        files.add_file(
+            "diplomat-wasm.mjs".into(),
+            format!("export {{ default, wasmModule }} from \"../{krate}/diplomat-wasm.mjs\";\n"),
+        );
+    } else {
+        /// `diplomat-runtime.mjs`, which only depends on the pointer width.
+        #[derive(Template)]
+        #[template(path = "js/runtime.mjs.jinja", escape = "none")]
+        struct RuntimeTemplate {
+            /// In bytes.
+            pointer_width: usize,
+        }
 
-    files.add_file(
-        "diplomat-runtime.mjs".into(),
-        include_str!("../../templates/js/runtime.mjs").into(),
-    );
-    files.add_file(
-        "diplomat-runtime.d.ts".into(),
-        include_str!("../../templates/js/runtime.d.ts").into(),
-    );
-    files.add_file(
-        "diplomat-wasm.mjs".into(),
-        include_str!("../../templates/js/wasm.mjs").into(),
-    );
+        files.add_file(
+            "diplomat-runtime.mjs".into(),
+            RuntimeTemplate {
+                pointer_width: pointer_width.layout().size(),
+            }
+            .render()
+            .unwrap(),
+        );
+        files.add_file(
+            "diplomat-runtime.d.ts".into(),
+            include_str!("../../templates/js/runtime.d.ts").into(),
+        );
+        files.add_file(
+            "diplomat-wasm.mjs".into(),
+            include_str!("../../templates/js/wasm.mjs").into(),
+        );
+    }
+
+    // The size, alignment and field offsets of the structs, by Rust name, for `checkStructLayouts`
+    let mut struct_layouts = BTreeMap::<&str, Vec<usize>>::new();
 
     for (id, ty) in tcx.all_types() {
         let _guard = errors.set_context_ty(ty.name().as_str().into());
@@ -90,6 +208,11 @@ pub(crate) fn run<'tcx>(
             continue;
         }
 
+        if tcx.external_crate(id).is_some() {
+            // Imported from the other crate's bindings
+            continue;
+        }
+
         let type_def = tcx.resolve_type(id);
 
         let _guard = errors.set_context_ty(type_def.name().as_str().into());
@@ -98,9 +221,11 @@ pub(crate) fn run<'tcx>(
 
         let context = TyGenContext {
             tcx,
+            id,
             type_name,
             formatter: &formatter,
             errors: &errors,
+            templates,
             imports: RefCell::new(BTreeSet::new()),
         };
 
@@ -122,12 +247,44 @@ pub(crate) fn run<'tcx>(
             _ => unreachable!("HIR/AST variant {:?} is unknown.", type_def),
         };
 
+        let struct_info = match type_def {
+            TypeDef::Struct(s) if !s.fields.is_empty() => Some(layout::struct_field_info(
+                s.fields.iter().map(|f| &f.ty),
+                tcx,
+                pointer_width,
+            )),
+            TypeDef::OutStruct(s) if !s.fields.is_empty() => Some(layout::struct_field_info(
+                s.fields.iter().map(|f| &f.ty),
+                tcx,
+                pointer_width,
+            )),
+            _ => None,
+        };
+        if let Some(info) = struct_info {
+            struct_layouts.insert(
+                type_def.name().as_str(),
+                [info.struct_layout.size(), info.struct_layout.align()]
+                    .into_iter()
+                    .chain(info.fields.iter().map(|f| f.offset))
+                    .collect(),
+            );
+        }
+
         let mut methods_info = MethodsInfo {
             methods: m
                 .iter()
-                .flat_map(|method| context.generate_method(id, method))
+                .flat_map(|method| {
+                    let stream = method
+                        .attrs
+                        .stream
+                        .then(|| context.generate_method(id, method, true));
+                    context
+                        .generate_method(id, method, false)
+                        .into_iter()
+                        .chain(stream.flatten())
+                })
                 .collect::<Vec<_>>(),
-            special_methods: context.generate_special_method(special_method_presence),
+            special_methods: context.generate_special_method(m, special_method_presence),
         };
 
         for file_type in [FileType::Module, FileType::Typescript] {
@@ -152,45 +309,228 @@ pub(crate) fn run<'tcx>(
                 _ => unreachable!("HIR/AST variant {:?} is unknown.", type_def),
             };
 
-            let file_name = formatter.fmt_file_name(&context.type_name, &file_type);
-
-            // Remove our self reference:
-            context.remove_import(context.type_name.clone().into());
+            let file_name = format!(
+                "{}{}",
+                formatter.fmt_namespace_dir(id),
+                formatter.fmt_file_name(&context.type_name, &file_type)
+            );
 
             files.add_file(file_name, context.generate_base(ts, contents));
         }
 
-        exports.push(
-            formatter
-                .fmt_export_statement(&context.type_name, false, "./".into())
-                .into(),
-        );
-        ts_exports.push(
-            formatter
-                .fmt_export_statement(&context.type_name, true, "./".into())
-                .into(),
-        )
+        let namespace = type_def.attrs().namespace.clone().unwrap_or_default();
+        let (js_exports, ts_exports) = exports.entry(namespace).or_default();
+        js_exports.push(formatter.fmt_export_statement(&context.type_name, false, "./".into()));
+        ts_exports.push(formatter.fmt_export_statement(&context.type_name, true, "./".into()));
+    }
+
+    /// `diplomat-layout.mjs`, which checks the layouts the bindings use against the WebAssembly module.
+    #[derive(Template)]
+    #[template(path = "js/layout.mjs.jinja", escape = "none")]
+    struct LayoutTemplate<'a> {
+        struct_layouts: BTreeMap<&'a str, Vec<usize>>,
+    }
+
+    files.add_file(
+        "diplomat-layout.mjs".into(),
+        LayoutTemplate { struct_layouts }.render().unwrap(),
+    );
+    files.add_file(
+        "diplomat-layout.d.ts".into(),
+        include_str!("../../templates/js/layout.d.ts").into(),
+    );
+
+    exports.entry(String::new()).or_default();
+    let namespaces = exports.keys().cloned().collect::<Vec<_>>();
+    let mut namespace_exports = BTreeMap::<String, BTreeSet<&str>>::new();
+    for namespace in &namespaces {
+        let mut child = namespace.as_str();
+        while let Some(parent) = child.rsplit_once("::").map(|(parent, _)| parent) {
+            namespace_exports
+                .entry(parent.into())
+                .or_default()
+                .insert(child);
+            child = parent;
+        }
+        if !child.is_empty() {
+            namespace_exports
+                .entry(String::new())
+                .or_default()
+                .insert(child);
+        }
+    }
+    for (parent, children) in namespace_exports {
+        let (js_exports, ts_exports) = exports.entry(parent).or_default();
+        for child in children {
+            let name = child.rsplit("::").next().unwrap_or(child);
+            js_exports.push(formatter.fmt_namespace_export_statement(name, false));
+            ts_exports.push(formatter.fmt_namespace_export_statement(name, true));
+        }
     }
 
     /// Represents the `index.mjs` file that `export`s all classes that we generate.
     #[derive(Template)]
     #[template(path = "js/index.js.jinja", escape = "none")]
     struct IndexTemplate<'a> {
-        exports: &'a Vec<Cow<'a, str>>,
+        exports: &'a [String],
         typescript: bool,
+        is_root: bool,
     }
 
-    let mut out_index = IndexTemplate {
-        exports: &exports,
-        typescript: false,
-    };
+    for (namespace, (js_exports, ts_exports)) in exports {
+        let dir = if namespace.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", namespace.replace("::", "/"))
+        };
 
-    files.add_file("index.mjs".into(), out_index.render().unwrap());
+        let mut out_index = IndexTemplate {
+            exports: &js_exports,
+            typescript: false,
+            is_root: dir.is_empty(),
+        };
 
-    out_index.typescript = true;
-    out_index.exports = &ts_exports;
+        files.add_file(format!("{dir}index.mjs"), out_index.render().unwrap());
 
-    files.add_file("index.d.ts".into(), out_index.render().unwrap());
+        out_index.typescript = true;
+        out_index.exports = &ts_exports;
+
+        files.add_file(format!("{dir}index.d.ts"), out_index.render().unwrap());
+    }
 
     (files, errors)
 }
+
+#[cfg(test)]
+mod test {
+    use diplomat_core::hir::{self, TypeContext};
+    use quote::quote;
+
+    #[test]
+    fn test_unknown_config_keys() {
+        let conf =
+            toml::from_str::<super::JsConfig>("pointer_width = 64\ntemplates-dir = \"templates\"")
+                .unwrap();
+        assert_eq!(conf.pointer_width, None);
+        assert_eq!(conf.unknown_keys().collect::<Vec<_>>(), ["pointer_width"]);
+
+        // demo_gen generates the JS bindings with its own config
+        let demo_conf = crate::demo_gen::DemoConfig {
+            explicit_generation: Some(true),
+            hide_default_renderer: Some(true),
+            module_name: Some("lib".into()),
+            relative_js_path: Some("../js".into()),
+            static_html: Some(true),
+            wasm_path: Some("lib.wasm".into()),
+            cli: Some(true),
+            pointer_width: Some(64),
+        };
+        let conf =
+            toml::from_str::<super::JsConfig>(&toml::to_string(&demo_conf).unwrap()).unwrap();
+        assert_eq!(conf.pointer_width, Some(64));
+        assert_eq!(conf.unknown_keys().count(), 0);
+    }
+
+    #[test]
+    fn test_wasm64() {
+        let file = syn::parse2::<syn::File>(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                pub struct Wrapper<'a> {
+                    opaque: &'a Opaque,
+                    len: u32,
+                }
+
+                impl Opaque {
+                    pub fn new() -> Box<Opaque> {
+                        unimplemented!()
+                    }
+
+                    pub fn name(&self, write: &mut DiplomatWrite) {
+                        unimplemented!()
+                    }
+
+                    pub fn next(&self) -> Option<Box<Opaque>> {
+                        unimplemented!()
+                    }
+
+                    pub fn wrap<'a>(&'a self) -> Wrapper<'a> {
+                        unimplemented!()
+                    }
+                }
+            }
+        })
+        .unwrap();
+        let mut attr_validator = hir::BasicAttributeValidator::new("js");
+        attr_validator.support = super::attr_support();
+        let tcx = TypeContext::from_syn(&file, attr_validator).expect("failed to lower");
+        let docs = hir::DocsUrlGenerator::with_base_urls(None, Default::default());
+
+        let conf = std::env::temp_dir().join(format!("diplomat-js-{}.toml", std::process::id()));
+        std::fs::write(&conf, "pointer-width = 64").unwrap();
+        let (files, errors) = super::run(&tcx, Some(&conf), &docs, &Default::default(), None);
+        std::fs::remove_file(&conf).unwrap();
+        assert!(errors.take_all().is_empty());
+        let files = files.take_files();
+
+        assert!(files["diplomat-runtime.mjs"].contains("export const POINTER_WIDTH = 8;"));
+        // `len` after an 8-byte pointer, padded to the alignment of the pointer
+        assert!(files["diplomat-layout.mjs"].contains(r#""Wrapper": [16, 8, 0, 8],"#));
+        insta::assert_snapshot!(files["Opaque.mjs"]);
+        insta::assert_snapshot!(files["Wrapper.mjs"]);
+    }
+
+    #[test]
+    fn test_extern_runtime() {
+        let other = syn::parse2::<syn::File>(quote! {
+            #[diplomat::bridge]
+            pub mod ffi {
+                #[diplomat::opaque]
+                pub struct Locale;
+            }
+        })
+        .unwrap();
+        let file = syn::parse2::<syn::File>(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                use other_ffi::ffi::Locale;
+
+                #[diplomat::opaque]
+                struct Formatter;
+
+                impl Formatter {
+                    pub fn new(locale: &Locale) -> Box<Formatter> {
+                        unimplemented!()
+                    }
+                }
+            }
+        })
+        .unwrap();
+        let mut attr_validator = hir::BasicAttributeValidator::new("js");
+        attr_validator.support = super::attr_support();
+        let tcx =
+            TypeContext::from_syn_with_externs(&file, &[("other_ffi", &other)], attr_validator)
+                .expect("failed to lower");
+        let docs = hir::DocsUrlGenerator::with_base_urls(None, Default::default());
+
+        let (files, errors) = super::run(&tcx, None, &docs, &Default::default(), Some("other_ffi"));
+        assert!(errors.take_all().is_empty());
+        let files = files.take_files();
+
+        assert!(!files.contains_key("Locale.mjs"));
+        assert!(
+            files["Formatter.mjs"].contains(r#"import { Locale } from "../other_ffi/Locale.mjs""#)
+        );
+        assert_eq!(
+            files["diplomat-runtime.mjs"],
+            "export * from \"../other_ffi/diplomat-runtime.mjs\";\n"
+        );
+        assert_eq!(
+            files["diplomat-wasm.mjs"],
+            "export { default, wasmModule } from \"../other_ffi/diplomat-wasm.mjs\";\n"
+        );
+    }
+}
@@ -0,0 +1,1036 @@
+//! JSON API description backend.
+//!
+//! Rather than generating bindings, this backend serializes the lowered [`TypeContext`]
+//! into a single `api.json` file so that tools not written in Rust (documentation portals,
+//! API review tooling, etc.) can consume the full bridge surface.
+//!
+//! The format is described in `docs/json_api.md`. Any change to the shape of the output
+//! must bump [`SCHEMA_VERSION`].
+
+use crate::{ErrorStore, FileMap};
+use diplomat_core::hir::{
+    self, BackendAttrSupport, CallbackInstantiationFunctionality, DocsUrlGenerator, LifetimeEnv,
+    MaybeStatic, Mutability, OpaqueOwner, ReturnType, SelfType, SpecialMethod, StringEncoding,
+    StructPathLike, SuccessType, TraitIdGetter, TyPosition, TypeContext, TypeDef, TypeId,
+    WriteEncoding,
+};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// The version of the JSON schema emitted by this backend.
+pub(crate) const SCHEMA_VERSION: u32 = 1;
+
+pub(crate) fn attr_support() -> BackendAttrSupport {
+    let mut a = BackendAttrSupport::default();
+
+    // The JSON description can represent everything the HIR can, so nothing is
+    // rejected here; consumers are expected to check what they can handle.
+    a.namespacing = true;
+    a.memory_sharing = true;
+    a.non_exhaustive_structs = true;
+    a.method_overloading = true;
+    a.utf8_strings = true;
+    a.utf16_strings = true;
+    a.static_slices = true;
+    a.static_borrows = true;
+    a.int128 = true;
+    a.int128_slices = true;
+    a.write16 = true;
+    a.reads = true;
+
+    a.constructors = true;
+    a.named_constructors = true;
+    a.fallible_constructors = true;
+    a.accessors = true;
+    a.stringifiers = true;
+    a.comparators = true;
+    a.iterators = true;
+    a.iterables = true;
+    a.indexing = true;
+    a.streaming_writes = true;
+    a.option = true;
+    a.callbacks = true;
+    a.traits = true;
+
+    a
+}
+
+pub(crate) fn run<'tcx>(
+    tcx: &'tcx TypeContext,
+    docs_url_gen: &'tcx DocsUrlGenerator,
+) -> (FileMap, ErrorStore<'tcx, String>) {
+    let files = FileMap::default();
+    let errors = ErrorStore::default();
+
+    let cx = JsonContext { tcx, docs_url_gen };
+
+    let api = Api {
+        schema_version: SCHEMA_VERSION,
+        types: tcx
+            .all_types()
+            .filter(|(id, ty)| !ty.attrs().disable && tcx.external_crate(*id).is_none())
+            .map(|(id, ty)| cx.gen_type_def(id, ty))
+            .collect(),
+        traits: tcx
+            .all_traits()
+            .filter(|(id, trt)| !trt.attrs.disable && tcx.external_crate(*id).is_none())
+            .map(|(_, trt)| cx.gen_trait_def(trt))
+            .collect(),
+    };
+
+    match serde_json::to_string_pretty(&api) {
+        Ok(mut json) => {
+            json.push('\n');
+            files.add_file("api.json".into(), json);
+        }
+        Err(e) => errors.push_error(format!("Failed to serialize API description: {e}")),
+    }
+
+    (files, errors)
+}
+
+/// The root of the `api.json` file.
+#[derive(Serialize)]
+pub(crate) struct Api<'tcx> {
+    pub schema_version: u32,
+    pub types: Vec<TypeDefJson<'tcx>>,
+    pub traits: Vec<TraitDefJson<'tcx>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum TypeDefJson<'tcx> {
+    Struct(StructDefJson<'tcx>),
+    OutStruct(StructDefJson<'tcx>),
+    Opaque(OpaqueDefJson<'tcx>),
+    Enum(EnumDefJson<'tcx>),
+}
+
+/// Information shared by every kind of type definition.
+#[derive(Serialize)]
+pub(crate) struct CommonJson<'tcx> {
+    pub name: &'tcx str,
+    pub docs: String,
+    pub attrs: AttrsJson<'tcx>,
+    pub methods: Vec<MethodJson<'tcx>>,
+    pub special_method_presence: SpecialMethodPresenceJson<'tcx>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct StructDefJson<'tcx> {
+    #[serde(flatten)]
+    pub common: CommonJson<'tcx>,
+    pub lifetimes: Vec<LifetimeJson<'tcx>>,
+    pub fields: Vec<FieldJson<'tcx>>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct OpaqueDefJson<'tcx> {
+    #[serde(flatten)]
+    pub common: CommonJson<'tcx>,
+    pub lifetimes: Vec<LifetimeJson<'tcx>>,
+    pub dtor_abi_name: &'tcx str,
+    /// From the `send`/`sync`/`thread_safe` markers
+    pub send: bool,
+    pub sync: bool,
+}
+
+#[derive(Serialize)]
+pub(crate) struct EnumDefJson<'tcx> {
+    #[serde(flatten)]
+    pub common: CommonJson<'tcx>,
+    pub variants: Vec<VariantJson<'tcx>>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct TraitDefJson<'tcx> {
+    pub name: &'tcx str,
+    pub docs: String,
+    pub attrs: AttrsJson<'tcx>,
+    pub lifetimes: Vec<LifetimeJson<'tcx>>,
+    pub methods: Vec<CallbackJson<'tcx>>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct FieldJson<'tcx> {
+    pub name: &'tcx str,
+    pub docs: String,
+    #[serde(rename = "type")]
+    pub ty: TypeJson<'tcx>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct VariantJson<'tcx> {
+    pub name: &'tcx str,
+    pub docs: String,
+    pub discriminant: isize,
+    pub attrs: AttrsJson<'tcx>,
+}
+
+/// A named lifetime, along with the lifetimes it is known to outlive.
+#[derive(Serialize)]
+pub(crate) struct LifetimeJson<'tcx> {
+    pub name: Cow<'tcx, str>,
+    pub outlives: Vec<Cow<'tcx, str>>,
+}
+
+/// The resolved `#[diplomat::attr]`s for the `json` backend.
+#[derive(Serialize)]
+pub(crate) struct AttrsJson<'tcx> {
+    pub namespace: Option<&'tcx str>,
+    /// The name after applying `rename`
+    pub rename: Cow<'tcx, str>,
+    pub special_method: Option<SpecialMethodJson<'tcx>>,
+    /// From the `stream` marker
+    pub stream: bool,
+    /// The `abi_rename` pattern, like `icu4x_{0}`, if any. `abi_name` of methods has it applied
+    pub abi_rename: Option<Cow<'tcx, str>>,
+    /// From `#[diplomat::demo]`
+    pub demo: DemoJson<'tcx>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct DemoJson<'tcx> {
+    pub generate: bool,
+    pub default_constructor: bool,
+    pub external: bool,
+    pub custom_func: Option<&'tcx str>,
+    pub input: DemoInputJson<'tcx>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct DemoInputJson<'tcx> {
+    /// Empty if not set
+    pub label: &'tcx str,
+    /// Empty if not set
+    pub default_value: &'tcx str,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub step: Option<f64>,
+    pub pattern: Option<&'tcx str>,
+    pub choices: &'tcx [String],
+    pub multiline: bool,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", content = "name", rename_all = "snake_case")]
+pub(crate) enum SpecialMethodJson<'tcx> {
+    Constructor,
+    NamedConstructor(Option<&'tcx str>),
+    Getter(Option<&'tcx str>),
+    Setter(Option<&'tcx str>),
+    Stringifier,
+    Comparison,
+    Iterator,
+    Iterable,
+    Indexer,
+}
+
+#[derive(Serialize)]
+pub(crate) struct SpecialMethodPresenceJson<'tcx> {
+    pub comparator: bool,
+    /// The type produced by the iterator, if this type is an iterator
+    pub iterator: Option<SuccessJson<'tcx>>,
+    /// The name of the iterator type, if this type is iterable
+    pub iterable: Option<&'tcx str>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct MethodJson<'tcx> {
+    pub name: &'tcx str,
+    pub abi_name: &'tcx str,
+    pub docs: String,
+    pub attrs: AttrsJson<'tcx>,
+    pub lifetimes: Vec<LifetimeJson<'tcx>>,
+    pub param_self: Option<TypeJson<'tcx>>,
+    pub params: Vec<ParamJson<'tcx>>,
+    pub output: ReturnJson<'tcx>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ParamJson<'tcx> {
+    pub name: Option<&'tcx str>,
+    #[serde(rename = "type")]
+    pub ty: TypeJson<'tcx>,
+    /// Only method parameters have attributes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attrs: Option<AttrsJson<'tcx>>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct CallbackJson<'tcx> {
+    pub name: Option<&'tcx str>,
+    pub docs: Option<String>,
+    pub takes_self: bool,
+    pub params: Vec<ParamJson<'tcx>>,
+    pub output: Option<Box<TypeJson<'tcx>>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum ReturnJson<'tcx> {
+    Infallible {
+        success: SuccessJson<'tcx>,
+    },
+    Fallible {
+        success: SuccessJson<'tcx>,
+        error: Option<TypeJson<'tcx>>,
+    },
+    Nullable {
+        success: SuccessJson<'tcx>,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", content = "type", rename_all = "snake_case")]
+pub(crate) enum SuccessJson<'tcx> {
+    Write,
+    Write16,
+    Unit,
+    Type(TypeJson<'tcx>),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum OwnershipJson {
+    Owned,
+    Borrowed,
+    BorrowedMut,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum TypeJson<'tcx> {
+    Primitive {
+        name: &'static str,
+    },
+    Opaque {
+        name: &'tcx str,
+        optional: bool,
+        ownership: OwnershipJson,
+        /// The lifetime of the borrow, if borrowed
+        lifetime: Option<Cow<'tcx, str>>,
+        lifetimes: Vec<Cow<'tcx, str>>,
+    },
+    Struct {
+        name: &'tcx str,
+        out: bool,
+        lifetimes: Vec<Cow<'tcx, str>>,
+    },
+    Enum {
+        name: &'tcx str,
+    },
+    Slice {
+        /// The element type: a primitive name, `"str"`, or `"strs"`
+        element: &'static str,
+        encoding: Option<&'static str>,
+        /// `None` for owned slices, and for `strs`, whose lifetime isn't tracked
+        lifetime: Option<Cow<'tcx, str>>,
+        mutable: bool,
+    },
+    Option {
+        inner: Box<TypeJson<'tcx>>,
+    },
+    ImplTrait {
+        name: &'tcx str,
+    },
+    Callback(CallbackJson<'tcx>),
+    /// A `&mut DiplomatRead` parameter
+    Read,
+}
+
+pub(crate) struct JsonContext<'tcx> {
+    pub tcx: &'tcx TypeContext,
+    pub docs_url_gen: &'tcx DocsUrlGenerator,
+}
+
+impl<'tcx> JsonContext<'tcx> {
+    fn gen_type_def(&self, id: TypeId, ty: TypeDef<'tcx>) -> TypeDefJson<'tcx> {
+        let common = CommonJson {
+            name: ty.name().as_str(),
+            docs: ty.docs().to_markdown(self.docs_url_gen),
+            attrs: self.gen_attrs(ty.attrs(), ty.name().as_str()),
+            methods: ty
+                .methods()
+                .iter()
+                .filter(|m| !m.attrs.disable)
+                .map(|m| self.gen_method(m))
+                .collect(),
+            special_method_presence: self
+                .gen_special_method_presence(ty.special_method_presence(), id),
+        };
+        match ty {
+            TypeDef::Struct(s) => TypeDefJson::Struct(StructDefJson {
+                common,
+                lifetimes: gen_lifetime_env(&s.lifetimes),
+                fields: s
+                    .fields
+                    .iter()
+                    .map(|f| self.gen_field(f, &s.lifetimes))
+                    .collect(),
+            }),
+            TypeDef::OutStruct(s) => TypeDefJson::OutStruct(StructDefJson {
+                common,
+                lifetimes: gen_lifetime_env(&s.lifetimes),
+                fields: s
+                    .fields
+                    .iter()
+                    .map(|f| self.gen_field(f, &s.lifetimes))
+                    .collect(),
+            }),
+            TypeDef::Opaque(o) => TypeDefJson::Opaque(OpaqueDefJson {
+                common,
+                lifetimes: gen_lifetime_env(&o.lifetimes),
+                dtor_abi_name: o.dtor_abi_name.as_str(),
+                send: o.attrs.thread_safety.send,
+                sync: o.attrs.thread_safety.sync,
+            }),
+            TypeDef::Enum(e) => TypeDefJson::Enum(EnumDefJson {
+                common,
+                variants: e
+                    .variants
+                    .iter()
+                    .map(|v| VariantJson {
+                        name: v.name.as_str(),
+                        docs: v.docs.to_markdown(self.docs_url_gen),
+                        discriminant: v.discriminant,
+                        attrs: self.gen_attrs(&v.attrs, v.name.as_str()),
+                    })
+                    .collect(),
+            }),
+            _ => unreachable!("unknown AST/HIR variant"),
+        }
+    }
+
+    fn gen_trait_def(&self, trt: &'tcx hir::TraitDef) -> TraitDefJson<'tcx> {
+        TraitDefJson {
+            name: trt.name.as_str(),
+            docs: trt.docs.to_markdown(self.docs_url_gen),
+            attrs: self.gen_attrs(&trt.attrs, trt.name.as_str()),
+            lifetimes: gen_lifetime_env(&trt.lifetimes),
+            methods: trt
+                .methods
+                .iter()
+                .map(|m| self.gen_callback(m, &trt.lifetimes))
+                .collect(),
+        }
+    }
+
+    fn gen_field<P: TyPosition>(
+        &self,
+        field: &'tcx hir::StructField<P>,
+        env: &'tcx LifetimeEnv,
+    ) -> FieldJson<'tcx> {
+        FieldJson {
+            name: field.name.as_str(),
+            docs: field.docs.to_markdown(self.docs_url_gen),
+            ty: self.gen_type(&field.ty, env),
+        }
+    }
+
+    fn gen_attrs(&self, attrs: &'tcx hir::Attrs, name: &'tcx str) -> AttrsJson<'tcx> {
+        AttrsJson {
+            namespace: attrs.namespace.as_deref(),
+            rename: attrs.rename.apply(name.into()),
+            special_method: attrs.special_method.as_ref().map(|s| match s {
+                SpecialMethod::Constructor => SpecialMethodJson::Constructor,
+                SpecialMethod::NamedConstructor(n) => {
+                    SpecialMethodJson::NamedConstructor(n.as_deref())
+                }
+                SpecialMethod::Getter(n) => SpecialMethodJson::Getter(n.as_deref()),
+                SpecialMethod::Setter(n) => SpecialMethodJson::Setter(n.as_deref()),
+                SpecialMethod::Stringifier => SpecialMethodJson::Stringifier,
+                SpecialMethod::Comparison => SpecialMethodJson::Comparison,
+                SpecialMethod::Iterator => SpecialMethodJson::Iterator,
+                SpecialMethod::Iterable => SpecialMethodJson::Iterable,
+                SpecialMethod::Indexer => SpecialMethodJson::Indexer,
+                _ => unreachable!("unknown AST/HIR variant"),
+            }),
+            stream: attrs.stream,
+            abi_rename: Some(attrs.abi_rename.apply("{0}".into())).filter(|p| p != "{0}"),
+            demo: DemoJson {
+                generate: attrs.demo_attrs.generate,
+                default_constructor: attrs.demo_attrs.default_constructor,
+                external: attrs.demo_attrs.external,
+                custom_func: attrs.demo_attrs.custom_func.as_deref(),
+                input: DemoInputJson {
+                    label: &attrs.demo_attrs.input_cfg.label,
+                    default_value: &attrs.demo_attrs.input_cfg.default_value,
+                    min: attrs.demo_attrs.input_cfg.min,
+                    max: attrs.demo_attrs.input_cfg.max,
+                    step: attrs.demo_attrs.input_cfg.step,
+                    pattern: attrs.demo_attrs.input_cfg.pattern.as_deref(),
+                    choices: &attrs.demo_attrs.input_cfg.choices,
+                    multiline: attrs.demo_attrs.input_cfg.multiline,
+                },
+            },
+        }
+    }
+
+    fn gen_special_method_presence(
+        &self,
+        presence: &'tcx hir::SpecialMethodPresence,
+        id: TypeId,
+    ) -> SpecialMethodPresenceJson<'tcx> {
+        // Iterator types are always expressed in terms of the `next()` method's lifetimes
+        let iterator = presence.iterator.as_ref().map(|s| {
+            let next = self
+                .tcx
+                .resolve_type(id)
+                .methods()
+                .iter()
+                .find(|m| matches!(m.attrs.special_method, Some(SpecialMethod::Iterator)))
+                .expect("Found iterator type without an iterator method");
+            self.gen_success(s, next)
+        });
+        SpecialMethodPresenceJson {
+            comparator: presence.comparator,
+            iterator,
+            iterable: presence
+                .iterable
+                .map(|i| self.tcx.resolve_opaque(i).name.as_str()),
+        }
+    }
+
+    fn gen_method(&self, method: &'tcx hir::Method) -> MethodJson<'tcx> {
+        let env = &method.lifetime_env;
+        MethodJson {
+            name: method.name.as_str(),
+            abi_name: method.abi_name.as_str(),
+            docs: method.docs.to_markdown(self.docs_url_gen),
+            attrs: self.gen_attrs(&method.attrs, method.name.as_str()),
+            lifetimes: gen_lifetime_env(env),
+            param_self: method
+                .param_self
+                .as_ref()
+                .map(|s| self.gen_self_type(&s.ty, env)),
+            params: method
+                .params
+                .iter()
+                .map(|p| ParamJson {
+                    name: Some(p.name.as_str()),
+                    ty: self.gen_type(&p.ty, env),
+                    attrs: Some(self.gen_attrs(&p.attrs, p.name.as_str())),
+                })
+                .collect(),
+            output: match method.output {
+                ReturnType::Infallible(ref s) => ReturnJson::Infallible {
+                    success: self.gen_success(s, method),
+                },
+                ReturnType::Fallible(ref s, ref e) => ReturnJson::Fallible {
+                    success: self.gen_success(s, method),
+                    error: e.as_ref().map(|e| self.gen_type(e, env)),
+                },
+                ReturnType::Nullable(ref s) => ReturnJson::Nullable {
+                    success: self.gen_success(s, method),
+                },
+            },
+        }
+    }
+
+    fn gen_callback(&self, cb: &'tcx hir::Callback, env: &'tcx LifetimeEnv) -> CallbackJson<'tcx> {
+        CallbackJson {
+            name: cb.name.as_ref().map(|n| n.as_str()),
+            docs: cb.docs.as_ref().map(|d| d.to_markdown(self.docs_url_gen)),
+            takes_self: cb.param_self.is_some(),
+            params: cb
+                .params
+                .iter()
+                .map(|p| ParamJson {
+                    name: p.name.as_ref().map(|n| n.as_str()),
+                    ty: self.gen_type(&p.ty, env),
+                    attrs: None,
+                })
+                .collect(),
+            output: cb
+                .output
+                .as_ref()
+                .as_ref()
+                .map(|o| Box::new(self.gen_type(o, env))),
+        }
+    }
+
+    fn gen_success(
+        &self,
+        success: &'tcx SuccessType,
+        method: &'tcx hir::Method,
+    ) -> SuccessJson<'tcx> {
+        match success {
+            SuccessType::Write if method.write_encoding == WriteEncoding::Utf16 => {
+                SuccessJson::Write16
+            }
+            SuccessType::Write => SuccessJson::Write,
+            SuccessType::Unit => SuccessJson::Unit,
+            SuccessType::OutType(o) => SuccessJson::Type(self.gen_type(o, &method.lifetime_env)),
+            _ => unreachable!("unknown AST/HIR variant"),
+        }
+    }
+
+    fn gen_self_type(&self, ty: &'tcx SelfType, env: &'tcx LifetimeEnv) -> TypeJson<'tcx> {
+        match ty {
+            SelfType::Opaque(o) => TypeJson::Opaque {
+                name: o.resolve(self.tcx).name.as_str(),
+                optional: false,
+                ownership: match o.owner.mutability {
+                    Mutability::Immutable => OwnershipJson::Borrowed,
+                    Mutability::Mutable => OwnershipJson::BorrowedMut,
+                },
+                lifetime: Some(fmt_lifetime(env, o.owner.lifetime)),
+                lifetimes: o
+                    .lifetimes
+                    .lifetimes()
+                    .map(|lt| fmt_lifetime(env, lt))
+                    .collect(),
+            },
+            SelfType::Struct(s) => TypeJson::Struct {
+                name: s.resolve(self.tcx).name.as_str(),
+                out: false,
+                lifetimes: s
+                    .lifetimes
+                    .lifetimes()
+                    .map(|lt| fmt_lifetime(env, lt))
+                    .collect(),
+            },
+            SelfType::Enum(e) => TypeJson::Enum {
+                name: e.resolve(self.tcx).name.as_str(),
+            },
+            _ => unreachable!("unknown AST/HIR variant"),
+        }
+    }
+
+    fn gen_type<P: TyPosition>(
+        &self,
+        ty: &'tcx hir::Type<P>,
+        env: &'tcx LifetimeEnv,
+    ) -> TypeJson<'tcx> {
+        match ty {
+            hir::Type::Primitive(p) => TypeJson::Primitive { name: p.as_str() },
+            hir::Type::Opaque(o) => {
+                let ownership = match o.owner.mutability() {
+                    None => OwnershipJson::Owned,
+                    Some(Mutability::Immutable) => OwnershipJson::Borrowed,
+                    Some(Mutability::Mutable) => OwnershipJson::BorrowedMut,
+                };
+                TypeJson::Opaque {
+                    name: o.resolve(self.tcx).name.as_str(),
+                    optional: o.is_optional(),
+                    ownership,
+                    lifetime: o.owner.lifetime().map(|lt| fmt_lifetime(env, lt)),
+                    lifetimes: o
+                        .lifetimes
+                        .lifetimes()
+                        .map(|lt| fmt_lifetime(env, lt))
+                        .collect(),
+                }
+            }
+            hir::Type::Struct(s) => {
+                let id = s.id();
+                TypeJson::Struct {
+                    name: self.tcx.resolve_type(id).name().as_str(),
+                    out: matches!(id, TypeId::OutStruct(..)),
+                    lifetimes: s
+                        .lifetimes()
+                        .lifetimes()
+                        .map(|lt| fmt_lifetime(env, lt))
+                        .collect(),
+                }
+            }
+            hir::Type::Enum(e) => TypeJson::Enum {
+                name: e.resolve(self.tcx).name.as_str(),
+            },
+            hir::Type::Slice(s) => {
+                let (element, encoding) = match s {
+                    hir::Slice::Str(_, enc) => ("str", Some(fmt_encoding(*enc))),
+                    hir::Slice::Strs(enc) => ("strs", Some(fmt_encoding(*enc))),
+                    hir::Slice::Primitive(_, p) => (p.as_str(), None),
+                    _ => unreachable!("unknown AST/HIR variant"),
+                };
+                let (lifetime, mutable) = match s {
+                    hir::Slice::Primitive(Some(b), _) => (
+                        Some(fmt_lifetime(env, b.lifetime)),
+                        b.mutability == Mutability::Mutable,
+                    ),
+                    // Strs always borrow, but their lifetime is not tracked in the HIR
+                    hir::Slice::Strs(_) => (None, false),
+                    _ => (s.lifetime().map(|lt| fmt_lifetime(env, *lt)), false),
+                };
+                TypeJson::Slice {
+                    element,
+                    encoding,
+                    lifetime,
+                    mutable,
+                }
+            }
+            hir::Type::DiplomatOption(inner) => TypeJson::Option {
+                inner: Box::new(self.gen_type(inner, env)),
+            },
+            hir::Type::ImplTrait(t) => {
+                let trt = self.tcx.resolve_trait(t.id());
+                TypeJson::ImplTrait {
+                    name: trt.name.as_str(),
+                }
+            }
+            hir::Type::Callback(c) => {
+                let params = c
+                    .get_inputs()
+                    .map(|inputs| {
+                        inputs
+                            .iter()
+                            .map(|p| ParamJson {
+                                name: p.name.as_ref().map(|n| n.as_str()),
+                                ty: self.gen_type(&p.ty, env),
+                                attrs: None,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let output = c
+                    .get_output_type()
+                    .ok()
+                    .and_then(|o| o.as_ref())
+                    .map(|o| Box::new(self.gen_type(o, env)));
+                TypeJson::Callback(CallbackJson {
+                    name: None,
+                    docs: None,
+                    takes_self: false,
+                    params,
+                    output,
+                })
+            }
+            hir::Type::Read => TypeJson::Read,
+            _ => unreachable!("unknown AST/HIR variant"),
+        }
+    }
+}
+
+fn gen_lifetime_env(env: &LifetimeEnv) -> Vec<LifetimeJson<'_>> {
+    env.all_lifetimes()
+        .map(|lt| LifetimeJson {
+            name: env.fmt_lifetime(lt),
+            outlives: env
+                .all_shorter_lifetimes(lt)
+                .skip(1)
+                .map(|s| env.fmt_lifetime(s))
+                .collect(),
+        })
+        .collect()
+}
+
+fn fmt_lifetime(env: &LifetimeEnv, lt: MaybeStatic<hir::Lifetime>) -> Cow<'_, str> {
+    match lt {
+        MaybeStatic::Static => "static".into(),
+        MaybeStatic::NonStatic(lt) => env.fmt_lifetime(lt),
+    }
+}
+
+fn fmt_encoding(encoding: StringEncoding) -> &'static str {
+    match encoding {
+        StringEncoding::UnvalidatedUtf8 => "unvalidated_utf8",
+        StringEncoding::UnvalidatedUtf16 => "unvalidated_utf16",
+        StringEncoding::Utf8 => "utf8",
+        _ => unreachable!("unknown AST/HIR variant"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use diplomat_core::hir::{self, TypeContext};
+    use quote::quote;
+
+    fn gen_json(tk_stream: proc_macro2::TokenStream) -> serde_json::Value {
+        let file = syn::parse2::<syn::File>(tk_stream).expect("failed to parse item");
+        let mut attr_validator = hir::BasicAttributeValidator::new("json");
+        attr_validator.support = super::attr_support();
+        let tcx = TypeContext::from_syn(&file, attr_validator).expect("failed to lower");
+        let docs = hir::DocsUrlGenerator::with_base_urls(None, Default::default());
+
+        let (files, errors) = super::run(&tcx, &docs);
+        assert!(errors.take_all().is_empty());
+        let files = files.take_files();
+        serde_json::from_str(&files["api.json"]).unwrap()
+    }
+
+    fn find<'a>(items: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+        items
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|t| t["name"] == name)
+            .unwrap_or_else(|| panic!("{name} not found"))
+    }
+
+    #[test]
+    fn test_api_json() {
+        let json = gen_json(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                /// An opaque
+                #[diplomat::opaque]
+                #[diplomat::attr(*, sync)]
+                struct Opaque<'a>(&'a u8);
+
+                struct Point {
+                    x: i32,
+                    y: i32,
+                }
+
+                enum Direction {
+                    North = 1,
+                    South = 2,
+                }
+
+                impl<'a> Opaque<'a> {
+                    #[diplomat::attr(auto, constructor)]
+                    pub fn new(input: &'a [u8]) -> Box<Opaque<'a>> {
+                        unimplemented!()
+                    }
+
+                    pub fn point(&self, direction: Direction) -> Result<Point, ()> {
+                        unimplemented!()
+                    }
+                }
+            }
+        });
+
+        assert_eq!(json["schema_version"], super::SCHEMA_VERSION);
+        let opaque = find(&json["types"], "Opaque");
+        assert_eq!(opaque["kind"], "opaque");
+        assert_eq!(opaque["docs"], "An opaque");
+        assert_eq!(opaque["lifetimes"][0]["name"], "a");
+        assert_eq!(opaque["send"], false);
+        assert_eq!(opaque["sync"], true);
+
+        let new = &opaque["methods"][0];
+        assert_eq!(new["attrs"]["special_method"]["kind"], "constructor");
+        assert_eq!(new["params"][0]["type"]["kind"], "slice");
+        assert_eq!(new["params"][0]["type"]["element"], "u8");
+        assert_eq!(new["output"]["success"]["type"]["ownership"], "owned");
+
+        let point = &opaque["methods"][1];
+        assert_eq!(point["output"]["kind"], "fallible");
+        assert_eq!(point["output"]["error"], serde_json::Value::Null);
+        assert_eq!(point["params"][0]["type"]["name"], "Direction");
+
+        let direction = find(&json["types"], "Direction");
+        assert_eq!(direction["variants"][1]["discriminant"], 2);
+        let strct = find(&json["types"], "Point");
+        assert_eq!(strct["fields"][1]["type"]["name"], "i32");
+    }
+
+    #[test]
+    fn test_disabled_items() {
+        let json = gen_json(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                #[diplomat::opaque]
+                #[diplomat::attr(json, disable)]
+                struct Hidden;
+
+                #[diplomat::attr(json, disable)]
+                trait HiddenTrait {
+                    fn run(&self);
+                }
+
+                impl Opaque {
+                    pub fn shown(&self) {}
+
+                    #[diplomat::attr(json, disable)]
+                    pub fn hidden(&self) {}
+                }
+            }
+        });
+
+        let types = json["types"].as_array().unwrap();
+        assert!(types.iter().all(|t| t["name"] != "Hidden"));
+        assert!(json["traits"].as_array().unwrap().is_empty());
+
+        let methods = find(&json["types"], "Opaque")["methods"]
+            .as_array()
+            .unwrap();
+        assert_eq!(methods.len(), 1);
+        assert_eq!(methods[0]["name"], "shown");
+    }
+
+    #[test]
+    fn test_attrs() {
+        let json = gen_json(quote! {
+            #[diplomat::bridge]
+            #[diplomat::abi_rename = "lib_{0}"]
+            mod ffi {
+                #[diplomat::opaque]
+                #[diplomat::attr(json, rename = "Renamed")]
+                struct Opaque;
+
+                impl Opaque {
+                    #[diplomat::demo(default_constructor)]
+                    pub fn new(
+                        #[diplomat::demo(input(label = "Count", min = 1, max = 10))] count: u8,
+                    ) -> Box<Opaque> {
+                        unimplemented!()
+                    }
+                }
+            }
+        });
+
+        let opaque = find(&json["types"], "Opaque");
+        assert_eq!(opaque["attrs"]["rename"], "Renamed");
+        assert_eq!(opaque["attrs"]["abi_rename"], "lib_{0}");
+        assert_eq!(opaque["attrs"]["demo"]["default_constructor"], false);
+
+        let new = &opaque["methods"][0];
+        assert_eq!(new["abi_name"], "lib_Opaque_new");
+        assert_eq!(new["attrs"]["abi_rename"], "lib_{0}");
+        assert_eq!(new["attrs"]["demo"]["default_constructor"], true);
+        let input = &new["params"][0]["attrs"]["demo"]["input"];
+        assert_eq!(input["label"], "Count");
+        assert_eq!(input["min"], 1.0);
+        assert_eq!(input["max"], 10.0);
+        assert_eq!(input["step"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_lifetimes() {
+        let json = gen_json(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque<'a>(&'a u8);
+
+                struct Borrowing<'a, 'b: 'a> {
+                    first: DiplomatStrSlice<'a>,
+                    second: DiplomatStrSlice<'b>,
+                }
+
+                impl<'a> Opaque<'a> {
+                    pub fn borrow<'b>(&'b self, other: &'a Opaque<'a>) -> &'b Opaque<'a> {
+                        unimplemented!()
+                    }
+
+                    pub fn strs(&self, strs: &[DiplomatStrSlice]) {}
+
+                    pub fn leaked() -> &'static [u8] {
+                        unimplemented!()
+                    }
+                }
+            }
+        });
+
+        let borrowing = find(&json["types"], "Borrowing");
+        let lifetimes = borrowing["lifetimes"].as_array().unwrap();
+        assert_eq!(lifetimes[0]["name"], "a");
+        assert_eq!(lifetimes[1]["name"], "b");
+        assert_eq!(lifetimes[1]["outlives"], serde_json::json!(["a"]));
+        assert_eq!(borrowing["fields"][0]["type"]["lifetime"], "a");
+        assert_eq!(borrowing["fields"][1]["type"]["lifetime"], "b");
+
+        let opaque = find(&json["types"], "Opaque");
+        let borrow = find(&opaque["methods"], "borrow");
+        assert_eq!(borrow["param_self"]["ownership"], "borrowed");
+        assert_eq!(borrow["param_self"]["lifetime"], "b");
+        assert_eq!(borrow["param_self"]["lifetimes"], serde_json::json!(["a"]));
+        assert_eq!(borrow["params"][0]["type"]["lifetime"], "a");
+        let output = &borrow["output"]["success"]["type"];
+        assert_eq!(output["lifetime"], "b");
+        assert_eq!(output["lifetimes"], serde_json::json!(["a"]));
+
+        // The lifetime of string slices is not tracked, so none is reported
+        let strs = &find(&opaque["methods"], "strs")["params"][0]["type"];
+        assert_eq!(strs["element"], "strs");
+        assert_eq!(strs["lifetime"], serde_json::Value::Null);
+
+        let leaked = &find(&opaque["methods"], "leaked")["output"]["success"]["type"];
+        assert_eq!(leaked["lifetime"], "static");
+    }
+
+    #[test]
+    fn test_type_kinds() {
+        let json = gen_json(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Opaque;
+
+                struct Input {
+                    a: u8,
+                }
+
+                #[diplomat::out]
+                struct Output {
+                    a: u8,
+                }
+
+                enum Kind {
+                    A,
+                }
+
+                trait Visitor {
+                    fn visit(&self, x: i32) -> u32;
+                }
+
+                impl Opaque {
+                    pub fn everything(
+                        &mut self,
+                        primitive: f64,
+                        optional: Option<&Opaque>,
+                        mutable: &mut Opaque,
+                        input: Input,
+                        kind: Kind,
+                        s: &DiplomatStr16,
+                        bytes: &mut [u8],
+                        owned_bytes: Box<[u32]>,
+                        option: DiplomatOption<u8>,
+                        visitor: impl Visitor,
+                        callback: impl Fn(i32) -> i32,
+                    ) -> Option<Output> {
+                        unimplemented!()
+                    }
+
+                    pub fn write(&self, write: &mut DiplomatWrite) {}
+                }
+            }
+        });
+
+        let opaque = find(&json["types"], "Opaque");
+        let everything = find(&opaque["methods"], "everything");
+        assert_eq!(everything["param_self"]["ownership"], "borrowed_mut");
+
+        let params = &everything["params"];
+        let ty = |name: &str| &find(params, name)["type"];
+        assert_eq!(ty("primitive")["kind"], "primitive");
+        assert_eq!(ty("primitive")["name"], "f64");
+        assert_eq!(ty("optional")["kind"], "opaque");
+        assert_eq!(ty("optional")["optional"], true);
+        assert_eq!(ty("optional")["ownership"], "borrowed");
+        assert_eq!(ty("mutable")["ownership"], "borrowed_mut");
+        assert_eq!(ty("input")["kind"], "struct");
+        assert_eq!(ty("input")["out"], false);
+        assert_eq!(ty("kind")["kind"], "enum");
+        assert_eq!(ty("s")["element"], "str");
+        assert_eq!(ty("s")["encoding"], "unvalidated_utf16");
+        assert_eq!(ty("bytes")["mutable"], true);
+        assert_eq!(ty("owned_bytes")["lifetime"], serde_json::Value::Null);
+        assert_eq!(ty("option")["kind"], "option");
+        assert_eq!(ty("option")["inner"]["name"], "u8");
+        assert_eq!(ty("visitor")["kind"], "impl_trait");
+        assert_eq!(ty("visitor")["name"], "Visitor");
+        assert_eq!(ty("callback")["kind"], "callback");
+        assert_eq!(ty("callback")["params"][0]["type"]["name"], "i32");
+        assert_eq!(ty("callback")["output"]["name"], "i32");
+
+        assert_eq!(everything["output"]["kind"], "nullable");
+        assert_eq!(everything["output"]["success"]["type"]["out"], true);
+
+        let write = find(&opaque["methods"], "write");
+        assert_eq!(write["output"]["success"]["kind"], "write");
+
+        let visitor = find(&json["traits"], "Visitor");
+        assert_eq!(visitor["methods"][0]["name"], "visit");
+        assert_eq!(visitor["methods"][0]["takes_self"], true);
+        assert_eq!(visitor["methods"][0]["output"]["name"], "u32");
+
+        assert_eq!(find(&json["types"], "Output")["kind"], "out_struct");
+    }
+}
@@ -1,12 +1,16 @@
 use diplomat_core::hir::{
     self,
     borrowing_param::{LifetimeEdge, LifetimeEdgeKind},
-    Docs, DocsUrlGenerator, FloatType, IntSizeType, IntType, LifetimeEnv, MaybeStatic,
-    PrimitiveType, Slice, StringEncoding, StructPathLike, TraitId, TyPosition, Type, TypeContext,
-    TypeId,
+    Docs, DocsUrlGenerator, FloatType, IntSizeType, IntType, Lifetime, LifetimeEnv, MaybeStatic,
+    OpaqueOwner, PrimitiveType, Slice, StringEncoding, StructPathLike, TraitId, TyPosition, Type,
+    TypeContext, TypeId,
 };
 use heck::ToLowerCamelCase;
-use std::{borrow::Cow, iter::once};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    iter::once,
+};
 
 /// This type mediates all formatting
 pub(super) struct KotlinFormatter<'tcx> {
@@ -14,6 +18,10 @@ pub(super) struct KotlinFormatter<'tcx> {
     strip_prefix: Option<String>,
     /// For generating doc.rs links
     docs_url_gen: &'tcx DocsUrlGenerator,
+    /// The package of the library, e.g. `dev.gigapixel.somelib`
+    package: String,
+    /// Names of types and traits that are declared in more than one namespace
+    clashing_names: BTreeSet<&'tcx str>,
 }
 
 const INVALID_METHOD_NAMES: &[&str] = &[
@@ -26,11 +34,34 @@ impl<'tcx> KotlinFormatter<'tcx> {
         tcx: &'tcx TypeContext,
         strip_prefix: Option<String>,
         docs_url_gen: &'tcx DocsUrlGenerator,
+        package: String,
     ) -> Self {
+        let mut namespaces = BTreeMap::<&str, BTreeSet<Option<&str>>>::new();
+        let types = tcx
+            .all_types()
+            .map(|(_, ty)| (ty.name().as_str(), ty.attrs()));
+        let traits = tcx
+            .all_traits()
+            .map(|(_, trt)| (trt.name.as_str(), &trt.attrs));
+        for (name, attrs) in types.chain(traits) {
+            if !attrs.disable {
+                namespaces
+                    .entry(name)
+                    .or_default()
+                    .insert(attrs.namespace.as_deref());
+            }
+        }
+        let clashing_names = namespaces
+            .into_iter()
+            .filter(|(_, namespaces)| namespaces.len() > 1)
+            .map(|(name, _)| name)
+            .collect();
         Self {
             tcx,
             strip_prefix,
             docs_url_gen,
+            package,
+            clashing_names,
         }
     }
 
@@ -46,7 +77,7 @@ impl<'tcx> KotlinFormatter<'tcx> {
             PrimitiveType::Int(IntType::U32) => format!("{name}.toInt()"),
             PrimitiveType::Int(IntType::U64) => format!("{name}.toLong()"),
             PrimitiveType::IntSize(IntSizeType::Usize) => format!("{name}.toLong()"),
-            PrimitiveType::Int128(_) => panic!("128 bit ints not supported"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
             _ => name.into(),
         }
     }
@@ -87,7 +118,7 @@ impl<'tcx> KotlinFormatter<'tcx> {
             PrimitiveType::IntSize(IntSizeType::Usize) => "Long",
             PrimitiveType::Float(FloatType::F32) => "Float",
             PrimitiveType::Float(FloatType::F64) => "Double",
-            PrimitiveType::Int128(_) => panic!("i128 not supported in Kotlin"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
         }
     }
 
@@ -108,7 +139,7 @@ impl<'tcx> KotlinFormatter<'tcx> {
             PrimitiveType::IntSize(IntSizeType::Usize) => "ULong",
             PrimitiveType::Float(FloatType::F32) => "Float",
             PrimitiveType::Float(FloatType::F64) => "Double",
-            PrimitiveType::Int128(_) => panic!("i128 not supported in Kotlin"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
         }
     }
 
@@ -124,6 +155,22 @@ impl<'tcx> KotlinFormatter<'tcx> {
         }
     }
 
+    /// The name of the variant of a method marked `stream` that writes to an `OutputStream`, such as `toStringTo`.
+    pub fn fmt_stream_method_name(&self, method: &hir::Method) -> String {
+        let name = method.name.as_str().to_lower_camel_case();
+        format!("{}To", method.attrs.rename.apply(name.into()))
+    }
+
+    /// Format the name of a named constructor, given as `#[diplomat::attr(.., named_constructor = "name")]`
+    pub fn fmt_constructor_name<'a>(&self, name: &str) -> Cow<'a, str> {
+        let name = name.to_lower_camel_case();
+        if INVALID_METHOD_NAMES.contains(&&*name) {
+            format!("{name}_").into()
+        } else {
+            name.into()
+        }
+    }
+
     pub fn fmt_trait_method_name<'a>(&self, method: &'a hir::Callback) -> Cow<'a, str> {
         if method.name.is_none() {
             panic!("Trait methods need a name");
@@ -188,14 +235,19 @@ impl<'tcx> KotlinFormatter<'tcx> {
             }
             .into(),
             Type::Struct(s) => {
-                let field_type_name: &str = self.tcx.resolve_type(s.id()).name().as_ref();
+                let field_type_name = self.fmt_type_raw_ref(s.id());
                 format!("{field_type_name}Native()").into()
             }
             Type::Enum(enum_def) => {
-                let field_type_name: &str = self.tcx.resolve_enum(enum_def.tcx_id).name.as_ref();
+                let field_type_name = self.fmt_type_raw_ref(enum_def.tcx_id.into());
                 format!("{field_type_name}.default().toNative()").into()
             }
             Type::Slice(_) => "Slice()".into(),
+            Type::DiplomatOption(inner) => format!(
+                "Option{}()",
+                super::result_type_ident(&self.fmt_struct_field_type_native(inner))
+            )
+            .into(),
             ty => unreachable!("reached struct field that can't be handled: {ty:?}"),
         }
     }
@@ -211,7 +263,7 @@ impl<'tcx> KotlinFormatter<'tcx> {
             PrimitiveType::IntSize(IntSizeType::Usize) => {
                 format!("{optional_conversion}.toULong()").into()
             }
-            PrimitiveType::Int128(_) => panic!("Int128 not supported"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
             _ => "".into(),
         }
     }
@@ -222,87 +274,95 @@ impl<'tcx> KotlinFormatter<'tcx> {
         lifetime_env: &'a LifetimeEnv,
         ty: &'a Type<P>,
     ) -> Cow<'tcx, str> {
+        self.fmt_native_to_kt(&format!("nativeStruct.{field_name}"), lifetime_env, ty)
+    }
+
+    /// Converts the native value `native` of a struct field of type `ty` to its Kotlin type
+    fn fmt_native_to_kt<P: TyPosition>(
+        &self,
+        native: &str,
+        lifetime_env: &LifetimeEnv,
+        ty: &Type<P>,
+    ) -> Cow<'tcx, str> {
+        // The edges of the struct that must be kept alive by anything borrowing from it for `lt`
+        let edges_for = |lt: Lifetime| {
+            let lts = lifetime_env
+                .all_longer_lifetimes(lt)
+                .map(|longer_lt| {
+                    let longer_lt = lifetime_env.fmt_lifetime(longer_lt);
+                    format!("{longer_lt}Edges")
+                })
+                .collect::<Vec<_>>();
+            if lts.is_empty() {
+                "listOf()".into()
+            } else {
+                lts.join("+")
+            }
+        };
         match ty {
             Type::Primitive(prim) => {
                 let maybe_unsized_conversion = self.fmt_unsized_conversion(*prim, false);
-                format!("nativeStruct.{field_name}{maybe_unsized_conversion}").into()
+                format!("{native}{maybe_unsized_conversion}").into()
             }
             Type::Opaque(opaque) => {
-                let lt_list: String =
-                    once("listOf()".to_string()) // we only support owned opaque types, so the self edges
-                                     // should be empty
-                        .chain(opaque.lifetimes.lifetimes().filter_map(|maybe_static_lt| match maybe_static_lt{
+                let self_edges = match opaque.owner.lifetime() {
+                    Some(MaybeStatic::NonStatic(lt)) => edges_for(lt),
+                    _ => "listOf()".into(),
+                };
+                let lt_list: String = once(self_edges)
+                    .chain(opaque.lifetimes.lifetimes().filter_map(|maybe_static_lt| {
+                        match maybe_static_lt {
                             MaybeStatic::Static => None,
-                            MaybeStatic::NonStatic(lt) => {
-                                let lts = lifetime_env
-                                    .all_longer_lifetimes(lt)
-                                    .map(|longer_lt|  {
-                                         let longer_lt = lifetime_env.fmt_lifetime(longer_lt);
-                                         format!("{longer_lt}Edges")
-                                    })
-                                    .collect::<Vec<_>>();
-                                 Some(if lts.is_empty() {
-                                    "listOf()".into()
-                                } else {
-                                    lts.join("+")
-                                })
-                            }
-                        }))
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                let ty_name =
-                    self.fmt_type_name(ty.id().expect("Failed to get type id for opaque"));
+                            MaybeStatic::NonStatic(lt) => Some(edges_for(lt)),
+                        }
+                    }))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let ty_name = self.fmt_type_ref(ty.id().expect("Failed to get type id for opaque"));
                 if opaque.is_optional() {
-                    format!(
-                        r#"if (nativeStruct.{field_name} == null) {{
-        null
-    }} else {{
-        {ty_name}(nativeStruct.{field_name}!!, {lt_list})
-    }}"#
-                    )
+                    format!("{native}?.let {{ {ty_name}(it, {lt_list}) }}")
                 } else {
-                    format!("{ty_name}(nativeStruct.{field_name}, {lt_list})")
+                    format!("{ty_name}({native}, {lt_list})")
                 }
                 .into()
             }
             Type::Struct(strct) => {
-                let ty_name =
-                    self.fmt_type_name(ty.id().expect("Failed to get type id for opaque"));
+                let ty_name = self.fmt_type_ref(ty.id().expect("Failed to get type id for opaque"));
                 let lt_list: String = strct
                     .lifetimes()
                     .lifetimes()
                     .filter_map(|maybe_static_lt| match maybe_static_lt {
                         MaybeStatic::Static => None,
-                        MaybeStatic::NonStatic(lt) => {
-                            let lt_name = lifetime_env.fmt_lifetime(lt);
-                            Some(format!("{lt_name}Edges"))
-                        }
+                        MaybeStatic::NonStatic(lt) => Some(edges_for(lt)),
                     })
                     .fold(String::new(), |accum, new| format!("{accum}, {new}"));
-                format!("{ty_name}(nativeStruct.{field_name}{lt_list})").into()
+                format!("{ty_name}({native}{lt_list})").into()
             }
             Type::Enum(enum_path) => {
-                let field_type_name: &str = self.tcx.resolve_enum(enum_path.tcx_id).name.as_ref();
-                format!("{field_type_name}.fromNative(nativeStruct.{field_name})").into()
+                let field_type_name = self.fmt_type_raw_ref(enum_path.tcx_id.into());
+                format!("{field_type_name}.fromNative({native})").into()
             }
             Type::Slice(Slice::Primitive(_, prim)) => format!(
-                "PrimitiveArrayTools.get{}Array(nativeStruct.{field_name})",
+                "PrimitiveArrayTools.get{}Array({native})",
                 self.fmt_primitive_as_kt(*prim)
             )
             .into(),
             Type::Slice(Slice::Str(_, StringEncoding::UnvalidatedUtf16)) => {
-                format!("PrimitiveArrayTools.getUtf16(nativeStruct.{field_name})").into()
+                format!("PrimitiveArrayTools.getUtf16({native})").into()
             }
             Type::Slice(Slice::Str(_, _)) => {
-                format!("PrimitiveArrayTools.getUtf8(nativeStruct.{field_name})").into()
+                format!("PrimitiveArrayTools.getUtf8({native})").into()
             }
             Type::Slice(Slice::Strs(StringEncoding::UnvalidatedUtf16)) => {
-                format!("PrimitiveArrayTools.getUt16s(nativeStruct.{field_name})").into()
-            }
-            Type::Slice(Slice::Strs(_)) => {
-                format!("PrimitiveArrayTools.getUt16s(nativeStruct.{field_name})").into()
+                format!("PrimitiveArrayTools.getUtf16s({native})").into()
             }
-            _ => todo!(),
+            Type::Slice(Slice::Strs(_)) => format!("PrimitiveArrayTools.getUtf8s({native})").into(),
+            Type::DiplomatOption(inner) => format!(
+                "{native}.option()?.let {{ {} }}",
+                self.fmt_native_to_kt("it", lifetime_env, inner)
+            )
+            .into(),
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -317,20 +377,23 @@ impl<'tcx> KotlinFormatter<'tcx> {
                 let optional = if op.is_optional() { "?" } else { "" };
                 format!(
                     "{}{optional}",
-                    self.fmt_type_name(ty.id().expect("Failed to get type id for opaque"))
+                    self.fmt_type_ref(ty.id().expect("Failed to get type id for opaque"))
                 )
                 .into()
             }
             Type::Struct(_) => {
-                self.fmt_type_name(ty.id().expect("Failed to get type id for struct"))
+                self.fmt_type_ref(ty.id().expect("Failed to get type id for struct"))
             }
-            Type::Enum(_) => self.fmt_type_name(ty.id().expect("Failed to get type id for enum")),
+            Type::Enum(_) => self.fmt_type_ref(ty.id().expect("Failed to get type id for enum")),
             Type::Slice(Slice::Primitive(_, prim)) => {
                 format!("{}Array", self.fmt_primitive_as_kt(*prim)).into()
             }
             Type::Slice(Slice::Str(_, _)) => "String".into(),
             Type::Slice(Slice::Strs(_)) => "List<String>".into(),
-            _ => todo!(),
+            Type::DiplomatOption(inner) => self
+                .fmt_nullable(&self.fmt_struct_field_type_kt(inner))
+                .into(),
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -356,15 +419,72 @@ impl<'tcx> KotlinFormatter<'tcx> {
                 let optional = if op.is_optional() { "?" } else { "" };
                 format!("Pointer{optional}").into()
             }
-            Type::Struct(s) => {
-                format!("{}Native", self.tcx.resolve_type(s.id()).name().as_str()).into()
-            }
+            Type::Struct(s) => format!("{}Native", self.fmt_type_raw_ref(s.id())).into(),
             Type::Enum(_) => "Int".into(),
             Type::Slice(_) => "Slice".into(),
+            Type::DiplomatOption(inner) => format!(
+                "Option{}",
+                super::result_type_ident(&self.fmt_struct_field_type_native(inner))
+            )
+            .into(),
             ty => unreachable!("reached struct field that can't be handled: {ty:?}"),
         }
     }
 
+    /// Format a namespace (`a::b`) as a sub-package (`a.b`)
+    pub fn fmt_namespace(&self, namespace: &str) -> String {
+        namespace.replace("::", ".")
+    }
+
+    fn fmt_qualifier(&self, name: &str, namespace: Option<&str>) -> String {
+        if !self.clashing_names.contains(name) {
+            return String::new();
+        }
+        match namespace {
+            Some(ns) => format!("{}.{}.", self.package, self.fmt_namespace(ns)),
+            None => format!("{}.", self.package),
+        }
+    }
+
+    /// The package prefix (`dev.gigapixel.somelib.a.b.`) with which references to a type
+    /// must be qualified.
+    ///
+    /// Types of other namespaces are visible through wildcard imports of their packages, which
+    /// cannot disambiguate types with the same name, so those are always referenced by their
+    /// fully qualified name. Unique names are not qualified.
+    pub fn fmt_type_qualifier(&self, id: TypeId) -> String {
+        let resolved = self.tcx.resolve_type(id);
+        self.fmt_qualifier(
+            resolved.name().as_str(),
+            resolved.attrs().namespace.as_deref(),
+        )
+    }
+
+    /// The package prefix with which references to a trait must be qualified, see
+    /// [`Self::fmt_type_qualifier`].
+    pub fn fmt_trait_qualifier(&self, id: TraitId) -> String {
+        let resolved = self.tcx.resolve_trait(id);
+        self.fmt_qualifier(resolved.name.as_str(), resolved.attrs.namespace.as_deref())
+    }
+
+    /// The name of a type as referenced from generated code, qualified if needed
+    pub fn fmt_type_ref(&self, id: TypeId) -> Cow<'tcx, str> {
+        let name = self.fmt_type_name(id);
+        match self.fmt_type_qualifier(id) {
+            qualifier if qualifier.is_empty() => name,
+            qualifier => format!("{qualifier}{name}").into(),
+        }
+    }
+
+    /// The raw name of a type as referenced from generated code, qualified if needed
+    fn fmt_type_raw_ref(&self, id: TypeId) -> String {
+        format!(
+            "{}{}",
+            self.fmt_type_qualifier(id),
+            self.tcx.resolve_type(id).name()
+        )
+    }
+
     pub fn fmt_type_name(&self, id: TypeId) -> Cow<'tcx, str> {
         let resolved = self.tcx.resolve_type(id);
 
@@ -470,7 +590,8 @@ pub mod test {
         let tcx = new_tcx(tk_stream);
         let docs_urls = std::collections::HashMap::new();
         let docs_generator = &diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
-        let formatter = KotlinFormatter::new(&tcx, None, docs_generator);
+        let formatter =
+            KotlinFormatter::new(&tcx, None, docs_generator, "dev.gigapixel.somelib".into());
         let opaques = tcx.opaques();
         assert!(!opaques.is_empty());
         let mut all_types = tcx.all_types();
@@ -5,8 +5,9 @@ use diplomat_core::hir::{
     Lifetimes, MaybeOwn, MaybeStatic, Method, Mutability, OpaquePath, Optional, OutType, Param,
     PrimitiveType, ReturnableStructDef, SelfType, Slice, SpecialMethod, StringEncoding,
     StructField, StructPath, StructPathLike, TraitIdGetter, TyPosition, Type, TypeContext, TypeDef,
+    TypeId,
 };
-use diplomat_core::hir::{ReturnType, SuccessType};
+use diplomat_core::hir::{ReturnType, SuccessType, WriteEncoding};
 
 use std::borrow::Cow;
 use std::cell::RefCell;
@@ -17,29 +18,37 @@ use std::path::Path;
 mod formatter;
 use formatter::KotlinFormatter;
 
+use crate::templates::{ClassHookVars, MethodHookVars, MethodHooks, TemplateOverrides};
 use crate::{ErrorStore, FileMap};
 use serde::{Deserialize, Serialize};
 
 pub(crate) fn attr_support() -> BackendAttrSupport {
     let mut a = BackendAttrSupport::default();
 
-    a.namespacing = false; // TODO
+    a.namespacing = true;
     a.memory_sharing = false;
     a.non_exhaustive_structs = true;
     a.method_overloading = true;
     a.utf8_strings = false;
     a.utf16_strings = true;
     a.static_slices = true;
-
-    a.constructors = false; // TODO
-    a.named_constructors = false; // TODO
-    a.fallible_constructors = false; // TODO
+    a.static_borrows = true;
+    a.int128 = false;
+    a.int128_slices = false;
+    a.write16 = true;
+    a.reads = true;
+    a.option = true;
+
+    a.constructors = true;
+    a.named_constructors = true;
+    a.fallible_constructors = true;
     a.accessors = false;
-    a.stringifiers = false; // TODO
-    a.comparators = false; // TODO
+    a.stringifiers = true;
+    a.comparators = true;
     a.iterators = true;
     a.iterables = true;
     a.indexing = true;
+    a.streaming_writes = true;
     a.callbacks = true;
     a.traits = true;
 
@@ -51,12 +60,15 @@ struct KotlinConfig {
     domain: String,
     lib_name: String,
     use_finalizers_not_cleaners: Option<bool>,
+    /// Assert that opaques which aren't `send` are only used on the thread that created them
+    thread_checks: Option<bool>,
 }
 
 pub(crate) fn run<'tcx>(
     tcx: &'tcx TypeContext,
     conf_path: Option<&Path>,
     docs_url_gen: &'tcx DocsUrlGenerator,
+    templates: &TemplateOverrides,
 ) -> (FileMap, ErrorStore<'tcx, String>) {
     let conf_path = conf_path.expect("Kotlin library needs to be called with config");
 
@@ -66,10 +78,12 @@ pub(crate) fn run<'tcx>(
         domain,
         lib_name,
         use_finalizers_not_cleaners,
+        thread_checks,
     } = toml::from_str::<KotlinConfig>(&conf_str)
         .expect("Failed to parse config. Required fields are `domain` and `lib_name`");
     let use_finalizers_not_cleaners = use_finalizers_not_cleaners.unwrap_or(false);
-    let formatter = KotlinFormatter::new(tcx, None, docs_url_gen);
+    let thread_checks = thread_checks.unwrap_or(false);
+    let formatter = KotlinFormatter::new(tcx, None, docs_url_gen, format!("{domain}.{lib_name}"));
 
     let files = FileMap::default();
     let errors = ErrorStore::default();
@@ -82,6 +96,8 @@ pub(crate) fn run<'tcx>(
         option_types: RefCell::new(BTreeSet::new()),
         formatter: &formatter,
         callback_params: &mut callback_params,
+        templates,
+        thread_checks,
     };
 
     for (_id, ty) in tcx.all_types() {
@@ -211,6 +227,7 @@ pub(crate) fn run<'tcx>(
     #[template(path = "kotlin/init.kt.jinja", escape = "none")]
     struct Init<'a> {
         domain: &'a str,
+        package_imports: Vec<String>,
         native_results: &'a [String],
         native_options: &'a [String],
         lib_name: &'a str,
@@ -219,6 +236,7 @@ pub(crate) fn run<'tcx>(
 
     let init = Init {
         domain: &domain,
+        package_imports: package_imports(tcx, &formatter, None, &domain, &lib_name),
         lib_name: &lib_name,
         native_results: native_results.as_slice(),
         native_options: native_options.as_slice(),
@@ -235,9 +253,106 @@ pub(crate) fn run<'tcx>(
         init,
     );
 
+    #[derive(Template)]
+    #[template(path = "kotlin/LayoutTest.kt.jinja", escape = "none")]
+    struct LayoutTest<'a> {
+        domain: &'a str,
+        lib_name: &'a str,
+        package_imports: Vec<String>,
+        structs: Vec<LayoutStruct<'a>>,
+    }
+
+    struct LayoutStruct<'a> {
+        name: &'a str,
+        size32: usize,
+        size64: usize,
+        /// The names and 32- and 64-bit offsets of the fields
+        fields: Vec<(Cow<'a, str>, usize, usize)>,
+    }
+
+    let mut structs = tcx
+        .all_types()
+        .filter(|(_, ty)| !ty.attrs().disable)
+        .filter_map(|(_, ty)| {
+            let ([info32, info64], field_names) = match ty {
+                TypeDef::Struct(s) if !s.fields.is_empty() => (
+                    crate::js::layout::struct_field_info_by_width(s, tcx),
+                    s.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(),
+                ),
+                TypeDef::OutStruct(s) if !s.fields.is_empty() => (
+                    crate::js::layout::struct_field_info_by_width(s, tcx),
+                    s.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(),
+                ),
+                _ => return None,
+            };
+            Some(LayoutStruct {
+                name: ty.name().as_str(),
+                size32: info32.struct_layout.size(),
+                size64: info64.struct_layout.size(),
+                fields: field_names
+                    .into_iter()
+                    .zip(info32.fields.iter().zip(&info64.fields))
+                    .map(|(name, (f32, f64))| {
+                        (formatter.fmt_field_name(name), f32.offset, f64.offset)
+                    })
+                    .collect(),
+            })
+        })
+        .collect::<Vec<_>>();
+    structs.sort_by_key(|s| s.name);
+
+    let layout_test = LayoutTest {
+        domain: &domain,
+        lib_name: &lib_name,
+        package_imports: package_imports(tcx, &formatter, None, &domain, &lib_name),
+        structs,
+    }
+    .render()
+    .expect("Failed to render layout test");
+
+    files.add_file(
+        format!(
+            "src/test/kotlin/{}/{lib_name}/DiplomatLayoutTest.kt",
+            domain.replace('.', "/")
+        ),
+        layout_test,
+    );
+
     (files, errors)
 }
 
+/// The packages of the library other than the one for `namespace`.
+fn package_imports(
+    tcx: &TypeContext,
+    formatter: &KotlinFormatter,
+    namespace: Option<&str>,
+    domain: &str,
+    lib_name: &str,
+) -> Vec<String> {
+    let namespaces = tcx
+        .all_types()
+        .map(|(_, ty)| ty.attrs())
+        .chain(tcx.all_traits().map(|(_, trt)| &trt.attrs))
+        .filter(|attrs| !attrs.disable)
+        .filter_map(|attrs| attrs.namespace.as_deref())
+        .map(|ns| formatter.fmt_namespace(ns))
+        .collect::<BTreeSet<_>>();
+    if namespaces.is_empty() {
+        return Vec::new();
+    }
+    namespace
+        .is_some()
+        .then(|| format!("{domain}.{lib_name}"))
+        .into_iter()
+        .chain(
+            namespaces
+                .iter()
+                .filter(|ns| Some(ns.as_str()) != namespace)
+                .map(|ns| format!("{domain}.{lib_name}.{ns}")),
+        )
+        .collect()
+}
+
 #[derive(Template, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 #[template(path = "kotlin/Option.kt.jinja")]
 struct TypeForResult<'d> {
@@ -245,6 +360,17 @@ struct TypeForResult<'d> {
     default: Option<Cow<'d, str>>,
 }
 
+impl TypeForResult<'_> {
+    fn ident(&self) -> String {
+        result_type_ident(&self.type_name)
+    }
+}
+
+/// The part of the name of a result or option class for a type, which may be qualified
+fn result_type_ident(type_name: &str) -> String {
+    type_name.replace('.', "_")
+}
+
 #[derive(Template, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 #[template(path = "kotlin/Result.kt.jinja")]
 struct NativeResult<'d> {
@@ -259,15 +385,29 @@ struct TyGenContext<'a, 'cx> {
     option_types: RefCell<BTreeSet<TypeForResult<'cx>>>,
     errors: &'a ErrorStore<'cx, String>,
     callback_params: &'a mut Vec<CallbackParamInfo>,
+    templates: &'a TemplateOverrides,
+    /// Whether to check that opaques which aren't `send` stay on their thread
+    thread_checks: bool,
 }
 
 impl<'a, 'cx> TyGenContext<'a, 'cx> {
+    fn class_prologue(&self, type_name: &str, kind: &'static str) -> Option<String> {
+        self.templates.class_prologue(
+            &ClassHookVars {
+                type_name,
+                namespace: None,
+                kind,
+            },
+            self.errors,
+        )
+    }
+
     fn gen_infallible_return_type_name(&self, success_type: &SuccessType) -> Cow<'cx, str> {
         match success_type {
             SuccessType::Unit => self.formatter.fmt_void().into(),
             SuccessType::Write => self.formatter.fmt_string().into(),
             SuccessType::OutType(ref o) => self.gen_type_name(o, None),
-            _ => panic!("Unsupported success type"),
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
     fn gen_return_type_name(&self, result_ty: &ReturnType) -> Cow<'cx, str> {
@@ -305,8 +445,9 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             Type::ImplTrait(ref trt) => {
                 let trait_id = trt.id();
                 let resolved = self.tcx.resolve_trait(trait_id);
+                let qualifier = self.formatter.fmt_trait_qualifier(trait_id);
                 let trait_name = resolved.name.to_string();
-                format!("DiplomatTrait_{trait_name}_Wrapper.fromTraitObj({name}).nativeStruct")
+                format!("{qualifier}DiplomatTrait_{trait_name}_Wrapper.fromTraitObj({name}).nativeStruct")
                     .into()
             }
             Type::Enum(_) => format!("{name}.toNative()").into(),
@@ -318,7 +459,14 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                 let real_param_name = name[name.rfind('_').unwrap() + 1..].to_string(); // past last _
                 format!("{name}.fromCallback({real_param_name}).nativeStruct").into()
             }
-            _ => todo!(),
+            Type::Read => format!("{name}Reader.read").into(),
+            Type::DiplomatOption(ref inner) => {
+                let option_type = self.gen_option_type_ffi(inner);
+                let inner_conversion = self.gen_kt_to_c_for_type(inner, "it".into());
+                format!("{name}?.let {{ {option_type}.some({inner_conversion}) }} ?: {option_type}.none()")
+                    .into()
+            }
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -327,7 +475,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             SuccessType::Unit => self.formatter.fmt_void().into(),
             SuccessType::Write => self.formatter.fmt_void().into(),
             SuccessType::OutType(ref o) => self.gen_type_name_ffi(o, None),
-            _ => panic!("Unsupported success type"),
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -382,7 +530,12 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                 let mut result_types = self.result_types.borrow_mut();
                 result_types.insert(result_type);
 
-                format!("Result{ok_type}{err_type}").into()
+                format!(
+                    "Result{}{}",
+                    result_type_ident(&ok_type),
+                    result_type_ident(&err_type)
+                )
+                .into()
             }
             ReturnType::Nullable(SuccessType::Unit | SuccessType::Write) => {
                 let mut option_types = self.option_types.borrow_mut();
@@ -392,28 +545,9 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                 });
                 "OptionUnit".into()
             }
-            ReturnType::Nullable(
-                ref success @ SuccessType::OutType(
-                    Type::Struct(..) | Type::Enum(..) | Type::Primitive(..),
-                ),
-            ) => {
-                let mut option_types = self.option_types.borrow_mut();
-                let infallible_return = self.gen_infallible_return_type_ffi(success);
-                let default = match success {
-                    SuccessType::OutType(Type::Struct(..) | Type::Enum(..)) => {
-                        format!("{infallible_return}()")
-                    }
-                    SuccessType::OutType(Type::Primitive(prim)) => {
-                        self.formatter.fmt_primitive_default(*prim).into()
-                    }
-                    _ => unreachable!("success type can only be one of the above"),
-                };
-                option_types.insert(TypeForResult {
-                    type_name: infallible_return.clone(),
-                    default: Some(default.into()),
-                });
-                format!("Option{infallible_return}").into()
-            }
+            ReturnType::Nullable(SuccessType::OutType(
+                ref o @ (Type::Struct(..) | Type::Enum(..) | Type::Primitive(..)),
+            )) => self.gen_option_type_ffi(o),
             ReturnType::Nullable(SuccessType::OutType(Type::Opaque(..))) => "Pointer?".into(),
             ReturnType::Nullable(SuccessType::OutType(Type::Slice(..))) => {
                 let mut option_types = self.option_types.borrow_mut();
@@ -423,7 +557,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
                 });
                 "OptionSlice".into()
             }
-            _ => panic!("unsupported return type"),
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -442,22 +576,44 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             Type::Struct(ref strct) => {
                 let type_id = strct.id();
                 let resolved = self.tcx.resolve_type(type_id);
-                format!("{}Native", resolved.name()).into()
+                let qualifier = self.formatter.fmt_type_qualifier(type_id);
+                format!("{qualifier}{}Native", resolved.name()).into()
             }
             Type::Enum(_) => "Int".into(),
             Type::Slice(_) => "Slice".into(),
+            Type::Read => "Pointer".into(),
             Type::Callback(_) => {
                 format!("DiplomatCallback_{}_Native", additional_name.unwrap()).into()
             }
             Type::ImplTrait(ref trt) => {
                 let trait_id = trt.id();
                 let resolved = self.tcx.resolve_trait(trait_id);
-                format!("DiplomatTrait_{}_Wrapper_Native", resolved.name).into()
+                let qualifier = self.formatter.fmt_trait_qualifier(trait_id);
+                format!("{qualifier}DiplomatTrait_{}_Wrapper_Native", resolved.name).into()
             }
+            Type::DiplomatOption(ref inner) => self.gen_option_type_ffi(inner),
             _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
+    /// The native `Option{T}` type used for a `DiplomatOption<T>`, or a nullable return of a struct, enum or primitive `T`.
+    ///
+    /// This has the same layout as `DiplomatOption<T>`, and gets generated in the library file.
+    fn gen_option_type_ffi<P: TyPosition>(&self, inner: &Type<P>) -> Cow<'cx, str> {
+        let type_name = self.gen_type_name_ffi(inner, None);
+        let default: Cow<'cx, str> = match inner {
+            Type::Struct(..) => format!("{type_name}()").into(),
+            Type::Enum(..) => "0".into(),
+            Type::Primitive(prim) => self.formatter.fmt_primitive_default(*prim).into(),
+            _ => unreachable!("DiplomatOption can only contain structs, enums and primitives"),
+        };
+        self.option_types.borrow_mut().insert(TypeForResult {
+            type_name: type_name.clone(),
+            default: Some(default),
+        });
+        format!("Option{}", result_type_ident(&type_name)).into()
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn gen_opaque_return_conversion<'d>(
         &'d self,
@@ -477,6 +633,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
         #[derive(Template)]
         #[template(path = "kotlin/OpaqueReturn.kt.jinja", escape = "none")]
         struct OpaqueReturn<'a, 'b> {
+            type_name: Cow<'b, str>,
             return_type_name: Cow<'b, str>,
             borrows: Vec<ParamsForLt<'b>>,
             is_owned: bool,
@@ -493,7 +650,13 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             params: Vec<Cow<'c, str>>,
         }
 
-        let return_type_name = opaque_def.name.to_string().into();
+        let type_name = opaque_def.name.to_string().into();
+        let return_type_name = format!(
+            "{}{}",
+            self.formatter.fmt_type_qualifier(opaque_path.tcx_id.into()),
+            opaque_def.name
+        )
+        .into();
         let self_edges = || match ownership {
             MaybeOwn::Borrow(Borrow {
                 lifetime: MaybeStatic::NonStatic(lt),
@@ -532,6 +695,7 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             .collect::<Vec<_>>();
 
         let opaque_return = OpaqueReturn {
+            type_name,
             return_type_name,
             borrows,
             is_owned,
@@ -547,10 +711,14 @@ impl<'a, 'cx> TyGenContext<'a, 'cx> {
             .expect("Failed to render opaque return block")
     }
 
-    fn write_return(return_type_modifier: &str) -> String {
+    fn write_return(encoding: WriteEncoding, return_type_modifier: &str) -> String {
+        let to_string = match encoding {
+            WriteEncoding::Utf16 => "writeToString16",
+            _ => "writeToString",
+        };
         format!(
             r#"
-val returnString = DW.writeToString(write)
+val returnString = DW.{to_string}(write)
 return returnString{return_type_modifier}"#
         )
     }
@@ -580,7 +748,7 @@ return string{return_type_modifier}"#
                 StringEncoding::Utf8 => {
                     format!("    return PrimitiveArrayTools.getUtf8({val_name})")
                 }
-                _ => todo!(),
+                _ => unreachable!("unknown AST/HIR variant"),
             },
             Slice::Str(None, enc) => match enc {
                 StringEncoding::UnvalidatedUtf16 => {
@@ -592,7 +760,7 @@ return string{return_type_modifier}"#
                 StringEncoding::Utf8 => {
                     Self::boxed_slice_return("Utf8", val_name, return_type_modifier)
                 }
-                _ => todo!(),
+                _ => unreachable!("unknown AST/HIR variant"),
             },
             Slice::Primitive(Some(_), prim_ty) => {
                 let prim_ty = self.formatter.fmt_primitive_as_ffi(*prim_ty);
@@ -603,8 +771,12 @@ return string{return_type_modifier}"#
                 let prim_ty_array = format!("{prim_ty}Array");
                 Self::boxed_slice_return(prim_ty_array.as_str(), val_name, return_type_modifier)
             }
-
-            _ => todo!(),
+            Slice::Strs(_) => {
+                self.errors
+                    .push_error("Returning slices of strings is not supported".into());
+                String::new()
+            }
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -612,6 +784,7 @@ return string{return_type_modifier}"#
     fn gen_struct_return_conversion<'d>(
         &'d self,
         struct_def: &'d ReturnableStructDef,
+        struct_id: TypeId,
         lifetimes: &'d Lifetimes,
         method_lifetimes_map: &'d MethodLtMap<'d>,
         lifetime_env: &'d LifetimeEnv,
@@ -625,10 +798,13 @@ return string{return_type_modifier}"#
             _ => false,
         };
 
+        let qualifier = self.formatter.fmt_type_qualifier(struct_id);
         let return_type_name = match struct_def {
-            ReturnableStructDef::Struct(strct) => strct.name.to_string().into(),
-            ReturnableStructDef::OutStruct(out_strct) => out_strct.name.to_string().into(),
-            _ => todo!(),
+            ReturnableStructDef::Struct(strct) => format!("{qualifier}{}", strct.name).into(),
+            ReturnableStructDef::OutStruct(out_strct) => {
+                format!("{qualifier}{}", out_strct.name).into()
+            }
+            _ => unreachable!("unknown AST/HIR variant"),
         };
 
         if is_zst {
@@ -706,6 +882,7 @@ return string{return_type_modifier}"#
                 let lifetimes = strct.lifetimes();
                 self.gen_struct_return_conversion(
                     &strct.resolve(self.tcx),
+                    strct.id(),
                     lifetimes,
                     method_lifetimes_map,
                     &method.lifetime_env,
@@ -717,14 +894,29 @@ return string{return_type_modifier}"#
             Type::Enum(enm) => {
                 let return_type = enm.resolve(self.tcx);
                 format!(
-                    "return {}.fromNative({val_name}){return_type_modifier}",
+                    "return {}{}.fromNative({val_name}){return_type_modifier}",
+                    self.formatter.fmt_type_qualifier(enm.tcx_id.into()),
                     return_type.name
                 )
             }
             Type::Slice(slc) => {
                 self.gen_slice_return_conversion(slc, val_name, return_type_modifier)
             }
-            _ => todo!(),
+            Type::DiplomatOption(inner) => format!(
+                r#"
+val intermediateOption = {val_name}.option() ?: return null{return_type_modifier}
+{}"#,
+                self.gen_out_type_return_conversion(
+                    method,
+                    method_lifetimes_map,
+                    cleanups,
+                    "intermediateOption",
+                    return_type_modifier,
+                    inner,
+                    use_finalizers_not_cleaners,
+                )
+            ),
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -760,6 +952,7 @@ val intermediateOption = {val_name}.option() ?: return null
                         "#,
                     self.gen_struct_return_conversion(
                         &strct.resolve(self.tcx),
+                        strct.id(),
                         lifetimes,
                         method_lifetimes_map,
                         &method.lifetime_env,
@@ -774,7 +967,8 @@ val intermediateOption = {val_name}.option() ?: return null
                 format!(
                     r#"
 val intermediateOption = {val_name}.option() ?: return null
-return {}.fromNative(intermediateOption)"#,
+return {}{}.fromNative(intermediateOption)"#,
+                    self.formatter.fmt_type_qualifier(enm.tcx_id.into()),
                     return_type.name
                 )
             }
@@ -787,7 +981,7 @@ val intermediateOption = {val_name}.option() ?: return null
                     self.gen_slice_return_conversion(slc, "intermediateOption", "")
                 )
             }
-            _ => todo!(),
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -803,7 +997,7 @@ val intermediateOption = {val_name}.option() ?: return null
         use_finalizers_not_cleaners: bool,
     ) -> String {
         match res {
-            SuccessType::Write => Self::write_return(return_type_postfix),
+            SuccessType::Write => Self::write_return(method.write_encoding, return_type_postfix),
             SuccessType::OutType(ref o) => self.gen_out_type_return_conversion(
                 method,
                 method_lifetimes_map,
@@ -815,7 +1009,7 @@ val intermediateOption = {val_name}.option() ?: return null
             ),
             SuccessType::Unit if return_type_postfix.is_empty() => "".into(),
             SuccessType::Unit => format!("return Unit{return_type_postfix}"),
-            _ => todo!(),
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -890,10 +1084,10 @@ val intermediateOption = {val_name}.option() ?: return null
 returnVal.option() ?: return null
 {}
                         "#,
-                Self::write_return("")
+                Self::write_return(method.write_encoding, "")
             ),
             ReturnType::Nullable(SuccessType::Unit) => "returnVal.option() ?: return null".into(),
-            _ => panic!("unsupported type"),
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -939,7 +1133,7 @@ returnVal.option() ?: return null
             Slice::Primitive(Some(_), _) => Some(format!("{param_name}Mem.close()").into()),
             Slice::Primitive(_, _) => None,
             Slice::Strs(_) => Some(format!("{param_name}Mem.forEach {{it.close()}}").into()),
-            _ => todo!(),
+            _ => unreachable!("unknown AST/HIR variant"),
         }
     }
 
@@ -949,6 +1143,7 @@ returnVal.option() ?: return null
         method: &'cx hir::Method,
         self_type: Option<&'cx SelfType>,
         struct_name: Option<&str>,
+        type_name: &str,
         use_finalizers_not_cleaners: bool,
     ) -> String {
         if method.attrs.disable {
@@ -959,10 +1154,12 @@ returnVal.option() ?: return null
         let native_method_name = method.abi_name.as_str();
 
         let mut param_decls_kt = Vec::with_capacity(method.params.len());
+        let mut param_names_kt = Vec::with_capacity(method.params.len());
         let mut param_types_ffi = Vec::with_capacity(method.params.len());
         let mut param_conversions = Vec::with_capacity(method.params.len());
         let mut slice_conversions = Vec::with_capacity(method.params.len());
         let mut cleanups = Vec::with_capacity(method.params.len());
+        let mut readers = Vec::new();
 
         match self_type {
             Some(st @ SelfType::Opaque(_)) => {
@@ -988,7 +1185,7 @@ returnVal.option() ?: return null
                 param_conversions.push(param_conversion.clone());
             }
             None => (),
-            _ => todo!(),
+            _ => unreachable!("unknown AST/HIR variant"),
         };
 
         for param in method.params.iter() {
@@ -1011,13 +1208,19 @@ returnVal.option() ?: return null
                         ParamBorrowInfo::BorrowedSlice => (),
                         ParamBorrowInfo::BorrowedOpaque => (),
                         ParamBorrowInfo::NotBorrowed => (),
-                        _ => todo!(),
+                        _ => unreachable!("unknown AST/HIR variant"),
                     };
                 }
 
                 Type::Struct(_) | Type::Opaque(_) => {
                     visitor.visit_param(&param.ty, &param_name);
                 }
+                Type::Read => {
+                    slice_conversions.push(
+                        format!("\nval {param_name}Reader = DiplomatReader({param_name})").into(),
+                    );
+                    readers.push(param_name.clone());
+                }
                 Type::Callback(Callback {
                     param_self: _,
                     params,
@@ -1066,7 +1269,16 @@ returnVal.option() ?: return null
                                 )
                             }
                             Type::Slice(_) => {
-                                panic!("Non-primitive slices are not allowed as callback args")
+                                self.errors.push_error(
+                                    "Non-primitive slices are not allowed as callback args".into(),
+                                );
+                                (in_name.clone(), format!("{}: Slice", in_name))
+                            }
+                            Type::DiplomatOption(_) => {
+                                self.errors.push_error(
+                                    "DiplomatOption is not supported in callbacks".into(),
+                                );
+                                (in_name.clone(), format!("{}: {}", in_name, in_ty))
                             }
                             Type::Opaque(_) => (
                                 format!("{}({}, listOf())", in_ty, in_name),
@@ -1081,6 +1293,12 @@ returnVal.option() ?: return null
                             match ty {
                                 Type::Enum(..) => ".toNative()",
                                 Type::Struct(..) => ".nativeStruct",
+                                Type::DiplomatOption(..) => {
+                                    self.errors.push_error(
+                                        "DiplomatOption is not supported in callbacks".into(),
+                                    );
+                                    ""
+                                }
                                 _ => "",
                             }
                             .into(),
@@ -1107,6 +1325,7 @@ returnVal.option() ?: return null
                 "{param_name}: {}",
                 self.gen_non_wrapped_type_name(&param.ty, additional_name.clone())
             ));
+            param_names_kt.push(param_name.clone());
             let param_name_to_pass = if let Type::Callback(_) = &param.ty {
                 self.gen_type_name(&param.ty, additional_name)
             } else {
@@ -1121,6 +1340,10 @@ returnVal.option() ?: return null
                 | ReturnType::Fallible(SuccessType::Write, _)
                 | ReturnType::Nullable(SuccessType::Write)
         );
+        let write_init = write_return.then(|| match method.write_encoding {
+            WriteEncoding::Utf16 => "DW.lib.diplomat_buffer_write16_create(0)",
+            _ => "DW.lib.diplomat_buffer_write_create(0)",
+        });
         if write_return {
             param_conversions.push("write".into());
         }
@@ -1129,7 +1352,7 @@ returnVal.option() ?: return null
         let return_ty = self.gen_return_type_name(&method.output);
 
         let method_lifetimes_map = visitor.borrow_map();
-        let return_expression = self
+        let mut return_expression: Cow<'_, str> = self
             .gen_return_conversion(
                 method,
                 method_lifetimes_map,
@@ -1138,14 +1361,21 @@ returnVal.option() ?: return null
             )
             .into();
 
+        // Exceptions thrown by the input streams of readers are rethrown once Rust is done reading
+        for reader in readers.iter().rev() {
+            return_expression = format!("{reader}Reader.finish()\n{return_expression}").into();
+        }
+
         // this should only be called in the special method generation below
         let non_option_type_name = |return_type: &ReturnType| match return_type {
             ReturnType::Infallible(ok) | ReturnType::Nullable(ok) => {
                 self.gen_infallible_return_type_name(ok)
             }
-            ReturnType::Fallible(_, _) => panic!(
-                "non_option_type_name should only be called for a return type that is optional"
-            ),
+            ReturnType::Fallible(_, _) => {
+                self.errors
+                    .push_error("Iterator and indexer methods cannot return a Result".into());
+                self.formatter.fmt_void().into()
+            }
         };
         let declaration = match method.attrs.special_method {
             Some(SpecialMethod::Iterator) => {
@@ -1154,7 +1384,9 @@ returnVal.option() ?: return null
                     special_methods.iterator_type = Some(non_option_ty.into());
                     format!("internal fun nextInternal({params}): {return_ty}")
                 } else {
-                    panic!("Can only have one iterator method per opaque struct")
+                    self.errors
+                        .push_error("Can only have one iterator method per opaque struct".into());
+                    String::new()
                 }
             }
             Some(SpecialMethod::Indexer) => {
@@ -1169,7 +1401,11 @@ returnVal.option() ?: return null
                                 ),
                             ..
                         }) => self.formatter.fmt_primitive_as_kt(*prim),
-                        _ => panic!("index type must be an integer type"),
+                        _ => {
+                            self.errors
+                                .push_error("index type must be an integer type".into());
+                            "Int"
+                        }
                     };
                     special_methods.indexer_type = Some(IndexerType {
                         index_type: index_type.into(),
@@ -1177,7 +1413,9 @@ returnVal.option() ?: return null
                     });
                     format!("internal fun getInternal({params}): {return_ty}")
                 } else {
-                    panic!("Can only have one indexer method per opaque struct")
+                    self.errors
+                        .push_error("Can only have one indexer method per opaque struct".into());
+                    String::new()
                 }
             }
             Some(SpecialMethod::Iterable) => {
@@ -1185,8 +1423,66 @@ returnVal.option() ?: return null
                     special_methods.iterable_type = Some(return_ty.to_string());
                     format!("override fun iterator(): {return_ty}")
                 } else {
-                    panic!("Can only have one iterable method per opaque struct")
+                    self.errors
+                        .push_error("Can only have one iterable method per opaque struct".into());
+                    String::new()
+                }
+            }
+            Some(SpecialMethod::Constructor) => {
+                special_methods
+                    .constructors
+                    .push(self.gen_constructor_factory(
+                        method,
+                        "operator fun invoke",
+                        &params,
+                        &param_names_kt,
+                    ));
+                format!(
+                    "fun {}({}): {return_ty}",
+                    self.formatter.fmt_method_name(method),
+                    params
+                )
+            }
+            Some(SpecialMethod::NamedConstructor(ref name)) => {
+                let method_name = self.formatter.fmt_method_name(method);
+                let ctor_name = match name {
+                    Some(name) => self.formatter.fmt_constructor_name(name),
+                    None => method_name.clone(),
+                };
+                // The method itself already is a factory with this name, unless it returns a `Result`,
+                // in which case the throwing version needs a different name.
+                let factory_name = match (ctor_name == method_name, &method.output) {
+                    (false, _) => Some(ctor_name),
+                    (true, ReturnType::Fallible(..)) => Some(format!("{ctor_name}OrThrow").into()),
+                    (true, _) => None,
+                };
+                if let Some(factory_name) = factory_name {
+                    special_methods
+                        .constructors
+                        .push(self.gen_constructor_factory(
+                            method,
+                            &format!("fun {factory_name}"),
+                            &params,
+                            &param_names_kt,
+                        ));
                 }
+                format!("fun {method_name}({params}): {return_ty}")
+            }
+            Some(SpecialMethod::Stringifier) => {
+                let method_name = self.formatter.fmt_method_name(method);
+                // `toString()` can't fail or return null
+                let unwrap = match method.output {
+                    ReturnType::Fallible(..) => ".getOrThrow()",
+                    ReturnType::Nullable(..) => " ?: \"null\"",
+                    ReturnType::Infallible(..) => "",
+                };
+                special_methods.stringifier = Some(format!("{method_name}(){unwrap}"));
+                format!("fun {method_name}({params}): {return_ty}")
+            }
+            Some(SpecialMethod::Comparison) => {
+                let method_name = self.formatter.fmt_method_name(method);
+                special_methods.comparator = Some(method_name.to_string());
+                format!("fun {method_name}({params}): {return_ty}")
             }
             _ => format!(
                 "fun {}({}): {return_ty}",
@@ -1195,18 +1491,109 @@ returnVal.option() ?: return null
             ),
         };
 
-        MethodTpl {
+        let method_name = declaration
+            .split("fun ")
+            .nth(1)
+            .and_then(|rest| rest.split('(').next())
+            .unwrap_or_default();
+        let hooks = self.templates.method_hooks(
+            &MethodHookVars {
+                type_name,
+                method_name,
+                abi_name: native_method_name,
+            },
+            self.errors,
+        );
+        let thread_check = self.thread_checks
+            && matches!(self_type, Some(SelfType::Opaque(o))
+                if self.tcx.resolve_opaque(o.tcx_id).attrs.thread_safety
+                    .is_thread_bound(o.owner.mutability.is_mutable()));
+
+        let docs = self.formatter.fmt_docs(&method.docs);
+
+        // Methods marked `stream` get a variant writing to an `OutputStream` instead of returning the string
+        let stream = method.attrs.stream.then(|| {
+            param_decls_kt.push("out: java.io.OutputStream".into());
+            let mut param_conversions = param_conversions.clone();
+            *param_conversions.last_mut().unwrap() = "write.write".into();
+            let mut docs = docs.clone();
+            if !docs.is_empty() {
+                docs.push_str("\n*\n*");
+            }
+            docs.push_str("Writes the output to [out] as it is written, instead of returning it.");
+            MethodTpl {
+                declaration: format!(
+                    "fun {}({}): Unit",
+                    self.formatter.fmt_stream_method_name(method),
+                    param_decls_kt.join(", ")
+                ),
+                native_method_name,
+                param_conversions,
+                return_expression: "write.finish()".into(),
+                write_init: Some("DiplomatStreamWrite(out)"),
+                slice_conversions: slice_conversions.clone(),
+                thread_check,
+                docs,
+                hooks: hooks.clone(),
+            }
+            .render()
+            .expect("Failed to render string for method")
+        });
+
+        let method = MethodTpl {
             // todo: comment,
             declaration,
             native_method_name,
             param_conversions,
             return_expression,
-            write_return,
+            write_init,
             slice_conversions,
+            thread_check,
+            docs,
+            hooks,
+        }
+        .render()
+        .expect("Failed to render string for method");
+
+        match stream {
+            Some(stream) => format!("{method}\n{stream}"),
+            None => method,
+        }
+    }
+
+    /// Generates a companion object factory forwarding to a constructor method, throwing
+    /// if the constructor returns an error.
+    fn gen_constructor_factory(
+        &self,
+        method: &'cx hir::Method,
+        declaration: &str,
+        params: &str,
+        param_names: &[Cow<'cx, str>],
+    ) -> String {
+        let (return_ty, fallible) = match &method.output {
+            ReturnType::Fallible(ok, _) => (self.gen_infallible_return_type_name(ok), true),
+            output => (self.gen_return_type_name(output), false),
+        };
+
+        #[derive(Template)]
+        #[template(path = "kotlin/ConstructorFactory.kt.jinja", escape = "none")]
+        struct ConstructorFactory<'a> {
+            declaration: String,
+            method_name: Cow<'a, str>,
+            param_names: &'a [Cow<'a, str>],
+            fallible: bool,
+            docs: String,
+        }
+
+        ConstructorFactory {
+            declaration: format!("{declaration}({params}): {return_ty}"),
+            method_name: self.formatter.fmt_method_name(method),
+            param_names,
+            fallible,
             docs: self.formatter.fmt_docs(&method.docs),
         }
         .render()
-        .expect("Failed to render string for method")
+        .expect("Failed to render constructor factory")
     }
 
     fn gen_native_method_info(
@@ -1227,7 +1614,7 @@ returnVal.option() ?: return null
                     self.tcx.resolve_struct(s.tcx_id).name.as_str()
                 )),
                 SelfType::Enum(_) => param_decls.push("inner: Int".into()),
-                _ => todo!(),
+                _ => unreachable!("unknown AST/HIR variant"),
             }
         };
         for param in method.params.iter() {
@@ -1265,6 +1652,37 @@ returnVal.option() ?: return null
         }
     }
 
+    /// The directory of the files of a type in `namespace`, its sub-package of the library package,
+    /// and the other packages of the library it imports.
+    ///
+    /// Types in other namespaces are referenced unqualified, so all other packages are imported.
+    /// Names declared in several namespaces are referenced by their fully qualified name instead,
+    /// see [`KotlinFormatter::fmt_type_qualifier`].
+    fn gen_package(
+        &self,
+        namespace: Option<&str>,
+        domain: &str,
+        lib_name: &str,
+    ) -> (String, Option<String>, Vec<String>) {
+        let namespace = namespace.map(|ns| self.formatter.fmt_namespace(ns));
+        let dir = match namespace {
+            Some(ref ns) => format!(
+                "{}/{lib_name}/{}",
+                domain.replace('.', "/"),
+                ns.replace('.', "/")
+            ),
+            None => format!("{}/{lib_name}", domain.replace('.', "/")),
+        };
+        let imports = package_imports(
+            self.tcx,
+            self.formatter,
+            namespace.as_deref(),
+            domain,
+            lib_name,
+        );
+        (dir, namespace, imports)
+    }
+
     fn gen_opaque_def(
         &mut self,
         ty: &'cx hir::OpaqueDef,
@@ -1297,12 +1715,12 @@ returnVal.option() ?: return null
                     method,
                     Some(self_param),
                     None,
+                    type_name,
                     use_finalizers_not_cleaners,
                 )
             })
             .collect::<Vec<_>>();
 
-        let mut unused_special_methods = SpecialMethods::default();
         let companion_methods = ty
             .methods
             .iter()
@@ -1310,10 +1728,11 @@ returnVal.option() ?: return null
             .filter(|method| method.param_self.is_none())
             .map(|method| {
                 self.gen_method(
-                    &mut unused_special_methods,
+                    &mut special_methods,
                     method,
                     None,
                     None,
+                    type_name,
                     use_finalizers_not_cleaners,
                 )
             })
@@ -1335,6 +1754,8 @@ returnVal.option() ?: return null
         struct ImplTemplate<'a> {
             domain: &'a str,
             lib_name: &'a str,
+            namespace: Option<String>,
+            package_imports: Vec<String>,
             type_name: &'a str,
             dtor_abi_name: &'a str,
             self_methods: &'a [String],
@@ -1344,24 +1765,41 @@ returnVal.option() ?: return null
             special_methods: SpecialMethodsImpl,
             callback_params: &'a [CallbackParamInfo],
             use_finalizers_not_cleaners: bool,
+            thread_checks: bool,
             docs: String,
+            class_prologue: Option<String>,
+        }
+
+        let (dir, namespace, package_imports) =
+            self.gen_package(ty.attrs.namespace.as_deref(), domain, lib_name);
+
+        let mut docs = self.formatter.fmt_docs(&ty.docs);
+        if let Some(thread_safety_docs) = ty.attrs.thread_safety.docs() {
+            if !docs.is_empty() {
+                docs.push_str("\n*\n*");
+            }
+            docs.push_str(thread_safety_docs);
         }
 
         (
-            format!("{}/{lib_name}/{type_name}.kt", domain.replace('.', "/")),
+            format!("{dir}/{type_name}.kt"),
             ImplTemplate {
                 domain,
                 lib_name,
+                namespace,
+                package_imports,
                 type_name,
                 dtor_abi_name: ty.dtor_abi_name.as_str(),
                 self_methods: self_methods.as_ref(),
                 companion_methods: companion_methods.as_ref(),
                 native_methods: native_methods.as_ref(),
                 lifetimes,
-                special_methods: SpecialMethodsImpl::new(special_methods),
+                special_methods: SpecialMethodsImpl::new(special_methods, type_name),
                 callback_params: self.callback_params.as_ref(),
                 use_finalizers_not_cleaners,
-                docs: self.formatter.fmt_docs(&ty.docs),
+                class_prologue: self.class_prologue(type_name, "opaque"),
+                thread_checks: self.thread_checks && !ty.attrs.thread_safety.send,
+                docs,
             }
             .render()
             .expect("failed to generate struct"),
@@ -1383,7 +1821,7 @@ returnVal.option() ?: return null
             .map(|method| self.gen_native_method_info(method, type_name))
             .collect::<Vec<_>>();
 
-        let mut unused_special_methods = SpecialMethods::default();
+        let mut special_methods = SpecialMethods::default();
         let self_methods = ty
             .methods
             .iter()
@@ -1395,10 +1833,11 @@ returnVal.option() ?: return null
             })
             .map(|(self_param, method)| {
                 self.gen_method(
-                    &mut unused_special_methods,
+                    &mut special_methods,
                     method,
                     Some(self_param),
                     Some(type_name),
+                    type_name,
                     use_finalizers_not_cleaners,
                 )
             })
@@ -1410,10 +1849,11 @@ returnVal.option() ?: return null
             .filter(|method| method.param_self.is_none())
             .map(|method| {
                 self.gen_method(
-                    &mut unused_special_methods,
+                    &mut special_methods,
                     method,
                     None,
                     Some(type_name),
+                    type_name,
                     use_finalizers_not_cleaners,
                 )
             })
@@ -1444,6 +1884,8 @@ returnVal.option() ?: return null
         struct ImplTemplate<'a> {
             domain: &'a str,
             lib_name: &'a str,
+            namespace: Option<String>,
+            package_imports: Vec<String>,
             type_name: &'a str,
             fields: Vec<StructFieldDef<'a>>,
             self_methods: &'a [String],
@@ -1451,14 +1893,29 @@ returnVal.option() ?: return null
             native_methods: &'a [NativeMethodInfo],
             callback_params: &'a [CallbackParamInfo],
             lifetimes: Vec<Cow<'a, str>>,
+            special_methods: SpecialMethodsImpl,
             docs: String,
+            class_prologue: Option<String>,
         }
 
         let fields = ty
             .fields
             .iter()
+            .filter(|field: &&StructField<P>| match field.ty {
+                Type::ImplTrait(..) | Type::Callback(..) => {
+                    self.errors.push_error(format!(
+                        "Trait and callback struct fields are not supported: {}",
+                        field.name.as_str()
+                    ));
+                    false
+                }
+                _ => true,
+            })
             .map(|field: &StructField<P>| {
                 let field_name = self.formatter.fmt_field_name(field.name.as_str());
+                if let Type::DiplomatOption(ref inner) = field.ty {
+                    self.gen_option_type_ffi(inner);
+                }
 
                 StructFieldDef {
                     name: field_name.clone(),
@@ -1475,11 +1932,16 @@ returnVal.option() ?: return null
             })
             .collect();
 
+        let (dir, namespace, package_imports) =
+            self.gen_package(ty.attrs.namespace.as_deref(), domain, lib_name);
+
         (
-            format!("{}/{lib_name}/{type_name}.kt", domain.replace('.', "/"),),
+            format!("{dir}/{type_name}.kt"),
             ImplTemplate {
                 domain,
                 lib_name,
+                namespace,
+                package_imports,
                 type_name,
                 fields,
                 self_methods: self_methods.as_ref(),
@@ -1487,7 +1949,9 @@ returnVal.option() ?: return null
                 native_methods: native_methods.as_ref(),
                 callback_params: self.callback_params.as_ref(),
                 lifetimes,
+                special_methods: SpecialMethodsImpl::new(special_methods, type_name),
                 docs: self.formatter.fmt_docs(&ty.docs),
+                class_prologue: self.class_prologue(type_name, "struct"),
             }
             .render()
             .expect("Failed to render struct template"),
@@ -1539,7 +2003,15 @@ returnVal.option() ?: return null
                         )
                     }
                     Type::Slice(_) => {
-                        panic!("Non-primitive slices are not allowed as callback args")
+                        self.errors.push_error(
+                            "Non-primitive slices are not allowed as callback args".into(),
+                        );
+                        (in_name.clone(), format!("{}: Slice", in_name))
+                    }
+                    Type::DiplomatOption(_) => {
+                        self.errors
+                            .push_error("DiplomatOption is not supported in trait methods".into());
+                        (in_name.clone(), format!("{}: {}", in_name, in_ty))
                     }
                     Type::Opaque(_) => (
                         format!("{}({}, listOf())", in_ty, in_name),
@@ -1564,6 +2036,11 @@ returnVal.option() ?: return null
                 match ty {
                     Type::Enum(..) => ".toNative()",
                     Type::Struct(..) => ".nativeStruct",
+                    Type::DiplomatOption(..) => {
+                        self.errors
+                            .push_error("DiplomatOption is not supported in trait methods".into());
+                        ""
+                    }
                     _ => "",
                 }
                 .into(),
@@ -1617,6 +2094,8 @@ returnVal.option() ?: return null
         struct ImplTemplate<'a> {
             domain: &'a str,
             lib_name: &'a str,
+            namespace: Option<String>,
+            package_imports: Vec<String>,
             trait_name: &'a str,
             trait_methods: &'a [TraitMethodInfo],
             trait_method_names: &'a str,
@@ -1624,11 +2103,16 @@ returnVal.option() ?: return null
             docs: String,
         }
 
+        let (dir, namespace, package_imports) =
+            self.gen_package(trt.attrs.namespace.as_deref(), domain, lib_name);
+
         (
-            format!("{}/{lib_name}/{trait_name}.kt", domain.replace('.', "/"),),
+            format!("{dir}/{trait_name}.kt"),
             ImplTemplate {
                 domain,
                 lib_name,
+                namespace,
+                package_imports,
                 trait_name,
                 trait_methods: trait_methods.as_ref(),
                 callback_params: self.callback_params.as_ref(),
@@ -1672,6 +2156,7 @@ returnVal.option() ?: return null
                     method,
                     Some(self_param),
                     None,
+                    type_name,
                     use_finalizers_not_cleaners,
                 )
             })
@@ -1688,6 +2173,7 @@ returnVal.option() ?: return null
                     method,
                     None,
                     None,
+                    type_name,
                     use_finalizers_not_cleaners,
                 )
             })
@@ -1749,35 +2235,46 @@ returnVal.option() ?: return null
         struct EnumDef<'d> {
             lib_name: Cow<'d, str>,
             domain: Cow<'d, str>,
+            namespace: Option<String>,
+            package_imports: Vec<String>,
             type_name: Cow<'d, str>,
             variants: &'d EnumVariants<'d>,
             self_methods: &'d [String],
             companion_methods: &'d [String],
             native_methods: &'d [NativeMethodInfo],
             callback_params: &'d [CallbackParamInfo],
+            special_methods: SpecialMethodsImpl,
             docs: String,
+            class_prologue: Option<String>,
         }
 
         let variants = EnumVariants::new(ty);
 
+        // Kotlin enums are always `Comparable` by their declaration order, and `compareTo` cannot be overridden.
+        special_methods.comparator = None;
+
+        let (dir, namespace, package_imports) =
+            self.gen_package(ty.attrs.namespace.as_deref(), domain, lib_name);
+
         let enum_def = EnumDef {
             lib_name: lib_name.into(),
             domain: domain.into(),
+            namespace,
+            package_imports,
             type_name: type_name.into(),
             variants: &variants,
             self_methods: self_methods.as_ref(),
             companion_methods: companion_methods.as_ref(),
             native_methods: native_methods.as_ref(),
             callback_params: self.callback_params.as_ref(),
+            special_methods: SpecialMethodsImpl::new(special_methods, type_name),
             docs: self.formatter.fmt_docs(&ty.docs),
+            class_prologue: self.class_prologue(type_name, "enum"),
         }
         .render()
         .unwrap_or_else(|err| panic!("Failed to render Enum {{type_name}}\n\tcause: {err}"));
 
-        (
-            format!("{}/{lib_name}/{type_name}.kt", domain.replace('.', "/"),),
-            enum_def,
-        )
+        (format!("{dir}/{type_name}.kt"), enum_def)
     }
 
     fn gen_native_type_name<P: TyPosition>(
@@ -1793,19 +2290,22 @@ returnVal.option() ?: return null
             }
             Type::Struct(ref strct) => {
                 let op_id = strct.id();
-                format!("{}Native", self.formatter.fmt_type_name(op_id)).into()
+                format!("{}Native", self.formatter.fmt_type_ref(op_id)).into()
             }
             Type::Enum(_) => "Int".into(),
             Type::Slice(_) => "Slice".into(),
+            Type::Read => "Pointer".into(),
             Type::Callback(_) => self.gen_type_name(ty, additional_name),
             Type::ImplTrait(ref trt) => {
                 let op_id = trt.id();
                 format!(
-                    "DiplomatTrait_{}_Wrapper_Native",
+                    "{}DiplomatTrait_{}_Wrapper_Native",
+                    self.formatter.fmt_trait_qualifier(op_id),
                     self.formatter.fmt_trait_name(op_id)
                 )
                 .into()
             }
+            Type::DiplomatOption(ref inner) => self.gen_option_type_ffi(inner),
             _ => unreachable!("unknown AST/HIR variant"),
         }
     }
@@ -1819,7 +2319,7 @@ returnVal.option() ?: return null
             Type::Primitive(prim) => self.formatter.fmt_primitive_as_kt(prim).into(),
             Type::Opaque(ref op) => {
                 let op_id = op.tcx_id.into();
-                let type_name = self.formatter.fmt_type_name(op_id);
+                let type_name = self.formatter.fmt_type_ref(op_id);
 
                 if self.tcx.resolve_type(op_id).attrs().disable {
                     self.errors
@@ -1835,23 +2335,29 @@ returnVal.option() ?: return null
             }
             Type::Struct(ref strct) => {
                 let op_id = strct.id();
-                self.formatter.fmt_type_name(op_id)
+                self.formatter.fmt_type_ref(op_id)
             }
             Type::ImplTrait(ref trt) => {
                 let op_id = trt.id();
                 format!(
-                    "DiplomatTrait_{}_Wrapper",
+                    "{}DiplomatTrait_{}_Wrapper",
+                    self.formatter.fmt_trait_qualifier(op_id),
                     self.formatter.fmt_trait_name(op_id)
                 )
                 .into()
             }
-            Type::Enum(ref enum_def) => self.formatter.fmt_type_name(enum_def.tcx_id.into()),
+            Type::Enum(ref enum_def) => self.formatter.fmt_type_ref(enum_def.tcx_id.into()),
             Type::Slice(hir::Slice::Str(_, _)) => self.formatter.fmt_string().into(),
             Type::Slice(hir::Slice::Primitive(_, ty)) => {
                 self.formatter.fmt_primitive_slice(ty).into()
             }
             Type::Callback(_) => format!("DiplomatCallback_{}", additional_name.unwrap()).into(),
             Type::Slice(hir::Slice::Strs(_)) => self.formatter.fmt_str_slices().into(),
+            Type::Read => "java.io.InputStream".into(),
+            Type::DiplomatOption(ref inner) => self
+                .formatter
+                .fmt_nullable(&self.gen_type_name(inner, None))
+                .into(),
             _ => unreachable!("unknown AST/HIR variant"),
         }
     }
@@ -1887,7 +2393,8 @@ returnVal.option() ?: return null
             Type::ImplTrait(trt) => {
                 let trait_id = trt.id();
                 let resolved = self.tcx.resolve_trait(trait_id);
-                resolved.name.to_string().into()
+                let qualifier = self.formatter.fmt_trait_qualifier(trait_id);
+                format!("{qualifier}{}", resolved.name).into()
             }
             _ => self.gen_type_name(ty, additional_name),
         }
@@ -1901,6 +2408,12 @@ struct SpecialMethods {
     iterator_type: Option<String>,
     indexer_type: Option<IndexerType>,
     iterable_type: Option<String>,
+    /// Companion object factories generated for constructors
+    constructors: Vec<String>,
+    /// The expression `toString()` returns, calling the stringifier method
+    stringifier: Option<String>,
+    /// The method `compareTo()` forwards to
+    comparator: Option<String>,
 }
 
 struct IndexerType {
@@ -1912,6 +2425,9 @@ struct IndexerType {
 struct SpecialMethodsImpl {
     iterator_type: Option<String>,
     indexer_type: Option<IndexerType>,
+    constructors: Vec<String>,
+    stringifier: Option<String>,
+    comparator: Option<String>,
     interfaces: Vec<String>,
 }
 
@@ -1921,7 +2437,11 @@ impl SpecialMethodsImpl {
             iterator_type,
             indexer_type,
             iterable_type,
+            constructors,
+            stringifier,
+            comparator,
         }: SpecialMethods,
+        type_name: &str,
     ) -> Self {
         let interfaces = iterator_type
             .iter()
@@ -1931,10 +2451,18 @@ impl SpecialMethodsImpl {
                     .iter()
                     .map(|iterable_type| format!("Iterable<{iterable_type}IteratorItem>")),
             )
+            .chain(
+                comparator
+                    .iter()
+                    .map(|_| format!("Comparable<{type_name}>")),
+            )
             .collect();
         Self {
             iterator_type,
             indexer_type,
+            constructors,
+            stringifier,
+            comparator,
             interfaces,
         }
     }
@@ -1951,9 +2479,13 @@ struct MethodTpl<'a> {
     /// Conversion code for each parameter
     param_conversions: Vec<Cow<'a, str>>,
     return_expression: Cow<'a, str>,
-    write_return: bool,
+    /// The expression creating the `write`, if the method returns a string
+    write_init: Option<&'static str>,
     slice_conversions: Vec<Cow<'a, str>>,
+    /// Whether to check that `this` is used on the thread that created it
+    thread_check: bool,
     docs: String,
+    hooks: MethodHooks,
 }
 
 struct NativeMethodInfo {
@@ -2041,7 +2573,8 @@ mod test {
             let docs_urls = HashMap::new();
             let docs_generator =
                 diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
-            let formatter = KotlinFormatter::new(&tcx, None, &docs_generator);
+            let formatter =
+                KotlinFormatter::new(&tcx, None, &docs_generator, "dev.gigapixel.somelib".into());
             let mut callback_params = Vec::new();
             let mut ty_gen_cx = TyGenContext {
                 tcx: &tcx,
@@ -2050,6 +2583,8 @@ mod test {
                 option_types: RefCell::new(BTreeSet::new()),
                 errors: &error_store,
                 callback_params: &mut callback_params,
+                templates: &Default::default(),
+                thread_checks: false,
             };
             let type_name = enum_def.name.to_string();
             // test that we can render and that it doesn't panic
@@ -2129,7 +2664,8 @@ mod test {
             let docs_urls = HashMap::new();
             let docs_generator =
                 diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
-            let formatter = KotlinFormatter::new(&tcx, None, &docs_generator);
+            let formatter =
+                KotlinFormatter::new(&tcx, None, &docs_generator, "dev.gigapixel.somelib".into());
             let mut callback_params = Vec::new();
             let mut ty_gen_cx = TyGenContext {
                 tcx: &tcx,
@@ -2138,6 +2674,8 @@ mod test {
                 option_types: RefCell::new(BTreeSet::new()),
                 errors: &error_store,
                 callback_params: &mut callback_params,
+                templates: &Default::default(),
+                thread_checks: false,
             };
             let type_name = strct.name.to_string();
             // test that we can render and that it doesn't panic
@@ -2181,7 +2719,8 @@ mod test {
             let docs_urls = HashMap::new();
             let docs_generator =
                 diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
-            let formatter = KotlinFormatter::new(&tcx, None, &docs_generator);
+            let formatter =
+                KotlinFormatter::new(&tcx, None, &docs_generator, "dev.gigapixel.somelib".into());
             let mut callback_params = Vec::new();
             let mut ty_gen_cx = TyGenContext {
                 tcx: &tcx,
@@ -2190,6 +2729,8 @@ mod test {
                 option_types: RefCell::new(BTreeSet::new()),
                 errors: &eror_store,
                 callback_params: &mut callback_params,
+                templates: &Default::default(),
+                thread_checks: false,
             };
             let type_name = opaque_def.name.to_string();
             // test that we can render and that it doesn't panic
@@ -2290,7 +2831,8 @@ mod test {
             let docs_urls = HashMap::new();
             let docs_generator =
                 diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
-            let formatter = KotlinFormatter::new(&tcx, None, &docs_generator);
+            let formatter =
+                KotlinFormatter::new(&tcx, None, &docs_generator, "dev.gigapixel.somelib".into());
             let mut callback_params = Vec::new();
             let mut ty_gen_cx = TyGenContext {
                 tcx: &tcx,
@@ -2299,6 +2841,8 @@ mod test {
                 option_types: RefCell::new(BTreeSet::new()),
                 errors: &eror_store,
                 callback_params: &mut callback_params,
+                templates: &Default::default(),
+                thread_checks: false,
             };
             let type_name = opaque_def.name.to_string();
             // test that we can render and that it doesn't panic
@@ -2341,7 +2885,8 @@ mod test {
             let docs_urls = HashMap::new();
             let docs_generator =
                 diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
-            let formatter = KotlinFormatter::new(&tcx, None, &docs_generator);
+            let formatter =
+                KotlinFormatter::new(&tcx, None, &docs_generator, "dev.gigapixel.somelib".into());
             let mut callback_params = Vec::new();
             let mut ty_gen_cx = TyGenContext {
                 tcx: &tcx,
@@ -2350,6 +2895,8 @@ mod test {
                 option_types: RefCell::new(BTreeSet::new()),
                 errors: &eror_store,
                 callback_params: &mut callback_params,
+                templates: &Default::default(),
+                thread_checks: false,
             };
             let type_name = opaque_def.name.to_string();
             // test that we can render and that it doesn't panic
@@ -2359,6 +2906,232 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_opaque_thread_checks() {
+        let tk_stream = quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                /// A counter
+                #[diplomat::opaque]
+                #[diplomat::attr(*, sync)]
+                struct Counter(i32);
+
+                impl Counter {
+                    pub fn get(&self) -> i32 {
+                        unimplemented!()
+                    }
+
+                    pub fn increment(&mut self) {
+                        unimplemented!()
+                    }
+                }
+            }
+        };
+        let tcx = new_tcx(tk_stream);
+        let mut all_types = tcx.all_types();
+        if let (_id, TypeDef::Opaque(opaque_def)) = all_types
+            .next()
+            .expect("Failed to generate first opaque def")
+        {
+            let error_store = ErrorStore::default();
+            let docs_urls = HashMap::new();
+            let docs_generator =
+                diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
+            let formatter =
+                KotlinFormatter::new(&tcx, None, &docs_generator, "dev.gigapixel.somelib".into());
+            let mut callback_params = Vec::new();
+            let mut ty_gen_cx = TyGenContext {
+                tcx: &tcx,
+                formatter: &formatter,
+                result_types: RefCell::new(BTreeSet::new()),
+                option_types: RefCell::new(BTreeSet::new()),
+                errors: &error_store,
+                callback_params: &mut callback_params,
+                templates: &Default::default(),
+                thread_checks: true,
+            };
+            let type_name = opaque_def.name.to_string();
+            let (_, result) =
+                ty_gen_cx.gen_opaque_def(opaque_def, &type_name, "dev.gigapixel", "somelib", false);
+            insta::assert_snapshot!(result)
+        }
+    }
+
+    #[test]
+    fn test_special_methods() {
+        let tk_stream = quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Counter(i32);
+
+                impl Counter {
+                    #[diplomat::attr(auto, constructor)]
+                    pub fn new(start: i32) -> Box<Counter> {
+                        unimplemented!()
+                    }
+
+                    #[diplomat::attr(auto, named_constructor = "with_limit")]
+                    pub fn create(start: i32, limit: i32) -> Result<Box<Counter>, ()> {
+                        unimplemented!()
+                    }
+
+                    #[diplomat::attr(auto, stringifier)]
+                    pub fn dump(&self, w: &mut DiplomatWrite) -> Result<(), ()> {
+                        unimplemented!()
+                    }
+
+                    #[diplomat::attr(auto, comparison)]
+                    pub fn compare(&self, other: &Counter) -> std::cmp::Ordering {
+                        unimplemented!()
+                    }
+                }
+            }
+        };
+        let tcx = new_tcx(tk_stream);
+        let mut all_types = tcx.all_types();
+        if let (_id, TypeDef::Opaque(opaque_def)) = all_types
+            .next()
+            .expect("Failed to generate first opaque def")
+        {
+            let error_store = ErrorStore::default();
+            let docs_urls = HashMap::new();
+            let docs_generator =
+                diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
+            let formatter =
+                KotlinFormatter::new(&tcx, None, &docs_generator, "dev.gigapixel.somelib".into());
+            let mut callback_params = Vec::new();
+            let mut ty_gen_cx = TyGenContext {
+                tcx: &tcx,
+                formatter: &formatter,
+                result_types: RefCell::new(BTreeSet::new()),
+                option_types: RefCell::new(BTreeSet::new()),
+                errors: &error_store,
+                callback_params: &mut callback_params,
+                templates: &Default::default(),
+                thread_checks: false,
+            };
+            let type_name = opaque_def.name.to_string();
+            let (_, result) =
+                ty_gen_cx.gen_opaque_def(opaque_def, &type_name, "dev.gigapixel", "somelib", false);
+            insta::assert_snapshot!(result)
+        }
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let tk_stream = quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                #[diplomat::attr(auto, namespace = "util::counting")]
+                struct Counter(i32);
+
+                #[diplomat::opaque]
+                #[diplomat::attr(auto, namespace = "other")]
+                struct Other(i32);
+
+                #[diplomat::opaque]
+                struct Root(i32);
+            }
+        };
+        let tcx = new_tcx(tk_stream);
+        let mut all_types = tcx.all_types();
+        if let (_id, TypeDef::Opaque(opaque_def)) = all_types
+            .next()
+            .expect("Failed to generate first opaque def")
+        {
+            let error_store = ErrorStore::default();
+            let docs_urls = HashMap::new();
+            let docs_generator =
+                diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
+            let formatter =
+                KotlinFormatter::new(&tcx, None, &docs_generator, "dev.gigapixel.somelib".into());
+            let mut callback_params = Vec::new();
+            let mut ty_gen_cx = TyGenContext {
+                tcx: &tcx,
+                formatter: &formatter,
+                result_types: RefCell::new(BTreeSet::new()),
+                option_types: RefCell::new(BTreeSet::new()),
+                errors: &error_store,
+                callback_params: &mut callback_params,
+                templates: &Default::default(),
+                thread_checks: false,
+            };
+            let type_name = opaque_def.name.to_string();
+            let (file_name, result) =
+                ty_gen_cx.gen_opaque_def(opaque_def, &type_name, "dev.gigapixel", "somelib", false);
+            assert_eq!(file_name, "dev/gigapixel/somelib/util/counting/Counter.kt");
+            insta::assert_snapshot!(result)
+        }
+    }
+
+    #[test]
+    fn test_namespace_clash() {
+        let tk_stream = quote! {
+            mod a {
+                #[diplomat::bridge]
+                pub mod ffi {
+                    #[diplomat::opaque]
+                    #[diplomat::attr(auto, namespace = "a")]
+                    pub struct Counter(i32);
+                }
+            }
+
+            mod b {
+                #[diplomat::bridge]
+                pub mod ffi {
+                    #[diplomat::attr(auto, namespace = "b")]
+                    pub struct Counter {
+                        pub count: i32,
+                    }
+
+                    #[diplomat::opaque]
+                    #[diplomat::attr(auto, namespace = "b")]
+                    pub struct User(i32);
+
+                    impl User {
+                        pub fn a_counter(&self) -> Box<crate::a::ffi::Counter> {
+                            todo!()
+                        }
+
+                        pub fn b_counter(&self) -> Counter {
+                            todo!()
+                        }
+                    }
+                }
+            }
+        };
+        let tcx = new_tcx(tk_stream);
+        let (_id, user) = tcx
+            .all_types()
+            .find(|(_, ty)| ty.name().as_str() == "User")
+            .expect("Failed to find User");
+        let TypeDef::Opaque(opaque_def) = user else {
+            panic!("User is not opaque");
+        };
+        let error_store = ErrorStore::default();
+        let docs_urls = HashMap::new();
+        let docs_generator = diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
+        let formatter =
+            KotlinFormatter::new(&tcx, None, &docs_generator, "dev.gigapixel.somelib".into());
+        let mut callback_params = Vec::new();
+        let mut ty_gen_cx = TyGenContext {
+            tcx: &tcx,
+            formatter: &formatter,
+            result_types: RefCell::new(BTreeSet::new()),
+            option_types: RefCell::new(BTreeSet::new()),
+            errors: &error_store,
+            callback_params: &mut callback_params,
+            templates: &Default::default(),
+            thread_checks: false,
+        };
+        let type_name = opaque_def.name.to_string();
+        let (_, result) =
+            ty_gen_cx.gen_opaque_def(opaque_def, &type_name, "dev.gigapixel", "somelib", false);
+        insta::assert_snapshot!(result)
+    }
+
     #[test]
     fn test_trait_gen() {
         let tk_stream = quote! {
@@ -2405,7 +3178,8 @@ mod test {
         let error_store = ErrorStore::default();
         let docs_urls = HashMap::new();
         let docs_generator = diplomat_core::hir::DocsUrlGenerator::with_base_urls(None, docs_urls);
-        let formatter = KotlinFormatter::new(&tcx, None, &docs_generator);
+        let formatter =
+            KotlinFormatter::new(&tcx, None, &docs_generator, "dev.gigapixel.somelib".into());
         let mut callback_params = Vec::new();
         let mut ty_gen_cx = TyGenContext {
             tcx: &tcx,
@@ -2414,6 +3188,8 @@ mod test {
             option_types: RefCell::new(BTreeSet::new()),
             errors: &error_store,
             callback_params: &mut callback_params,
+            templates: &Default::default(),
+            thread_checks: false,
         };
         let trait_name = trait_def.name.to_string();
         // test that we can render and that it doesn't panic
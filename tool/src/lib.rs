@@ -3,12 +3,18 @@
 
 // Backends
 pub mod c;
-mod mojo;
 mod cpp;
 mod dart;
 mod demo_gen;
+mod diagnostics;
+mod diff;
 mod js;
+mod json;
 mod kotlin;
+mod mojo;
+mod rust_paths;
+mod templates;
+mod wit;
 
 use colored::*;
 use core::mem;
@@ -18,10 +24,26 @@ use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+pub use diagnostics::MessageFormat;
 pub use hir::DocsUrlGenerator;
 
+/// Options for [`gen_with_options()`] that [`gen()`] leaves at their defaults.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct GenOptions {
+    /// How to print lowering errors
+    pub message_format: MessageFormat,
+    /// Other bridge crates, as `(crate name, path to lib.rs)` pairs, whose types may be referenced
+    /// by this bridge. Bindings are not generated for their types; instead the bindings generated
+    /// for those crates are included or imported.
+    pub externs: Vec<(String, PathBuf)>,
+}
+
+/// Backends that can generate bindings for a bridge referencing types from other bridge crates.
+const EXTERN_SUPPORTED_TARGETS: &[&str] = &["c", "cpp", "js", "json"];
+
 pub fn gen(
     entry: &Path,
     target_language: &str,
@@ -29,6 +51,27 @@ pub fn gen(
     docs_url_gen: &DocsUrlGenerator,
     library_config: Option<&Path>,
     silent: bool,
+) -> std::io::Result<()> {
+    gen_with_options(
+        entry,
+        target_language,
+        out_folder,
+        docs_url_gen,
+        library_config,
+        silent,
+        &GenOptions::default(),
+    )
+}
+
+/// Like [`gen()`], with additional [`GenOptions`].
+pub fn gen_with_options(
+    entry: &Path,
+    target_language: &str,
+    out_folder: &Path,
+    docs_url_gen: &DocsUrlGenerator,
+    library_config: Option<&Path>,
+    silent: bool,
+    options: &GenOptions,
 ) -> std::io::Result<()> {
     if !entry.exists() {
         eprintln!(
@@ -46,35 +89,44 @@ pub fn gen(
 
     // The HIR backends used to be named "c2", "js2", etc
     let target_language = target_language.strip_suffix('2').unwrap_or(target_language);
-    let mut attr_validator = hir::BasicAttributeValidator::new(target_language);
-    attr_validator.support = match target_language {
-        "c" => c::attr_support(),
-        "cpp" => cpp::attr_support(),
-        "dart" => dart::attr_support(),
-        "js" => js::attr_support(),
-        "demo_gen" => {
-            // So renames and disables are carried across.
-            attr_validator.other_backend_names = vec!["js".to_string()];
-            demo_gen::attr_support()
-        }
-        "mojo" => mojo::attr_support(),
-        "kotlin" => kotlin::attr_support(),
-        o => panic!("Unknown target: {}", o),
-    };
-
-    let module = syn_inline_mod::parse_and_inline_modules(entry);
-    let tcx = hir::TypeContext::from_syn(&module, attr_validator).unwrap_or_else(|e| {
-        for (ctx, err) in e {
-            eprintln!("Lowering error in {ctx}: {err}");
-        }
-        std::process::exit(1);
-    });
+    if !options.externs.is_empty() && !EXTERN_SUPPORTED_TARGETS.contains(&target_language) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!(
+                "The {target_language} backend does not support bridges referencing other crates (supported: {})",
+                EXTERN_SUPPORTED_TARGETS.join(", ")
+            ),
+        ));
+    }
+    if let Some((name, path)) = options.externs.iter().find(|(_, path)| !path.exists()) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!(
+                "The entry file of external crate {name} does not exist: {}",
+                std::env::current_dir().unwrap().join(path).display()
+            ),
+        ));
+    }
+    let tcx = lower(
+        entry,
+        &options.externs,
+        attr_validator(target_language),
+        options.message_format,
+    );
+    let templates = templates::TemplateOverrides::load(library_config, target_language)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
 
     let (files, errors) = match target_language {
         "c" => c::run(&tcx),
-        "cpp" => cpp::run(&tcx),
-        "dart" => dart::run(&tcx, docs_url_gen),
-        "js" => js::run(&tcx, docs_url_gen),
+        "cpp" => cpp::run(&tcx, &templates),
+        "dart" => dart::run(&tcx, library_config, docs_url_gen, &templates),
+        "js" => js::run(
+            &tcx,
+            library_config,
+            docs_url_gen,
+            &templates,
+            options.externs.first().map(|(name, _)| name.as_str()),
+        ),
         "demo_gen" => {
             let conf = library_config.map(|c| {
                 let str = std::fs::read_to_string(c)
@@ -89,19 +141,22 @@ pub fn gen(
                 .map(|c| c.module_name.is_some() || c.relative_js_path.is_some())
                 .unwrap_or(false)
             {
-                gen(
+                gen_with_options(
                     entry,
                     "js",
                     &out_folder.join("js"),
                     docs_url_gen,
                     library_config,
                     silent,
+                    options,
                 )?;
             }
-            demo_gen::run(entry, &tcx, docs_url_gen, conf)
+            demo_gen::run(entry, out_folder, &tcx, docs_url_gen, conf)
         }
         "mojo" => mojo::run(&tcx),
-        "kotlin" => kotlin::run(&tcx, library_config, docs_url_gen),
+        "kotlin" => kotlin::run(&tcx, library_config, docs_url_gen, &templates),
+        "json" => json::run(&tcx, docs_url_gen),
+        "wit" => wit::run(&tcx, entry, library_config, docs_url_gen),
         o => panic!("Unknown target: {}", o),
     };
 
@@ -125,6 +180,9 @@ pub fn gen(
         );
     }
     for (subpath, text) in files.take_files() {
+        let text = templates
+            .wrap_file(target_language, &subpath, text)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
         let out_path = out_folder.join(subpath);
         if !silent {
             println!("{}", format!("  {}", out_path.display()).dimmed());
@@ -136,6 +194,120 @@ pub fn gen(
     Ok(())
 }
 
+/// The attribute validator used when lowering for `target_language`.
+fn attr_validator(target_language: &str) -> hir::BasicAttributeValidator {
+    let mut attr_validator = hir::BasicAttributeValidator::new(target_language);
+    attr_validator.support = match target_language {
+        "c" => c::attr_support(),
+        "cpp" => cpp::attr_support(),
+        "dart" => dart::attr_support(),
+        "js" => js::attr_support(),
+        "demo_gen" => {
+            // So renames and disables are carried across.
+            attr_validator.other_backend_names = vec!["js".to_string()];
+            demo_gen::attr_support()
+        }
+        "mojo" => mojo::attr_support(),
+        "kotlin" => kotlin::attr_support(),
+        "json" => json::attr_support(),
+        "wit" => wit::attr_support(),
+        o => panic!("Unknown target: {}", o),
+    };
+    attr_validator
+}
+
+/// Parse and lower the bridge at `entry`, along with the crates in `externs` it may reference,
+/// reporting lowering errors and exiting if there are any.
+fn lower(
+    entry: &Path,
+    externs: &[(String, PathBuf)],
+    attr_validator: hir::BasicAttributeValidator,
+    message_format: MessageFormat,
+) -> hir::TypeContext {
+    let mut sources = diagnostics::SourceFiles::default();
+    let mut inline = |path: &Path| {
+        syn_inline_mod::InlinerBuilder::default()
+            .inline_with_callback(path, |path, text| sources.add(path, text))
+            .unwrap()
+            .into_output_and_errors()
+            .0
+    };
+    let module = inline(entry);
+    let extern_modules = externs
+        .iter()
+        .map(|(name, path)| (name.as_str(), inline(path)))
+        .collect::<Vec<_>>();
+    let extern_modules = extern_modules
+        .iter()
+        .map(|(name, module)| (*name, module))
+        .collect::<Vec<_>>();
+    hir::TypeContext::from_syn_with_externs(&module, &extern_modules, attr_validator)
+        .unwrap_or_else(|e| {
+            diagnostics::report_lowering_errors(&sources, &e, message_format);
+            std::process::exit(1);
+        })
+}
+
+/// The backends checked by [`diff()`] when no targets are given.
+pub const DIFF_DEFAULT_TARGETS: &[&str] = &["c", "cpp", "dart", "js", "kotlin", "mojo"];
+
+/// Compare two versions of a bridge, printing all changes visible to the given backends.
+///
+/// Returns whether any ABI- or source-breaking change was found.
+pub fn diff(old_entry: &Path, new_entry: &Path, targets: &[String]) -> std::io::Result<bool> {
+    for entry in [old_entry, new_entry] {
+        if !entry.exists() {
+            eprintln!(
+                "{}The entry file does not exist.\n{}",
+                "Error: ".red().bold(),
+                format!("{}", std::env::current_dir()?.join(entry).display()).red()
+            );
+            std::process::exit(1);
+        }
+    }
+
+    let mut breaking = false;
+    for target_language in targets {
+        let target_language = target_language.strip_suffix('2').unwrap_or(target_language);
+        let validator = attr_validator(target_language);
+        let support = validator.support;
+        let old = lower(old_entry, &[], validator, MessageFormat::Human);
+        let new = lower(
+            new_entry,
+            &[],
+            attr_validator(target_language),
+            MessageFormat::Human,
+        );
+        let changes = diff::DiffContext::new(
+            &old,
+            &new,
+            support,
+            target_language == "c",
+            target_language == "cpp",
+        )
+        .run();
+
+        println!(
+            "{}",
+            format!("Changes visible to {target_language}:").bold()
+        );
+        if changes.is_empty() {
+            println!("{}", "  none".dimmed());
+        }
+        for change in changes {
+            let line = format!("  {change}");
+            if change.abi_breaking || change.source_breaking {
+                breaking = true;
+                println!("{}", line.red());
+            } else {
+                println!("{}", line.dimmed());
+            }
+        }
+    }
+
+    Ok(breaking)
+}
+
 /// This type abstracts over files being written to.
 #[derive(Default, Debug)]
 pub struct FileMap {
@@ -156,6 +328,11 @@ impl FileMap {
         }
         self.files.borrow_mut().insert(name, contents);
     }
+
+    /// The files added so far, for backends that bundle their own output.
+    pub fn files(&self) -> std::cell::Ref<'_, HashMap<String, String>> {
+        self.files.borrow()
+    }
 }
 
 /// This type acts as a "store" for errors, which can be appended to.
@@ -1,20 +1,27 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
 /// diplomat-tool CLI options, as parsed by [clap-derive].
 #[derive(Debug, Parser)]
 #[clap(
     name = "diplomat-tool",
-    about = "Generate bindings to a target language"
+    about = "Generate bindings to a target language",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 struct Opt {
-    /// The target language, "c", "cpp", "js", "demo_gen", "mojo", or "kotlin" (JVM)
-    #[clap()]
-    target_language: String,
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// The target language, "c", "cpp", "js", "demo_gen", "mojo", "kotlin" (JVM),
+    /// "wit" (a WASI component-model world and adapter),
+    /// or "json" (a machine-readable description of the API)
+    #[clap(required = true)]
+    target_language: Option<String>,
 
     /// The folder that stores the bindings.
-    #[clap(value_parser)]
-    out_folder: PathBuf,
+    #[clap(value_parser, required = true)]
+    out_folder: Option<PathBuf>,
 
     #[clap(short = 'u', long)]
     docs_base_urls: Vec<String>,
@@ -23,22 +30,81 @@ struct Opt {
     #[clap(short, long, value_parser, default_value = "src/lib.rs")]
     entry: PathBuf,
 
+    /// Another bridge crate whose types are referenced by this one, as `<crate>=<path to lib.rs>`.
+    /// Bindings are not generated for its types; the bindings generated for that crate are
+    /// included or imported instead. May be repeated.
+    #[clap(long = "extern", value_parser = parse_extern)]
+    externs: Vec<(String, PathBuf)>,
+
     /// The path to an optional config file to override code generation defaults.
-    /// This is currently used by kotlin and demo_gen.
+    /// This is currently used by kotlin, js, demo_gen and wit, and by all backends for `templates-dir`
+    /// (see docs/templates.md).
     #[clap(short, long, value_parser)]
     library_config: Option<PathBuf>,
 
     #[clap(short = 's', long)]
     silent: bool,
+
+    /// How to print errors: "human", or "json" for one JSON object per line.
+    #[clap(long, value_parser, default_value = "human")]
+    message_format: diplomat_tool::MessageFormat,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Report ABI- and source-breaking changes between two versions of a bridge
+    Diff(DiffOpt),
+}
+
+/// Options for `diplomat-tool diff`.
+#[derive(Debug, clap::Args)]
+struct DiffOpt {
+    /// The path to the lib.rs file of the old version.
+    #[clap(value_parser)]
+    old_entry: PathBuf,
+
+    /// The path to the lib.rs file of the new version.
+    #[clap(value_parser)]
+    new_entry: PathBuf,
+
+    /// The backends to check compatibility for. Defaults to all binding backends.
+    #[clap(short, long)]
+    target: Vec<String>,
+}
+
+fn parse_extern(s: &str) -> Result<(String, PathBuf), String> {
+    let (name, path) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected `<crate>=<path>`, found `{s}`"))?;
+    Ok((name.replace('-', "_"), path.into()))
 }
 
 fn main() -> std::io::Result<()> {
     let opt = Opt::parse();
 
-    diplomat_tool::gen(
+    if let Some(Command::Diff(opt)) = opt.command {
+        let targets = if opt.target.is_empty() {
+            diplomat_tool::DIFF_DEFAULT_TARGETS
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        } else {
+            opt.target
+        };
+        if diplomat_tool::diff(&opt.old_entry, &opt.new_entry, &targets)? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let mut options = diplomat_tool::GenOptions::default();
+    options.message_format = opt.message_format;
+    options.externs = opt.externs;
+
+    diplomat_tool::gen_with_options(
         &opt.entry,
-        &opt.target_language,
-        &opt.out_folder,
+        opt.target_language.as_deref().expect("required by clap"),
+        opt.out_folder.as_deref().expect("required by clap"),
         &diplomat_core::hir::DocsUrlGenerator::with_base_urls(
             opt.docs_base_urls
                 .iter()
@@ -61,5 +127,6 @@ fn main() -> std::io::Result<()> {
         ),
         opt.library_config.as_deref(),
         opt.silent,
+        &options,
     )
 }
@@ -12,13 +12,11 @@ use std::borrow::Cow;
 ///
 /// If you find yourself needing an identifier formatted in a context not yet available here, please add a new method
 ///
-/// This type may be used by other backends attempting to figure out the names
-/// of C types and methods.
-pub struct CFormatter<'tcx> {
+pub struct MojoFormatter<'tcx> {
     tcx: &'tcx TypeContext,
 }
 
-impl<'tcx> CFormatter<'tcx> {
+impl<'tcx> MojoFormatter<'tcx> {
     pub fn new(tcx: &'tcx TypeContext) -> Self {
         Self { tcx }
     }
@@ -28,20 +26,16 @@ impl<'tcx> CFormatter<'tcx> {
 
     /// Resolve and format a named type for use in code (without the namespace)
     pub fn fmt_type_name(&self, id: TypeId) -> Cow<'tcx, str> {
-        let resolved = self.tcx.resolve_type(id);
-        let name: Cow<_> = resolved.name().as_str().into();
-        let attrs = resolved.attrs();
+        self.tcx.resolve_type(id).name().as_str().into()
+    }
 
-        // Only apply renames in cpp mode, in pure C mode you'd want the
-        name
+    /// Resolve and format the name of the raw C-layout type an opaque wrapper points to
+    pub fn fmt_opaque_ffi_name(&self, id: TypeId) -> String {
+        format!("{}_ffi", self.fmt_type_name(id))
     }
 
     pub fn fmt_trait_name(&self, id: TraitId) -> Cow<'tcx, str> {
-        let resolved = self.tcx.resolve_trait(id);
-        let name: Cow<_> = resolved.name.as_str().into();
-        let attrs = &resolved.attrs;
-
-        name
+        self.tcx.resolve_trait(id).name.as_str().into()
     }
 
     /// Given a type found inside a DiplomatOption<T>, provide the name of the corresponding option type
@@ -54,31 +48,19 @@ impl<'tcx> CFormatter<'tcx> {
         ty_name: &str,
     ) -> String {
         match ty {
-            hir::Type::Primitive(prim) => self.diplomat_namespace(format!("Option{}", self.fmt_primitive_name_for_derived_type(*prim)).into()).into(),
+            hir::Type::Primitive(prim) => self.diplomat_namespace(format!("Option{}Result", self.fmt_primitive_name_for_derived_type(*prim)).into()).into(),
             hir::Type::Struct(..) | hir::Type::Enum(..) => format!("{ty_name}_option"),
             _ => unreachable!("Called fmt_optional_type_name with type {ty_name}, which is not allowed inside an Option")
         }
     }
 
-    /// Resolve and format a named type for use in code (with a namespace, if needed by C++)
+    /// Resolve and format a named type for use in code (with a namespace, if needed)
     pub fn fmt_type_name_maybe_namespaced(&self, id: SymbolId) -> Cow<'tcx, str> {
-        let (name, attrs) = match id {
-            SymbolId::TypeId(id) => {
-                let resolved = self.tcx.resolve_type(id);
-                let name: Cow<_> = resolved.name().as_str().into();
-                let attrs = resolved.attrs();
-                (name, attrs)
-            }
-            SymbolId::TraitId(id) => {
-                let resolved = self.tcx.resolve_trait(id);
-                let name: Cow<_> = resolved.name.as_str().into();
-                let attrs = &resolved.attrs;
-                (name, attrs)
-            }
+        let name = match id {
+            SymbolId::TypeId(id) => self.fmt_type_name(id),
+            SymbolId::TraitId(id) => self.fmt_trait_name(id),
             _ => panic!("Unexpected symbol ID type"),
         };
-        // Only apply renames in cpp mode, in pure C mode you'd want the
-        // method names to match the type names.
         self.diplomat_namespace(name)
     }
 
@@ -96,7 +78,7 @@ impl<'tcx> CFormatter<'tcx> {
             _ => panic!("Unexpected symbol ID type"),
         };
         let type_name = type_name.to_lowercase();
-        format!("{type_name}_d.mojo")
+        format!("{}{type_name}_d.mojo", self.fmt_namespace_dir(id))
     }
     /// Resolve and format the name of a type for use in header names: impl version
     pub fn fmt_impl_header_path(&self, id: SymbolId) -> String {
@@ -106,7 +88,20 @@ impl<'tcx> CFormatter<'tcx> {
             _ => panic!("Unexpected symbol ID type"),
         };
         let type_name = type_name.to_lowercase();
-        format!("{type_name}.mojo")
+        format!("{}{type_name}.mojo", self.fmt_namespace_dir(id))
+    }
+
+    /// The package directory for a type, e.g. `a/b/` for a type in the namespace `a::b`
+    pub fn fmt_namespace_dir(&self, id: SymbolId) -> String {
+        let attrs = match id {
+            SymbolId::TypeId(id) => self.tcx.resolve_type(id).attrs(),
+            SymbolId::TraitId(id) => &self.tcx.resolve_trait(id).attrs,
+            _ => panic!("Unexpected symbol ID type"),
+        };
+        match attrs.namespace {
+            Some(ref ns) => format!("{}/", ns.replace("::", "/")),
+            None => String::new(),
+        }
     }
 
     /// Format an enum variant.
@@ -121,7 +116,28 @@ impl<'tcx> CFormatter<'tcx> {
     /// Format a field name or parameter name
     // might need splitting in the future if we decide to support renames here
     pub fn fmt_param_name<'a>(&self, ident: &'a str) -> Cow<'a, str> {
-        ident.into()
+        if MOJO_KEYWORDS.contains(&ident) {
+            format!("{ident}_").into()
+        } else {
+            ident.into()
+        }
+    }
+
+    /// Format the name of the raw function binding for a method
+    pub fn fmt_ffi_method_name(&self, method: &'tcx hir::Method) -> String {
+        method.abi_name.as_str().to_lowercase()
+    }
+
+    /// Format the name of a method on a wrapper type, mapping special methods to Mojo's dunder methods
+    pub fn fmt_method_name(&self, method: &'tcx hir::Method) -> Cow<'tcx, str> {
+        match method.attrs.special_method {
+            Some(hir::SpecialMethod::Constructor) => "__init__".into(),
+            Some(hir::SpecialMethod::NamedConstructor(Some(ref name))) => self.fmt_param_name(name),
+            Some(hir::SpecialMethod::Stringifier) => "__str__".into(),
+            Some(hir::SpecialMethod::Indexer) => "__getitem__".into(),
+            Some(hir::SpecialMethod::Iterable) => "__iter__".into(),
+            _ => self.fmt_param_name(method.name.as_str()),
+        }
     }
 
     pub fn fmt_ptr<'a>(&self, ident: &'a str, mutability: hir::Mutability) -> Cow<'a, str> {
@@ -148,7 +164,7 @@ impl<'tcx> CFormatter<'tcx> {
             PrimitiveType::Int(IntType::U32) => "c_uint32",
             PrimitiveType::Int(IntType::I64) => "c_int64",
             PrimitiveType::Int(IntType::U64) => "c_uint64",
-            PrimitiveType::Int128(_) => panic!("i128 not supported in C"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
             PrimitiveType::IntSize(IntSizeType::Isize) => "c_intptr",
             PrimitiveType::IntSize(IntSizeType::Usize) => "c_size_t",
             PrimitiveType::Float(FloatType::F32) => "c_float",
@@ -171,7 +187,7 @@ impl<'tcx> CFormatter<'tcx> {
             PrimitiveType::Int(IntType::U32) => "U32",
             PrimitiveType::Int(IntType::I64) => "I64",
             PrimitiveType::Int(IntType::U64) => "U64",
-            PrimitiveType::Int128(_) => panic!("i128 not supported in C"),
+            PrimitiveType::Int128(_) => unreachable!("i128 is rejected during lowering"),
             PrimitiveType::IntSize(IntSizeType::Isize) => "Isize",
             PrimitiveType::IntSize(IntSizeType::Usize) => "Usize",
             PrimitiveType::Float(FloatType::F32) => "F32",
@@ -220,3 +236,11 @@ impl<'tcx> CFormatter<'tcx> {
         ty
     }
 }
+
+/// Identifiers that cannot be used as Mojo parameter or method names
+const MOJO_KEYWORDS: &[&str] = &[
+    "alias", "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+    "elif", "else", "except", "finally", "fn", "for", "from", "global", "if", "import", "in",
+    "inout", "is", "lambda", "mut", "nonlocal", "not", "or", "out", "owned", "pass", "raise",
+    "raises", "ref", "return", "self", "struct", "trait", "try", "var", "while", "with", "yield",
+];
@@ -5,9 +5,8 @@ use std::fmt;
 use std::fmt::Write;
 
 static BASE_INCLUDES: &str = r#"
-from memory import UnsafePointer
+from memory import ArcPointer, UnsafePointer
 from sys.ffi import DLHandle
-from .diplomat_runtime import *
 "#;
 
 /// This abstraction allows us to build up headers piece by piece without needing
@@ -21,7 +20,7 @@ pub struct Header {
     /// Example:
     /// ```mojo
     /// from .Foo import *
-    /// from .diplomat_runtime import *
+    /// from .ns.Bar import *
     /// ```
     pub includes: BTreeSet<String>,
     /// The decl file corresponding to this impl file. Empty if this is not an impl file.
@@ -68,12 +67,25 @@ impl fmt::Write for Header {
 #[derive(Template)]
 #[template(path = "mojo/base.mojo.jinja", escape = "none")]
 struct HeaderTemplate<'a> {
+    path: &'a str,
     header_guard: Cow<'a, str>,
     decl_include: Option<&'a String>,
     includes: &'a BTreeSet<String>,
     body: Cow<'a, str>,
 }
 
+impl HeaderTemplate<'_> {
+    /// The relative module path of the file at `include` (relative to the root package),
+    /// for importing it from this file.
+    fn fmt_module(&self, include: &str) -> String {
+        let up = ".".repeat(self.path.matches('/').count());
+        format!(
+            ".{up}{}",
+            include.trim_end_matches(".mojo").replace('/', ".")
+        )
+    }
+}
+
 impl fmt::Display for Header {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let header_guard = &self.path;
@@ -86,6 +98,7 @@ impl fmt::Display for Header {
         };
 
         HeaderTemplate {
+            path: &self.path,
             header_guard: header_guard.into(),
             includes: &self.includes,
             decl_include: self.decl_include.as_ref(),
@@ -2,34 +2,40 @@ mod formatter;
 mod header;
 mod ty;
 
-pub use self::formatter::CFormatter;
-pub(crate) use self::header::Header;
+pub use self::formatter::MojoFormatter;
 pub use self::ty::TyGenContext;
 
 use crate::{ErrorStore, FileMap};
 use diplomat_core::hir;
 use diplomat_core::hir::BackendAttrSupport;
+use std::collections::BTreeSet;
 
 pub(crate) fn attr_support() -> BackendAttrSupport {
     let mut a = BackendAttrSupport::default();
 
-    a.namespacing = false;
+    a.namespacing = true;
     a.memory_sharing = true;
     a.non_exhaustive_structs = false;
     a.method_overloading = false;
     a.utf8_strings = true;
     a.utf16_strings = true;
     a.static_slices = true;
+    a.static_borrows = true;
+    a.int128 = false;
+    a.int128_slices = false;
+    a.write16 = false;
+    a.reads = false;
 
-    a.constructors = false;
-    a.named_constructors = false;
-    a.fallible_constructors = false;
+    a.constructors = true;
+    a.named_constructors = true;
+    a.fallible_constructors = true;
     a.accessors = false;
     a.comparators = false;
-    a.stringifiers = false;
-    a.iterators = false;
-    a.iterables = false;
-    a.indexing = false;
+    a.stringifiers = true;
+    a.iterators = true;
+    a.iterables = true;
+    a.indexing = true;
+    a.streaming_writes = false;
     a.option = true;
     a.callbacks = true;
     a.traits = true;
@@ -39,14 +45,40 @@ pub(crate) fn attr_support() -> BackendAttrSupport {
 
 #[derive(askama::Template)]
 #[template(path = "mojo/runtime.mojo.jinja", escape = "none")]
-pub struct Runtime;
+pub struct Runtime {
+    /// The primitive element types of slices, as (name in derived types, Mojo type)
+    slice_views: &'static [(&'static str, &'static str)],
+}
+
+const SLICE_VIEWS: &[(&str, &str)] = &[
+    ("Bool", "c_bool"),
+    ("Char", "c_char32"),
+    ("I8", "c_int8"),
+    ("U8", "c_uint8"),
+    ("I16", "c_int16"),
+    ("U16", "c_uint16"),
+    ("I32", "c_int32"),
+    ("U32", "c_uint32"),
+    ("I64", "c_int64"),
+    ("U64", "c_uint64"),
+    ("Isize", "c_intptr"),
+    ("Usize", "c_size_t"),
+    ("F32", "c_float"),
+    ("F64", "c_double"),
+];
 
 pub(crate) fn run(tcx: &hir::TypeContext) -> (FileMap, ErrorStore<String>) {
     let files = FileMap::default();
-    let formatter = CFormatter::new(tcx);
+    let formatter = MojoFormatter::new(tcx);
     let errors = ErrorStore::default();
 
-    files.add_file("diplomat_runtime.mojo".into(), Runtime.to_string());
+    files.add_file(
+        "diplomat_runtime.mojo".into(),
+        Runtime {
+            slice_views: SLICE_VIEWS,
+        }
+        .to_string(),
+    );
 
     for (id, ty) in tcx.all_types() {
         if ty.attrs().disable {
@@ -105,5 +137,120 @@ pub(crate) fn run(tcx: &hir::TypeContext) -> (FileMap, ErrorStore<String>) {
     }
     // loop over traits too
 
+    // Every namespace is a package, which needs an `__init__.mojo`
+    let packages = tcx
+        .all_types()
+        .map(|(_, ty)| ty.attrs())
+        .chain(tcx.all_traits().map(|(_, trt)| &trt.attrs))
+        .filter(|attrs| !attrs.disable)
+        .filter_map(|attrs| attrs.namespace.as_deref())
+        .flat_map(|ns| {
+            let segments = ns.split("::").collect::<Vec<_>>();
+            (1..=segments.len())
+                .map(|i| segments[..i].join("/"))
+                .collect::<Vec<_>>()
+        })
+        .collect::<BTreeSet<_>>();
+    for package in packages {
+        files.add_file(format!("{package}/__init__.mojo"), String::new());
+    }
+
     (files, errors)
 }
+
+#[cfg(test)]
+mod test {
+    use diplomat_core::hir::{self, TypeContext};
+    use quote::quote;
+    use std::collections::HashMap;
+
+    fn gen_mojo(tk_stream: proc_macro2::TokenStream) -> HashMap<String, String> {
+        let file = syn::parse2::<syn::File>(tk_stream).expect("failed to parse item");
+        let mut attr_validator = hir::BasicAttributeValidator::new("mojo");
+        attr_validator.support = super::attr_support();
+        let tcx = TypeContext::from_syn(&file, attr_validator).expect("failed to lower");
+
+        let (files, errors) = super::run(&tcx);
+        assert!(errors.take_all().is_empty());
+        files.take_files()
+    }
+
+    #[test]
+    fn test_wrapper_methods() {
+        let files = gen_mojo(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Counter(u32);
+
+                pub struct Point {
+                    x: i32,
+                    y: i64,
+                }
+
+                impl Counter {
+                    #[diplomat::attr(auto, constructor)]
+                    pub fn new(start: u32) -> Box<Counter> {
+                        unimplemented!()
+                    }
+
+                    pub fn point(&self) -> Result<Point, u8> {
+                        unimplemented!()
+                    }
+
+                    pub fn maybe_point(&self) -> Option<Point> {
+                        unimplemented!()
+                    }
+
+                    #[diplomat::attr(auto, stringifier)]
+                    pub fn format(&self, write: &mut DiplomatWrite) {
+                        unimplemented!()
+                    }
+                }
+            }
+        });
+        insta::assert_snapshot!(files["counter_d.mojo"]);
+        insta::assert_snapshot!(files["counter.mojo"]);
+    }
+
+    #[test]
+    fn test_borrowed_edges() {
+        let files = gen_mojo(quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Owner(u32);
+
+                #[diplomat::opaque]
+                struct Borrower<'a>(&'a u32);
+
+                impl Owner {
+                    pub fn borrow<'a>(&'a self) -> &'a Borrower<'a> {
+                        unimplemented!()
+                    }
+
+                    pub fn merge<'a>(&'a self, other: &'a Owner, extra: Option<&'a Owner>) -> Box<Borrower<'a>> {
+                        unimplemented!()
+                    }
+
+                    pub fn unrelated<'a, 'b>(&'a self, other: &'b Owner) -> Box<Borrower<'b>> {
+                        unimplemented!()
+                    }
+                }
+            }
+        });
+        let owner = &files["owner_d.mojo"];
+        assert!(
+            owner.contains(
+                "return Borrower(owner_borrow(self._ptr), False, DiplomatEdges() + self._edges)"
+            ),
+            "{owner}"
+        );
+        assert!(owner.contains("DiplomatEdges() + (extra.value()._edges if extra else DiplomatEdges()) + other._edges + self._edges"), "{owner}");
+        assert!(owner.contains("return Borrower(owner_unrelated(self._ptr, other._ptr), True, DiplomatEdges() + other._edges)"), "{owner}");
+
+        let borrower = &files["borrower_d.mojo"];
+        assert!(!borrower.contains("__del__"), "{borrower}");
+        assert!(borrower.contains("self._edges.append(ArcPointer(DiplomatOwner(ptr.bitcast[c_void](), _borrower_destroy_erased)))"), "{borrower}");
+    }
+}
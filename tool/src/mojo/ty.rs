@@ -1,21 +1,26 @@
-use super::formatter::CFormatter;
+use super::formatter::MojoFormatter;
 use super::header::Header;
 use crate::ErrorStore;
 use askama::Template;
+use diplomat_core::hir::borrowing_param::{BorrowedLifetimeInfo, LifetimeEdgeKind};
 use diplomat_core::hir::TypeContext;
 use diplomat_core::hir::{
-    self, CallbackInstantiationFunctionality, OpaqueOwner, ReturnableStructDef, StructPathLike,
-    SymbolId, TraitIdGetter, TyPosition, Type, TypeDef, TypeId,
+    self, CallbackInstantiationFunctionality, Lifetime, MaybeStatic, OpaqueOwner, ReturnType,
+    ReturnableStructDef, SelfType, SpecialMethod, StructPathLike, SuccessType, SymbolId,
+    TraitIdGetter, TyPosition, Type, TypeDef, TypeId,
 };
 use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Write;
 
 #[derive(Template)]
 #[template(path = "mojo/enum.mojo.jinja", escape = "none")]
 struct EnumTemplate<'a> {
     ty: &'a hir::EnumDef,
-    fmt: &'a CFormatter<'a>,
+    fmt: &'a MojoFormatter<'a>,
     ty_name: &'a str,
+    methods: Vec<String>,
+    is_stringable: bool,
 }
 
 #[derive(Template)]
@@ -23,6 +28,8 @@ struct EnumTemplate<'a> {
 struct StructTemplate<'a> {
     ty_name: Cow<'a, str>,
     fields: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    methods: Vec<String>,
+    is_stringable: bool,
 }
 
 #[derive(Template)]
@@ -36,12 +43,18 @@ struct TraitTemplate<'a> {
 #[template(path = "mojo/opaque.mojo.jinja", escape = "none")]
 struct OpaqueTemplate<'a> {
     ty_name: Cow<'a, str>,
+    ffi_name: String,
+    dtor_name: String,
+    methods: Vec<String>,
+    is_stringable: bool,
+    iterator: Option<IteratorInfo>,
 }
 
 #[derive(Template)]
 #[template(path = "mojo/impl.mojo.jinja", escape = "none")]
 struct ImplTemplate<'a> {
     methods: Vec<MethodTemplate<'a>>,
+    result_structs: Vec<ResultStruct>,
     cb_structs_and_defs: Vec<CallbackAndStructDef>,
     ty_name: Cow<'a, str>,
     dtor_name: Option<&'a str>,
@@ -56,6 +69,36 @@ struct MethodTemplate<'a> {
     method_name: String,
 }
 
+/// A method on an idiomatic wrapper type, calling into the raw function binding
+#[derive(Template)]
+#[template(path = "mojo/method.mojo.jinja", escape = "none")]
+struct WrapperMethodTemplate<'a> {
+    is_static: bool,
+    method_name: Cow<'a, str>,
+    params: String,
+    raises: bool,
+    return_ty: Option<Cow<'a, str>>,
+    body: Vec<String>,
+}
+
+/// The raw return type of a fallible or nullable method.
+///
+/// Mojo has no unions, so the result stores only the larger of the `ok` and `err` payloads
+/// (padded to the size of the union) and the other one is read out through a pointer cast.
+struct ResultStruct {
+    name: String,
+    fields: Vec<(String, String)>,
+    /// A payload that is not stored as a field, as (name, type)
+    cast_payload: Option<(String, String)>,
+}
+
+/// How an iterator type produces its items
+struct IteratorInfo {
+    /// The wrapper method returning the next item
+    next_method: String,
+    item_ty: String,
+}
+
 #[derive(Clone)]
 struct CallbackAndStructDef {
     name: String,
@@ -63,10 +106,59 @@ struct CallbackAndStructDef {
     return_type: String,
 }
 
+/// The opaque parameters of a wrapper method that returned opaques may borrow from
+///
+/// Wrappers keep the objects they borrow from alive by holding on to their `_edges`.
+/// Anything else a method borrows from (slices, structs) is owned by the caller.
+struct ReturnEdges<'tcx> {
+    borrow_map: BTreeMap<Lifetime, BorrowedLifetimeInfo<'tcx>>,
+    /// The expression for the `_edges` of each opaque parameter, by parameter name
+    param_edges: BTreeMap<String, String>,
+}
+
+impl ReturnEdges<'_> {
+    /// The `DiplomatEdges` of the objects a returned opaque borrows from
+    fn fmt_edges(&self, op: &hir::OpaquePath<hir::Optional, hir::MaybeOwn>) -> String {
+        let mut params = BTreeSet::new();
+        for lt in op
+            .owner
+            .lifetime()
+            .into_iter()
+            .chain(op.lifetimes.lifetimes())
+        {
+            let MaybeStatic::NonStatic(lt) = lt else {
+                continue;
+            };
+            let Some(info) = self.borrow_map.get(&lt) else {
+                continue;
+            };
+            for edge in &info.incoming_edges {
+                if let LifetimeEdgeKind::OpaqueParam = edge.kind {
+                    params.insert(edge.param_name.as_str());
+                }
+            }
+        }
+        params
+            .into_iter()
+            .filter_map(|param| self.param_edges.get(param))
+            .fold("DiplomatEdges()".into(), |edges, param_edges| {
+                format!("{edges} + {param_edges}")
+            })
+    }
+}
+
+/// The idiomatic wrapper methods of a type, along with what they make it conform to
+#[derive(Default)]
+struct WrapperMethods {
+    methods: Vec<String>,
+    is_stringable: bool,
+    iterator: Option<IteratorInfo>,
+}
+
 /// The context used for generating a particular type
 pub struct TyGenContext<'cx, 'tcx> {
     pub tcx: &'tcx TypeContext,
-    pub formatter: &'cx CFormatter<'tcx>,
+    pub formatter: &'cx MojoFormatter<'tcx>,
     pub errors: &'cx ErrorStore<'tcx, String>,
     pub id: SymbolId,
     pub decl_header_path: &'cx String,
@@ -77,25 +169,40 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
     pub fn gen_enum_def(&self, def: &'tcx hir::EnumDef) -> Header {
         let mut decl_header = Header::new(self.decl_header_path.clone());
         let ty_name = self.formatter.fmt_type_name(self.id.try_into().unwrap());
+        let methods = self.gen_wrapper_methods(&def.methods, &mut decl_header);
         EnumTemplate {
             ty: def,
             fmt: self.formatter,
             ty_name: &ty_name,
+            methods: methods.methods,
+            is_stringable: methods.is_stringable,
         }
         .render_into(&mut decl_header)
         .unwrap();
 
-        decl_header
+        self.finish_decl_header(decl_header)
     }
 
-    pub fn gen_opaque_def(&self, _def: &'tcx hir::OpaqueDef) -> Header {
+    pub fn gen_opaque_def(&self, def: &'tcx hir::OpaqueDef) -> Header {
         let mut decl_header = Header::new(self.decl_header_path.clone());
-        let ty_name = self.formatter.fmt_type_name(self.id.try_into().unwrap());
-        OpaqueTemplate { ty_name }
-            .render_into(&mut decl_header)
-            .unwrap();
+        let id: TypeId = self.id.try_into().unwrap();
+        let ty_name = self.formatter.fmt_type_name(id);
+        let methods = self.gen_wrapper_methods(&def.methods, &mut decl_header);
+        OpaqueTemplate {
+            ty_name,
+            ffi_name: self.formatter.fmt_opaque_ffi_name(id),
+            dtor_name: def.dtor_abi_name.as_str().to_lowercase(),
+            methods: methods.methods,
+            is_stringable: methods.is_stringable,
+            iterator: methods.iterator,
+        }
+        .render_into(&mut decl_header)
+        .unwrap();
 
-        decl_header
+        // The destructor is a raw function binding
+        decl_header.includes.insert(self.impl_header_path.clone());
+
+        self.finish_decl_header(decl_header)
     }
 
     pub fn gen_struct_def<P: TyPosition>(&self, def: &'tcx hir::StructDef<P>) -> Header {
@@ -112,11 +219,23 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
                 &mut cb_structs_and_defs, // for now this gets ignored, there are no callbacks in struct fields
             ));
         }
+        let methods = self.gen_wrapper_methods(&def.methods, &mut decl_header);
+
+        StructTemplate {
+            ty_name,
+            fields,
+            methods: methods.methods,
+            is_stringable: methods.is_stringable,
+        }
+        .render_into(&mut decl_header)
+        .unwrap();
 
-        StructTemplate { ty_name, fields }
-            .render_into(&mut decl_header)
-            .unwrap();
+        self.finish_decl_header(decl_header)
+    }
 
+    /// Removes the include of the decl file itself, added when a method refers to its own type
+    fn finish_decl_header(&self, mut decl_header: Header) -> Header {
+        decl_header.includes.remove(self.decl_header_path);
         decl_header
     }
 
@@ -157,6 +276,7 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
     pub fn gen_impl(&self, ty: hir::TypeDef<'tcx>) -> Header {
         let mut impl_header = Header::new(self.impl_header_path.clone());
         let mut methods = vec![];
+        let mut result_structs = vec![];
         let mut cb_structs_and_defs = vec![];
         for method in ty.methods() {
             if method.attrs.disable {
@@ -167,12 +287,19 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
                 self.tcx.fmt_symbol_name_diagnostics(self.id),
                 method.name.as_str().into(),
             );
-            let (method_chunk, callback_defs) = self.gen_method(method, &mut impl_header);
+            let (method_chunk, callback_defs) =
+                self.gen_method(method, &mut impl_header, &mut result_structs);
             methods.push(method_chunk);
             cb_structs_and_defs.extend_from_slice(&callback_defs);
         }
 
-        let ty_name = self.formatter.fmt_type_name(self.id.try_into().unwrap());
+        let ty_name = match ty {
+            TypeDef::Opaque(_) => self
+                .formatter
+                .fmt_opaque_ffi_name(self.id.try_into().unwrap())
+                .into(),
+            _ => self.formatter.fmt_type_name(self.id.try_into().unwrap()),
+        };
 
         let dtor_name = if let TypeDef::Opaque(opaque) = ty {
             Some(opaque.dtor_abi_name.as_str())
@@ -183,6 +310,7 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
         ImplTemplate {
             ty_name,
             methods,
+            result_structs,
             cb_structs_and_defs,
             dtor_name,
             dtor_method_name,
@@ -206,12 +334,10 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
         &self,
         method: &'tcx hir::Method,
         header: &mut Header,
+        result_structs: &mut Vec<ResultStruct>,
     ) -> (MethodTemplate<'tcx>, Vec<CallbackAndStructDef>) {
-        use diplomat_core::hir::{ReturnType, SuccessType};
         let abi_name = method.abi_name.as_str();
-        // Right now these are the same, but we may eventually support renaming
-        // and should be sure to use method_name when naming the result type
-        let method_name = abi_name;
+        let method_name = self.formatter.fmt_ffi_method_name(method);
         let mut param_decls = Vec::new();
         let mut cb_structs_and_defs = vec![];
         if let Some(ref self_ty) = method.param_self {
@@ -236,8 +362,7 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
         }
 
         let return_ty: Cow<str> = match method.output {
-            // todo:
-            ReturnType::Infallible(SuccessType::Unit) => "None".into(), // todo:void
+            ReturnType::Infallible(SuccessType::Unit) => "None".into(),
             ReturnType::Infallible(SuccessType::Write) => {
                 param_decls.push((
                     format!("UnsafePointer[{}]", self.formatter.fmt_write_name()).into(),
@@ -256,7 +381,7 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
                 let ok_ty = match ok {
                     SuccessType::Write => {
                         param_decls.push((
-                            format!("{}*", self.formatter.fmt_write_name()).into(),
+                            format!("UnsafePointer[{}]", self.formatter.fmt_write_name()).into(),
                             "write".into(),
                         ));
                         None
@@ -265,7 +390,10 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
                     SuccessType::OutType(o) => Some(o),
                     _ => unreachable!("unknown AST/HIR variant"),
                 };
-                self.gen_result_ty(method_name, ok_ty, err, header).into()
+                let result = self.gen_result_ty(&method_name, ok_ty, err, header);
+                let name = result.name.clone();
+                result_structs.push(result);
+                name.into()
             }
             _ => unreachable!("unknown AST/HIR variant"),
         };
@@ -282,13 +410,9 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
             write!(&mut params, "{comma}{decl_name}: {decl_ty}").unwrap();
         }
 
-        if params.is_empty() {
-            params.push_str("");
-        }
-
         let mut param_names = String::new();
         let mut first = true;
-        for (decl_ty, decl_name) in &param_decls {
+        for (_, decl_name) in &param_decls {
             let comma = if first {
                 first = false;
                 ""
@@ -298,12 +422,6 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
             write!(&mut param_names, "{comma}{decl_name}").unwrap();
         }
 
-        if param_names.is_empty() {
-            param_names.push_str("");
-        }
-
-        let method_name = abi_name.to_lowercase();
-
         (
             MethodTemplate {
                 abi_name,
@@ -322,69 +440,464 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
         ok_ty: Option<&hir::OutType>,
         err_ty: Option<&hir::OutType>,
         header: &mut Header,
-    ) -> String {
-        let ok_ty = ok_ty.filter(|t| {
-            let Type::Struct(s) = t else {
-                return true;
-            };
-            match s.resolve(self.tcx) {
-                ReturnableStructDef::Struct(s) => !s.fields.is_empty(),
-                ReturnableStructDef::OutStruct(s) => !s.fields.is_empty(),
-                _ => unreachable!("unknown AST/HIR variant"),
+    ) -> ResultStruct {
+        let ok_ty = ok_ty.filter(|t| self.is_nonempty_payload(t));
+        let err_ty = err_ty.filter(|t| self.is_nonempty_payload(t));
+
+        let mut fields = vec![];
+        let mut cast_payload = None;
+        match (ok_ty, err_ty) {
+            (Some(ok), Some(err)) => {
+                let (ok_size, ok_align) = self.native_layout(ok);
+                let (err_size, err_align) = self.native_layout(err);
+                let align = ok_align.max(err_align);
+                let union_size = round_up(ok_size.max(err_size), align);
+
+                let ok = ("ok".to_string(), self.gen_ty_name(ok, header).into_owned());
+                let err = (
+                    "err".to_string(),
+                    self.gen_ty_name(err, header).into_owned(),
+                );
+                let (stored, cast, stored_size) = if ok_size >= err_size {
+                    (ok, err, ok_size)
+                } else {
+                    (err, ok, err_size)
+                };
+                fields.push(stored);
+                for i in 0..union_size - stored_size {
+                    fields.push((format!("_padding{i}"), "c_uint8".into()));
+                }
+                cast_payload = Some(cast);
             }
-        });
+            (Some(ok), None) => fields.push(("ok".into(), self.gen_ty_name(ok, header).into())),
+            (None, Some(err)) => fields.push(("err".into(), self.gen_ty_name(err, header).into())),
+            (None, None) => {}
+        }
+        fields.push(("is_ok".into(), "c_bool".into()));
 
-        let err_ty = err_ty.filter(|t| {
-            let Type::Struct(s) = t else {
-                return true;
-            };
-            match s.resolve(self.tcx) {
-                ReturnableStructDef::Struct(s) => !s.fields.is_empty(),
-                ReturnableStructDef::OutStruct(s) => !s.fields.is_empty(),
+        // We name the result type after the function, like the C backend does
+        ResultStruct {
+            name: format!("{fn_name}_result"),
+            fields,
+            cast_payload,
+        }
+    }
+
+    /// Empty structs are not passed over FFI, so they take up no space in a result
+    fn is_nonempty_payload(&self, ty: &hir::OutType) -> bool {
+        let Type::Struct(s) = ty else {
+            return true;
+        };
+        match s.resolve(self.tcx) {
+            ReturnableStructDef::Struct(s) => !s.fields.is_empty(),
+            ReturnableStructDef::OutStruct(s) => !s.fields.is_empty(),
+            _ => unreachable!("unknown AST/HIR variant"),
+        }
+    }
+
+    /// The size and alignment of a type in the C ABI of 64-bit platforms
+    fn native_layout<P: TyPosition>(&self, ty: &Type<P>) -> (usize, usize) {
+        use diplomat_core::hir::{FloatType, IntType, PrimitiveType};
+        fn struct_layout(fields: impl Iterator<Item = (usize, usize)>) -> (usize, usize) {
+            let (size, align) =
+                fields.fold((0usize, 1usize), |(offset, align), (size, field_align)| {
+                    (round_up(offset, field_align) + size, align.max(field_align))
+                });
+            (round_up(size, align), align)
+        }
+        match ty {
+            Type::Primitive(prim) => {
+                let size = match prim {
+                    PrimitiveType::Bool
+                    | PrimitiveType::Byte
+                    | PrimitiveType::Int(IntType::I8 | IntType::U8) => 1,
+                    PrimitiveType::Int(IntType::I16 | IntType::U16) => 2,
+                    PrimitiveType::Char
+                    | PrimitiveType::Int(IntType::I32 | IntType::U32)
+                    | PrimitiveType::Float(FloatType::F32) => 4,
+                    _ => 8,
+                };
+                (size, size)
+            }
+            Type::Opaque(_) => (8, 8),
+            Type::Slice(_) => (16, 8),
+            Type::Enum(_) => (4, 4),
+            Type::Struct(st) => match self.tcx.resolve_type(st.id()) {
+                TypeDef::Struct(def) => {
+                    struct_layout(def.fields.iter().map(|f| self.native_layout(&f.ty)))
+                }
+                TypeDef::OutStruct(def) => {
+                    struct_layout(def.fields.iter().map(|f| self.native_layout(&f.ty)))
+                }
                 _ => unreachable!("unknown AST/HIR variant"),
+            },
+            Type::DiplomatOption(inner) => {
+                struct_layout([self.native_layout(inner), (1, 1)].into_iter())
             }
-        });
+            _ => unreachable!("unknown AST/HIR variant: {ty:?}"),
+        }
+    }
 
-        let ok_name = if let Some(ok) = ok_ty {
-            let ok_name = self.gen_ty_name(ok, header);
-            format!("{ok_name}")
-        } else {
-            "".into()
+    /// Generates the idiomatic wrapper methods for the methods of a type
+    fn gen_wrapper_methods(
+        &self,
+        methods: &'tcx [hir::Method],
+        header: &mut Header,
+    ) -> WrapperMethods {
+        let mut wrapper = WrapperMethods::default();
+        for method in methods {
+            if method.attrs.disable {
+                continue;
+            }
+            let _guard = self.errors.set_context_method(
+                self.tcx.fmt_symbol_name_diagnostics(self.id),
+                method.name.as_str().into(),
+            );
+            let method_template = self.gen_wrapper_method(method, header);
+
+            match method.attrs.special_method {
+                Some(SpecialMethod::Stringifier) => wrapper.is_stringable = true,
+                Some(SpecialMethod::Iterator) => {
+                    let item_ty = match method.output {
+                        ReturnType::Nullable(SuccessType::OutType(ref o)) => {
+                            self.gen_wrapper_ty_name(o, header)
+                        }
+                        ReturnType::Infallible(SuccessType::OutType(
+                            ref o @ Type::Opaque(ref op),
+                        )) if op.is_optional() => {
+                            let _ = self.gen_wrapper_ty_name(o, header);
+                            self.formatter
+                                .fmt_type_name_maybe_namespaced(TypeId::from(op.tcx_id).into())
+                        }
+                        _ => {
+                            self.errors.push_error(format!(
+                                "Iterator method {} must return an Option",
+                                method.name.as_str()
+                            ));
+                            continue;
+                        }
+                    };
+                    wrapper.iterator = Some(IteratorInfo {
+                        next_method: method_template.method_name.to_string(),
+                        item_ty: item_ty.into(),
+                    });
+                }
+                _ => {}
+            }
+
+            wrapper.methods.push(method_template.render().unwrap());
+        }
+
+        if !wrapper.methods.is_empty() {
+            header.includes.insert(self.impl_header_path.clone());
+        }
+        wrapper
+    }
+
+    fn gen_wrapper_method(
+        &self,
+        method: &'tcx hir::Method,
+        header: &mut Header,
+    ) -> WrapperMethodTemplate<'tcx> {
+        let is_constructor = matches!(
+            method.attrs.special_method,
+            Some(SpecialMethod::Constructor)
+        );
+        let is_indexer = matches!(method.attrs.special_method, Some(SpecialMethod::Indexer));
+
+        let mut params = vec![];
+        let mut args: Vec<Cow<str>> = vec![];
+        let mut visitor = method.borrowing_param_visitor(self.tcx);
+        let mut param_edges = BTreeMap::new();
+        if is_constructor {
+            params.push("out self".to_string());
+        }
+        if let Some(ref param_self) = method.param_self {
+            params.push("self".into());
+            visitor.visit_param(&param_self.ty.clone().into(), "self");
+            args.push(match param_self.ty {
+                SelfType::Opaque(..) => {
+                    param_edges.insert("self".to_string(), "self._edges".to_string());
+                    "self._ptr".into()
+                }
+                _ => "self".into(),
+            });
+        }
+
+        for param in &method.params {
+            match param.ty {
+                Type::Callback(..) | Type::ImplTrait(..) => {
+                    // These are passed through as their raw wrapper structs
+                    let (ty, name) = self.gen_ty_decl(
+                        &param.ty,
+                        param.name.as_str(),
+                        header,
+                        Some(method.abi_name.to_string()),
+                        &mut vec![],
+                    );
+                    params.push(format!("{name}: {ty}"));
+                    args.push(name.into_owned().into());
+                }
+                _ => {
+                    let name = self.formatter.fmt_param_name(param.name.as_str());
+                    let ty = self.gen_wrapper_ty_name(&param.ty, header);
+                    visitor.visit_param(&param.ty, &name);
+                    match param.ty {
+                        Type::Opaque(ref op) if op.is_optional() => {
+                            param_edges.insert(
+                                name.to_string(),
+                                format!("({name}.value()._edges if {name} else DiplomatEdges())"),
+                            );
+                        }
+                        Type::Opaque(..) => {
+                            param_edges.insert(name.to_string(), format!("{name}._edges"));
+                        }
+                        _ => {}
+                    }
+                    params.push(format!("{name}: {ty}"));
+                    args.push(self.gen_wrapper_to_ffi(&param.ty, &name));
+                }
+            }
+        }
+        let edges = ReturnEdges {
+            borrow_map: visitor.borrow_map(),
+            param_edges,
         };
 
-        let oi_name = match ok_name.as_str() {
-            "c_int8_t" => "I8",
-            "c_int8" => "I8",
-            "c_uint8_t" => "U8",
-            "c_uint8" => "U8",
-            "c_int16_t" => "I16",
-            "c_int16" => "I16",
-            "c_uint16_t" => "U16",
-            "c_uint16" => "U16",
-            "c_int32_t" => "I32",
-            "c_int32" => "I32",
-            "c_uint32_t" => "U32",
-            "c_uint32" => "U32",
-            "c_int64_t" => "I64",
-            "c_int64" => "I64",
-            "c_uint64_t" => "U64",
-            "c_uint64" => "U64",
-            "intptr_t" => "Isize",
-            "c_size_t" => "Usize",
-            "c_size" => "Usize",
-            "c_float" => "F32",
-            "c_double" => "F64",
-            "c_bool" => "Bool",
-            "c_char32_t" => "Char",
-            "c_char32" => "Char",
-            "c_char" => "String",
-            "c_char16_t" => "String16",
-            "c_char16" => "String16",
-            "DiplomatStringView" => "Strings",
-            "DiplomatString16View" => "Strings16",
-            _ => unreachable!("unknown type {ok_name}"),
+        let mut body = vec![];
+        let is_write = matches!(
+            method.output,
+            ReturnType::Infallible(SuccessType::Write)
+                | ReturnType::Fallible(SuccessType::Write, _)
+                | ReturnType::Nullable(SuccessType::Write)
+        );
+        if is_write {
+            body.push("var write = diplomat_buffer_write_create(0)".to_string());
+            args.push("write".into());
+        }
+        let call = format!(
+            "{}({})",
+            self.formatter.fmt_ffi_method_name(method),
+            args.join(", ")
+        );
+        let ret = |expr: &str| {
+            if is_constructor {
+                format!("self = {expr}")
+            } else {
+                format!("return {expr}")
+            }
         };
-        format!("Option{oi_name}Result")
+
+        let mut raises = false;
+        let return_ty: Option<Cow<str>> = match method.output {
+            ReturnType::Infallible(SuccessType::Unit) => {
+                body.push(call);
+                None
+            }
+            ReturnType::Infallible(SuccessType::Write) => {
+                body.push(call);
+                body.push(ret("diplomat_buffer_write_to_string(write)"));
+                Some("String".into())
+            }
+            // Optional opaques are returned as nullable pointers
+            ReturnType::Infallible(SuccessType::OutType(ref o @ Type::Opaque(ref op)))
+                if op.is_optional() =>
+            {
+                body.push(format!("var result = {call}"));
+                if is_indexer {
+                    raises = true;
+                    body.push("if not result:".into());
+                    body.push("    raise Error(\"index out of bounds\")".into());
+                    body.push(ret(&self.gen_ffi_to_wrapper(o, "result", false, &edges)));
+                    let _ = self.gen_wrapper_ty_name(o, header);
+                    Some(
+                        self.formatter
+                            .fmt_type_name_maybe_namespaced(TypeId::from(op.tcx_id).into()),
+                    )
+                } else {
+                    body.push(ret(&self.gen_ffi_to_wrapper(o, "result", true, &edges)));
+                    Some(self.gen_wrapper_ty_name(o, header))
+                }
+            }
+            ReturnType::Infallible(SuccessType::OutType(ref o)) => {
+                body.push(ret(&self.gen_ffi_to_wrapper(o, &call, true, &edges)));
+                Some(self.gen_wrapper_ty_name(o, header))
+            }
+            ReturnType::Nullable(SuccessType::Unit) if !is_indexer => {
+                // Option<()> is a boolean
+                body.push(ret(&format!("{call}.is_ok")));
+                Some("Bool".into())
+            }
+            ReturnType::Fallible(ref ok, _) | ReturnType::Nullable(ref ok) => {
+                body.push(format!("var result = {call}"));
+                if is_write {
+                    body.push("var string = diplomat_buffer_write_to_string(write)".into());
+                }
+                let err = if let ReturnType::Fallible(_, ref err) = method.output {
+                    Some(err.as_ref())
+                } else {
+                    None
+                };
+                // Which of the payloads is read through a pointer cast, see `gen_result_ty`
+                let cast_payload = match (ok, err.flatten()) {
+                    (SuccessType::OutType(ok), Some(err))
+                        if self.is_nonempty_payload(ok) && self.is_nonempty_payload(err) =>
+                    {
+                        if self.native_layout(ok).0 >= self.native_layout(err).0 {
+                            Some("err")
+                        } else {
+                            Some("ok")
+                        }
+                    }
+                    _ => None,
+                };
+                let payload = |name: &str, ty: &hir::OutType| {
+                    if !self.is_nonempty_payload(ty) {
+                        // Empty structs are not stored in the result at all
+                        format!("{}()", self.gen_ty_name(ty, &mut Header::default()))
+                    } else if cast_payload == Some(name) {
+                        format!("result.{name}()")
+                    } else {
+                        format!("result.{name}")
+                    }
+                };
+
+                body.push("if not result.is_ok:".into());
+                match err {
+                    Some(err) => {
+                        raises = true;
+                        let error = match err {
+                            None => format!("\"{} failed\"", method.name.as_str()),
+                            Some(err @ (Type::Enum(..) | Type::Primitive(..))) => {
+                                let _ = self.gen_ty_name(err, header);
+                                format!("String({})", payload("err", err))
+                            }
+                            Some(err) => {
+                                // Owned errors are freed when their wrapper is dropped
+                                let err_name = self.gen_wrapper_ty_name(err, header);
+                                body.push(format!(
+                                    "    _ = {}",
+                                    self.gen_ffi_to_wrapper(
+                                        err,
+                                        &payload("err", err),
+                                        true,
+                                        &edges
+                                    )
+                                ));
+                                format!("\"{err_name}\"")
+                            }
+                        };
+                        body.push(format!("    raise Error({error})"));
+                    }
+                    None if is_indexer => {
+                        raises = true;
+                        body.push("    raise Error(\"index out of bounds\")".into());
+                    }
+                    None => body.push("    return None".into()),
+                }
+
+                let ok_ty: Option<Cow<str>> = match ok {
+                    SuccessType::Unit => None,
+                    SuccessType::Write => {
+                        body.push(ret("string"));
+                        Some("String".into())
+                    }
+                    SuccessType::OutType(o) => {
+                        body.push(ret(&self.gen_ffi_to_wrapper(
+                            o,
+                            &payload("ok", o),
+                            true,
+                            &edges,
+                        )));
+                        Some(self.gen_wrapper_ty_name(o, header))
+                    }
+                    _ => unreachable!("unknown AST/HIR variant"),
+                };
+                match (ok_ty, &method.output) {
+                    (Some(ty), ReturnType::Nullable(_)) if !is_indexer => {
+                        Some(format!("Optional[{ty}]").into())
+                    }
+                    (ty, _) => ty,
+                }
+            }
+            _ => unreachable!("unknown AST/HIR variant"),
+        };
+
+        WrapperMethodTemplate {
+            is_static: method.param_self.is_none() && !is_constructor,
+            method_name: self.formatter.fmt_method_name(method),
+            params: params.join(", "),
+            raises,
+            return_ty: if is_constructor { None } else { return_ty },
+            body,
+        }
+    }
+
+    /// The type of a parameter or return value of a wrapper method
+    fn gen_wrapper_ty_name<P: TyPosition>(
+        &self,
+        ty: &Type<P>,
+        header: &mut Header,
+    ) -> Cow<'tcx, str> {
+        match ty {
+            Type::Opaque(op) => {
+                let op_id: TypeId = op.tcx_id.into();
+                // Registers the include and checks that the type is enabled
+                let _ = self.gen_ty_name(ty, header);
+                let ty_name = self.formatter.fmt_type_name_maybe_namespaced(op_id.into());
+                if op.is_optional() {
+                    format!("Optional[{ty_name}]").into()
+                } else {
+                    ty_name
+                }
+            }
+            _ => self.gen_ty_name(ty, header),
+        }
+    }
+
+    /// Converts a wrapper method parameter to the type taken by the raw function binding
+    fn gen_wrapper_to_ffi<P: TyPosition>(&self, ty: &Type<P>, name: &str) -> Cow<'tcx, str> {
+        match ty {
+            Type::Opaque(op) if op.is_optional() => {
+                let ffi_name = self.formatter.fmt_opaque_ffi_name(op.tcx_id.into());
+                format!("{name}.value()._ptr if {name} else UnsafePointer[{ffi_name}]()").into()
+            }
+            Type::Opaque(..) => format!("{name}._ptr").into(),
+            _ => name.to_string().into(),
+        }
+    }
+
+    /// Converts a value returned by a raw function binding to the type returned by the wrapper method
+    ///
+    /// Optional opaques are only checked for null if `check_null` is set.
+    fn gen_ffi_to_wrapper(
+        &self,
+        ty: &hir::OutType,
+        value: &str,
+        check_null: bool,
+        edges: &ReturnEdges,
+    ) -> String {
+        match ty {
+            Type::Opaque(op) => {
+                let ty_name = self
+                    .formatter
+                    .fmt_type_name_maybe_namespaced(TypeId::from(op.tcx_id).into());
+                // Borrowed opaques are not freed by the wrapper, and hold on to
+                // the edges of their owners instead
+                let is_owned = if op.owner.as_borrowed().is_none() {
+                    "True"
+                } else {
+                    "False"
+                };
+                let edges = edges.fmt_edges(op);
+                if op.is_optional() && check_null {
+                    format!("Optional[{ty_name}]({ty_name}({value}, {is_owned}, {edges})) if {value} else Optional[{ty_name}](None)")
+                } else {
+                    format!("{ty_name}({value}, {is_owned}, {edges})")
+                }
+            }
+            _ => value.into(),
+        }
     }
 
     /// Generates a decl for a given type, returned as (type, name)
@@ -476,7 +989,8 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
                 }
                 // unwrap_or(mut) since owned pointers need to not be const
                 let mutability = op.owner.mutability().unwrap_or(hir::Mutability::Mutable);
-                let ret = self.formatter.fmt_ptr(&ty_name, mutability);
+                let ffi_name = self.formatter.fmt_opaque_ffi_name(op_id);
+                let ret = self.formatter.fmt_ptr(&ffi_name, mutability);
                 header
                     .includes
                     .insert(self.formatter.fmt_decl_header_path(op_id.into()));
@@ -535,3 +1049,8 @@ impl<'cx, 'tcx> TyGenContext<'cx, 'tcx> {
         ty_name
     }
 }
+
+/// Rounds `n` up to the next multiple of `align`
+fn round_up(n: usize, align: usize) -> usize {
+    (n + align - 1) / align * align
+}
@@ -0,0 +1,49 @@
+//! Rust paths of the types declared in a bridge, for backends that generate Rust code calling
+//! into it (the `demo_gen` CLI and the `wit` adapter).
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use diplomat_core::ast;
+
+/// Find the full Rust path (i.e. `{root}::decimal::ffi::FixedDecimal`) of every type in the bridge at `entry`,
+/// keyed by type name.
+///
+/// `root` is the path the bridge crate is reachable under, i.e. its [`crate_name()`] or `crate`.
+pub(crate) fn type_paths(entry: &Path, root: &str) -> BTreeMap<String, String> {
+    let mut paths = BTreeMap::new();
+
+    fn collect(module: &ast::Module, parent: &str, paths: &mut BTreeMap<String, String>) {
+        let path = format!("{parent}::{}", module.name.as_str());
+        for name in module.declared_types.keys() {
+            paths.insert(name.as_str().into(), format!("{path}::{}", name.as_str()));
+        }
+        for sub_module in &module.sub_modules {
+            collect(sub_module, &path, paths);
+        }
+    }
+
+    let file = ast::File::from(&syn_inline_mod::parse_and_inline_modules(entry));
+    for module in file.modules.values() {
+        collect(module, root, &mut paths);
+    }
+
+    paths
+}
+
+/// The name that the crate containing `entry` is imported under, from the closest `Cargo.toml`.
+pub(crate) fn crate_name(entry: &Path) -> Option<String> {
+    let manifest = entry
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| manifest.exists())?;
+    let manifest: toml::Value = toml::from_str(&std::fs::read_to_string(manifest).ok()?).ok()?;
+
+    let name = manifest
+        .get("lib")
+        .and_then(|lib| lib.get("name"))
+        .or_else(|| manifest.get("package")?.get("name"))?
+        .as_str()?;
+
+    Some(name.replace('-', "_"))
+}
@@ -0,0 +1,427 @@
+//! User-supplied template hooks.
+//!
+//! The bulk of each backend is rendered from askama templates compiled into diplomat-tool. To let
+//! users customize parts of the output without forking, the library config may point to a
+//! templates directory (`templates-dir = "..."`, relative to the config file) containing
+//! `<backend>/<hook>.tmpl` files. These are rendered at runtime with [`tinytemplate`], and the result is
+//! spliced into the generated code at the hook's position.
+//!
+//! See `docs/templates.md` for the list of hooks and the variables available to each.
+
+use crate::ErrorStore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tinytemplate::TinyTemplate;
+
+/// Hooks supported by every backend that generates source files.
+const FILE_HOOKS: &[&str] = &["file_preamble", "file_epilogue"];
+
+/// Hooks supported by backends that generate classes.
+const CLASS_HOOKS: &[&str] = &["class_prologue", "method_prologue", "method_body"];
+
+/// Stands in for the method body while rendering the `method_body` hook, so that the hook can be
+/// split into the code before and after the body.
+const BODY_PLACEHOLDER: &str = "\u{0}body\u{0}";
+
+/// All hooks supported by a backend.
+fn backend_hooks(backend: &str) -> Vec<&'static str> {
+    let mut hooks = Vec::new();
+    if !source_extensions(backend).is_empty() {
+        hooks.extend(FILE_HOOKS);
+    }
+    if matches!(backend, "cpp" | "dart" | "js" | "kotlin") {
+        hooks.extend(CLASS_HOOKS);
+    }
+    hooks
+}
+
+/// The extensions of the source files a backend generates, which get the file hooks.
+///
+/// Other generated files, such as build scripts or HTML, are left alone.
+fn source_extensions(backend: &str) -> &'static [&'static str] {
+    match backend {
+        "c" => &[".h"],
+        "cpp" => &[".hpp"],
+        "dart" => &[".dart"],
+        "js" | "demo_gen" => &[".mjs", ".d.ts"],
+        "kotlin" => &[".kt"],
+        "mojo" => &[".mojo"],
+        _ => &[],
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TemplatesConfig {
+    /// Also accepted as `templates_dir`
+    #[serde(alias = "templates_dir")]
+    templates_dir: Option<PathBuf>,
+}
+
+/// Variables for the `file_preamble` and `file_epilogue` hooks.
+#[derive(Serialize)]
+struct FileHookVars<'a> {
+    backend: &'a str,
+    file_name: &'a str,
+    diplomat_version: &'a str,
+}
+
+/// Variables for the `class_prologue` hook.
+#[derive(Serialize)]
+pub(crate) struct ClassHookVars<'a> {
+    /// The fully qualified name of the type
+    pub type_name: &'a str,
+    pub namespace: Option<&'a str>,
+    /// "enum", "opaque", or "struct"
+    pub kind: &'static str,
+}
+
+/// Variables for the `method_prologue` and `method_body` hooks.
+#[derive(Serialize)]
+pub(crate) struct MethodHookVars<'a> {
+    /// The fully qualified name of the type
+    pub type_name: &'a str,
+    /// The name of the method in the target language
+    pub method_name: &'a str,
+    /// The name of the C function called
+    pub abi_name: &'a str,
+}
+
+/// Like [`MethodHookVars`], with the method body for the `method_body` hook.
+#[derive(Serialize)]
+struct MethodBodyHookVars<'a> {
+    #[serde(flatten)]
+    method: &'a MethodHookVars<'a>,
+    body: &'a str,
+}
+
+/// The method hooks rendered for a method, to be inserted around its body.
+#[derive(Default, Clone)]
+pub(crate) struct MethodHooks {
+    /// The `method_prologue`, followed by the part of `method_body` before the body
+    pub before_body: Option<String>,
+    /// The part of `method_body` after the body
+    pub after_body: Option<String>,
+}
+
+/// The template hooks the user supplied for a backend.
+#[derive(Default)]
+pub(crate) struct TemplateOverrides {
+    /// The extensions of the files that get file hooks
+    source_extensions: &'static [&'static str],
+    templates: HashMap<&'static str, String>,
+}
+
+impl TemplateOverrides {
+    /// Load the hooks for `backend` from the `templates-dir` set in `library_config`, if any.
+    pub(crate) fn load(library_config: Option<&Path>, backend: &str) -> Result<Self, String> {
+        let Some(config) = library_config else {
+            return Ok(Self::default());
+        };
+        let str = std::fs::read_to_string(config)
+            .map_err(|err| format!("Could not open config toml file {config:?}: {err}"))?;
+        let Some(dir) = toml::from_str::<TemplatesConfig>(&str)
+            .map_err(|err| format!("Parsing error in {config:?}: {err}"))?
+            .templates_dir
+        else {
+            return Ok(Self::default());
+        };
+        let dir = config.parent().unwrap_or(Path::new("")).join(dir);
+        Self::from_dir(&dir.join(backend), backend)
+    }
+
+    fn from_dir(dir: &Path, backend: &str) -> Result<Self, String> {
+        let mut templates = HashMap::new();
+        let source_extensions = source_extensions(backend);
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            // No overrides for this backend
+            return Ok(Self::default());
+        };
+        let known = backend_hooks(backend);
+        for entry in entries {
+            let path = entry
+                .map_err(|err| format!("Could not read templates directory {dir:?}: {err}"))?
+                .path();
+            if path.extension().map_or(true, |ext| ext != "tmpl") {
+                continue;
+            }
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+            let Some(hook) = known.iter().find(|h| **h == stem) else {
+                return Err(format!(
+                    "Unknown template hook {path:?} for backend {backend}, expected one of: {}",
+                    known.join(", ")
+                ));
+            };
+            let text = std::fs::read_to_string(&path)
+                .map_err(|err| format!("Could not read template {path:?}: {err}"))?;
+            // Check the syntax upfront so that errors are reported even if the hook isn't used.
+            TinyTemplate::new()
+                .add_template(hook, &text)
+                .map_err(|err| format!("Error parsing template {path:?}: {err}"))?;
+            templates.insert(*hook, text);
+        }
+        Ok(Self {
+            source_extensions,
+            templates,
+        })
+    }
+
+    /// Render `hook` with the given variables. Returns `Ok(None)` if the user didn't supply the hook.
+    fn render(&self, hook: &str, vars: &impl Serialize) -> Result<Option<String>, String> {
+        let Some(text) = self.templates.get(hook) else {
+            return Ok(None);
+        };
+        let mut tt = TinyTemplate::new();
+        // Generated code is not HTML
+        tt.set_default_formatter(&tinytemplate::format_unescaped);
+        tt.add_template(hook, text).map_err(|e| e.to_string())?;
+        tt.render(hook, vars)
+            .map(Some)
+            .map_err(|e| format!("Error rendering `{hook}` template: {e}"))
+    }
+
+    /// Render the `class_prologue` hook, without trailing whitespace, reporting errors to `errors`.
+    pub(crate) fn class_prologue(
+        &self,
+        vars: &ClassHookVars,
+        errors: &ErrorStore<String>,
+    ) -> Option<String> {
+        match self.render("class_prologue", vars) {
+            Ok(prologue) => prologue.map(|p| p.trim_end().to_owned()),
+            Err(e) => {
+                errors.push_error(e);
+                None
+            }
+        }
+    }
+
+    /// Render the `method_prologue` and `method_body` hooks, reporting errors to `errors`.
+    pub(crate) fn method_hooks(
+        &self,
+        vars: &MethodHookVars,
+        errors: &ErrorStore<String>,
+    ) -> MethodHooks {
+        self.try_method_hooks(vars).unwrap_or_else(|e| {
+            errors.push_error(e);
+            MethodHooks::default()
+        })
+    }
+
+    fn try_method_hooks(&self, vars: &MethodHookVars) -> Result<MethodHooks, String> {
+        let prologue = self.render("method_prologue", vars)?;
+        let wrapper = self.render(
+            "method_body",
+            &MethodBodyHookVars {
+                method: vars,
+                body: BODY_PLACEHOLDER,
+            },
+        )?;
+        let (before, after) = match wrapper {
+            Some(ref wrapper) => {
+                let mut parts = wrapper.split(BODY_PLACEHOLDER);
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some(before), Some(after), None) => (before, after),
+                    _ => {
+                        return Err(
+                            "The `method_body` template must use `{body}` exactly once".into()
+                        )
+                    }
+                }
+            }
+            None => ("", ""),
+        };
+        let before_body = [prologue.as_deref().unwrap_or(""), before]
+            .iter()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let after_body = after.trim();
+        Ok(MethodHooks {
+            before_body: (!before_body.is_empty()).then(|| before_body),
+            after_body: (!after_body.is_empty()).then(|| after_body.to_owned()),
+        })
+    }
+
+    /// Apply the `file_preamble` and `file_epilogue` hooks to a generated file, if it is a source file.
+    pub(crate) fn wrap_file(
+        &self,
+        backend: &str,
+        file_name: &str,
+        contents: String,
+    ) -> Result<String, String> {
+        if !self
+            .source_extensions
+            .iter()
+            .any(|ext| file_name.ends_with(ext))
+        {
+            return Ok(contents);
+        }
+        let vars = FileHookVars {
+            backend,
+            file_name,
+            diplomat_version: env!("CARGO_PKG_VERSION"),
+        };
+        let preamble = self.render("file_preamble", &vars)?;
+        let epilogue = self.render("file_epilogue", &vars)?;
+        if preamble.is_none() && epilogue.is_none() {
+            return Ok(contents);
+        }
+        let mut out = preamble.unwrap_or_default();
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&contents);
+        if let Some(epilogue) = epilogue {
+            if !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str(&epilogue);
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ClassHookVars, MethodHookVars, TemplateOverrides, TemplatesConfig};
+    use crate::ErrorStore;
+
+    fn overrides(backend: &str, hooks: &[(&'static str, &str)]) -> TemplateOverrides {
+        TemplateOverrides {
+            source_extensions: super::source_extensions(backend),
+            templates: hooks.iter().map(|(k, v)| (*k, v.to_string())).collect(),
+        }
+    }
+
+    const METHOD: MethodHookVars = MethodHookVars {
+        type_name: "ns::Foo",
+        method_name: "bar",
+        abi_name: "Foo_bar",
+    };
+
+    #[test]
+    fn test_method_hooks() {
+        let templates = overrides(
+            "cpp",
+            &[
+                (
+                    "method_prologue",
+                    "TRACE(\"{type_name}::{method_name}\");\n",
+                ),
+                (
+                    "method_body",
+                    "try \\{\n{body}\n} catch (...) \\{ {abi_name}_failed(); }",
+                ),
+            ],
+        );
+        let errors = ErrorStore::default();
+        let hooks = templates.method_hooks(&METHOD, &errors);
+        assert!(errors.take_all().is_empty());
+        assert_eq!(
+            hooks.before_body.as_deref(),
+            Some("TRACE(\"ns::Foo::bar\");\ntry {")
+        );
+        assert_eq!(
+            hooks.after_body.as_deref(),
+            Some("} catch (...) { Foo_bar_failed(); }")
+        );
+    }
+
+    #[test]
+    fn test_method_hooks_unset() {
+        let errors = ErrorStore::default();
+        let hooks = overrides("cpp", &[]).method_hooks(&METHOD, &errors);
+        assert!(errors.take_all().is_empty());
+        assert!(hooks.before_body.is_none());
+        assert!(hooks.after_body.is_none());
+    }
+
+    #[test]
+    fn test_method_body_without_body() {
+        for body in ["no body here", "{body}{body}"] {
+            let templates = overrides("js", &[("method_body", body)]);
+            let errors = ErrorStore::default();
+            let hooks = templates.method_hooks(&METHOD, &errors);
+            let errors = errors.take_all();
+            assert_eq!(errors.len(), 1, "{body}");
+            assert!(errors[0].1.contains("exactly once"), "{}", errors[0].1);
+            assert!(hooks.before_body.is_none());
+        }
+    }
+
+    #[test]
+    fn test_class_prologue() {
+        let templates = overrides(
+            "dart",
+            &[(
+                "class_prologue",
+                "// {kind} {type_name}{{ if namespace }} in {namespace}{{ endif }}\n\n",
+            )],
+        );
+        let errors = ErrorStore::default();
+        let vars = ClassHookVars {
+            type_name: "Foo",
+            namespace: None,
+            kind: "opaque",
+        };
+        assert_eq!(
+            templates.class_prologue(&vars, &errors).as_deref(),
+            Some("// opaque Foo")
+        );
+        let vars = ClassHookVars {
+            namespace: Some("ns"),
+            ..vars
+        };
+        assert_eq!(
+            templates.class_prologue(&vars, &errors).as_deref(),
+            Some("// opaque Foo in ns")
+        );
+        assert!(errors.take_all().is_empty());
+    }
+
+    #[test]
+    fn test_wrap_file() {
+        let templates = overrides(
+            "dart",
+            &[
+                ("file_preamble", "// {file_name}"),
+                ("file_epilogue", "// end"),
+            ],
+        );
+        assert_eq!(
+            templates
+                .wrap_file("dart", "Foo.g.dart", "class Foo {}".into())
+                .unwrap(),
+            "// Foo.g.dart\nclass Foo {}\n// end"
+        );
+        // Only source files are wrapped
+        assert_eq!(
+            templates
+                .wrap_file("dart", "pubspec.yaml", "name: foo".into())
+                .unwrap(),
+            "name: foo"
+        );
+    }
+
+    #[test]
+    fn test_templates_dir_key() {
+        for config in [r#"templates-dir = "t""#, r#"templates_dir = "t""#] {
+            let config = toml::from_str::<TemplatesConfig>(config).unwrap();
+            assert_eq!(config.templates_dir, Some("t".into()));
+        }
+    }
+
+    #[test]
+    fn test_unknown_hook() {
+        let dir = std::env::temp_dir().join(format!("diplomat-templates-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("class_prologue.tmpl"), "// {type_name}").unwrap();
+        let err = TemplateOverrides::from_dir(&dir, "c").err();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let err = err.expect("class hooks are not supported by the C backend");
+        assert!(err.contains("Unknown template hook"), "{err}");
+        assert!(err.contains("file_preamble, file_epilogue"), "{err}");
+    }
+}
@@ -0,0 +1,190 @@
+//! This module contains functions for formatting names in the WIT world and the Rust adapter
+
+use diplomat_core::hir::{self, Docs, DocsUrlGenerator, TypeContext, TypeId};
+use heck::{ToKebabCase, ToSnakeCase, ToUpperCamelCase};
+use std::collections::BTreeMap;
+
+/// Keywords of the WIT language, which have to be escaped with `%` when used as identifiers.
+const WIT_KEYWORDS: &[&str] = &[
+    "as",
+    "async",
+    "bool",
+    "borrow",
+    "char",
+    "constructor",
+    "enum",
+    "error-context",
+    "export",
+    "f32",
+    "f64",
+    "flags",
+    "from",
+    "func",
+    "future",
+    "import",
+    "include",
+    "interface",
+    "list",
+    "option",
+    "own",
+    "package",
+    "record",
+    "resource",
+    "result",
+    "s16",
+    "s32",
+    "s64",
+    "s8",
+    "static",
+    "stream",
+    "string",
+    "tuple",
+    "type",
+    "u16",
+    "u32",
+    "u64",
+    "u8",
+    "use",
+    "variant",
+    "with",
+    "world",
+];
+
+/// Keywords that `wit-bindgen` appends a `_` to when they are used as Rust identifiers.
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// This type mediates all formatting
+///
+/// All identifiers from the HIR should go through here before being formatted
+/// into the output: This makes it easy to handle reserved words or add rename support
+///
+/// WIT names are kebab-case, and `wit-bindgen` derives the Rust names in the adapter from them, so most
+/// names are formatted twice: once for the world, and once the way `wit-bindgen` will spell them.
+pub(crate) struct WitFormatter<'tcx> {
+    tcx: &'tcx TypeContext,
+    docs_url_gen: &'tcx DocsUrlGenerator,
+    /// Full Rust paths of the bridge types, keyed by type name. See [`crate::rust_paths::type_paths`].
+    rust_paths: BTreeMap<String, String>,
+}
+
+impl<'tcx> WitFormatter<'tcx> {
+    pub fn new(
+        tcx: &'tcx TypeContext,
+        docs_url_gen: &'tcx DocsUrlGenerator,
+        rust_paths: BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            tcx,
+            docs_url_gen,
+            rust_paths,
+        }
+    }
+
+    /// Format a WIT identifier, i.e. `FixedDecimal` as `fixed-decimal`.
+    pub fn fmt_ident(&self, name: &str) -> String {
+        wit_ident(name)
+    }
+
+    /// Format the (renamed) name of a type as used in the WIT world.
+    pub fn fmt_type_name(&self, id: TypeId) -> String {
+        let type_def = self.tcx.resolve_type(id);
+        let name = type_def
+            .attrs()
+            .rename
+            .apply(type_def.name().as_str().into());
+        wit_ident(&name)
+    }
+
+    /// Format the name `wit-bindgen` gives the Rust type generated for a type in the WIT world.
+    pub fn fmt_rust_type_name(&self, id: TypeId) -> String {
+        rust_type_ident(&self.fmt_type_name(id))
+    }
+
+    /// Format the full path of a bridge type in the crate, i.e. `crate::ffi::FixedDecimal`.
+    pub fn fmt_bridge_path(&self, id: TypeId) -> String {
+        let name = self.tcx.resolve_type(id).name().as_str();
+        self.rust_paths
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| format!("crate::ffi::{name}"))
+    }
+
+    /// Format the name of a method as used in the WIT world.
+    ///
+    /// Methods of structs and enums become free functions of the interface, so they are prefixed with the type name.
+    pub fn fmt_method_name(&self, method: &hir::Method, owner: Option<TypeId>) -> String {
+        let name = wit_ident(&method.attrs.rename.apply(method.name.as_str().into()));
+        match owner {
+            Some(id) => format!("{}-{name}", self.fmt_type_name(id)),
+            None => name,
+        }
+    }
+
+    /// Format the name of an enum variant as used in the WIT world.
+    pub fn fmt_enum_variant(&self, variant: &hir::EnumVariant) -> String {
+        wit_ident(&variant.attrs.rename.apply(variant.name.as_str().into()))
+    }
+
+    /// Format the documentation of an item as `///` comments, one per line.
+    pub fn fmt_docs(&self, docs: &Docs) -> Vec<String> {
+        docs.to_markdown(self.docs_url_gen)
+            .trim()
+            .lines()
+            .map(|line| format!("/// {line}").trim_end().to_string())
+            .collect()
+    }
+}
+
+/// Format a name as a kebab-case WIT identifier, escaping keywords.
+///
+/// Every word of a WIT identifier has to start with a letter, so words starting with a digit are
+/// joined to the previous one (`Matrix_3x3` is `matrix3x3`), and so are capitals following a digit
+/// (`Vec2D` is `vec2d`, not `vec2-d`).
+pub(crate) fn wit_ident(name: &str) -> String {
+    let mut prev = None;
+    let name = name
+        .chars()
+        .map(|c| {
+            let after_digit = prev.map_or(false, |p: char| p.is_ascii_digit());
+            prev = Some(c);
+            if after_digit {
+                c.to_ascii_lowercase()
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+    let mut ident = String::new();
+    for word in name.to_kebab_case().split('-') {
+        if !ident.is_empty() && !word.starts_with(|c: char| c.is_ascii_digit()) {
+            ident.push('-');
+        }
+        ident.push_str(word);
+    }
+    if WIT_KEYWORDS.contains(&ident.as_str()) {
+        format!("%{ident}")
+    } else {
+        ident
+    }
+}
+
+/// The name `wit-bindgen` gives a Rust type generated for the WIT type `ident`.
+pub(crate) fn rust_type_ident(ident: &str) -> String {
+    ident.trim_start_matches('%').to_upper_camel_case()
+}
+
+/// The name `wit-bindgen` gives a Rust function, field or parameter generated for the WIT item `ident`.
+pub(crate) fn rust_value_ident(ident: &str) -> String {
+    let name = ident.trim_start_matches('%').to_snake_case();
+    if RUST_KEYWORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
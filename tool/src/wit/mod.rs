@@ -0,0 +1,434 @@
+//! WIT / component-model backend.
+//!
+//! Generates a WIT world describing the bridge (`wit/world.wit`), and a Rust adapter (`adapter.rs`) that
+//! implements it on top of the bridge with `wit-bindgen`, so that the library can be built as a WASI
+//! component and used from any component-model host.
+//!
+//! See `docs/wit.md` for how to set up the library crate.
+mod formatter;
+mod ty;
+
+use std::path::Path;
+
+use askama::Template;
+use diplomat_core::hir::{BackendAttrSupport, DocsUrlGenerator, TypeContext, TypeDef};
+use serde::Deserialize;
+
+use crate::{rust_paths, ErrorStore, FileMap};
+use formatter::{rust_value_ident, wit_ident, WitFormatter};
+use ty::TyGenContext;
+
+pub(crate) fn attr_support() -> BackendAttrSupport {
+    let mut a = BackendAttrSupport::default();
+
+    a.namespacing = false;
+    a.memory_sharing = false;
+    a.non_exhaustive_structs = false;
+    a.method_overloading = false;
+    a.utf8_strings = true;
+    a.utf16_strings = true;
+    a.static_slices = false;
+    a.static_borrows = false;
+    a.int128 = false;
+    a.write16 = false;
+    a.reads = false;
+    a.option = true;
+
+    // Constructors and special methods are left to the bindings generated for the host.
+    a.constructors = false;
+    a.named_constructors = false;
+    a.fallible_constructors = false;
+    a.accessors = false;
+    a.stringifiers = false;
+    a.comparators = false;
+    a.iterators = false;
+    a.iterables = false;
+    a.indexing = false;
+    a.streaming_writes = false;
+    a.callbacks = false;
+    a.traits = false;
+
+    a
+}
+
+/// Configuration for the WIT backend, read from the `--library-config` file.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct WitConfig {
+    /// The WIT package, i.e. `my-org:decimal@1.0.0`. Defaults to `<crate name>:bridge`.
+    package: Option<String>,
+    /// The name of the world. Defaults to `bridge`.
+    world: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "wit/world.wit.jinja", escape = "none")]
+struct World {
+    package: String,
+    world: String,
+    records: Vec<WitRecord>,
+    enums: Vec<WitEnum>,
+    resources: Vec<WitResource>,
+    funcs: Vec<WitFunc>,
+}
+
+pub(super) struct WitRecord {
+    docs: Vec<String>,
+    name: String,
+    fields: Vec<WitField>,
+}
+
+pub(super) struct WitField {
+    docs: Vec<String>,
+    name: String,
+    ty: String,
+}
+
+pub(super) struct WitEnum {
+    docs: Vec<String>,
+    name: String,
+    cases: Vec<WitCase>,
+}
+
+pub(super) struct WitCase {
+    docs: Vec<String>,
+    name: String,
+}
+
+pub(super) struct WitResource {
+    docs: Vec<String>,
+    name: String,
+    funcs: Vec<WitFunc>,
+}
+
+pub(super) struct WitFunc {
+    docs: Vec<String>,
+    name: String,
+    /// Whether this is a `static func` of a resource.
+    is_static: bool,
+    /// `(name, type)` pairs.
+    params: Vec<(String, String)>,
+    result: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "wit/adapter.rs.jinja", escape = "none")]
+struct Adapter {
+    /// The text of the world, which `wit-bindgen` is invoked on.
+    wit: String,
+    world: String,
+    /// The path of the module `wit-bindgen` generates the exported interface in.
+    interface_path: String,
+    resources: Vec<AdapterResource>,
+    records: Vec<AdapterRecord>,
+    enums: Vec<AdapterEnum>,
+    funcs: Vec<AdapterFunc>,
+}
+
+pub(super) struct AdapterResource {
+    /// The name `wit-bindgen` gives the resource, which is also used for the type implementing it.
+    name: String,
+    bridge_path: String,
+    funcs: Vec<AdapterFunc>,
+}
+
+pub(super) struct AdapterRecord {
+    name: String,
+    bridge_path: String,
+    /// The (anonymous) lifetime parameters of the bridge type, if any.
+    lifetimes: String,
+    /// Whether the record can be converted to the bridge type, i.e. it does not have lifetimes.
+    input: bool,
+    fields: Vec<AdapterRecordField>,
+}
+
+pub(super) struct AdapterRecordField {
+    rust_name: String,
+    bridge_name: String,
+    /// Converts `value.<rust_name>` to the bridge type.
+    to_bridge: String,
+    /// Converts `value.<bridge_name>` to the `wit-bindgen` type.
+    from_bridge: String,
+}
+
+pub(super) struct AdapterEnum {
+    name: String,
+    bridge_path: String,
+    /// `(wit-bindgen name, bridge name)` pairs.
+    variants: Vec<(String, String)>,
+}
+
+pub(super) struct AdapterFunc {
+    name: String,
+    self_param: Option<&'static str>,
+    /// `(name, type)` pairs.
+    params: Vec<(String, String)>,
+    output: Option<String>,
+    /// Statements converting parameters before the call.
+    prelude: Vec<String>,
+    body: String,
+}
+
+pub(crate) fn run<'tcx>(
+    tcx: &'tcx TypeContext,
+    entry: &Path,
+    conf_path: Option<&Path>,
+    docs_url_gen: &'tcx DocsUrlGenerator,
+) -> (FileMap, ErrorStore<'tcx, String>) {
+    let files = FileMap::default();
+    let errors = ErrorStore::default();
+
+    let conf = conf_path
+        .map(|conf_path| {
+            let conf_str = std::fs::read_to_string(conf_path).unwrap_or_else(|err| {
+                panic!("Could not open config toml file: {conf_path:?} : {err}")
+            });
+            toml::from_str::<WitConfig>(&conf_str)
+                .unwrap_or_else(|err| panic!("Parsing error in {conf_path:?}: {err}"))
+        })
+        .unwrap_or_default();
+
+    let package = conf.package.unwrap_or_else(|| {
+        let name = rust_paths::crate_name(entry).unwrap_or_else(|| "diplomat".into());
+        format!("{}:bridge", wit_ident(&name))
+    });
+    let world = conf
+        .world
+        .map_or_else(|| "bridge".into(), |w| wit_ident(&w));
+    let Some(interface_path) = interface_path(&package) else {
+        errors.push_error(format!(
+            "Invalid WIT package {package:?}, expected `<namespace>:<name>`"
+        ));
+        return (files, errors);
+    };
+
+    let formatter = WitFormatter::new(tcx, docs_url_gen, rust_paths::type_paths(entry, "crate"));
+    let (world, adapter) = gen_world(tcx, &formatter, &errors, package, world, interface_path);
+
+    files.add_file("wit/world.wit".into(), world.render().unwrap());
+    files.add_file("adapter.rs".into(), adapter.render().unwrap());
+
+    (files, errors)
+}
+
+/// Generate the world and the adapter implementing it.
+fn gen_world<'tcx>(
+    tcx: &'tcx TypeContext,
+    formatter: &WitFormatter<'tcx>,
+    errors: &ErrorStore<'tcx, String>,
+    package: String,
+    world: String,
+    interface_path: String,
+) -> (World, Adapter) {
+    let context = TyGenContext {
+        tcx,
+        formatter,
+        errors,
+    };
+
+    let mut out = World {
+        package,
+        world: world.clone(),
+        records: Vec::new(),
+        enums: Vec::new(),
+        resources: Vec::new(),
+        funcs: Vec::new(),
+    };
+    let mut adapter = Adapter {
+        wit: String::new(),
+        world,
+        interface_path,
+        resources: Vec::new(),
+        records: Vec::new(),
+        enums: Vec::new(),
+        funcs: Vec::new(),
+    };
+
+    for (id, ty) in tcx.all_types() {
+        if ty.attrs().disable {
+            continue;
+        }
+        let _guard = errors.set_context_ty(ty.name().as_str().into());
+
+        match ty {
+            TypeDef::Opaque(o) => {
+                let (resource, adapter_resource) = context.gen_opaque_def(id, o);
+                out.resources.push(resource);
+                adapter.resources.push(adapter_resource);
+                continue;
+            }
+            TypeDef::Struct(s) => {
+                let (record, adapter_record) = context.gen_struct_def(id, s);
+                out.records.push(record);
+                adapter.records.push(adapter_record);
+            }
+            TypeDef::OutStruct(s) => {
+                let (record, adapter_record) = context.gen_struct_def(id, s);
+                out.records.push(record);
+                adapter.records.push(adapter_record);
+            }
+            TypeDef::Enum(e) => {
+                let (wit_enum, adapter_enum) = context.gen_enum_def(id, e);
+                out.enums.push(wit_enum);
+                adapter.enums.push(adapter_enum);
+            }
+            _ => unreachable!("unknown AST/HIR variant"),
+        }
+
+        // Methods of structs and enums are free functions of the interface.
+        for method in ty.methods().iter().filter(|m| !m.attrs.disable) {
+            let _guard =
+                errors.set_context_method(ty.name().as_str().into(), method.name.as_str().into());
+            let (func, adapter_func) = context.gen_method(id, method, true);
+            out.funcs.push(func);
+            adapter.funcs.push(adapter_func);
+        }
+    }
+
+    adapter.wit = out.render().unwrap();
+
+    (out, adapter)
+}
+
+/// The path of the module that `wit-bindgen` generates for the `api` interface exported from `package`.
+fn interface_path(package: &str) -> Option<String> {
+    // Versions are not part of the path.
+    let package = package.split('@').next()?;
+    let (namespace, name) = package.split_once(':')?;
+    if namespace.is_empty() || name.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "exports::{}::{}::api",
+        rust_value_ident(namespace),
+        rust_value_ident(name)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diplomat_core::hir;
+    use std::collections::BTreeMap;
+
+    fn gen(tk_stream: proc_macro2::TokenStream) -> (String, String) {
+        let file = syn::parse2::<syn::File>(tk_stream).expect("failed to parse item");
+        let mut attr_validator = hir::BasicAttributeValidator::new("wit");
+        attr_validator.support = attr_support();
+        let tcx = TypeContext::from_syn(&file, attr_validator).expect("failed to lower");
+        let docs_url_gen = hir::DocsUrlGenerator::with_base_urls(None, Default::default());
+        let formatter = WitFormatter::new(&tcx, &docs_url_gen, BTreeMap::new());
+        let errors = ErrorStore::default();
+        let (_, adapter) = gen_world(
+            &tcx,
+            &formatter,
+            &errors,
+            "my-org:decimal".into(),
+            "decimal".into(),
+            interface_path("my-org:decimal").unwrap(),
+        );
+        let errors = errors.take_all();
+        assert!(
+            errors.is_empty(),
+            "{:?}",
+            errors
+                .into_iter()
+                .map(|(cx, err)| format!("{cx}: {err}"))
+                .collect::<Vec<_>>()
+        );
+        let rendered = adapter.render().unwrap();
+        (adapter.wit, rendered)
+    }
+
+    #[test]
+    fn test_idents() {
+        assert_eq!(wit_ident("FixedDecimal"), "fixed-decimal");
+        assert_eq!(wit_ident("Vec2D"), "vec2d");
+        assert_eq!(wit_ident("Vec2DPoint"), "vec2d-point");
+        assert_eq!(wit_ident("Matrix_3x3"), "matrix3x3");
+        assert_eq!(wit_ident("type"), "%type");
+        assert_eq!(wit_ident("to_string"), "to-string");
+        assert_eq!(rust_value_ident("%type"), "type_");
+        assert_eq!(
+            interface_path("my-org:decimal@1.0.0").as_deref(),
+            Some("exports::my_org::decimal::api")
+        );
+        assert_eq!(interface_path("decimal"), None);
+    }
+
+    #[test]
+    fn test_world() {
+        let (wit, adapter) = gen(quote::quote! {
+            #[diplomat::bridge]
+            mod ffi {
+                #[diplomat::opaque]
+                struct Decimal(i64);
+
+                enum Sign {
+                    Negative,
+                    Positive,
+                }
+
+                struct Options {
+                    sign: Sign,
+                    digits: u8,
+                }
+
+                impl Decimal {
+                    pub fn new(v: i32) -> Box<Decimal> {
+                        unimplemented!()
+                    }
+
+                    pub fn from_str(s: &DiplomatStr) -> Result<Box<Decimal>, ()> {
+                        unimplemented!()
+                    }
+
+                    pub fn multiply_pow10(&mut self, power: i16) {
+                        unimplemented!()
+                    }
+
+                    pub fn format(&self, options: Options, write: &mut DiplomatWrite) {
+                        unimplemented!()
+                    }
+                }
+
+                impl Options {
+                    pub fn flip(self) -> Options {
+                        unimplemented!()
+                    }
+                }
+            }
+        });
+
+        assert!(wit.contains("package my-org:decimal;"), "{wit}");
+        assert!(wit.contains("resource decimal {"), "{wit}");
+        assert!(
+            wit.contains("from-str: static func(s: string) -> result<decimal>;"),
+            "{wit}"
+        );
+        assert!(wit.contains("multiply-pow10: func(power: s16);"), "{wit}");
+        assert!(
+            wit.contains("format: func(options: options) -> string;"),
+            "{wit}"
+        );
+        assert!(
+            wit.contains("options-flip: func(this: options) -> options;"),
+            "{wit}"
+        );
+        assert!(wit.contains("world decimal {"), "{wit}");
+
+        assert!(
+            adapter.contains("use exports::my_org::decimal::api as wit;"),
+            "{adapter}"
+        );
+        assert!(
+            adapter
+                .contains("crate::ffi::Decimal::multiply_pow10(&mut self.0.borrow_mut(), power)"),
+            "{adapter}"
+        );
+        assert!(
+            adapter.contains("crate::ffi::Decimal::from_str(s.as_bytes().into())"),
+            "{adapter}"
+        );
+    }
+}
@@ -0,0 +1,646 @@
+use diplomat_core::hir::{
+    EnumDef, FloatType, IntSizeType, IntType, MaybeStatic, Method, OpaqueDef, OpaqueOwner,
+    PrimitiveType, ReturnType, SelfType, Slice, StringEncoding, StructDef, StructPathLike,
+    SuccessType, TyPosition, Type, TypeContext, TypeDef, TypeId,
+};
+
+use super::formatter::{rust_type_ident, rust_value_ident, WitFormatter};
+use super::{
+    AdapterEnum, AdapterFunc, AdapterRecord, AdapterRecordField, AdapterResource, WitCase, WitEnum,
+    WitField, WitFunc, WitRecord, WitResource,
+};
+use crate::ErrorStore;
+
+/// Generates the WIT declarations for each type, along with the adapter code that converts between
+/// the Rust types generated by `wit-bindgen` and the bridge's types.
+pub(super) struct TyGenContext<'a, 'tcx> {
+    pub tcx: &'tcx TypeContext,
+    pub formatter: &'a WitFormatter<'tcx>,
+    pub errors: &'a ErrorStore<'tcx, String>,
+}
+
+impl<'a, 'tcx> TyGenContext<'a, 'tcx> {
+    /// Opaques become resources, which the adapter implements by wrapping the boxed opaque.
+    pub fn gen_opaque_def(
+        &self,
+        id: TypeId,
+        def: &'tcx OpaqueDef,
+    ) -> (WitResource, AdapterResource) {
+        if def.lifetimes.num_lifetimes() > 0 {
+            self.errors.push_error(
+                "Opaques that borrow from other values cannot be exported as resources".into(),
+            );
+        }
+
+        let mut resource = WitResource {
+            docs: self.formatter.fmt_docs(&def.docs),
+            name: self.formatter.fmt_type_name(id),
+            funcs: Vec::new(),
+        };
+        let mut adapter = AdapterResource {
+            name: self.formatter.fmt_rust_type_name(id),
+            bridge_path: self.formatter.fmt_bridge_path(id),
+            funcs: Vec::new(),
+        };
+
+        for method in def.methods.iter().filter(|m| !m.attrs.disable) {
+            let _guard = self
+                .errors
+                .set_context_method(def.name.as_str().into(), method.name.as_str().into());
+            let (func, adapter_func) = self.gen_method(id, method, false);
+            resource.funcs.push(func);
+            adapter.funcs.push(adapter_func);
+        }
+
+        (resource, adapter)
+    }
+
+    /// Structs become records, with a `From` impl to convert them in either direction.
+    ///
+    /// Records are copied across the component boundary, so only structs without lifetimes can be
+    /// converted to the bridge's type.
+    pub fn gen_struct_def<P: TyPosition>(
+        &self,
+        id: TypeId,
+        def: &'tcx StructDef<P>,
+    ) -> (WitRecord, AdapterRecord) {
+        if def.fields.is_empty() {
+            self.errors
+                .push_error("Records need at least one field".into());
+        }
+        let num_lifetimes = def.lifetimes.num_lifetimes();
+        let input = num_lifetimes == 0 && matches!(self.tcx.resolve_type(id), TypeDef::Struct(_));
+
+        let mut record = WitRecord {
+            docs: self.formatter.fmt_docs(&def.docs),
+            name: self.formatter.fmt_type_name(id),
+            fields: Vec::new(),
+        };
+        let mut adapter = AdapterRecord {
+            name: self.formatter.fmt_rust_type_name(id),
+            bridge_path: self.formatter.fmt_bridge_path(id),
+            lifetimes: if num_lifetimes == 0 {
+                String::new()
+            } else {
+                format!("<{}>", vec!["'_"; num_lifetimes].join(", "))
+            },
+            input,
+            fields: Vec::new(),
+        };
+
+        for field in &def.fields {
+            let name = self.formatter.fmt_ident(field.name.as_str());
+            let rust_name = rust_value_ident(&name);
+            let bridge_name = field.name.as_str().to_string();
+            record.fields.push(WitField {
+                docs: self.formatter.fmt_docs(&field.docs),
+                ty: self.gen_wit_type(&field.ty),
+                name,
+            });
+            adapter.fields.push(AdapterRecordField {
+                to_bridge: if input {
+                    let mut prelude = Vec::new();
+                    let expr =
+                        self.gen_to_bridge(&field.ty, &format!("value.{rust_name}"), &mut prelude);
+                    if !prelude.is_empty() {
+                        self.errors.push_error(format!(
+                            "Field {bridge_name} cannot be converted without allocating, and is not supported in records"
+                        ));
+                    }
+                    expr
+                } else {
+                    String::new()
+                },
+                from_bridge: self.gen_from_bridge(&field.ty, &format!("value.{bridge_name}")),
+                rust_name,
+                bridge_name,
+            });
+        }
+
+        (record, adapter)
+    }
+
+    /// Enums become WIT enums, converted with a `match` in either direction.
+    pub fn gen_enum_def(&self, id: TypeId, def: &'tcx EnumDef) -> (WitEnum, AdapterEnum) {
+        if def.variants.is_empty() {
+            self.errors
+                .push_error("Enums need at least one variant".into());
+        }
+        let mut wit_enum = WitEnum {
+            docs: self.formatter.fmt_docs(&def.docs),
+            name: self.formatter.fmt_type_name(id),
+            cases: Vec::new(),
+        };
+        let mut adapter = AdapterEnum {
+            name: self.formatter.fmt_rust_type_name(id),
+            bridge_path: self.formatter.fmt_bridge_path(id),
+            variants: Vec::new(),
+        };
+
+        for variant in &def.variants {
+            let name = self.formatter.fmt_enum_variant(variant);
+            adapter
+                .variants
+                .push((rust_type_ident(&name), variant.name.as_str().to_string()));
+            wit_enum.cases.push(WitCase {
+                docs: self.formatter.fmt_docs(&variant.docs),
+                name,
+            });
+        }
+
+        (wit_enum, adapter)
+    }
+
+    /// Generate the WIT function for a method, and its implementation in the adapter.
+    ///
+    /// Methods of opaques are resource methods (`free` is `false`); methods of structs and enums are
+    /// free functions of the interface, taking `self` as a `this` parameter.
+    pub fn gen_method(
+        &self,
+        owner: TypeId,
+        method: &'tcx Method,
+        free: bool,
+    ) -> (WitFunc, AdapterFunc) {
+        let name = self
+            .formatter
+            .fmt_method_name(method, if free { Some(owner) } else { None });
+        let bridge_path = self.formatter.fmt_bridge_path(owner);
+
+        let mut func = WitFunc {
+            docs: self.formatter.fmt_docs(&method.docs),
+            name: name.clone(),
+            is_static: !free && method.param_self.is_none(),
+            params: Vec::new(),
+            result: None,
+        };
+        let mut adapter = AdapterFunc {
+            name: rust_value_ident(&name),
+            self_param: None,
+            params: Vec::new(),
+            output: None,
+            prelude: Vec::new(),
+            body: String::new(),
+        };
+
+        let mut args = Vec::new();
+        match method.param_self.as_ref().map(|s| &s.ty) {
+            Some(SelfType::Opaque(path)) => {
+                if path.owner.mutability.is_mutable() {
+                    args.push("&mut self.0.borrow_mut()".to_string());
+                } else {
+                    args.push("&self.0.borrow()".to_string());
+                }
+                adapter.self_param = Some("&self");
+            }
+            Some(ty @ (SelfType::Struct(_) | SelfType::Enum(_))) => {
+                let ty: Type = ty.clone().into();
+                func.params.push(("this".into(), self.gen_wit_type(&ty)));
+                adapter
+                    .params
+                    .push(("this".into(), self.gen_rust_type(&ty)));
+                args.push(self.gen_to_bridge(&ty, "this", &mut adapter.prelude));
+            }
+            Some(_) => unreachable!("unknown AST/HIR variant"),
+            None => {}
+        }
+
+        for param in &method.params {
+            let name = self.formatter.fmt_ident(param.name.as_str());
+            let rust_name = rust_value_ident(&name);
+            func.params.push((name, self.gen_wit_type(&param.ty)));
+            adapter
+                .params
+                .push((rust_name.clone(), self.gen_rust_type(&param.ty)));
+            args.push(self.gen_to_bridge(&param.ty, &rust_name, &mut adapter.prelude));
+        }
+
+        let writes = method.output.success_type().is_write();
+        if writes {
+            args.push("write".into());
+        }
+        let call = format!(
+            "{bridge_path}::{}({})",
+            method.name.as_str(),
+            args.join(", ")
+        );
+
+        (func.result, adapter.output, adapter.body) = self.gen_return(&method.output, call);
+
+        (func, adapter)
+    }
+
+    /// Generate the WIT result type and adapter return type of a method, along with the adapter
+    /// expression that evaluates `call` and converts its result.
+    fn gen_return(
+        &self,
+        output: &'tcx ReturnType,
+        call: String,
+    ) -> (Option<String>, Option<String>, String) {
+        let success = |ty: &'tcx SuccessType| match ty {
+            SuccessType::Unit => None,
+            SuccessType::Write => Some(("string".to_string(), "String".to_string())),
+            SuccessType::OutType(ty) => Some((self.gen_wit_type(ty), self.gen_rust_type(ty))),
+            _ => unreachable!("unknown AST/HIR variant"),
+        };
+        let convert_ok = |ty: &'tcx SuccessType| match ty {
+            SuccessType::Unit => None,
+            SuccessType::Write => Some("|()| out".to_string()),
+            SuccessType::OutType(ty) => {
+                Some(format!("|value| {}", self.gen_from_bridge(ty, "value")))
+            }
+            _ => unreachable!("unknown AST/HIR variant"),
+        };
+        let with_write =
+            |body: String| format!("{{ let (result, out) = with_write(|write| {call}); {body} }}");
+
+        match output {
+            ReturnType::Infallible(SuccessType::Unit) => (None, None, call),
+            ReturnType::Infallible(SuccessType::Write) => (
+                Some("string".into()),
+                Some("String".into()),
+                format!("with_write(|write| {call}).1"),
+            ),
+            ReturnType::Infallible(SuccessType::OutType(ty)) => (
+                Some(self.gen_wit_type(ty)),
+                Some(self.gen_rust_type(ty)),
+                self.gen_from_bridge(ty, &call),
+            ),
+            ReturnType::Nullable(SuccessType::Unit) => (
+                Some("bool".into()),
+                Some("bool".into()),
+                format!("{call}.is_some()"),
+            ),
+            ReturnType::Nullable(ok) => {
+                let (wit, rust) = success(ok).unwrap_or_default();
+                let map = format!(".map({})", convert_ok(ok).unwrap_or_default());
+                (
+                    Some(format!("option<{wit}>")),
+                    Some(format!("Option<{rust}>")),
+                    if ok.is_write() {
+                        with_write(format!("result{map}"))
+                    } else {
+                        format!("{call}{map}")
+                    },
+                )
+            }
+            ReturnType::Fallible(ok, err) => {
+                let ok_ty = success(ok);
+                let err_ty = err
+                    .as_ref()
+                    .map(|ty| (self.gen_wit_type(ty), self.gen_rust_type(ty)));
+                let wit = match (&ok_ty, &err_ty) {
+                    (None, None) => "result".to_string(),
+                    (Some((ok, _)), None) => format!("result<{ok}>"),
+                    (None, Some((err, _))) => format!("result<_, {err}>"),
+                    (Some((ok, _)), Some((err, _))) => format!("result<{ok}, {err}>"),
+                };
+                let rust = format!(
+                    "Result<{}, {}>",
+                    ok_ty.map(|(_, rust)| rust).as_deref().unwrap_or("()"),
+                    err_ty.map(|(_, rust)| rust).as_deref().unwrap_or("()")
+                );
+
+                let mut map = String::new();
+                if let Some(convert) = convert_ok(ok) {
+                    map.push_str(&format!(".map({convert})"));
+                }
+                if let Some(err) = err {
+                    map.push_str(&format!(
+                        ".map_err(|error| {})",
+                        self.gen_from_bridge(err, "error")
+                    ));
+                }
+
+                (
+                    Some(wit),
+                    Some(rust),
+                    if ok.is_write() {
+                        with_write(format!("result{map}"))
+                    } else {
+                        format!("{call}{map}")
+                    },
+                )
+            }
+            _ => unreachable!("unknown AST/HIR variant"),
+        }
+    }
+
+    /// The WIT type of a type in the bridge.
+    ///
+    /// Opaques are owned when they are returned (`foo`), and borrowed when they are passed in (`borrow<foo>`).
+    pub fn gen_wit_type<P: TyPosition>(&self, ty: &Type<P>) -> String {
+        match ty {
+            Type::Primitive(prim) => self.gen_wit_primitive(*prim).into(),
+            Type::Opaque(path) => {
+                let name = self.formatter.fmt_type_name(path.tcx_id.into());
+                let name = if path.owner.is_owned() {
+                    name
+                } else {
+                    format!("borrow<{name}>")
+                };
+                if path.is_optional() {
+                    format!("option<{name}>")
+                } else {
+                    name
+                }
+            }
+            Type::Struct(path) => self.formatter.fmt_type_name(path.id()),
+            Type::Enum(path) => self.formatter.fmt_type_name(path.tcx_id.into()),
+            Type::Slice(Slice::Str(..)) => "string".into(),
+            Type::Slice(Slice::Primitive(_, prim)) => {
+                format!("list<{}>", self.gen_wit_primitive(*prim))
+            }
+            Type::Slice(Slice::Strs(..)) => "list<string>".into(),
+            Type::DiplomatOption(inner) => format!("option<{}>", self.gen_wit_type(inner)),
+            Type::Callback(..) | Type::ImplTrait(..) => {
+                self.errors
+                    .push_error("Callbacks and traits are not supported by the WIT backend".into());
+                String::new()
+            }
+            _ => unreachable!("unknown AST/HIR variant"),
+        }
+    }
+
+    fn gen_wit_primitive(&self, prim: PrimitiveType) -> &'static str {
+        match prim {
+            PrimitiveType::Bool => "bool",
+            PrimitiveType::Char => "char",
+            PrimitiveType::Byte => "u8",
+            PrimitiveType::Int(IntType::I8) => "s8",
+            PrimitiveType::Int(IntType::I16) => "s16",
+            PrimitiveType::Int(IntType::I32) => "s32",
+            PrimitiveType::Int(IntType::I64) => "s64",
+            PrimitiveType::Int(IntType::U8) => "u8",
+            PrimitiveType::Int(IntType::U16) => "u16",
+            PrimitiveType::Int(IntType::U32) => "u32",
+            PrimitiveType::Int(IntType::U64) => "u64",
+            // Components are 32-bit.
+            PrimitiveType::IntSize(IntSizeType::Isize) => "s32",
+            PrimitiveType::IntSize(IntSizeType::Usize) => "u32",
+            PrimitiveType::Float(FloatType::F32) => "f32",
+            PrimitiveType::Float(FloatType::F64) => "f64",
+            PrimitiveType::Int128(_) => {
+                self.errors
+                    .push_error("128-bit integers are not supported by the component model".into());
+                ""
+            }
+        }
+    }
+
+    /// The Rust type `wit-bindgen` uses for [`Self::gen_wit_type()`].
+    pub fn gen_rust_type<P: TyPosition>(&self, ty: &Type<P>) -> String {
+        match ty {
+            Type::Primitive(prim) => self.gen_rust_primitive(*prim).into(),
+            Type::Opaque(path) => {
+                let name = self.formatter.fmt_rust_type_name(path.tcx_id.into());
+                let name = if path.owner.is_owned() {
+                    format!("wit::{name}")
+                } else {
+                    format!("wit::{name}Borrow<'_>")
+                };
+                if path.is_optional() {
+                    format!("Option<{name}>")
+                } else {
+                    name
+                }
+            }
+            Type::Struct(path) => format!("wit::{}", self.formatter.fmt_rust_type_name(path.id())),
+            Type::Enum(path) => format!(
+                "wit::{}",
+                self.formatter.fmt_rust_type_name(path.tcx_id.into())
+            ),
+            Type::Slice(Slice::Str(..)) => "String".into(),
+            Type::Slice(Slice::Primitive(_, prim)) => {
+                format!("Vec<{}>", self.gen_rust_primitive(*prim))
+            }
+            Type::Slice(Slice::Strs(..)) => "Vec<String>".into(),
+            Type::DiplomatOption(inner) => format!("Option<{}>", self.gen_rust_type(inner)),
+            // Reported by `gen_wit_type`.
+            _ => String::new(),
+        }
+    }
+
+    fn gen_rust_primitive(&self, prim: PrimitiveType) -> &'static str {
+        match prim {
+            PrimitiveType::Bool => "bool",
+            PrimitiveType::Char => "char",
+            PrimitiveType::Byte => "u8",
+            PrimitiveType::Int(int) => int.as_str(),
+            PrimitiveType::IntSize(IntSizeType::Isize) => "i32",
+            PrimitiveType::IntSize(IntSizeType::Usize) => "u32",
+            PrimitiveType::Float(FloatType::F32) => "f32",
+            PrimitiveType::Float(FloatType::F64) => "f64",
+            // Reported by `gen_wit_primitive`.
+            PrimitiveType::Int128(_) => "",
+        }
+    }
+
+    /// The conversion of a primitive from its `wit-bindgen` type to the bridge's, if they differ.
+    fn gen_primitive_to_bridge(&self, prim: PrimitiveType, expr: &str) -> Option<String> {
+        match prim {
+            // `DiplomatChar` is a `u32`.
+            PrimitiveType::Char => Some(format!("u32::from({expr})")),
+            PrimitiveType::IntSize(IntSizeType::Isize) => Some(format!("{expr} as isize")),
+            PrimitiveType::IntSize(IntSizeType::Usize) => Some(format!("{expr} as usize")),
+            _ => None,
+        }
+    }
+
+    /// The conversion of a primitive from the bridge's type to its `wit-bindgen` type, if they differ.
+    fn gen_primitive_from_bridge(&self, prim: PrimitiveType, expr: &str) -> Option<String> {
+        match prim {
+            // A `DiplomatChar` is not validated.
+            PrimitiveType::Char => Some(format!(
+                "char::from_u32({expr}).unwrap_or(char::REPLACEMENT_CHARACTER)"
+            )),
+            PrimitiveType::IntSize(IntSizeType::Isize) => Some(format!("{expr} as i32")),
+            PrimitiveType::IntSize(IntSizeType::Usize) => Some(format!("{expr} as u32")),
+            _ => None,
+        }
+    }
+
+    /// The expression converting the `wit-bindgen` value in the local `name` to the bridge type `ty`.
+    ///
+    /// Conversions that need to keep an allocation alive for the duration of the call (i.e. re-encoding a
+    /// string as UTF-16) shadow `name` with a statement pushed to `prelude`.
+    pub fn gen_to_bridge<P: TyPosition>(
+        &self,
+        ty: &Type<P>,
+        name: &str,
+        prelude: &mut Vec<String>,
+    ) -> String {
+        match ty {
+            Type::Primitive(prim) => self
+                .gen_primitive_to_bridge(*prim, name)
+                .unwrap_or_else(|| name.to_string()),
+            Type::Opaque(path) => {
+                let resource = self.formatter.fmt_rust_type_name(path.tcx_id.into());
+                let (borrow, reference) = match path.owner.mutability() {
+                    Some(m) if m.is_mutable() => ("borrow_mut", "&mut "),
+                    _ => ("borrow", "&"),
+                };
+                if path.is_optional() {
+                    prelude.push(format!(
+                        "let {name} = {name}.as_ref().map(|r| r.get::<{resource}>().0.{borrow}());"
+                    ));
+                    let as_ref = if reference == "&" { "as_ref" } else { "as_mut" };
+                    format!("{name}.{as_ref}().map(|r| {reference}***r)")
+                } else {
+                    format!("{reference}{name}.get::<{resource}>().0.{borrow}()")
+                }
+            }
+            Type::Struct(path) => {
+                if let TypeDef::Struct(def) = self.tcx.resolve_type(path.id()) {
+                    if def.lifetimes.num_lifetimes() > 0 {
+                        self.errors.push_error(format!(
+                            "Struct {} borrows from other values, and cannot be passed in",
+                            def.name
+                        ));
+                    }
+                }
+                format!(
+                    "{}::from({name})",
+                    self.formatter.fmt_bridge_path(path.id())
+                )
+            }
+            Type::Enum(path) => format!(
+                "{}::from({name})",
+                self.formatter.fmt_bridge_path(path.tcx_id.into())
+            ),
+            Type::Slice(Slice::Str(lifetime, encoding)) => {
+                if let Some(MaybeStatic::Static) = lifetime {
+                    self.errors
+                        .push_error("'static strings cannot be passed in".into());
+                }
+                match (encoding, lifetime) {
+                    (StringEncoding::UnvalidatedUtf16, lifetime) => {
+                        prelude.push(format!(
+                            "let {name}: Vec<u16> = {name}.encode_utf16().collect();"
+                        ));
+                        if lifetime.is_some() {
+                            format!("(&{name}[..]).into()")
+                        } else {
+                            format!("{name}.into_boxed_slice().into()")
+                        }
+                    }
+                    (StringEncoding::UnvalidatedUtf8, Some(_)) => {
+                        format!("{name}.as_bytes().into()")
+                    }
+                    (StringEncoding::UnvalidatedUtf8, None) => {
+                        format!("{name}.into_bytes().into_boxed_slice().into()")
+                    }
+                    (_, Some(_)) => format!("{name}.as_str().into()"),
+                    (_, None) => format!("{name}.into_boxed_str().into()"),
+                }
+            }
+            Type::Slice(Slice::Primitive(borrow, prim)) => {
+                if matches!(borrow, Some(b) if b.mutability.is_mutable()) {
+                    self.errors.push_error(
+                        "Mutable slices cannot be passed in, lists are copied across the component boundary"
+                            .into(),
+                    );
+                }
+                if let Some(MaybeStatic::Static) = borrow.as_ref().map(|b| &b.lifetime) {
+                    self.errors
+                        .push_error("'static slices cannot be passed in".into());
+                }
+                if let Some(convert) = self.gen_primitive_to_bridge(*prim, "v") {
+                    prelude.push(format!(
+                        "let {name}: Vec<_> = {name}.into_iter().map(|v| {convert}).collect();"
+                    ));
+                }
+                if borrow.is_some() {
+                    format!("(&{name}[..]).into()")
+                } else {
+                    format!("{name}.into_boxed_slice().into()")
+                }
+            }
+            Type::Slice(Slice::Strs(encoding)) => {
+                let str_slice = match encoding {
+                    StringEncoding::UnvalidatedUtf8 => {
+                        "diplomat_runtime::DiplomatStrSlice::from(s.as_bytes())"
+                    }
+                    StringEncoding::UnvalidatedUtf16 => {
+                        prelude.push(format!(
+                            "let {name}: Vec<Vec<u16>> = {name}.iter().map(|s| s.encode_utf16().collect()).collect();"
+                        ));
+                        "diplomat_runtime::DiplomatStr16Slice::from(&s[..])"
+                    }
+                    _ => "diplomat_runtime::DiplomatUtf8StrSlice::from(s.as_str())",
+                };
+                format!("(&{name}.iter().map(|s| {str_slice}).collect::<Vec<_>>()[..]).into()")
+            }
+            Type::DiplomatOption(inner) => {
+                let mut inner_prelude = Vec::new();
+                let convert = self.gen_to_bridge(inner, "v", &mut inner_prelude);
+                if !inner_prelude.is_empty() {
+                    self.errors
+                        .push_error("Options of this type are not supported".into());
+                }
+                format!("{name}.map(|v| {convert}).into()")
+            }
+            // Reported by `gen_wit_type`.
+            _ => name.to_string(),
+        }
+    }
+
+    /// The expression converting the bridge value `expr` of type `ty` to its `wit-bindgen` type.
+    pub fn gen_from_bridge<P: TyPosition>(&self, ty: &Type<P>, expr: &str) -> String {
+        match ty {
+            Type::Primitive(prim) => self
+                .gen_primitive_from_bridge(*prim, expr)
+                .unwrap_or_else(|| expr.to_string()),
+            Type::Opaque(path) => {
+                if !path.owner.is_owned() {
+                    self.errors.push_error(
+                        "Borrowed opaques cannot be returned, resources need to be owned".into(),
+                    );
+                }
+                let resource = self.formatter.fmt_rust_type_name(path.tcx_id.into());
+                let wrap =
+                    |expr: &str| format!("wit::{resource}::new({resource}(RefCell::new({expr})))");
+                if path.is_optional() {
+                    format!("{expr}.map(|v| {})", wrap("v"))
+                } else {
+                    wrap(expr)
+                }
+            }
+            Type::Struct(path) => format!(
+                "wit::{}::from({expr})",
+                self.formatter.fmt_rust_type_name(path.id())
+            ),
+            Type::Enum(path) => format!(
+                "wit::{}::from({expr})",
+                self.formatter.fmt_rust_type_name(path.tcx_id.into())
+            ),
+            Type::Slice(Slice::Str(_, encoding)) => match encoding {
+                StringEncoding::UnvalidatedUtf8 => {
+                    format!("String::from_utf8_lossy(&{expr}[..]).into_owned()")
+                }
+                StringEncoding::UnvalidatedUtf16 => {
+                    format!("String::from_utf16_lossy(&{expr}[..])")
+                }
+                _ => format!("String::from(&{expr}[..])"),
+            },
+            Type::Slice(Slice::Primitive(_, prim)) => {
+                match self.gen_primitive_from_bridge(*prim, "v") {
+                    Some(convert) => {
+                        format!("{expr}.iter().map(|&v| {convert}).collect::<Vec<_>>()")
+                    }
+                    None => format!("{expr}[..].to_vec()"),
+                }
+            }
+            Type::Slice(Slice::Strs(..)) => {
+                self.errors
+                    .push_error("Lists of strings cannot be returned".into());
+                String::new()
+            }
+            Type::DiplomatOption(inner) => format!(
+                "{expr}.into_option().map(|v| {})",
+                self.gen_from_bridge(inner, "v")
+            ),
+            // Reported by `gen_wit_type`.
+            _ => expr.to_string(),
+        }
+    }
+}
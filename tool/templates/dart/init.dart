@@ -21,6 +21,11 @@ final _nopFree = core.Finalizer((nothing) => {});
 // ignore: unused_element
 final _rustFree = core.Finalizer((({ffi.Pointer<ffi.Void> pointer, int bytes, int align}) record) => _diplomat_free(record.pointer, record.bytes, record.align));
 
+// Owns the memory of `'static` slices passed to Rust. Rust may hold on to these for
+// the rest of the program, so this arena is never released.
+// ignore: unused_element
+final _staticArena = ffi2.Arena();
+
 // ignore: unused_element
 final class _RustAlloc implements ffi.Allocator {
   @override
@@ -62,3 +67,92 @@ class _FinalizedArena {
     }
   }
 }
+
+/// The level of a log record emitted by the native library, from the least to the most verbose.
+enum DiplomatLogLevel { off, error, warn, info, debug, trace }
+
+typedef _LogSinkNative = ffi.Void Function(ffi.Uint8, ffi.Pointer<ffi.Uint8>, ffi.Size, ffi.Pointer<ffi.Uint8>, ffi.Size);
+
+ffi.NativeCallable<_LogSinkNative>? _logSink;
+
+/// Routes the `log` records of the native library up to [maxLevel] to [sink], replacing the previous
+/// sink. Passing `null` stops forwarding records.
+///
+/// This requires the library to be built with the `log` feature of `diplomat-runtime`.
+///
+/// The sink is called synchronously, so the library must only log on the thread of the isolate that
+/// registered it, which is the case for records logged while calling into the library.
+void setLogSink(void Function(DiplomatLogLevel level, String target, String message)? sink, {DiplomatLogLevel maxLevel = DiplomatLogLevel.info}) {
+  final old = _logSink;
+  _logSink = sink == null ? null : ffi.NativeCallable<_LogSinkNative>.isolateLocal(
+    (int level, ffi.Pointer<ffi.Uint8> target, int targetLen, ffi.Pointer<ffi.Uint8> message, int messageLen) {
+      sink(
+        DiplomatLogLevel.values[level],
+        Utf8Decoder(allowMalformed: true).convert(target.asTypedList(targetLen)),
+        Utf8Decoder(allowMalformed: true).convert(message.asTypedList(messageLen)),
+      );
+    },
+  );
+  _diplomat_log_set_sink(_logSink?.nativeFunction ?? ffi.nullptr, maxLevel.index);
+  old?.close();
+}
+
+/// Routes the `log` records of the native library up to [maxLevel] to `dart:developer`'s [developer.log],
+/// named after their target. See [setLogSink].
+void useDeveloperLog({DiplomatLogLevel maxLevel = DiplomatLogLevel.info}) {
+  setLogSink((level, target, message) {
+    // The values of `package:logging`'s levels.
+    final value = switch (level) {
+      DiplomatLogLevel.off => 2000,
+      DiplomatLogLevel.error => 1000,
+      DiplomatLogLevel.warn => 900,
+      DiplomatLogLevel.info => 800,
+      DiplomatLogLevel.debug => 500,
+      DiplomatLogLevel.trace => 300,
+    };
+    developer.log(message, name: target, level: value);
+  }, maxLevel: maxLevel);
+}
+
+/// Sets the most verbose level of the native library's log records that are forwarded. See [setLogSink].
+void setLogLevel(DiplomatLogLevel level) {
+  _diplomat_log_set_max_level(level.index);
+}
+
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.NativeFunction<_LogSinkNative>>, ffi.Uint8)>(symbol: 'diplomat_log_set_sink')
+// ignore: non_constant_identifier_names
+external void _diplomat_log_set_sink(ffi.Pointer<ffi.NativeFunction<_LogSinkNative>> sink, int maxLevel);
+
+@ffi.Native<ffi.Void Function(ffi.Uint8)>(symbol: 'diplomat_log_set_max_level', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_log_set_max_level(int level);
+
+/// A report of the objects of the native library that haven't been freed, with one `<name>: <count>`
+/// line per kind of object. Empty if everything was freed.
+///
+/// Requires the library to be built with the `leak-check` feature of `diplomat-runtime`. Objects are
+/// freed by finalizers once they are garbage collected, so this is most useful after disposing arenas.
+String leakReport() {
+  if (_diplomat_leak_count() == 0) {
+    return '';
+  }
+  final write = _Write();
+  _diplomat_leak_report(write._ffi);
+  return write.finalize();
+}
+
+/// Throws a [StateError] listing the objects of the native library that haven't been freed. See [leakReport].
+void assertNoLeaks() {
+  final report = leakReport();
+  if (report.isNotEmpty) {
+    throw StateError('Leaked objects:\n$report');
+  }
+}
+
+@ffi.Native<ffi.Size Function()>(symbol: 'diplomat_leak_count', isLeaf: true)
+// ignore: non_constant_identifier_names
+external int _diplomat_leak_count();
+
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_leak_report', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_leak_report(ffi.Pointer<ffi.Opaque> write);
@@ -0,0 +1,61 @@
+typedef _ReadSourceNative = ffi.IntPtr Function(ffi.Pointer<ffi.Void>, ffi.Pointer<ffi.Uint8>, ffi.Size);
+
+/// A `DiplomatRead` that reads from the chunks of a [core.Iterable], for `DiplomatRead` parameters.
+final class _Reader {
+  late final ffi.NativeCallable<_ReadSourceNative> _callable;
+  late final ffi.Pointer<ffi.Opaque> _ffi;
+  core.Object? _error;
+  core.StackTrace? _stackTrace;
+
+  _Reader(core.Iterable<core.List<int>> source) {
+    final chunks = source.iterator;
+    core.List<int> chunk = const [];
+    var offset = 0;
+    _callable = ffi.NativeCallable<_ReadSourceNative>.isolateLocal(
+      (ffi.Pointer<ffi.Void> _, ffi.Pointer<ffi.Uint8> buf, int len) {
+        if (_error != null) {
+          return -1;
+        }
+        try {
+          while (offset == chunk.length) {
+            if (!chunks.moveNext()) {
+              return 0;
+            }
+            chunk = chunks.current;
+            offset = 0;
+          }
+        } catch (e, s) {
+          _error = e;
+          _stackTrace = s;
+          return -1;
+        }
+        final read = chunk.length - offset < len ? chunk.length - offset : len;
+        buf.asTypedList(read).setRange(0, read, chunk, offset);
+        offset += read;
+        return read;
+      },
+      exceptionalReturn: -1,
+    );
+    _ffi = _diplomat_read_create(ffi.nullptr, _callable.nativeFunction);
+  }
+
+  /// Frees the reader, and rethrows the error thrown by the source, if any.
+  void finalize() {
+    _diplomat_read_destroy(_ffi);
+    _callable.close();
+    final error = _error;
+    if (error != null) {
+      core.Error.throwWithStackTrace(error, _stackTrace!);
+    }
+  }
+}
+
+@meta.RecordUse()
+@ffi.Native<ffi.Pointer<ffi.Opaque> Function(ffi.Pointer<ffi.Void>, ffi.Pointer<ffi.NativeFunction<_ReadSourceNative>>)>(symbol: 'diplomat_read_create', isLeaf: true)
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Opaque> _diplomat_read_create(ffi.Pointer<ffi.Void> context, ffi.Pointer<ffi.NativeFunction<_ReadSourceNative>> read);
+
+@meta.RecordUse()
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_read_destroy', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_read_destroy(ffi.Pointer<ffi.Opaque> ptr);
\ No newline at end of file
@@ -0,0 +1,47 @@
+typedef _WriteSinkNative = ffi.Bool Function(ffi.Pointer<ffi.Void>, ffi.Pointer<ffi.Uint8>, ffi.Size);
+
+/// A `DiplomatWrite` that adds its output to a [core.Sink] as it is written, for methods marked `stream`.
+final class _StreamWrite {
+  late final ffi.NativeCallable<_WriteSinkNative> _callable;
+  late final ffi.Pointer<ffi.Opaque> _ffi;
+  core.Object? _error;
+  core.StackTrace? _stackTrace;
+
+  _StreamWrite(core.Sink<core.List<int>> sink) {
+    _callable = ffi.NativeCallable<_WriteSinkNative>.isolateLocal(
+      (ffi.Pointer<ffi.Void> _, ffi.Pointer<ffi.Uint8> chunk, int len) {
+        try {
+          // The chunk is only valid during the call
+          sink.add(Uint8List.fromList(chunk.asTypedList(len)));
+          return true;
+        } catch (e, s) {
+          _error = e;
+          _stackTrace = s;
+          return false;
+        }
+      },
+      exceptionalReturn: false,
+    );
+    _ffi = _diplomat_stream_write_create(ffi.nullptr, _callable.nativeFunction, 1024);
+  }
+
+  /// Frees the write, and rethrows the error thrown by the sink, if any.
+  void finalize() {
+    _diplomat_stream_write_destroy(_ffi);
+    _callable.close();
+    final error = _error;
+    if (error != null) {
+      core.Error.throwWithStackTrace(error, _stackTrace!);
+    }
+  }
+}
+
+@meta.RecordUse()
+@ffi.Native<ffi.Pointer<ffi.Opaque> Function(ffi.Pointer<ffi.Void>, ffi.Pointer<ffi.NativeFunction<_WriteSinkNative>>, ffi.Size)>(symbol: 'diplomat_stream_write_create', isLeaf: true)
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Opaque> _diplomat_stream_write_create(ffi.Pointer<ffi.Void> context, ffi.Pointer<ffi.NativeFunction<_WriteSinkNative>> sink, int cap);
+
+@meta.RecordUse()
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_stream_write_destroy', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_stream_write_destroy(ffi.Pointer<ffi.Opaque> ptr);
\ No newline at end of file
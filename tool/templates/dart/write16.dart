@@ -0,0 +1,37 @@
+final class _Write16 {
+  final ffi.Pointer<ffi.Opaque> _ffi;
+
+  _Write16() : _ffi = _diplomat_buffer_write16_create(0);
+  
+  String finalize() {
+    try {
+      final buf = _diplomat_buffer_write16_get_units(_ffi);
+      if (buf == ffi.Pointer.fromAddress(0)) {
+        throw core.OutOfMemoryError();
+      }
+      return core.String.fromCharCodes(buf.asTypedList(_diplomat_buffer_write16_len(_ffi)));
+    } finally {
+      _diplomat_buffer_write16_destroy(_ffi);
+    }
+  }
+}
+
+@meta.RecordUse()
+@ffi.Native<ffi.Pointer<ffi.Opaque> Function(ffi.Size)>(symbol: 'diplomat_buffer_write16_create', isLeaf: true)
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Opaque> _diplomat_buffer_write16_create(int len);
+
+@meta.RecordUse()
+@ffi.Native<ffi.Size Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_buffer_write16_len', isLeaf: true)
+// ignore: non_constant_identifier_names
+external int _diplomat_buffer_write16_len(ffi.Pointer<ffi.Opaque> ptr);
+
+@meta.RecordUse()
+@ffi.Native<ffi.Pointer<ffi.Uint16> Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_buffer_write16_get_units', isLeaf: true)
+// ignore: non_constant_identifier_names
+external ffi.Pointer<ffi.Uint16> _diplomat_buffer_write16_get_units(ffi.Pointer<ffi.Opaque> ptr);
+
+@meta.RecordUse()
+@ffi.Native<ffi.Void Function(ffi.Pointer<ffi.Opaque>)>(symbol: 'diplomat_buffer_write16_destroy', isLeaf: true)
+// ignore: non_constant_identifier_names
+external void _diplomat_buffer_write16_destroy(ffi.Pointer<ffi.Opaque> ptr);
\ No newline at end of file
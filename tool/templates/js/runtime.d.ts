@@ -12,4 +12,25 @@
 ///
 /// A `String` can be constructed from a `codepoint` using `String.fromCodePoint()`. 
 export type codepoint = number;
-export type pointer = number;
\ No newline at end of file
+/// Pointers are `number`s on wasm32 and `bigint`s on wasm64.
+export type pointer = number | bigint;
+
+/// A panic of the Rust library, thrown when the panic hook of `diplomat-runtime` is installed.
+export class RustPanicError extends Error {
+    /// The source file that panicked, if known.
+    readonly file: string | null;
+    /// The line of the panic in `file`, if known.
+    readonly line: number | null;
+    /// The column of the panic in `file`, if known.
+    readonly column: number | null;
+
+    constructor(message: string, file: string | null, line: number | null, column: number | null);
+
+    /// The last panic of the library, or `null` if it hasn't panicked since the hook was installed.
+    static last(wasm: WebAssembly.Exports): RustPanicError | null;
+}
+
+/// Throws an `Error` listing the opaques and runtime allocations of the library that haven't been freed.
+///
+/// Requires the library to be built with the `leak-check` feature of `diplomat-runtime`.
+export function assertNoLeaks(wasm: WebAssembly.Exports): void;
@@ -1,7 +1,9 @@
 import cfg from '../diplomat.config.mjs';
-import {readString8} from './diplomat-runtime.mjs'
+import {readString8, DiplomatStreamWrite, DiplomatReader, RustPanicError} from './diplomat-runtime.mjs'
 
 let wasm;
+// Kept for `checkStructLayouts`, which reads the layouts the library was built with from it.
+let wasmModule;
 
 const imports = {
 env: {
@@ -20,8 +22,15 @@ env: {
     diplomat_console_warn_js(ptr, len) {
         console.warn(readString8(wasm, ptr, len));
     },
+    diplomat_stream_write_js(id, ptr, len) {
+        return DiplomatStreamWrite.write(wasm, id, ptr, len);
+    },
+    diplomat_read_js(id, ptr, len) {
+        return DiplomatReader.read(wasm, id, ptr, len);
+    },
     diplomat_throw_error_js(ptr, len) {
-        throw new Error(readString8(wasm, ptr, len));
+        // The formatted message is only for hosts that don't read the panic info
+        throw RustPanicError.last(wasm);
     }
 }
 }
@@ -32,21 +41,36 @@ if (globalThis.process?.getBuiltinModule) {
     const wasmFile = new Uint8Array(fs.readFileSync(cfg['wasm_path']));
     const loadedWasm = await WebAssembly.instantiate(wasmFile, imports);
     wasm = loadedWasm.instance.exports;
+    wasmModule = loadedWasm.module;
 } else if (globalThis.process) {
     // Node (<22)
     const fs = await import('fs');
     const wasmFile = new Uint8Array(fs.readFileSync(cfg['wasm_path']));
     const loadedWasm = await WebAssembly.instantiate(wasmFile, imports);
     wasm = loadedWasm.instance.exports;
+    wasmModule = loadedWasm.module;
 } else {
     // Browser
     const loadedWasm = await WebAssembly.instantiateStreaming(fetch(cfg['wasm_path']), imports);
     wasm = loadedWasm.instance.exports;
+    wasmModule = loadedWasm.module;
 }
 
 wasm.diplomat_init();
+if (cfg['panic_hook']) {
+    wasm.diplomat_install_panic_hook();
+}
+if (cfg['log_level'] !== undefined) {
+    const level = ['off', 'error', 'warn', 'info', 'debug', 'trace'].indexOf(cfg['log_level']);
+    if (level === -1) {
+        throw new Error(`Unknown log_level ${cfg['log_level']}, expected one of off, error, warn, info, debug or trace`);
+    }
+    // Only exported if the library is built with the `log` feature of `diplomat-runtime`.
+    wasm.diplomat_log_set_max_level?.(level);
+}
 if (cfg['init'] !== undefined) {
     cfg['init'](wasm);
 }
 
 export default wasm;
+export { wasmModule };